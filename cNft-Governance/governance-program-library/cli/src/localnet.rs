@@ -0,0 +1,594 @@
+//! cnft-voter-localnet bootstraps solana-test-validator with a ready to use cNFT governance setup
+//!
+//! It deploys the nft-voter program together with spl-governance, mpl-token-metadata, mpl-bubblegum,
+//! spl-account-compression and spl-noop, creates a Realm using the plugin, an MPL collection, a Bubblegum tree
+//! with sample compressed NFTs minted to the given wallets and configures the Registrar for the collection.
+//! The created addresses are printed as JSON and the validator keeps running until the process is stopped.
+
+use std::{
+    convert::TryFrom,
+    fs,
+    mem::size_of,
+    path::PathBuf,
+    process::{ Child, Command },
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+use anchor_lang::{ InstructionData, ToAccountMetas };
+use clap::Parser;
+use cnft_verification::get_asset_id;
+use gpl_nft_voter::state::{
+    get_registrar_address,
+    get_registrar_extension_address,
+    max_voter_weight_record::get_max_voter_weight_record_address,
+};
+use mpl_bubblegum::{
+    instructions::{ CreateTreeConfigBuilder, MintToCollectionV1Builder },
+    types::{ Collection, Creator, MetadataArgs, TokenProgramVersion, TokenStandard },
+};
+use mpl_token_metadata::{
+    instructions::{ CreateMasterEditionV3Builder, CreateMetadataAccountV3Builder },
+    types::{ CollectionDetails, DataV2 },
+};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    system_program,
+    transaction::Transaction,
+};
+use spl_account_compression::{ state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, ConcurrentMerkleTree };
+use spl_governance::state::{
+    enums::MintMaxVoterWeightSource,
+    realm::{ get_realm_address, GoverningTokenConfigAccountArgs },
+};
+
+type LocalnetResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// The spl-governance program id used by the tests and the localnet
+const GOVERNANCE_PROGRAM_ID: &str = "Governance111111111111111111111111111111111";
+
+/// Tree dimensions of the sample tree (up to 16k compressed NFTs)
+const TREE_MAX_DEPTH: u32 = 14;
+const TREE_MAX_BUFFER_SIZE: u32 = 64;
+
+#[derive(Parser)]
+#[clap(
+    name = "cnft-voter-localnet",
+    about = "Bootstraps solana-test-validator with a cNFT governance Realm for UI development"
+)]
+struct Args {
+    /// Wallets which receive the sample compressed NFTs
+    #[clap(long = "wallet", required = true)]
+    wallets: Vec<Pubkey>,
+
+    /// The number of compressed NFTs minted to each wallet
+    #[clap(long, default_value = "3")]
+    nfts_per_wallet: u32,
+
+    /// The built nft-voter program
+    #[clap(long, default_value = "target/deploy/gpl_nft_voter.so")]
+    program: PathBuf,
+
+    /// The directory with the dependency programs (spl_governance.so, mpl_bubblegum.so, ...)
+    #[clap(long, default_value = "programs/nft-voter/tests/fixtures")]
+    fixtures: PathBuf,
+
+    /// The ledger directory of the validator which is reset on every start
+    #[clap(long, default_value = "test-ledger")]
+    ledger: PathBuf,
+
+    /// RPC url of the validator
+    #[clap(long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Writes the created addresses to the file in addition to stdout
+    #[clap(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct LocalnetAddresses {
+    rpc_url: String,
+    nft_voter_program_id: String,
+    governance_program_id: String,
+    payer: String,
+    realm: String,
+    realm_authority_keypair: Vec<u8>,
+    community_mint: String,
+    registrar: String,
+    max_voter_weight_record: String,
+    collection_mint: String,
+    merkle_tree: String,
+    compressed_nfts: Vec<CompressedNftAddresses>,
+}
+
+#[derive(Serialize)]
+struct CompressedNftAddresses {
+    asset_id: String,
+    owner: String,
+    leaf_index: u32,
+}
+
+struct Localnet {
+    rpc_client: RpcClient,
+    payer: Keypair,
+    governance_program_id: Pubkey,
+}
+
+fn main() -> LocalnetResult<()> {
+    let args = Args::parse();
+
+    let mut validator = start_validator(&args)?;
+
+    let result = bootstrap(&args);
+
+    if let Err(err) = result {
+        validator.kill()?;
+        return Err(err);
+    }
+
+    println!("Localnet is running at {}, press Ctrl+C to stop it", args.url);
+    validator.wait()?;
+
+    Ok(())
+}
+
+fn start_validator(args: &Args) -> LocalnetResult<Child> {
+    let fixture = |name: &str| args.fixtures.join(name).to_string_lossy().to_string();
+
+    let programs = [
+        (gpl_nft_voter::id().to_string(), args.program.to_string_lossy().to_string()),
+        (GOVERNANCE_PROGRAM_ID.to_string(), fixture("spl_governance.so")),
+        (mpl_token_metadata::ID.to_string(), fixture("mpl_token_metadata.so")),
+        (mpl_bubblegum::ID.to_string(), fixture("mpl_bubblegum.so")),
+        (spl_account_compression::id().to_string(), fixture("spl_account_compression.so")),
+        (spl_noop::id().to_string(), fixture("spl_noop.so")),
+    ];
+
+    let mut command = Command::new("solana-test-validator");
+    command.arg("--reset").arg("--quiet").arg("--ledger").arg(&args.ledger);
+
+    for (program_id, program) in programs.iter() {
+        command.arg("--bpf-program").arg(program_id).arg(program);
+    }
+
+    Ok(command.spawn()?)
+}
+
+fn bootstrap(args: &Args) -> LocalnetResult<()> {
+    let localnet = Localnet {
+        rpc_client: RpcClient::new_with_commitment(args.url.clone(), CommitmentConfig::confirmed()),
+        payer: Keypair::new(),
+        governance_program_id: Pubkey::from_str(GOVERNANCE_PROGRAM_ID)?,
+    };
+
+    localnet.wait_for_validator()?;
+    localnet.airdrop(&localnet.payer.pubkey(), 1_000)?;
+
+    let collection_size = args.wallets.len() as u32 * args.nfts_per_wallet;
+
+    let (realm, realm_authority, community_mint) = localnet.create_realm()?;
+    let collection_mint = localnet.create_collection(collection_size)?;
+    let (registrar, max_voter_weight_record) = localnet.create_registrar(
+        &realm,
+        &realm_authority,
+        &community_mint,
+        &collection_mint,
+        collection_size
+    )?;
+
+    let merkle_tree = localnet.create_tree()?;
+
+    let mut compressed_nfts = vec![];
+    for wallet in args.wallets.iter() {
+        for _ in 0..args.nfts_per_wallet {
+            let leaf_index = compressed_nfts.len() as u32;
+            let asset_id = localnet.mint_compressed_nft(
+                &merkle_tree,
+                &collection_mint,
+                wallet,
+                leaf_index
+            )?;
+
+            compressed_nfts.push(CompressedNftAddresses {
+                asset_id: asset_id.to_string(),
+                owner: wallet.to_string(),
+                leaf_index,
+            });
+        }
+    }
+
+    let addresses = LocalnetAddresses {
+        rpc_url: args.url.clone(),
+        nft_voter_program_id: gpl_nft_voter::id().to_string(),
+        governance_program_id: localnet.governance_program_id.to_string(),
+        payer: localnet.payer.pubkey().to_string(),
+        realm: realm.to_string(),
+        realm_authority_keypair: realm_authority.to_bytes().to_vec(),
+        community_mint: community_mint.to_string(),
+        registrar: registrar.to_string(),
+        max_voter_weight_record: max_voter_weight_record.to_string(),
+        collection_mint: collection_mint.to_string(),
+        merkle_tree: merkle_tree.to_string(),
+        compressed_nfts,
+    };
+
+    let json = serde_json::to_string_pretty(&addresses)?;
+    println!("{}", json);
+
+    if let Some(output) = &args.output {
+        fs::write(output, json)?;
+    }
+
+    Ok(())
+}
+
+impl Localnet {
+    fn wait_for_validator(&self) -> LocalnetResult<()> {
+        for _ in 0..60 {
+            if self.rpc_client.get_health().is_ok() {
+                return Ok(());
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+
+        Err("solana-test-validator didn't start in 60 seconds".into())
+    }
+
+    fn airdrop(&self, to: &Pubkey, sol: u64) -> LocalnetResult<()> {
+        let signature = self.rpc_client.request_airdrop(to, sol * LAMPORTS_PER_SOL)?;
+
+        while !self.rpc_client.confirm_transaction(&signature)? {
+            thread::sleep(Duration::from_millis(500));
+        }
+
+        Ok(())
+    }
+
+    fn process_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair]
+    ) -> LocalnetResult<()> {
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.rpc_client.get_latest_blockhash()?
+        );
+
+        self.rpc_client.send_and_confirm_transaction(&transaction)?;
+
+        Ok(())
+    }
+
+    fn create_mint(&self, mint_authority: &Pubkey) -> LocalnetResult<Pubkey> {
+        let mint = Keypair::new();
+        let rent = self.rpc_client.get_minimum_balance_for_rent_exemption(
+            spl_token::state::Mint::LEN
+        )?;
+
+        self.process_transaction(
+            &[
+                system_instruction::create_account(
+                    &self.payer.pubkey(),
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::id()
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    mint_authority,
+                    Some(mint_authority),
+                    0
+                )?,
+            ],
+            &[&mint]
+        )?;
+
+        Ok(mint.pubkey())
+    }
+
+    /// Creates Realm with the nft-voter plugin configured for the community mint
+    fn create_realm(&self) -> LocalnetResult<(Pubkey, Keypair, Pubkey)> {
+        let realm_authority = Keypair::new();
+        let community_mint = self.create_mint(&self.payer.pubkey())?;
+        let realm_name = format!("cNFT Realm {}", &community_mint.to_string()[..8]);
+
+        let create_realm_ix = spl_governance::instruction::create_realm(
+            &self.governance_program_id,
+            &realm_authority.pubkey(),
+            &community_mint,
+            &self.payer.pubkey(),
+            None,
+            Some(GoverningTokenConfigAccountArgs {
+                voter_weight_addin: Some(gpl_nft_voter::id()),
+                max_voter_weight_addin: Some(gpl_nft_voter::id()),
+                token_type: Default::default(),
+            }),
+            None,
+            realm_name.clone(),
+            1,
+            MintMaxVoterWeightSource::FULL_SUPPLY_FRACTION
+        );
+
+        self.process_transaction(&[create_realm_ix], &[])?;
+
+        Ok((
+            get_realm_address(&self.governance_program_id, &realm_name),
+            realm_authority,
+            community_mint,
+        ))
+    }
+
+    /// Creates sized MPL collection with the payer as the update authority
+    fn create_collection(&self, collection_size: u32) -> LocalnetResult<Pubkey> {
+        let payer = self.payer.pubkey();
+        let collection_mint = self.create_mint(&payer)?;
+
+        let token_account = Keypair::new();
+        let rent = self.rpc_client.get_minimum_balance_for_rent_exemption(
+            spl_token::state::Account::LEN
+        )?;
+
+        self.process_transaction(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &token_account.pubkey(),
+                    rent,
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::id()
+                ),
+                spl_token::instruction::initialize_account(
+                    &spl_token::id(),
+                    &token_account.pubkey(),
+                    &collection_mint,
+                    &payer
+                )?,
+                spl_token::instruction::mint_to(
+                    &spl_token::id(),
+                    &collection_mint,
+                    &token_account.pubkey(),
+                    &payer,
+                    &[],
+                    1
+                )?,
+            ],
+            &[&token_account]
+        )?;
+
+        let metadata_program_id = mpl_token_metadata::ID;
+        let metadata = Pubkey::find_program_address(
+            &[b"metadata", metadata_program_id.as_ref(), collection_mint.as_ref()],
+            &metadata_program_id
+        ).0;
+        let master_edition = Pubkey::find_program_address(
+            &[b"metadata", metadata_program_id.as_ref(), collection_mint.as_ref(), b"edition"],
+            &metadata_program_id
+        ).0;
+
+        let create_metadata_ix = CreateMetadataAccountV3Builder::new()
+            .metadata(metadata)
+            .mint(collection_mint)
+            .mint_authority(payer)
+            .payer(payer)
+            .update_authority(payer, true)
+            .data(DataV2 {
+                name: "cNFT Governance".to_string(),
+                symbol: "CNFTG".to_string(),
+                uri: "https://localhost/collection.json".to_string(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            })
+            .is_mutable(true)
+            .collection_details(CollectionDetails::V1 {
+                size: collection_size as u64,
+            })
+            .instruction();
+
+        let create_master_edition_ix = CreateMasterEditionV3Builder::new()
+            .edition(master_edition)
+            .mint(collection_mint)
+            .update_authority(payer)
+            .mint_authority(payer)
+            .payer(payer)
+            .metadata(metadata)
+            .max_supply(0)
+            .instruction();
+
+        self.process_transaction(&[create_metadata_ix, create_master_edition_ix], &[])?;
+
+        Ok(collection_mint)
+    }
+
+    /// Creates Registrar and MaxVoterWeightRecord and configures the collection with weight 1
+    fn create_registrar(
+        &self,
+        realm: &Pubkey,
+        realm_authority: &Keypair,
+        community_mint: &Pubkey,
+        collection_mint: &Pubkey,
+        collection_size: u32
+    ) -> LocalnetResult<(Pubkey, Pubkey)> {
+        let registrar = get_registrar_address(realm, community_mint);
+        let max_voter_weight_record = get_max_voter_weight_record_address(realm, community_mint);
+
+        let create_registrar_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: (gpl_nft_voter::accounts::CreateRegistrar {
+                registrar,
+                registrar_extension: get_registrar_extension_address(&registrar),
+                governance_program_id: self.governance_program_id,
+                realm: *realm,
+                governing_token_mint: *community_mint,
+                realm_authority: realm_authority.pubkey(),
+                payer: self.payer.pubkey(),
+                system_program: system_program::id(),
+            }).to_account_metas(None),
+            data: (gpl_nft_voter::instruction::CreateRegistrar {
+                max_collections: 10,
+            }).data(),
+        };
+
+        let create_max_voter_weight_record_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: (gpl_nft_voter::accounts::CreateMaxVoterWeightRecord {
+                governance_program_id: self.governance_program_id,
+                realm: *realm,
+                realm_governing_token_mint: *community_mint,
+                max_voter_weight_record,
+                payer: self.payer.pubkey(),
+                system_program: system_program::id(),
+            }).to_account_metas(None),
+            data: (gpl_nft_voter::instruction::CreateMaxVoterWeightRecord {}).data(),
+        };
+
+        let configure_collection_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: (gpl_nft_voter::accounts::ConfigureCollection {
+                registrar,
+                registrar_extension: get_registrar_extension_address(&registrar),
+                realm: *realm,
+                realm_authority: realm_authority.pubkey(),
+                collection: *collection_mint,
+                max_voter_weight_record,
+            }).to_account_metas(None),
+            data: (gpl_nft_voter::instruction::ConfigureCollection {
+                weight: 1,
+                size: collection_size,
+            }).data(),
+        };
+
+        self.process_transaction(
+            &[create_registrar_ix, create_max_voter_weight_record_ix, configure_collection_ix],
+            &[realm_authority]
+        )?;
+
+        Ok((registrar, max_voter_weight_record))
+    }
+
+    /// Creates private Bubblegum tree with the payer as the tree creator
+    fn create_tree(&self) -> LocalnetResult<Pubkey> {
+        let merkle_tree = Keypair::new();
+        let tree_authority = get_tree_authority_address(&merkle_tree.pubkey());
+
+        let merkle_tree_size =
+            CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 +
+            size_of::<ConcurrentMerkleTree<{ TREE_MAX_DEPTH as usize }, { TREE_MAX_BUFFER_SIZE as usize }>>();
+        let rent = self.rpc_client.get_minimum_balance_for_rent_exemption(merkle_tree_size)?;
+
+        let tree_alloc_ix = system_instruction::create_account(
+            &self.payer.pubkey(),
+            &merkle_tree.pubkey(),
+            rent,
+            u64::try_from(merkle_tree_size)?,
+            &spl_account_compression::id()
+        );
+
+        let create_tree_ix = CreateTreeConfigBuilder::new()
+            .tree_config(tree_authority)
+            .merkle_tree(merkle_tree.pubkey())
+            .payer(self.payer.pubkey())
+            .tree_creator(self.payer.pubkey())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .system_program(system_program::id())
+            .max_depth(TREE_MAX_DEPTH)
+            .max_buffer_size(TREE_MAX_BUFFER_SIZE)
+            .public(false)
+            .instruction();
+
+        self.process_transaction(&[tree_alloc_ix, create_tree_ix], &[&merkle_tree])?;
+
+        Ok(merkle_tree.pubkey())
+    }
+
+    /// Mints compressed NFT with verified collection to the given owner
+    fn mint_compressed_nft(
+        &self,
+        merkle_tree: &Pubkey,
+        collection_mint: &Pubkey,
+        owner: &Pubkey,
+        leaf_index: u32
+    ) -> LocalnetResult<Pubkey> {
+        let metadata_program_id = mpl_token_metadata::ID;
+        let collection_metadata = Pubkey::find_program_address(
+            &[b"metadata", metadata_program_id.as_ref(), collection_mint.as_ref()],
+            &metadata_program_id
+        ).0;
+        let collection_master_edition = Pubkey::find_program_address(
+            &[b"metadata", metadata_program_id.as_ref(), collection_mint.as_ref(), b"edition"],
+            &metadata_program_id
+        ).0;
+
+        let metadata_args = MetadataArgs {
+            name: format!("cNFT #{}", leaf_index),
+            symbol: "CNFTG".to_string(),
+            uri: format!("https://localhost/{}.json", leaf_index),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            token_program_version: TokenProgramVersion::Original,
+            collection: Some(Collection {
+                verified: false,
+                key: *collection_mint,
+            }),
+            uses: None,
+            creators: vec![Creator {
+                address: self.payer.pubkey(),
+                verified: true,
+                share: 100,
+            }],
+        };
+
+        let mint_ix = MintToCollectionV1Builder::new()
+            .tree_config(get_tree_authority_address(merkle_tree))
+            .leaf_owner(*owner)
+            .leaf_delegate(*owner)
+            .merkle_tree(*merkle_tree)
+            .payer(self.payer.pubkey())
+            .tree_creator_or_delegate(self.payer.pubkey())
+            .collection_authority(self.payer.pubkey())
+            .collection_authority_record_pda(None)
+            .collection_mint(*collection_mint)
+            .collection_metadata(collection_metadata)
+            .collection_edition(collection_master_edition)
+            .bubblegum_signer(
+                Pubkey::find_program_address(&[b"collection_cpi"], &mpl_bubblegum::ID).0
+            )
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .token_metadata_program(metadata_program_id)
+            .system_program(system_program::id())
+            .metadata(metadata_args)
+            .instruction();
+
+        self.process_transaction(&[mint_ix], &[])?;
+
+        Ok(get_asset_id(merkle_tree, leaf_index as u64))
+    }
+}
+
+fn get_tree_authority_address(merkle_tree: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &mpl_bubblegum::ID).0
+}
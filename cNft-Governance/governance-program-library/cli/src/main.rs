@@ -0,0 +1,347 @@
+use std::{ fs, path::PathBuf };
+
+use anchor_lang::{ AccountDeserialize, InstructionData, ToAccountMetas };
+use clap::{ Parser, Subcommand };
+use gpl_nft_voter::state::{
+    get_registrar_extension_address,
+    max_voter_weight_record::get_max_voter_weight_record_address,
+    Registrar,
+    RegistrarExtension,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{ read_keypair_file, Keypair },
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use registrar_config::{
+    parse_collection_weight_mode,
+    parse_collection_weight_strategy,
+    CollectionChange,
+    RegistrarConfigExport,
+};
+
+mod registrar_config;
+mod tree_inspection;
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[clap(name = "nft-voter-cli", about = "Command line tools for the NFT voter plugin")]
+struct Cli {
+    /// RPC url of the cluster
+    #[clap(long, short = 'u', default_value = "http://localhost:8899")]
+    url: String,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Serializes the voting configuration (collections, weights, modes) of a Registrar to JSON
+    ExportRegistrar {
+        registrar: Pubkey,
+
+        /// Output file, stdout if not set
+        #[clap(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+
+    /// Applies a configuration exported by export-registrar to a Registrar on the cluster
+    /// Only the collections which differ are configured
+    ApplyConfig {
+        /// The Registrar to apply the configuration to
+        registrar: Pubkey,
+
+        /// The configuration file produced by export-registrar
+        config: PathBuf,
+
+        /// Keypair of the Realm authority of the target Registrar
+        #[clap(long)]
+        realm_authority: PathBuf,
+
+        /// Fee payer keypair, the Realm authority if not set
+        #[clap(long)]
+        payer: Option<PathBuf>,
+
+        /// Prints the changes without sending any transactions
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Decodes the header and state of a concurrent merkle tree (depth, buffer, canopy, authority, root)
+    /// and reports whether the compressed NFTs of the tree can be verified by the plugin
+    InspectTree {
+        tree: Pubkey,
+    },
+}
+
+fn main() -> CliResult<()> {
+    let cli = Cli::parse();
+    let rpc_client = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+
+    match cli.command {
+        Command::ExportRegistrar { registrar, output } => {
+            export_registrar(&rpc_client, &registrar, output)
+        }
+        Command::ApplyConfig { registrar, config, realm_authority, payer, dry_run } => {
+            let realm_authority = read_keypair(&realm_authority)?;
+            let payer = match payer {
+                Some(payer) => Some(read_keypair(&payer)?),
+                None => None,
+            };
+
+            apply_config(
+                &rpc_client,
+                &registrar,
+                &config,
+                &realm_authority,
+                payer.as_ref().unwrap_or(&realm_authority),
+                dry_run
+            )
+        }
+        Command::InspectTree { tree } => inspect_tree(&rpc_client, &tree),
+    }
+}
+
+fn read_keypair(path: &PathBuf) -> CliResult<Keypair> {
+    read_keypair_file(path).map_err(|err| {
+        format!("Can't read keypair {}: {}", path.display(), err).into()
+    })
+}
+
+fn get_registrar(rpc_client: &RpcClient, registrar: &Pubkey) -> CliResult<Registrar> {
+    let data = rpc_client.get_account_data(registrar)?;
+    Ok(Registrar::try_deserialize(&mut data.as_slice())?)
+}
+
+fn get_registrar_extension(
+    rpc_client: &RpcClient,
+    registrar: &Pubkey
+) -> CliResult<RegistrarExtension> {
+    let data = rpc_client.get_account_data(&get_registrar_extension_address(registrar))?;
+    Ok(RegistrarExtension::try_deserialize(&mut data.as_slice())?)
+}
+
+fn export_registrar(
+    rpc_client: &RpcClient,
+    registrar_address: &Pubkey,
+    output: Option<PathBuf>
+) -> CliResult<()> {
+    let registrar = get_registrar(rpc_client, registrar_address)?;
+    let registrar_extension = get_registrar_extension(rpc_client, registrar_address)?;
+    let export = RegistrarConfigExport::from_registrar(
+        registrar_address,
+        &registrar,
+        &registrar_extension
+    );
+    let json = serde_json::to_string_pretty(&export)?;
+
+    match output {
+        Some(output) => fs::write(output, json)?,
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
+
+fn inspect_tree(rpc_client: &RpcClient, tree: &Pubkey) -> CliResult<()> {
+    let tree_account = rpc_client.get_account(tree)?;
+    let inspection = tree_inspection::inspect_tree(tree, &tree_account)?;
+
+    println!("{}", serde_json::to_string_pretty(&inspection)?);
+
+    Ok(())
+}
+
+fn apply_config(
+    rpc_client: &RpcClient,
+    registrar_address: &Pubkey,
+    config: &PathBuf,
+    realm_authority: &Keypair,
+    payer: &Keypair,
+    dry_run: bool
+) -> CliResult<()> {
+    let export: RegistrarConfigExport = serde_json::from_str(&fs::read_to_string(config)?)?;
+
+    if parse_collection_weight_mode(&export.collection_weight_mode).is_none() {
+        return Err(
+            format!("Invalid collection_weight_mode {}", export.collection_weight_mode).into()
+        );
+    }
+
+    for collection_export in export.collections.iter() {
+        if parse_collection_weight_strategy(&collection_export.weight_strategy).is_none() {
+            return Err(
+                format!(
+                    "Invalid weight_strategy {} of collection {}",
+                    collection_export.weight_strategy,
+                    collection_export.collection
+                ).into()
+            );
+        }
+    }
+
+    let registrar = get_registrar(rpc_client, registrar_address)?;
+    let registrar_extension = get_registrar_extension(rpc_client, registrar_address)?;
+
+    for difference in export.diff_registrar_config(&registrar_extension) {
+        println!("Registrar config differs and must be set using configure_registrar: {}", difference);
+    }
+
+    let changes = export.diff_collections(&registrar, &registrar_extension)?;
+
+    if changes.is_empty() {
+        println!("Collections are up to date");
+        return Ok(());
+    }
+
+    let max_voter_weight_record = get_max_voter_weight_record_address(
+        &registrar.realm,
+        &registrar.governing_token_mint
+    );
+
+    for change in changes.iter() {
+        println!("{:?}", change);
+
+        if dry_run {
+            continue;
+        }
+
+        let instruction = get_collection_change_instruction(
+            change,
+            registrar_address,
+            &registrar.realm,
+            &realm_authority.pubkey(),
+            &max_voter_weight_record
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, realm_authority],
+            rpc_client.get_latest_blockhash()?
+        );
+
+        let signature = rpc_client.send_and_confirm_transaction(&transaction)?;
+        println!("Signature: {}", signature);
+    }
+
+    Ok(())
+}
+
+fn get_collection_change_instruction(
+    change: &CollectionChange,
+    registrar: &Pubkey,
+    realm: &Pubkey,
+    realm_authority: &Pubkey,
+    max_voter_weight_record: &Pubkey
+) -> Instruction {
+    match change {
+        CollectionChange::ConfigureCollection { collection, weight, size } => {
+            let data = (gpl_nft_voter::instruction::ConfigureCollection {
+                weight: *weight,
+                size: *size,
+            }).data();
+
+            let accounts = (gpl_nft_voter::accounts::ConfigureCollection {
+                registrar: *registrar,
+                registrar_extension: get_registrar_extension_address(registrar),
+                realm: *realm,
+                realm_authority: *realm_authority,
+                collection: *collection,
+                max_voter_weight_record: *max_voter_weight_record,
+            }).to_account_metas(None);
+
+            Instruction {
+                program_id: gpl_nft_voter::id(),
+                accounts,
+                data,
+            }
+        }
+        CollectionChange::ConfigureCreatorBoosts { collection, creator_boosts } => {
+            let data = (gpl_nft_voter::instruction::ConfigureCreatorBoosts {
+                collection: *collection,
+                creator_boosts: creator_boosts.clone(),
+            }).data();
+
+            let accounts = (gpl_nft_voter::accounts::ConfigureCreatorBoosts {
+                registrar: *registrar,
+                registrar_extension: get_registrar_extension_address(registrar),
+                realm: *realm,
+                realm_authority: *realm_authority,
+                max_voter_weight_record: *max_voter_weight_record,
+            }).to_account_metas(None);
+
+            Instruction {
+                program_id: gpl_nft_voter::id(),
+                accounts,
+                data,
+            }
+        }
+        CollectionChange::ConfigureCollectionExpiry { collection, expires_at } => {
+            let data = (gpl_nft_voter::instruction::ConfigureCollectionExpiry {
+                collection: *collection,
+                expires_at: *expires_at,
+            }).data();
+
+            let accounts = (gpl_nft_voter::accounts::ConfigureCollectionExpiry {
+                registrar: *registrar,
+                registrar_extension: get_registrar_extension_address(registrar),
+                realm: *realm,
+                realm_authority: *realm_authority,
+            }).to_account_metas(None);
+
+            Instruction {
+                program_id: gpl_nft_voter::id(),
+                accounts,
+                data,
+            }
+        }
+        CollectionChange::ConfigureCollectionWeightStrategy { collection, weight_strategy } => {
+            let data = (gpl_nft_voter::instruction::ConfigureCollectionWeightStrategy {
+                collection: *collection,
+                weight_strategy: *weight_strategy,
+            }).data();
+
+            let accounts = (gpl_nft_voter::accounts::ConfigureCollectionWeightStrategy {
+                registrar: *registrar,
+                registrar_extension: get_registrar_extension_address(registrar),
+                realm: *realm,
+                realm_authority: *realm_authority,
+                max_voter_weight_record: *max_voter_weight_record,
+            }).to_account_metas(None);
+
+            Instruction {
+                program_id: gpl_nft_voter::id(),
+                accounts,
+                data,
+            }
+        }
+        CollectionChange::ConfigureCollectionUsesLimit { collection, uses_limited } => {
+            let data = (gpl_nft_voter::instruction::ConfigureCollectionUsesLimit {
+                collection: *collection,
+                uses_limited: *uses_limited,
+            }).data();
+
+            let accounts = (gpl_nft_voter::accounts::ConfigureCollectionUsesLimit {
+                registrar: *registrar,
+                registrar_extension: get_registrar_extension_address(registrar),
+                realm: *realm,
+                realm_authority: *realm_authority,
+            }).to_account_metas(None);
+
+            Instruction {
+                program_id: gpl_nft_voter::id(),
+                accounts,
+                data,
+            }
+        }
+    }
+}
+
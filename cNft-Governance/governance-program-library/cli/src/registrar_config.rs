@@ -0,0 +1,741 @@
+use std::str::FromStr;
+
+use gpl_nft_voter::state::{
+    CollectionConfig,
+    CollectionExtension,
+    CollectionWeightMode,
+    CollectionWeightStrategy,
+    CreatorBoost,
+    Registrar,
+    RegistrarExtension,
+    MAX_CREATOR_BOOSTS,
+};
+use serde::{ Deserialize, Serialize };
+use solana_sdk::pubkey::{ ParsePubkeyError, Pubkey };
+
+/// Portable snapshot of the voting configuration of a Registrar
+/// Addresses are stored as base58 strings to keep the file human readable and diffable
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegistrarConfigExport {
+    /// The Registrar the snapshot was taken from
+    pub registrar: String,
+
+    /// Realm of the Registrar
+    pub realm: String,
+
+    /// Governing token mint the Registrar is for
+    pub governing_token_mint: String,
+
+    /// Collection weight mode of the Registrar (PerAsset, UniqueCollections or Normalized)
+    pub collection_weight_mode: String,
+
+    /// Max weight a single collection can contribute to a Proposal in basis points (0 means no cap)
+    pub max_collection_weight_per_proposal: u16,
+
+    /// Aggregate weight of a collection in Normalized mode
+    pub normalized_collection_weight: u64,
+
+    /// Max number of assets a single cast_nft_vote can take (0 means not limited)
+    #[serde(default)]
+    pub max_assets_per_tx: u16,
+
+    /// Whether multi-transaction votes must be completed with finalize_nft_vote
+    #[serde(default)]
+    pub require_vote_finalization: bool,
+
+    /// Max number of compressed NFTs of a tree a voter can submit per slot (0 means not limited)
+    #[serde(default)]
+    pub max_tree_assets_per_slot: u16,
+
+    /// Number of slots a compressed NFT proof attestation stays fresh (0 means not used)
+    #[serde(default)]
+    pub proof_attestation_freshness_slots: u64,
+
+    /// Max number of tree changes since the root of a compressed NFT proof (0 means not limited)
+    #[serde(default)]
+    pub max_tree_root_age: u32,
+
+    /// Whether voters can migrate their vote records and delegations to a new wallet
+    #[serde(default)]
+    pub allow_voter_migration: bool,
+
+    /// Min number of slots between the creation of an NFT action ticket and its use (0 means not required)
+    #[serde(default)]
+    pub weight_record_min_age_slots: u64,
+
+    /// Whether the votes write VoterCheckpoints
+    #[serde(default)]
+    pub record_voter_checkpoints: bool,
+
+    /// Whether the VoterWeightRecords must have a validated weight_action_target
+    #[serde(default)]
+    pub require_weight_action_target: bool,
+
+    /// Whether the assets of unconfigured collections are resolved through CollectionAliases
+    #[serde(default)]
+    pub allow_collection_aliases: bool,
+
+    /// Floor weight of the voters registered before registration_cutoff_slot (0 means not used)
+    #[serde(default)]
+    pub min_weight_if_registered_before_slot: u64,
+
+    /// Slot the voters must have registered before to get the floor weight
+    #[serde(default)]
+    pub registration_cutoff_slot: u64,
+
+    /// Bubblegum program the compressed NFTs are verified against (default means the program of the cluster)
+    #[serde(default = "default_bubblegum_program_override")]
+    pub bubblegum_program_override: String,
+
+    /// Whether the voter weight of wallets is scaled by their WalletWeightOverride
+    #[serde(default)]
+    pub allow_wallet_weight_overrides: bool,
+
+    /// Whether the owners of compressed NFTs can rent their voter weight to other wallets
+    #[serde(default)]
+    pub allow_weight_leases: bool,
+
+    /// Merkle tree of the BallotTree vote receipts are minted to (default means receipts aren't minted)
+    #[serde(default = "default_vote_receipt_tree")]
+    pub vote_receipt_tree: String,
+
+    /// Configured voting collections
+    pub collections: Vec<CollectionConfigExport>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CollectionConfigExport {
+    pub collection: String,
+    pub weight: u64,
+    pub size: u32,
+
+    #[serde(default)]
+    pub creator_boosts: Vec<CreatorBoostExport>,
+
+    /// Unix timestamp at which the collection expires (0 means never)
+    #[serde(default)]
+    pub expires_at: i64,
+
+    /// The weight strategy of the collection (Linear, Quadratic, Membership or Capped:<max_assets>)
+    #[serde(default = "default_weight_strategy")]
+    pub weight_strategy: String,
+
+    /// Whether the NFTs of the collection are limited by their Metaplex Uses
+    #[serde(default)]
+    pub uses_limited: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CreatorBoostExport {
+    pub creator: String,
+    pub boost_bps: u16,
+}
+
+/// A change required to bring a Registrar in line with a RegistrarConfigExport
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollectionChange {
+    /// configure_collection with the given weight and size
+    /// Collections missing in the export are disabled using weight 0
+    /// because collections can't be removed from a Registrar
+    ConfigureCollection {
+        collection: Pubkey,
+        weight: u64,
+        size: u32,
+    },
+
+    /// configure_creator_boosts with the given boosts
+    ConfigureCreatorBoosts {
+        collection: Pubkey,
+        creator_boosts: Vec<CreatorBoost>,
+    },
+
+    /// configure_collection_expiry with the given expiry
+    ConfigureCollectionExpiry {
+        collection: Pubkey,
+        expires_at: i64,
+    },
+
+    /// configure_collection_weight_strategy with the given strategy
+    ConfigureCollectionWeightStrategy {
+        collection: Pubkey,
+        weight_strategy: CollectionWeightStrategy,
+    },
+
+    /// configure_collection_uses_limit with the given Uses limit
+    ConfigureCollectionUsesLimit {
+        collection: Pubkey,
+        uses_limited: bool,
+    },
+}
+
+impl RegistrarConfigExport {
+    pub fn from_registrar(
+        registrar_address: &Pubkey,
+        registrar: &Registrar,
+        registrar_extension: &RegistrarExtension
+    ) -> Self {
+        Self {
+            registrar: registrar_address.to_string(),
+            realm: registrar.realm.to_string(),
+            governing_token_mint: registrar.governing_token_mint.to_string(),
+            collection_weight_mode: format!("{:?}", registrar_extension.config.collection_weight_mode),
+            max_collection_weight_per_proposal: registrar_extension.config.max_collection_weight_per_proposal,
+            normalized_collection_weight: registrar_extension.config.normalized_collection_weight,
+            max_assets_per_tx: registrar_extension.config.max_assets_per_tx,
+            require_vote_finalization: registrar_extension.config.require_vote_finalization,
+            max_tree_assets_per_slot: registrar_extension.config.max_tree_assets_per_slot,
+            proof_attestation_freshness_slots: registrar_extension.config.proof_attestation_freshness_slots,
+            max_tree_root_age: registrar_extension.config.max_tree_root_age,
+            allow_voter_migration: registrar_extension.config.allow_voter_migration,
+            weight_record_min_age_slots: registrar_extension.config.weight_record_min_age_slots,
+            record_voter_checkpoints: registrar_extension.config.record_voter_checkpoints,
+            require_weight_action_target: registrar_extension.config.require_weight_action_target,
+            allow_collection_aliases: registrar_extension.config.allow_collection_aliases,
+            min_weight_if_registered_before_slot: registrar_extension.config.min_weight_if_registered_before_slot,
+            registration_cutoff_slot: registrar_extension.config.registration_cutoff_slot,
+            bubblegum_program_override: registrar_extension.config.bubblegum_program_override.to_string(),
+            allow_wallet_weight_overrides: registrar_extension.config.allow_wallet_weight_overrides,
+            allow_weight_leases: registrar_extension.config.allow_weight_leases,
+            vote_receipt_tree: registrar_extension.config.vote_receipt_tree.to_string(),
+            collections: registrar.collection_configs
+                .iter()
+                .map(|cc| {
+                    CollectionConfigExport::from_collection_config(
+                        cc,
+                        &registrar_extension.get_collection_extension(&cc.collection)
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the voting configuration fields which differ from the given RegistrarExtension
+    /// Note: They are only reported because configure_registrar replaces the whole RegistrarConfig
+    /// which also holds cluster specific addresses (treasuries, authorities) and must be set explicitly
+    pub fn diff_registrar_config(&self, registrar_extension: &RegistrarExtension) -> Vec<String> {
+        let mut differences = vec![];
+
+        let collection_weight_mode = format!("{:?}", registrar_extension.config.collection_weight_mode);
+        if self.collection_weight_mode != collection_weight_mode {
+            differences.push(
+                format!(
+                    "collection_weight_mode: {} -> {}",
+                    collection_weight_mode,
+                    self.collection_weight_mode
+                )
+            );
+        }
+
+        if
+            self.max_collection_weight_per_proposal !=
+            registrar_extension.config.max_collection_weight_per_proposal
+        {
+            differences.push(
+                format!(
+                    "max_collection_weight_per_proposal: {} -> {}",
+                    registrar_extension.config.max_collection_weight_per_proposal,
+                    self.max_collection_weight_per_proposal
+                )
+            );
+        }
+
+        if self.normalized_collection_weight != registrar_extension.config.normalized_collection_weight {
+            differences.push(
+                format!(
+                    "normalized_collection_weight: {} -> {}",
+                    registrar_extension.config.normalized_collection_weight,
+                    self.normalized_collection_weight
+                )
+            );
+        }
+
+        if self.max_assets_per_tx != registrar_extension.config.max_assets_per_tx {
+            differences.push(
+                format!(
+                    "max_assets_per_tx: {} -> {}",
+                    registrar_extension.config.max_assets_per_tx,
+                    self.max_assets_per_tx
+                )
+            );
+        }
+
+        if self.require_vote_finalization != registrar_extension.config.require_vote_finalization {
+            differences.push(
+                format!(
+                    "require_vote_finalization: {} -> {}",
+                    registrar_extension.config.require_vote_finalization,
+                    self.require_vote_finalization
+                )
+            );
+        }
+
+        if self.max_tree_assets_per_slot != registrar_extension.config.max_tree_assets_per_slot {
+            differences.push(
+                format!(
+                    "max_tree_assets_per_slot: {} -> {}",
+                    registrar_extension.config.max_tree_assets_per_slot,
+                    self.max_tree_assets_per_slot
+                )
+            );
+        }
+
+        if
+            self.proof_attestation_freshness_slots !=
+            registrar_extension.config.proof_attestation_freshness_slots
+        {
+            differences.push(
+                format!(
+                    "proof_attestation_freshness_slots: {} -> {}",
+                    registrar_extension.config.proof_attestation_freshness_slots,
+                    self.proof_attestation_freshness_slots
+                )
+            );
+        }
+
+        if self.max_tree_root_age != registrar_extension.config.max_tree_root_age {
+            differences.push(
+                format!(
+                    "max_tree_root_age: {} -> {}",
+                    registrar_extension.config.max_tree_root_age,
+                    self.max_tree_root_age
+                )
+            );
+        }
+
+        if self.allow_voter_migration != registrar_extension.config.allow_voter_migration {
+            differences.push(
+                format!(
+                    "allow_voter_migration: {} -> {}",
+                    registrar_extension.config.allow_voter_migration,
+                    self.allow_voter_migration
+                )
+            );
+        }
+
+        if self.weight_record_min_age_slots != registrar_extension.config.weight_record_min_age_slots {
+            differences.push(
+                format!(
+                    "weight_record_min_age_slots: {} -> {}",
+                    registrar_extension.config.weight_record_min_age_slots,
+                    self.weight_record_min_age_slots
+                )
+            );
+        }
+
+        if self.record_voter_checkpoints != registrar_extension.config.record_voter_checkpoints {
+            differences.push(
+                format!(
+                    "record_voter_checkpoints: {} -> {}",
+                    registrar_extension.config.record_voter_checkpoints,
+                    self.record_voter_checkpoints
+                )
+            );
+        }
+
+        if self.require_weight_action_target != registrar_extension.config.require_weight_action_target {
+            differences.push(
+                format!(
+                    "require_weight_action_target: {} -> {}",
+                    registrar_extension.config.require_weight_action_target,
+                    self.require_weight_action_target
+                )
+            );
+        }
+
+        if self.allow_collection_aliases != registrar_extension.config.allow_collection_aliases {
+            differences.push(
+                format!(
+                    "allow_collection_aliases: {} -> {}",
+                    registrar_extension.config.allow_collection_aliases,
+                    self.allow_collection_aliases
+                )
+            );
+        }
+
+        if
+            self.min_weight_if_registered_before_slot !=
+            registrar_extension.config.min_weight_if_registered_before_slot
+        {
+            differences.push(
+                format!(
+                    "min_weight_if_registered_before_slot: {} -> {}",
+                    registrar_extension.config.min_weight_if_registered_before_slot,
+                    self.min_weight_if_registered_before_slot
+                )
+            );
+        }
+
+        if self.registration_cutoff_slot != registrar_extension.config.registration_cutoff_slot {
+            differences.push(
+                format!(
+                    "registration_cutoff_slot: {} -> {}",
+                    registrar_extension.config.registration_cutoff_slot,
+                    self.registration_cutoff_slot
+                )
+            );
+        }
+
+        if self.bubblegum_program_override != registrar_extension.config.bubblegum_program_override.to_string() {
+            differences.push(
+                format!(
+                    "bubblegum_program_override: {} -> {}",
+                    registrar_extension.config.bubblegum_program_override,
+                    self.bubblegum_program_override
+                )
+            );
+        }
+
+        if self.allow_wallet_weight_overrides != registrar_extension.config.allow_wallet_weight_overrides {
+            differences.push(
+                format!(
+                    "allow_wallet_weight_overrides: {} -> {}",
+                    registrar_extension.config.allow_wallet_weight_overrides,
+                    self.allow_wallet_weight_overrides
+                )
+            );
+        }
+
+        if self.allow_weight_leases != registrar_extension.config.allow_weight_leases {
+            differences.push(
+                format!(
+                    "allow_weight_leases: {} -> {}",
+                    registrar_extension.config.allow_weight_leases,
+                    self.allow_weight_leases
+                )
+            );
+        }
+
+        if self.vote_receipt_tree != registrar_extension.config.vote_receipt_tree.to_string() {
+            differences.push(
+                format!(
+                    "vote_receipt_tree: {} -> {}",
+                    registrar_extension.config.vote_receipt_tree,
+                    self.vote_receipt_tree
+                )
+            );
+        }
+
+        differences
+    }
+
+    /// Returns the minimal set of collection changes required to apply the export to the given Registrar
+    pub fn diff_collections(
+        &self,
+        registrar: &Registrar,
+        registrar_extension: &RegistrarExtension
+    ) -> Result<Vec<CollectionChange>, ParsePubkeyError> {
+        let mut changes = vec![];
+
+        for collection_export in self.collections.iter() {
+            let collection = Pubkey::from_str(&collection_export.collection)?;
+            let creator_boosts = collection_export.get_creator_boosts()?;
+
+            // Collections without extension (including the collections not configured yet) use the default settings
+            let current_extension = registrar_extension.get_collection_extension(&collection);
+
+            let current = registrar.collection_configs
+                .iter()
+                .find(|cc| cc.collection == collection);
+
+            let configures_collection = match current {
+                Some(cc) => cc.weight != collection_export.weight || cc.size != collection_export.size,
+                None => true,
+            };
+
+            if configures_collection {
+                changes.push(CollectionChange::ConfigureCollection {
+                    collection,
+                    weight: collection_export.weight,
+                    size: collection_export.size,
+                });
+            }
+
+            let current_creator_boosts = get_used_creator_boosts(&current_extension.creator_boosts);
+
+            if current_creator_boosts != creator_boosts {
+                changes.push(CollectionChange::ConfigureCreatorBoosts {
+                    collection,
+                    creator_boosts,
+                });
+            }
+
+            if current_extension.expires_at != collection_export.expires_at {
+                changes.push(CollectionChange::ConfigureCollectionExpiry {
+                    collection,
+                    expires_at: collection_export.expires_at,
+                });
+            }
+
+            let weight_strategy = parse_collection_weight_strategy(
+                &collection_export.weight_strategy
+            ).unwrap_or_default();
+
+            if current_extension.weight_strategy != weight_strategy {
+                changes.push(CollectionChange::ConfigureCollectionWeightStrategy {
+                    collection,
+                    weight_strategy,
+                });
+            }
+
+            if current_extension.uses_limited != collection_export.uses_limited {
+                changes.push(CollectionChange::ConfigureCollectionUsesLimit {
+                    collection,
+                    uses_limited: collection_export.uses_limited,
+                });
+            }
+        }
+
+        // Disable the collections which are not in the export
+        for cc in registrar.collection_configs.iter() {
+            let is_exported = self.collections
+                .iter()
+                .any(|collection_export| collection_export.collection == cc.collection.to_string());
+
+            if !is_exported && cc.weight > 0 {
+                changes.push(CollectionChange::ConfigureCollection {
+                    collection: cc.collection,
+                    weight: 0,
+                    size: cc.size,
+                });
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+impl CollectionConfigExport {
+    pub fn from_collection_config(
+        collection_config: &CollectionConfig,
+        collection_extension: &CollectionExtension
+    ) -> Self {
+        Self {
+            collection: collection_config.collection.to_string(),
+            weight: collection_config.weight,
+            size: collection_config.size,
+            creator_boosts: get_used_creator_boosts(&collection_extension.creator_boosts)
+                .iter()
+                .map(|cb| CreatorBoostExport {
+                    creator: cb.creator.to_string(),
+                    boost_bps: cb.boost_bps,
+                })
+                .collect(),
+            expires_at: collection_extension.expires_at,
+            weight_strategy: format_collection_weight_strategy(&collection_extension.weight_strategy),
+            uses_limited: collection_extension.uses_limited,
+        }
+    }
+
+    pub fn get_creator_boosts(&self) -> Result<Vec<CreatorBoost>, ParsePubkeyError> {
+        self.creator_boosts
+            .iter()
+            .map(|cb| {
+                Ok(CreatorBoost {
+                    creator: Pubkey::from_str(&cb.creator)?,
+                    boost_bps: cb.boost_bps,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the creator boosts which are in use
+fn get_used_creator_boosts(creator_boosts: &[CreatorBoost; MAX_CREATOR_BOOSTS]) -> Vec<CreatorBoost> {
+    creator_boosts
+        .iter()
+        .filter(|cb| cb.creator != Pubkey::default())
+        .copied()
+        .collect()
+}
+
+/// Parses the collection weight mode name produced by export
+pub fn parse_collection_weight_mode(name: &str) -> Option<CollectionWeightMode> {
+    match name {
+        "PerAsset" => Some(CollectionWeightMode::PerAsset),
+        "UniqueCollections" => Some(CollectionWeightMode::UniqueCollections),
+        "Normalized" => Some(CollectionWeightMode::Normalized),
+        _ => None,
+    }
+}
+
+fn default_bubblegum_program_override() -> String {
+    Pubkey::default().to_string()
+}
+
+fn default_vote_receipt_tree() -> String {
+    Pubkey::default().to_string()
+}
+
+/// Returns the exported name of CollectionWeightStrategy
+/// The parameters of the strategy are appended after a colon
+pub fn format_collection_weight_strategy(weight_strategy: &CollectionWeightStrategy) -> String {
+    match weight_strategy {
+        CollectionWeightStrategy::Linear => "Linear".to_string(),
+        CollectionWeightStrategy::Quadratic => "Quadratic".to_string(),
+        CollectionWeightStrategy::Membership => "Membership".to_string(),
+        CollectionWeightStrategy::Capped { max_assets } => format!("Capped:{}", max_assets),
+    }
+}
+
+/// Parses CollectionWeightStrategy from its exported name
+pub fn parse_collection_weight_strategy(name: &str) -> Option<CollectionWeightStrategy> {
+    match name.split_once(':') {
+        Some(("Capped", max_assets)) => {
+            let weight_strategy = CollectionWeightStrategy::Capped {
+                max_assets: max_assets.parse().ok()?,
+            };
+
+            weight_strategy.is_valid().then_some(weight_strategy)
+        }
+        Some(_) => None,
+        None =>
+            match name {
+                "Linear" => Some(CollectionWeightStrategy::Linear),
+                "Quadratic" => Some(CollectionWeightStrategy::Quadratic),
+                "Membership" => Some(CollectionWeightStrategy::Membership),
+                _ => None,
+            }
+    }
+}
+
+fn default_weight_strategy() -> String {
+    format_collection_weight_strategy(&CollectionWeightStrategy::default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_registrar(collection_configs: Vec<CollectionConfig>) -> Registrar {
+        Registrar {
+            governance_program_id: Pubkey::new_unique(),
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            collection_configs,
+            reserved: [0; 128],
+        }
+    }
+
+    fn create_collection_config(weight: u64, size: u32) -> CollectionConfig {
+        CollectionConfig::new(Pubkey::new_unique(), weight, size)
+    }
+
+    #[test]
+    fn test_diff_collections_with_same_registrar() {
+        // Arrange
+        let registrar = create_registrar(
+            vec![create_collection_config(1, 10), create_collection_config(2, 20)]
+        );
+        let registrar_extension = RegistrarExtension::new(Pubkey::new_unique());
+        let export = RegistrarConfigExport::from_registrar(
+            &Pubkey::new_unique(),
+            &registrar,
+            &registrar_extension
+        );
+
+        // Act
+        let changes = export.diff_collections(&registrar, &registrar_extension).unwrap();
+
+        // Assert
+        assert!(changes.is_empty());
+        assert!(export.diff_registrar_config(&registrar_extension).is_empty());
+    }
+
+    #[test]
+    fn test_diff_collections() {
+        // Arrange
+        let unchanged = create_collection_config(1, 10);
+        let changed = create_collection_config(2, 20);
+        let removed = create_collection_config(3, 30);
+        let added = create_collection_config(4, 40);
+        let creator = Pubkey::new_unique();
+
+        let mut source = create_registrar(vec![unchanged, changed, added]);
+        source.collection_configs[1].weight = 5;
+
+        let mut unchanged_extension = CollectionExtension::new(unchanged.collection);
+        unchanged_extension.creator_boosts[0] = CreatorBoost {
+            creator,
+            boost_bps: 5_000,
+        };
+
+        let mut source_extension = RegistrarExtension::new(Pubkey::new_unique());
+        source_extension.set_collection_extension(unchanged_extension);
+        source_extension.set_collection_extension(CollectionExtension {
+            expires_at: 1_000,
+            weight_strategy: CollectionWeightStrategy::Capped { max_assets: 3 },
+            uses_limited: true,
+            ..CollectionExtension::new(changed.collection)
+        });
+
+        let export = RegistrarConfigExport::from_registrar(
+            &Pubkey::new_unique(),
+            &source,
+            &source_extension
+        );
+
+        let target = create_registrar(vec![unchanged, changed, removed]);
+        let target_extension = RegistrarExtension::new(Pubkey::new_unique());
+
+        // Act
+        let changes = export.diff_collections(&target, &target_extension).unwrap();
+
+        // Assert
+        assert_eq!(changes, vec![
+            CollectionChange::ConfigureCreatorBoosts {
+                collection: unchanged.collection,
+                creator_boosts: vec![CreatorBoost {
+                    creator,
+                    boost_bps: 5_000,
+                }],
+            },
+            CollectionChange::ConfigureCollection {
+                collection: changed.collection,
+                weight: 5,
+                size: 20,
+            },
+            CollectionChange::ConfigureCollectionExpiry {
+                collection: changed.collection,
+                expires_at: 1_000,
+            },
+            CollectionChange::ConfigureCollectionWeightStrategy {
+                collection: changed.collection,
+                weight_strategy: CollectionWeightStrategy::Capped { max_assets: 3 },
+            },
+            CollectionChange::ConfigureCollectionUsesLimit {
+                collection: changed.collection,
+                uses_limited: true,
+            },
+            CollectionChange::ConfigureCollection {
+                collection: added.collection,
+                weight: 4,
+                size: 40,
+            },
+            CollectionChange::ConfigureCollection {
+                collection: removed.collection,
+                weight: 0,
+                size: 30,
+            }
+        ]);
+    }
+
+    #[test]
+    fn test_parse_collection_weight_strategy() {
+        // Arrange
+        let weight_strategies = [
+            CollectionWeightStrategy::Linear,
+            CollectionWeightStrategy::Quadratic,
+            CollectionWeightStrategy::Membership,
+            CollectionWeightStrategy::Capped { max_assets: 3 },
+        ];
+
+        // Act + Assert
+        for weight_strategy in weight_strategies {
+            let name = format_collection_weight_strategy(&weight_strategy);
+            assert_eq!(parse_collection_weight_strategy(&name), Some(weight_strategy));
+        }
+
+        assert_eq!(parse_collection_weight_strategy("Capped:0"), None);
+        assert_eq!(parse_collection_weight_strategy("Linear:1"), None);
+    }
+}
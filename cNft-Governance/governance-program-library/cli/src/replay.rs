@@ -0,0 +1,431 @@
+//! cnft-voter-replay replays a mainnet vote transaction of the nft-voter program in solana-program-test
+//!
+//! The transaction is fetched by its signature together with the accounts it references and the nft-voter
+//! instructions of the transaction (ex. create_cnft_action_ticket and cast_nft_vote) are executed against the local
+//! source of the program at the slot and time of the transaction. The logs and the result of the replay are printed
+//! next to the original ones so production failures can be reproduced with added logs or under a debugger.
+//!
+//! The original signatures can't be reused with the program-test bank and hence the signers are impersonated.
+//! The account named `payer` in the program IDL is replaced with a funded local keypair and the other signers
+//! (ex. voter_authority) are marked as signers before the program is entered. For that reason the program runs
+//! natively (processor!) and the instructions of the other programs in the transaction (ex. spl-governance CastVote)
+//! are not replayed.
+//!
+//! RPC serves only the current state of the accounts. The accounts written by the transaction or by any later
+//! transaction (ex. the merkle tree of the voted cNFTs) must be supplied as of the slot of the transaction
+//! with --account for the replay to be faithful.
+
+use std::{ collections::HashMap, fs, path::PathBuf, str::FromStr, sync::Mutex };
+
+use clap::Parser;
+use serde::Deserialize;
+use solana_account_decoder::UiAccount;
+use solana_client::{ rpc_client::RpcClient, rpc_config::RpcTransactionConfig };
+use solana_program_test::{ processor, ProgramTest };
+use solana_sdk::{
+    account::Account,
+    account_info::AccountInfo,
+    account_utils::StateMut,
+    bpf_loader_upgradeable::{ self, UpgradeableLoaderState },
+    clock::Clock,
+    commitment_config::CommitmentConfig,
+    entrypoint::ProgramResult,
+    hash::hashv,
+    instruction::{ AccountMeta, Instruction },
+    message::VersionedMessage,
+    native_loader,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    sysvar,
+    transaction::Transaction,
+};
+use solana_transaction_status::{ UiLoadedAddresses, UiTransactionEncoding };
+
+type ReplayResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// The maximum number of accounts which can be fetched with a single getMultipleAccounts request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Signers of the replayed instructions which are impersonated by process_replayed_instruction
+static FORCED_SIGNERS: Mutex<Vec<Pubkey>> = Mutex::new(Vec::new());
+
+#[derive(Parser)]
+#[clap(
+    name = "cnft-voter-replay",
+    about = "Replays a mainnet nft-voter transaction in solana-program-test against the local program source"
+)]
+struct Args {
+    /// Signature of the transaction to replay
+    signature: String,
+
+    /// RPC url of the cluster the transaction was executed on
+    #[clap(long, short = 'u', default_value = "https://api.mainnet-beta.solana.com")]
+    url: String,
+
+    /// Account state as of the slot of the transaction given as <pubkey>=<file>
+    /// The file has the format of `solana account <pubkey> --output json`
+    #[clap(long = "account")]
+    accounts: Vec<String>,
+
+    /// The IDL of the program used to find the payer accounts of the instructions
+    #[clap(long, default_value = "target/idl/gpl_nft_voter.json")]
+    idl: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Idl {
+    instructions: Vec<IdlInstruction>,
+}
+
+#[derive(Deserialize)]
+struct IdlInstruction {
+    name: String,
+    accounts: Vec<IdlAccount>,
+}
+
+#[derive(Deserialize)]
+struct IdlAccount {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct AccountFile {
+    account: UiAccount,
+}
+
+/// The account keys of the transaction with their privileges in the original message
+struct TransactionAccount {
+    pubkey: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    /// The account was created by the transaction and hence didn't exist before it
+    created: bool,
+}
+
+/// Entry of the program which impersonates FORCED_SIGNERS before entering the program
+fn process_replayed_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8]
+) -> ProgramResult {
+    let forced_signers = FORCED_SIGNERS.lock().unwrap().clone();
+
+    let accounts = accounts
+        .iter()
+        .cloned()
+        .map(|mut account| {
+            if forced_signers.contains(account.key) {
+                account.is_signer = true;
+            }
+            account
+        })
+        .collect::<Vec<_>>();
+
+    // Anchor entry requires the accounts to live as long as the AccountInfo data (&'info [AccountInfo<'info>])
+    // The replay runs a single transaction and the leaked accounts are released when the process exits
+    gpl_nft_voter::entry(program_id, Box::leak(accounts.into_boxed_slice()), data)
+}
+
+#[tokio::main]
+async fn main() -> ReplayResult<()> {
+    let args = Args::parse();
+
+    let rpc_client = RpcClient::new_with_commitment(args.url.clone(), CommitmentConfig::confirmed());
+    let signature = Signature::from_str(&args.signature)?;
+
+    let confirmed_transaction = rpc_client.get_transaction_with_config(&signature, RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    })?;
+
+    let slot = confirmed_transaction.slot;
+    let block_time = confirmed_transaction.block_time.ok_or("The transaction has no block time")?;
+    let meta = confirmed_transaction.transaction.meta.ok_or("The transaction has no status meta")?;
+    let transaction = confirmed_transaction.transaction.transaction
+        .decode()
+        .ok_or("The transaction can't be decoded")?;
+
+    let loaded_addresses: Option<UiLoadedAddresses> = meta.loaded_addresses.clone().into();
+    let transaction_accounts = get_transaction_accounts(
+        &transaction.message,
+        loaded_addresses,
+        &meta.pre_balances
+    )?;
+
+    let payer_indexes = get_payer_indexes(&args.idl)?;
+    let overrides = parse_account_overrides(&args.accounts)?;
+
+    let mut program_test = ProgramTest::new(
+        "gpl_nft_voter",
+        gpl_nft_voter::id(),
+        processor!(process_replayed_instruction)
+    );
+    program_test.prefer_bpf(false);
+
+    for (pubkey, account) in fetch_accounts(&rpc_client, &transaction_accounts, &overrides)? {
+        program_test.add_account(pubkey, account);
+    }
+
+    let mut context = program_test.start_with_context().await;
+    context.warp_to_slot(slot)?;
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await?;
+    clock.unix_timestamp = block_time;
+    context.set_sysvar(&clock);
+
+    let message = &transaction.message;
+    let mut instructions = vec![];
+    let mut forced_signers = vec![];
+
+    for (instruction_index, compiled_instruction) in message.instructions().iter().enumerate() {
+        let program_id = transaction_accounts[compiled_instruction.program_id_index as usize].pubkey;
+
+        if program_id != gpl_nft_voter::id() {
+            println!("Skipping instruction #{} of program {}", instruction_index, program_id);
+            continue;
+        }
+
+        let payer_index = compiled_instruction.data
+            .get(..8)
+            .and_then(|discriminator| payer_indexes.get(discriminator))
+            .copied()
+            .flatten();
+
+        let accounts = compiled_instruction.accounts
+            .iter()
+            .enumerate()
+            .map(|(position, account_index)| {
+                let account = &transaction_accounts[*account_index as usize];
+
+                if Some(position) == payer_index {
+                    AccountMeta::new(context.payer.pubkey(), true)
+                } else {
+                    if account.is_signer && !forced_signers.contains(&account.pubkey) {
+                        forced_signers.push(account.pubkey);
+                    }
+
+                    if account.is_writable {
+                        AccountMeta::new(account.pubkey, false)
+                    } else {
+                        AccountMeta::new_readonly(account.pubkey, false)
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        instructions.push(Instruction {
+            program_id,
+            accounts,
+            data: compiled_instruction.data.clone(),
+        });
+    }
+
+    if instructions.is_empty() {
+        return Err("The transaction has no nft-voter instructions".into());
+    }
+
+    *FORCED_SIGNERS.lock().unwrap() = forced_signers;
+
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await?;
+    let replay_transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        recent_blockhash
+    );
+
+    let replay = context.banks_client.process_transaction_with_metadata(replay_transaction).await?;
+
+    let original_logs: Option<Vec<String>> = meta.log_messages.into();
+
+    println!("Original result at slot {}: {:?}", slot, meta.err);
+    for log in original_logs.unwrap_or_default() {
+        println!("  {}", log);
+    }
+
+    println!("Replay result: {:?}", replay.result);
+    for log in replay.metadata.map(|metadata| metadata.log_messages).unwrap_or_default() {
+        println!("  {}", log);
+    }
+
+    Ok(())
+}
+
+/// Returns the account keys of the message including the addresses loaded from lookup tables
+fn get_transaction_accounts(
+    message: &VersionedMessage,
+    loaded_addresses: Option<UiLoadedAddresses>,
+    pre_balances: &[u64]
+) -> ReplayResult<Vec<TransactionAccount>> {
+    let header = message.header();
+    let static_keys = message.static_account_keys();
+
+    let num_signers = header.num_required_signatures as usize;
+    let num_writable_signers = num_signers - (header.num_readonly_signed_accounts as usize);
+    let num_writable_keys = static_keys.len() - (header.num_readonly_unsigned_accounts as usize);
+
+    let mut accounts = static_keys
+        .iter()
+        .enumerate()
+        .map(|(index, pubkey)| (
+            *pubkey,
+            index < num_signers,
+            if index < num_signers {
+                index < num_writable_signers
+            } else {
+                index < num_writable_keys
+            },
+        ))
+        .collect::<Vec<_>>();
+
+    if let Some(loaded_addresses) = loaded_addresses {
+        for pubkey in loaded_addresses.writable.iter() {
+            accounts.push((Pubkey::from_str(pubkey)?, false, true));
+        }
+        for pubkey in loaded_addresses.readonly.iter() {
+            accounts.push((Pubkey::from_str(pubkey)?, false, false));
+        }
+    }
+
+    Ok(
+        accounts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (pubkey, is_signer, is_writable))| TransactionAccount {
+                pubkey,
+                is_signer,
+                is_writable,
+                created: pre_balances.get(index).copied() == Some(0),
+            })
+            .collect()
+    )
+}
+
+/// Returns the position of the payer account for each instruction discriminator of the IDL
+fn get_payer_indexes(idl: &PathBuf) -> ReplayResult<HashMap<Vec<u8>, Option<usize>>> {
+    let idl: Idl = serde_json::from_slice(&fs::read(idl)?)?;
+
+    Ok(
+        idl.instructions
+            .iter()
+            .map(|instruction| {
+                let discriminator = hashv(
+                    &[b"global:", to_snake_case(&instruction.name).as_bytes()]
+                ).to_bytes()[..8].to_vec();
+
+                let payer_index = instruction.accounts
+                    .iter()
+                    .position(|account| account.name == "payer");
+
+                (discriminator, payer_index)
+            })
+            .collect()
+    )
+}
+
+/// Converts the camelCase IDL instruction name to the snake_case name used for the Anchor discriminator
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake_case.push('_');
+            snake_case.push(c.to_ascii_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+
+    snake_case
+}
+
+fn parse_account_overrides(accounts: &[String]) -> ReplayResult<HashMap<Pubkey, Account>> {
+    let mut overrides = HashMap::new();
+
+    for account in accounts.iter() {
+        let (pubkey, file) = account
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid account override {}, expected <pubkey>=<file>", account))?;
+
+        let account_file: AccountFile = serde_json::from_slice(&fs::read(file)?)?;
+        let account = account_file.account
+            .decode::<Account>()
+            .ok_or_else(|| format!("The account in {} can't be decoded", file))?;
+
+        overrides.insert(Pubkey::from_str(pubkey)?, account);
+    }
+
+    Ok(overrides)
+}
+
+/// Fetches the accounts of the transaction and the program data of the invoked upgradeable programs
+/// The builtin programs, sysvars and the nft-voter program itself are provided by program-test
+fn fetch_accounts(
+    rpc_client: &RpcClient,
+    transaction_accounts: &[TransactionAccount],
+    overrides: &HashMap<Pubkey, Account>
+) -> ReplayResult<Vec<(Pubkey, Account)>> {
+    let pubkeys = transaction_accounts
+        .iter()
+        .filter(|account| !account.created && !overrides.contains_key(&account.pubkey))
+        .map(|account| account.pubkey)
+        .collect::<Vec<_>>();
+
+    let mut accounts = fetch_multiple_accounts(rpc_client, &pubkeys)?;
+
+    let mut program_data_addresses = vec![];
+    for (_, account) in accounts.iter() {
+        if account.executable && account.owner == bpf_loader_upgradeable::id() {
+            if let UpgradeableLoaderState::Program { programdata_address } = account.state()? {
+                program_data_addresses.push(programdata_address);
+            }
+        }
+    }
+    accounts.extend(fetch_multiple_accounts(rpc_client, &program_data_addresses)?);
+
+    for transaction_account in transaction_accounts.iter() {
+        if
+            transaction_account.is_writable &&
+            !transaction_account.created &&
+            !overrides.contains_key(&transaction_account.pubkey)
+        {
+            eprintln!(
+                "Warning: {} was written by the transaction and its current state is used, supply it with --account to replay it as of the transaction slot",
+                transaction_account.pubkey
+            );
+        }
+    }
+
+    accounts.extend(overrides.iter().map(|(pubkey, account)| (*pubkey, account.clone())));
+
+    Ok(
+        accounts
+            .into_iter()
+            .filter(
+                |(pubkey, account)|
+                    *pubkey != gpl_nft_voter::id() &&
+                    account.owner != native_loader::id() &&
+                    account.owner != sysvar::id()
+            )
+            .collect()
+    )
+}
+
+fn fetch_multiple_accounts(
+    rpc_client: &RpcClient,
+    pubkeys: &[Pubkey]
+) -> ReplayResult<Vec<(Pubkey, Account)>> {
+    let mut accounts = vec![];
+
+    for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for (pubkey, account) in chunk.iter().zip(rpc_client.get_multiple_accounts(chunk)?) {
+            if let Some(account) = account {
+                accounts.push((*pubkey, account));
+            }
+        }
+    }
+
+    Ok(accounts)
+}
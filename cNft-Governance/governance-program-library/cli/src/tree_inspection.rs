@@ -0,0 +1,192 @@
+use std::convert::TryInto;
+
+use anchor_lang::AnchorDeserialize;
+use gpl_nft_voter::tools::{
+    program_ids::{ BUBBLEGUM_ID, MPL_ACCOUNT_COMPRESSION_ID, SPL_ACCOUNT_COMPRESSION_ID },
+    tree_shapes::{ get_concurrent_merkle_tree_size, is_supported_tree_shape },
+};
+use serde::Serialize;
+use solana_sdk::{ account::Account, pubkey::Pubkey };
+use spl_account_compression::state::{
+    ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+/// Offset of ConcurrentMerkleTreeHeaderDataV1.authority in the header
+/// account_type(1), header version(1), max_buffer_size(4), max_depth(4)
+const HEADER_AUTHORITY_OFFSET: usize = 10;
+
+/// Offset of ConcurrentMerkleTreeHeaderDataV1.creation_slot in the header
+const HEADER_CREATION_SLOT_OFFSET: usize = 42;
+
+/// Size of ConcurrentMerkleTree fields preceding change_logs: sequence_number(8), active_index(8), buffer_size(8)
+const CHANGE_LOGS_OFFSET: usize = 24;
+
+/// Offset of ConcurrentMerkleTree.active_index
+const ACTIVE_INDEX_OFFSET: usize = 8;
+
+/// Decoded header and state of a concurrent merkle tree and whether the plugin can verify its leaves
+/// Addresses and the root are printed as base58 strings
+#[derive(Serialize, Debug)]
+pub struct TreeInspection {
+    pub tree: String,
+
+    /// spl-account-compression or mpl-account-compression program which owns the tree
+    pub owner_program: String,
+
+    pub max_depth: u32,
+
+    pub max_buffer_size: u32,
+
+    /// Depth of the canopy derived from the size of the account
+    pub canopy_depth: u32,
+
+    pub authority: String,
+
+    /// Whether the authority is the Bubblegum TreeConfig PDA of the tree
+    pub bubblegum_authority: bool,
+
+    pub creation_slot: u64,
+
+    pub current_root: String,
+
+    /// Index of the rightmost leaf appended to the tree
+    pub rightmost_index: u32,
+
+    /// Whether (max_depth, max_buffer_size) is one of the tree shapes supported by the plugin
+    pub supported_shape: bool,
+
+    /// The reasons create_cnft_action_ticket would reject the assets of the tree, empty if it's supported
+    pub unsupported_reasons: Vec<String>,
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, String> {
+    bytes
+        .get(offset..offset + 8)
+        .map(|value| u64::from_le_bytes(value.try_into().unwrap()))
+        .ok_or_else(|| "Tree account data is too short".to_string())
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|value| u32::from_le_bytes(value.try_into().unwrap()))
+        .ok_or_else(|| "Tree account data is too short".to_string())
+}
+
+/// Returns the depth of the canopy stored in the given number of bytes
+/// A canopy of depth d stores the 2^(d+1) - 2 upper nodes of the tree
+fn get_canopy_depth(canopy_bytes: usize) -> Result<u32, String> {
+    let canopy_nodes = canopy_bytes / 32;
+
+    let mut canopy_depth = 0;
+    while (1usize << (canopy_depth + 1)) - 2 < canopy_nodes {
+        canopy_depth += 1;
+    }
+
+    if canopy_bytes % 32 != 0 || (1usize << (canopy_depth + 1)) - 2 != canopy_nodes {
+        return Err(format!("Invalid canopy size {}", canopy_bytes));
+    }
+
+    Ok(canopy_depth)
+}
+
+/// Decodes the given spl-account-compression or mpl-account-compression tree account
+pub fn inspect_tree(tree: &Pubkey, tree_account: &Account) -> Result<TreeInspection, String> {
+    let mut unsupported_reasons = vec![];
+
+    let owner_program = if tree_account.owner == SPL_ACCOUNT_COMPRESSION_ID {
+        "spl-account-compression".to_string()
+    } else if tree_account.owner == MPL_ACCOUNT_COMPRESSION_ID {
+        "mpl-account-compression".to_string()
+    } else {
+        unsupported_reasons.push(format!("Tree is owned by unknown program {}", tree_account.owner));
+        tree_account.owner.to_string()
+    };
+
+    let data = &tree_account.data;
+
+    let header_bytes = data
+        .get(..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1)
+        .ok_or_else(|| "Tree account data is too short".to_string())?;
+
+    let header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes).map_err(|err| {
+        format!("Can't decode tree header: {}", err)
+    })?;
+
+    let max_depth = header.get_max_depth();
+    let max_buffer_size = header.get_max_buffer_size();
+
+    let authority = Pubkey::new_from_array(
+        header_bytes[HEADER_AUTHORITY_OFFSET..HEADER_AUTHORITY_OFFSET + 32].try_into().unwrap()
+    );
+    let tree_config = Pubkey::find_program_address(&[tree.as_ref()], &BUBBLEGUM_ID).0;
+    let bubblegum_authority = authority == tree_config;
+
+    if !bubblegum_authority {
+        unsupported_reasons.push(
+            format!("Tree authority {} is not the Bubblegum TreeConfig {}", authority, tree_config)
+        );
+    }
+
+    let supported_shape = is_supported_tree_shape(max_depth, max_buffer_size);
+
+    if !supported_shape {
+        unsupported_reasons.push(
+            format!(
+                "Unsupported tree shape max_depth: {} max_buffer_size: {}",
+                max_depth,
+                max_buffer_size
+            )
+        );
+    }
+
+    let tree_size = get_concurrent_merkle_tree_size(max_depth as usize, max_buffer_size as usize);
+    let tree_bytes = &data[CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..];
+
+    if tree_bytes.len() < tree_size {
+        return Err(
+            format!(
+                "Tree account data is too short for max_depth: {} max_buffer_size: {}",
+                max_depth,
+                max_buffer_size
+            )
+        );
+    }
+
+    let canopy_depth = get_canopy_depth(tree_bytes.len() - tree_size)?;
+
+    // ChangeLog { root, path: [Node; max_depth], index: u32, _padding: u32 }
+    let change_log_size = 32 + 32 * (max_depth as usize) + 4 + 4;
+
+    let active_index = read_u64(tree_bytes, ACTIVE_INDEX_OFFSET)? as usize;
+    let root_offset = CHANGE_LOGS_OFFSET + active_index * change_log_size;
+    let current_root: [u8; 32] = tree_bytes
+        .get(root_offset..root_offset + 32)
+        .ok_or_else(|| format!("Invalid active_index {}", active_index))?
+        .try_into()
+        .unwrap();
+
+    // rightmost_proof: Path { proof: [Node; max_depth], leaf, index: u32, _padding: u32 } follows the change logs
+    let rightmost_proof_offset = CHANGE_LOGS_OFFSET + (max_buffer_size as usize) * change_log_size;
+    let rightmost_index = read_u32(
+        tree_bytes,
+        rightmost_proof_offset + 32 * (max_depth as usize) + 32
+    )?;
+
+    Ok(TreeInspection {
+        tree: tree.to_string(),
+        owner_program,
+        max_depth,
+        max_buffer_size,
+        canopy_depth,
+        authority: authority.to_string(),
+        bubblegum_authority,
+        creation_slot: read_u64(header_bytes, HEADER_CREATION_SLOT_OFFSET)?,
+        // The root is a 32 bytes hash and is printed in base58 like the roots returned by DAS API
+        current_root: Pubkey::new_from_array(current_root).to_string(),
+        rightmost_index,
+        supported_shape,
+        unsupported_reasons,
+    })
+}
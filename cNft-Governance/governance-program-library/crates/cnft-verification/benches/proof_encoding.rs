@@ -0,0 +1,60 @@
+//! Compares the proof-in-accounts and proof-in-data encodings of compressed NFT proofs across tree depths
+//!
+//! The transaction bytes of both encodings are printed for trees without canopy and with the largest canopy
+//! which keeps 3 proof nodes, and the time of the merkle proof verification done by the program for the data
+//! encoding is measured by criterion
+//! The compute units of both encodings are measured by the proof_encoding program tests of the NFT voter
+//!
+//! Run: cargo bench -p cnft-verification --bench proof_encoding
+
+use cnft_verification::{
+    get_proof_encoding_size,
+    recompute_merkle_root,
+    select_proof_encoding,
+    verify_merkle_proof,
+    ProofEncoding,
+    LEAF_SCHEMA_V1,
+};
+use criterion::{ black_box, criterion_group, criterion_main, BenchmarkId, Criterion };
+
+/// Max depths of the trees supported by spl-account-compression
+const MAX_DEPTHS: [u32; 7] = [3, 5, 14, 17, 20, 24, 30];
+
+fn print_proof_encoding_sizes() {
+    println!("max_depth canopy_depth accounts_bytes data_bytes selected");
+
+    for max_depth in MAX_DEPTHS {
+        for canopy_depth in [0, max_depth.saturating_sub(3)] {
+            println!(
+                "{:>9} {:>12} {:>14} {:>10} {:?}",
+                max_depth,
+                canopy_depth,
+                get_proof_encoding_size(ProofEncoding::Accounts, max_depth, canopy_depth),
+                get_proof_encoding_size(ProofEncoding::Data, max_depth, canopy_depth),
+                select_proof_encoding(max_depth, canopy_depth, LEAF_SCHEMA_V1)
+            );
+        }
+    }
+}
+
+fn bench_verify_merkle_proof(c: &mut Criterion) {
+    print_proof_encoding_sizes();
+
+    let mut group = c.benchmark_group("verify_merkle_proof");
+
+    for max_depth in MAX_DEPTHS {
+        let leaf = [1u8; 32];
+        let proof: Vec<[u8; 32]> = (0..max_depth).map(|i| [(i as u8) + 2; 32]).collect();
+        let index = (1u32 << max_depth) - 1;
+        let root = recompute_merkle_root(leaf, &proof, index);
+
+        group.bench_with_input(BenchmarkId::from_parameter(max_depth), &proof, |b, proof| {
+            b.iter(|| verify_merkle_proof(black_box(root), black_box(leaf), proof, index))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify_merkle_proof);
+criterion_main!(benches);
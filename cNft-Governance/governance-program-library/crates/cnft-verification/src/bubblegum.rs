@@ -0,0 +1,163 @@
+//! Conversions from the mpl-bubblegum metadata types
+//! Used by clients and tests which mint the assets using mpl-bubblegum instructions
+
+use mpl_bubblegum::types as bubblegum;
+
+use crate::metadata::*;
+
+impl From<&bubblegum::Creator> for Creator {
+    fn from(creator: &bubblegum::Creator) -> Self {
+        Self {
+            address: creator.address,
+            verified: creator.verified,
+            share: creator.share,
+        }
+    }
+}
+
+impl From<&bubblegum::Collection> for Collection {
+    fn from(collection: &bubblegum::Collection) -> Self {
+        Self {
+            verified: collection.verified,
+            key: collection.key,
+        }
+    }
+}
+
+impl From<&bubblegum::Uses> for Uses {
+    fn from(uses: &bubblegum::Uses) -> Self {
+        Self {
+            use_method: match uses.use_method {
+                bubblegum::UseMethod::Burn => UseMethod::Burn,
+                bubblegum::UseMethod::Multiple => UseMethod::Multiple,
+                bubblegum::UseMethod::Single => UseMethod::Single,
+            },
+            remaining: uses.remaining,
+            total: uses.total,
+        }
+    }
+}
+
+impl From<&bubblegum::MetadataArgs> for MetadataArgs {
+    fn from(metadata: &bubblegum::MetadataArgs) -> Self {
+        Self {
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            primary_sale_happened: metadata.primary_sale_happened,
+            is_mutable: metadata.is_mutable,
+            edition_nonce: metadata.edition_nonce,
+            token_standard: metadata.token_standard.as_ref().map(|token_standard| {
+                match token_standard {
+                    bubblegum::TokenStandard::NonFungible => TokenStandard::NonFungible,
+                    bubblegum::TokenStandard::FungibleAsset => TokenStandard::FungibleAsset,
+                    bubblegum::TokenStandard::Fungible => TokenStandard::Fungible,
+                    bubblegum::TokenStandard::NonFungibleEdition =>
+                        TokenStandard::NonFungibleEdition,
+                }
+            }),
+            collection: metadata.collection.as_ref().map(Collection::from),
+            uses: metadata.uses.as_ref().map(Uses::from),
+            token_program_version: match metadata.token_program_version {
+                bubblegum::TokenProgramVersion::Original => TokenProgramVersion::Original,
+                bubblegum::TokenProgramVersion::Token2022 => TokenProgramVersion::Token2022,
+            },
+            creators: metadata.creators.iter().map(Creator::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use solana_program::pubkey::Pubkey;
+
+    use super::*;
+    use crate::leaf::{ get_asset_id, get_leaf_node };
+
+    fn get_bubblegum_metadata() -> bubblegum::MetadataArgs {
+        bubblegum::MetadataArgs {
+            name: "cNFT".to_string(),
+            symbol: "CNFT".to_string(),
+            uri: "https://example.com/cnft.json".to_string(),
+            seller_fee_basis_points: 500,
+            primary_sale_happened: true,
+            is_mutable: false,
+            edition_nonce: Some(254),
+            token_standard: Some(bubblegum::TokenStandard::NonFungible),
+            collection: Some(bubblegum::Collection {
+                verified: true,
+                key: Pubkey::new_unique(),
+            }),
+            uses: Some(bubblegum::Uses {
+                use_method: bubblegum::UseMethod::Multiple,
+                remaining: 3,
+                total: 5,
+            }),
+            token_program_version: bubblegum::TokenProgramVersion::Original,
+            creators: vec![
+                bubblegum::Creator {
+                    address: Pubkey::new_unique(),
+                    verified: true,
+                    share: 60,
+                },
+                bubblegum::Creator {
+                    address: Pubkey::new_unique(),
+                    verified: false,
+                    share: 40,
+                }
+            ],
+        }
+    }
+
+    #[test]
+    fn test_hashes_match_bubblegum() {
+        // Arrange
+        let bubblegum_metadata = get_bubblegum_metadata();
+        let metadata = MetadataArgs::from(&bubblegum_metadata);
+
+        // Act
+        let data_hash = hash_metadata(&metadata);
+        let creator_hash = hash_creators(&metadata.creators);
+
+        // Assert
+        assert_eq!(data_hash, mpl_bubblegum::hash::hash_metadata(&bubblegum_metadata).unwrap());
+        assert_eq!(creator_hash, mpl_bubblegum::hash::hash_creators(&bubblegum_metadata.creators));
+    }
+
+    #[test]
+    fn test_leaf_node_matches_bubblegum() {
+        // Arrange
+        let tree = Pubkey::new_unique();
+        let leaf_owner = Pubkey::new_unique();
+        let leaf_delegate = Pubkey::new_unique();
+        let nonce = 42;
+        let data_hash = [1; 32];
+        let creator_hash = [2; 32];
+
+        // Act
+        let asset_id = get_asset_id(&tree, nonce);
+        let leaf_node = get_leaf_node(
+            &asset_id,
+            &leaf_owner,
+            &leaf_delegate,
+            nonce,
+            &data_hash,
+            &creator_hash
+        );
+
+        // Assert
+        assert_eq!(asset_id, mpl_bubblegum::utils::get_asset_id(&tree, nonce));
+        assert_eq!(
+            leaf_node,
+            (bubblegum::LeafSchema::V1 {
+                id: asset_id,
+                owner: leaf_owner,
+                delegate: leaf_delegate,
+                nonce,
+                data_hash,
+                creator_hash,
+            }).hash()
+        );
+    }
+}
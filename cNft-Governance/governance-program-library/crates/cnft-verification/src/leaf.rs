@@ -0,0 +1,127 @@
+use solana_program::{ keccak::hashv, pubkey::Pubkey };
+
+/// The mpl-bubblegum program id the asset ids are derived with
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = solana_program::pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// Seed prefix of the asset id PDA
+pub const ASSET_PREFIX: &[u8] = b"asset";
+
+/// Version of the leaf schema (mpl-bubblegum LeafSchema::V1)
+pub const LEAF_SCHEMA_V1: u8 = 1;
+
+/// Version of the leaf schema (Bubblegum LeafSchema::V2) used by the trees of mpl-account-compression
+pub const LEAF_SCHEMA_V2: u8 = 2;
+
+/// Returns the id of the asset minted into the given tree with the given nonce
+pub fn get_asset_id(tree: &Pubkey, nonce: u64) -> Pubkey {
+    get_asset_id_with_program(tree, nonce, &BUBBLEGUM_PROGRAM_ID)
+}
+
+/// Returns the id of the asset minted into the given tree with the given nonce by the given Bubblegum program
+pub fn get_asset_id_with_program(tree: &Pubkey, nonce: u64, bubblegum_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ASSET_PREFIX, tree.as_ref(), &nonce.to_le_bytes()],
+        bubblegum_program_id
+    ).0
+}
+
+/// Returns the leaf node of the asset as stored in the merkle tree
+/// keccak(version || asset_id || owner || delegate || nonce || data_hash || creator_hash)
+pub fn get_leaf_node(
+    asset_id: &Pubkey,
+    leaf_owner: &Pubkey,
+    leaf_delegate: &Pubkey,
+    nonce: u64,
+    data_hash: &[u8; 32],
+    creator_hash: &[u8; 32]
+) -> [u8; 32] {
+    hashv(
+        &[
+            &[LEAF_SCHEMA_V1],
+            asset_id.as_ref(),
+            leaf_owner.as_ref(),
+            leaf_delegate.as_ref(),
+            &nonce.to_le_bytes(),
+            data_hash,
+            creator_hash,
+        ]
+    ).to_bytes()
+}
+
+/// Returns the collection_hash of the LeafSchema::V2 leaf
+/// keccak(collection) where the assets without collection are hashed with the default pubkey
+pub fn hash_collection(collection: Option<&Pubkey>) -> [u8; 32] {
+    hashv(&[collection.unwrap_or(&Pubkey::default()).as_ref()]).to_bytes()
+}
+
+/// Returns the LeafSchema::V2 leaf node of the asset as stored in the merkle tree
+/// keccak(version || asset_id || owner || delegate || nonce || data_hash || creator_hash || collection_hash ||
+/// asset_data_hash || flags)
+pub fn get_leaf_node_v2(
+    asset_id: &Pubkey,
+    leaf_owner: &Pubkey,
+    leaf_delegate: &Pubkey,
+    nonce: u64,
+    data_hash: &[u8; 32],
+    creator_hash: &[u8; 32],
+    collection_hash: &[u8; 32],
+    asset_data_hash: &[u8; 32],
+    flags: u8
+) -> [u8; 32] {
+    hashv(
+        &[
+            &[LEAF_SCHEMA_V2],
+            asset_id.as_ref(),
+            leaf_owner.as_ref(),
+            leaf_delegate.as_ref(),
+            &nonce.to_le_bytes(),
+            data_hash,
+            creator_hash,
+            collection_hash,
+            asset_data_hash,
+            &[flags],
+        ]
+    ).to_bytes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_leaf_node_v2_differs_from_v1() {
+        // Arrange
+        let asset_id = Pubkey::new_unique();
+        let leaf_owner = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+
+        // Act
+        let leaf_node_v1 = get_leaf_node(&asset_id, &leaf_owner, &leaf_owner, 0, &[1; 32], &[2; 32]);
+        let leaf_node_v2 = get_leaf_node_v2(
+            &asset_id,
+            &leaf_owner,
+            &leaf_owner,
+            0,
+            &[1; 32],
+            &[2; 32],
+            &hash_collection(Some(&collection)),
+            &[0; 32],
+            0
+        );
+        let other_collection_leaf_node_v2 = get_leaf_node_v2(
+            &asset_id,
+            &leaf_owner,
+            &leaf_owner,
+            0,
+            &[1; 32],
+            &[2; 32],
+            &hash_collection(Some(&Pubkey::new_unique())),
+            &[0; 32],
+            0
+        );
+
+        // Assert
+        assert_ne!(leaf_node_v1, leaf_node_v2);
+        assert_ne!(leaf_node_v2, other_collection_leaf_node_v2);
+    }
+}
@@ -0,0 +1,20 @@
+//! Compressed NFT (Bubblegum) verification primitives
+//!
+//! The asset id derivation, metadata hashing, leaf hashing, merkle proof verification and vote record
+//! PDA derivation used by the NFT voter program, the clients and the test reference implementation
+//! The derivation matches mpl-bubblegum 0.7 (LeafSchema V1) and Bubblegum LeafSchema V2, and doesn't depend on Anchor
+
+pub mod leaf;
+pub mod merkle;
+pub mod metadata;
+pub mod proof_encoding;
+pub mod vote_record;
+
+#[cfg(feature = "mpl-bubblegum")]
+pub mod bubblegum;
+
+pub use leaf::*;
+pub use merkle::*;
+pub use metadata::*;
+pub use proof_encoding::*;
+pub use vote_record::*;
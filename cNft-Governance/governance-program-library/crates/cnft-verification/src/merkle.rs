@@ -0,0 +1,58 @@
+use solana_program::keccak::hashv;
+
+/// Recomputes the merkle tree root from the given leaf, its proof and index
+pub fn recompute_merkle_root(leaf: [u8; 32], proof: &[[u8; 32]], index: u32) -> [u8; 32] {
+    let mut node = leaf;
+
+    for (i, sibling) in proof.iter().enumerate() {
+        node = if (index >> i) & 1 == 0 {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+
+    node
+}
+
+/// Verifies the given leaf is at the given index of the merkle tree with the given root
+/// The proof must be the full proof (no canopy)
+pub fn verify_merkle_proof(root: [u8; 32], leaf: [u8; 32], proof: &[[u8; 32]], index: u32) -> bool {
+    recompute_merkle_root(leaf, proof, index) == root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_recompute_merkle_root() {
+        // Arrange
+        let leaves = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+
+        let left = hashv(&[&leaves[0], &leaves[1]]).to_bytes();
+        let right = hashv(&[&leaves[2], &leaves[3]]).to_bytes();
+        let expected_root = hashv(&[&left, &right]).to_bytes();
+
+        // Act
+        let root = recompute_merkle_root(leaves[2], &[leaves[3], left], 2);
+
+        // Assert
+        assert_eq!(root, expected_root);
+    }
+
+    #[test]
+    fn test_verify_merkle_proof_with_invalid_index() {
+        // Arrange
+        let leaves = [[1u8; 32], [2u8; 32]];
+        let root = hashv(&[&leaves[0], &leaves[1]]).to_bytes();
+
+        // Act
+        let is_valid = verify_merkle_proof(root, leaves[0], &[leaves[1]], 0);
+        let is_invalid_index_valid = verify_merkle_proof(root, leaves[0], &[leaves[1]], 1);
+
+        // Assert
+        assert!(is_valid);
+        assert!(!is_invalid_index_valid);
+    }
+}
@@ -0,0 +1,143 @@
+use borsh::{ BorshDeserialize, BorshSerialize };
+use solana_program::{ keccak::{ hash, hashv }, pubkey::Pubkey };
+
+/// Token program version of the compressed NFT
+/// Mirrors mpl_bubblegum::types::TokenProgramVersion
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+/// Mirrors mpl_bubblegum::types::TokenStandard
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+/// Mirrors mpl_bubblegum::types::Creator
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Mirrors mpl_bubblegum::types::Collection
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Mirrors mpl_bubblegum::types::UseMethod
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+/// Mirrors mpl_bubblegum::types::Uses
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+/// Metadata of the compressed NFT hashed into the leaf
+/// Mirrors mpl_bubblegum::types::MetadataArgs and the field order defines the borsh encoding
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+/// Metadata of the compressed NFT hashed into the LeafSchema::V2 leaf
+/// Mirrors Bubblegum MetadataArgsV2 where the collection is hashed into the leaf and it has no verified flag
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MetadataArgsV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub token_standard: Option<TokenStandard>,
+    pub creators: Vec<Creator>,
+    pub collection: Option<Pubkey>,
+}
+
+/// Returns the data_hash of the given metadata
+/// keccak(keccak(borsh(MetadataArgs)) || seller_fee_basis_points)
+pub fn hash_metadata(metadata: &MetadataArgs) -> [u8; 32] {
+    let metadata_args_hash = hash(&metadata.try_to_vec().unwrap());
+
+    hashv(
+        &[metadata_args_hash.as_ref(), &metadata.seller_fee_basis_points.to_le_bytes()]
+    ).to_bytes()
+}
+
+/// Returns the data_hash of the given LeafSchema::V2 metadata
+/// keccak(keccak(borsh(MetadataArgsV2)) || seller_fee_basis_points)
+pub fn hash_metadata_v2(metadata: &MetadataArgsV2) -> [u8; 32] {
+    let metadata_args_hash = hash(&metadata.try_to_vec().unwrap());
+
+    hashv(
+        &[metadata_args_hash.as_ref(), &metadata.seller_fee_basis_points.to_le_bytes()]
+    ).to_bytes()
+}
+
+/// Returns the creator_hash of the given creators
+/// keccak(address || verified || share ...)
+pub fn hash_creators(creators: &[Creator]) -> [u8; 32] {
+    let creator_data = creators
+        .iter()
+        .map(|creator| [creator.address.as_ref(), &[creator.verified as u8, creator.share]].concat())
+        .collect::<Vec<Vec<u8>>>();
+
+    hashv(
+        &creator_data
+            .iter()
+            .map(|data| data.as_slice())
+            .collect::<Vec<&[u8]>>()
+    ).to_bytes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hash_creators() {
+        // Arrange
+        let creator = Creator {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 100,
+        };
+
+        // Act
+        let creator_hash = hash_creators(&[creator.clone()]);
+
+        // Assert
+        assert_eq!(
+            creator_hash,
+            hashv(&[creator.address.as_ref(), &[1, 100]]).to_bytes()
+        );
+    }
+}
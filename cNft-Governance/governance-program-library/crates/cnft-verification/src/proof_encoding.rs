@@ -0,0 +1,90 @@
+use crate::leaf::LEAF_SCHEMA_V2;
+
+/// Size of a proof node
+pub const PROOF_NODE_SIZE: usize = 32;
+
+/// Encoding of the merkle proof of a compressed NFT supplied to create_cnft_action_ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofEncoding {
+    /// The proof nodes are supplied as accounts after the tree (CompressedNftAsset.proof_len)
+    /// and the leaf is verified by the account-compression VerifyLeaf CPI
+    /// The nodes stored in the tree canopy are omitted
+    Accounts,
+
+    /// The proof nodes are supplied as instruction data (CompressedNftAsset.proof_nodes)
+    /// and the leaf is verified by the program without the CPI
+    /// The full proof must be supplied because the canopy is not used
+    Data,
+}
+
+/// Returns the number of transaction bytes taken by the proof of a leaf of the tree with the given depths
+/// Accounts: each node is a unique account key (32 bytes) of the message and an account index (1 byte) of the instruction
+/// Data: the nodes are a borsh Vec (4 bytes length prefix) of the full proof
+/// Note: The compact-u16 prefixes of the message can grow by 1 byte when they cross 127 and they are not counted
+pub fn get_proof_encoding_size(encoding: ProofEncoding, max_depth: u32, canopy_depth: u32) -> usize {
+    match encoding {
+        ProofEncoding::Accounts => {
+            (max_depth.saturating_sub(canopy_depth) as usize) * (PROOF_NODE_SIZE + 1)
+        }
+        ProofEncoding::Data => 4 + (max_depth as usize) * PROOF_NODE_SIZE,
+    }
+}
+
+/// Returns the proof encoding which takes fewer transaction bytes for a leaf of the tree with the given depths
+/// Data is selected when both encodings have the same size because it doesn't use the VerifyLeaf CPI
+/// LeafSchema::V2 assets (mpl-account-compression trees) can only be verified using Data
+///
+/// The compute units and transaction sizes of both encodings across tree depths are measured
+/// by the proof_encoding bench of this crate and the proof_encoding program tests of the NFT voter
+pub fn select_proof_encoding(
+    max_depth: u32,
+    canopy_depth: u32,
+    leaf_schema_version: u8
+) -> ProofEncoding {
+    if leaf_schema_version == LEAF_SCHEMA_V2 {
+        return ProofEncoding::Data;
+    }
+
+    if
+        get_proof_encoding_size(ProofEncoding::Data, max_depth, canopy_depth) <=
+        get_proof_encoding_size(ProofEncoding::Accounts, max_depth, canopy_depth)
+    {
+        ProofEncoding::Data
+    } else {
+        ProofEncoding::Accounts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::leaf::LEAF_SCHEMA_V1;
+
+    #[test]
+    fn test_get_proof_encoding_size() {
+        // Act
+        let accounts_size = get_proof_encoding_size(ProofEncoding::Accounts, 14, 10);
+        let data_size = get_proof_encoding_size(ProofEncoding::Data, 14, 10);
+
+        // Assert
+        assert_eq!(accounts_size, 4 * 33);
+        assert_eq!(data_size, 4 + 14 * 32);
+    }
+
+    #[test]
+    fn test_select_proof_encoding() {
+        // Act + Assert
+
+        // Without canopy the data encoding saves the account index of each node
+        assert_eq!(select_proof_encoding(3, 0, LEAF_SCHEMA_V1), ProofEncoding::Accounts);
+        assert_eq!(select_proof_encoding(4, 0, LEAF_SCHEMA_V1), ProofEncoding::Data);
+        assert_eq!(select_proof_encoding(14, 0, LEAF_SCHEMA_V1), ProofEncoding::Data);
+
+        // The canopy shortens only the proof supplied as accounts
+        assert_eq!(select_proof_encoding(14, 1, LEAF_SCHEMA_V1), ProofEncoding::Accounts);
+        assert_eq!(select_proof_encoding(20, 10, LEAF_SCHEMA_V1), ProofEncoding::Accounts);
+
+        // LeafSchema::V2 leaves can't be verified using the proof accounts
+        assert_eq!(select_proof_encoding(20, 10, LEAF_SCHEMA_V2), ProofEncoding::Data);
+    }
+}
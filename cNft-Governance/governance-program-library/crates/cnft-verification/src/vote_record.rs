@@ -0,0 +1,87 @@
+use solana_program::pubkey::Pubkey;
+
+/// Seed prefix of the vote record PDA
+pub const VOTE_RECORD_PREFIX: &[u8] = b"nft-vote-record";
+
+/// Returns the seeds of the vote record PDA of the asset used to vote on the proposal through the registrar
+/// The registrar is part of the seeds to keep the records of realms sharing trees and collections apart
+pub fn get_cnft_vote_record_seeds<'a>(
+    registrar: &'a Pubkey,
+    proposal: &'a Pubkey,
+    asset_id: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [VOTE_RECORD_PREFIX, registrar.as_ref(), proposal.as_ref(), asset_id.as_ref()]
+}
+
+/// Returns the address of the vote record of the asset used to vote on the proposal through the registrar
+/// Clients should use it instead of deriving the seeds by hand
+pub fn get_cnft_vote_record_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    asset_id: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_cnft_vote_record_seeds(registrar, proposal, asset_id),
+        program_id
+    ).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_cnft_vote_record_address_is_unique() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let registrars = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let proposals = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let asset_ids = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let mut addresses = vec![];
+
+        // Act
+        for registrar in registrars.iter() {
+            for proposal in proposals.iter() {
+                for asset_id in asset_ids.iter() {
+                    addresses.push(
+                        get_cnft_vote_record_address(&program_id, registrar, proposal, asset_id)
+                    );
+                }
+            }
+        }
+
+        // Assert
+        let mut unique_addresses = addresses.clone();
+        unique_addresses.sort();
+        unique_addresses.dedup();
+
+        assert_eq!(unique_addresses.len(), addresses.len());
+    }
+
+    #[test]
+    fn test_get_cnft_vote_record_address_with_swapped_keys() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let registrar = Pubkey::new_unique();
+        let proposal = Pubkey::new_unique();
+        let asset_id = Pubkey::new_unique();
+
+        // Act
+        let address = get_cnft_vote_record_address(&program_id, &registrar, &proposal, &asset_id);
+
+        // Assert
+        // Every key has a fixed position in the seeds and the same keys in different roles don't collide
+        for (registrar, proposal, asset_id) in [
+            (&proposal, &registrar, &asset_id),
+            (&registrar, &asset_id, &proposal),
+            (&asset_id, &proposal, &registrar),
+        ] {
+            assert_ne!(
+                address,
+                get_cnft_vote_record_address(&program_id, registrar, proposal, asset_id)
+            );
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use borsh::{ BorshDeserialize, BorshSerialize };
+use solana_program::{ hash::hash, pubkey::Pubkey };
+
+use crate::CoreError;
+
+/// The size of the account discriminator prefixing the NFT voter accounts
+pub const DISCRIMINATOR_SIZE: usize = 8;
+
+/// Account of the NFT voter program which can be decoded using decode_account
+pub trait NftVoterAccount: BorshDeserialize {
+    /// The account name the discriminator is derived from
+    const ACCOUNT_NAME: &'static str;
+
+    /// Returns the discriminator prefixing the account data, derived from ACCOUNT_NAME by default
+    fn get_discriminator() -> [u8; DISCRIMINATOR_SIZE] {
+        get_account_discriminator(Self::ACCOUNT_NAME)
+    }
+}
+
+/// Returns the discriminator of the given account name
+/// sha256("account:{account_name}")[..8]
+pub fn get_account_discriminator(account_name: &str) -> [u8; DISCRIMINATOR_SIZE] {
+    let mut discriminator = [0; DISCRIMINATOR_SIZE];
+    discriminator.copy_from_slice(
+        &hash(format!("account:{}", account_name).as_bytes()).to_bytes()[..DISCRIMINATOR_SIZE]
+    );
+    discriminator
+}
+
+/// Decodes the account from its data checking the discriminator
+/// The space allocated beyond the account (ex. for the collections which are not configured yet) is ignored
+pub fn decode_account<T: NftVoterAccount>(data: &[u8]) -> Result<T, CoreError> {
+    if data.len() < DISCRIMINATOR_SIZE {
+        return Err(CoreError::InvalidAccountDiscriminator);
+    }
+
+    let (discriminator, mut data) = data.split_at(DISCRIMINATOR_SIZE);
+
+    if discriminator != T::get_discriminator() {
+        return Err(CoreError::InvalidAccountDiscriminator);
+    }
+
+    T::deserialize(&mut data).map_err(|_| CoreError::InvalidAccountData)
+}
+
+/// VoterWeightAction as defined in spl-governance-addin-api
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+impl VoterWeightAction {
+    /// Returns the NftActionTicket seed prefix of the action
+    /// Note: The misspelled SignOffProposal ticket type is part of the PDA seeds and it must be preserved
+    pub fn get_ticket_type(&self) -> &'static str {
+        match self {
+            VoterWeightAction::CastVote => "nft-castVote-ticket",
+            VoterWeightAction::CommentProposal => "nft-commentProposal-ticket",
+            VoterWeightAction::CreateGovernance => "nft-createGovernance-ticket",
+            VoterWeightAction::CreateProposal => "nft-createProposal-ticket",
+            VoterWeightAction::SignOffProposal => "nft-cignOffProposal-ticket",
+        }
+    }
+}
+
+/// State of the voter weight accumulated by the plugin
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoterWeightState {
+    Consumed,
+    Accumulating,
+    Finalized,
+}
+
+/// VoterWeightRecord account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct VoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+    pub voter_weight_expiry: Option<u64>,
+    pub weight_action: Option<VoterWeightAction>,
+    pub weight_action_target: Option<Pubkey>,
+    pub weight_state: VoterWeightState,
+    pub record_nonce: u32,
+    pub reserved: [u8; 3],
+}
+
+impl NftVoterAccount for VoterWeightRecord {
+    const ACCOUNT_NAME: &'static str = "VoterWeightRecord";
+}
+
+impl VoterWeightRecord {
+    /// Returns the weight state as of the given slot
+    /// Finalized weight is only valid in the slot it was finalized at and it's Consumed afterwards
+    pub fn get_weight_state(&self, slot: u64) -> VoterWeightState {
+        match self.weight_state {
+            VoterWeightState::Finalized if self.voter_weight_expiry < Some(slot) => {
+                VoterWeightState::Consumed
+            }
+            weight_state => weight_state,
+        }
+    }
+}
+
+/// MaxVoterWeightRecord account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MaxVoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub max_voter_weight: u64,
+    pub max_voter_weight_expiry: Option<u64>,
+    pub reserved: [u8; 8],
+}
+
+impl NftVoterAccount for MaxVoterWeightRecord {
+    const ACCOUNT_NAME: &'static str = "MaxVoterWeightRecord";
+}
+
+/// NftActionTicket account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct NftActionTicket {
+    pub registrar: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub weight: u64,
+    pub collection: Pubkey,
+    pub expiry: Option<u64>,
+    pub delegation_expires_at: i64,
+    pub created_at_slot: u64,
+}
+
+impl NftVoterAccount for NftActionTicket {
+    const ACCOUNT_NAME: &'static str = "NftActionTicket";
+
+    /// NftActionTicket isn't an Anchor account and keeps the fixed discriminator of the program
+    fn get_discriminator() -> [u8; DISCRIMINATOR_SIZE] {
+        [170, 179, 4, 130, 24, 148, 185, 97]
+    }
+}
+
+/// The max number of creator boosts of a collection
+pub const MAX_CREATOR_BOOSTS: usize = 3;
+
+/// The max number of Bubblegum tree authorities of the Registrar
+pub const MAX_TREE_AUTHORITIES: usize = 4;
+
+/// 100% expressed in basis points
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Extra weight of the NFTs of a collection created by the given creator
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CreatorBoost {
+    pub creator: Pubkey,
+    pub boost_bps: u16,
+}
+
+/// How the weight of the NFTs of a collection held by a single voter is aggregated
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollectionWeightStrategy {
+    #[default]
+    Linear,
+    Quadratic,
+    Membership,
+    Capped {
+        max_assets: u16,
+    },
+}
+
+/// Configuration of an NFT collection used for governance power
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectionConfig {
+    pub collection: Pubkey,
+    pub size: u32,
+    pub weight: u64,
+    pub reserved: [u8; 8],
+}
+
+/// Collection settings stored in RegistrarExtension.collection_extensions
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CollectionExtension {
+    pub collection: Pubkey,
+    pub creator_boosts: [CreatorBoost; MAX_CREATOR_BOOSTS],
+    pub expires_at: i64,
+    pub weight_strategy: CollectionWeightStrategy,
+    pub uses_limited: bool,
+    pub reserved: [u8; 32],
+}
+
+impl CollectionExtension {
+    /// Returns true if the collection is expired at the given unix timestamp
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        self.expires_at > 0 && unix_timestamp >= self.expires_at
+    }
+}
+
+/// Defines how the weight of the NFTs held by a voter is aggregated
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionWeightMode {
+    PerAsset,
+    UniqueCollections,
+    Normalized,
+}
+
+/// Registrar level voting configuration
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistrarConfig {
+    pub collection_weight_mode: CollectionWeightMode,
+    pub max_collection_weight_per_proposal: u16,
+    pub min_holding_slots: u64,
+    pub holding_attestation_authority: Pubkey,
+    pub require_proposal_creation_ticket: bool,
+    pub track_voter_weight_detail: bool,
+    pub tree_authority_allowlist: [Pubkey; MAX_TREE_AUTHORITIES],
+    pub allow_precomputed_metadata_hashes: bool,
+    pub voter_allowlist_root: [u8; 32],
+    pub rent_reclaim_treasury: Pubkey,
+    pub rent_reclaim_treasury_share_bps: u16,
+    pub track_collection_stats: bool,
+    pub parent_registrar: Pubkey,
+    pub require_same_transaction_consumption: bool,
+    pub normalized_collection_weight: u64,
+    pub allow_core_assets: bool,
+    pub max_assets_per_tx: u16,
+    pub require_vote_finalization: bool,
+    pub max_tree_assets_per_slot: u16,
+    pub proof_attestation_freshness_slots: u64,
+    pub max_tree_root_age: u32,
+    pub allow_voter_migration: bool,
+    pub leaf_hash_verifier: Pubkey,
+    pub weight_record_min_age_slots: u64,
+    pub record_voter_checkpoints: bool,
+    pub require_weight_action_target: bool,
+    pub weight_export_program: Pubkey,
+    pub allow_collection_aliases: bool,
+    pub min_weight_if_registered_before_slot: u64,
+    pub registration_cutoff_slot: u64,
+    pub bubblegum_program_override: Pubkey,
+    pub allow_wallet_weight_overrides: bool,
+    pub allow_weight_leases: bool,
+    pub vote_receipt_tree: Pubkey,
+}
+
+/// RegistrarMetrics account with the activity counters of a shard of the voters of the Registrar
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistrarMetrics {
+    pub registrar: Pubkey,
+    pub shard: u8,
+    pub total_votes_cast: u64,
+    pub total_weight_granted: u64,
+    pub last_activity_slot: u64,
+    pub reserved: [u8; 16],
+}
+
+impl NftVoterAccount for RegistrarMetrics {
+    const ACCOUNT_NAME: &'static str = "RegistrarMetrics";
+}
+
+/// Registrar account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Registrar {
+    pub governance_program_id: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub collection_configs: Vec<CollectionConfig>,
+    pub reserved: [u8; 128],
+}
+
+impl NftVoterAccount for Registrar {
+    const ACCOUNT_NAME: &'static str = "Registrar";
+}
+
+/// RegistrarExtension account with the voting configuration of the Registrar
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RegistrarExtension {
+    pub registrar: Pubkey,
+    pub version: u8,
+    pub config: RegistrarConfig,
+    pub sub_registrars_max_voter_weight: u64,
+    pub legacy_nft_vote_records_cutoff: i64,
+    pub registered_voters: u64,
+    pub collection_extensions: Vec<CollectionExtension>,
+    pub reserved: [u8; 128],
+}
+
+impl NftVoterAccount for RegistrarExtension {
+    const ACCOUNT_NAME: &'static str = "RegistrarExtension";
+}
+
+impl Registrar {
+    /// Returns the config of the given collection or None if the collection is not configured
+    pub fn find_collection_config(&self, collection: &Pubkey) -> Option<&CollectionConfig> {
+        self.collection_configs.iter().find(|cc| cc.collection == *collection)
+    }
+}
+
+impl RegistrarExtension {
+    /// Returns the extension of the given collection or the default settings if the collection has no extension
+    pub fn get_collection_extension(&self, collection: &Pubkey) -> CollectionExtension {
+        self.collection_extensions
+            .iter()
+            .find(|ce| ce.collection == *collection)
+            .copied()
+            .unwrap_or(CollectionExtension {
+                collection: *collection,
+                ..Default::default()
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_account_discriminator() {
+        // Act + Assert
+        assert_eq!(get_account_discriminator("Registrar"), [193, 202, 205, 51, 78, 168, 150, 128]);
+        assert_eq!(NftActionTicket::get_discriminator(), [170, 179, 4, 130, 24, 148, 185, 97]);
+    }
+
+    #[test]
+    fn test_decode_max_voter_weight_record() {
+        // Arrange
+        let max_voter_weight_record = MaxVoterWeightRecord {
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            max_voter_weight: 1_000,
+            max_voter_weight_expiry: None,
+            reserved: [0; 8],
+        };
+
+        let data = [
+            &get_account_discriminator("MaxVoterWeightRecord")[..],
+            &max_voter_weight_record.try_to_vec().unwrap(),
+            &[0; 16],
+        ].concat();
+
+        // Act
+        let decoded = decode_account::<MaxVoterWeightRecord>(&data).unwrap();
+
+        // Assert
+        assert_eq!(decoded, max_voter_weight_record);
+    }
+
+    #[test]
+    fn test_decode_account_with_invalid_discriminator_error() {
+        // Arrange
+        let data = [&get_account_discriminator("Registrar")[..], &[0; 200]].concat();
+
+        // Act
+        let err = decode_account::<MaxVoterWeightRecord>(&data).err().unwrap();
+
+        // Assert
+        assert_eq!(err, CoreError::InvalidAccountDiscriminator);
+        assert_eq!(
+            decode_account::<Registrar>(&[0; 4]).err().unwrap(),
+            CoreError::InvalidAccountDiscriminator
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Errors of the cnft-voter-core helpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreError {
+    /// The account data doesn't start with the discriminator of the expected account
+    InvalidAccountDiscriminator,
+
+    /// The account data can't be deserialized into the expected account
+    InvalidAccountData,
+
+    /// The collection of the asset is not configured in the Registrar
+    CollectionNotFound,
+
+    /// The weight doesn't fit into u64
+    ArithmeticOverflow,
+
+    /// The weight can't be converted to u64
+    CastError,
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::InvalidAccountDiscriminator => write!(f, "Invalid account discriminator"),
+            CoreError::InvalidAccountData => write!(f, "Invalid account data"),
+            CoreError::CollectionNotFound => write!(f, "Collection not found"),
+            CoreError::ArithmeticOverflow => write!(f, "Arithmetic overflow"),
+            CoreError::CastError => write!(f, "Cast error"),
+        }
+    }
+}
+
+impl std::error::Error for CoreError {}
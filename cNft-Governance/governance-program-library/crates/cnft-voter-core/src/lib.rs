@@ -0,0 +1,15 @@
+//! Read-only NFT voter helpers for web clients
+//!
+//! The PDA derivation, account decoding and voter weight simulation of the NFT voter program
+//! implemented without Anchor and the program crate so the crate builds for wasm32-unknown-unknown
+//! The account layouts and the weight math mirror the program and are pinned by tests/voter_core.rs of the program
+
+pub mod accounts;
+pub mod error;
+pub mod pda;
+pub mod weight;
+
+pub use accounts::*;
+pub use error::*;
+pub use pda::*;
+pub use weight::*;
@@ -0,0 +1,257 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::VoterWeightAction;
+
+pub use cnft_verification::get_cnft_vote_record_address;
+
+/// The NFT voter program id
+/// The helpers take the program id to support deployments under other ids, ex. devnet builds
+pub const NFT_VOTER_PROGRAM_ID: Pubkey = solana_program::pubkey!(
+    "GnftVc21v2BRchsRa9dGdrVmJPLZiRHe9j2offnFTZFg"
+);
+
+fn find_program_address(program_id: &Pubkey, seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+/// Returns the Registrar address of the given realm and governing token mint
+pub fn get_registrar_address(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey
+) -> Pubkey {
+    find_program_address(program_id, &[b"registrar", realm.as_ref(), governing_token_mint.as_ref()])
+}
+
+/// Returns the RegistrarExtension address of the given Registrar
+pub fn get_registrar_extension_address(program_id: &Pubkey, registrar: &Pubkey) -> Pubkey {
+    find_program_address(program_id, &[b"registrar-extension", registrar.as_ref()])
+}
+
+/// Returns the VoterWeightRecord address of the given voter
+pub fn get_voter_weight_record_address(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[
+            b"voter-weight-record",
+            realm.as_ref(),
+            governing_token_mint.as_ref(),
+            governing_token_owner.as_ref(),
+        ]
+    )
+}
+
+/// Returns the MaxVoterWeightRecord address of the given realm and governing token mint
+pub fn get_max_voter_weight_record_address(
+    program_id: &Pubkey,
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[b"max-voter-weight-record", realm.as_ref(), governing_token_mint.as_ref()]
+    )
+}
+
+/// Returns the NftActionTicket address of the given NFT created for the given action
+pub fn get_nft_action_ticket_address(
+    program_id: &Pubkey,
+    action: VoterWeightAction,
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    nft_mint: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[
+            action.get_ticket_type().as_bytes(),
+            registrar.as_ref(),
+            governing_token_owner.as_ref(),
+            nft_mint.as_ref(),
+        ]
+    )
+}
+
+/// Returns the VoterFreezeRecord address of the given voter
+pub fn get_voter_freeze_record_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[b"voter-freeze-record", registrar.as_ref(), governing_token_owner.as_ref()]
+    )
+}
+
+/// Returns the VoterGroup address of the given voting wallet
+pub fn get_voter_group_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    voting_wallet: &Pubkey
+) -> Pubkey {
+    find_program_address(program_id, &[b"voter-group", registrar.as_ref(), voting_wallet.as_ref()])
+}
+
+/// Returns the VoterGroupMembership address of the given member wallet
+pub fn get_voter_group_membership_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    member: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[b"voter-group-membership", registrar.as_ref(), member.as_ref()]
+    )
+}
+
+/// Returns the ColdWalletLink address of the given cold wallet
+pub fn get_cold_wallet_link_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    cold_wallet: &Pubkey
+) -> Pubkey {
+    find_program_address(program_id, &[b"cold-wallet-link", registrar.as_ref(), cold_wallet.as_ref()])
+}
+
+/// Returns the VoterCheckpoint address of the vote of the given voter on the given proposal
+pub fn get_voter_checkpoint_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[
+            b"voter-checkpoint",
+            registrar.as_ref(),
+            proposal.as_ref(),
+            governing_token_owner.as_ref(),
+        ]
+    )
+}
+
+/// Returns the BallotTree address of the given merkle tree
+pub fn get_ballot_tree_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    merkle_tree: &Pubkey
+) -> Pubkey {
+    find_program_address(program_id, &[b"ballot-tree", registrar.as_ref(), merkle_tree.as_ref()])
+}
+
+/// Returns the Campaign address of the given proposal
+pub fn get_campaign_address(program_id: &Pubkey, registrar: &Pubkey, proposal: &Pubkey) -> Pubkey {
+    find_program_address(program_id, &[b"campaign", registrar.as_ref(), proposal.as_ref()])
+}
+
+/// Returns the CampaignClaim address of the given claimant
+pub fn get_campaign_claim_address(
+    program_id: &Pubkey,
+    campaign: &Pubkey,
+    claimant: &Pubkey
+) -> Pubkey {
+    find_program_address(program_id, &[b"campaign-claim", campaign.as_ref(), claimant.as_ref()])
+}
+
+/// Returns the CollectionAlias address of the given collection
+pub fn get_collection_alias_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    collection: &Pubkey
+) -> Pubkey {
+    find_program_address(program_id, &[b"collection-alias", registrar.as_ref(), collection.as_ref()])
+}
+
+/// Returns the WeightLease address of the given asset of the given owner
+pub fn get_weight_lease_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    asset_id: &Pubkey,
+    owner: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[b"weight-lease", registrar.as_ref(), asset_id.as_ref(), owner.as_ref()]
+    )
+}
+
+/// Returns the VoterRegistration address of the given voter
+pub fn get_voter_registration_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[b"voter-registration", registrar.as_ref(), governing_token_owner.as_ref()]
+    )
+}
+
+/// Returns the WalletWeightOverride address of the given wallet
+pub fn get_wallet_weight_override_address(
+    program_id: &Pubkey,
+    registrar: &Pubkey,
+    wallet: &Pubkey
+) -> Pubkey {
+    find_program_address(
+        program_id,
+        &[b"wallet-weight-override", registrar.as_ref(), wallet.as_ref()]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_nft_action_ticket_address_is_unique_per_action() {
+        // Arrange
+        let registrar = Pubkey::new_unique();
+        let governing_token_owner = Pubkey::new_unique();
+        let nft_mint = Pubkey::new_unique();
+
+        // Act
+        let cast_vote_ticket = get_nft_action_ticket_address(
+            &NFT_VOTER_PROGRAM_ID,
+            VoterWeightAction::CastVote,
+            &registrar,
+            &governing_token_owner,
+            &nft_mint
+        );
+        let create_proposal_ticket = get_nft_action_ticket_address(
+            &NFT_VOTER_PROGRAM_ID,
+            VoterWeightAction::CreateProposal,
+            &registrar,
+            &governing_token_owner,
+            &nft_mint
+        );
+
+        // Assert
+        assert_ne!(cast_vote_ticket, create_proposal_ticket);
+    }
+
+    #[test]
+    fn test_get_registrar_address_with_program_id() {
+        // Arrange
+        let realm = Pubkey::new_unique();
+        let governing_token_mint = Pubkey::new_unique();
+
+        // Act
+        let registrar = get_registrar_address(&NFT_VOTER_PROGRAM_ID, &realm, &governing_token_mint);
+        let devnet_registrar = get_registrar_address(
+            &Pubkey::new_unique(),
+            &realm,
+            &governing_token_mint
+        );
+
+        // Assert
+        assert_ne!(registrar, devnet_registrar);
+    }
+}
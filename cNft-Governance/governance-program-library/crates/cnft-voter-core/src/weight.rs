@@ -0,0 +1,474 @@
+use solana_program::pubkey::Pubkey;
+use std::convert::TryFrom;
+
+use crate::{
+    CollectionConfig,
+    CollectionExtension,
+    CollectionWeightMode,
+    CollectionWeightStrategy,
+    CoreError,
+    MAX_BASIS_POINTS,
+    Registrar,
+    RegistrarConfig,
+    RegistrarExtension,
+};
+
+/// An asset (NFT, compressed NFT or Core asset) held by the voter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedAsset {
+    /// The verified collection of the asset
+    pub collection: Pubkey,
+
+    /// The verified creators of the asset used to apply the creator boosts
+    pub verified_creators: Vec<Pubkey>,
+}
+
+impl RegistrarConfig {
+    /// Returns the weight of an NFT of a collection of the given size normalized according to collection_weight_mode
+    pub fn normalize_weight(&self, weight: u64, collection_size: u32) -> Result<u64, CoreError> {
+        if self.collection_weight_mode != CollectionWeightMode::Normalized || collection_size == 0 {
+            return Ok(weight);
+        }
+
+        let normalized_weight =
+            ((weight as u128) * (self.normalized_collection_weight as u128)) /
+            (collection_size as u128);
+
+        u64::try_from(normalized_weight).map_err(|_| CoreError::CastError)
+    }
+
+    /// Returns the max weight NFTs of a single collection can cast on a Proposal or None if it's not capped
+    pub fn get_max_collection_weight_per_proposal(&self, max_voter_weight: u64) -> Option<u64> {
+        if self.max_collection_weight_per_proposal == 0 {
+            return None;
+        }
+
+        Some(
+            (((max_voter_weight as u128) * (self.max_collection_weight_per_proposal as u128)) /
+                (MAX_BASIS_POINTS as u128)) as u64
+        )
+    }
+}
+
+impl CollectionExtension {
+    /// Returns the weight of an NFT of the collection with the given verified creators
+    /// weight is the weight of the collection from its CollectionConfig
+    pub fn get_weight(&self, weight: u64, verified_creators: &[Pubkey]) -> Result<u64, CoreError> {
+        let boost_bps = self.creator_boosts
+            .iter()
+            .filter(|cb| cb.creator != Pubkey::default() && verified_creators.contains(&cb.creator))
+            .map(|cb| cb.boost_bps)
+            .max()
+            .unwrap_or(0);
+
+        get_boosted_weight(weight, boost_bps)
+    }
+
+    /// Returns the weight of an NFT of the collection with the highest creator boost
+    pub fn get_max_asset_weight(&self, weight: u64) -> Result<u64, CoreError> {
+        let boost_bps = self.creator_boosts
+            .iter()
+            .filter(|cb| cb.creator != Pubkey::default())
+            .map(|cb| cb.boost_bps)
+            .max()
+            .unwrap_or(0);
+
+        get_boosted_weight(weight, boost_bps)
+    }
+}
+
+impl CollectionWeightStrategy {
+    /// Returns the weight the NFT with the given index (0 based) among the NFTs of the collection
+    /// counted for the voter adds to the voter weight
+    pub fn get_asset_weight(&self, asset_weight: u64, asset_index: u32) -> u64 {
+        let is_weighted = match self {
+            CollectionWeightStrategy::Linear => true,
+            CollectionWeightStrategy::Quadratic => isqrt(asset_index + 1) > isqrt(asset_index),
+            CollectionWeightStrategy::Membership => asset_index == 0,
+            CollectionWeightStrategy::Capped { max_assets } => asset_index < (*max_assets as u32),
+        };
+
+        if is_weighted { asset_weight } else { 0 }
+    }
+}
+
+impl Registrar {
+    /// Returns the max voting power of all configured collections, the rolled up sub-DAO Registrars
+    /// and the floor weight of the registered voters
+    /// It's the max_voter_weight the program sets on MaxVoterWeightRecord
+    pub fn get_max_voter_weight(&self, registrar_extension: &RegistrarExtension) -> Result<u64, CoreError> {
+        let registered_voters_max_weight = registrar_extension.registered_voters
+            .checked_mul(registrar_extension.config.min_weight_if_registered_before_slot)
+            .ok_or(CoreError::ArithmeticOverflow)?;
+
+        let collections_max_voter_weight = self.collection_configs
+            .iter()
+            .try_fold(registrar_extension.sub_registrars_max_voter_weight, |sum, cc| {
+                let max_asset_weight = registrar_extension.config.normalize_weight(
+                    registrar_extension.get_collection_extension(&cc.collection).get_max_asset_weight(cc.weight)?,
+                    cc.size
+                )?;
+                let collection_max_weight = (cc.size as u64)
+                    .checked_mul(max_asset_weight)
+                    .ok_or(CoreError::ArithmeticOverflow)?;
+
+                sum.checked_add(collection_max_weight).ok_or(CoreError::ArithmeticOverflow)
+            })?;
+
+        collections_max_voter_weight
+            .checked_add(registered_voters_max_weight)
+            .ok_or(CoreError::ArithmeticOverflow)
+    }
+
+    /// Returns the weight of an NFT of the given collection with the given verified creators
+    /// NFTs of expired collections have no weight
+    pub fn get_nft_weight(
+        &self,
+        registrar_extension: &RegistrarExtension,
+        collection_config: &CollectionConfig,
+        verified_creators: &[Pubkey],
+        unix_timestamp: i64
+    ) -> Result<u64, CoreError> {
+        let collection_extension = registrar_extension.get_collection_extension(&collection_config.collection);
+
+        if collection_extension.is_expired(unix_timestamp) {
+            return Ok(0);
+        }
+
+        registrar_extension.config.normalize_weight(
+            collection_extension.get_weight(collection_config.weight, verified_creators)?,
+            collection_config.size
+        )
+    }
+}
+
+/// Returns the voter weight the given assets produce for a single Proposal vote at the given unix timestamp
+/// The weight is computed the same way as preview_voter_weight using the collection weight mode,
+/// the weight strategies and the collection cap
+/// Note: The voter allowlist, holding period, governing token type and asset uses are not checked
+/// and they can still reject the assets
+pub fn simulate_voter_weight(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
+    assets: &[SimulatedAsset],
+    unix_timestamp: i64
+) -> Result<u64, CoreError> {
+    let max_collection_weight = registrar_extension.config.get_max_collection_weight_per_proposal(
+        registrar.get_max_voter_weight(registrar_extension)?
+    );
+
+    let mut voter_weight: u64 = 0;
+
+    // The number of assets counted and the weight counted for each collection
+    let mut collection_tallies: Vec<(Pubkey, u32, u64)> = vec![];
+
+    for asset in assets.iter() {
+        let collection_config = registrar
+            .find_collection_config(&asset.collection)
+            .ok_or(CoreError::CollectionNotFound)?;
+
+        let asset_weight = registrar.get_nft_weight(
+            registrar_extension,
+            collection_config,
+            &asset.verified_creators,
+            unix_timestamp
+        )?;
+
+        let collection_idx = match collection_tallies.iter().position(|(c, _, _)| *c == asset.collection) {
+            Some(collection_idx) => collection_idx,
+            None => {
+                collection_tallies.push((asset.collection, 0, 0));
+                collection_tallies.len() - 1
+            }
+        };
+
+        let (_, asset_count, collection_weight) = &mut collection_tallies[collection_idx];
+
+        // In UniqueCollections mode each collection counts only once regardless of its own strategy
+        let weight_strategy = if
+            registrar_extension.config.collection_weight_mode == CollectionWeightMode::UniqueCollections
+        {
+            CollectionWeightStrategy::Membership
+        } else {
+            registrar_extension.get_collection_extension(&asset.collection).weight_strategy
+        };
+
+        let asset_weight = weight_strategy.get_asset_weight(asset_weight, *asset_count);
+        *asset_count += 1;
+
+        // Once the collection cap is reached the assets of the collection don't contribute any weight
+        let asset_weight = match max_collection_weight {
+            Some(max_collection_weight) =>
+                asset_weight.min(max_collection_weight.saturating_sub(*collection_weight)),
+            None => asset_weight,
+        };
+
+        *collection_weight = collection_weight
+            .checked_add(asset_weight)
+            .ok_or(CoreError::ArithmeticOverflow)?;
+        voter_weight = voter_weight.checked_add(asset_weight).ok_or(CoreError::ArithmeticOverflow)?;
+    }
+
+    Ok(voter_weight)
+}
+
+fn get_boosted_weight(weight: u64, boost_bps: u16) -> Result<u64, CoreError> {
+    let boost = ((weight as u128) * (boost_bps as u128)) / (MAX_BASIS_POINTS as u128);
+    let boost = u64::try_from(boost).map_err(|_| CoreError::CastError)?;
+
+    weight.checked_add(boost).ok_or(CoreError::ArithmeticOverflow)
+}
+
+/// Returns floor(sqrt(value))
+fn isqrt(value: u32) -> u32 {
+    let mut root = 0u32;
+
+    while ((root + 1) as u64) * ((root + 1) as u64) <= (value as u64) {
+        root += 1;
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ CreatorBoost, MAX_TREE_AUTHORITIES };
+
+    fn create_test_registrar(collection_configs: Vec<CollectionConfig>) -> Registrar {
+        Registrar {
+            governance_program_id: Pubkey::new_unique(),
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            collection_configs,
+            reserved: [0; 128],
+        }
+    }
+
+    fn create_test_registrar_extension() -> RegistrarExtension {
+        RegistrarExtension {
+            registrar: Pubkey::new_unique(),
+            version: 1,
+            config: RegistrarConfig {
+                collection_weight_mode: CollectionWeightMode::PerAsset,
+                max_collection_weight_per_proposal: 0,
+                min_holding_slots: 0,
+                holding_attestation_authority: Pubkey::default(),
+                require_proposal_creation_ticket: false,
+                track_voter_weight_detail: false,
+                tree_authority_allowlist: [Pubkey::default(); MAX_TREE_AUTHORITIES],
+                allow_precomputed_metadata_hashes: false,
+                voter_allowlist_root: [0; 32],
+                rent_reclaim_treasury: Pubkey::default(),
+                rent_reclaim_treasury_share_bps: 0,
+                track_collection_stats: false,
+                parent_registrar: Pubkey::default(),
+                require_same_transaction_consumption: false,
+                normalized_collection_weight: 0,
+                allow_core_assets: false,
+                max_assets_per_tx: 0,
+                require_vote_finalization: false,
+                max_tree_assets_per_slot: 0,
+                proof_attestation_freshness_slots: 0,
+                max_tree_root_age: 0,
+                allow_voter_migration: false,
+                leaf_hash_verifier: Pubkey::default(),
+                weight_record_min_age_slots: 0,
+                record_voter_checkpoints: false,
+                require_weight_action_target: false,
+                weight_export_program: Pubkey::default(),
+                allow_collection_aliases: false,
+                min_weight_if_registered_before_slot: 0,
+                registration_cutoff_slot: 0,
+                bubblegum_program_override: Pubkey::default(),
+                allow_wallet_weight_overrides: false,
+                allow_weight_leases: false,
+                vote_receipt_tree: Pubkey::default(),
+            },
+            sub_registrars_max_voter_weight: 0,
+            legacy_nft_vote_records_cutoff: 0,
+            registered_voters: 0,
+            collection_extensions: vec![],
+            reserved: [0; 128],
+        }
+    }
+
+    fn create_test_collection_config(size: u32, weight: u64) -> CollectionConfig {
+        CollectionConfig {
+            collection: Pubkey::new_unique(),
+            size,
+            weight,
+            reserved: [0; 8],
+        }
+    }
+
+    fn create_test_collection_extension(collection: &Pubkey) -> CollectionExtension {
+        CollectionExtension {
+            collection: *collection,
+            ..Default::default()
+        }
+    }
+
+    fn create_test_assets(collection: &Pubkey, asset_count: usize) -> Vec<SimulatedAsset> {
+        vec![
+            SimulatedAsset {
+                collection: *collection,
+                verified_creators: vec![],
+            };
+            asset_count
+        ]
+    }
+
+    #[test]
+    fn test_simulate_voter_weight() {
+        // Arrange
+        let creator = Pubkey::new_unique();
+        let collection_config = create_test_collection_config(10, 4);
+        let registrar = create_test_registrar(vec![collection_config]);
+
+        let mut collection_extension = create_test_collection_extension(&collection_config.collection);
+        collection_extension.creator_boosts[0] = CreatorBoost {
+            creator,
+            boost_bps: 5_000,
+        };
+
+        let mut registrar_extension = create_test_registrar_extension();
+        registrar_extension.collection_extensions.push(collection_extension);
+
+        let mut assets = create_test_assets(&collection_config.collection, 2);
+        assets[1].verified_creators.push(creator);
+
+        // Act
+        let voter_weight = simulate_voter_weight(&registrar, &registrar_extension, &assets, 0).unwrap();
+
+        // Assert
+        assert_eq!(voter_weight, 4 + 6);
+        assert_eq!(registrar.get_max_voter_weight(&registrar_extension).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_simulate_voter_weight_with_unique_collections() {
+        // Arrange
+        let collection_config = create_test_collection_config(10, 4);
+        let registrar = create_test_registrar(vec![collection_config]);
+        let mut registrar_extension = create_test_registrar_extension();
+        registrar_extension.config.collection_weight_mode = CollectionWeightMode::UniqueCollections;
+
+        let assets = create_test_assets(&collection_config.collection, 3);
+
+        // Act
+        let voter_weight = simulate_voter_weight(&registrar, &registrar_extension, &assets, 0).unwrap();
+
+        // Assert
+        assert_eq!(voter_weight, 4);
+    }
+
+    #[test]
+    fn test_simulate_voter_weight_with_weight_strategies() {
+        // Arrange
+        let quadratic_collection_config = create_test_collection_config(10, 3);
+        let capped_collection_config = create_test_collection_config(10, 2);
+
+        let registrar = create_test_registrar(
+            vec![quadratic_collection_config, capped_collection_config]
+        );
+
+        let mut quadratic_collection_extension =
+            create_test_collection_extension(&quadratic_collection_config.collection);
+        quadratic_collection_extension.weight_strategy = CollectionWeightStrategy::Quadratic;
+
+        let mut capped_collection_extension =
+            create_test_collection_extension(&capped_collection_config.collection);
+        capped_collection_extension.weight_strategy = CollectionWeightStrategy::Capped { max_assets: 2 };
+
+        let mut registrar_extension = create_test_registrar_extension();
+        registrar_extension.collection_extensions = vec![
+            quadratic_collection_extension,
+            capped_collection_extension
+        ];
+
+        let mut assets = create_test_assets(&quadratic_collection_config.collection, 4);
+        assets.extend(create_test_assets(&capped_collection_config.collection, 5));
+
+        // Act
+        let voter_weight = simulate_voter_weight(&registrar, &registrar_extension, &assets, 0).unwrap();
+
+        // Assert
+        assert_eq!(voter_weight, 6 + 4);
+    }
+
+    #[test]
+    fn test_simulate_voter_weight_with_collection_cap() {
+        // Arrange
+        let collection_config = create_test_collection_config(10, 4);
+        let registrar = create_test_registrar(vec![collection_config]);
+        let mut registrar_extension = create_test_registrar_extension();
+        registrar_extension.config.max_collection_weight_per_proposal = 1_000;
+
+        let assets = create_test_assets(&collection_config.collection, 3);
+
+        // Act
+        let voter_weight = simulate_voter_weight(&registrar, &registrar_extension, &assets, 0).unwrap();
+
+        // Assert
+        // 10% of the max voter weight 40
+        assert_eq!(voter_weight, 4);
+    }
+
+    #[test]
+    fn test_simulate_voter_weight_with_expired_collection() {
+        // Arrange
+        let collection_config = create_test_collection_config(10, 4);
+        let registrar = create_test_registrar(vec![collection_config]);
+
+        let mut collection_extension = create_test_collection_extension(&collection_config.collection);
+        collection_extension.expires_at = 100;
+
+        let mut registrar_extension = create_test_registrar_extension();
+        registrar_extension.collection_extensions.push(collection_extension);
+
+        let assets = create_test_assets(&collection_config.collection, 2);
+
+        // Act
+        let voter_weight = simulate_voter_weight(&registrar, &registrar_extension, &assets, 100).unwrap();
+
+        // Assert
+        assert_eq!(voter_weight, 0);
+    }
+
+    #[test]
+    fn test_simulate_voter_weight_with_collection_not_found_error() {
+        // Arrange
+        let registrar = create_test_registrar(vec![create_test_collection_config(10, 4)]);
+        let assets = create_test_assets(&Pubkey::new_unique(), 1);
+
+        // Act
+        let err = simulate_voter_weight(
+            &registrar,
+            &create_test_registrar_extension(),
+            &assets,
+            0
+        ).err().unwrap();
+
+        // Assert
+        assert_eq!(err, CoreError::CollectionNotFound);
+    }
+
+    #[test]
+    fn test_get_max_voter_weight_with_overflow_error() {
+        // Arrange
+        let mut registrar = create_test_registrar(vec![create_test_collection_config(2, u64::MAX)]);
+        let mut registrar_extension = create_test_registrar_extension();
+
+        // Act
+        let err = registrar.get_max_voter_weight(&registrar_extension).err().unwrap();
+
+        registrar.collection_configs[0].weight = 1;
+        registrar_extension.config.collection_weight_mode = CollectionWeightMode::Normalized;
+        registrar_extension.config.normalized_collection_weight = 100;
+
+        // Assert
+        assert_eq!(err, CoreError::ArithmeticOverflow);
+        assert_eq!(registrar.get_max_voter_weight(&registrar_extension).unwrap(), 100);
+    }
+}
@@ -0,0 +1,11 @@
+//! Client helpers of the NFT voter plugin
+//!
+//! The helpers build ready-to-sign transactions of the plugin instructions from the voter's assets,
+//! estimate the rent of the accounts the instructions create
+//! and depend on the program crate only for the account and instruction layouts
+
+pub mod rent;
+pub mod vote_packer;
+
+pub use rent::*;
+pub use vote_packer::*;
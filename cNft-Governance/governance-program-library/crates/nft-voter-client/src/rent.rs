@@ -0,0 +1,68 @@
+//! Estimates the rent of the plugin accounts created by the clients
+//!
+//! The sizes are taken from the same const functions the program uses in its `space` constraints
+//! so the estimates can't drift from the accounts created on-chain
+
+use gpl_nft_voter::state::*;
+use solana_sdk::rent::Rent;
+
+/// Returns the lamports create_registrar takes from the payer for the Registrar and RegistrarExtension
+/// with capacity for the given number of collections
+pub fn get_registrar_rent(rent: &Rent, max_collections: u8) -> u64 {
+    rent.minimum_balance(Registrar::space_for(max_collections)) +
+        rent.minimum_balance(RegistrarExtension::space_for(max_collections))
+}
+
+/// Returns the lamports create_registrar_template takes from the payer for the RegistrarTemplate
+pub fn get_registrar_template_rent(rent: &Rent, max_collections: u8) -> u64 {
+    rent.minimum_balance(RegistrarTemplate::space_for(max_collections))
+}
+
+/// Returns the lamports create_voter_weight_detail takes from the payer for the VoterWeightDetail
+pub fn get_voter_weight_detail_rent(rent: &Rent, max_collections: u8) -> u64 {
+    rent.minimum_balance(VoterWeightDetail::space_for(max_collections))
+}
+
+/// Returns the lamports create_cnft_action_ticket takes from the payer for the tickets of the given number of assets
+/// The rent is returned to the payer once the tickets are consumed or expire
+pub fn get_nft_action_tickets_rent(rent: &Rent, assets_count: usize) -> u64 {
+    rent.minimum_balance(NFT_ACTION_TICKET_SIZE) * (assets_count as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_registrar_rent_grows_with_max_collections() {
+        // Arrange
+        let rent = Rent::default();
+
+        // Act
+        let rent_for_one = get_registrar_rent(&rent, 1);
+        let rent_for_ten = get_registrar_rent(&rent, 10);
+
+        // Assert
+        let collection_size =
+            (Registrar::space_for(2) - Registrar::space_for(1)) +
+            (RegistrarExtension::space_for(2) - RegistrarExtension::space_for(1));
+
+        assert_eq!(
+            rent_for_ten - rent_for_one,
+            rent.minimum_balance(9 * collection_size) - rent.minimum_balance(0)
+        );
+    }
+
+    #[test]
+    fn test_get_nft_action_tickets_rent() {
+        // Arrange
+        let rent = Rent::default();
+
+        // Act
+        let tickets_rent = get_nft_action_tickets_rent(&rent, 3);
+
+        // Assert
+        assert_eq!(tickets_rent, 3 * rent.minimum_balance(NFT_ACTION_TICKET_SIZE));
+        assert_eq!(get_nft_action_tickets_rent(&rent, 0), 0);
+    }
+}
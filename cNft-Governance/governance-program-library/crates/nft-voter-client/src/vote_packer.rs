@@ -0,0 +1,1034 @@
+//! Packs the votes of voters holding many compressed NFTs into the minimum number of transactions
+//!
+//! A vote with many assets is cast using the continuation flow of the program:
+//! create_cnft_action_ticket transactions verify the assets and create the tickets,
+//! cast_nft_vote transactions accumulate the weight of the tickets and the last transaction
+//! bundles finalize_nft_vote with spl-gov CastVote
+//!
+//! The assets are added to each transaction until it would exceed the packet size or the compute unit limit.
+//! When the assets share accounts across transactions (ex. trees and upper proof nodes) an address lookup table
+//! is created for them if it saves more transactions than it takes to create it
+
+use std::{ collections::HashMap, fmt };
+
+use anchor_lang::{ InstructionData, ToAccountMetas };
+use ::cnft_verification::{ get_asset_id, get_asset_id_with_program };
+use gpl_nft_voter::state::*;
+use solana_address_lookup_table_program::instruction::{ create_lookup_table, extend_lookup_table };
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    clock::Slot,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{ AccountMeta, Instruction },
+    message::{ v0, CompileError, VersionedMessage },
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+
+/// The max number of addresses of an address lookup table
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// The number of addresses added to the lookup table by a single extend_lookup_table transaction
+pub const LOOKUP_TABLE_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// Compressed NFT of the voter with its merkle proof
+#[derive(Clone, Debug)]
+pub struct VoteAsset {
+    /// The merkle tree of the asset
+    pub tree: Pubkey,
+
+    /// The asset supplied to create_cnft_action_ticket
+    /// proof_len must be the number of the proof accounts
+    pub params: CompressedNftAsset,
+
+    /// The proof nodes supplied as accounts after the tree (without the canopy nodes)
+    /// It's empty when the proof is supplied as CompressedNftAsset.proof_nodes
+    pub proof: Vec<Pubkey>,
+
+    /// Whether the weight of the asset held by another wallet is rented to the voter (WeightLease)
+    /// instead of delegated by a ColdWalletLink
+    pub weight_lease: bool,
+}
+
+impl VoteAsset {
+    pub fn get_asset_id(&self) -> Pubkey {
+        get_asset_id(&self.tree, self.params.nonce)
+    }
+
+    fn get_proof_nodes_count(&self) -> usize {
+        self.proof.len() + self.params.proof_nodes.as_ref().map_or(0, |nodes| nodes.len())
+    }
+}
+
+/// Accounts and arguments of the vote
+#[derive(Clone, Debug)]
+pub struct VoteContext {
+    pub registrar_address: Pubkey,
+
+    /// The Registrar account
+    pub registrar: Registrar,
+
+    /// The RegistrarExtension of the Registrar, its config selects the optional accounts of the instructions
+    pub registrar_extension: RegistrarExtension,
+
+    pub voter_weight_record: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_token_owner_record: Pubkey,
+
+    /// The governing_token_owner or its delegate
+    pub voter_authority: Pubkey,
+
+    /// The fee payer and the authority of the address lookup table
+    pub payer: Pubkey,
+
+    pub governance: Pubkey,
+    pub proposal: Pubkey,
+
+    /// The Proposal.voting_at timestamp, it selects whether the legacy NftVoteRecords are supplied to cast_nft_vote
+    pub proposal_voting_at: Option<i64>,
+
+    pub vote_kind: NftVoteKind,
+
+    /// Proof of the voter in RegistrarExtension.config.voter_allowlist_root, empty if the allowlist isn't enabled
+    pub voter_allowlist_proof: Vec<[u8; 32]>,
+
+    /// Instructions executed after finalize_nft_vote in the last transaction (ex. spl-gov CastVote)
+    pub cast_vote_instructions: Vec<Instruction>,
+
+    pub recent_blockhash: Hash,
+
+    /// Recent slot the address lookup table is derived with
+    pub recent_slot: Slot,
+}
+
+/// Compute unit estimates used to pack the instructions
+/// The defaults are conservative and can be tuned with the compute units measured by the proof_encoding program tests
+#[derive(Clone, Debug, PartialEq)]
+pub struct VotePackerConfig {
+    /// The compute unit limit of a transaction
+    pub compute_unit_limit: u32,
+
+    /// Compute units of an instruction without any assets
+    pub instruction_compute_units: u32,
+
+    /// Compute units of verifying an asset and creating its ticket without the proof
+    pub ticket_compute_units: u32,
+
+    /// Compute units of each proof node of an asset
+    pub proof_node_compute_units: u32,
+
+    /// Compute units of counting a ticket and creating its NftVoteRecord
+    pub vote_compute_units: u32,
+
+    /// Whether an address lookup table can be created when it saves transactions
+    pub allow_lookup_table: bool,
+}
+
+impl Default for VotePackerConfig {
+    fn default() -> Self {
+        Self {
+            compute_unit_limit: 1_400_000,
+            instruction_compute_units: 20_000,
+            ticket_compute_units: 45_000,
+            proof_node_compute_units: 1_000,
+            vote_compute_units: 25_000,
+            allow_lookup_table: true,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VotePackerError {
+    /// No assets were given
+    NoAssets,
+
+    /// The asset requires an ed25519 attestation (holding period or leaf hash) which can't be packed
+    UnsupportedAsset(Pubkey),
+
+    /// The asset doesn't fit into a transaction on its own
+    AssetTooLarge(Pubkey),
+
+    /// The Registrar weight isn't accumulative and the assets don't fit into a single cast_nft_vote transaction
+    VoteCannotBeSplit,
+
+    /// The message can't be compiled
+    Compile(CompileError),
+}
+
+impl fmt::Display for VotePackerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VotePackerError::NoAssets => write!(f, "No assets to vote with"),
+            VotePackerError::UnsupportedAsset(asset_id) =>
+                write!(f, "Asset {} requires an ed25519 attestation", asset_id),
+            VotePackerError::AssetTooLarge(asset_id) =>
+                write!(f, "Asset {} doesn't fit into a transaction", asset_id),
+            VotePackerError::VoteCannotBeSplit =>
+                write!(f, "Registrar weight isn't accumulative and the vote can't be split"),
+            VotePackerError::Compile(err) => write!(f, "Can't compile message: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for VotePackerError {}
+
+impl From<CompileError> for VotePackerError {
+    fn from(err: CompileError) -> Self {
+        VotePackerError::Compile(err)
+    }
+}
+
+/// Message of the packed vote with the assets it covers
+#[derive(Clone, Debug)]
+pub struct PackedMessage {
+    pub message: VersionedMessage,
+    pub asset_ids: Vec<Pubkey>,
+}
+
+/// Messages of the vote in the execution order
+/// The messages are signed by the payer and the voter_authority with VersionedTransaction::try_new
+#[derive(Clone, Debug)]
+pub struct PackedVote {
+    /// The address lookup table used by the other messages
+    pub lookup_table: Option<AddressLookupTableAccount>,
+
+    /// Create and extend the lookup table
+    /// They must be confirmed before the other messages are sent and the addresses
+    /// can only be used from the slot following the last extension
+    pub lookup_table_messages: Vec<VersionedMessage>,
+
+    /// create_cnft_action_ticket messages which can be sent in parallel
+    pub create_ticket_messages: Vec<PackedMessage>,
+
+    /// cast_nft_vote messages sent once all the tickets are created
+    pub cast_vote_messages: Vec<PackedMessage>,
+
+    /// finalize_nft_vote followed by VoteContext.cast_vote_instructions sent last
+    pub finalize_message: VersionedMessage,
+}
+
+impl PackedVote {
+    pub fn get_transactions_count(&self) -> usize {
+        self.lookup_table_messages.len() +
+            self.create_ticket_messages.len() +
+            self.cast_vote_messages.len() +
+            1
+    }
+}
+
+/// Packs the vote of the given assets into the minimum number of transactions
+/// The assets are sorted by tree and leaf index to share the trees and the upper proof nodes
+/// between the assets of the same transaction and each transaction is filled greedily
+pub fn pack_vote(
+    context: &VoteContext,
+    assets: &[VoteAsset],
+    config: &VotePackerConfig
+) -> Result<PackedVote, VotePackerError> {
+    if assets.is_empty() {
+        return Err(VotePackerError::NoAssets);
+    }
+
+    for asset in assets {
+        if context.registrar_extension.config.min_holding_slots > 0 || asset.params.leaf_hash.is_some() {
+            return Err(VotePackerError::UnsupportedAsset(asset.get_asset_id()));
+        }
+    }
+
+    let mut assets: Vec<&VoteAsset> = assets.iter().collect();
+    assets.sort_by_key(|asset| (asset.tree, asset.params.index));
+
+    let without_lookup_table = pack_vote_messages(context, &assets, config, None)?;
+
+    if !config.allow_lookup_table {
+        return Ok(without_lookup_table);
+    }
+
+    let (lookup_table, lookup_table_messages) = get_lookup_table(context, &assets)?;
+    let with_lookup_table = pack_vote_messages(context, &assets, config, Some(lookup_table))?;
+
+    if
+        lookup_table_messages.len() + with_lookup_table.get_transactions_count() <
+        without_lookup_table.get_transactions_count()
+    {
+        Ok(PackedVote {
+            lookup_table_messages,
+            ..with_lookup_table
+        })
+    } else {
+        Ok(without_lookup_table)
+    }
+}
+
+fn pack_vote_messages(
+    context: &VoteContext,
+    assets: &[&VoteAsset],
+    config: &VotePackerConfig,
+    lookup_table: Option<AddressLookupTableAccount>
+) -> Result<PackedVote, VotePackerError> {
+    let lookup_tables: Vec<AddressLookupTableAccount> = lookup_table.iter().cloned().collect();
+
+    let create_ticket_messages = pack_messages(
+        context,
+        assets,
+        &lookup_tables,
+        None,
+        |assets| vec![get_create_cnft_action_ticket_ix(context, assets)],
+        |assets| {
+            assets.iter().fold(config.instruction_compute_units, |units, asset| {
+                units +
+                    config.ticket_compute_units +
+                    config.proof_node_compute_units * (asset.get_proof_nodes_count() as u32)
+            })
+        },
+        config
+    )?;
+
+    let max_assets_per_tx = context.registrar_extension.config.max_assets_per_tx as usize;
+
+    let cast_vote_messages = pack_messages(
+        context,
+        assets,
+        &lookup_tables,
+        if max_assets_per_tx > 0 { Some(max_assets_per_tx) } else { None },
+        |assets| vec![get_cast_nft_vote_ix(context, assets)],
+        |assets| config.instruction_compute_units + config.vote_compute_units * (assets.len() as u32),
+        config
+    )?;
+
+    // The weight of a vote cast by several instructions must be accumulative
+    if cast_vote_messages.len() > 1 && !context.registrar.is_voter_weight_accumulative(&context.registrar_extension) {
+        return Err(VotePackerError::VoteCannotBeSplit);
+    }
+
+    let mut finalize_instructions = vec![get_finalize_nft_vote_ix(context)];
+    finalize_instructions.extend(context.cast_vote_instructions.iter().cloned());
+
+    let finalize_message = compile_message(context, &finalize_instructions, &lookup_tables)?;
+
+    Ok(PackedVote {
+        lookup_table,
+        lookup_table_messages: vec![],
+        create_ticket_messages,
+        cast_vote_messages,
+        finalize_message,
+    })
+}
+
+/// Splits the assets into messages adding the assets to each message while it fits into a transaction
+fn pack_messages<B, C>(
+    context: &VoteContext,
+    assets: &[&VoteAsset],
+    lookup_tables: &[AddressLookupTableAccount],
+    max_assets_per_message: Option<usize>,
+    build_instructions: B,
+    estimate_compute_units: C,
+    config: &VotePackerConfig
+)
+    -> Result<Vec<PackedMessage>, VotePackerError>
+    where B: Fn(&[&VoteAsset]) -> Vec<Instruction>, C: Fn(&[&VoteAsset]) -> u32
+{
+    let mut messages = vec![];
+    let mut start = 0;
+
+    while start < assets.len() {
+        let mut packed: Option<(usize, VersionedMessage)> = None;
+        let max_end = max_assets_per_message.map_or(assets.len(), |max| {
+            (start + max).min(assets.len())
+        });
+
+        for end in start + 1..=max_end {
+            let compute_units = estimate_compute_units(&assets[start..end]);
+
+            if compute_units > config.compute_unit_limit {
+                break;
+            }
+
+            let mut instructions = vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_units)
+            ];
+            instructions.extend(build_instructions(&assets[start..end]));
+
+            let message = compile_message(context, &instructions, lookup_tables)?;
+
+            if get_transaction_size(&message) > PACKET_DATA_SIZE {
+                break;
+            }
+
+            packed = Some((end, message));
+        }
+
+        let (end, message) = packed.ok_or_else(||
+            VotePackerError::AssetTooLarge(assets[start].get_asset_id())
+        )?;
+
+        messages.push(PackedMessage {
+            message,
+            asset_ids: assets[start..end]
+                .iter()
+                .map(|asset| asset.get_asset_id())
+                .collect(),
+        });
+
+        start = end;
+    }
+
+    Ok(messages)
+}
+
+fn compile_message(
+    context: &VoteContext,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount]
+) -> Result<VersionedMessage, VotePackerError> {
+    Ok(
+        VersionedMessage::V0(
+            v0::Message::try_compile(
+                &context.payer,
+                instructions,
+                lookup_tables,
+                context.recent_blockhash
+            )?
+        )
+    )
+}
+
+/// Returns the size of the signed transaction of the message
+pub fn get_transaction_size(message: &VersionedMessage) -> usize {
+    let signatures_count = message.header().num_required_signatures as usize;
+
+    // compact-u16 length of the signatures (< 128) followed by the signatures and the message
+    1 + signatures_count * 64 + message.serialize().len()
+}
+
+/// Returns the lookup table of the accounts used by the vote instructions and the messages creating it
+/// The accounts used the most are kept when there are more than LOOKUP_TABLE_MAX_ADDRESSES
+fn get_lookup_table(
+    context: &VoteContext,
+    assets: &[&VoteAsset]
+) -> Result<(AddressLookupTableAccount, Vec<VersionedMessage>), VotePackerError> {
+    let signers = [context.payer, context.voter_authority];
+    let mut usage: HashMap<Pubkey, usize> = HashMap::new();
+
+    let instructions = assets
+        .iter()
+        .flat_map(|asset| {
+            vec![
+                get_create_cnft_action_ticket_ix(context, &[asset]),
+                get_cast_nft_vote_ix(context, &[asset])
+            ]
+        })
+        .chain(std::iter::once(get_finalize_nft_vote_ix(context)));
+
+    for instruction in instructions {
+        for account in instruction.accounts.iter() {
+            if !signers.contains(&account.pubkey) {
+                *usage.entry(account.pubkey).or_default() += 1;
+            }
+        }
+    }
+
+    let mut addresses: Vec<(Pubkey, usize)> = usage.into_iter().collect();
+    addresses.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    addresses.truncate(LOOKUP_TABLE_MAX_ADDRESSES);
+
+    let addresses: Vec<Pubkey> = addresses
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    let (create_lookup_table_ix, lookup_table_address) = create_lookup_table(
+        context.payer,
+        context.payer,
+        context.recent_slot
+    );
+
+    let mut messages = vec![];
+
+    for (i, chunk) in addresses.chunks(LOOKUP_TABLE_ADDRESSES_PER_EXTEND).enumerate() {
+        let extend_lookup_table_ix = extend_lookup_table(
+            lookup_table_address,
+            context.payer,
+            Some(context.payer),
+            chunk.to_vec()
+        );
+
+        let instructions = if i == 0 {
+            vec![create_lookup_table_ix.clone(), extend_lookup_table_ix]
+        } else {
+            vec![extend_lookup_table_ix]
+        };
+
+        messages.push(compile_message(context, &instructions, &[])?);
+    }
+
+    Ok((
+        AddressLookupTableAccount {
+            key: lookup_table_address,
+            addresses,
+        },
+        messages,
+    ))
+}
+
+/// Returns create_cnft_action_ticket instruction creating the CastVote tickets of the assets
+/// The optional accounts of each asset are supplied in the order required by the instruction
+pub fn get_create_cnft_action_ticket_ix(context: &VoteContext, assets: &[&VoteAsset]) -> Instruction {
+    let registrar_config = &context.registrar_extension.config;
+    let registrar = &context.registrar_address;
+    let governing_token_owner = &context.governing_token_owner;
+    let voter_weight_action = VoterWeightAction::CastVote;
+    let ticket_type = format!("nft-{}-ticket", voter_weight_action);
+
+    let data = (gpl_nft_voter::instruction::CreateCnftActionTicket {
+        voter_weight_action,
+        params: assets
+            .iter()
+            .map(|asset| asset.params.clone())
+            .collect(),
+        voter_allowlist_proof: context.voter_allowlist_proof.clone(),
+    }).data();
+
+    let accounts = gpl_nft_voter::accounts::CreateCnftActionTicket {
+        voter: gpl_nft_voter::accounts::VoterAccounts {
+            registrar: *registrar,
+            registrar_extension: get_registrar_extension_address(registrar),
+            voter_weight_record: context.voter_weight_record,
+            voter_authority: context.voter_authority,
+        },
+        payer: context.payer,
+        compression_program: spl_account_compression::id(),
+        system_program: system_program::id(),
+        instructions: sysvar::instructions::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+
+    if registrar_config.allow_voter_migration && registrar_config.min_holding_slots > 0 {
+        account_metas.push(
+            AccountMeta::new_readonly(
+                get_voter_migration_address(registrar, governing_token_owner),
+                false
+            )
+        );
+    }
+
+    for asset in assets {
+        let asset_id = get_asset_id_with_program(
+            &asset.tree,
+            asset.params.nonce,
+            &registrar_config.get_bubblegum_program_id()
+        );
+
+        account_metas.push(AccountMeta::new_readonly(asset.tree, false));
+        account_metas.extend(
+            asset.proof.iter().map(|node| AccountMeta::new_readonly(*node, false))
+        );
+        account_metas.push(
+            AccountMeta::new(
+                get_nft_action_ticket_address(
+                    &ticket_type,
+                    registrar,
+                    governing_token_owner,
+                    &asset_id
+                ).0,
+                false
+            )
+        );
+
+        if asset.params.uses.is_some() {
+            account_metas.push(AccountMeta::new(get_asset_uses_record_address(&asset_id), false));
+        }
+
+        if registrar_config.max_tree_assets_per_slot > 0 {
+            account_metas.push(
+                AccountMeta::new(
+                    get_tree_rate_limit_record_address(registrar, &asset.tree, governing_token_owner),
+                    false
+                )
+            );
+        }
+
+        if registrar_config.is_proof_attestation_enabled() {
+            account_metas.push(
+                AccountMeta::new(get_proof_attestation_address(registrar, &asset_id), false)
+            );
+        }
+
+        if asset.params.leaf_owner != *governing_token_owner {
+            let delegation = if asset.weight_lease {
+                get_weight_lease_address(registrar, &asset_id, &asset.params.leaf_owner)
+            } else {
+                get_cold_wallet_link_address(registrar, &asset.params.leaf_owner)
+            };
+
+            account_metas.push(AccountMeta::new_readonly(delegation, false));
+        }
+
+        // The owner can't use the asset while its weight is rented out
+        if registrar_config.allow_weight_leases && !asset.weight_lease {
+            account_metas.push(
+                AccountMeta::new_readonly(
+                    get_weight_lease_address(registrar, &asset_id, &asset.params.leaf_owner),
+                    false
+                )
+            );
+        }
+
+        if context.registrar.uses_collection_alias(&context.registrar_extension, &asset.params) {
+            account_metas.push(
+                AccountMeta::new_readonly(
+                    get_collection_alias_address(
+                        registrar,
+                        &asset.params.collection.as_ref().unwrap().key
+                    ),
+                    false
+                )
+            );
+        }
+
+        if registrar_config.is_tree_authority_allowlist_enabled() {
+            account_metas.push(
+                AccountMeta::new_readonly(
+                    Pubkey::find_program_address(
+                        &[asset.tree.as_ref()],
+                        &registrar_config.get_bubblegum_program_id()
+                    ).0,
+                    false
+                )
+            );
+        }
+    }
+
+    Instruction {
+        program_id: gpl_nft_voter::id(),
+        accounts: account_metas,
+        data,
+    }
+}
+
+/// Returns cast_nft_vote instruction counting the CastVote tickets of the assets
+pub fn get_cast_nft_vote_ix(context: &VoteContext, assets: &[&VoteAsset]) -> Instruction {
+    let registrar = &context.registrar_address;
+    let registrar_config = &context.registrar_extension.config;
+    let ticket_type = format!("nft-{}-ticket", VoterWeightAction::CastVote);
+
+    let data = (gpl_nft_voter::instruction::CastNftVote {
+        proposal: context.proposal,
+        simulate_only: false,
+        memo: None,
+        use_voter_rent_escrow: false,
+        use_voting_session: false,
+        vote_kind: context.vote_kind,
+        use_voter_group: false,
+    }).data();
+
+    let accounts = gpl_nft_voter::accounts::CastNftVote {
+        registrar: *registrar,
+        registrar_extension: get_registrar_extension_address(registrar),
+        voter_weight_record: context.voter_weight_record,
+        voter_token_owner_record: context.voter_token_owner_record,
+        voter_authority: context.voter_authority,
+        payer: context.payer,
+        system_program: system_program::id(),
+        voter_freeze_record: get_voter_freeze_record_address(
+            registrar,
+            &context.governing_token_owner
+        ),
+        governance: context.governance,
+        target_proposal: context.proposal,
+        registrar_metrics: Some(
+            get_registrar_metrics_address(registrar, &context.governing_token_owner)
+        ),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+
+    if registrar_config.track_voter_weight_detail {
+        account_metas.push(
+            AccountMeta::new(get_voter_weight_detail_address(&context.voter_weight_record), false)
+        );
+    }
+
+    if registrar_config.record_voter_checkpoints {
+        account_metas.push(
+            AccountMeta::new(
+                get_voter_checkpoint_address(
+                    registrar,
+                    &context.proposal,
+                    &context.governing_token_owner
+                ),
+                false
+            )
+        );
+    }
+
+    // The CollectionVoteWeightRecords are supplied when the collection weight per Proposal is capped
+    let caps_collection_weight = registrar_config.max_collection_weight_per_proposal > 0;
+
+    // The legacy NftVoteRecords are supplied when the Proposal could be voted on before the Registrar was migrated
+    let has_legacy_nft_vote_records = context.registrar_extension.has_legacy_nft_vote_records(
+        context.proposal_voting_at
+    );
+
+    for asset in assets {
+        let asset_id = get_asset_id_with_program(
+            &asset.tree,
+            asset.params.nonce,
+            &registrar_config.get_bubblegum_program_id()
+        );
+        let collection = asset.params.collection.as_ref().map_or(Pubkey::default(), |c| c.key);
+
+        account_metas.push(
+            AccountMeta::new(
+                get_nft_action_ticket_address(
+                    &ticket_type,
+                    registrar,
+                    &context.governing_token_owner,
+                    &asset_id
+                ).0,
+                false
+            )
+        );
+        account_metas.push(
+            AccountMeta::new(
+                get_nft_vote_record_address(registrar, &context.proposal, &asset_id),
+                false
+            )
+        );
+
+        if has_legacy_nft_vote_records {
+            account_metas.push(
+                AccountMeta::new_readonly(
+                    get_legacy_nft_vote_record_address(&context.proposal, &asset_id),
+                    false
+                )
+            );
+        }
+
+        if caps_collection_weight {
+            account_metas.push(
+                AccountMeta::new(
+                    get_collection_vote_weight_record_address(registrar, &context.proposal, &collection),
+                    false
+                )
+            );
+        }
+
+        if registrar_config.track_collection_stats {
+            account_metas.push(
+                AccountMeta::new(get_collection_stats_address(registrar, &collection), false)
+            );
+        }
+    }
+
+    Instruction {
+        program_id: gpl_nft_voter::id(),
+        accounts: account_metas,
+        data,
+    }
+}
+
+/// Returns finalize_nft_vote instruction making the accumulated weight valid for spl-gov CastVote
+pub fn get_finalize_nft_vote_ix(context: &VoteContext) -> Instruction {
+    let data = (gpl_nft_voter::instruction::FinalizeNftVote {
+        proposal: context.proposal,
+    }).data();
+
+    let accounts = gpl_nft_voter::accounts::FinalizeNftVote {
+        registrar: context.registrar_address,
+        registrar_extension: get_registrar_extension_address(&context.registrar_address),
+        voter_weight_record: context.voter_weight_record,
+        voter_token_owner_record: context.voter_token_owner_record,
+        voter_authority: context.voter_authority,
+        voter_freeze_record: get_voter_freeze_record_address(
+            &context.registrar_address,
+            &context.governing_token_owner
+        ),
+        governance: context.governance,
+        target_proposal: context.proposal,
+        instructions: sysvar::instructions::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+
+    // The floor weight of the voter is applied to the finalized weight
+    if context.registrar_extension.config.is_min_voter_weight_enabled() {
+        account_metas.push(
+            AccountMeta::new_readonly(
+                get_voter_registration_address(
+                    &context.registrar_address,
+                    &context.governing_token_owner
+                ),
+                false
+            )
+        );
+    }
+
+    // The wallet override set by the realm is applied to the finalized weight
+    if context.registrar_extension.config.allow_wallet_weight_overrides {
+        account_metas.push(
+            AccountMeta::new_readonly(
+                get_wallet_weight_override_address(
+                    &context.registrar_address,
+                    &context.governing_token_owner
+                ),
+                false
+            )
+        );
+    }
+
+    Instruction {
+        program_id: gpl_nft_voter::id(),
+        accounts: account_metas,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_context() -> VoteContext {
+        let voter = Pubkey::new_unique();
+
+        let registrar_address = Pubkey::new_unique();
+
+        VoteContext {
+            registrar_address,
+            registrar: Registrar {
+                governance_program_id: Pubkey::new_unique(),
+                realm: Pubkey::new_unique(),
+                governing_token_mint: Pubkey::new_unique(),
+                collection_configs: vec![],
+                reserved: [0; 128],
+            },
+            registrar_extension: RegistrarExtension::new(registrar_address),
+            voter_weight_record: Pubkey::new_unique(),
+            governing_token_owner: voter,
+            voter_token_owner_record: Pubkey::new_unique(),
+            voter_authority: voter,
+            payer: Pubkey::new_unique(),
+            governance: Pubkey::new_unique(),
+            proposal: Pubkey::new_unique(),
+            proposal_voting_at: Some(1_000),
+            vote_kind: NftVoteKind::Approve,
+            voter_allowlist_proof: vec![],
+            cast_vote_instructions: vec![],
+            recent_blockhash: Hash::default(),
+            recent_slot: 100,
+        }
+    }
+
+    fn create_test_asset(context: &VoteContext, tree: Pubkey, index: u32, proof: Vec<Pubkey>) -> VoteAsset {
+        VoteAsset {
+            tree,
+            params: CompressedNftAsset {
+                name: "cNFT".to_string(),
+                symbol: "CNFT".to_string(),
+                uri: "https://example.com/cnft.json".to_string(),
+                collection: Some(Collection {
+                    verified: true,
+                    key: Pubkey::new_unique(),
+                }),
+                seller_fee_basis_points: 0,
+                primary_sale_happened: false,
+                is_mutable: true,
+                edition_nonce: None,
+                creators: vec![],
+                uses: None,
+                root: [1; 32],
+                leaf_owner: context.governing_token_owner,
+                leaf_delegate: context.governing_token_owner,
+                index,
+                nonce: index as u64,
+                proof_len: proof.len() as u8,
+                last_transfer_slot: None,
+                data_hash: None,
+                creator_hash: None,
+                proof_nodes: None,
+                use_proof_attestation: false,
+                leaf_schema: LeafSchema::V1,
+                leaf_hash: None,
+            },
+            proof,
+            weight_lease: false,
+        }
+    }
+
+    fn create_test_assets(context: &VoteContext, count: u32, proof_len: usize) -> Vec<VoteAsset> {
+        let tree = Pubkey::new_unique();
+
+        (0..count)
+            .map(|index| {
+                let proof = (0..proof_len).map(|_| Pubkey::new_unique()).collect();
+                create_test_asset(context, tree, index, proof)
+            })
+            .collect()
+    }
+
+    fn assert_messages_fit(packed_vote: &PackedVote) {
+        let messages = packed_vote.lookup_table_messages
+            .iter()
+            .chain(packed_vote.create_ticket_messages.iter().map(|m| &m.message))
+            .chain(packed_vote.cast_vote_messages.iter().map(|m| &m.message))
+            .chain(std::iter::once(&packed_vote.finalize_message));
+
+        for message in messages {
+            assert!(get_transaction_size(message) <= PACKET_DATA_SIZE);
+        }
+    }
+
+    fn get_assets_count(messages: &[PackedMessage]) -> usize {
+        messages
+            .iter()
+            .map(|m| m.asset_ids.len())
+            .sum()
+    }
+
+    #[test]
+    fn test_pack_vote() {
+        // Arrange
+        let context = create_test_context();
+        let assets = create_test_assets(&context, 30, 4);
+
+        let config = VotePackerConfig {
+            allow_lookup_table: false,
+            ..VotePackerConfig::default()
+        };
+
+        // Act
+        let packed_vote = pack_vote(&context, &assets, &config).unwrap();
+
+        // Assert
+        assert_messages_fit(&packed_vote);
+        assert!(packed_vote.create_ticket_messages.len() > 1);
+        assert_eq!(get_assets_count(&packed_vote.create_ticket_messages), 30);
+        assert_eq!(get_assets_count(&packed_vote.cast_vote_messages), 30);
+        assert!(packed_vote.lookup_table.is_none());
+    }
+
+    #[test]
+    fn test_pack_vote_with_max_assets_per_tx() {
+        // Arrange
+        let mut context = create_test_context();
+        context.registrar_extension.config.max_assets_per_tx = 2;
+
+        let assets = create_test_assets(&context, 5, 0);
+
+        // Act
+        let packed_vote = pack_vote(&context, &assets, &VotePackerConfig::default()).unwrap();
+
+        // Assert
+        assert_eq!(packed_vote.cast_vote_messages.len(), 3);
+        assert!(packed_vote.cast_vote_messages.iter().all(|m| m.asset_ids.len() <= 2));
+    }
+
+    #[test]
+    fn test_pack_vote_with_compute_unit_limit() {
+        // Arrange
+        let context = create_test_context();
+        let assets = create_test_assets(&context, 4, 0);
+
+        let config = VotePackerConfig {
+            compute_unit_limit: 100_000,
+            allow_lookup_table: false,
+            ..VotePackerConfig::default()
+        };
+
+        // Act
+        let packed_vote = pack_vote(&context, &assets, &config).unwrap();
+
+        // Assert
+        // 20_000 + 45_000 per ticket allows a single ticket per transaction
+        assert_eq!(packed_vote.create_ticket_messages.len(), 4);
+    }
+
+    #[test]
+    fn test_pack_vote_with_lookup_table() {
+        // Arrange
+        let context = create_test_context();
+        let tree = Pubkey::new_unique();
+        let proof: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+
+        // The assets share the proof nodes and the accounts are repeated in every transaction
+        let assets: Vec<VoteAsset> = (0..40)
+            .map(|index| create_test_asset(&context, tree, index, proof.clone()))
+            .collect();
+
+        let config_without_lookup_table = VotePackerConfig {
+            allow_lookup_table: false,
+            ..VotePackerConfig::default()
+        };
+
+        let packed_vote_without_lookup_table = pack_vote(
+            &context,
+            &assets,
+            &config_without_lookup_table
+        ).unwrap();
+
+        // Act
+        let packed_vote = pack_vote(&context, &assets, &VotePackerConfig::default()).unwrap();
+
+        // Assert
+        assert_messages_fit(&packed_vote);
+        assert!(packed_vote.lookup_table.is_some());
+        assert!(!packed_vote.lookup_table_messages.is_empty());
+        assert!(
+            packed_vote.get_transactions_count() <
+                packed_vote_without_lookup_table.get_transactions_count()
+        );
+        assert_eq!(get_assets_count(&packed_vote.create_ticket_messages), 40);
+    }
+
+    #[test]
+    fn test_pack_vote_with_asset_too_large_error() {
+        // Arrange
+        let context = create_test_context();
+        let assets = create_test_assets(&context, 1, 40);
+
+        let config = VotePackerConfig {
+            allow_lookup_table: false,
+            ..VotePackerConfig::default()
+        };
+
+        // Act
+        let err = pack_vote(&context, &assets, &config).err().unwrap();
+
+        // Assert
+        assert_eq!(err, VotePackerError::AssetTooLarge(assets[0].get_asset_id()));
+    }
+
+    #[test]
+    fn test_pack_vote_with_not_accumulative_weight_error() {
+        // Arrange
+        let mut context = create_test_context();
+        context.registrar_extension.config.collection_weight_mode = CollectionWeightMode::UniqueCollections;
+        context.registrar_extension.config.max_assets_per_tx = 1;
+
+        let assets = create_test_assets(&context, 2, 0);
+
+        // Act
+        let err = pack_vote(&context, &assets, &VotePackerConfig::default()).err().unwrap();
+
+        // Assert
+        assert_eq!(err, VotePackerError::VoteCannotBeSplit);
+    }
+
+    #[test]
+    fn test_pack_vote_with_holding_attestation_error() {
+        // Arrange
+        let mut context = create_test_context();
+        context.registrar_extension.config.min_holding_slots = 10;
+
+        let assets = create_test_assets(&context, 1, 0);
+
+        // Act
+        let err = pack_vote(&context, &assets, &VotePackerConfig::default()).err().unwrap();
+
+        // Assert
+        assert_eq!(err, VotePackerError::UnsupportedAsset(assets[0].get_asset_id()));
+    }
+}
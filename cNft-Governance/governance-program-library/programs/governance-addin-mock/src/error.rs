@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum GovernanceAddinMockError {
+    #[msg("Voter weight addin is not configured for the governing token")]
+    VoterWeightAddinNotConfigured,
+
+    #[msg("Max voter weight addin is not configured for the governing token")]
+    MaxVoterWeightAddinNotConfigured,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use solana_program::program::set_return_data;
+use spl_governance::addins::max_voter_weight;
+use spl_governance::state::{ realm, realm_config };
+
+use crate::error::GovernanceAddinMockError;
+
+/// Reads the MaxVoterWeightRecord of the governing_token_mint the way spl-governance does when tipping proposals
+/// The spl-governance instance is the owner of the Realm
+#[derive(Accounts)]
+pub struct ConsumeMaxVoterWeight<'info> {
+    /// CHECK: Validated by spl-governance get_realm_data_for_governing_token_mint
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated by spl-governance get_realm_config_data_for_realm
+    pub realm_config: UncheckedAccount<'info>,
+
+    /// CHECK: Either the community or the council mint of the Realm
+    pub governing_token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by spl-governance get_max_voter_weight_record_data_for_realm_and_governing_token_mint
+    pub max_voter_weight_record: UncheckedAccount<'info>,
+}
+
+pub fn consume_max_voter_weight(ctx: Context<ConsumeMaxVoterWeight>) -> Result<()> {
+    let governance_program_id = ctx.accounts.realm.owner;
+    let realm_key = ctx.accounts.realm.key();
+    let governing_token_mint = ctx.accounts.governing_token_mint.key();
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        governance_program_id,
+        &ctx.accounts.realm,
+        &governing_token_mint
+    )?;
+
+    let realm_config = realm_config::get_realm_config_data_for_realm(
+        governance_program_id,
+        &ctx.accounts.realm_config,
+        &realm_key
+    )?;
+
+    let max_voter_weight_addin = realm_config
+        .get_token_config(&realm, &governing_token_mint)?
+        .max_voter_weight_addin.ok_or(GovernanceAddinMockError::MaxVoterWeightAddinNotConfigured)?;
+
+    let max_voter_weight_record =
+        max_voter_weight::get_max_voter_weight_record_data_for_realm_and_governing_token_mint(
+            &max_voter_weight_addin,
+            &ctx.accounts.max_voter_weight_record,
+            &realm_key,
+            &governing_token_mint
+        )?;
+
+    max_voter_weight::assert_is_valid_max_voter_weight(&max_voter_weight_record)?;
+
+    msg!("MAX VOTER WEIGHT: {}", max_voter_weight_record.max_voter_weight);
+    set_return_data(&max_voter_weight_record.max_voter_weight.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use solana_program::program::set_return_data;
+use spl_governance::addins::voter_weight;
+use spl_governance::state::{ realm, realm_config, token_owner_record };
+use spl_governance_addin_api::voter_weight::VoterWeightAction as AddinVoterWeightAction;
+
+use crate::error::GovernanceAddinMockError;
+
+/// VoterWeightAction enum as defined in spl-governance-addin-api
+/// It's redefined here for Anchor to export it to IDL
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+impl From<VoterWeightAction> for AddinVoterWeightAction {
+    fn from(action: VoterWeightAction) -> Self {
+        match action {
+            VoterWeightAction::CastVote => AddinVoterWeightAction::CastVote,
+            VoterWeightAction::CommentProposal => AddinVoterWeightAction::CommentProposal,
+            VoterWeightAction::CreateGovernance => AddinVoterWeightAction::CreateGovernance,
+            VoterWeightAction::CreateProposal => AddinVoterWeightAction::CreateProposal,
+            VoterWeightAction::SignOffProposal => AddinVoterWeightAction::SignOffProposal,
+        }
+    }
+}
+
+/// Reads the VoterWeightRecord of the TokenOwnerRecord the way spl-governance does for the given action
+/// The spl-governance instance is the owner of the Realm
+#[derive(Accounts)]
+pub struct ConsumeVoterWeight<'info> {
+    /// CHECK: Validated by spl-governance get_realm_data
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: PDA validated by spl-governance get_realm_config_data_for_realm
+    pub realm_config: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by spl-governance get_token_owner_record_data_for_realm
+    pub token_owner_record: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by spl-governance get_voter_weight_record_data_for_token_owner_record
+    pub voter_weight_record: UncheckedAccount<'info>,
+}
+
+pub fn consume_voter_weight(
+    ctx: Context<ConsumeVoterWeight>,
+    weight_action: VoterWeightAction,
+    weight_action_target: Pubkey
+) -> Result<()> {
+    let governance_program_id = ctx.accounts.realm.owner;
+    let realm_key = ctx.accounts.realm.key();
+
+    let realm = realm::get_realm_data(governance_program_id, &ctx.accounts.realm)?;
+
+    let token_owner_record = token_owner_record::get_token_owner_record_data_for_realm(
+        governance_program_id,
+        &ctx.accounts.token_owner_record,
+        &realm_key
+    )?;
+
+    let realm_config = realm_config::get_realm_config_data_for_realm(
+        governance_program_id,
+        &ctx.accounts.realm_config,
+        &realm_key
+    )?;
+
+    let voter_weight_addin = realm_config
+        .get_token_config(&realm, &token_owner_record.governing_token_mint)?
+        .voter_weight_addin.ok_or(GovernanceAddinMockError::VoterWeightAddinNotConfigured)?;
+
+    let voter_weight_record = voter_weight::get_voter_weight_record_data_for_token_owner_record(
+        &voter_weight_addin,
+        &ctx.accounts.voter_weight_record,
+        &token_owner_record
+    )?;
+
+    voter_weight::assert_is_valid_voter_weight(
+        &voter_weight_record,
+        weight_action.into(),
+        &weight_action_target
+    )?;
+
+    msg!("VOTER WEIGHT: {}", voter_weight_record.voter_weight);
+    set_return_data(&voter_weight_record.voter_weight.to_le_bytes());
+
+    Ok(())
+}
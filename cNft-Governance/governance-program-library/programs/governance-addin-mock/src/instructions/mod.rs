@@ -0,0 +1,5 @@
+pub use consume_voter_weight::*;
+mod consume_voter_weight;
+
+pub use consume_max_voter_weight::*;
+mod consume_max_voter_weight;
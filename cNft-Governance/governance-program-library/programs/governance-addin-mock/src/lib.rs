@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+pub mod error;
+
+mod instructions;
+pub use instructions::*;
+
+declare_id!("AddinMock1111111111111111111111111111111111");
+
+/// Mock of the spl-governance side of the voter weight addin interface
+/// The instructions read the plugin records with the same spl-governance functions the governance program uses
+/// and return the accepted weight as u64 LE return data
+#[program]
+pub mod governance_addin_mock {
+    use super::*;
+
+    pub fn consume_voter_weight(
+        ctx: Context<ConsumeVoterWeight>,
+        weight_action: VoterWeightAction,
+        weight_action_target: Pubkey
+    ) -> Result<()> {
+        instructions::consume_voter_weight(ctx, weight_action, weight_action_target)
+    }
+
+    pub fn consume_max_voter_weight(ctx: Context<ConsumeMaxVoterWeight>) -> Result<()> {
+        instructions::consume_max_voter_weight(ctx)
+    }
+}
@@ -0,0 +1,467 @@
+//! Feeds arbitrary instruction data through the Anchor dispatch of the program
+//!
+//! The instructions are either structured (valid discriminator and arbitrary arguments)
+//! or raw (valid discriminator and arbitrary bytes, or entirely arbitrary bytes)
+//! No accounts are supplied and hence the instructions fail once the arguments are deserialized
+//! The target ensures the deserialization of the arguments (ex. variable length proofs and metadata)
+//! never panics or aborts and only fails with an error
+//!
+//! cargo +nightly fuzz run instruction_decoder
+
+#![no_main]
+
+use anchor_lang::{ prelude::Pubkey, Discriminator, InstructionData };
+use arbitrary::{ Arbitrary, Result, Unstructured };
+use gpl_nft_voter::state::*;
+use gpl_nft_voter::tools::fuzz::arbitrary_pubkey;
+use libfuzzer_sys::fuzz_target;
+
+/// Discriminators of all the program instructions
+const DISCRIMINATORS: &[[u8; 8]] = &[
+    gpl_nft_voter::instruction::CreateRegistrar::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateRegistrarTemplate::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureRegistrarTemplate::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateRegistrarFromTemplate::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateVoterWeightRecord::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateVoterWeightDetail::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateMaxVoterWeightRecord::DISCRIMINATOR,
+    gpl_nft_voter::instruction::UpdateVoterWeightRecord::DISCRIMINATOR,
+    gpl_nft_voter::instruction::UpdateVoterWeightRecordForTarget::DISCRIMINATOR,
+    gpl_nft_voter::instruction::RelinquishNftVote::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ReclaimNftVoteRecords::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureCollection::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureCollectionsBatch::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureCreatorBoosts::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureCollectionExpiry::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureCollectionWeightStrategy::DISCRIMINATOR,
+    gpl_nft_voter::instruction::PruneExpiredCollections::DISCRIMINATOR,
+    gpl_nft_voter::instruction::PreviewVoterWeight::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureRegistrar::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ExtendAccount::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureGovernanceWeight::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConfigureSubRegistrar::DISCRIMINATOR,
+    gpl_nft_voter::instruction::SyncParentWeight::DISCRIMINATOR,
+    gpl_nft_voter::instruction::FreezeVoter::DISCRIMINATOR,
+    gpl_nft_voter::instruction::UnfreezeVoter::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CastNftVote::DISCRIMINATOR,
+    gpl_nft_voter::instruction::FinalizeNftVote::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateNftActionTicket::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateCoreAssetActionTicket::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateCnftActionTicket::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateProposalReceipt::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ConsumeProposalCreationTicket::DISCRIMINATOR,
+    gpl_nft_voter::instruction::LinkColdWallet::DISCRIMINATOR,
+    gpl_nft_voter::instruction::RevokeColdWalletLink::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CloseExpiredDelegation::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateVoterRentEscrow::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CloseVoterRentEscrow::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateVotingSession::DISCRIMINATOR,
+    gpl_nft_voter::instruction::RevokeVotingSession::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ValidateRealmConfig::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CastNftVoteMulti::DISCRIMINATOR,
+    gpl_nft_voter::instruction::SelectMultiProposalVote::DISCRIMINATOR,
+    gpl_nft_voter::instruction::MigrateVoter::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateVoterGroup::DISCRIMINATOR,
+    gpl_nft_voter::instruction::JoinVoterGroup::DISCRIMINATOR,
+    gpl_nft_voter::instruction::LeaveVoterGroup::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ValidateRegistrar::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateBallotTree::DISCRIMINATOR,
+    gpl_nft_voter::instruction::MintBallot::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateCampaign::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ClaimBallot::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CastBallotVote::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CreateWeightSnapshot::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CastSnapshotVote::DISCRIMINATOR,
+    gpl_nft_voter::instruction::WriteExternalVoterWeight::DISCRIMINATOR,
+    gpl_nft_voter::instruction::RentVotingPower::DISCRIMINATOR,
+    gpl_nft_voter::instruction::ReclaimVotingPower::DISCRIMINATOR,
+    gpl_nft_voter::instruction::MapCollectionAlias::DISCRIMINATOR,
+    gpl_nft_voter::instruction::RegisterVoter::DISCRIMINATOR,
+    gpl_nft_voter::instruction::CloseOrphanedNftVoteRecords::DISCRIMINATOR,
+];
+
+#[derive(Arbitrary, Debug)]
+enum FuzzInstruction {
+    /// Valid discriminator followed by arbitrary bytes
+    Raw {
+        discriminator_index: usize,
+        data: Vec<u8>,
+    },
+
+    /// Entirely arbitrary bytes
+    Bytes(Vec<u8>),
+
+    CreateRegistrar {
+        max_collections: u8,
+    },
+
+    CreateRegistrarTemplate {
+        template_id: u64,
+        max_collections: u8,
+    },
+
+    ConfigureRegistrarTemplate {
+        config: RegistrarConfig,
+        collections: Vec<CollectionConfigArgs>,
+    },
+
+    CreateRegistrarFromTemplate {
+        max_collections: u8,
+    },
+
+    CreateVoterWeightRecord {
+        #[arbitrary(with = arbitrary_pubkey)]
+        governing_token_owner: Pubkey,
+    },
+
+    CreateVoterWeightDetail {
+        max_collections: u8,
+    },
+
+    UpdateVoterWeightRecord {
+        voter_weight_action: VoterWeightAction,
+        use_voter_group: bool,
+        expected_record_nonce: u32,
+    },
+
+    UpdateVoterWeightRecordForTarget {
+        voter_weight_action: VoterWeightAction,
+    },
+
+    ConfigureCollection {
+        weight: u64,
+        size: u32,
+    },
+
+    ConfigureCollectionsBatch {
+        collections: Vec<CollectionConfigArgs>,
+    },
+
+    ConfigureCreatorBoosts {
+        #[arbitrary(with = arbitrary_pubkey)]
+        collection: Pubkey,
+        creator_boosts: Vec<CreatorBoost>,
+    },
+
+    ConfigureCollectionExpiry {
+        #[arbitrary(with = arbitrary_pubkey)]
+        collection: Pubkey,
+        expires_at: i64,
+    },
+
+    ConfigureCollectionWeightStrategy {
+        #[arbitrary(with = arbitrary_pubkey)]
+        collection: Pubkey,
+        weight_strategy: CollectionWeightStrategy,
+    },
+
+    PreviewVoterWeight {
+        #[arbitrary(with = arbitrary_pubkey)]
+        governing_token_owner: Pubkey,
+        nft_count: u8,
+        params: Vec<CompressedNftAsset>,
+    },
+
+    ConfigureRegistrar {
+        config: RegistrarConfig,
+    },
+
+    ExtendAccount {
+        extra_space: u32,
+    },
+
+    ConfigureGovernanceWeight {
+        weight_multiplier_bps: u32,
+    },
+
+    ConfigureSubRegistrar {
+        weight_scale_bps: u32,
+    },
+
+    SyncParentWeight {
+        voter_weight_action: VoterWeightAction,
+        #[arbitrary(with = arbitrary_optional_pubkey)]
+        weight_action_target: Option<Pubkey>,
+    },
+
+    FreezeVoter {
+        #[arbitrary(with = arbitrary_pubkey)]
+        governing_token_owner: Pubkey,
+    },
+
+    CastNftVote {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+        simulate_only: bool,
+        memo: Option<String>,
+        use_voter_rent_escrow: bool,
+        use_voting_session: bool,
+        vote_kind: NftVoteKind,
+        use_voter_group: bool,
+    },
+
+    FinalizeNftVote {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+    },
+
+    CreateNftActionTicket {
+        voter_weight_action: VoterWeightAction,
+        voter_allowlist_proof: Vec<[u8; 32]>,
+    },
+
+    CreateCoreAssetActionTicket {
+        voter_weight_action: VoterWeightAction,
+        voter_allowlist_proof: Vec<[u8; 32]>,
+    },
+
+    CreateCnftActionTicket {
+        voter_weight_action: VoterWeightAction,
+        params: Vec<CompressedNftAsset>,
+        voter_allowlist_proof: Vec<[u8; 32]>,
+    },
+
+    LinkColdWallet {
+        #[arbitrary(with = arbitrary_pubkey)]
+        cold_wallet: Pubkey,
+        expires_at: i64,
+    },
+
+    CreateVoterRentEscrow {
+        lamports: u64,
+    },
+
+    CreateVotingSession {
+        #[arbitrary(with = arbitrary_pubkey)]
+        session_authority: Pubkey,
+        expires_at_slot: u64,
+    },
+
+    ValidateRealmConfig {
+        voter_weight_addin: bool,
+        max_voter_weight_addin: bool,
+    },
+
+    CastNftVoteMulti {
+        #[arbitrary(with = arbitrary_pubkeys)]
+        proposals: Vec<Pubkey>,
+        vote_kind: NftVoteKind,
+    },
+
+    SelectMultiProposalVote {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+    },
+
+    MigrateVoter {
+        cold_wallet_links_count: u8,
+    },
+
+    LeaveVoterGroup {
+        #[arbitrary(with = arbitrary_pubkey)]
+        member: Pubkey,
+    },
+
+    CreateBallotTree {
+        max_depth: u32,
+        max_buffer_size: u32,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+
+    CreateCampaign {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+        allowlist_root: [u8; 32],
+        ballot_weight: u64,
+    },
+
+    ClaimBallot {
+        allowlist_proof: Vec<[u8; 32]>,
+    },
+
+    CastBallotVote {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+    },
+
+    CreateWeightSnapshot {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+        snapshot_slot: u64,
+        holdings_root: [u8; 32],
+        total_weight: u64,
+    },
+
+    CastSnapshotVote {
+        #[arbitrary(with = arbitrary_pubkey)]
+        proposal: Pubkey,
+        voter_weight: u64,
+        proof: Vec<[u8; 32]>,
+    },
+
+    RentVotingPower {
+        #[arbitrary(with = arbitrary_pubkey)]
+        asset_id: Pubkey,
+        #[arbitrary(with = arbitrary_pubkey)]
+        renter: Pubkey,
+        expires_at_slot: u64,
+    },
+
+    MapCollectionAlias {
+        #[arbitrary(with = arbitrary_pubkey)]
+        collection: Pubkey,
+        #[arbitrary(with = arbitrary_pubkey)]
+        target_collection: Pubkey,
+        weight_bps: u16,
+    },
+
+    ConfigureWalletWeightOverride {
+        #[arbitrary(with = arbitrary_pubkey)]
+        wallet: Pubkey,
+        weight_bps: u16,
+    },
+}
+
+fn arbitrary_optional_pubkey(u: &mut Unstructured) -> Result<Option<Pubkey>> {
+    Ok(if u.arbitrary()? { Some(arbitrary_pubkey(u)?) } else { None })
+}
+
+fn arbitrary_pubkeys(u: &mut Unstructured) -> Result<Vec<Pubkey>> {
+    (0..u.arbitrary_len::<[u8; 32]>()?).map(|_| arbitrary_pubkey(u)).collect()
+}
+
+fn get_instruction_data(instruction: FuzzInstruction) -> Vec<u8> {
+    match instruction {
+        FuzzInstruction::Raw { discriminator_index, data } => {
+            let discriminator = DISCRIMINATORS[discriminator_index % DISCRIMINATORS.len()];
+            [&discriminator[..], &data[..]].concat()
+        }
+        FuzzInstruction::Bytes(data) => data,
+        FuzzInstruction::CreateRegistrar { max_collections } =>
+            (gpl_nft_voter::instruction::CreateRegistrar { max_collections }).data(),
+        FuzzInstruction::CreateRegistrarTemplate { template_id, max_collections } =>
+            (gpl_nft_voter::instruction::CreateRegistrarTemplate { template_id, max_collections }).data(),
+        FuzzInstruction::ConfigureRegistrarTemplate { config, collections } =>
+            (gpl_nft_voter::instruction::ConfigureRegistrarTemplate { config, collections }).data(),
+        FuzzInstruction::CreateRegistrarFromTemplate { max_collections } =>
+            (gpl_nft_voter::instruction::CreateRegistrarFromTemplate { max_collections }).data(),
+        FuzzInstruction::CreateVoterWeightRecord { governing_token_owner } =>
+            (gpl_nft_voter::instruction::CreateVoterWeightRecord { governing_token_owner }).data(),
+        FuzzInstruction::CreateVoterWeightDetail { max_collections } =>
+            (gpl_nft_voter::instruction::CreateVoterWeightDetail { max_collections }).data(),
+        FuzzInstruction::UpdateVoterWeightRecord {
+            voter_weight_action,
+            use_voter_group,
+            expected_record_nonce,
+        } =>
+            (gpl_nft_voter::instruction::UpdateVoterWeightRecord {
+                voter_weight_action,
+                use_voter_group,
+                expected_record_nonce,
+            }).data(),
+        FuzzInstruction::UpdateVoterWeightRecordForTarget { voter_weight_action } =>
+            (gpl_nft_voter::instruction::UpdateVoterWeightRecordForTarget { voter_weight_action }).data(),
+        FuzzInstruction::ConfigureCollection { weight, size } =>
+            (gpl_nft_voter::instruction::ConfigureCollection { weight, size }).data(),
+        FuzzInstruction::ConfigureCollectionsBatch { collections } =>
+            (gpl_nft_voter::instruction::ConfigureCollectionsBatch { collections }).data(),
+        FuzzInstruction::ConfigureCreatorBoosts { collection, creator_boosts } =>
+            (gpl_nft_voter::instruction::ConfigureCreatorBoosts { collection, creator_boosts }).data(),
+        FuzzInstruction::ConfigureCollectionExpiry { collection, expires_at } =>
+            (gpl_nft_voter::instruction::ConfigureCollectionExpiry { collection, expires_at }).data(),
+        FuzzInstruction::ConfigureCollectionWeightStrategy { collection, weight_strategy } =>
+            (gpl_nft_voter::instruction::ConfigureCollectionWeightStrategy { collection, weight_strategy }).data(),
+        FuzzInstruction::PreviewVoterWeight { governing_token_owner, nft_count, params } =>
+            (gpl_nft_voter::instruction::PreviewVoterWeight { governing_token_owner, nft_count, params }).data(),
+        FuzzInstruction::ConfigureRegistrar { config } =>
+            (gpl_nft_voter::instruction::ConfigureRegistrar { config }).data(),
+        FuzzInstruction::ExtendAccount { extra_space } =>
+            (gpl_nft_voter::instruction::ExtendAccount { extra_space }).data(),
+        FuzzInstruction::ConfigureGovernanceWeight { weight_multiplier_bps } =>
+            (gpl_nft_voter::instruction::ConfigureGovernanceWeight { weight_multiplier_bps }).data(),
+        FuzzInstruction::ConfigureSubRegistrar { weight_scale_bps } =>
+            (gpl_nft_voter::instruction::ConfigureSubRegistrar { weight_scale_bps }).data(),
+        FuzzInstruction::SyncParentWeight { voter_weight_action, weight_action_target } =>
+            (gpl_nft_voter::instruction::SyncParentWeight { voter_weight_action, weight_action_target }).data(),
+        FuzzInstruction::FreezeVoter { governing_token_owner } =>
+            (gpl_nft_voter::instruction::FreezeVoter { governing_token_owner }).data(),
+        FuzzInstruction::CastNftVote { proposal, simulate_only, memo, use_voter_rent_escrow, use_voting_session, vote_kind, use_voter_group } =>
+            (gpl_nft_voter::instruction::CastNftVote { proposal, simulate_only, memo, use_voter_rent_escrow, use_voting_session, vote_kind, use_voter_group }).data(),
+        FuzzInstruction::FinalizeNftVote { proposal } =>
+            (gpl_nft_voter::instruction::FinalizeNftVote { proposal }).data(),
+        FuzzInstruction::CreateNftActionTicket { voter_weight_action, voter_allowlist_proof } =>
+            (gpl_nft_voter::instruction::CreateNftActionTicket { voter_weight_action, voter_allowlist_proof }).data(),
+        FuzzInstruction::CreateCoreAssetActionTicket { voter_weight_action, voter_allowlist_proof } =>
+            (gpl_nft_voter::instruction::CreateCoreAssetActionTicket { voter_weight_action, voter_allowlist_proof }).data(),
+        FuzzInstruction::CreateCnftActionTicket { voter_weight_action, params, voter_allowlist_proof } =>
+            (gpl_nft_voter::instruction::CreateCnftActionTicket { voter_weight_action, params, voter_allowlist_proof }).data(),
+        FuzzInstruction::LinkColdWallet { cold_wallet, expires_at } =>
+            (gpl_nft_voter::instruction::LinkColdWallet { cold_wallet, expires_at }).data(),
+        FuzzInstruction::CreateVoterRentEscrow { lamports } =>
+            (gpl_nft_voter::instruction::CreateVoterRentEscrow { lamports }).data(),
+        FuzzInstruction::CreateVotingSession { session_authority, expires_at_slot } =>
+            (gpl_nft_voter::instruction::CreateVotingSession { session_authority, expires_at_slot }).data(),
+        FuzzInstruction::ValidateRealmConfig { voter_weight_addin, max_voter_weight_addin } =>
+            (gpl_nft_voter::instruction::ValidateRealmConfig { voter_weight_addin, max_voter_weight_addin }).data(),
+        FuzzInstruction::CastNftVoteMulti { proposals, vote_kind } =>
+            (gpl_nft_voter::instruction::CastNftVoteMulti { proposals, vote_kind }).data(),
+        FuzzInstruction::SelectMultiProposalVote { proposal } =>
+            (gpl_nft_voter::instruction::SelectMultiProposalVote { proposal }).data(),
+        FuzzInstruction::MigrateVoter { cold_wallet_links_count } =>
+            (gpl_nft_voter::instruction::MigrateVoter { cold_wallet_links_count }).data(),
+        FuzzInstruction::LeaveVoterGroup { member } =>
+            (gpl_nft_voter::instruction::LeaveVoterGroup { member }).data(),
+        FuzzInstruction::CreateBallotTree { max_depth, max_buffer_size, name, symbol, uri } =>
+            (gpl_nft_voter::instruction::CreateBallotTree {
+                max_depth,
+                max_buffer_size,
+                name,
+                symbol,
+                uri,
+            }).data(),
+        FuzzInstruction::CreateCampaign { proposal, allowlist_root, ballot_weight } =>
+            (gpl_nft_voter::instruction::CreateCampaign {
+                proposal,
+                allowlist_root,
+                ballot_weight,
+            }).data(),
+        FuzzInstruction::ClaimBallot { allowlist_proof } =>
+            (gpl_nft_voter::instruction::ClaimBallot { allowlist_proof }).data(),
+        FuzzInstruction::CastBallotVote { proposal, root, nonce, index } =>
+            (gpl_nft_voter::instruction::CastBallotVote { proposal, root, nonce, index }).data(),
+        FuzzInstruction::CreateWeightSnapshot { proposal, snapshot_slot, holdings_root, total_weight } =>
+            (gpl_nft_voter::instruction::CreateWeightSnapshot {
+                proposal,
+                snapshot_slot,
+                holdings_root,
+                total_weight,
+            }).data(),
+        FuzzInstruction::CastSnapshotVote { proposal, voter_weight, proof } =>
+            (gpl_nft_voter::instruction::CastSnapshotVote { proposal, voter_weight, proof }).data(),
+        FuzzInstruction::RentVotingPower { asset_id, renter, expires_at_slot } =>
+            (gpl_nft_voter::instruction::RentVotingPower { asset_id, renter, expires_at_slot }).data(),
+        FuzzInstruction::MapCollectionAlias { collection, target_collection, weight_bps } =>
+            (gpl_nft_voter::instruction::MapCollectionAlias {
+                collection,
+                target_collection,
+                weight_bps,
+            }).data(),
+        FuzzInstruction::ConfigureWalletWeightOverride { wallet, weight_bps } =>
+            (gpl_nft_voter::instruction::ConfigureWalletWeightOverride {
+                wallet,
+                weight_bps,
+            }).data(),
+    }
+}
+
+fuzz_target!(|instruction: FuzzInstruction| {
+    let data = get_instruction_data(instruction);
+
+    // Any error is fine as long as the dispatch doesn't panic
+    let _ = gpl_nft_voter::entry(&gpl_nft_voter::id(), &[], &data);
+});
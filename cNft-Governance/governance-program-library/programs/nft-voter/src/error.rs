@@ -91,4 +91,383 @@ pub enum NftVoterError {
 
     #[msg("Voter With Invalid Ticket")]
     InvalidNftTicket,
+
+    #[msg("Vote weight can't be accumulated in UniqueCollections mode or with non linear weight strategies")]
+    VoteWeightCannotBeAccumulated,
+
+    #[msg("Invalid max collection weight per proposal")]
+    InvalidMaxCollectionWeightPerProposal,
+
+    #[msg("Invalid CollectionVoteWeightRecord")]
+    InvalidCollectionVoteWeightRecord,
+
+    #[msg("Ed25519 signature not found")]
+    Ed25519SignatureNotFound,
+
+    #[msg("Missing holding attestation")]
+    MissingHoldingAttestation,
+
+    #[msg("NFT holding period not met")]
+    HoldingPeriodNotMet,
+
+    #[msg("ProposalCreationTicket is required to create Proposal")]
+    ProposalCreationTicketRequired,
+
+    #[msg("ProposalCreationTicket expired")]
+    ProposalCreationTicketExpired,
+
+    #[msg("Invalid ProposalCreationTicket")]
+    InvalidProposalCreationTicket,
+
+    #[msg("Invalid AssetUsesRecord")]
+    InvalidAssetUsesRecord,
+
+    #[msg("All uses of the NFT were consumed")]
+    NftUsesExhausted,
+
+    #[msg("Invalid weight multiplier")]
+    InvalidWeightMultiplier,
+
+    #[msg("Invalid GovernanceWeightConfig")]
+    InvalidGovernanceWeightConfig,
+
+    #[msg("Invalid VoterWeightAction target")]
+    InvalidVoterWeightActionTarget,
+
+    #[msg("Missing VoterWeightDetail")]
+    MissingVoterWeightDetail,
+
+    #[msg("Invalid VoterWeightDetail")]
+    InvalidVoterWeightDetail,
+
+    #[msg("VoterWeightDetail collections limit reached")]
+    VoterWeightDetailCollectionsLimitReached,
+
+    #[msg("Invalid TreeConfig")]
+    InvalidTreeConfig,
+
+    #[msg("Tree authority is not allowed")]
+    TreeAuthorityNotAllowed,
+
+    #[msg("Precomputed metadata hashes are not allowed")]
+    PrecomputedMetadataHashesNotAllowed,
+
+    #[msg("Invalid Registrar config")]
+    InvalidRegistrarConfig,
+
+    #[msg("Invalid merkle tree")]
+    InvalidMerkleTree,
+
+    #[msg("Invalid proof data")]
+    InvalidProofData,
+
+    #[msg("Invalid NftVoteRecord")]
+    InvalidNftVoteRecord,
+
+    #[msg("Voter is not allowed")]
+    VoterNotAllowed,
+
+    #[msg("Voter is frozen")]
+    VoterFrozen,
+
+    #[msg("Invalid VoterFreezeRecord")]
+    InvalidVoterFreezeRecord,
+
+    #[msg("Rent reclaim is not enabled")]
+    RentReclaimNotEnabled,
+
+    #[msg("Proposal voting must be finished")]
+    ProposalVotingNotFinished,
+
+    #[msg("Invalid collections batch")]
+    InvalidCollectionsBatch,
+
+    #[msg("Invalid CollectionStats")]
+    InvalidCollectionStats,
+
+    #[msg("Invalid creator boosts")]
+    InvalidCreatorBoosts,
+
+    #[msg("Proposal deadline for the action has passed")]
+    ProposalActionDeadlinePassed,
+
+    #[msg("Invalid Proposal")]
+    InvalidProposal,
+
+    #[msg("Invalid parent Registrar")]
+    InvalidParentRegistrar,
+
+    #[msg("VoterWeightRecord expired")]
+    VoterWeightRecordExpired,
+
+    #[msg("Invalid VoterWeightAction")]
+    InvalidVoterWeightAction,
+
+    #[msg("VoterWeightRecord must be consumed in the same transaction")]
+    VoterWeightRecordNotConsumed,
+
+    #[msg("Metaplex Core assets are not supported by the program build")]
+    CoreAssetsNotSupported,
+
+    #[msg("Metaplex Core assets are not allowed by the Registrar")]
+    CoreAssetsNotAllowed,
+
+    #[msg("Invalid Metaplex Core asset")]
+    InvalidCoreAsset,
+
+    #[msg("Invalid governing token mint")]
+    InvalidGoverningTokenMint,
+
+    #[msg("Too many assets for a single transaction")]
+    TooManyAssetsPerTransaction,
+
+    #[msg("No vote weight accumulated for the Proposal")]
+    VoteWeightNotAccumulated,
+
+    #[msg("Account can't be extended")]
+    AccountCannotBeExtended,
+
+    #[msg("Invalid account extension")]
+    InvalidAccountExtension,
+
+    #[msg("Invalid collection expiry")]
+    InvalidCollectionExpiry,
+
+    #[msg("No expired collections to prune")]
+    NoExpiredCollections,
+
+    #[msg("Invalid RegistrarTemplate")]
+    InvalidRegistrarTemplate,
+
+    #[msg("Invalid RegistrarTemplate authority")]
+    InvalidRegistrarTemplateAuthority,
+
+    #[msg("Tree rate limit exceeded")]
+    TreeRateLimitExceeded,
+
+    #[msg("Invalid TreeRateLimitRecord")]
+    InvalidTreeRateLimitRecord,
+
+    #[msg("Invalid ProofAttestation")]
+    InvalidProofAttestation,
+
+    #[msg("ProofAttestation expired")]
+    ProofAttestationExpired,
+
+    #[msg("Stale tree root")]
+    StaleTreeRoot,
+
+    #[msg("Invalid ColdWalletLink")]
+    InvalidColdWalletLink,
+
+    #[msg("ColdWalletLink expired")]
+    ColdWalletLinkExpired,
+
+    #[msg("Invalid ColdWalletLink expiry")]
+    InvalidColdWalletLinkExpiry,
+
+    #[msg("Plugin is not the voter weight addin of the Realm")]
+    PluginNotVoterWeightAddin,
+
+    #[msg("Plugin is not the max voter weight addin of the Realm")]
+    PluginNotMaxVoterWeightAddin,
+
+    #[msg("Leaf schema version doesn't match the merkle tree")]
+    LeafSchemaVersionMismatch,
+
+    #[msg("Invalid leaf schema data")]
+    InvalidLeafSchemaData,
+
+    #[msg("Vote memo is too long")]
+    VoteMemoTooLong,
+
+    #[msg("ColdWalletLink hasn't expired")]
+    ColdWalletLinkNotExpired,
+
+    #[msg("Invalid VoterRentEscrow")]
+    InvalidVoterRentEscrow,
+
+    #[msg("VoterRentEscrow has insufficient funds")]
+    VoterRentEscrowInsufficientFunds,
+
+    #[msg("Invalid VotingSession")]
+    InvalidVotingSession,
+
+    #[msg("VotingSession expired")]
+    VotingSessionExpired,
+
+    #[msg("Invalid VotingSession expiry")]
+    InvalidVotingSessionExpiry,
+
+    #[msg("Invalid collection weight strategy")]
+    InvalidCollectionWeightStrategy,
+
+    #[msg("Program invariant violated")]
+    InvariantViolated,
+
+    #[msg("Not enough remaining accounts")]
+    NotEnoughRemainingAccounts,
+
+    #[msg("Too many remaining accounts")]
+    TooManyRemainingAccounts,
+
+    #[msg("Invalid MultiProposalVote")]
+    InvalidMultiProposalVote,
+
+    #[msg("Multi proposal vote is not supported by the Registrar config")]
+    MultiProposalVoteNotSupported,
+
+    #[msg("Voter migration is not allowed by the Registrar config")]
+    VoterMigrationNotAllowed,
+
+    #[msg("Invalid VoterMigration")]
+    InvalidVoterMigration,
+
+    #[msg("Attested leaf hashes are not allowed by the Registrar config")]
+    LeafHashVerificationNotAllowed,
+
+    #[msg("Invalid attested leaf hash data")]
+    InvalidLeafHashData,
+
+    #[msg("Collection metadata has no CollectionDetails")]
+    MissingCollectionDetails,
+
+    #[msg("NFT ticket can't be used before RegistrarExtension.config.weight_record_min_age_slots pass")]
+    NftTicketTooRecent,
+
+    #[msg("VoterCheckpoint must be supplied when RegistrarExtension.config.record_voter_checkpoints is set")]
+    MissingVoterCheckpoint,
+
+    #[msg("Invalid VoterCheckpoint")]
+    InvalidVoterCheckpoint,
+
+    #[msg("NftVoteRecord vote kind doesn't match the governance VoteRecord")]
+    NftVoteKindMismatch,
+
+    #[msg("Merkle tree max_depth and max_buffer_size are not a supported tree shape")]
+    UnsupportedMerkleTreeShape,
+
+    #[msg("Merkle tree authority must be the Bubblegum TreeConfig of the tree")]
+    InvalidMerkleTreeAuthority,
+
+    #[msg("Invalid VoterGroup")]
+    InvalidVoterGroup,
+
+    #[msg("Invalid VoterGroup member")]
+    InvalidVoterGroupMember,
+
+    #[msg("VoterGroup has reached the max number of members")]
+    VoterGroupFull,
+
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+
+    #[msg("Numeric conversion overflow")]
+    CastError,
+
+    #[msg("Ballot name, symbol or uri exceeds the Metaplex limits")]
+    InvalidBallotMetadata,
+
+    #[msg("Wallet is not in the Campaign allowlist")]
+    CampaignClaimNotAllowed,
+
+    #[msg("Invalid Campaign ballot weight")]
+    InvalidCampaignBallotWeight,
+
+    #[msg("Invalid BallotTree")]
+    InvalidBallotTree,
+
+    #[msg("VoterWeightAction target is required by the Registrar")]
+    WeightActionTargetRequired,
+
+    #[msg("Invalid WeightSnapshot slot")]
+    InvalidWeightSnapshotSlot,
+
+    #[msg("Voter weight is not included in the WeightSnapshot")]
+    InvalidWeightSnapshotProof,
+
+    #[msg("Voter weight export is not allowed")]
+    WeightExportNotAllowed,
+
+    #[msg("Invalid external VoterWeightRecord")]
+    InvalidExternalVoterWeightRecord,
+
+    #[msg("VoterWeightRecord nonce doesn't match the expected nonce")]
+    InvalidVoterWeightRecordNonce,
+
+    #[msg("Invalid WeightLease")]
+    InvalidWeightLease,
+
+    #[msg("WeightLease expired")]
+    WeightLeaseExpired,
+
+    #[msg("Invalid WeightLease expiry")]
+    InvalidWeightLeaseExpiry,
+
+    #[msg("WeightLease hasn't expired")]
+    WeightLeaseNotExpired,
+
+    #[msg("Invalid CollectionAlias")]
+    InvalidCollectionAlias,
+
+    #[msg("Invalid CollectionAlias weight")]
+    InvalidCollectionAliasWeight,
+
+    #[msg("Invalid VoterRegistration")]
+    InvalidVoterRegistration,
+
+    #[msg("Tree root evicted from the changelog buffer by concurrent tree changes")]
+    TreeBufferChurn,
+
+    #[msg("NftVoteRecord is not orphaned")]
+    NftVoteRecordNotOrphaned,
+
+    #[msg("Invalid WalletWeightOverride")]
+    InvalidWalletWeightOverride,
+
+    #[msg("Invalid WalletWeightOverride weight")]
+    InvalidWalletWeightOverrideWeight,
+
+    #[msg("Invalid RegistrarExtension")]
+    InvalidRegistrarExtension,
+
+    #[msg("Invalid legacy NftVoteRecord")]
+    InvalidLegacyNftVoteRecord,
+
+    #[msg("Voter registration is closed")]
+    VoterRegistrationClosed,
+
+    #[msg("WeightLeases are not allowed")]
+    WeightLeasesNotAllowed,
+
+    #[msg("The weight of the asset is rented out by an active WeightLease")]
+    WeightLeaseActive,
+
+    #[msg("WeightLease hasn't started")]
+    WeightLeaseNotStarted,
+
+    #[msg("Vote receipts are not enabled")]
+    VoteReceiptsNotEnabled,
+
+    #[msg("Assets of a Uses limited collection must be supplied with their metadata and Uses")]
+    AssetUsesRequired,
+
+    #[msg("RegistrarMetrics must be supplied unless the vote is simulated")]
+    RegistrarMetricsRequired,
+
+    #[msg("Wallet is already in a VoterGroup")]
+    WalletAlreadyInVoterGroup,
+}
+
+/// Logs the context of the account-compression VerifyLeaf CPI of the given asset
+/// The compression error alone (ex. "Invalid root recomputed from proof") doesn't identify the asset
+/// and the context lets user vote failures be diagnosed from explorer logs
+/// Note: A failed CPI aborts the transaction and can't be handled by the caller
+/// hence the context is logged before the CPI and it's the last log preceding the compression error
+/// The keys are logged using sol_log_pubkey to keep the compute cost low when many assets are verified
+pub fn log_verify_leaf_context(tree: &Pubkey, asset_id: &Pubkey, root: &[u8; 32], proof_len: usize) {
+    msg!("VerifyLeaf tree, asset_id, root, proof_len: {}", proof_len);
+    tree.log();
+    asset_id.log();
+    Pubkey::new_from_array(*root).log();
 }
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Max length of NftVoteMemo.memo in bytes
+pub const MAX_VOTE_MEMO_LENGTH: usize = 280;
+
+/// Voter rationale emitted by cast_nft_vote when the memo argument is provided
+/// The memo is emitted once per cast_nft_vote instruction and not stored in NftVoteRecords
+/// which are created for each NFT and would duplicate the memo
+#[event]
+pub struct NftVoteMemo {
+    /// The Registrar the vote was cast with
+    pub registrar: Pubkey,
+
+    /// Proposal which was voted on
+    pub proposal: Pubkey,
+
+    /// The voter who cast the vote
+    pub governing_token_owner: Pubkey,
+
+    /// UTF-8 memo of at most MAX_VOTE_MEMO_LENGTH bytes
+    pub memo: String,
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::instructions::{ BurnCpi, BurnCpiAccounts, BurnInstructionArgs };
+use spl_account_compression::{ program::SplAccountCompression, Noop };
+use spl_governance::state::{ governance, proposal as spl_proposal };
+
+use crate::error::NftVoterError;
+use crate::instructions::voter_accounts::*;
+use crate::state::*;
+use crate::tools::program_ids::Bubblegum;
+use crate::tools::governance::assert_proposal_action_deadline;
+
+/// Casts vote on the Campaign Proposal with a ballot of the Campaign
+/// The ballot is burnt and VoterWeightRecord is set to Campaign.ballot_weight for the Proposal
+/// and hence the instruction has to be executed inside the same transaction as spl-gov.CastVote
+///
+/// The voter must be the owner and the delegate of the ballot and the proof of the ballot
+/// must be supplied as remaining accounts
+///
+/// The optional VoterRegistration and WalletWeightOverride PDAs of the voter are supplied before the proof
+/// the same way as for finalize_nft_vote and the ballot weight is adjusted by the floor weight and the wallet override
+#[derive(Accounts)]
+#[instruction(proposal: Pubkey)]
+pub struct CastBallotVote<'info> {
+    pub voter: VoterAccounts<'info>,
+
+    #[account(
+        mut,
+        seeds = [ b"campaign".as_ref(),
+                voter.registrar.key().as_ref(),
+                proposal.as_ref()],
+        bump,
+        has_one = ballot_tree @ NftVoterError::InvalidBallotTree,
+        constraint = voter.voter_weight_record.governing_token_owner == voter.voter_authority.key()
+        @ NftVoterError::InvalidTokenOwnerForVoterWeightRecord
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(has_one = merkle_tree @ NftVoterError::InvalidBallotTree)]
+    pub ballot_tree: Account<'info, BallotTree>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                voter.registrar.key().as_ref(),
+                voter.voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = voter.registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal the vote is cast on which must match the proposal argument
+    #[account(
+        owner = voter.registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum TreeConfig of the tree validated by Bubblegum
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Merkle tree of the BallotTree validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn cast_ballot_vote<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CastBallotVote<'info>>,
+    proposal: Pubkey,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32
+) -> Result<()> {
+    let registrar = &ctx.accounts.voter.registrar;
+    let registrar_extension = &ctx.accounts.voter.registrar_extension;
+
+    // Ensure the Governance belongs to Registrar.realm
+    let governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let target_proposal = spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    assert_proposal_action_deadline(
+        &target_proposal,
+        &governance.config,
+        &VoterWeightAction::CastVote
+    )?;
+
+    let ballot_tree = &ctx.accounts.ballot_tree;
+    let (data_hash, creator_hash) = get_ballot_hashes(ballot_tree, &ballot_tree.key())?;
+    let voter_authority = ctx.accounts.voter.voter_authority.to_account_info();
+
+    let (voter_weight_adjustment, proof_accounts) = split_voter_weight_adjustment_accounts(
+        registrar,
+        registrar_extension,
+        voter_authority.key,
+        ctx.remaining_accounts
+    )?;
+
+    // The voter signs the burn as the owner of the ballot so the ballot can't be used again
+    let proof_accounts = proof_accounts
+        .iter()
+        .map(|proof_account| (proof_account, false, false))
+        .collect::<Vec<_>>();
+
+    BurnCpi::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+        BurnCpiAccounts {
+            tree_config: &ctx.accounts.tree_authority.to_account_info(),
+            leaf_owner: (&voter_authority, true),
+            leaf_delegate: (&voter_authority, false),
+            merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+            log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: &ctx.accounts.compression_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        BurnInstructionArgs {
+            root,
+            data_hash,
+            creator_hash,
+            nonce,
+            index,
+        }
+    ).invoke_with_remaining_accounts(&proof_accounts)?;
+
+    let voter_weight_expiry = Clock::get()?.slot;
+
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.ballots_cast = campaign.ballots_cast
+        .checked_add(1)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+    let voter_weight_record = &mut ctx.accounts.voter.voter_weight_record;
+
+    // The record is only valid as of the current slot and for casting vote on the Campaign Proposal
+    finalize_voter_weight(
+        voter_weight_record,
+        campaign.ballot_weight,
+        voter_weight_expiry,
+        &voter_weight_adjustment
+    );
+    voter_weight_record.weight_action = Some(VoterWeightAction::CastVote);
+    voter_weight_record.weight_action_target = Some(proposal);
+
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, 1).set()
+}
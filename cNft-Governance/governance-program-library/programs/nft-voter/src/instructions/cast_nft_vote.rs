@@ -1,10 +1,13 @@
 use crate::error::NftVoterError;
-use crate::{ id, state::* };
+use crate::events::{ NftVoteMemo, MAX_VOTE_MEMO_LENGTH };
+use crate::sanity;
+use crate::state::*;
 use crate::tools::accounts::close_nft_action_ticket_account;
+use crate::tools::accounts_cursor::AccountsCursor;
+use crate::tools::governance::assert_proposal_action_deadline;
 use anchor_lang::prelude::*;
 use anchor_lang::Accounts;
-use itertools::Itertools;
-use spl_governance_tools::account::create_and_serialize_account_signed;
+use spl_governance::state::{ governance, proposal as spl_proposal };
 
 /// Casts NFT vote. The NFTs used for voting are tracked using NftVoteRecord accounts
 /// This instruction updates VoterWeightRecord which is valid for the current Slot and the target Proposal only
@@ -12,9 +15,50 @@ use spl_governance_tools::account::create_and_serialize_account_signed;
 ///
 /// CastNftVote is accumulative and can be invoked using several transactions if voter owns more than 5 NFTs to calculate total voter_weight
 /// In this scenario only the last CastNftVote should be bundled  with spl-gov.CastVote in the same transaction
+/// or FinalizeNftVote can be bundled with spl-gov.CastVote instead once all the NFTs are counted
 ///
-/// CastNftVote instruction and NftVoteRecord are not directional. They don't record vote choice (ex Yes/No)
-/// VoteChoice is recorded by spl-gov in VoteRecord and this CastNftVote only tracks voting NFTs
+/// When RegistrarExtension.config.require_vote_finalization is set the accumulated weight is left Accumulating and expired
+/// and it can only be taken by spl-gov.CastVote once FinalizeNftVote is executed
+///
+/// When RegistrarExtension.config.max_assets_per_tx is set the number of NFTs per instruction is limited
+/// and voters with more NFTs have to use several CastNftVote instructions
+///
+/// CastNftVote instruction is not directional and the voter weight is the same for any vote choice (ex Yes/No)
+/// VoteChoice is recorded by spl-gov in VoteRecord and NftVoteRecord only records the vote_kind supplied by the voter
+/// (Approve, Deny, Abstain or Veto) which is validated against the spl-gov VoteRecord by RelinquishNftVote
+///
+/// When simulate_only is set then all the checks are performed but no accounts are created or updated
+/// and the voter weight the instruction would produce is set as the instruction return data
+/// It allows wallets to validate votes with many NFTs before paying rent for NftVoteRecords
+///
+/// The resulting voter weight and the number of NFTs counted are set as VoterWeightReturnData return data
+///
+/// When RegistrarExtension.config.track_collection_stats is set each vote is counted in CollectionStats of the NFT collection
+///
+/// The vote is rejected once the voting time of the Proposal (including cool off time) has ended
+/// The vote is also rejected when a ticket was created with a ColdWalletLink which has expired since
+///
+/// When use_voter_rent_escrow is set the VoterRentEscrow of the voter is supplied after the VotingSession
+/// and the rent of the created accounts is paid from the escrow instead of the payer
+///
+/// When use_voting_session is set the VotingSession of the voter is supplied after the VoterWeightDetail
+/// and voter_authority can be the session authority of the VotingSession instead of the voter or its delegate
+///
+/// When memo is provided it's emitted as NftVoteMemo event to record the voter rationale on-chain
+/// The memo is limited to MAX_VOTE_MEMO_LENGTH bytes and it's not emitted when simulate_only is set
+///
+/// The votes and the weight they granted are counted in the RegistrarMetrics shard of the voter
+///
+/// When use_voter_group is set the VoterGroup of the voter followed by the VoterFreezeRecords of the group members
+/// is supplied after the optional VotingSession and the CastVote tickets of the group members are counted
+/// together with the voter's own tickets
+///
+/// When RegistrarExtension.config.record_voter_checkpoints is set the VoterCheckpoint of the vote is supplied
+/// after the VoterRentEscrow and it's written with the (accumulated) voter weight
+///
+/// When the Proposal started voting before the Registrar was migrated (RegistrarExtension.legacy_nft_vote_records_cutoff)
+/// the legacy ["nft-vote-record",proposal,nft_mint] NftVoteRecord is supplied after the NftVoteRecord of each NFT
+/// and it must be empty to prevent the NFTs which voted before the upgrade from voting again
 ///
 #[derive(Accounts)]
 #[instruction(proposal: Pubkey)]
@@ -22,6 +66,10 @@ pub struct CastNftVote<'info> {
     /// The NFT voting registrar
     pub registrar: Account<'info, Registrar>,
 
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
     #[account(
         mut,
         constraint = voter_weight_record.realm == registrar.realm
@@ -38,42 +86,212 @@ pub struct CastNftVote<'info> {
 
     /// Authority of the voter who casts the vote
     /// It can be either governing_token_owner or its delegate and must sign this instruction
+    /// When use_voting_session is set it's the session authority of the VotingSession
     pub voter_authority: Signer<'info>,
 
     /// The account which pays for the transaction
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal the vote is cast on which must match the proposal argument
+    #[account(
+        owner = registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+
+    /// RegistrarMetrics shard of the voter the votes are counted in
+    /// It must be supplied unless simulate_only is set and it's omitted for simulated votes so they don't create it
+    #[account(
+        init_if_needed,
+        seeds = [ b"registrar-metrics".as_ref(),
+                registrar.key().as_ref(),
+                &[get_registrar_metrics_shard(&voter_weight_record.governing_token_owner)]],
+        bump,
+        payer = payer,
+        space = RegistrarMetrics::get_space()
+    )]
+    pub registrar_metrics: Option<Account<'info, RegistrarMetrics>>,
 }
 
 /// Casts vote with the NFT
 pub fn cast_nft_vote<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CastNftVote<'info>>,
-    proposal: Pubkey
+    proposal: Pubkey,
+    simulate_only: bool,
+    memo: Option<String>,
+    use_voter_rent_escrow: bool,
+    use_voting_session: bool,
+    vote_kind: NftVoteKind,
+    use_voter_group: bool
 ) -> Result<()> {
+    if let Some(memo) = &memo {
+        require!(memo.len() <= MAX_VOTE_MEMO_LENGTH, NftVoterError::VoteMemoTooLong);
+    }
+
     let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
     let voter_weight_record = &mut ctx.accounts.voter_weight_record;
     let payer = &mut ctx.accounts.payer.to_account_info();
     let rent = Rent::get()?;
     let mut voter_weight = 0u64;
 
-    let governing_token_owner = resolve_governing_token_owner(
-        registrar,
-        &ctx.accounts.voter_token_owner_record,
-        &ctx.accounts.voter_authority,
-        voter_weight_record
+    let (voter_weight_detail_info, remaining_accounts) = split_voter_weight_detail_account(
+        registrar_extension,
+        ctx.remaining_accounts
     )?;
 
+    let (voting_session_info, remaining_accounts) = split_voting_session_account(
+        use_voting_session,
+        remaining_accounts
+    )?;
+
+    let governing_token_owner = if let Some(voting_session_info) = voting_session_info {
+        resolve_governing_token_owner_for_voting_session(
+            registrar,
+            &ctx.accounts.voter_token_owner_record,
+            &ctx.accounts.voter_authority,
+            voter_weight_record,
+            voting_session_info
+        )?
+    } else {
+        resolve_governing_token_owner(
+            registrar,
+            &ctx.accounts.voter_token_owner_record,
+            &ctx.accounts.voter_authority,
+            voter_weight_record
+        )?
+    };
+
+    let (governing_token_owners, remaining_accounts) = split_voter_group_accounts(
+        use_voter_group,
+        &registrar.key(),
+        &governing_token_owner,
+        remaining_accounts
+    )?;
+
+    // Ensure the Governance belongs to Registrar.realm
+    let governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let target_proposal = spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    assert_proposal_action_deadline(
+        &target_proposal,
+        &governance.config,
+        &VoterWeightAction::CastVote
+    )?;
+
+    let current_slot = Clock::get()?.slot;
+    let unix_timestamp = Clock::get()?.unix_timestamp;
+    let voter_weight_expiry = current_slot;
+
+    // When finalization is required only the Accumulating weight can be accumulated further
+    // and a new vote starts once the previous weight was Finalized and Consumed
+    let is_accumulating =
+        voter_weight_record.weight_action_target == Some(proposal) &&
+        voter_weight_record.weight_action == Some(VoterWeightAction::CastVote) &&
+        (!registrar_extension.config.require_vote_finalization ||
+            voter_weight_record.get_weight_state(current_slot) == VoterWeightState::Accumulating);
+
+    // In UniqueCollections mode and with non linear weight strategies the weight of an NFT depends on
+    // the NFTs of its collection which were already counted and we can't track the previous instructions
+    if !registrar.is_voter_weight_accumulative(registrar_extension) {
+        require!(!is_accumulating, NftVoterError::VoteWeightCannotBeAccumulated);
+    }
+
+    // When the weight per collection is capped then each NFT is supplied with
+    // (nft_action_ticket, nft_vote_record, collection_vote_weight_record) accounts
+    // and with (nft_action_ticket, nft_vote_record) otherwise
+    // When the Proposal can have legacy NftVoteRecords the legacy_nft_vote_record follows the nft_vote_record
+    // When the collection stats are tracked then the collection_stats account is supplied last
+    // The optional VoterWeightDetail, VotingSession, VoterGroup (with the VoterFreezeRecords of its members),
+    // VoterRentEscrow and VoterCheckpoint accounts are supplied before the NFT accounts
+    let max_collection_weight = registrar_extension.config.get_max_collection_weight_per_proposal(
+        registrar.get_max_voter_weight(registrar_extension)?
+    );
+    let has_legacy_nft_vote_records = registrar_extension.has_legacy_nft_vote_records(
+        target_proposal.voting_at
+    );
+    let accounts_per_nft =
+        (if max_collection_weight.is_some() { 3 } else { 2 }) +
+        (has_legacy_nft_vote_records as usize) +
+        (registrar_extension.config.track_collection_stats as usize);
+
+    let (voter_rent_escrow_info, remaining_accounts) = split_voter_rent_escrow_account(
+        &registrar.key(),
+        &governing_token_owner,
+        use_voter_rent_escrow,
+        remaining_accounts
+    )?;
+
+    let (voter_checkpoint_infos, nft_accounts_list) = split_voter_checkpoint_accounts(
+        registrar_extension,
+        1,
+        remaining_accounts
+    )?;
+
+    if registrar_extension.config.max_assets_per_tx > 0 {
+        require!(
+            nft_accounts_list.len() / accounts_per_nft <=
+                (registrar_extension.config.max_assets_per_tx as usize),
+            NftVoterError::TooManyAssetsPerTransaction
+        );
+    }
+
     let mut to_closed_accounts = vec![];
     let mut unique_nft_action_tickets: Vec<Pubkey> = vec![];
+    let mut collection_weight_tally = CollectionWeightTally::default();
+    let mut contributions = vec![];
+    let mut accounts_cursor = AccountsCursor::new(nft_accounts_list);
+
+    while !accounts_cursor.is_empty() {
+        let nft_action_ticket_info = accounts_cursor.next("nft_action_ticket")?;
+        let nft_vote_record_info = accounts_cursor.next("nft_vote_record")?;
+        let legacy_nft_vote_record_info = accounts_cursor.next_if(
+            has_legacy_nft_vote_records,
+            "legacy_nft_vote_record"
+        )?;
+        let collection_vote_weight_record_info = accounts_cursor.next_if(
+            max_collection_weight.is_some(),
+            "collection_vote_weight_record"
+        )?;
+        let collection_stats_info = accounts_cursor.next_if(
+            registrar_extension.config.track_collection_stats,
+            "collection_stats"
+        )?;
 
-    for (nft_action_ticket_info, nft_vote_record_info) in ctx.remaining_accounts.iter().tuples() {
         if unique_nft_action_tickets.contains(&nft_action_ticket_info.key) {
             return Err(NftVoterError::DuplicatedNftDetected.into());
         }
         // Create NFT vote record to ensure the same NFT hasn't been already used for voting
-        // Note: The correct PDA of the NftVoteRecord is validated in create_and_serialize_account_signed
-        // It ensures the NftVoteRecord is for ('nft-vote-record',proposal,nft_mint) seeds
+        // Note: The correct PDA of the NftVoteRecord is validated in create_and_serialize_account_with_rent_escrow
+        // It ensures the NftVoteRecord is for ('nft-vote-record',registrar,proposal,nft_mint) seeds
         require!(nft_vote_record_info.data_is_empty(), NftVoterError::NftAlreadyVoted);
         require!(
             !nft_action_ticket_info.data_is_empty(), //this might be a problem
@@ -83,23 +301,29 @@ pub fn cast_nft_vote<'a, 'b, 'c, 'info>(
 
         // Note: deserializa NftActionTicket to ownership checking and get nft voting weight
         // It ensure the NftActionTicket is for (nft-{action}-ticket,registrar,governing_token_owner,nft_mint) seeds
+        // where governing_token_owner is the voter or a member of its VoterGroup
         let data_bytes = nft_action_ticket_info.data.clone();
         let data = NftActionTicket::try_from_slice(&data_bytes.borrow())?;
         let ticket_type = format!("nft-{}-ticket", &VoterWeightAction::CastVote).to_string();
         let nft_action_ticket_address = get_nft_action_ticket_address(
             &ticket_type,
             &registrar.key(),
-            &governing_token_owner,
+            &data.governing_token_owner,
             &data.nft_mint
         ).0;
 
         require!(
-            data.governing_token_owner == governing_token_owner &&
+            governing_token_owners.contains(&data.governing_token_owner) &&
                 nft_action_ticket_address == *nft_action_ticket_info.key,
             NftVoterError::InvalidNftTicket
         );
 
-        require!(data.expiry.unwrap() >= Clock::get()?.slot, NftVoterError::NftTicketExpired);
+        data.assert_consumable(current_slot, registrar_extension.config.weight_record_min_age_slots)?;
+        data.assert_delegation_not_expired(unix_timestamp)?;
+
+        if let Some(legacy_nft_vote_record_info) = legacy_nft_vote_record_info {
+            assert_legacy_nft_vote_record_is_empty(legacy_nft_vote_record_info, &proposal, &data.nft_mint)?;
+        }
 
         // Note: proposal.governing_token_mint must match voter_weight_record.governing_token_mint
         // We don't verify it here because spl-gov does the check in cast_vote
@@ -107,57 +331,290 @@ pub fn cast_nft_vote<'a, 'b, 'c, 'info>(
 
         // Note: Once the NFT plugin is enabled the governing_token_mint is used only as identity
         // for the voting population and the tokens of that mint are no longer used
-        let nft_vote_record = NftVoteRecord {
-            account_discriminator: NftVoteRecord::ACCOUNT_DISCRIMINATOR,
-            proposal,
-            nft_mint: data.nft_mint.clone(),
-            governing_token_owner,
-            reserved: [0; 8],
-        };
-
-        // Anchor doesn't natively support dynamic account creation using remaining_accounts
-        // and we have to take it on the manual drive
-        create_and_serialize_account_signed(
-            &ctx.accounts.payer.to_account_info(),
-            nft_vote_record_info,
-            &nft_vote_record,
-            &get_nft_vote_record_seeds(&proposal, &data.nft_mint),
-            &id(),
-            &ctx.accounts.system_program.to_account_info(),
-            &rent,
-            0
-        )?;
+        if simulate_only {
+            // create_and_serialize_account_with_rent_escrow is not invoked and we have to validate the PDA explicitly
+            require_keys_eq!(
+                get_nft_vote_record_address(&registrar.key(), &proposal, &data.nft_mint),
+                *nft_vote_record_info.key,
+                NftVoterError::InvalidNftVoteRecord
+            );
+        }
 
         // adding this is the close the account after cpi transaction
         // https://solana.stackexchange.com/questions/4481/error-processing-instruction-0-sum-of-account-balances-before-and-after-instruc
         // https://solana.stackexchange.com/questions/4519/anchor-error-error-processing-instruction-0-sum-of-account-balances-before-and
         to_closed_accounts.push(nft_action_ticket_info.to_account_info());
         unique_nft_action_tickets.push(nft_action_ticket_info.key());
-        voter_weight = voter_weight.checked_add(data.weight).unwrap();
+
+        if let Some(collection_stats_info) = collection_stats_info {
+            // Note: The correct PDA of the CollectionStats is validated in create_and_serialize_account_with_rent_escrow
+            if collection_stats_info.data_is_empty() {
+                if simulate_only {
+                    require_keys_eq!(
+                        get_collection_stats_address(&registrar.key(), &data.collection),
+                        *collection_stats_info.key,
+                        NftVoterError::InvalidCollectionStats
+                    );
+                } else {
+                    create_and_serialize_account_with_rent_escrow(
+                        &ctx.accounts.payer.to_account_info(),
+                        voter_rent_escrow_info,
+                        collection_stats_info,
+                        &CollectionStats::new(registrar.key(), data.collection),
+                        &get_collection_stats_seeds(&registrar.key(), &data.collection),
+                        &ctx.accounts.system_program.to_account_info(),
+                        &rent
+                    )?;
+                }
+            }
+
+            let mut collection_stats = if simulate_only && collection_stats_info.data_is_empty() {
+                CollectionStats::new(registrar.key(), data.collection)
+            } else {
+                get_collection_stats_data_for_registrar_and_collection(
+                    collection_stats_info,
+                    &registrar.key(),
+                    &data.collection
+                )?
+            };
+
+            collection_stats.record_vote(&governing_token_owner, current_slot)?;
+
+            if !simulate_only {
+                collection_stats_info.data
+                    .borrow_mut()
+                    .copy_from_slice(&collection_stats.try_to_vec()?);
+            }
+        }
+
+        // The NFTs of expired (or pruned) collections don't contribute any weight
+        // even when the tickets were created before the collection expired
+        let nft_weight = if registrar.is_collection_active(registrar_extension, &data.collection, unix_timestamp) {
+            data.weight
+        } else {
+            0
+        };
+
+        let mut nft_vote_weight = collection_weight_tally.add_asset(
+            registrar_extension,
+            &data.collection,
+            nft_weight
+        );
+        let mut nft_collection_weight = None;
+
+        if
+            let (Some(max_collection_weight), Some(collection_vote_weight_record_info)) = (
+                max_collection_weight,
+                collection_vote_weight_record_info,
+            )
+        {
+            // Note: The correct PDA of the CollectionVoteWeightRecord is validated in create_and_serialize_account_with_rent_escrow
+            if collection_vote_weight_record_info.data_is_empty() {
+                if simulate_only {
+                    require_keys_eq!(
+                        get_collection_vote_weight_record_address(
+                            &registrar.key(),
+                            &proposal,
+                            &data.collection
+                        ),
+                        *collection_vote_weight_record_info.key,
+                        NftVoterError::InvalidCollectionVoteWeightRecord
+                    );
+                } else {
+                    create_and_serialize_account_with_rent_escrow(
+                        &ctx.accounts.payer.to_account_info(),
+                        voter_rent_escrow_info,
+                        collection_vote_weight_record_info,
+                        &CollectionVoteWeightRecord::new(registrar.key(), proposal, data.collection),
+                        &get_collection_vote_weight_record_seeds(
+                            &registrar.key(),
+                            &proposal,
+                            &data.collection
+                        ),
+                        &ctx.accounts.system_program.to_account_info(),
+                        &rent
+                    )?;
+                }
+            }
+
+            let mut collection_vote_weight_record = if
+                simulate_only &&
+                collection_vote_weight_record_info.data_is_empty()
+            {
+                CollectionVoteWeightRecord::new(registrar.key(), proposal, data.collection)
+            } else {
+                get_collection_vote_weight_record_data_for_registrar_proposal_and_collection(
+                    collection_vote_weight_record_info,
+                    &registrar.key(),
+                    &proposal,
+                    &data.collection
+                )?
+            };
+
+            // Once the collection cap is reached the NFTs of the collection don't contribute any weight
+            nft_vote_weight = nft_vote_weight.min(
+                max_collection_weight.saturating_sub(collection_vote_weight_record.voter_weight)
+            );
+
+            collection_vote_weight_record.voter_weight = collection_vote_weight_record.voter_weight
+                .checked_add(nft_vote_weight)
+                .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+            if !simulate_only {
+                collection_vote_weight_record_info.data
+                    .borrow_mut()
+                    .copy_from_slice(&collection_vote_weight_record.try_to_vec()?);
+            }
+
+            nft_collection_weight = Some(NftVoteRecordCollectionWeight {
+                collection: data.collection,
+                voter_weight: nft_vote_weight,
+            });
+        }
+
+        if !simulate_only {
+            // Anchor doesn't natively support dynamic account creation using remaining_accounts
+            // and we have to take it on the manual drive
+            // The NftVoteRecord keeps the weight the NFT added to the CollectionVoteWeightRecord so it can be released on relinquish
+            let registrar_key = registrar.key();
+            let nft_vote_record_seeds = get_nft_vote_record_seeds(
+                &registrar_key,
+                &proposal,
+                &data.nft_mint
+            );
+
+            if let Some(nft_collection_weight) = nft_collection_weight {
+                create_and_serialize_account_with_rent_escrow(
+                    &ctx.accounts.payer.to_account_info(),
+                    voter_rent_escrow_info,
+                    nft_vote_record_info,
+                    &NftVoteRecordWithCollectionWeight::new(
+                        proposal,
+                        data.nft_mint,
+                        governing_token_owner,
+                        vote_kind,
+                        nft_collection_weight
+                    ),
+                    &nft_vote_record_seeds,
+                    &ctx.accounts.system_program.to_account_info(),
+                    &rent
+                )?;
+            } else {
+                create_and_serialize_account_with_rent_escrow(
+                    &ctx.accounts.payer.to_account_info(),
+                    voter_rent_escrow_info,
+                    nft_vote_record_info,
+                    &NftVoteRecord::new(proposal, data.nft_mint, governing_token_owner, vote_kind),
+                    &nft_vote_record_seeds,
+                    &ctx.accounts.system_program.to_account_info(),
+                    &rent
+                )?;
+            }
+        }
+
+        voter_weight = voter_weight
+            .checked_add(nft_vote_weight)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+        contributions.push((data.collection, nft_vote_weight));
     }
 
-    if
-        voter_weight_record.weight_action_target == Some(proposal) &&
-        voter_weight_record.weight_action == Some(VoterWeightAction::CastVote)
-    {
+    if simulate_only {
+        let simulated_voter_weight = if is_accumulating {
+            voter_weight_record.voter_weight
+                .checked_add(voter_weight)
+                .ok_or(NftVoterError::ArithmeticOverflow)?
+        } else {
+            voter_weight
+        };
+
+        return VoterWeightReturnData::new(simulated_voter_weight, contributions.len()).set();
+    }
+
+    if let Some(voter_weight_detail_info) = voter_weight_detail_info {
+        record_voter_weight_detail(
+            voter_weight_detail_info,
+            &voter_weight_record.key(),
+            &contributions,
+            is_accumulating
+        )?;
+    }
+
+    let previous_voter_weight_expiry = voter_weight_record.voter_weight_expiry;
+
+    let total_voter_weight = if is_accumulating {
         // If cast_nft_vote is called for the same proposal then we keep accumulating the weight
         // this way cast_nft_vote can be called multiple times in different transactions to allow voting with any number of NFTs
-        voter_weight_record.voter_weight = voter_weight_record.voter_weight
+        voter_weight_record.voter_weight
             .checked_add(voter_weight)
-            .unwrap();
+            .ok_or(NftVoterError::ArithmeticOverflow)?
     } else {
-        voter_weight_record.voter_weight = voter_weight;
-    }
+        voter_weight
+    };
 
-    // The record is only valid as of the current slot
-    voter_weight_record.voter_weight_expiry = Some(Clock::get()?.slot);
+    if registrar_extension.config.require_vote_finalization {
+        // The weight can't be taken by spl-gov until it's finalized using finalize_nft_vote
+        voter_weight_record.voter_weight = total_voter_weight;
+        voter_weight_record.voter_weight_expiry = Some(0);
+        voter_weight_record.weight_state = VoterWeightState::Accumulating;
+    } else {
+        // The record is only valid as of the current slot
+        // The floor weight and the wallet overrides require vote finalization and hence the weight isn't adjusted
+        finalize_voter_weight(
+            voter_weight_record,
+            total_voter_weight,
+            voter_weight_expiry,
+            &VoterWeightAdjustment::default()
+        );
+    }
 
     // The record is only valid for casting vote on the given Proposal
     voter_weight_record.weight_action = Some(VoterWeightAction::CastVote);
     voter_weight_record.weight_action_target = Some(proposal);
 
+    if is_accumulating {
+        sanity::check_voter_weight_expiry(
+            previous_voter_weight_expiry,
+            voter_weight_record.voter_weight_expiry
+        )?;
+    }
+
+    sanity::check_voter_weight(registrar, registrar_extension, voter_weight_record.voter_weight)?;
+
+    if let Some(voter_checkpoint_info) = voter_checkpoint_infos.first() {
+        record_voter_checkpoint(
+            &ctx.accounts.payer.to_account_info(),
+            voter_rent_escrow_info,
+            voter_checkpoint_info,
+            &registrar.key(),
+            &proposal,
+            &governing_token_owner,
+            voter_weight_record.voter_weight,
+            current_slot,
+            &ctx.accounts.system_program.to_account_info(),
+            &rent
+        )?;
+    }
+
     for clased_account in to_closed_accounts.iter() {
         close_nft_action_ticket_account(clased_account, payer)?;
     }
-    Ok(())
+
+    if let Some(memo) = memo {
+        emit!(NftVoteMemo {
+            registrar: registrar.key(),
+            proposal,
+            governing_token_owner,
+            memo,
+        });
+    }
+
+    let registrar_metrics = ctx.accounts.registrar_metrics
+        .as_mut()
+        .ok_or(NftVoterError::RegistrarMetricsRequired)?;
+
+    registrar_metrics.registrar = registrar.key();
+    registrar_metrics.shard = get_registrar_metrics_shard(&governing_token_owner);
+    registrar_metrics.record_votes(contributions.len() as u64, voter_weight, current_slot);
+
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, contributions.len()).set()
 }
@@ -0,0 +1,295 @@
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::*;
+use crate::tools::accounts::close_nft_action_ticket_account;
+use crate::tools::accounts_cursor::AccountsCursor;
+use crate::tools::governance::assert_proposal_action_deadline;
+use anchor_lang::prelude::*;
+use anchor_lang::Accounts;
+use spl_governance::state::{ governance, proposal as spl_proposal };
+
+/// Casts NFT vote on a batch of Proposals of the same Governance using the NFTs verified once
+/// The nft-castVote-tickets are consumed once and NftVoteRecords are created for every Proposal
+/// which cuts the verification cost (ex. compressed NFT proofs) by the number of Proposals
+///
+/// The weight is stored in MultiProposalVote and it's moved to VoterWeightRecord for one Proposal at a time
+/// by select_multi_proposal_vote which has to be executed inside the same transaction as spl-gov.CastVote
+/// Casting a multi proposal vote again replaces the Proposals the weight wasn't selected for yet
+///
+/// The remaining accounts are the Proposals (in the order of the proposals argument) followed by
+/// the nft_action_ticket and the NftVoteRecord of every Proposal for each NFT
+/// When RegistrarExtension.config.record_voter_checkpoints is set the VoterCheckpoints of the Proposals (in the same order)
+/// are supplied after the Proposals
+///
+/// The vote_kind is recorded in the NftVoteRecords of all the Proposals and the spl-gov Votes should be of the same kind
+///
+/// Note: Multi proposal votes are not accumulative and all the NFTs must be supplied in a single instruction
+/// They are not supported when the collection weight per Proposal is capped or the collection stats are tracked
+/// and for Proposals which started voting before the Registrar was migrated and can have legacy NftVoteRecords
+/// (the votes on such Proposals must be cast using cast_nft_vote)
+///
+/// The NftVoteRecords and the weight granted for each of the Proposals are counted in the RegistrarMetrics shard of the voter
+#[derive(Accounts)]
+pub struct CastNftVoteMulti<'info> {
+    /// The NFT voting registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// TokenOwnerRecord of the voter who casts the vote
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    #[account(owner = registrar.governance_program_id)]
+    voter_token_owner_record: UncheckedAccount<'info>,
+
+    /// Authority of the voter who casts the vote
+    /// It can be either governing_token_owner or its delegate and must sign this instruction
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"multi-proposal-vote".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        payer = payer,
+        space = MultiProposalVote::get_space()
+    )]
+    pub multi_proposal_vote: Account<'info, MultiProposalVote>,
+
+    /// The account which pays for the transaction
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account all the Proposals are for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// RegistrarMetrics shard of the voter the votes are counted in
+    #[account(
+        init_if_needed,
+        seeds = [ b"registrar-metrics".as_ref(),
+                registrar.key().as_ref(),
+                &[get_registrar_metrics_shard(&voter_weight_record.governing_token_owner)]],
+        bump,
+        payer = payer,
+        space = RegistrarMetrics::get_space()
+    )]
+    pub registrar_metrics: Account<'info, RegistrarMetrics>,
+}
+
+/// Casts vote with the NFTs on the given Proposals
+pub fn cast_nft_vote_multi<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CastNftVoteMulti<'info>>,
+    proposals: Vec<Pubkey>,
+    vote_kind: NftVoteKind
+) -> Result<()> {
+    require!(
+        !proposals.is_empty() && proposals.len() <= MAX_MULTI_VOTE_PROPOSALS,
+        NftVoterError::InvalidMultiProposalVote
+    );
+
+    for (proposal_idx, proposal) in proposals.iter().enumerate() {
+        require!(!proposals[..proposal_idx].contains(proposal), NftVoterError::InvalidProposal);
+    }
+
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+
+    // The collection caps and stats require accounts per NFT and Proposal which would defeat the purpose of the batch
+    require!(
+        registrar_extension.config.max_collection_weight_per_proposal == 0 &&
+            !registrar_extension.config.track_collection_stats,
+        NftVoterError::MultiProposalVoteNotSupported
+    );
+
+    let governing_token_owner = resolve_governing_token_owner(
+        registrar,
+        &ctx.accounts.voter_token_owner_record,
+        &ctx.accounts.voter_authority,
+        &ctx.accounts.voter_weight_record
+    )?;
+
+    // Ensure the Governance belongs to Registrar.realm
+    let governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let mut accounts_cursor = AccountsCursor::new(ctx.remaining_accounts);
+    let proposal_infos = accounts_cursor.take(proposals.len(), "proposal")?;
+    let voter_checkpoint_infos = accounts_cursor.take(
+        if registrar_extension.config.record_voter_checkpoints { proposals.len() } else { 0 },
+        "voter_checkpoint"
+    )?;
+
+    for (proposal, proposal_info) in proposals.iter().zip(proposal_infos.iter()) {
+        require_keys_eq!(*proposal, *proposal_info.key, NftVoterError::InvalidProposal);
+
+        let target_proposal = spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+            &registrar.governance_program_id,
+            proposal_info,
+            &ctx.accounts.governance.key(),
+            &registrar.governing_token_mint
+        )?;
+
+        assert_proposal_action_deadline(
+            &target_proposal,
+            &governance.config,
+            &VoterWeightAction::CastVote
+        )?;
+
+        require!(
+            !registrar_extension.has_legacy_nft_vote_records(target_proposal.voting_at),
+            NftVoterError::MultiProposalVoteNotSupported
+        );
+    }
+
+    let payer = &ctx.accounts.payer.to_account_info();
+    let system_program = &ctx.accounts.system_program.to_account_info();
+    let rent = Rent::get()?;
+    let current_slot = Clock::get()?.slot;
+    let unix_timestamp = Clock::get()?.unix_timestamp;
+
+    let mut voter_weight = 0u64;
+    let mut to_closed_accounts = vec![];
+    let mut unique_nft_action_tickets: Vec<Pubkey> = vec![];
+    let mut collection_weight_tally = CollectionWeightTally::default();
+
+    while !accounts_cursor.is_empty() {
+        let nft_action_ticket_info = accounts_cursor.next("nft_action_ticket")?;
+        let nft_vote_record_infos = accounts_cursor.take(proposals.len(), "nft_vote_record")?;
+
+        if unique_nft_action_tickets.contains(nft_action_ticket_info.key) {
+            return Err(NftVoterError::DuplicatedNftDetected.into());
+        }
+
+        require!(!nft_action_ticket_info.data_is_empty(), NftVoterError::NftFailedVerification);
+        require!(*nft_action_ticket_info.owner == crate::id(), NftVoterError::InvalidAccountOwner);
+
+        // It ensures the NftActionTicket is for (nft-{action}-ticket,registrar,governing_token_owner,nft_mint) seeds
+        let data = NftActionTicket::try_from_slice(&nft_action_ticket_info.data.borrow())?;
+        let ticket_type = format!("nft-{}-ticket", &VoterWeightAction::CastVote).to_string();
+        let nft_action_ticket_address = get_nft_action_ticket_address(
+            &ticket_type,
+            &registrar.key(),
+            &governing_token_owner,
+            &data.nft_mint
+        ).0;
+
+        require!(
+            data.governing_token_owner == governing_token_owner &&
+                nft_action_ticket_address == *nft_action_ticket_info.key,
+            NftVoterError::InvalidNftTicket
+        );
+
+        data.assert_consumable(current_slot, registrar_extension.config.weight_record_min_age_slots)?;
+        data.assert_delegation_not_expired(unix_timestamp)?;
+
+        // The NftVoteRecords ensure the NFT can't be used again for any of the Proposals
+        // Note: The correct PDAs of the NftVoteRecords are validated in create_and_serialize_account_with_rent_escrow
+        for (proposal, nft_vote_record_info) in proposals.iter().zip(nft_vote_record_infos.iter()) {
+            require!(nft_vote_record_info.data_is_empty(), NftVoterError::NftAlreadyVoted);
+
+            create_and_serialize_account_with_rent_escrow(
+                payer,
+                None,
+                nft_vote_record_info,
+                &NftVoteRecord::new(*proposal, data.nft_mint, governing_token_owner, vote_kind),
+                &get_nft_vote_record_seeds(&registrar.key(), proposal, &data.nft_mint),
+                system_program,
+                &rent
+            )?;
+        }
+
+        to_closed_accounts.push(nft_action_ticket_info.to_account_info());
+        unique_nft_action_tickets.push(nft_action_ticket_info.key());
+
+        // The NFTs of expired (or pruned) collections don't contribute any weight
+        let nft_weight = if registrar.is_collection_active(registrar_extension, &data.collection, unix_timestamp) {
+            data.weight
+        } else {
+            0
+        };
+
+        let nft_vote_weight = collection_weight_tally.add_asset(
+            registrar_extension,
+            &data.collection,
+            nft_weight
+        );
+
+        voter_weight = voter_weight
+            .checked_add(nft_vote_weight)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+    }
+
+    if registrar_extension.config.max_assets_per_tx > 0 {
+        require!(
+            unique_nft_action_tickets.len() <= (registrar_extension.config.max_assets_per_tx as usize),
+            NftVoterError::TooManyAssetsPerTransaction
+        );
+    }
+
+    sanity::check_voter_weight(registrar, registrar_extension, voter_weight)?;
+
+    for closed_account in to_closed_accounts.iter() {
+        close_nft_action_ticket_account(closed_account, payer)?;
+    }
+
+    for (proposal, voter_checkpoint_info) in proposals.iter().zip(voter_checkpoint_infos.iter()) {
+        record_voter_checkpoint(
+            payer,
+            None,
+            voter_checkpoint_info,
+            &registrar.key(),
+            proposal,
+            &governing_token_owner,
+            voter_weight,
+            current_slot,
+            system_program,
+            &rent
+        )?;
+    }
+
+    let proposals_count = proposals.len() as u64;
+    let multi_proposal_vote = &mut ctx.accounts.multi_proposal_vote;
+
+    multi_proposal_vote.registrar = registrar.key();
+    multi_proposal_vote.governing_token_owner = governing_token_owner;
+    multi_proposal_vote.voter_weight = voter_weight;
+    multi_proposal_vote.proposals = proposals;
+
+    let registrar_metrics = &mut ctx.accounts.registrar_metrics;
+
+    registrar_metrics.registrar = registrar.key();
+    registrar_metrics.shard = get_registrar_metrics_shard(&governing_token_owner);
+    registrar_metrics.record_votes(
+        (unique_nft_action_tickets.len() as u64).saturating_mul(proposals_count),
+        voter_weight.saturating_mul(proposals_count),
+        current_slot
+    );
+
+    VoterWeightReturnData::new(voter_weight, unique_nft_action_tickets.len()).set()
+}
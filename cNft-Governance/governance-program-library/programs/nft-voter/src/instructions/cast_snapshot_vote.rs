@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::{ governance, proposal as spl_proposal };
+
+use crate::error::NftVoterError;
+use crate::instructions::voter_accounts::*;
+use crate::state::*;
+use crate::tools::governance::assert_proposal_action_deadline;
+
+/// Casts vote on the Proposal with the voter weight of the voter in the WeightSnapshot of the Proposal
+/// VoterWeightRecord is set to the weight proven against WeightSnapshot.holdings_root
+/// and hence the instruction has to be executed inside the same transaction as spl-gov.CastVote
+///
+/// The optional VoterRegistration and WalletWeightOverride PDAs of the voter are supplied as remaining accounts
+/// the same way as for finalize_nft_vote and the snapshot weight is adjusted by the floor weight and the wallet override
+///
+/// Note: The voter can't vote twice with the same weight because spl-gov allows a single VoteRecord per voter
+#[derive(Accounts)]
+#[instruction(proposal: Pubkey)]
+pub struct CastSnapshotVote<'info> {
+    pub voter: VoterAccounts<'info>,
+
+    #[account(
+        seeds = [ b"weight-snapshot".as_ref(),
+                voter.registrar.key().as_ref(),
+                proposal.as_ref()],
+        bump,
+        constraint = voter.voter_weight_record.governing_token_owner == voter.voter_authority.key()
+        @ NftVoterError::InvalidTokenOwnerForVoterWeightRecord
+    )]
+    pub weight_snapshot: Account<'info, WeightSnapshot>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                voter.registrar.key().as_ref(),
+                voter.voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = voter.registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal the vote is cast on which must match the proposal argument
+    #[account(
+        owner = voter.registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+}
+
+pub fn cast_snapshot_vote(
+    ctx: Context<CastSnapshotVote>,
+    proposal: Pubkey,
+    voter_weight: u64,
+    proof: Vec<[u8; 32]>
+) -> Result<()> {
+    let registrar = &ctx.accounts.voter.registrar;
+    let registrar_extension = &ctx.accounts.voter.registrar_extension;
+
+    // Ensure the Governance belongs to Registrar.realm
+    let governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let target_proposal = spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    assert_proposal_action_deadline(
+        &target_proposal,
+        &governance.config,
+        &VoterWeightAction::CastVote
+    )?;
+
+    ctx.accounts.weight_snapshot.assert_voter_weight(
+        &ctx.accounts.voter.voter_authority.key(),
+        voter_weight,
+        &proof
+    )?;
+
+    let voter_weight_expiry = Clock::get()?.slot;
+
+    let (voter_weight_adjustment, _) = split_voter_weight_adjustment_accounts(
+        registrar,
+        registrar_extension,
+        &ctx.accounts.voter.voter_authority.key(),
+        ctx.remaining_accounts
+    )?;
+
+    let voter_weight_record = &mut ctx.accounts.voter.voter_weight_record;
+
+    // The record is only valid as of the current slot and for casting vote on the snapshot Proposal
+    finalize_voter_weight(
+        voter_weight_record,
+        voter_weight,
+        voter_weight_expiry,
+        &voter_weight_adjustment
+    );
+    voter_weight_record.weight_action = Some(VoterWeightAction::CastVote);
+    voter_weight_record.weight_action_target = Some(proposal);
+
+    // No NFTs are counted by snapshot votes
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, 0).set()
+}
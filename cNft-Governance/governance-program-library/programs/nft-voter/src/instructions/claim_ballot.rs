@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::{ program::SplAccountCompression, Noop };
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::program_ids::Bubblegum;
+
+/// Claims a ballot of the Campaign for a wallet in the Campaign allowlist
+/// The ballot is minted to the claimant and the CampaignClaim of the claimant prevents claiming another ballot
+///
+/// The claimant must supply allowlist_proof of its wallet in Campaign.allowlist_root
+#[derive(Accounts)]
+pub struct ClaimBallot<'info> {
+    #[account(
+        mut,
+        has_one = ballot_tree @ NftVoterError::InvalidBallotTree
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        mut,
+        seeds = [ b"ballot-tree".as_ref(),
+                campaign.registrar.as_ref(),
+                merkle_tree.key().as_ref()],
+        bump,
+        has_one = merkle_tree @ NftVoterError::InvalidBallotTree
+    )]
+    pub ballot_tree: Account<'info, BallotTree>,
+
+    #[account(
+        init,
+        seeds = [ b"campaign-claim".as_ref(),
+                campaign.key().as_ref(),
+                claimant.key().as_ref()],
+        bump,
+        payer = payer,
+        space = CampaignClaim::get_space()
+    )]
+    pub campaign_claim: Account<'info, CampaignClaim>,
+
+    /// The wallet the ballot is minted to
+    pub claimant: Signer<'info>,
+
+    /// CHECK: Bubblegum TreeConfig of the tree validated by Bubblegum
+    #[account(mut)]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Merkle tree of the BallotTree validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_ballot(ctx: Context<ClaimBallot>, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+    let claimant = ctx.accounts.claimant.key();
+
+    ctx.accounts.campaign.assert_claimant_allowed(&claimant, &allowlist_proof)?;
+
+    let ballot_metadata = ctx.accounts.ballot_tree.get_ballot_metadata(&ctx.accounts.ballot_tree.key());
+
+    mint_ballot_to(
+        &mut ctx.accounts.ballot_tree,
+        ctx.bumps.ballot_tree,
+        ballot_metadata,
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.claimant.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.bubblegum_program.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.ballots_claimed = campaign.ballots_claimed
+        .checked_add(1)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+    let campaign_claim = &mut ctx.accounts.campaign_claim;
+
+    campaign_claim.campaign = campaign.key();
+    campaign_claim.claimant = claimant;
+    campaign_claim.claimed_at = Clock::get()?.slot;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Closes the expired ColdWalletLink (delegation of the cold wallet compressed NFTs to the hot wallet)
+/// and returns its rent to the hot wallet
+/// The instruction is permissionless to let anybody clean up the standing delegations once they expire
+///
+/// Note: Revoked links can only be closed once they expire because until then the account rejects
+/// the replays of the previous cold wallet signatures. Once expired, all the previous signatures
+/// have expiries in the past and they are rejected by link_cold_wallet without the account
+#[derive(Accounts)]
+pub struct CloseExpiredDelegation<'info> {
+    #[account(mut, close = hot_wallet)]
+    pub cold_wallet_link: Account<'info, ColdWalletLink>,
+
+    /// CHECK: The hot wallet of the link which receives the rent
+    #[account(mut, address = cold_wallet_link.hot_wallet @ NftVoterError::InvalidColdWalletLink)]
+    pub hot_wallet: UncheckedAccount<'info>,
+}
+
+pub fn close_expired_delegation(ctx: Context<CloseExpiredDelegation>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.cold_wallet_link.expires_at,
+        NftVoterError::ColdWalletLinkNotExpired
+    );
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use spl_governance::state::{ proposal, realm };
+use spl_governance_tools::account::dispose_account;
+
+/// Disposes NftVoteRecords of Proposals of a deleted Governance
+/// The records can't be relinquished or reclaimed because the Governance and Proposal accounts
+/// they are validated against no longer exist and the instruction lets a realm wind-down clean up the plugin state
+///
+/// The instruction must be signed by the realm authority (ex. executed by a governance Proposal)
+/// and the records are orphaned when either the Proposal or its Governance no longer exists
+/// The NftVoteRecords are supplied as remaining accounts each followed by its governing_token_owner
+/// which receives the rent of the record
+///
+/// Note: NftVoteRecord doesn't store the payer of the rent and it's returned to the voter who cast the vote
+/// Only the records of the Registrar can be disposed and the legacy records without the registrar in the PDA seeds
+/// must be relinquished by the voters
+#[derive(Accounts)]
+pub struct CloseOrphanedNftVoteRecords<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    /// CHECK: The deleted Governance account the Proposal was for
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: The Proposal the NftVoteRecords were cast on
+    /// It's validated against the Governance only when it still exists
+    pub proposal: UncheckedAccount<'info>,
+}
+
+/// Returns true if the given spl-governance account was deleted (closed or reassigned)
+fn is_deleted(account_info: &AccountInfo, governance_program_id: &Pubkey) -> bool {
+    account_info.owner != governance_program_id || account_info.data_is_empty()
+}
+
+pub fn close_orphaned_nft_vote_records(ctx: Context<CloseOrphanedNftVoteRecords>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority == Some(ctx.accounts.realm_authority.key()),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    let governance_program_id = &registrar.governance_program_id;
+
+    // A Proposal which still exists must belong to the given Governance and the Governance must have been deleted
+    if !is_deleted(&ctx.accounts.proposal, governance_program_id) {
+        let _proposal = proposal::get_proposal_data_for_governance(
+            governance_program_id,
+            &ctx.accounts.proposal,
+            &ctx.accounts.governance.key(),
+        )?;
+
+        require!(
+            is_deleted(&ctx.accounts.governance, governance_program_id),
+            NftVoterError::NftVoteRecordNotOrphaned
+        );
+    }
+
+    let mut remaining_accounts = ctx.remaining_accounts.chunks_exact(2);
+
+    for accounts in &mut remaining_accounts {
+        let (nft_vote_record_info, governing_token_owner_info) = (&accounts[0], &accounts[1]);
+
+        let nft_vote_record = get_nft_vote_record_data_for_proposal_and_token_owner(
+            nft_vote_record_info,
+            &ctx.accounts.proposal.key(),
+            governing_token_owner_info.key
+        )?;
+
+        // Ensure the record was cast using the Registrar
+        require_keys_eq!(
+            *nft_vote_record_info.key,
+            get_nft_vote_record_address(
+                &registrar.key(),
+                &nft_vote_record.proposal,
+                &nft_vote_record.nft_mint
+            ),
+            NftVoterError::InvalidNftVoteRecord
+        );
+
+        dispose_account(nft_vote_record_info, governing_token_owner_info)?;
+    }
+
+    require!(
+        remaining_accounts.remainder().is_empty(),
+        NftVoterError::InvalidTokenOwnerForNftVoteRecord
+    );
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Closes VoterRentEscrow and returns the remaining lamports (including its rent) to the beneficiary
+#[derive(Accounts)]
+pub struct CloseVoterRentEscrow<'info> {
+    #[account(
+        mut,
+        close = beneficiary,
+        constraint = voter_rent_escrow.governing_token_owner == governing_token_owner.key()
+        @ NftVoterError::InvalidVoterRentEscrow,
+    )]
+    pub voter_rent_escrow: Account<'info, VoterRentEscrow>,
+
+    /// The voter who owns the escrow
+    pub governing_token_owner: Signer<'info>,
+
+    /// CHECK: Any account chosen by the voter to receive the lamports
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
+pub fn close_voter_rent_escrow(_ctx: Context<CloseVoterRentEscrow>) -> Result<()> {
+    Ok(())
+}
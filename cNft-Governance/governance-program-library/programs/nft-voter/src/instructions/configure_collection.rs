@@ -5,22 +5,38 @@ use anchor_lang::{
 };
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
 use spl_governance::state::realm;
 
+use mpl_token_metadata::types::CollectionDetails;
+use std::convert::TryFrom;
+
 use crate::error::NftVoterError;
-use crate::state::{max_voter_weight_record::MaxVoterWeightRecord, CollectionConfig, Registrar};
+use crate::sanity;
+use crate::state::{
+    assert_collection_account, max_voter_weight_record::MaxVoterWeightRecord, CollectionConfig, Registrar,
+    RegistrarExtension,
+};
+use crate::tools::accounts_cursor::AccountsCursor;
+use crate::tools::token_metadata::get_token_metadata_for_mint;
 
 /// Configures NFT voting collection which defines what NFTs can be used for governances
 /// and what weight they have
 /// The instruction updates MaxVoterWeightRecord which is used by spl-gov to determine max voting power
 /// used to calculate voting quorum    
+///
+/// When size is 0 the size is read from CollectionDetails of the collection metadata
+/// which must be supplied as the only remaining account
+/// It keeps MaxVoterWeightRecord in line with the on-chain size of sized MPL collections
 #[derive(Accounts)]
 pub struct ConfigureCollection<'info> {
     /// Registrar for which we configure this Collection
     #[account(mut)]
     pub registrar: Account<'info, Registrar>,
 
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
     #[account(
        address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
        owner = registrar.governance_program_id
@@ -31,8 +47,9 @@ pub struct ConfigureCollection<'info> {
     /// Authority of the Realm must sign and match Realm.authority
     pub realm_authority: Signer<'info>,
 
-    // Collection which is going to be used for voting
-    pub collection: Account<'info, Mint>,
+    /// Collection which is going to be used for voting
+    /// CHECK: It must be an MPL collection Mint or a Metaplex Core collection when RegistrarExtension.config.allow_core_assets is set
+    pub collection: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -45,14 +62,25 @@ pub struct ConfigureCollection<'info> {
     pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
 }
 
-pub fn configure_collection(
-    ctx: Context<ConfigureCollection>,
+pub fn configure_collection<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ConfigureCollection<'info>>,
     weight: u64,
     size: u32,
 ) -> Result<()> {
+    let size = if size == 0 {
+        let mut accounts_cursor = AccountsCursor::new(ctx.remaining_accounts);
+        let collection_metadata_info = accounts_cursor.next("collection_metadata")?;
+        accounts_cursor.finish()?;
+
+        get_collection_details_size(collection_metadata_info, &ctx.accounts.collection.key())?
+    } else {
+        size
+    };
+
     require!(size > 0, NftVoterError::InvalidCollectionSize);
 
     let registrar = &mut ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
 
     let realm = realm::get_realm_data_for_governing_token_mint(
         &registrar.governance_program_id,
@@ -75,25 +103,9 @@ pub fn configure_collection(
 
     let collection = &ctx.accounts.collection;
 
-    let collection_config = CollectionConfig {
-        collection: collection.key(),
-        weight,
-        reserved: [0; 8],
-        size,
-    };
-
-    let collection_idx = registrar
-        .collection_configs
-        .iter()
-        .position(|cc| cc.collection == collection.key());
+    assert_collection_account(&registrar_extension.config, collection)?;
 
-    if let Some(collection_idx) = collection_idx {
-        registrar.collection_configs[collection_idx] = collection_config;
-    } else {
-        // Note: In the current runtime version push() would throw an error if we exceed
-        // max_collections specified when the Registrar was created
-        registrar.collection_configs.push(collection_config);
-    }
+    registrar.set_collection_config(CollectionConfig::new(collection.key(), weight, size));
 
     // TODO: if weight == 0 then remove the collection from config
     // Currently if weight is set to 0 then the collection won't be removed but it won't have any governance power
@@ -101,14 +113,23 @@ pub fn configure_collection(
     // Update MaxVoterWeightRecord based on max voting power of the collections
     let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
 
-    max_voter_weight_record.max_voter_weight = registrar
-        .collection_configs
-        .iter()
-        .try_fold(0u64, |sum, cc| sum.checked_add(cc.get_max_weight()))
-        .unwrap();
+    sanity::check_max_voter_weight(registrar, registrar_extension)?;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
 
     // The weight never expires and only changes when collections are configured
     max_voter_weight_record.max_voter_weight_expiry = None;
 
     Ok(())
 }
+
+/// Returns the size of the sized MPL collection from CollectionDetails of its metadata
+fn get_collection_details_size(collection_metadata_info: &AccountInfo, collection: &Pubkey) -> Result<u32> {
+    let collection_metadata = get_token_metadata_for_mint(collection_metadata_info, collection)?;
+
+    match collection_metadata.collection_details {
+        Some(CollectionDetails::V1 { size }) =>
+            u32::try_from(size).map_err(|_| NftVoterError::InvalidCollectionSize.into()),
+        None => err!(NftVoterError::MissingCollectionDetails),
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, RegistrarExtension };
+
+/// Configures the expiry of an already configured NFT voting collection
+/// Once expired the NFTs of the collection have no governance power (ex. seasonal membership passes)
+/// and the collection can be removed from the Registrar using permissionless prune_expired_collections
+#[derive(Accounts)]
+pub struct ConfigureCollectionExpiry<'info> {
+    /// Registrar for which we configure the collection expiry
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar and the expiry of its collections
+    #[account(mut, has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+}
+
+/// expires_at is the unix timestamp at which the collection expires and 0 means the collection never expires
+pub fn configure_collection_expiry(
+    ctx: Context<ConfigureCollectionExpiry>,
+    collection: Pubkey,
+    expires_at: i64
+) -> Result<()> {
+    require!(expires_at >= 0, NftVoterError::InvalidCollectionExpiry);
+
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    registrar.get_collection_config(&collection)?;
+
+    let mut collection_extension = registrar_extension.get_collection_extension(&collection);
+    collection_extension.expires_at = expires_at;
+
+    registrar_extension.set_collection_extension(collection_extension);
+
+    Ok(())
+}
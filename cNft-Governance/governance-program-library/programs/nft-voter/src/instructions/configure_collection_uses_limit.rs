@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, RegistrarExtension };
+
+/// Configures whether the NFTs of an already configured NFT voting collection are limited by their Metaplex Uses
+/// The compressed NFTs of a Uses limited collection must be supplied with their metadata and Uses
+/// so each vote consumes a use tracked by AssetUsesRecord
+#[derive(Accounts)]
+pub struct ConfigureCollectionUsesLimit<'info> {
+    /// Registrar for which we configure the collection Uses limit
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar and the Uses limits of its collections
+    #[account(mut, has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+}
+
+pub fn configure_collection_uses_limit(
+    ctx: Context<ConfigureCollectionUsesLimit>,
+    collection: Pubkey,
+    uses_limited: bool
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    registrar.get_collection_config(&collection)?;
+
+    let mut collection_extension = registrar_extension.get_collection_extension(&collection);
+    collection_extension.uses_limited = uses_limited;
+
+    registrar_extension.set_collection_extension(collection_extension);
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::{
+    max_voter_weight_record::MaxVoterWeightRecord,
+    CollectionWeightStrategy,
+    Registrar,
+    RegistrarExtension,
+};
+
+/// Configures the weight strategy of an already configured NFT voting collection
+/// The strategy defines how the weight of the NFTs of the collection held by a single voter is aggregated
+/// Note: With non linear strategies the voter weight can't be accumulated by several cast_nft_vote instructions
+///
+/// The instruction updates MaxVoterWeightRecord which is used by spl-gov to determine max voting power
+#[derive(Accounts)]
+pub struct ConfigureCollectionWeightStrategy<'info> {
+    /// Registrar for which we configure the collection weight strategy
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar and the weight strategies of its collections
+    #[account(mut, has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = max_voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+
+        constraint = max_voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+}
+
+pub fn configure_collection_weight_strategy(
+    ctx: Context<ConfigureCollectionWeightStrategy>,
+    collection: Pubkey,
+    weight_strategy: CollectionWeightStrategy
+) -> Result<()> {
+    require!(weight_strategy.is_valid(), NftVoterError::InvalidCollectionWeightStrategy);
+
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    registrar.get_collection_config(&collection)?;
+
+    let mut collection_extension = registrar_extension.get_collection_extension(&collection);
+    collection_extension.weight_strategy = weight_strategy;
+
+    registrar_extension.set_collection_extension(collection_extension);
+
+    // Update MaxVoterWeightRecord based on max voting power of the collections
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    sanity::check_max_voter_weight(registrar, registrar_extension)?;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    // The weight never expires and only changes when collections are configured
+    max_voter_weight_record.max_voter_weight_expiry = None;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::{
+    assert_collection_account,
+    max_voter_weight_record::MaxVoterWeightRecord,
+    CollectionConfig,
+    CollectionConfigArgs,
+    Registrar,
+    RegistrarExtension,
+    MAX_COLLECTIONS_PER_BATCH,
+};
+
+/// Configures up to MAX_COLLECTIONS_PER_BATCH NFT voting collections in a single instruction
+/// It's equivalent to executing configure_collection for each of the collections
+/// and it allows to onboard many collections using a single Proposal
+///
+/// The collection mints (or Metaplex Core collections) must be supplied as remaining accounts
+/// in the same order as the collections args
+#[derive(Accounts)]
+pub struct ConfigureCollectionsBatch<'info> {
+    /// Registrar for which we configure the Collections
+    #[account(mut)]
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = max_voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+
+        constraint = max_voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+}
+
+pub fn configure_collections_batch<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ConfigureCollectionsBatch<'info>>,
+    collections: Vec<CollectionConfigArgs>
+) -> Result<()> {
+    require!(
+        !collections.is_empty() &&
+            collections.len() <= MAX_COLLECTIONS_PER_BATCH &&
+            collections.len() == ctx.remaining_accounts.len(),
+        NftVoterError::InvalidCollectionsBatch
+    );
+
+    let registrar = &mut ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    for (args, collection_info) in collections.iter().zip(ctx.remaining_accounts.iter()) {
+        require!(args.size > 0, NftVoterError::InvalidCollectionSize);
+
+        require_keys_eq!(
+            args.collection,
+            *collection_info.key,
+            NftVoterError::InvalidCollectionsBatch
+        );
+
+        // Ensure the collection is a Mint (or Core collection) the same way as configure_collection does
+        assert_collection_account(&registrar_extension.config, collection_info)?;
+
+        registrar.set_collection_config(CollectionConfig::new(args.collection, args.weight, args.size));
+    }
+
+    // Update MaxVoterWeightRecord based on max voting power of the collections
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    sanity::check_max_voter_weight(registrar, registrar_extension)?;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    // The weight never expires and only changes when collections are configured
+    max_voter_weight_record.max_voter_weight_expiry = None;
+
+    Ok(())
+}
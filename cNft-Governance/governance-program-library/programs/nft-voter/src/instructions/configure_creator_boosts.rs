@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::{
+    max_voter_weight_record::MaxVoterWeightRecord,
+    CreatorBoost,
+    Registrar,
+    RegistrarExtension,
+    MAX_CREATOR_BOOSTS,
+};
+
+/// Configures the creator boosts of an already configured NFT voting collection
+/// NFTs of the collection with a verified creator matching one of the boosts get the extra weight
+/// The instruction updates MaxVoterWeightRecord which is used by spl-gov to determine max voting power
+/// assuming all NFTs of the collection get the highest boost
+#[derive(Accounts)]
+pub struct ConfigureCreatorBoosts<'info> {
+    /// Registrar for which we configure the creator boosts
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar and the creator boosts of its collections
+    #[account(mut, has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = max_voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+
+        constraint = max_voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+}
+
+pub fn configure_creator_boosts(
+    ctx: Context<ConfigureCreatorBoosts>,
+    collection: Pubkey,
+    creator_boosts: Vec<CreatorBoost>
+) -> Result<()> {
+    require!(creator_boosts.len() <= MAX_CREATOR_BOOSTS, NftVoterError::InvalidCreatorBoosts);
+
+    for (i, creator_boost) in creator_boosts.iter().enumerate() {
+        require!(
+            creator_boost.creator != Pubkey::default() &&
+                !creator_boosts[..i].iter().any(|cb| cb.creator == creator_boost.creator),
+            NftVoterError::InvalidCreatorBoosts
+        );
+    }
+
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    registrar.get_collection_config(&collection)?;
+
+    let mut collection_extension = registrar_extension.get_collection_extension(&collection);
+
+    collection_extension.creator_boosts = [CreatorBoost::default(); MAX_CREATOR_BOOSTS];
+    collection_extension.creator_boosts[..creator_boosts.len()].copy_from_slice(&creator_boosts);
+
+    registrar_extension.set_collection_extension(collection_extension);
+
+    // Update MaxVoterWeightRecord based on max voting power of the collections
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    sanity::check_max_voter_weight(registrar, registrar_extension)?;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    // The weight never expires and only changes when collections are configured
+    max_voter_weight_record.max_voter_weight_expiry = None;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::{ governance, realm };
+
+use crate::error::NftVoterError;
+use crate::state::{ GovernanceWeightConfig, Registrar, MAX_WEIGHT_MULTIPLIER_BPS };
+
+/// Configures the weight multiplier applied to the voter weight for actions targeting the given Governance
+/// The multiplier is used by update_voter_weight_record_for_target
+#[derive(Accounts)]
+pub struct ConfigureGovernanceWeight<'info> {
+    /// Registrar for which we configure the Governance weight
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance the weight multiplier applies to
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"governance-weight-config".as_ref(),
+                registrar.key().as_ref(),
+                governance.key().as_ref()],
+        bump,
+        payer = payer,
+        space = GovernanceWeightConfig::get_space()
+    )]
+    pub governance_weight_config: Account<'info, GovernanceWeightConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_governance_weight(
+    ctx: Context<ConfigureGovernanceWeight>,
+    weight_multiplier_bps: u32,
+) -> Result<()> {
+    require!(
+        weight_multiplier_bps > 0 && weight_multiplier_bps <= MAX_WEIGHT_MULTIPLIER_BPS,
+        NftVoterError::InvalidWeightMultiplier
+    );
+
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    // Ensure the Governance belongs to Registrar.realm
+    let _governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm,
+    )?;
+
+    let governance_weight_config = &mut ctx.accounts.governance_weight_config;
+
+    governance_weight_config.registrar = registrar.key();
+    governance_weight_config.governance = ctx.accounts.governance.key();
+    governance_weight_config.weight_multiplier_bps = weight_multiplier_bps;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{Registrar, RegistrarConfig, RegistrarExtension};
+
+/// Configures the Registrar level voting options
+/// The options apply to all NFT collections configured for the Registrar
+#[derive(Accounts)]
+pub struct ConfigureRegistrar<'info> {
+    /// Registrar which we configure
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension of the Registrar the configuration is stored in
+    #[account(
+        mut,
+        has_one = registrar @ NftVoterError::InvalidRegistrarExtension
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+}
+
+pub fn configure_registrar(ctx: Context<ConfigureRegistrar>, config: RegistrarConfig) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    config.assert_is_valid()?;
+
+    require!(
+        config.parent_registrar != registrar.key(),
+        NftVoterError::InvalidParentRegistrar
+    );
+
+    ctx.accounts.registrar_extension.config = config;
+
+    Ok(())
+}
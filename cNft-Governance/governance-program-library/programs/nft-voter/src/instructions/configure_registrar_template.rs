@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::{
+    assert_collection_account,
+    CollectionConfig,
+    CollectionConfigArgs,
+    RegistrarConfig,
+    RegistrarTemplate,
+};
+
+/// Configures the collections and the voting options of RegistrarTemplate
+/// The given collections replace the existing collections of the template
+/// Creator boosts and expiry are not templated and they can be configured on the created Registrars
+///
+/// The collection mints (or Metaplex Core collections) must be supplied as remaining accounts
+/// in the same order as the collections
+///
+/// Note: Registrars already created from the template are not affected by the change
+#[derive(Accounts)]
+pub struct ConfigureRegistrarTemplate<'info> {
+    #[account(
+        mut,
+        has_one = authority @ NftVoterError::InvalidRegistrarTemplateAuthority
+    )]
+    pub registrar_template: Account<'info, RegistrarTemplate>,
+
+    /// Authority of the template must sign and match RegistrarTemplate.authority
+    pub authority: Signer<'info>,
+}
+
+pub fn configure_registrar_template<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ConfigureRegistrarTemplate<'info>>,
+    config: RegistrarConfig,
+    collections: Vec<CollectionConfigArgs>
+) -> Result<()> {
+    let registrar_template = &mut ctx.accounts.registrar_template;
+
+    require!(
+        collections.len() <= (registrar_template.max_collections as usize) &&
+            collections.len() == ctx.remaining_accounts.len(),
+        NftVoterError::InvalidRegistrarTemplate
+    );
+
+    config.assert_is_valid()?;
+
+    for (index, (args, collection_info)) in collections
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .enumerate() {
+        require!(args.size > 0, NftVoterError::InvalidCollectionSize);
+
+        require_keys_eq!(
+            args.collection,
+            *collection_info.key,
+            NftVoterError::InvalidRegistrarTemplate
+        );
+
+        // Each collection can only be configured once the same way as on Registrar
+        require!(
+            !collections[..index].iter().any(|c| c.collection == args.collection),
+            NftVoterError::InvalidRegistrarTemplate
+        );
+
+        assert_collection_account(&config, collection_info)?;
+    }
+
+    registrar_template.config = config;
+    registrar_template.collection_configs = collections
+        .iter()
+        .map(|args| CollectionConfig {
+            collection: args.collection,
+            size: args.size,
+            weight: args.weight,
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(())
+}
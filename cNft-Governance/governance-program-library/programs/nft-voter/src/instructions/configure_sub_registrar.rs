@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, SubRegistrarConfig, MAX_WEIGHT_MULTIPLIER_BPS };
+
+/// Approves the given sub-DAO Registrar to roll up its voter weight into the parent Registrar
+/// using the given weight scale
+/// The weight is rolled up by sync_parent_weight once the sub-DAO Registrar points to the parent
+/// using RegistrarExtension.config.parent_registrar
+///
+/// Note: A changed weight scale is applied to the max voter weight on the next sync_parent_weight
+#[derive(Accounts)]
+pub struct ConfigureSubRegistrar<'info> {
+    /// The parent Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    /// The sub-DAO Registrar
+    #[account(
+        constraint = sub_registrar.key() != registrar.key() @ NftVoterError::InvalidParentRegistrar
+    )]
+    pub sub_registrar: Account<'info, Registrar>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"sub-registrar-config".as_ref(),
+                registrar.key().as_ref(),
+                sub_registrar.key().as_ref()],
+        bump,
+        payer = payer,
+        space = SubRegistrarConfig::get_space()
+    )]
+    pub sub_registrar_config: Account<'info, SubRegistrarConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_sub_registrar(
+    ctx: Context<ConfigureSubRegistrar>,
+    weight_scale_bps: u32,
+) -> Result<()> {
+    require!(
+        weight_scale_bps > 0 && weight_scale_bps <= MAX_WEIGHT_MULTIPLIER_BPS,
+        NftVoterError::InvalidWeightMultiplier
+    );
+
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    let sub_registrar_config = &mut ctx.accounts.sub_registrar_config;
+
+    sub_registrar_config.parent_registrar = registrar.key();
+    sub_registrar_config.sub_registrar = ctx.accounts.sub_registrar.key();
+    sub_registrar_config.weight_scale_bps = weight_scale_bps;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, WalletWeightOverride, MAX_BASIS_POINTS };
+
+/// Sets the voter weight override of the given wallet expressed in basis points of the weight of its NFTs
+/// The realm authority (ex. executing a governance Proposal) can neutralize the voting power of sanctioned
+/// or treasury wallets holding member NFTs with weight_bps 0 and restore it with weight_bps 10000
+///
+/// Note: The override is only applied when RegistrarExtension.config.allow_wallet_weight_overrides is set
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct ConfigureWalletWeightOverride<'info> {
+    /// Registrar for which we configure the wallet weight override
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"wallet-weight-override".as_ref(),
+                registrar.key().as_ref(),
+                wallet.as_ref()],
+        bump,
+        payer = payer,
+        space = WalletWeightOverride::get_space()
+    )]
+    pub wallet_weight_override: Account<'info, WalletWeightOverride>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_wallet_weight_override(
+    ctx: Context<ConfigureWalletWeightOverride>,
+    wallet: Pubkey,
+    weight_bps: u16
+) -> Result<()> {
+    // The override can't increase the weight above the weight accounted for in MaxVoterWeightRecord
+    require!(weight_bps <= MAX_BASIS_POINTS, NftVoterError::InvalidWalletWeightOverrideWeight);
+
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    let wallet_weight_override = &mut ctx.accounts.wallet_weight_override;
+
+    wallet_weight_override.registrar = registrar.key();
+    wallet_weight_override.wallet = wallet;
+    wallet_weight_override.weight_bps = weight_bps;
+
+    Ok(())
+}
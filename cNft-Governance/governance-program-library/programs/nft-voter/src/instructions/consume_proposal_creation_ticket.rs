@@ -0,0 +1,87 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Consumes ProposalCreationTicket and updates VoterWeightRecord for CreateProposal action
+/// The ticket is disposed and hence it can be used to create a single Proposal only
+/// This instruction updates VoterWeightRecord which is valid for the current Slot and the ticket Governance only
+/// and hance the instruction has to be executed inside the same transaction as spl-gov create_proposal
+///
+/// The optional VoterRegistration and WalletWeightOverride PDAs of the voter are supplied as remaining accounts
+/// the same way as for update_voter_weight_record and the ticket weight is adjusted by the floor weight and the wallet override
+#[derive(Accounts)]
+pub struct ConsumeProposalCreationTicket<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+        constraint = proposal_creation_ticket.registrar == registrar.key()
+        @ NftVoterError::InvalidProposalCreationTicket,
+
+        constraint = proposal_creation_ticket.governing_token_owner == voter_weight_record.governing_token_owner
+        @ NftVoterError::InvalidProposalCreationTicket,
+    )]
+    pub proposal_creation_ticket: Account<'info, ProposalCreationTicket>,
+
+    /// CHECK: The beneficiary who receives lamports from the disposed ProposalCreationTicket can be any account
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+}
+
+pub fn consume_proposal_creation_ticket(ctx: Context<ConsumeProposalCreationTicket>) -> Result<()> {
+    let proposal_creation_ticket = &ctx.accounts.proposal_creation_ticket;
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+    let clock = Clock::get()?;
+
+    require!(
+        proposal_creation_ticket.expiry >= clock.slot,
+        NftVoterError::ProposalCreationTicketExpired
+    );
+
+    let (voter_weight_adjustment, _) = split_voter_weight_adjustment_accounts(
+        &ctx.accounts.registrar,
+        &ctx.accounts.registrar_extension,
+        &voter_weight_record.governing_token_owner,
+        ctx.remaining_accounts
+    )?;
+
+    // Record is only valid as of the current slot
+    finalize_voter_weight(
+        voter_weight_record,
+        proposal_creation_ticket.voter_weight,
+        clock.slot,
+        &voter_weight_adjustment
+    );
+
+    // Set the action and target to make it specific to creating Proposal for the ticket Governance
+    voter_weight_record.weight_action = Some(VoterWeightAction::CreateProposal);
+    voter_weight_record.weight_action_target = Some(proposal_creation_ticket.governance);
+
+    Ok(())
+}
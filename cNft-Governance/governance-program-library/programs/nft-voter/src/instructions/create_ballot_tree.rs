@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::instructions::{
+    CreateTreeConfigCpi,
+    CreateTreeConfigCpiAccounts,
+    CreateTreeConfigInstructionArgs,
+};
+use spl_account_compression::{ program::SplAccountCompression, Noop };
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::program_ids::Bubblegum;
+
+/// Creates a Bubblegum tree whose tree creator and tree delegate is the BallotTree PDA of the Registrar
+/// Ballot cNFTs can then be minted to the members of the DAO with mint_ballot
+/// which lets DAOs run cNFT voting without any external minting tooling
+///
+/// The merkle tree account must be allocated (owned by spl-account-compression) by the caller beforehand
+/// and Bubblegum creates the TreeConfig (tree_authority) of the tree
+#[derive(Accounts)]
+#[instruction(max_depth: u32, max_buffer_size: u32, name: String, symbol: String, uri: String)]
+pub struct CreateBallotTree<'info> {
+    /// Registrar for which we create the tree
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"ballot-tree".as_ref(),
+                registrar.key().as_ref(),
+                merkle_tree.key().as_ref()],
+        bump,
+        payer = payer,
+        space = BallotTree::get_space()
+    )]
+    pub ballot_tree: Account<'info, BallotTree>,
+
+    /// CHECK: Bubblegum TreeConfig of the tree validated and created by Bubblegum
+    #[account(mut)]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Empty merkle tree account validated and initialized by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_ballot_tree(
+    ctx: Context<CreateBallotTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    name: String,
+    symbol: String,
+    uri: String
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    BallotTree::assert_valid_metadata(&name, &symbol, &uri)?;
+
+    let registrar_key = registrar.key();
+    let merkle_tree_key = ctx.accounts.merkle_tree.key();
+    let bump = [ctx.bumps.ballot_tree];
+    let mut signer_seeds = get_ballot_tree_seeds(&registrar_key, &merkle_tree_key).to_vec();
+    signer_seeds.push(&bump);
+
+    // The BallotTree signs as the tree creator and becomes the tree delegate
+    // The tree is private and only the BallotTree can mint to it
+    CreateTreeConfigCpi::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+        CreateTreeConfigCpiAccounts {
+            tree_config: &ctx.accounts.tree_authority.to_account_info(),
+            merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            tree_creator: &ctx.accounts.ballot_tree.to_account_info(),
+            log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: &ctx.accounts.compression_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        CreateTreeConfigInstructionArgs {
+            max_depth,
+            max_buffer_size,
+            public: Some(false),
+        }
+    ).invoke_signed(&[&signer_seeds[..]])?;
+
+    let ballot_tree = &mut ctx.accounts.ballot_tree;
+
+    ballot_tree.registrar = registrar_key;
+    ballot_tree.merkle_tree = merkle_tree_key;
+    ballot_tree.name = name;
+    ballot_tree.symbol = symbol;
+    ballot_tree.uri = uri;
+    ballot_tree.ballots_minted = 0;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::{ governance, proposal as spl_proposal, realm };
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Creates a Campaign to run a one-off referendum on the Proposal with the ballots of the BallotTree
+/// The wallets in the allowlist claim their ballots with claim_ballot and vote with cast_ballot_vote
+/// Each ballot gives ballot_weight voter weight and it's burnt by the vote
+#[derive(Accounts)]
+#[instruction(proposal: Pubkey)]
+pub struct CreateCampaign<'info> {
+    /// Registrar for which we create the campaign
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    /// The BallotTree the ballots of the campaign are minted to
+    #[account(has_one = registrar)]
+    pub ballot_tree: Account<'info, BallotTree>,
+
+    #[account(
+        init,
+        seeds = [ b"campaign".as_ref(),
+                registrar.key().as_ref(),
+                proposal.as_ref()],
+        bump,
+        payer = payer,
+        space = Campaign::get_space()
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal of the referendum which must match the proposal argument
+    #[account(
+        owner = registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_campaign(
+    ctx: Context<CreateCampaign>,
+    proposal: Pubkey,
+    allowlist_root: [u8; 32],
+    ballot_weight: u64
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    require!(ballot_weight > 0, NftVoterError::InvalidCampaignBallotWeight);
+
+    // Ensure the Proposal belongs to Registrar.realm and it's voted with Registrar.governing_token_mint
+    governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    let campaign = &mut ctx.accounts.campaign;
+
+    campaign.registrar = registrar.key();
+    campaign.ballot_tree = ctx.accounts.ballot_tree.key();
+    campaign.proposal = proposal;
+    campaign.allowlist_root = allowlist_root;
+    campaign.ballot_weight = ballot_weight;
+    campaign.ballots_claimed = 0;
+    campaign.ballots_cast = 0;
+
+    Ok(())
+}
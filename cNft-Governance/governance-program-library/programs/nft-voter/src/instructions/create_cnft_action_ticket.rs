@@ -1,8 +1,16 @@
 use crate::error::NftVoterError;
+use crate::instructions::voter_accounts::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use spl_account_compression::program::SplAccountCompression;
-use crate::tools::accounts::create_nft_action_ticket_account;
+use crate::tools::accounts::{ create_nft_action_ticket_account, serialize_nft_action_ticket_account };
+use crate::tools::accounts_cursor::AccountsCursor;
+use crate::tools::anchor::get_anchor_account_data;
+use crate::tools::ed25519::assert_ed25519_signature;
+use ::cnft_verification::get_asset_id_with_program;
+use solana_program::sysvar;
+use spl_governance_tools::account::create_and_serialize_account_signed;
 
 /// Create NFT action ticket. Everytime a voter want to do some voting with NFT, they need to get a ticket first.
 /// This instruction will check the validation of the NFT and create a ticket for the voter.
@@ -12,67 +20,402 @@ use crate::tools::accounts::create_nft_action_ticket_account;
 /// If the action instruction succeed, the ticket will be closed.
 /// Otherwise, the ticket will be kept and can be used in the next action.
 ///
+/// Compressed NFTs with Metaplex Uses can be used for voting a limited number of times.
+/// Each nft-castVote-ticket consumes one use tracked by AssetUsesRecord supplied after the ticket.
+/// Note: Recreating an unused nft-castVote-ticket consumes another use.
+///
+/// When RegistrarExtension.config.max_tree_assets_per_slot is set the TreeRateLimitRecord of the tree and the voter
+/// must be supplied after the ticket (and AssetUsesRecord) of each compressed NFT.
+///
+/// When RegistrarExtension.config.proof_attestation_freshness_slots is set the ProofAttestation of each compressed NFT
+/// must be supplied after the TreeRateLimitRecord. Verified proofs are attested and within the freshness window
+/// the assets can set CompressedNftAsset.use_proof_attestation and be supplied without the proof.
+///
+/// Compressed NFTs held by a cold wallet linked to the voter by link_cold_wallet can be used by the voter.
+/// The ColdWalletLink of the cold wallet must be supplied after the ProofAttestation of each such compressed NFT.
+///
+/// Compressed NFTs whose weight is rented to the voter by rent_voting_power can be used by the voter until the lease expires.
+/// The WeightLease of the asset is supplied in place of the ColdWalletLink and the ticket expiry is capped at the lease expiry.
+///
+/// When RegistrarExtension.config.allow_weight_leases is set the WeightLease PDA of the owner of each compressed NFT
+/// which isn't used with a WeightLease must be supplied after the ColdWalletLink (it doesn't have to exist).
+/// The owner can't use the asset while its weight is rented out.
+///
+/// When RegistrarExtension.config.allow_collection_aliases is set the CollectionAlias of the collection of each compressed NFT
+/// whose collection isn't configured must be supplied after the ColdWalletLink (or WeightLease).
+///
+/// When RegistrarExtension.config.tree_authority_allowlist is set the Bubblegum TreeConfig of the tree
+/// must be supplied as the last account of each compressed NFT.
+///
+/// When RegistrarExtension.config.voter_allowlist_root is set the voter must supply voter_allowlist_proof of its wallet.
+///
+/// When RegistrarExtension.config.allow_voter_migration and min_holding_slots are set the VoterMigration PDA of the voter
+/// must be supplied as the first remaining account (it doesn't have to exist). The holding period of the compressed NFTs
+/// moved from the previous wallet of a migrated voter can then be attested for the previous wallet.
+///
+/// When RegistrarExtension.config.leaf_hash_verifier is set the compressed NFTs can be supplied with CompressedNftAsset.leaf_hash
+/// instead of the metadata. The leaf hash must be attested by the verifier using an ed25519 program instruction
+/// preceding this instruction in the transaction.
+///
+/// The merkle proof of each compressed NFT can be supplied either as proof_len accounts after the tree
+/// or as CompressedNftAsset.proof_nodes instruction data, in which case proof_len must be 0.
+///
 /// This is the instruction for verifying compressed NFT.
 #[derive(Accounts)]
 #[instruction(voter_weight_action:VoterWeightAction, params: Vec<CompressedNftAsset>)]
 pub struct CreateCnftActionTicket<'info> {
-    pub registrar: Account<'info, Registrar>,
-
-    #[account(
-        mut,
-        constraint = voter_weight_record.realm == registrar.realm
-        @ NftVoterError::InvalidVoterWeightRecordRealm,
-        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
-        @ NftVoterError::InvalidVoterWeightRecordMint,
-    )]
-    pub voter_weight_record: Account<'info, VoterWeightRecord>,
-    pub voter_authority: Signer<'info>,
+    pub voter: VoterAccounts<'info>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
 
     pub compression_program: Program<'info, SplAccountCompression>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Instructions sysvar used to read holding and leaf hash attestations
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
 }
 
 pub fn create_cnft_action_ticket<'info>(
     ctx: Context<'_, '_, '_, 'info, CreateCnftActionTicket<'info>>,
     voter_weight_action: VoterWeightAction,
-    params: Vec<CompressedNftAsset>
+    params: Vec<CompressedNftAsset>,
+    voter_allowlist_proof: Vec<[u8; 32]>
 ) -> Result<()> {
-    let registrar = &ctx.accounts.registrar;
-    let governing_token_owner = &ctx.accounts.voter_weight_record.governing_token_owner;
-    let remaining_accounts = &mut ctx.remaining_accounts.to_vec();
+    let registrar = &ctx.accounts.voter.registrar;
+    let registrar_extension = &ctx.accounts.voter.registrar_extension;
+    let governing_token_owner = &ctx.accounts.voter.voter_weight_record.governing_token_owner;
+
+    registrar_extension.config.assert_voter_allowed(governing_token_owner, &voter_allowlist_proof)?;
+    let mut accounts_cursor = AccountsCursor::new(ctx.remaining_accounts);
+
+    // When the voters can migrate the holding periods of their previous wallets the VoterMigration is supplied first
+    let checks_voter_migration =
+        registrar_extension.config.allow_voter_migration && registrar_extension.config.min_holding_slots > 0;
+
+    let voter_migration = match accounts_cursor.next_if(checks_voter_migration, "voter_migration")? {
+        Some(voter_migration_info) =>
+            get_voter_migration_data_for_new_wallet(
+                voter_migration_info,
+                &registrar.key(),
+                governing_token_owner
+            )?,
+        None => None,
+    };
     let compression_program = &ctx.accounts.compression_program.to_account_info();
     let system_program = &ctx.accounts.system_program.to_account_info();
     let payer = &ctx.accounts.payer.to_account_info();
     let mut unique_asset_ids: Vec<Pubkey> = vec![];
+    let bubblegum_program_id = registrar_extension.config.get_bubblegum_program_id();
+
+    for param in params.iter() {
+        let proof_len = param.proof_len as usize;
+
+        // Each vote consumes one use of the assets with Uses and the AssetUsesRecord is supplied after the ticket
+        let consumes_use =
+            param.uses.is_some() && voter_weight_action == VoterWeightAction::CastVote;
+
+        // When the submissions per tree are limited the TreeRateLimitRecord is supplied after the AssetUsesRecord
+        let checks_tree_rate_limit = registrar_extension.config.max_tree_assets_per_slot > 0;
 
-    let mut start = 0;
-    for i in 0..params.len() {
-        let param = &params[i];
-        let proof_len = param.proof_len;
-        let accounts = &remaining_accounts[start..start + (proof_len as usize) + 2];
+        // When the proofs are attested the ProofAttestation is supplied after the TreeRateLimitRecord
+        let attests_proof = registrar_extension.config.is_proof_attestation_enabled();
 
-        let tree_account = accounts[0].clone();
-        let proofs = accounts[1..(proof_len as usize) + 1].to_vec();
-        let cnft_action_ticket_info = accounts.last().unwrap().clone();
+        // When the asset is held by a linked cold wallet or rented to the voter
+        // the ColdWalletLink or WeightLease is supplied after the ProofAttestation
+        let uses_delegation = param.leaf_owner != *governing_token_owner;
+
+        // When the collection is migrated to a configured collection the CollectionAlias is supplied after the delegation
+        let uses_collection_alias = registrar.uses_collection_alias(registrar_extension, param);
+
+        // When the tree authorities are restricted the tree TreeConfig is supplied as the last account
+        let checks_tree_authority = registrar_extension.config.is_tree_authority_allowlist_enabled();
+
+        let tree_account = accounts_cursor.next("tree")?;
+        let proofs = accounts_cursor.take(proof_len, "proof node")?.to_vec();
+        let cnft_action_ticket_info = accounts_cursor.next("cnft_action_ticket")?;
+        let asset_uses_record_info = accounts_cursor.next_if(consumes_use, "asset_uses_record")?;
+        let tree_rate_limit_record_info = accounts_cursor.next_if(
+            checks_tree_rate_limit,
+            "tree_rate_limit_record"
+        )?;
+        let proof_attestation_info = accounts_cursor.next_if(attests_proof, "proof_attestation")?;
+        let delegation_info = accounts_cursor.next_if(uses_delegation, "cold_wallet_link")?;
+
+        // When the weight can be rented the WeightLease of the owner is supplied after the delegation
+        // unless the asset is used by the renter with the WeightLease itself
+        let uses_weight_lease = match delegation_info {
+            Some(delegation_info) => is_weight_lease(delegation_info)?,
+            None => false,
+        };
+        let owner_weight_lease_info = accounts_cursor.next_if(
+            registrar_extension.config.allow_weight_leases && !uses_weight_lease,
+            "weight_lease"
+        )?;
+        let collection_alias_info = accounts_cursor.next_if(
+            uses_collection_alias,
+            "collection_alias"
+        )?;
+        let tree_config_info = accounts_cursor.next_if(checks_tree_authority, "tree_config")?;
         let ticket_type = format!("nft-{}-ticket", &voter_weight_action).to_string();
 
-        let (cnft_vote_weight, asset_id) = resolve_cnft_vote_weight(
+        if let Some(tree_config_info) = tree_config_info {
+            assert_tree_authority_allowed(registrar_extension, tree_account, tree_config_info)?;
+        }
+
+        // The rate limit is applied before the proof is verified to cap the verification work per slot
+        if let Some(tree_rate_limit_record_info) = tree_rate_limit_record_info {
+            // Note: The correct PDA of the TreeRateLimitRecord is validated in create_and_serialize_account_signed
+            if tree_rate_limit_record_info.data_is_empty() {
+                create_and_serialize_account_signed(
+                    payer,
+                    tree_rate_limit_record_info,
+                    &TreeRateLimitRecord::new(
+                        registrar.key(),
+                        *tree_account.key,
+                        *governing_token_owner
+                    ),
+                    &get_tree_rate_limit_record_seeds(
+                        &registrar.key(),
+                        tree_account.key,
+                        governing_token_owner
+                    ),
+                    &crate::id(),
+                    system_program,
+                    &Rent::get()?,
+                    0
+                )?;
+            }
+
+            let mut tree_rate_limit_record = get_tree_rate_limit_record_data_for_tree_and_voter(
+                tree_rate_limit_record_info,
+                &registrar.key(),
+                tree_account.key,
+                governing_token_owner
+            )?;
+
+            tree_rate_limit_record.record_submission(
+                Clock::get()?.slot,
+                registrar_extension.config.max_tree_assets_per_slot
+            )?;
+
+            tree_rate_limit_record_info.data
+                .borrow_mut()
+                .copy_from_slice(&tree_rate_limit_record.try_to_vec()?);
+        }
+
+        let proof_attestation = if param.use_proof_attestation {
+            let proof_attestation_info = proof_attestation_info.ok_or(
+                NftVoterError::InvalidProofAttestation
+            )?;
+
+            Some(
+                get_proof_attestation_data_for_asset(
+                    proof_attestation_info,
+                    &registrar.key(),
+                    &get_asset_id_with_program(tree_account.key, param.nonce, &bubblegum_program_id)
+                )?
+            )
+        } else {
+            None
+        };
+
+        // The asset owner is either the voter, the cold wallet which authorized the voter or the owner which rented the weight
+        // The expiry of the link is kept in the ticket to reject the ticket once the link expires
+        // and the ticket expiry is capped at the lease expiry to revert the rented weight to the owner
+        let mut ticket_expiry = registrar_extension.config.get_nft_action_ticket_expiry(Clock::get()?.slot);
+
+        let (asset_owner, delegation_expires_at) = match delegation_info {
+            Some(weight_lease_info) if uses_weight_lease => {
+                let weight_lease = get_anchor_account_data::<WeightLease>(weight_lease_info)?;
+
+                weight_lease.assert_can_vote_for(
+                    &registrar.key(),
+                    &get_asset_id_with_program(tree_account.key, param.nonce, &bubblegum_program_id),
+                    &param.leaf_owner,
+                    governing_token_owner,
+                    Clock::get()?.slot
+                )?;
+
+                ticket_expiry = weight_lease.get_ticket_expiry(ticket_expiry);
+
+                (param.leaf_owner, 0)
+            }
+            Some(cold_wallet_link_info) => {
+                let cold_wallet_link = get_anchor_account_data::<ColdWalletLink>(cold_wallet_link_info)?;
+
+                cold_wallet_link.assert_can_vote_for(
+                    &registrar.key(),
+                    &param.leaf_owner,
+                    governing_token_owner,
+                    Clock::get()?.unix_timestamp
+                )?;
+
+                (param.leaf_owner, cold_wallet_link.expires_at)
+            }
+            None => (*governing_token_owner, 0),
+        };
+
+        // The weight of the asset rented out belongs to the renter until the lease expires
+        if let Some(owner_weight_lease_info) = owner_weight_lease_info {
+            assert_asset_not_leased(
+                owner_weight_lease_info,
+                &registrar.key(),
+                &get_asset_id_with_program(tree_account.key, param.nonce, &bubblegum_program_id),
+                &param.leaf_owner,
+                Clock::get()?.slot
+            )?;
+        }
+
+        if param.leaf_hash.is_some() {
+            assert_leaf_hash_attested(
+                registrar_extension,
+                &ctx.accounts.instructions,
+                &get_asset_id_with_program(tree_account.key, param.nonce, &bubblegum_program_id),
+                param
+            )?;
+        }
+
+        let collection_alias = match collection_alias_info {
+            Some(collection_alias_info) =>
+                Some(
+                    get_collection_alias_data_for_collection(
+                        collection_alias_info,
+                        &registrar.key(),
+                        &param.collection.as_ref().unwrap().key
+                    )?
+                ),
+            None => None,
+        };
+
+        let (cnft_vote_weight, asset_id, collection) = resolve_cnft_vote_weight(
             &registrar,
-            &governing_token_owner,
-            &tree_account,
+            registrar_extension,
+            &asset_owner,
+            tree_account,
             &mut unique_asset_ids,
             &param,
             proofs,
-            compression_program
+            compression_program,
+            proof_attestation.as_ref(),
+            collection_alias.as_ref()
         )?;
 
+        // Attest the verified proof to let the following tickets of the asset skip the proof
+        // Note: Attestations are only refreshed by proofs and never extended by the attestation itself
+        if
+            let Some(proof_attestation_info) = proof_attestation_info.filter(
+                |_| !param.use_proof_attestation
+            )
+        {
+            let proof_attestation = ProofAttestation::new(
+                registrar.key(),
+                asset_id,
+                param.leaf_owner,
+                param.root,
+                get_compressed_nft_leaf_node(&asset_id, &param),
+                Clock::get()?.slot
+            );
+
+            // Note: The correct PDA of the ProofAttestation is validated in create_and_serialize_account_signed
+            if proof_attestation_info.data_is_empty() {
+                create_and_serialize_account_signed(
+                    payer,
+                    proof_attestation_info,
+                    &proof_attestation,
+                    &get_proof_attestation_seeds(&registrar.key(), &asset_id),
+                    &crate::id(),
+                    system_program,
+                    &Rent::get()?,
+                    0
+                )?;
+            } else {
+                get_proof_attestation_data_for_asset(
+                    proof_attestation_info,
+                    &registrar.key(),
+                    &asset_id
+                )?;
+
+                proof_attestation_info.data
+                    .borrow_mut()
+                    .copy_from_slice(&proof_attestation.try_to_vec()?);
+            }
+        }
+
+        // Reject assets acquired recently to mitigate vote buying using flash transfers
+        if registrar_extension.config.min_holding_slots > 0 {
+            let last_transfer_slot = param.last_transfer_slot.ok_or(
+                NftVoterError::MissingHoldingAttestation
+            )?;
+
+            let assert_holding_attested = |holder: &Pubkey| {
+                assert_ed25519_signature(
+                    &ctx.accounts.instructions,
+                    &registrar_extension.config.holding_attestation_authority,
+                    &get_holding_attestation_message(&asset_id, holder, last_transfer_slot)
+                )
+            };
+
+            // The assets moved from the previous wallet of a migrated voter can be attested for the previous wallet
+            match
+                voter_migration
+                    .as_ref()
+                    .and_then(|vm| vm.get_previous_holder(&param.leaf_owner, last_transfer_slot))
+            {
+                Some(previous_holder) =>
+                    assert_holding_attested(&param.leaf_owner).or_else(|_|
+                        assert_holding_attested(&previous_holder)
+                    )?,
+                None => assert_holding_attested(&param.leaf_owner)?,
+            }
+
+            require!(
+                Clock::get()?.slot.saturating_sub(last_transfer_slot) >=
+                    registrar_extension.config.min_holding_slots,
+                NftVoterError::HoldingPeriodNotMet
+            );
+        }
+
+        if let Some(asset_uses_record_info) = asset_uses_record_info {
+            // Note: The correct PDA of the AssetUsesRecord is validated in create_and_serialize_account_signed
+            if asset_uses_record_info.data_is_empty() {
+                create_and_serialize_account_signed(
+                    payer,
+                    asset_uses_record_info,
+                    &AssetUsesRecord::new(asset_id),
+                    &get_asset_uses_record_seeds(&asset_id),
+                    &crate::id(),
+                    system_program,
+                    &Rent::get()?,
+                    0
+                )?;
+            }
+
+            let mut asset_uses_record = get_asset_uses_record_data_for_asset(
+                asset_uses_record_info,
+                &asset_id
+            )?;
+
+            require!(
+                asset_uses_record.used < param.uses.as_ref().unwrap().remaining,
+                NftVoterError::NftUsesExhausted
+            );
+
+            asset_uses_record.used = asset_uses_record.used
+                .checked_add(1)
+                .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+            asset_uses_record_info.data
+                .borrow_mut()
+                .copy_from_slice(&asset_uses_record.try_to_vec()?);
+        }
+
         // if the ticket PDA account doesn't exist, create it
         if cnft_action_ticket_info.data_is_empty() {
             create_nft_action_ticket_account(
                 payer,
-                &cnft_action_ticket_info,
+                cnft_action_ticket_info,
                 &registrar.key().clone(),
                 &governing_token_owner,
                 &asset_id,
@@ -88,16 +431,26 @@ pub fn create_cnft_action_ticket<'info>(
             governing_token_owner: governing_token_owner.clone(),
             nft_mint: asset_id.clone(),
             weight: cnft_vote_weight,
-            expiry: Some(Clock::get()?.slot + 10),
+            collection,
+            expiry: Some(ticket_expiry),
+            delegation_expires_at,
+            created_at_slot: Clock::get()?.slot,
         };
-        // serialize_nft_action_ticket_account(
-        //     &serialized_data.try_to_vec()?,
-        //     &mut cnft_action_ticket_info
-        // )?;
-        cnft_action_ticket_info.data.borrow_mut().copy_from_slice(&serialized_data.try_to_vec()?);
 
-        start += (proof_len as usize) + 2;
+        serialize_nft_action_ticket_account(
+            payer,
+            cnft_action_ticket_info,
+            &serialized_data.try_to_vec()?,
+            system_program
+        )?;
     }
 
-    Ok(())
+    accounts_cursor.finish()
+}
+
+/// Returns true if the given delegation account supplied for an asset of another wallet is a WeightLease
+fn is_weight_lease(delegation_info: &AccountInfo) -> Result<bool> {
+    let data = delegation_info.try_borrow_data()?;
+
+    Ok(data.get(..8) == Some(&WeightLease::discriminator()[..]))
 }
@@ -0,0 +1,86 @@
+use crate::instructions::voter_accounts::*;
+use crate::state::*;
+use crate::tools::accounts::{ create_nft_action_ticket_account, serialize_nft_action_ticket_account };
+use anchor_lang::prelude::*;
+use itertools::Itertools;
+
+/// Create NFT action ticket for Metaplex Core assets
+/// It's the equivalent of create_nft_action_ticket for assets of the configured collections migrated to Metaplex Core
+/// and the tickets are used by cast_nft_vote and update_voter_weight_record the same way as the NFT tickets
+///
+/// Core assets are only accepted when RegistrarExtension.config.allow_core_assets is set
+/// and the program is built with the metaplex-core feature
+///
+/// When RegistrarExtension.config.voter_allowlist_root is set the voter must supply voter_allowlist_proof of its wallet.
+///
+/// The asset and the ticket accounts of each asset must be supplied as remaining accounts
+#[derive(Accounts)]
+#[instruction(voter_weight_action:VoterWeightAction)]
+pub struct CreateCoreAssetActionTicket<'info> {
+    pub voter: VoterAccounts<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_core_asset_action_ticket<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateCoreAssetActionTicket<'info>>,
+    voter_weight_action: VoterWeightAction,
+    voter_allowlist_proof: Vec<[u8; 32]>
+) -> Result<()> {
+    let registrar = &ctx.accounts.voter.registrar;
+    let registrar_extension = &ctx.accounts.voter.registrar_extension;
+    let governing_token_owner = &ctx.accounts.voter.voter_weight_record.governing_token_owner;
+
+    registrar_extension.config.assert_voter_allowed(governing_token_owner, &voter_allowlist_proof)?;
+    let system_program = &ctx.accounts.system_program.to_account_info();
+    let payer = &ctx.accounts.payer.to_account_info();
+    let mut unique_assets: Vec<Pubkey> = vec![];
+    let ticket_type = format!("nft-{}-ticket", &voter_weight_action).to_string();
+
+    for (asset_info, nft_action_ticket_info) in ctx.remaining_accounts.iter().tuples() {
+        let (asset_vote_weight, asset, collection) = resolve_core_asset_vote_weight(
+            registrar,
+            registrar_extension,
+            governing_token_owner,
+            asset_info,
+            &mut unique_assets
+        )?;
+
+        // if the ticket PDA account doesn't exist, create it
+        if nft_action_ticket_info.data_is_empty() {
+            create_nft_action_ticket_account(
+                payer,
+                nft_action_ticket_info,
+                &registrar.key(),
+                governing_token_owner,
+                &asset,
+                &ticket_type,
+                system_program
+            )?;
+        }
+
+        // otherwise, update the ticket expiry to extend to the time usage of the ticket
+        let serialized_data = NftActionTicket {
+            account_discriminator: NftActionTicket::ACCOUNT_DISCRIMINATOR,
+            registrar: registrar.key(),
+            governing_token_owner: *governing_token_owner,
+            nft_mint: asset,
+            weight: asset_vote_weight,
+            collection,
+            expiry: Some(registrar_extension.config.get_nft_action_ticket_expiry(Clock::get()?.slot)),
+            delegation_expires_at: 0,
+            created_at_slot: Clock::get()?.slot,
+        };
+
+        serialize_nft_action_ticket_account(
+            payer,
+            nft_action_ticket_info,
+            &serialized_data.try_to_vec()?,
+            system_program
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use crate::tools::spl_token::assert_is_valid_governing_token_mint;
 use spl_governance::state::realm;
 
 use crate::state::max_voter_weight_record::MaxVoterWeightRecord;
@@ -29,7 +29,8 @@ pub struct CreateMaxVoterWeightRecord<'info> {
     pub realm: UncheckedAccount<'info>,
 
     /// Either the realm community mint or the council mint.
-    pub realm_governing_token_mint: Account<'info, Mint>,
+    /// CHECK: spl-token or spl-token-2022 mint validated in the instruction
+    pub realm_governing_token_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -38,6 +39,9 @@ pub struct CreateMaxVoterWeightRecord<'info> {
 }
 
 pub fn create_max_voter_weight_record(ctx: Context<CreateMaxVoterWeightRecord>) -> Result<()> {
+    // The mint can be either spl-token or spl-token-2022 mint
+    assert_is_valid_governing_token_mint(&ctx.accounts.realm_governing_token_mint)?;
+
     // Deserialize the Realm to validate it
     let _realm = realm::get_realm_data_for_governing_token_mint(
         &ctx.accounts.governance_program_id.key(),
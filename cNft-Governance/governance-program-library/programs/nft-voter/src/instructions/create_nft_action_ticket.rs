@@ -1,6 +1,6 @@
-use crate::error::NftVoterError;
+use crate::instructions::voter_accounts::*;
 use crate::state::*;
-use crate::tools::accounts::create_nft_action_ticket_account;
+use crate::tools::accounts::{ create_nft_action_ticket_account, serialize_nft_action_ticket_account };
 use anchor_lang::prelude::*;
 use itertools::Itertools;
 
@@ -12,22 +12,14 @@ use itertools::Itertools;
 /// If the action instruction succeed, the ticket will be closed.
 /// Otherwise, the ticket will be kept and can be used in the next action.
 ///
+/// When RegistrarExtension.config.voter_allowlist_root is set the voter must supply voter_allowlist_proof of its wallet.
+///
 /// This is the instruction for verifying NFT.
 #[derive(Accounts)]
 #[instruction(voter_weight_action:VoterWeightAction)]
 pub struct CreateNftActionTicket<'info> {
-    pub registrar: Account<'info, Registrar>,
-
-    #[account(
-        mut,
-        constraint = voter_weight_record.realm == registrar.realm
-        @ NftVoterError::InvalidVoterWeightRecordRealm,
-        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
-        @ NftVoterError::InvalidVoterWeightRecordMint,
-    )]
-    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+    pub voter: VoterAccounts<'info>,
 
-    pub voter_authority: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -35,10 +27,14 @@ pub struct CreateNftActionTicket<'info> {
 
 pub fn create_nft_action_ticket<'info>(
     ctx: Context<'_, '_, '_, 'info, CreateNftActionTicket<'info>>,
-    voter_weight_action: VoterWeightAction
+    voter_weight_action: VoterWeightAction,
+    voter_allowlist_proof: Vec<[u8; 32]>
 ) -> Result<()> {
-    let registrar = &ctx.accounts.registrar;
-    let governing_token_owner = &ctx.accounts.voter_weight_record.governing_token_owner;
+    let registrar = &ctx.accounts.voter.registrar;
+    let registrar_extension = &ctx.accounts.voter.registrar_extension;
+    let governing_token_owner = &ctx.accounts.voter.voter_weight_record.governing_token_owner;
+
+    registrar_extension.config.assert_voter_allowed(governing_token_owner, &voter_allowlist_proof)?;
     let system_program = &ctx.accounts.system_program.to_account_info();
     let payer = &ctx.accounts.payer.to_account_info();
     let mut unique_nft_mints: Vec<Pubkey> = vec![];
@@ -47,8 +43,9 @@ pub fn create_nft_action_ticket<'info>(
     for (nft_info, nft_metadata_info, nft_action_ticket_info) in ctx.remaining_accounts
         .iter()
         .tuples() {
-        let (nft_vote_weight, nft_mint) = resolve_nft_vote_weight_and_mint(
+        let (nft_vote_weight, nft_mint, collection) = resolve_nft_vote_weight_and_mint(
             registrar,
+            registrar_extension,
             &governing_token_owner,
             nft_info,
             nft_metadata_info,
@@ -75,10 +72,18 @@ pub fn create_nft_action_ticket<'info>(
             governing_token_owner: governing_token_owner.clone(),
             nft_mint: nft_mint.clone(),
             weight: nft_vote_weight,
-            expiry: Some(Clock::get()?.slot + 10),
+            collection,
+            expiry: Some(registrar_extension.config.get_nft_action_ticket_expiry(Clock::get()?.slot)),
+            delegation_expires_at: 0,
+            created_at_slot: Clock::get()?.slot,
         };
 
-        nft_action_ticket_info.data.borrow_mut().copy_from_slice(&serialized_data.try_to_vec()?);
+        serialize_nft_action_ticket_account(
+            payer,
+            nft_action_ticket_info,
+            &serialized_data.try_to_vec()?,
+            system_program
+        )?;
     }
 
     Ok(())
@@ -0,0 +1,84 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use spl_governance::state::governance;
+
+/// Creates ProposalCreationTicket for the voter and the given Governance
+/// The voter proves NFT holdings once by consuming createProposal NFT action tickets supplied in remaining_accounts
+/// and receives a short lived ticket which can be used to create a single Proposal using consume_proposal_creation_ticket
+#[derive(Accounts)]
+pub struct CreateProposalReceipt<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance the voter wants to create Proposal for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"proposal-creation-ticket".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref(),
+                governance.key().as_ref()],
+        bump,
+        payer = payer,
+        space = ProposalCreationTicket::get_space()
+    )]
+    pub proposal_creation_ticket: Account<'info, ProposalCreationTicket>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal_receipt<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CreateProposalReceipt<'info>>
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+    let governing_token_owner = &ctx.accounts.voter_weight_record.governing_token_owner;
+
+    // Ensure the Governance belongs to Registrar.realm and is owned by Registrar.governance_program_id
+    let _governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let (voter_weight, _) = consume_nft_action_tickets(
+        registrar,
+        registrar_extension,
+        std::slice::from_ref(governing_token_owner),
+        &VoterWeightAction::CreateProposal,
+        ctx.remaining_accounts,
+        &ctx.accounts.payer.to_account_info()
+    )?;
+
+    let proposal_creation_ticket = &mut ctx.accounts.proposal_creation_ticket;
+
+    proposal_creation_ticket.registrar = registrar.key();
+    proposal_creation_ticket.governing_token_owner = *governing_token_owner;
+    proposal_creation_ticket.governance = ctx.accounts.governance.key();
+    proposal_creation_ticket.voter_weight = voter_weight;
+    proposal_creation_ticket.expiry = Clock::get()?.slot
+        .checked_add(PROPOSAL_CREATION_TICKET_LIFETIME_SLOTS)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use crate::error::NftVoterError;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use crate::tools::spl_token::assert_is_valid_governing_token_mint;
 use spl_governance::state::realm;
 
 /// Creates Registrar storing NFT governance configuration for spl-gov Realm
@@ -16,10 +16,20 @@ pub struct CreateRegistrar<'info> {
         seeds = [b"registrar".as_ref(),realm.key().as_ref(), governing_token_mint.key().as_ref()],
         bump,
         payer = payer,
-        space = Registrar::get_space(max_collections)
+        space = Registrar::space_for(max_collections)
     )]
     pub registrar: Account<'info, Registrar>,
 
+    /// The RegistrarExtension storing the voting configuration of the Registrar
+    #[account(
+        init,
+        seeds = [b"registrar-extension".as_ref(), registrar.key().as_ref()],
+        bump,
+        payer = payer,
+        space = RegistrarExtension::space_for(max_collections)
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
     /// The program id of the spl-governance program the realm belongs to
     /// CHECK: Can be any instance of spl-governance and it's not known at the compilation time
     #[account(executable)]
@@ -40,7 +50,8 @@ pub struct CreateRegistrar<'info> {
     ///
     /// Note: Once the NFT plugin is enabled the governing_token_mint is used only as identity
     /// for the voting population and the tokens of that are no longer used
-    pub governing_token_mint: Account<'info, Mint>,
+    /// CHECK: spl-token or spl-token-2022 mint validated in the instruction
+    pub governing_token_mint: UncheckedAccount<'info>,
 
     /// realm_authority must sign and match Realm.authority
     pub realm_authority: Signer<'info>,
@@ -64,6 +75,13 @@ pub fn create_registrar(ctx: Context<CreateRegistrar>, _max_collections: u8) ->
     registrar.realm = ctx.accounts.realm.key();
     registrar.governing_token_mint = ctx.accounts.governing_token_mint.key();
 
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+    registrar_extension.registrar = registrar.key();
+    registrar_extension.version = REGISTRAR_EXTENSION_VERSION;
+
+    // The mint can be either spl-token or spl-token-2022 mint
+    assert_is_valid_governing_token_mint(&ctx.accounts.governing_token_mint)?;
+
     // Verify that realm_authority is the expected authority of the Realm
     // and that the mint matches one of the realm mints too
     let realm = realm::get_realm_data_for_governing_token_mint(
@@ -0,0 +1,134 @@
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::*;
+use crate::state::max_voter_weight_record::MaxVoterWeightRecord;
+use anchor_lang::prelude::*;
+use crate::tools::spl_token::assert_is_valid_governing_token_mint;
+use spl_governance::state::realm;
+
+/// Creates Registrar for spl-gov Realm with the collections and the voting options copied from RegistrarTemplate
+/// It allows protocols which spin up many sub-realms to gate them with identical NFT collections
+///
+/// The instruction updates MaxVoterWeightRecord of the Realm which must be created
+/// using create_max_voter_weight_record before the Registrar
+#[derive(Accounts)]
+#[instruction(max_collections: u8)]
+pub struct CreateRegistrarFromTemplate<'info> {
+    /// The NFT voting Registrar
+    /// There can only be a single registrar per governance Realm and governing mint of the Realm
+    #[account(
+        init,
+        seeds = [b"registrar".as_ref(),realm.key().as_ref(), governing_token_mint.key().as_ref()],
+        bump,
+        payer = payer,
+        space = Registrar::space_for(max_collections)
+    )]
+    pub registrar: Account<'info, Registrar>,
+
+    /// The RegistrarExtension storing the voting configuration of the Registrar
+    #[account(
+        init,
+        seeds = [b"registrar-extension".as_ref(), registrar.key().as_ref()],
+        bump,
+        payer = payer,
+        space = RegistrarExtension::space_for(max_collections)
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    /// The template the Registrar is created from
+    pub registrar_template: Account<'info, RegistrarTemplate>,
+
+    /// The program id of the spl-governance program the realm belongs to
+    /// CHECK: Can be any instance of spl-governance and it's not known at the compilation time
+    #[account(executable)]
+    pub governance_program_id: UncheckedAccount<'info>,
+
+    /// An spl-governance Realm
+    ///
+    /// Realm is validated in the instruction:
+    /// - Realm is owned by the governance_program_id
+    /// - governing_token_mint must be the community or council mint
+    /// - realm_authority is realm.authority
+    /// CHECK: Owned by spl-governance instance specified in governance_program_id
+    #[account(owner = governance_program_id.key())]
+    pub realm: UncheckedAccount<'info>,
+
+    /// Either the realm community mint or the council mint.
+    /// It must match Realm.community_mint or Realm.config.council_mint
+    /// CHECK: spl-token or spl-token-2022 mint validated in the instruction
+    pub governing_token_mint: UncheckedAccount<'info>,
+
+    /// realm_authority must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = max_voter_weight_record.realm == realm.key()
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+
+        constraint = max_voter_weight_record.governing_token_mint == governing_token_mint.key()
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a new Registrar for the given Realm from RegistrarTemplate
+///
+/// max_collections is used allocate account size for the maximum number of governing NFT collections
+/// and it must be at least the number of the template collections
+pub fn create_registrar_from_template(
+    ctx: Context<CreateRegistrarFromTemplate>,
+    max_collections: u8
+) -> Result<()> {
+    let registrar_template = &ctx.accounts.registrar_template;
+
+    require!(
+        registrar_template.collection_configs.len() <= (max_collections as usize),
+        NftVoterError::InvalidRegistrarTemplate
+    );
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.governance_program_id = ctx.accounts.governance_program_id.key();
+    registrar.realm = ctx.accounts.realm.key();
+    registrar.governing_token_mint = ctx.accounts.governing_token_mint.key();
+
+    // The mint can be either spl-token or spl-token-2022 mint
+    assert_is_valid_governing_token_mint(&ctx.accounts.governing_token_mint)?;
+
+    // Verify that realm_authority is the expected authority of the Realm
+    // and that the mint matches one of the realm mints too
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    registrar.collection_configs = registrar_template.collection_configs.clone();
+
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+    registrar_extension.registrar = registrar.key();
+    registrar_extension.version = REGISTRAR_EXTENSION_VERSION;
+    registrar_extension.config = registrar_template.config;
+
+    // Update MaxVoterWeightRecord based on max voting power of the copied collections
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    sanity::check_max_voter_weight(registrar, registrar_extension)?;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    // The weight never expires and only changes when collections are configured
+    max_voter_weight_record.max_voter_weight_expiry = None;
+
+    Ok(())
+}
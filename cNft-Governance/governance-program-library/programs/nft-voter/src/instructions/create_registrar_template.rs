@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::RegistrarTemplate;
+
+/// Creates RegistrarTemplate which Registrars of many Realms can be created from
+/// The template is empty and it's set up using configure_registrar_template
+#[derive(Accounts)]
+#[instruction(template_id: u64, max_collections: u8)]
+pub struct CreateRegistrarTemplate<'info> {
+    #[account(
+        init,
+        seeds = [ b"registrar-template".as_ref(),
+                authority.key().as_ref(),
+                template_id.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = RegistrarTemplate::space_for(max_collections)
+    )]
+    pub registrar_template: Account<'info, RegistrarTemplate>,
+
+    /// The authority which can configure the template
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a new RegistrarTemplate for the given authority
+///
+/// max_collections is used to allocate account size for the maximum number of collections of the template
+/// and Registrars created from the template must be allocated for at least the number of configured collections
+pub fn create_registrar_template(
+    ctx: Context<CreateRegistrarTemplate>,
+    template_id: u64,
+    max_collections: u8
+) -> Result<()> {
+    let registrar_template = &mut ctx.accounts.registrar_template;
+
+    registrar_template.authority = ctx.accounts.authority.key();
+    registrar_template.template_id = template_id;
+    registrar_template.max_collections = max_collections;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Creates an empty VoterGroup for the voting wallet
+/// Other wallets join the group with join_voter_group and their NFT action tickets
+/// can then be consumed by update_voter_weight_record and cast_nft_vote for the voting wallet
+///
+/// The voting wallet must not be a member of another VoterGroup of the Registrar
+#[derive(Accounts)]
+pub struct CreateVoterGroup<'info> {
+    /// The Registrar the group is created for
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-group".as_ref(),
+                registrar.key().as_ref(),
+                voting_wallet.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoterGroup::get_space()
+    )]
+    pub voter_group: Account<'info, VoterGroup>,
+
+    /// CHECK: VoterGroupMembership PDA of the voting wallet which must not exist
+    #[account(
+        seeds = [ b"voter-group-membership".as_ref(),
+                registrar.key().as_ref(),
+                voting_wallet.key().as_ref()],
+        bump,
+        constraint = voting_wallet_membership.data_is_empty() @ NftVoterError::WalletAlreadyInVoterGroup
+    )]
+    pub voting_wallet_membership: UncheckedAccount<'info>,
+
+    /// The wallet which votes with the weight of the group
+    pub voting_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_voter_group(ctx: Context<CreateVoterGroup>) -> Result<()> {
+    let voter_group = &mut ctx.accounts.voter_group;
+
+    voter_group.registrar = ctx.accounts.registrar.key();
+    voter_group.voting_wallet = ctx.accounts.voting_wallet.key();
+    voter_group.members = vec![];
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{ transfer, Transfer };
+
+use crate::state::*;
+
+/// Creates VoterRentEscrow for the voter and deposits the given lamports into it
+/// The deposited lamports pay the rent of the accounts created by cast_nft_vote when use_voter_rent_escrow is set
+/// The escrow can be topped up later by plain system transfers to its address
+#[derive(Accounts)]
+pub struct CreateVoterRentEscrow<'info> {
+    /// The Registrar the escrow is created for
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-rent-escrow".as_ref(),
+                registrar.key().as_ref(),
+                governing_token_owner.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoterRentEscrow::get_space()
+    )]
+    pub voter_rent_escrow: Account<'info, VoterRentEscrow>,
+
+    /// The voter the escrow is created for
+    pub governing_token_owner: Signer<'info>,
+
+    /// The account which pays for the escrow account and the deposit
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_voter_rent_escrow(ctx: Context<CreateVoterRentEscrow>, lamports: u64) -> Result<()> {
+    let voter_rent_escrow = &mut ctx.accounts.voter_rent_escrow;
+
+    voter_rent_escrow.registrar = ctx.accounts.registrar.key();
+    voter_rent_escrow.governing_token_owner = ctx.accounts.governing_token_owner.key();
+
+    if lamports > 0 {
+        transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.voter_rent_escrow.to_account_info(),
+            }),
+            lamports
+        )?;
+    }
+
+    Ok(())
+}
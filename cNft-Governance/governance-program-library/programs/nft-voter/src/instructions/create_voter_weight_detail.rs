@@ -0,0 +1,48 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Creates VoterWeightDetail which summarizes the NFTs which produced VoterWeightRecord.voter_weight
+/// The detail is maintained when RegistrarExtension.config.track_voter_weight_detail is set
+#[derive(Accounts)]
+#[instruction(max_collections: u8)]
+pub struct CreateVoterWeightDetail<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-weight-detail".as_ref(),
+                voter_weight_record.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoterWeightDetail::space_for(max_collections)
+    )]
+    pub voter_weight_detail: Account<'info, VoterWeightDetail>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_voter_weight_detail(
+    ctx: Context<CreateVoterWeightDetail>,
+    max_collections: u8,
+) -> Result<()> {
+    let voter_weight_detail = &mut ctx.accounts.voter_weight_detail;
+
+    voter_weight_detail.voter_weight_record = ctx.accounts.voter_weight_record.key();
+    voter_weight_detail.max_collections = max_collections;
+
+    Ok(())
+}
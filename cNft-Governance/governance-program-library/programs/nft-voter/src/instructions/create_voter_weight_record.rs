@@ -1,6 +1,6 @@
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use crate::tools::spl_token::assert_is_valid_governing_token_mint;
 use spl_governance::state::realm;
 
 /// Creates VoterWeightRecord used by spl-gov
@@ -31,7 +31,8 @@ pub struct CreateVoterWeightRecord<'info> {
     pub realm: UncheckedAccount<'info>,
 
     /// Either the realm community mint or the council mint.
-    pub realm_governing_token_mint: Account<'info, Mint>,
+    /// CHECK: spl-token or spl-token-2022 mint validated in the instruction
+    pub realm_governing_token_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -43,6 +44,9 @@ pub fn create_voter_weight_record(
     ctx: Context<CreateVoterWeightRecord>,
     governing_token_owner: Pubkey,
 ) -> Result<()> {
+    // The mint can be either spl-token or spl-token-2022 mint
+    assert_is_valid_governing_token_mint(&ctx.accounts.realm_governing_token_mint)?;
+
     // Deserialize the Realm to validate it
     let _realm = realm::get_realm_data_for_governing_token_mint(
         &ctx.accounts.governance_program_id.key(),
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Creates VotingSession authorizing the session authority to cast votes for the voter until the given slot
+/// The session authority can sign cast_nft_vote instead of the voter and it can't sign any other instruction
+///
+/// Creating a session again replaces the session authority and the expiry of the existing session
+#[derive(Accounts)]
+pub struct CreateVotingSession<'info> {
+    /// The Registrar the session is created for
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"voting-session".as_ref(),
+                registrar.key().as_ref(),
+                governing_token_owner.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VotingSession::get_space()
+    )]
+    pub voting_session: Account<'info, VotingSession>,
+
+    /// The voter who authorizes the session
+    pub governing_token_owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_voting_session(
+    ctx: Context<CreateVotingSession>,
+    session_authority: Pubkey,
+    expires_at_slot: u64
+) -> Result<()> {
+    let current_slot = Clock::get()?.slot;
+
+    require!(
+        expires_at_slot > current_slot &&
+            expires_at_slot - current_slot <= MAX_VOTING_SESSION_DURATION_SLOTS,
+        NftVoterError::InvalidVotingSessionExpiry
+    );
+
+    let voting_session = &mut ctx.accounts.voting_session;
+
+    voting_session.registrar = ctx.accounts.registrar.key();
+    voting_session.governing_token_owner = ctx.accounts.governing_token_owner.key();
+    voting_session.session_authority = session_authority;
+    voting_session.expires_at_slot = expires_at_slot;
+
+    Ok(())
+}
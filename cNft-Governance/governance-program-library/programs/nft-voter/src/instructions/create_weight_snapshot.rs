@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::{ governance, proposal as spl_proposal, realm };
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Creates a WeightSnapshot of the voter weights as of snapshot_slot for the Proposal
+/// The voters cast their votes on the Proposal with cast_snapshot_vote using the weights of the snapshot
+/// instead of their live holdings
+///
+/// The snapshot is posted by the realm authority or by RegistrarExtension.config.holding_attestation_authority (oracle)
+/// and it can't be changed once created
+#[derive(Accounts)]
+#[instruction(proposal: Pubkey)]
+pub struct CreateWeightSnapshot<'info> {
+    /// Registrar for which we create the snapshot
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm or the holding attestation authority of the Registrar
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"weight-snapshot".as_ref(),
+                registrar.key().as_ref(),
+                proposal.as_ref()],
+        bump,
+        payer = payer,
+        space = WeightSnapshot::get_space()
+    )]
+    pub weight_snapshot: Account<'info, WeightSnapshot>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal of the snapshot which must match the proposal argument
+    #[account(
+        owner = registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_weight_snapshot(
+    ctx: Context<CreateWeightSnapshot>,
+    proposal: Pubkey,
+    snapshot_slot: u64,
+    holdings_root: [u8; 32],
+    total_weight: u64
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+    let authority = ctx.accounts.authority.key();
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint
+    )?;
+
+    require!(
+        realm.authority == Some(authority) ||
+            (registrar_extension.config.holding_attestation_authority != Pubkey::default() &&
+                registrar_extension.config.holding_attestation_authority == authority),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    // The holdings can only be taken at a slot which already happened
+    require!(snapshot_slot <= Clock::get()?.slot, NftVoterError::InvalidWeightSnapshotSlot);
+
+    // Ensure the Proposal belongs to Registrar.realm and it's voted with Registrar.governing_token_mint
+    governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    let weight_snapshot = &mut ctx.accounts.weight_snapshot;
+
+    weight_snapshot.registrar = registrar.key();
+    weight_snapshot.proposal = proposal;
+    weight_snapshot.snapshot_slot = snapshot_slot;
+    weight_snapshot.holdings_root = holdings_root;
+    weight_snapshot.total_weight = total_weight;
+    weight_snapshot.authority = authority;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{ transfer, Transfer };
+use anchor_lang::Discriminator;
+use solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ GovernanceWeightConfig, Registrar, RegistrarExtension };
+
+/// Extends the Registrar or an account of the Registrar by the given number of zeroed bytes
+/// It allows future versions to append fields (caps, modes, stats) to the existing accounts
+/// without recreating the PDAs and it can also be used to make room for more collections on the Registrar
+///
+/// Only the Anchor accounts which tolerate trailing space can be extended (Registrar, RegistrarExtension and GovernanceWeightConfig)
+/// The records with explicit discriminators (NftVoteRecord etc.) are versioned and new fields are appended with new versions
+#[derive(Accounts)]
+pub struct ExtendAccount<'info> {
+    /// Registrar the extended account belongs to
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    /// CHECK: The Registrar itself or an account of the Registrar which is validated in the instruction
+    #[account(mut, owner = crate::id() @ NftVoterError::InvalidAccountOwner)]
+    pub account: UncheckedAccount<'info>,
+
+    /// The account which pays the rent for the extra space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn extend_account(ctx: Context<ExtendAccount>, extra_space: u32) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    require!(
+        extra_space > 0 && (extra_space as usize) <= MAX_PERMITTED_DATA_INCREASE,
+        NftVoterError::InvalidAccountExtension
+    );
+
+    let account_info = ctx.accounts.account.to_account_info();
+
+    require_keys_eq!(
+        get_extendable_account_registrar(&account_info)?,
+        registrar.key(),
+        NftVoterError::AccountCannotBeExtended
+    );
+
+    let new_size = account_info
+        .data_len()
+        .checked_add(extra_space as usize)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+    let rent_lamports = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(account_info.lamports());
+
+    if rent_lamports > 0 {
+        transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account_info.clone(),
+            }),
+            rent_lamports
+        )?;
+    }
+
+    account_info.realloc(new_size, true)?;
+
+    Ok(())
+}
+
+/// Returns the Registrar the given account belongs to if it's an account which can be extended
+fn get_extendable_account_registrar(account_info: &AccountInfo) -> Result<Pubkey> {
+    let data = account_info.try_borrow_data()?;
+
+    match data.get(..8) {
+        Some(discriminator) if discriminator == Registrar::discriminator() => {
+            Ok(account_info.key())
+        }
+        Some(discriminator) if discriminator == RegistrarExtension::discriminator() => {
+            Ok(RegistrarExtension::try_deserialize(&mut &data[..])?.registrar)
+        }
+        Some(discriminator) if discriminator == GovernanceWeightConfig::discriminator() => {
+            Ok(GovernanceWeightConfig::try_deserialize(&mut &data[..])?.registrar)
+        }
+        _ => err!(NftVoterError::AccountCannotBeExtended),
+    }
+}
@@ -0,0 +1,163 @@
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::*;
+use crate::tools::governance::{
+    assert_proposal_action_deadline,
+    assert_voter_weight_record_consumed_in_transaction,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::Accounts;
+use solana_program::sysvar;
+use spl_governance::state::{ governance, proposal as spl_proposal };
+
+/// Finalizes NFT vote accumulated by several CastNftVote instructions
+///
+/// Voters with more NFTs than fit into a single transaction (or RegistrarExtension.config.max_assets_per_tx)
+/// invoke CastNftVote in several transactions and each of them adds the weight of its NFTs to VoterWeightRecord
+/// FinalizeNftVote doesn't count any NFTs. It makes the accumulated weight valid for the current slot
+/// and has to be executed inside the same transaction as spl-gov.CastVote
+///
+/// The accumulated weight is backed by the NftVoteRecords created for the Proposal and it's reset by RelinquishNftVote
+///
+/// When RegistrarExtension.config.require_vote_finalization is set the VoterWeightRecord must be Accumulating
+/// and it must be taken by spl-gov.CastVote following this instruction in the same transaction
+///
+/// When RegistrarExtension.config.min_weight_if_registered_before_slot is set the VoterRegistration PDA of the voter
+/// must be supplied as the first remaining account and the finalized weight is never lower than the floor weight of the voter
+///
+/// When RegistrarExtension.config.allow_wallet_weight_overrides is set the WalletWeightOverride PDA of the voter
+/// must be supplied after the optional VoterRegistration and the finalized weight is scaled by the override
+#[derive(Accounts)]
+#[instruction(proposal: Pubkey)]
+pub struct FinalizeNftVote<'info> {
+    /// The NFT voting registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// TokenOwnerRecord of the voter who casts the vote
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    #[account(owner = registrar.governance_program_id)]
+    voter_token_owner_record: UncheckedAccount<'info>,
+
+    /// Authority of the voter who casts the vote
+    /// It can be either governing_token_owner or its delegate and must sign this instruction
+    pub voter_authority: Signer<'info>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal the vote is cast on which must match the proposal argument
+    #[account(
+        owner = registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar used to ensure the VoterWeightRecord is consumed in the same transaction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn finalize_nft_vote(ctx: Context<FinalizeNftVote>, proposal: Pubkey) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+
+    resolve_governing_token_owner(
+        registrar,
+        &ctx.accounts.voter_token_owner_record,
+        &ctx.accounts.voter_authority,
+        voter_weight_record
+    )?;
+
+    // Ensure the Governance belongs to Registrar.realm
+    let governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let target_proposal = spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    assert_proposal_action_deadline(
+        &target_proposal,
+        &governance.config,
+        &VoterWeightAction::CastVote
+    )?;
+
+    require!(
+        voter_weight_record.weight_action_target == Some(proposal) &&
+            voter_weight_record.weight_action == Some(VoterWeightAction::CastVote),
+        NftVoterError::VoteWeightNotAccumulated
+    );
+
+    let current_slot = Clock::get()?.slot;
+
+    if registrar_extension.config.require_vote_finalization {
+        require!(
+            voter_weight_record.get_weight_state(current_slot) == VoterWeightState::Accumulating,
+            NftVoterError::VoteWeightNotAccumulated
+        );
+
+        assert_voter_weight_record_consumed_in_transaction(
+            &ctx.accounts.instructions,
+            &registrar.governance_program_id,
+            &voter_weight_record.key()
+        )?;
+    }
+
+    let (voter_weight_adjustment, _) = split_voter_weight_adjustment_accounts(
+        registrar,
+        registrar_extension,
+        &voter_weight_record.governing_token_owner,
+        ctx.remaining_accounts
+    )?;
+
+    let voter_weight = voter_weight_record.voter_weight;
+    let previous_voter_weight_expiry = voter_weight_record.voter_weight_expiry;
+
+    // The record is only valid as of the current slot
+    finalize_voter_weight(
+        voter_weight_record,
+        voter_weight,
+        current_slot,
+        &voter_weight_adjustment
+    );
+
+    sanity::check_voter_weight_expiry(
+        previous_voter_weight_expiry,
+        voter_weight_record.voter_weight_expiry
+    )?;
+
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, 0).set()
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, VoterFreezeRecord };
+
+/// Freezes the given voter by creating VoterFreezeRecord
+/// A frozen voter can't cast votes or update its voter weight until it's unfrozen by unfreeze_voter
+/// It's an emergency instruction for the Realm authority to respond to compromised wallets (ex. stolen cNFTs)
+#[derive(Accounts)]
+#[instruction(governing_token_owner: Pubkey)]
+pub struct FreezeVoter<'info> {
+    /// Registrar for which we freeze the voter
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                governing_token_owner.as_ref()],
+        bump,
+        payer = payer,
+        space = VoterFreezeRecord::get_space()
+    )]
+    pub voter_freeze_record: Account<'info, VoterFreezeRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn freeze_voter(ctx: Context<FreezeVoter>, governing_token_owner: Pubkey) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    let voter_freeze_record = &mut ctx.accounts.voter_freeze_record;
+
+    voter_freeze_record.registrar = registrar.key();
+    voter_freeze_record.governing_token_owner = governing_token_owner;
+    voter_freeze_record.frozen_at = Clock::get()?.slot;
+
+    Ok(())
+}
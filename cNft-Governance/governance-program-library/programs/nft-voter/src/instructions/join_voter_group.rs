@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Adds the member to the VoterGroup of the voting wallet
+/// Both the member and the voting wallet must sign so a wallet can't be added to a group
+/// nor join a group without the consent of the other side
+///
+/// The VoterGroupMembership of the member is created so the member can't join another VoterGroup
+/// and the member must not be the voting wallet of a VoterGroup either
+#[derive(Accounts)]
+pub struct JoinVoterGroup<'info> {
+    #[account(
+        mut,
+        has_one = voting_wallet @ NftVoterError::InvalidVoterGroup,
+    )]
+    pub voter_group: Account<'info, VoterGroup>,
+
+    /// The wallet which votes with the weight of the group
+    pub voting_wallet: Signer<'info>,
+
+    /// The wallet joining the group
+    pub member: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-group-membership".as_ref(),
+                voter_group.registrar.as_ref(),
+                member.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoterGroupMembership::get_space()
+    )]
+    pub voter_group_membership: Account<'info, VoterGroupMembership>,
+
+    /// CHECK: VoterGroup PDA of the member which must not exist
+    #[account(
+        seeds = [ b"voter-group".as_ref(),
+                voter_group.registrar.as_ref(),
+                member.key().as_ref()],
+        bump,
+        constraint = member_voter_group.data_is_empty() @ NftVoterError::WalletAlreadyInVoterGroup
+    )]
+    pub member_voter_group: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn join_voter_group(ctx: Context<JoinVoterGroup>) -> Result<()> {
+    let voter_group = &mut ctx.accounts.voter_group;
+    let member = ctx.accounts.member.key();
+
+    voter_group.add_member(&member)?;
+
+    let voter_group_membership = &mut ctx.accounts.voter_group_membership;
+
+    voter_group_membership.registrar = voter_group.registrar;
+    voter_group_membership.voter_group = voter_group.key();
+    voter_group_membership.member = member;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Removes the member from the VoterGroup, closes its VoterGroupMembership and returns the rent to the beneficiary
+/// The member can be removed by either the member or the voting wallet of the group
+/// Note: Tickets of the member consumed before it left the group stay counted in the current VoterWeightRecord
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct LeaveVoterGroup<'info> {
+    #[account(
+        mut,
+        constraint = voter_group.voting_wallet == authority.key() || member == authority.key()
+        @ NftVoterError::InvalidVoterGroupMember,
+    )]
+    pub voter_group: Account<'info, VoterGroup>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [ b"voter-group-membership".as_ref(),
+                voter_group.registrar.as_ref(),
+                member.as_ref()],
+        bump,
+        has_one = voter_group @ NftVoterError::InvalidVoterGroupMember,
+    )]
+    pub voter_group_membership: Account<'info, VoterGroupMembership>,
+
+    /// The member or the voting wallet of the group
+    pub authority: Signer<'info>,
+
+    /// CHECK: Any account chosen by the authority to receive the rent
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
+pub fn leave_voter_group(ctx: Context<LeaveVoterGroup>, member: Pubkey) -> Result<()> {
+    ctx.accounts.voter_group.remove_member(&member)
+}
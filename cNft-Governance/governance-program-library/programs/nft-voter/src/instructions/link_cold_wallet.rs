@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use solana_program::sysvar;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::ed25519::assert_ed25519_signature;
+
+/// Links the cold wallet to the hot wallet and authorizes the hot wallet to vote with the compressed NFTs
+/// held by the cold wallet until the given expiry
+/// The cold wallet doesn't sign the transaction and authorizes the link with an ed25519 signature
+/// of get_cold_wallet_link_message() verified by the ed25519 program instruction preceding this instruction
+///
+/// The link can be renewed or moved to another hot wallet with a new signature and later expiry
+/// Note: The expiry must increase with every signature to prevent replaying the previous signatures
+/// and it's also the way to restore a revoked link
+#[derive(Accounts)]
+#[instruction(cold_wallet: Pubkey)]
+pub struct LinkColdWallet<'info> {
+    /// The Registrar the cold wallet is linked for
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"cold-wallet-link".as_ref(),
+                registrar.key().as_ref(),
+                cold_wallet.as_ref()],
+        bump,
+        payer = payer,
+        space = ColdWalletLink::get_space()
+    )]
+    pub cold_wallet_link: Account<'info, ColdWalletLink>,
+
+    /// The hot wallet authorized by the cold wallet
+    pub hot_wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar used to read the cold wallet signature
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn link_cold_wallet(
+    ctx: Context<LinkColdWallet>,
+    cold_wallet: Pubkey,
+    expires_at: i64
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let hot_wallet = ctx.accounts.hot_wallet.key();
+
+    let unix_timestamp = Clock::get()?.unix_timestamp;
+
+    require!(
+        expires_at > unix_timestamp &&
+            expires_at - unix_timestamp <= MAX_COLD_WALLET_LINK_DURATION,
+        NftVoterError::InvalidColdWalletLinkExpiry
+    );
+
+    assert_ed25519_signature(
+        &ctx.accounts.instructions,
+        &cold_wallet,
+        &get_cold_wallet_link_message(&registrar.key(), &hot_wallet, expires_at)
+    )?;
+
+    let cold_wallet_link = &mut ctx.accounts.cold_wallet_link;
+
+    require!(
+        expires_at > cold_wallet_link.expires_at,
+        NftVoterError::InvalidColdWalletLinkExpiry
+    );
+
+    cold_wallet_link.registrar = registrar.key();
+    cold_wallet_link.cold_wallet = cold_wallet;
+    cold_wallet_link.hot_wallet = hot_wallet;
+    cold_wallet_link.expires_at = expires_at;
+    cold_wallet_link.revoked = false;
+
+    Ok(())
+}
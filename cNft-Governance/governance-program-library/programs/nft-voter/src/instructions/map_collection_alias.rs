@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ CollectionAlias, Registrar, MAX_BASIS_POINTS };
+
+/// Maps the previous collection of a collection migration to the configured target collection
+/// The compressed NFTs of the previous collection count under the target collection config
+/// with the given weight_bps of its weight when RegistrarExtension.config.allow_collection_aliases is set
+///
+/// The alias can be remapped to change the target collection or the weight and it can be neutralized with weight_bps 0
+/// Note: The assets of both collections count towards the target collection size used for the max voter weight
+/// and the size must be configured for the holders of both collections
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct MapCollectionAlias<'info> {
+    /// Registrar for which we map the collection alias
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"collection-alias".as_ref(),
+                registrar.key().as_ref(),
+                collection.as_ref()],
+        bump,
+        payer = payer,
+        space = CollectionAlias::get_space()
+    )]
+    pub collection_alias: Account<'info, CollectionAlias>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn map_collection_alias(
+    ctx: Context<MapCollectionAlias>,
+    collection: Pubkey,
+    target_collection: Pubkey,
+    weight_bps: u16
+) -> Result<()> {
+    require!(weight_bps <= MAX_BASIS_POINTS, NftVoterError::InvalidCollectionAliasWeight);
+
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    // The configured collections always count under their own config
+    require!(
+        registrar.find_collection_config(&collection).is_none(),
+        NftVoterError::InvalidCollectionAlias
+    );
+
+    registrar.get_collection_config(&target_collection)?;
+
+    let collection_alias = &mut ctx.accounts.collection_alias;
+
+    collection_alias.registrar = registrar.key();
+    collection_alias.collection = collection;
+    collection_alias.target_collection = target_collection;
+    collection_alias.weight_bps = weight_bps;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Creates the RegistrarExtension of a Registrar created by a previous version of the program
+///
+/// The Registrar itself isn't changed and the extension is created with the default config
+/// which preserves the voting behaviour the Registrar had before the upgrade
+/// The instruction is permissionless and the realm authority can change the config with configure_registrar afterwards
+///
+/// The migration time is recorded as RegistrarExtension.legacy_nft_vote_records_cutoff
+/// Votes on the Proposals which started voting before it must prove the NFTs don't have legacy NftVoteRecords
+///
+/// max_collections is the number of collections the extension is allocated for
+/// and it limits the collections which can have creator boosts, expiry or a weight strategy
+#[derive(Accounts)]
+#[instruction(max_collections: u8)]
+pub struct MigrateRegistrar<'info> {
+    /// The Registrar to migrate
+    pub registrar: Account<'info, Registrar>,
+
+    /// The RegistrarExtension of the Registrar
+    /// It can only be created once and the instruction fails for Registrars which already have the extension
+    #[account(
+        init,
+        seeds = [b"registrar-extension".as_ref(), registrar.key().as_ref()],
+        bump,
+        payer = payer,
+        space = RegistrarExtension::space_for(max_collections)
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_registrar(ctx: Context<MigrateRegistrar>, _max_collections: u8) -> Result<()> {
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+    registrar_extension.registrar = ctx.accounts.registrar.key();
+    registrar_extension.version = REGISTRAR_EXTENSION_VERSION;
+    registrar_extension.legacy_nft_vote_records_cutoff = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::accounts_cursor::AccountsCursor;
+use crate::tools::anchor::{ get_anchor_account_data, set_anchor_account_data };
+use crate::tools::governance::get_vote_record_address;
+use anchor_lang::prelude::*;
+use spl_governance::state::{ enums::ProposalState, proposal as spl_proposal };
+
+/// Migrates the voter to a new wallet when the previous wallet is rotated (ex. it was compromised)
+/// Both wallets must sign the instruction and the migration must be allowed by RegistrarExtension.config.allow_voter_migration
+///
+/// The open NftVoteRecords of the previous wallet are moved to the new wallet which can then relinquish them
+/// and the ColdWalletLinks authorizing the previous wallet as the hot wallet are moved to the new wallet
+/// VoterMigration is created for the new wallet and the compressed NFTs moved from the previous wallet
+/// keep the holding period attested for the previous wallet (see create_cnft_action_ticket)
+///
+/// The remaining accounts are cold_wallet_links_count ColdWalletLinks followed by
+/// (nft_vote_record, proposal, vote_record) for each NftVoteRecord where vote_record is the spl-gov VoteRecord
+/// of the previous wallet for the Proposal
+///
+/// The NftVoteRecords of Proposals which are still in Voting state can only be moved once the vote of the previous wallet
+/// was withdrawn in spl-gov. Otherwise the new wallet could relinquish the records and vote with the same NFTs again
+///
+/// Note: The accounts with PDAs derived from the wallet (ex. VoterWeightRecord) are not moved and must be created for the new wallet
+#[derive(Accounts)]
+pub struct MigrateVoter<'info> {
+    /// The NFT voting registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    /// The wallet the voter migrates from
+    pub previous_wallet: Signer<'info>,
+
+    /// The wallet the voter migrates to
+    #[account(
+        constraint = new_wallet.key() != previous_wallet.key() @ NftVoterError::InvalidVoterMigration
+    )]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-migration".as_ref(),
+                registrar.key().as_ref(),
+                new_wallet.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoterMigration::get_space()
+    )]
+    pub voter_migration: Account<'info, VoterMigration>,
+
+    /// CHECK: VoterFreezeRecord PDA of the previous wallet which must not exist for the voter to be able to migrate
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                previous_wallet.key().as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_voter<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MigrateVoter<'info>>,
+    cold_wallet_links_count: u8
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+
+    require!(registrar_extension.config.allow_voter_migration, NftVoterError::VoterMigrationNotAllowed);
+
+    let previous_wallet = ctx.accounts.previous_wallet.key();
+    let new_wallet = ctx.accounts.new_wallet.key();
+
+    let mut accounts_cursor = AccountsCursor::new(ctx.remaining_accounts);
+
+    for cold_wallet_link_info in accounts_cursor
+        .take(cold_wallet_links_count as usize, "cold_wallet_link")?
+        .iter() {
+        let mut cold_wallet_link = get_anchor_account_data::<ColdWalletLink>(cold_wallet_link_info)?;
+
+        require!(
+            cold_wallet_link.registrar == registrar.key() &&
+                cold_wallet_link.hot_wallet == previous_wallet,
+            NftVoterError::InvalidColdWalletLink
+        );
+
+        cold_wallet_link.hot_wallet = new_wallet;
+        set_anchor_account_data(cold_wallet_link_info, &cold_wallet_link)?;
+    }
+
+    while !accounts_cursor.is_empty() {
+        let nft_vote_record_info = accounts_cursor.next("nft_vote_record")?;
+        let proposal_info = accounts_cursor.next("proposal")?;
+        let vote_record_info = accounts_cursor.next("vote_record")?;
+
+        let mut nft_vote_record = get_nft_vote_record_data_for_proposal_and_token_owner(
+            nft_vote_record_info,
+            proposal_info.key,
+            &previous_wallet
+        )?;
+
+        // Only the records of the Registrar can be moved
+        require_keys_eq!(
+            get_nft_vote_record_address(&registrar.key(), proposal_info.key, &nft_vote_record.nft_mint),
+            *nft_vote_record_info.key,
+            NftVoterError::InvalidNftVoteRecord
+        );
+
+        let proposal = spl_proposal::get_proposal_data(
+            &registrar.governance_program_id,
+            proposal_info
+        )?;
+
+        // The vote of the previous wallet must be withdrawn the same way it must be for relinquish_nft_vote
+        if proposal.state == ProposalState::Voting {
+            require_keys_eq!(
+                get_vote_record_address(
+                    &registrar.governance_program_id,
+                    &registrar.realm,
+                    &registrar.governing_token_mint,
+                    &previous_wallet,
+                    proposal_info.key
+                ),
+                *vote_record_info.key,
+                NftVoterError::InvalidVoteRecordForNftVoteRecord
+            );
+
+            require!(
+                vote_record_info.data_is_empty() || vote_record_info.try_borrow_data()?[0] == 0,
+                NftVoterError::VoteRecordMustBeWithdrawn
+            );
+        }
+
+        nft_vote_record.governing_token_owner = new_wallet;
+        nft_vote_record.serialize(&mut &mut nft_vote_record_info.try_borrow_mut_data()?[..])?;
+    }
+
+    accounts_cursor.finish()?;
+
+    let voter_migration = &mut ctx.accounts.voter_migration;
+
+    voter_migration.registrar = registrar.key();
+    voter_migration.previous_wallet = previous_wallet;
+    voter_migration.new_wallet = new_wallet;
+    voter_migration.migrated_at_slot = Clock::get()?.slot;
+
+    Ok(())
+}
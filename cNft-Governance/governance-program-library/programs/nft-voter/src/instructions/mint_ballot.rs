@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::{ program::SplAccountCompression, Noop };
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::program_ids::Bubblegum;
+
+/// Mints a ballot cNFT of the BallotTree to the given member of the DAO
+/// The ballot metadata is the name, symbol and uri of the BallotTree with the BallotTree as the only verified creator
+///
+/// The Realm authority must sign and the mint is signed by the BallotTree as the tree delegate
+#[derive(Accounts)]
+pub struct MintBallot<'info> {
+    /// Registrar the BallotTree belongs to
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ b"ballot-tree".as_ref(),
+                registrar.key().as_ref(),
+                merkle_tree.key().as_ref()],
+        bump,
+        has_one = registrar,
+        has_one = merkle_tree
+    )]
+    pub ballot_tree: Account<'info, BallotTree>,
+
+    /// CHECK: Bubblegum TreeConfig of the tree validated by Bubblegum
+    #[account(mut)]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Merkle tree of the BallotTree validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Any wallet of a member the ballot is minted to
+    pub leaf_owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_ballot(ctx: Context<MintBallot>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    let ballot_metadata = ctx.accounts.ballot_tree.get_ballot_metadata(&ctx.accounts.ballot_tree.key());
+
+    mint_ballot_to(
+        &mut ctx.accounts.ballot_tree,
+        ctx.bumps.ballot_tree,
+        ballot_metadata,
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.leaf_owner.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.bubblegum_program.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+
+    Ok(())
+}
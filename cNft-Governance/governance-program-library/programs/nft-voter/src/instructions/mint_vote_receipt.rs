@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::{ program::SplAccountCompression, Noop };
+use spl_governance::state::{ governance, proposal, vote_record };
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::governance::get_vote_record_address;
+use crate::tools::program_ids::Bubblegum;
+use ::cnft_verification::get_asset_id_with_program;
+
+/// Mints the vote receipt cNFT to the voter who voted on the Proposal
+/// The receipt is minted to the BallotTree of RegistrarConfig.vote_receipt_tree and its uri encodes the Proposal
+/// and the Vote recorded by spl-gov in the VoteRecord of the voter
+///
+/// Anyone can mint the receipt once the vote is cast and the payer pays the fees and the rent of VoteReceipt
+/// The VoteReceipt PDA ensures a single receipt is minted for the vote of the voter on the Proposal
+///
+/// Note: It requires RegistrarExtension.config.vote_receipt_tree
+#[derive(Accounts)]
+pub struct MintVoteReceipt<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(
+        has_one = registrar @ NftVoterError::InvalidRegistrarExtension
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub proposal: UncheckedAccount<'info>,
+
+    /// CHECK: The spl-gov VoteRecord of the voter on the Proposal validated in the instruction
+    pub vote_record: UncheckedAccount<'info>,
+
+    /// CHECK: The voter the receipt is minted to, it doesn't have to sign
+    pub governing_token_owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"vote-receipt".as_ref(),
+                registrar.key().as_ref(),
+                proposal.key().as_ref(),
+                governing_token_owner.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoteReceipt::get_space()
+    )]
+    pub vote_receipt: Account<'info, VoteReceipt>,
+
+    #[account(
+        mut,
+        seeds = [ b"ballot-tree".as_ref(),
+                registrar.key().as_ref(),
+                merkle_tree.key().as_ref()],
+        bump,
+        has_one = registrar,
+        has_one = merkle_tree
+    )]
+    pub ballot_tree: Account<'info, BallotTree>,
+
+    /// CHECK: Bubblegum TreeConfig of the tree validated by Bubblegum
+    #[account(mut)]
+    pub tree_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Merkle tree of the BallotTree validated by spl-account-compression
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// The fee payer of the receipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub bubblegum_program: Program<'info, Bubblegum>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_vote_receipt(ctx: Context<MintVoteReceipt>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let config = &ctx.accounts.registrar_extension.config;
+
+    require!(config.is_vote_receipts_enabled(), NftVoterError::VoteReceiptsNotEnabled);
+    require_keys_eq!(
+        ctx.accounts.merkle_tree.key(),
+        config.vote_receipt_tree,
+        NftVoterError::InvalidBallotTree
+    );
+
+    // Ensure the Governance belongs to Registrar.realm and is owned by Registrar.governance_program_id
+    let _governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    // Ensure the Proposal belongs to Governance from Registrar.realm and Registrar.governing_token_mint
+    let _proposal = proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    let proposal_key = ctx.accounts.proposal.key();
+    let governing_token_owner = ctx.accounts.governing_token_owner.key();
+
+    require_keys_eq!(
+        ctx.accounts.vote_record.key(),
+        get_vote_record_address(
+            &registrar.governance_program_id,
+            &registrar.realm,
+            &registrar.governing_token_mint,
+            &governing_token_owner,
+            &proposal_key
+        ),
+        NftVoterError::InvalidVoteRecordAccount
+    );
+
+    // The VoteRecord is created by spl-gov CastVote and disposed when the vote is withdrawn
+    // hence it only exists when the voter voted on the Proposal
+    let vote = vote_record::get_vote_record_data(
+        &registrar.governance_program_id,
+        &ctx.accounts.vote_record
+    )?.vote;
+
+    let ballot_tree_key = ctx.accounts.ballot_tree.key();
+    let metadata = ctx.accounts.ballot_tree.get_vote_receipt_metadata(
+        &ballot_tree_key,
+        &proposal_key,
+        &vote
+    )?;
+
+    // The BallotTree is the only minter of the private tree and hence the nonce of the receipt
+    // is the number of the cNFTs minted to the tree before
+    let asset_id = get_asset_id_with_program(
+        &ctx.accounts.merkle_tree.key(),
+        ctx.accounts.ballot_tree.ballots_minted,
+        &ctx.accounts.bubblegum_program.key()
+    );
+
+    mint_ballot_to(
+        &mut ctx.accounts.ballot_tree,
+        ctx.bumps.ballot_tree,
+        metadata,
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.governing_token_owner.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.bubblegum_program.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info()
+    )?;
+
+    let vote_receipt = &mut ctx.accounts.vote_receipt;
+
+    vote_receipt.registrar = registrar.key();
+    vote_receipt.proposal = proposal_key;
+    vote_receipt.governing_token_owner = governing_token_owner;
+    vote_receipt.vote_kind = NftVoteKind::from(&vote);
+    vote_receipt.asset_id = asset_id;
+
+    Ok(())
+}
@@ -1,26 +1,194 @@
 pub use configure_collection::*;
 mod configure_collection;
 
+pub use configure_collections_batch::*;
+mod configure_collections_batch;
+
+pub use configure_creator_boosts::*;
+mod configure_creator_boosts;
+
+pub use configure_registrar::*;
+mod configure_registrar;
+
+pub use configure_governance_weight::*;
+mod configure_governance_weight;
+
+pub use configure_sub_registrar::*;
+mod configure_sub_registrar;
+
+pub use sync_parent_weight::*;
+mod sync_parent_weight;
+
 pub use create_registrar::*;
 mod create_registrar;
 
+pub use migrate_registrar::*;
+mod migrate_registrar;
+
+pub use create_registrar_template::*;
+mod create_registrar_template;
+
+pub use configure_registrar_template::*;
+mod configure_registrar_template;
+
+pub use create_registrar_from_template::*;
+mod create_registrar_from_template;
+
 pub use create_voter_weight_record::*;
 mod create_voter_weight_record;
 
 pub use create_max_voter_weight_record::*;
 mod create_max_voter_weight_record;
 
+pub use create_voter_weight_detail::*;
+mod create_voter_weight_detail;
+
 pub use update_voter_weight_record::*;
 mod update_voter_weight_record;
 
+pub use update_voter_weight_record_for_target::*;
+mod update_voter_weight_record_for_target;
+
 pub use relinquish_nft_vote::*;
 mod relinquish_nft_vote;
 
 pub use cast_nft_vote::*;
 mod cast_nft_vote;
 
+pub use finalize_nft_vote::*;
+mod finalize_nft_vote;
+
+pub use voter_accounts::*;
+mod voter_accounts;
+
 pub use create_cnft_action_ticket::*;
 mod create_cnft_action_ticket;
 
 pub use create_nft_action_ticket::*;
 mod create_nft_action_ticket;
+
+pub use create_core_asset_action_ticket::*;
+mod create_core_asset_action_ticket;
+
+pub use create_proposal_receipt::*;
+mod create_proposal_receipt;
+
+pub use consume_proposal_creation_ticket::*;
+mod consume_proposal_creation_ticket;
+
+pub use freeze_voter::*;
+mod freeze_voter;
+
+pub use unfreeze_voter::*;
+mod unfreeze_voter;
+
+pub use reclaim_nft_vote_records::*;
+mod reclaim_nft_vote_records;
+
+pub use extend_account::*;
+mod extend_account;
+
+pub use configure_collection_expiry::*;
+mod configure_collection_expiry;
+
+pub use prune_expired_collections::*;
+mod prune_expired_collections;
+
+pub use preview_voter_weight::*;
+mod preview_voter_weight;
+
+pub use link_cold_wallet::*;
+mod link_cold_wallet;
+
+pub use revoke_cold_wallet_link::*;
+mod revoke_cold_wallet_link;
+
+pub use close_expired_delegation::*;
+mod close_expired_delegation;
+
+pub use create_voter_rent_escrow::*;
+mod create_voter_rent_escrow;
+
+pub use close_voter_rent_escrow::*;
+mod close_voter_rent_escrow;
+
+pub use create_voting_session::*;
+mod create_voting_session;
+
+pub use revoke_voting_session::*;
+mod revoke_voting_session;
+
+pub use validate_realm_config::*;
+mod validate_realm_config;
+
+pub use configure_collection_weight_strategy::*;
+mod configure_collection_weight_strategy;
+
+pub use configure_collection_uses_limit::*;
+mod configure_collection_uses_limit;
+
+pub use cast_nft_vote_multi::*;
+mod cast_nft_vote_multi;
+
+pub use select_multi_proposal_vote::*;
+mod select_multi_proposal_vote;
+
+pub use migrate_voter::*;
+mod migrate_voter;
+
+pub use create_voter_group::*;
+mod create_voter_group;
+
+pub use join_voter_group::*;
+mod join_voter_group;
+
+pub use leave_voter_group::*;
+mod leave_voter_group;
+
+pub use validate_registrar::*;
+mod validate_registrar;
+
+pub use create_ballot_tree::*;
+mod create_ballot_tree;
+
+pub use mint_ballot::*;
+mod mint_ballot;
+
+pub use create_campaign::*;
+mod create_campaign;
+
+pub use claim_ballot::*;
+mod claim_ballot;
+
+pub use cast_ballot_vote::*;
+mod cast_ballot_vote;
+
+pub use create_weight_snapshot::*;
+mod create_weight_snapshot;
+
+pub use cast_snapshot_vote::*;
+mod cast_snapshot_vote;
+
+pub use write_external_voter_weight::*;
+mod write_external_voter_weight;
+
+pub use rent_voting_power::*;
+mod rent_voting_power;
+
+pub use reclaim_voting_power::*;
+mod reclaim_voting_power;
+
+pub use map_collection_alias::*;
+mod map_collection_alias;
+
+pub use register_voter::*;
+mod register_voter;
+
+pub use close_orphaned_nft_vote_records::*;
+mod close_orphaned_nft_vote_records;
+
+pub use configure_wallet_weight_override::*;
+mod configure_wallet_weight_override;
+
+pub use mint_vote_receipt::*;
+mod mint_vote_receipt;
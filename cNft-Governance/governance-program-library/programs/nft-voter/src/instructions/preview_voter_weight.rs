@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::program::SplAccountCompression;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::accounts_cursor::AccountsCursor;
+
+/// Computes the voter weight of the given NFTs and compressed NFTs owned by governing_token_owner
+/// and sets it as VoterWeightReturnData return data without creating or updating any accounts
+/// It doesn't require VoterWeightRecord nor a signature and it's meant to be simulated by wallets
+/// to show the expected voting power before the voter creates any accounts
+///
+/// The remaining accounts are (nft, nft_metadata) pairs for nft_count NFTs followed by the accounts of
+/// the compressed NFTs: tree, proof_len proof nodes, the CollectionAlias when the asset collection is aliased
+/// and the tree TreeConfig when RegistrarExtension.config.tree_authority_allowlist is set
+///
+/// The weight is computed for a single Proposal vote using the collection weight mode and the collection cap
+/// Note: The voter allowlist, holding period, leaf hash attestations and asset uses are not checked
+/// and they can still reject the assets
+#[derive(Accounts)]
+pub struct PreviewVoterWeight<'info> {
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+pub fn preview_voter_weight<'info>(
+    ctx: Context<'_, '_, '_, 'info, PreviewVoterWeight<'info>>,
+    governing_token_owner: Pubkey,
+    nft_count: u8,
+    params: Vec<CompressedNftAsset>
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+    let compression_program = &ctx.accounts.compression_program.to_account_info();
+    let mut accounts_cursor = AccountsCursor::new(ctx.remaining_accounts);
+
+    // (collection, weight) of each asset
+    let mut asset_weights = vec![];
+
+    let mut unique_nft_mints = vec![];
+
+    for _ in 0..nft_count {
+        let nft_info = accounts_cursor.next("nft")?;
+        let nft_metadata_info = accounts_cursor.next("nft_metadata")?;
+
+        let (nft_weight, _nft_mint, collection) = resolve_nft_vote_weight_and_mint(
+            registrar,
+            registrar_extension,
+            &governing_token_owner,
+            nft_info,
+            nft_metadata_info,
+            &mut unique_nft_mints
+        )?;
+
+        asset_weights.push((collection, nft_weight));
+    }
+
+    let mut unique_asset_ids = vec![];
+    let checks_tree_authority = registrar_extension.config.is_tree_authority_allowlist_enabled();
+
+    for param in params.iter() {
+        let tree_account = accounts_cursor.next("tree")?;
+        let proofs = accounts_cursor.take(param.proof_len as usize, "proof node")?.to_vec();
+
+        let collection_alias = match
+            accounts_cursor.next_if(registrar.uses_collection_alias(registrar_extension, param), "collection_alias")?
+        {
+            Some(collection_alias_info) =>
+                Some(
+                    get_collection_alias_data_for_collection(
+                        collection_alias_info,
+                        &registrar.key(),
+                        &param.collection.as_ref().unwrap().key
+                    )?
+                ),
+            None => None,
+        };
+
+        if let Some(tree_config_info) = accounts_cursor.next_if(checks_tree_authority, "tree_config")? {
+            assert_tree_authority_allowed(registrar_extension, tree_account, tree_config_info)?;
+        }
+
+        let (cnft_weight, _asset_id, collection) = resolve_cnft_vote_weight(
+            registrar,
+            registrar_extension,
+            &governing_token_owner,
+            tree_account,
+            &mut unique_asset_ids,
+            param,
+            proofs,
+            compression_program,
+            None,
+            collection_alias.as_ref()
+        )?;
+
+        asset_weights.push((collection, cnft_weight));
+    }
+
+    accounts_cursor.finish()?;
+
+    let max_collection_weight = registrar_extension.config.get_max_collection_weight_per_proposal(
+        registrar.get_max_voter_weight(registrar_extension)?
+    );
+
+    let mut collection_weight_tally = CollectionWeightTally::default();
+
+    let mut voter_weight: u64 = 0;
+
+    // The weight counted for each collection
+    let mut collection_weights: Vec<(Pubkey, u64)> = vec![];
+
+    for (collection, asset_weight) in asset_weights.iter() {
+        let asset_weight = collection_weight_tally.add_asset(registrar_extension, collection, *asset_weight);
+
+        let collection_idx = match collection_weights.iter().position(|(c, _)| c == collection) {
+            Some(collection_idx) => collection_idx,
+            None => {
+                collection_weights.push((*collection, 0));
+                collection_weights.len() - 1
+            }
+        };
+
+        let collection_weight = &mut collection_weights[collection_idx].1;
+
+        // Once the collection cap is reached the assets of the collection don't contribute any weight
+        let asset_weight = match max_collection_weight {
+            Some(max_collection_weight) =>
+                asset_weight.min(max_collection_weight.saturating_sub(*collection_weight)),
+            None => asset_weight,
+        };
+
+        *collection_weight = collection_weight
+            .checked_add(asset_weight)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+        voter_weight = voter_weight
+            .checked_add(asset_weight)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+    }
+
+    VoterWeightReturnData::new(voter_weight, asset_weights.len()).set()
+}
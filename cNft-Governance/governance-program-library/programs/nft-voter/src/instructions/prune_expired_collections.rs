@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::{ max_voter_weight_record::MaxVoterWeightRecord, Registrar, RegistrarExtension };
+
+/// Removes the expired collections from the Registrar
+/// The instruction is permissionless and updates MaxVoterWeightRecord
+/// so the max voting power used by spl-gov no longer includes the expired collections
+#[derive(Accounts)]
+pub struct PruneExpiredCollections<'info> {
+    /// Registrar from which the expired collections are removed
+    #[account(mut)]
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar and the expiry of its collections
+    #[account(mut, has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        constraint = max_voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+
+        constraint = max_voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+}
+
+pub fn prune_expired_collections(ctx: Context<PruneExpiredCollections>) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar;
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+    let unix_timestamp = Clock::get()?.unix_timestamp;
+
+    let collections_count = registrar.collection_configs.len();
+
+    registrar.collection_configs.retain(|cc| {
+        !registrar_extension.get_collection_extension(&cc.collection).is_expired(unix_timestamp)
+    });
+
+    require!(
+        registrar.collection_configs.len() < collections_count,
+        NftVoterError::NoExpiredCollections
+    );
+
+    // The settings of the removed collections are removed with them
+    registrar_extension.collection_extensions.retain(|ce| !ce.is_expired(unix_timestamp));
+
+    // Update MaxVoterWeightRecord based on max voting power of the remaining collections
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    sanity::check_max_voter_weight(registrar, registrar_extension)?;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    // The weight never expires and only changes when collections are configured or pruned
+    max_voter_weight_record.max_voter_weight_expiry = None;
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::accounts::dispose_account_with_treasury_share;
+use anchor_lang::prelude::*;
+use spl_governance::state::{ enums::ProposalState, governance, proposal };
+
+/// Disposes NftVoteRecords of a Proposal which is no longer in Voting state
+/// The instruction is permissionless and allows cranks to clean up the records left by voters
+/// The reclaimed rent is split between the crank and RegistrarExtension.config.rent_reclaim_treasury
+/// using RegistrarExtension.config.rent_reclaim_treasury_share_bps
+///
+/// Note: Voters can still use relinquish_nft_vote to reclaim the rent before the records are cleaned up by cranks
+/// The NftVoteRecords to dispose are supplied as remaining accounts and can belong to different voters
+#[derive(Accounts)]
+pub struct ReclaimNftVoteRecords<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    #[account(owner = registrar.governance_program_id)]
+    pub proposal: UncheckedAccount<'info>,
+
+    /// CHECK: The treasury configured for the Registrar
+    #[account(
+        mut,
+        address = registrar_extension.config.rent_reclaim_treasury @ NftVoterError::RentReclaimNotEnabled
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: The crank which receives the remainder of the reclaimed rent can be any account
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
+pub fn reclaim_nft_vote_records(ctx: Context<ReclaimNftVoteRecords>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+
+    require!(registrar_extension.config.is_rent_reclaim_enabled(), NftVoterError::RentReclaimNotEnabled);
+
+    // Ensure the Governance belongs to Registrar.realm and is owned by Registrar.governance_program_id
+    let _governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm,
+    )?;
+
+    // Ensure the Proposal belongs to Governance from Registrar.realm and Registrar.governing_token_mint and is owned by Registrar.governance_program_id
+    let proposal = proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint,
+    )?;
+
+    // NftVoteRecords prevent the NFTs from voting twice and can't be disposed while the Proposal is in Voting state
+    require!(
+        proposal.state != ProposalState::Voting,
+        NftVoterError::ProposalVotingNotFinished
+    );
+
+    for nft_vote_record_info in ctx.remaining_accounts.iter() {
+        // Ensure NftVoteRecord is for the given Proposal
+        let nft_vote_record = get_nft_vote_record_data(nft_vote_record_info)?;
+
+        require!(
+            nft_vote_record.proposal == ctx.accounts.proposal.key(),
+            NftVoterError::InvalidProposalForNftVoteRecord
+        );
+
+        dispose_account_with_treasury_share(
+            nft_vote_record_info,
+            &ctx.accounts.beneficiary,
+            &ctx.accounts.treasury,
+            registrar_extension.config.rent_reclaim_treasury_share_bps
+        )?;
+    }
+
+    Ok(())
+}
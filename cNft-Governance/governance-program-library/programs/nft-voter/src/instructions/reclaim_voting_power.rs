@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Closes the WeightLease and returns its rent to the owner once the lease expires
+/// The renter can give the voting power back at any time and the lease then expires
+/// once the tickets the renter already created expire, so the weight isn't counted for both wallets
+/// The owner or the renter closes the lease with another reclaim_voting_power after that
+#[derive(Accounts)]
+pub struct ReclaimVotingPower<'info> {
+    #[account(
+        mut,
+        constraint = weight_lease.owner == authority.key() ||
+            weight_lease.renter == authority.key()
+        @ NftVoterError::InvalidWeightLease,
+    )]
+    pub weight_lease: Account<'info, WeightLease>,
+
+    /// RegistrarExtension of the Registrar of the lease
+    #[account(
+        constraint = registrar_extension.registrar == weight_lease.registrar
+        @ NftVoterError::InvalidRegistrarExtension
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    /// CHECK: The owner of the lease which receives the rent
+    #[account(mut, address = weight_lease.owner @ NftVoterError::InvalidWeightLease)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The owner or the renter of the lease
+    pub authority: Signer<'info>,
+}
+
+pub fn reclaim_voting_power(ctx: Context<ReclaimVotingPower>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let weight_lease = &mut ctx.accounts.weight_lease;
+
+    if weight_lease.is_active(slot) {
+        require!(
+            weight_lease.renter == ctx.accounts.authority.key(),
+            NftVoterError::WeightLeaseNotExpired
+        );
+
+        weight_lease.end_early(&ctx.accounts.registrar_extension.config, slot);
+
+        return Ok(());
+    }
+
+    weight_lease.close(ctx.accounts.owner.to_account_info())
+}
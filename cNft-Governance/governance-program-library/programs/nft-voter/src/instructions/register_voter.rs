@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::max_voter_weight_record::MaxVoterWeightRecord;
+use crate::state::*;
+
+/// Registers the voter with the Registrar and records the slot of the registration
+/// Voters registered before RegistrarConfig.registration_cutoff_slot keep the floor weight
+/// RegistrarConfig.min_weight_if_registered_before_slot
+///
+/// The floor weight is granted without any holdings and hence the voters are registered by the realm authority
+/// (ex. executing a governance Proposal with the snapshot of the holders) and only before the cutoff slot
+/// The floor weight of the registered voters is added to MaxVoterWeightRecord
+///
+/// The registration can't be re-created and hence a wallet can only be registered once
+#[derive(Accounts)]
+pub struct RegisterVoter<'info> {
+    /// The Registrar the voter registers with
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(
+        mut,
+        has_one = registrar @ NftVoterError::InvalidRegistrarExtension
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [ b"voter-registration".as_ref(),
+                registrar.key().as_ref(),
+                governing_token_owner.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VoterRegistration::get_space()
+    )]
+    pub voter_registration: Account<'info, VoterRegistration>,
+
+    /// CHECK: The voter registered by the realm authority, it doesn't have to sign
+    pub governing_token_owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = max_voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+
+        constraint = max_voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_voter(ctx: Context<RegisterVoter>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    let registrar_extension = &mut ctx.accounts.registrar_extension;
+    let current_slot = Clock::get()?.slot;
+
+    // The voters registered at or after the cutoff slot don't have the floor weight
+    require!(
+        registrar_extension.config.is_min_voter_weight_enabled() &&
+            current_slot < registrar_extension.config.registration_cutoff_slot,
+        NftVoterError::VoterRegistrationClosed
+    );
+
+    let voter_registration = &mut ctx.accounts.voter_registration;
+
+    voter_registration.registrar = registrar.key();
+    voter_registration.governing_token_owner = ctx.accounts.governing_token_owner.key();
+    voter_registration.registered_at_slot = current_slot;
+
+    registrar_extension.registered_voters = registrar_extension.registered_voters
+        .checked_add(1)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+    let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+
+    max_voter_weight_record.max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    // The weight never expires and only changes when collections are configured or voters registered
+    max_voter_weight_record.max_voter_weight_expiry = None;
+
+    Ok(())
+}
@@ -1,14 +1,21 @@
 use crate::error::NftVoterError;
 use crate::state::*;
 use crate::state::{get_nft_vote_record_data_for_proposal_and_token_owner, Registrar};
+use crate::tools::accounts_cursor::AccountsCursor;
 use crate::tools::governance::get_vote_record_address;
 use anchor_lang::prelude::*;
-use spl_governance::state::{enums::ProposalState, governance, proposal};
+use spl_governance::state::{enums::ProposalState, governance, proposal, vote_record};
 use spl_governance_tools::account::dispose_account;
 
 /// Disposes NftVoteRecord and recovers the rent from the accounts   
 /// It can only be executed when voting on the target Proposal ended or voter withdrew vote from the Proposal
 ///
+/// When voting on the Proposal ended and the spl-gov VoteRecord of the voter exists then the vote_kind
+/// recorded in the NftVoteRecords must match the kind of the spl-gov Vote
+///
+/// The NftVoteRecords are supplied as remaining accounts and each V3 NftVoteRecord must be followed by
+/// the CollectionVoteWeightRecord of its collection which releases the weight the NFT contributed to it
+///
 /// Note: If a voter votes with NFT and transfers the token then in the current version of the program the new owner can't withdraw the vote
 /// In order to support that scenario a change in spl-governance is needed
 /// It would have to support revoke_vote instruction which would take as input VoteWeightRecord with the following values:
@@ -92,18 +99,18 @@ pub fn relinquish_nft_vote(ctx: Context<RelinquishNftVote>) -> Result<()> {
     // When vote is withdrawn in spl-gov then VoteRecord is disposed and we have to assert it doesn't exist
     //
     // If the Proposal is in any other state then we can dispose NftVoteRecords without any additional Proposal checks
-    if proposal.state == ProposalState::Voting {
-        let vote_record_info = &ctx.accounts.vote_record.to_account_info();
+    let vote_record_info = &ctx.accounts.vote_record.to_account_info();
 
-        // Ensure the given VoteRecord address matches the expected PDA
-        let vote_record_key = get_vote_record_address(
-            &registrar.governance_program_id,
-            &registrar.realm,
-            &registrar.governing_token_mint,
-            &governing_token_owner,
-            &ctx.accounts.proposal.key(),
-        );
+    let vote_record_key = get_vote_record_address(
+        &registrar.governance_program_id,
+        &registrar.realm,
+        &registrar.governing_token_mint,
+        &governing_token_owner,
+        &ctx.accounts.proposal.key(),
+    );
 
+    if proposal.state == ProposalState::Voting {
+        // Ensure the given VoteRecord address matches the expected PDA
         require!(
             vote_record_key == vote_record_info.key(),
             NftVoterError::InvalidVoteRecordForNftVoteRecord
@@ -116,6 +123,20 @@ pub fn relinquish_nft_vote(ctx: Context<RelinquishNftVote>) -> Result<()> {
         );
     }
 
+    // The spl-gov Vote the NftVoteRecords were used for
+    // It's only known when the voting ended and the VoteRecord of the voter is supplied
+    let vote = if proposal.state != ProposalState::Voting
+        && vote_record_info.key() == vote_record_key
+        && !vote_record_info.data_is_empty()
+    {
+        Some(
+            vote_record::get_vote_record_data(&registrar.governance_program_id, vote_record_info)?
+                .vote,
+        )
+    } else {
+        None
+    };
+
     // Prevent relinquishing NftVoteRecords within the VoterWeightRecord expiration period
     // It's needed when multiple stacked voter-weight plugins are used
     // Without the assertion the following vector of attack exists
@@ -128,14 +149,44 @@ pub fn relinquish_nft_vote(ctx: Context<RelinquishNftVote>) -> Result<()> {
     }
 
     // Dispose all NftVoteRecords
-    for nft_vote_record_info in ctx.remaining_accounts.iter() {
+    let mut accounts_cursor = AccountsCursor::new(ctx.remaining_accounts);
+
+    while !accounts_cursor.is_empty() {
+        let nft_vote_record_info = accounts_cursor.next("nft_vote_record")?;
+
         // Ensure NftVoteRecord is for the given Proposal and TokenOwner
-        let _nft_vote_record = get_nft_vote_record_data_for_proposal_and_token_owner(
+        let nft_vote_record = get_nft_vote_record_data_for_proposal_and_token_owner(
             nft_vote_record_info,
             &ctx.accounts.proposal.key(),
             &governing_token_owner,
         )?;
 
+        if let Some(vote) = &vote {
+            nft_vote_record.assert_vote_kind(vote)?;
+        }
+
+        // Release the weight the NFT contributed to the capped weight of its collection
+        if let Some(collection_weight) =
+            get_nft_vote_record_collection_weight(nft_vote_record_info, &nft_vote_record)?
+        {
+            let collection_vote_weight_record_info =
+                accounts_cursor.next("collection_vote_weight_record")?;
+
+            let mut collection_vote_weight_record =
+                get_collection_vote_weight_record_data_for_registrar_proposal_and_collection(
+                    collection_vote_weight_record_info,
+                    &registrar.key(),
+                    &ctx.accounts.proposal.key(),
+                    &collection_weight.collection,
+                )?;
+
+            collection_vote_weight_record.release_voter_weight(collection_weight.voter_weight);
+
+            collection_vote_weight_record.serialize(
+                &mut &mut collection_vote_weight_record_info.try_borrow_mut_data()?[..],
+            )?;
+        }
+
         dispose_account(nft_vote_record_info, &ctx.accounts.beneficiary)?;
     }
 
@@ -144,6 +195,7 @@ pub fn relinquish_nft_vote(ctx: Context<RelinquishNftVote>) -> Result<()> {
     voter_weight_record.voter_weight_expiry = Some(0);
 
     voter_weight_record.weight_action_target = None;
+    voter_weight_record.weight_state = VoterWeightState::Consumed;
 
     Ok(())
 }
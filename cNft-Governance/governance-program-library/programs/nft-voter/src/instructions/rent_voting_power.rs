@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use spl_account_compression::program::SplAccountCompression;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::merkle_tree::assert_bubblegum_merkle_tree;
+use ::cnft_verification::get_asset_id_with_program;
+
+/// Rents the voter weight of the given compressed NFT of the owner to the renter until the given slot
+/// The renter creates create_cnft_action_ticket tickets for the asset with the WeightLease
+/// and the weight reverts to the owner once the lease expires
+///
+/// The owner must prove it holds the asset using the merkle proof supplied as proof_len accounts after the tree
+/// or as CompressedNftAsset.proof_nodes instruction data
+/// The owner can't create tickets for the asset while the lease is active and the renter can use the asset
+/// only once the tickets the owner created before the lease expire, so the weight is never counted for both wallets
+/// The lease can be renewed or rented to another wallet only once the previous lease expires
+///
+/// Note: It requires RegistrarExtension.config.allow_weight_leases
+#[derive(Accounts)]
+#[instruction(asset_id: Pubkey)]
+pub struct RentVotingPower<'info> {
+    /// The Registrar the weight is rented for
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(
+        has_one = registrar @ NftVoterError::InvalidRegistrarExtension
+    )]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        init_if_needed,
+        seeds = [ b"weight-lease".as_ref(),
+                registrar.key().as_ref(),
+                asset_id.as_ref(),
+                owner.key().as_ref()],
+        bump,
+        payer = payer,
+        space = WeightLease::get_space()
+    )]
+    pub weight_lease: Account<'info, WeightLease>,
+
+    /// The wallet which holds the compressed NFT
+    pub owner: Signer<'info>,
+
+    /// CHECK: The merkle tree of the compressed NFT verified by the proof
+    pub tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rent_voting_power<'info>(
+    ctx: Context<'_, '_, '_, 'info, RentVotingPower<'info>>,
+    asset_id: Pubkey,
+    params: CompressedNftAsset,
+    renter: Pubkey,
+    expires_at_slot: u64
+) -> Result<()> {
+    let registrar_extension = &ctx.accounts.registrar_extension;
+
+    require!(registrar_extension.config.allow_weight_leases, NftVoterError::WeightLeasesNotAllowed);
+
+    let slot = Clock::get()?.slot;
+
+    require!(expires_at_slot > slot, NftVoterError::InvalidWeightLeaseExpiry);
+    require!(renter != ctx.accounts.owner.key(), NftVoterError::InvalidWeightLease);
+
+    let tree_account = &ctx.accounts.tree.to_account_info();
+    let bubblegum_program_id = registrar_extension.config.get_bubblegum_program_id();
+
+    require_keys_eq!(
+        get_asset_id_with_program(tree_account.key, params.nonce, &bubblegum_program_id),
+        asset_id,
+        NftVoterError::InvalidWeightLease
+    );
+    require_keys_eq!(params.leaf_owner, ctx.accounts.owner.key(), NftVoterError::VoterDoesNotOwnNft);
+
+    // Only the ownership of the asset is verified and the leaf must be computed on-chain from the asset data
+    require!(
+        params.leaf_hash.is_none() && !params.use_proof_attestation,
+        NftVoterError::InvalidProofData
+    );
+
+    assert_leaf_schema_valid(tree_account, &params)?;
+    assert_bubblegum_merkle_tree(tree_account, &bubblegum_program_id)?;
+    assert_tree_root_fresh(tree_account, &params.root, registrar_extension.config.max_tree_root_age)?;
+
+    verify_compressed_nft(
+        tree_account,
+        &asset_id,
+        &params,
+        ctx.remaining_accounts.to_vec(),
+        &ctx.accounts.compression_program.to_account_info()
+    )?;
+
+    let weight_lease = &mut ctx.accounts.weight_lease;
+
+    // The renter keeps the weight until the lease expires
+    require!(!weight_lease.is_active(slot), NftVoterError::WeightLeaseNotExpired);
+
+    weight_lease.registrar = ctx.accounts.registrar.key();
+    weight_lease.asset_id = asset_id;
+    weight_lease.owner = ctx.accounts.owner.key();
+    weight_lease.renter = renter;
+
+    // The tickets the owner created before the lease can still be used until they expire
+    weight_lease.starts_at_slot = registrar_extension.config.get_nft_action_ticket_expiry(slot).saturating_add(1);
+    weight_lease.expires_at_slot = expires_at_slot;
+
+    require!(
+        weight_lease.expires_at_slot > weight_lease.starts_at_slot,
+        NftVoterError::InvalidWeightLeaseExpiry
+    );
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Revokes ColdWalletLink before its expiry
+/// The link can be revoked by either the cold or the hot wallet
+/// Note: The account is kept to reject the replays of the previous cold wallet signatures
+#[derive(Accounts)]
+pub struct RevokeColdWalletLink<'info> {
+    #[account(
+        mut,
+        constraint = cold_wallet_link.cold_wallet == authority.key() ||
+            cold_wallet_link.hot_wallet == authority.key()
+        @ NftVoterError::InvalidColdWalletLink,
+    )]
+    pub cold_wallet_link: Account<'info, ColdWalletLink>,
+
+    /// The cold or the hot wallet of the link
+    pub authority: Signer<'info>,
+}
+
+pub fn revoke_cold_wallet_link(ctx: Context<RevokeColdWalletLink>) -> Result<()> {
+    ctx.accounts.cold_wallet_link.revoked = true;
+
+    Ok(())
+}
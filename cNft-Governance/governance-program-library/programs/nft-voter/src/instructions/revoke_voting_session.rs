@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Revokes VotingSession before its expiry and returns the rent to the beneficiary
+/// The session can be revoked by either the voter or the session authority
+#[derive(Accounts)]
+pub struct RevokeVotingSession<'info> {
+    #[account(
+        mut,
+        close = beneficiary,
+        constraint = voting_session.governing_token_owner == authority.key() ||
+            voting_session.session_authority == authority.key()
+        @ NftVoterError::InvalidVotingSession,
+    )]
+    pub voting_session: Account<'info, VotingSession>,
+
+    /// The voter or the session authority of the session
+    pub authority: Signer<'info>,
+
+    /// CHECK: Any account chosen by the authority to receive the rent
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
+pub fn revoke_voting_session(_ctx: Context<RevokeVotingSession>) -> Result<()> {
+    Ok(())
+}
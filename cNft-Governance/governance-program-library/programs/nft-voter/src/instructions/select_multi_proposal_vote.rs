@@ -0,0 +1,144 @@
+use crate::error::NftVoterError;
+use crate::sanity;
+use crate::state::*;
+use crate::tools::governance::assert_proposal_action_deadline;
+use anchor_lang::prelude::*;
+use anchor_lang::Accounts;
+use spl_governance::state::{ governance, proposal as spl_proposal };
+
+/// Selects the weight of MultiProposalVote for one of its Proposals
+/// The weight counted by CastNftVoteMulti is set on VoterWeightRecord which is valid for the current Slot
+/// and the selected Proposal only and hence the instruction has to be executed inside the same transaction as spl-gov.CastVote
+///
+/// Once the weight was selected for all the Proposals the MultiProposalVote is closed and the rent is returned to the beneficiary
+///
+/// The optional VoterRegistration and WalletWeightOverride PDAs of the voter are supplied as remaining accounts
+/// the same way as for finalize_nft_vote and the selected weight is adjusted by the floor weight and the wallet override
+#[derive(Accounts)]
+#[instruction(proposal: Pubkey)]
+pub struct SelectMultiProposalVote<'info> {
+    /// The NFT voting registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// TokenOwnerRecord of the voter who casts the vote
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    #[account(owner = registrar.governance_program_id)]
+    voter_token_owner_record: UncheckedAccount<'info>,
+
+    /// Authority of the voter who casts the vote
+    /// It can be either governing_token_owner or its delegate and must sign this instruction
+    pub voter_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ b"multi-proposal-vote".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+    )]
+    pub multi_proposal_vote: Account<'info, MultiProposalVote>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance account the Proposal is for
+    #[account(owner = registrar.governance_program_id)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// The Proposal the vote is cast on which must match the proposal argument
+    #[account(
+        owner = registrar.governance_program_id,
+        address = proposal @ NftVoterError::InvalidProposal
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+
+    /// CHECK: Any account chosen by the voter to receive the rent once MultiProposalVote is closed
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
+pub fn select_multi_proposal_vote(
+    ctx: Context<SelectMultiProposalVote>,
+    proposal: Pubkey
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+
+    resolve_governing_token_owner(
+        registrar,
+        &ctx.accounts.voter_token_owner_record,
+        &ctx.accounts.voter_authority,
+        voter_weight_record
+    )?;
+
+    // Ensure the Governance belongs to Registrar.realm
+    let governance = governance::get_governance_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.governance,
+        &registrar.realm
+    )?;
+
+    let target_proposal = spl_proposal::get_proposal_data_for_governance_and_governing_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.target_proposal,
+        &ctx.accounts.governance.key(),
+        &registrar.governing_token_mint
+    )?;
+
+    assert_proposal_action_deadline(
+        &target_proposal,
+        &governance.config,
+        &VoterWeightAction::CastVote
+    )?;
+
+    let (voter_weight_adjustment, _) = split_voter_weight_adjustment_accounts(
+        registrar,
+        registrar_extension,
+        &voter_weight_record.governing_token_owner,
+        ctx.remaining_accounts
+    )?;
+
+    let multi_proposal_vote = &mut ctx.accounts.multi_proposal_vote;
+    multi_proposal_vote.take_proposal(&proposal)?;
+
+    // The record is only valid as of the current slot and for casting vote on the selected Proposal
+    finalize_voter_weight(
+        voter_weight_record,
+        multi_proposal_vote.voter_weight,
+        Clock::get()?.slot,
+        &voter_weight_adjustment
+    );
+    voter_weight_record.weight_action = Some(VoterWeightAction::CastVote);
+    voter_weight_record.weight_action_target = Some(proposal);
+
+    sanity::check_voter_weight(registrar, registrar_extension, voter_weight_record.voter_weight)?;
+
+    if multi_proposal_vote.proposals.is_empty() {
+        multi_proposal_vote.close(ctx.accounts.beneficiary.to_account_info())?;
+    }
+
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, 0).set()
+}
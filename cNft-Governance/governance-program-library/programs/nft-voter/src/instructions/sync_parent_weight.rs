@@ -0,0 +1,193 @@
+use crate::error::NftVoterError;
+use crate::state::max_voter_weight_record::MaxVoterWeightRecord;
+use crate::state::*;
+use crate::tools::governance::split_weight_action_target_account;
+use anchor_lang::prelude::*;
+
+/// Rolls up the voter weight earned in a sub-DAO into the parent Registrar
+/// The sub-DAO VoterWeightRecord must be updated for the given action in the current slot
+/// and the parent VoterWeightRecord gets the weight scaled by SubRegistrarConfig.weight_scale_bps
+/// It also syncs the scaled max voter weight of the sub-DAO into the parent MaxVoterWeightRecord
+///
+/// Similar to update_voter_weight_record the parent VoterWeightRecord is valid for the current Slot
+/// and the given target action only and hance the instruction has to be executed inside the same transaction
+/// as the corresponding spl-gov instruction of the parent Realm
+///
+/// The optional VoterRegistration and WalletWeightOverride PDAs of the voter in the parent Registrar are supplied
+/// as the first remaining accounts the same way as for update_voter_weight_record
+/// and the scaled weight is adjusted by the floor weight and the wallet override of the parent Registrar
+///
+/// When the parent RegistrarExtension.config.require_weight_action_target is set the target (Governance or Proposal
+/// of the parent Realm) must be supplied after them and it must match weight_action_target
+///
+/// Note: CastVote weight can't be rolled up because the NFTs used for voting are tracked per Proposal
+/// by the Registrar of the Realm the Proposal belongs to
+#[derive(Accounts)]
+pub struct SyncParentWeight<'info> {
+    /// The sub-DAO Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension of the sub-DAO Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    /// The parent Registrar the sub-DAO Registrar points to
+    #[account(
+        constraint = registrar_extension.config.parent_registrar == parent_registrar.key()
+        @ NftVoterError::InvalidParentRegistrar
+    )]
+    pub parent_registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension of the parent Registrar the sub-DAO max voter weight is rolled up into
+    #[account(
+        mut,
+        constraint = parent_registrar_extension.registrar == parent_registrar.key()
+        @ NftVoterError::InvalidRegistrarExtension
+    )]
+    pub parent_registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        seeds = [ b"sub-registrar-config".as_ref(),
+                parent_registrar.key().as_ref(),
+                registrar.key().as_ref()],
+        bump
+    )]
+    pub sub_registrar_config: Account<'info, SubRegistrarConfig>,
+
+    /// The sub-DAO VoterWeightRecord the weight is earned in
+    #[account(
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// The parent VoterWeightRecord of the same voter
+    #[account(
+        mut,
+        constraint = parent_voter_weight_record.realm == parent_registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = parent_voter_weight_record.governing_token_mint == parent_registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+        constraint = parent_voter_weight_record.governing_token_owner == voter_weight_record.governing_token_owner
+        @ NftVoterError::InvalidTokenOwnerForVoterWeightRecord,
+    )]
+    pub parent_voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// The sub-DAO MaxVoterWeightRecord
+    #[account(
+        constraint = max_voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+        constraint = max_voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    /// The parent MaxVoterWeightRecord
+    #[account(
+        mut,
+        constraint = parent_max_voter_weight_record.realm == parent_registrar.realm
+        @ NftVoterError::InvalidMaxVoterWeightRecordRealm,
+        constraint = parent_max_voter_weight_record.governing_token_mint == parent_registrar.governing_token_mint
+        @ NftVoterError::InvalidMaxVoterWeightRecordMint,
+    )]
+    pub parent_max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter in the parent Registrar which must not exist for the weight to be rolled up
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                parent_registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = parent_voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub parent_voter_freeze_record: UncheckedAccount<'info>,
+}
+
+pub fn sync_parent_weight(
+    ctx: Context<SyncParentWeight>,
+    voter_weight_action: VoterWeightAction,
+    weight_action_target: Option<Pubkey>
+) -> Result<()> {
+    // voter_weight for CastVote action can't be rolled up
+    require!(
+        voter_weight_action != VoterWeightAction::CastVote,
+        NftVoterError::CastVoteIsNotAllowed
+    );
+
+    let voter_weight_record = &ctx.accounts.voter_weight_record;
+    let current_slot = Clock::get()?.slot;
+
+    // The sub-DAO weight must be evaluated for the same action in the current slot
+    require!(
+        voter_weight_record.voter_weight_expiry == Some(current_slot),
+        NftVoterError::VoterWeightRecordExpired
+    );
+    require!(
+        voter_weight_record.weight_action == Some(voter_weight_action),
+        NftVoterError::InvalidVoterWeightAction
+    );
+
+    let (voter_weight_adjustment, remaining_accounts) = split_voter_weight_adjustment_accounts(
+        &ctx.accounts.parent_registrar,
+        &ctx.accounts.parent_registrar_extension,
+        &voter_weight_record.governing_token_owner,
+        ctx.remaining_accounts
+    )?;
+
+    // The target must be a validated account of the parent Realm when it's required by the parent Registrar
+    let (parent_weight_action_target, _) = split_weight_action_target_account(
+        &ctx.accounts.parent_registrar,
+        &ctx.accounts.parent_registrar_extension,
+        &voter_weight_action,
+        remaining_accounts
+    )?;
+
+    if parent_weight_action_target.is_some() {
+        require!(
+            weight_action_target == parent_weight_action_target,
+            NftVoterError::InvalidVoterWeightActionTarget
+        );
+    }
+
+    let sub_registrar_config = &mut ctx.accounts.sub_registrar_config;
+    let parent_registrar = &ctx.accounts.parent_registrar;
+    let parent_registrar_extension = &mut ctx.accounts.parent_registrar_extension;
+
+    // Replace the previously synced max voter weight of the sub-DAO with the current one
+    let max_voter_weight = sub_registrar_config.apply_weight_scale(
+        ctx.accounts.max_voter_weight_record.max_voter_weight
+    );
+
+    parent_registrar_extension.sub_registrars_max_voter_weight = parent_registrar_extension.sub_registrars_max_voter_weight
+        .checked_sub(sub_registrar_config.max_voter_weight)
+        .ok_or(NftVoterError::ArithmeticOverflow)?
+        .checked_add(max_voter_weight)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+    sub_registrar_config.max_voter_weight = max_voter_weight;
+
+    let parent_max_voter_weight_record = &mut ctx.accounts.parent_max_voter_weight_record;
+
+    parent_max_voter_weight_record.max_voter_weight = parent_registrar.get_max_voter_weight(
+        parent_registrar_extension
+    )?;
+    parent_max_voter_weight_record.max_voter_weight_expiry = None;
+
+    let parent_voter_weight_record = &mut ctx.accounts.parent_voter_weight_record;
+
+    // Record is only valid as of the current slot
+    finalize_voter_weight(
+        parent_voter_weight_record,
+        sub_registrar_config.apply_weight_scale(voter_weight_record.voter_weight),
+        current_slot,
+        &voter_weight_adjustment
+    );
+
+    // Set the action and target to make it specific and prevent being used for other targets
+    parent_voter_weight_record.weight_action = Some(voter_weight_action);
+    parent_voter_weight_record.weight_action_target = weight_action_target;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, VoterFreezeRecord };
+
+/// Unfreezes the voter frozen by freeze_voter and disposes VoterFreezeRecord
+#[derive(Accounts)]
+pub struct UnfreezeVoter<'info> {
+    /// Registrar for which we unfreeze the voter
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// Authority of the Realm must sign and match Realm.authority
+    pub realm_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+        constraint = voter_freeze_record.registrar == registrar.key()
+        @ NftVoterError::InvalidVoterFreezeRecord,
+    )]
+    pub voter_freeze_record: Account<'info, VoterFreezeRecord>,
+
+    /// CHECK: The beneficiary who receives lamports from the disposed VoterFreezeRecord can be any account
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
+pub fn unfreeze_voter(ctx: Context<UnfreezeVoter>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint,
+    )?;
+
+    require!(
+        realm.authority.unwrap() == ctx.accounts.realm_authority.key(),
+        NftVoterError::InvalidRealmAuthority
+    );
+
+    Ok(())
+}
@@ -1,7 +1,13 @@
 use crate::error::NftVoterError;
+use crate::sanity;
 use crate::state::*;
-use crate::tools::accounts::close_nft_action_ticket_account;
+use crate::tools::governance::{
+    assert_voter_weight_record_consumed_in_transaction,
+    assert_voter_weight_record_update,
+    split_weight_action_target_account,
+};
 use anchor_lang::prelude::*;
+use solana_program::sysvar;
 
 /// Updates VoterWeightRecord to evaluate governance power for non voting use cases: CreateProposal, CreateGovernance etc...
 /// This instruction updates VoterWeightRecord which is valid for the current Slot and the given target action only
@@ -11,12 +17,38 @@ use anchor_lang::prelude::*;
 /// can only be used with max 10 NFTs due to Solana transaction size limit
 /// It could be supported in future version by introducing bookkeeping accounts to track the NFTs
 /// which were already used to calculate the total weight
+///
+/// When RegistrarExtension.config.require_same_transaction_consumption is set the VoterWeightRecord must be taken
+/// by an spl-gov instruction following this instruction in the same transaction
+///
+/// The resulting voter weight and the number of NFTs counted are set as VoterWeightReturnData return data
+///
+/// When use_voter_group is set the VoterGroup of the voter followed by the VoterFreezeRecords of the group members
+/// must be supplied after the optional VoterWeightDetail and the tickets of the group members are counted
+/// together with the voter's own tickets
+///
+/// When RegistrarExtension.config.require_weight_action_target is set the target of the action (Governance or Proposal)
+/// must be supplied after the optional VoterWeightDetail and it's set as weight_action_target
+/// The target of CreateGovernance is the Realm and it's not supplied
+///
+/// When RegistrarExtension.config.min_weight_if_registered_before_slot is set the VoterRegistration PDA of the voter
+/// must be supplied as the first remaining account and the voter weight is never lower than the floor weight of the voter
+///
+/// When RegistrarExtension.config.allow_wallet_weight_overrides is set the WalletWeightOverride PDA of the voter
+/// must be supplied after the optional VoterRegistration and the voter weight is scaled by the override
+///
+/// expected_record_nonce must match VoterWeightRecord.record_nonce which is incremented by the update
+/// It prevents two clients updating the record concurrently for different targets from overwriting each other
 #[derive(Accounts)]
 #[instruction(voter_weight_action:VoterWeightAction)]
 pub struct UpdateVoterWeightRecord<'info> {
     /// The NFT voting Registrar
     pub registrar: Account<'info, Registrar>,
 
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
     #[account(
         mut,
         constraint = voter_weight_record.realm == registrar.realm
@@ -28,70 +60,116 @@ pub struct UpdateVoterWeightRecord<'info> {
     pub voter_weight_record: Account<'info, VoterWeightRecord>,
     #[account(mut)]
     pub payer: Signer<'info>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar used to ensure the VoterWeightRecord is consumed in the same transaction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
 }
 
 pub fn update_voter_weight_record(
     ctx: Context<UpdateVoterWeightRecord>,
-    voter_weight_action: VoterWeightAction
+    voter_weight_action: VoterWeightAction,
+    use_voter_group: bool,
+    expected_record_nonce: u32
 ) -> Result<()> {
     let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
     let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+
+    assert_voter_weight_record_update(
+        voter_weight_record,
+        registrar_extension,
+        &voter_weight_action,
+        expected_record_nonce
+    )?;
+
     let governing_token_owner = &voter_weight_record.governing_token_owner;
     let payer = &mut ctx.accounts.payer.to_account_info();
 
-    match voter_weight_action {
-        // voter_weight for CastVote action can't be evaluated using this instruction
-        VoterWeightAction::CastVote => {
-            return err!(NftVoterError::CastVoteIsNotAllowed);
-        }
-        | VoterWeightAction::CommentProposal
-        | VoterWeightAction::CreateGovernance
-        | VoterWeightAction::CreateProposal
-        | VoterWeightAction::SignOffProposal => {}
-    }
-
-    let mut voter_weight = 0u64;
-    let mut unique_nft_action_tickets = vec![];
-
-    for nft_action_ticket in ctx.remaining_accounts.iter() {
-        if unique_nft_action_tickets.contains(&nft_action_ticket.key) {
-            return Err(NftVoterError::DuplicatedNftDetected.into());
-        }
-
-        require!(nft_action_ticket.data_is_empty() == false, NftVoterError::NftFailedVerification);
-        require!(*nft_action_ticket.owner == crate::id(), NftVoterError::InvalidAccountOwner);
-
-        let data_bytes = nft_action_ticket.data.clone();
-        let data = NftActionTicket::try_from_slice(&data_bytes.borrow())?;
-
-        let ticket_type = format!("nft-{}-ticket", &voter_weight_action).to_string();
-        let nft_action_ticket_address = get_nft_action_ticket_address(
-            &ticket_type,
-            &registrar.key(),
-            governing_token_owner,
-            &data.nft_mint
-        ).0;
-
-        require!(
-            data.governing_token_owner == *governing_token_owner &&
-                nft_action_ticket_address == *nft_action_ticket.key,
-            NftVoterError::InvalidNftTicket
-        );
-        require!(data.expiry.unwrap() >= Clock::get()?.slot, NftVoterError::NftTicketExpired);
-
-        close_nft_action_ticket_account(nft_action_ticket, payer)?;
-        unique_nft_action_tickets.push(&nft_action_ticket.key);
-        voter_weight = voter_weight.checked_add(data.weight).unwrap();
+    let (voter_weight_adjustment, remaining_accounts) = split_voter_weight_adjustment_accounts(
+        registrar,
+        registrar_extension,
+        governing_token_owner,
+        ctx.remaining_accounts
+    )?;
+
+    let (voter_weight_detail_info, nft_action_tickets) = split_voter_weight_detail_account(
+        registrar_extension,
+        remaining_accounts
+    )?;
+
+    let (weight_action_target, nft_action_tickets) = split_weight_action_target_account(
+        registrar,
+        registrar_extension,
+        &voter_weight_action,
+        nft_action_tickets
+    )?;
+
+    let (governing_token_owners, nft_action_tickets) = split_voter_group_accounts(
+        use_voter_group,
+        &registrar.key(),
+        governing_token_owner,
+        nft_action_tickets
+    )?;
+
+    let (voter_weight, contributions) = consume_nft_action_tickets(
+        registrar,
+        registrar_extension,
+        &governing_token_owners,
+        &voter_weight_action,
+        nft_action_tickets,
+        payer
+    )?;
+
+    if let Some(voter_weight_detail_info) = voter_weight_detail_info {
+        record_voter_weight_detail(
+            voter_weight_detail_info,
+            &voter_weight_record.key(),
+            &contributions,
+            false
+        )?;
     }
 
-    voter_weight_record.voter_weight = voter_weight;
+    let previous_voter_weight_expiry = voter_weight_record.voter_weight_expiry;
 
     // Record is only valid as of the current slot
-    voter_weight_record.voter_weight_expiry = Some(Clock::get()?.slot);
+    finalize_voter_weight(
+        voter_weight_record,
+        voter_weight,
+        Clock::get()?.slot,
+        &voter_weight_adjustment
+    );
 
     // Set the action to make it specific and prevent being used for voting
     voter_weight_record.weight_action = Some(voter_weight_action);
-    voter_weight_record.weight_action_target = None;
+    voter_weight_record.weight_action_target = weight_action_target;
+
+    // The nonce only needs to differ from the previous one and hence it wraps around
+    voter_weight_record.record_nonce = voter_weight_record.record_nonce.wrapping_add(1);
+
+    sanity::check_voter_weight_expiry(
+        previous_voter_weight_expiry,
+        voter_weight_record.voter_weight_expiry
+    )?;
+    sanity::check_voter_weight(registrar, registrar_extension, voter_weight_record.voter_weight)?;
+
+    if registrar_extension.config.require_same_transaction_consumption {
+        assert_voter_weight_record_consumed_in_transaction(
+            &ctx.accounts.instructions,
+            &registrar.governance_program_id,
+            &voter_weight_record.key()
+        )?;
+    }
 
-    Ok(())
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, contributions.len()).set()
 }
@@ -0,0 +1,206 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use crate::tools::governance::{
+    assert_proposal_action_deadline,
+    assert_voter_weight_record_consumed_in_transaction,
+    assert_voter_weight_record_update,
+};
+use anchor_lang::prelude::*;
+use solana_program::sysvar;
+use spl_governance::state::{ governance, proposal };
+
+/// Updates VoterWeightRecord for the given target using the weight multiplier configured for the target Governance
+/// The target is the Governance for CreateProposal action and the Proposal for CommentProposal and SignOffProposal actions
+/// in which case the multiplier is selected using the Proposal's Governance
+///
+/// Similar to update_voter_weight_record the VoterWeightRecord is valid for the current Slot and the given target action only
+/// and hance the instruction has to be executed inside the same transaction as the corresponding spl-gov instruction
+///
+/// When the target is a Proposal the weight is rejected once the deadline of the action has passed
+///
+/// When RegistrarExtension.config.require_same_transaction_consumption is set the VoterWeightRecord must be taken
+/// by an spl-gov instruction following this instruction in the same transaction
+///
+/// The resulting voter weight and the number of NFTs counted are set as VoterWeightReturnData return data
+///
+/// expected_record_nonce must match VoterWeightRecord.record_nonce which is incremented by the update
+/// the same way as for update_voter_weight_record
+///
+/// The optional VoterRegistration and WalletWeightOverride PDAs of the voter are supplied as the first remaining accounts
+/// the same way as for update_voter_weight_record and the weight with the multiplier is adjusted
+/// by the floor weight and the wallet override
+#[derive(Accounts)]
+#[instruction(voter_weight_action:VoterWeightAction)]
+pub struct UpdateVoterWeightRecordForTarget<'info> {
+    /// The NFT voting Registrar
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    #[account(
+        constraint = governance_weight_config.registrar == registrar.key()
+        @ NftVoterError::InvalidGovernanceWeightConfig,
+    )]
+    pub governance_weight_config: Account<'info, GovernanceWeightConfig>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance or Proposal the action targets
+    #[account(owner = registrar.governance_program_id)]
+    pub target: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: VoterFreezeRecord PDA of the voter which must not exist for the voter to be able to vote
+    #[account(
+        seeds = [ b"voter-freeze-record".as_ref(),
+                registrar.key().as_ref(),
+                voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+        constraint = voter_freeze_record.data_is_empty() @ NftVoterError::VoterFrozen
+    )]
+    pub voter_freeze_record: UncheckedAccount<'info>,
+
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    /// Governance the weight multiplier is configured for
+    /// It's used to resolve the deadline when the target is a Proposal
+    #[account(
+        owner = registrar.governance_program_id,
+        address = governance_weight_config.governance @ NftVoterError::InvalidGovernanceWeightConfig
+    )]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar used to ensure the VoterWeightRecord is consumed in the same transaction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+pub fn update_voter_weight_record_for_target(
+    ctx: Context<UpdateVoterWeightRecordForTarget>,
+    voter_weight_action: VoterWeightAction,
+    expected_record_nonce: u32
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+    let governance_weight_config = &ctx.accounts.governance_weight_config;
+    let target = &ctx.accounts.target;
+
+    assert_voter_weight_record_update(
+        &ctx.accounts.voter_weight_record,
+        registrar_extension,
+        &voter_weight_action,
+        expected_record_nonce
+    )?;
+
+    // Resolve the Governance of the target the weight multiplier is configured for
+    let target_governance = match voter_weight_action {
+        // CastVote is rejected by assert_voter_weight_record_update
+        // and CreateGovernance targets the Realm and there is no Governance to select the multiplier
+        VoterWeightAction::CastVote | VoterWeightAction::CreateGovernance => {
+            return err!(NftVoterError::InvalidVoterWeightActionTarget);
+        }
+        VoterWeightAction::CreateProposal => {
+            // Ensure the Governance belongs to Registrar.realm
+            let _governance = governance::get_governance_data_for_realm(
+                &registrar.governance_program_id,
+                target,
+                &registrar.realm
+            )?;
+
+            target.key()
+        }
+        VoterWeightAction::CommentProposal | VoterWeightAction::SignOffProposal => {
+            let proposal = proposal::get_proposal_data_for_governance_and_governing_mint(
+                &registrar.governance_program_id,
+                target,
+                &governance_weight_config.governance,
+                &registrar.governing_token_mint
+            )?;
+
+            let governance = governance::get_governance_data_for_realm(
+                &registrar.governance_program_id,
+                &ctx.accounts.governance,
+                &registrar.realm
+            )?;
+
+            assert_proposal_action_deadline(&proposal, &governance.config, &voter_weight_action)?;
+
+            proposal.governance
+        }
+    };
+
+    require!(
+        governance_weight_config.governance == target_governance,
+        NftVoterError::InvalidGovernanceWeightConfig
+    );
+
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+    let governing_token_owner = &voter_weight_record.governing_token_owner;
+
+    let (voter_weight_adjustment, remaining_accounts) = split_voter_weight_adjustment_accounts(
+        registrar,
+        registrar_extension,
+        governing_token_owner,
+        ctx.remaining_accounts
+    )?;
+
+    let (voter_weight_detail_info, nft_action_tickets) = split_voter_weight_detail_account(
+        registrar_extension,
+        remaining_accounts
+    )?;
+
+    let (voter_weight, contributions) = consume_nft_action_tickets(
+        registrar,
+        registrar_extension,
+        std::slice::from_ref(governing_token_owner),
+        &voter_weight_action,
+        nft_action_tickets,
+        &ctx.accounts.payer.to_account_info()
+    )?;
+
+    if let Some(voter_weight_detail_info) = voter_weight_detail_info {
+        record_voter_weight_detail(
+            voter_weight_detail_info,
+            &voter_weight_record.key(),
+            &contributions,
+            false
+        )?;
+    }
+
+    // Record is only valid as of the current slot
+    finalize_voter_weight(
+        voter_weight_record,
+        governance_weight_config.apply_weight_multiplier(voter_weight),
+        Clock::get()?.slot,
+        &voter_weight_adjustment
+    );
+
+    // Set the action and target to make it specific and prevent being used for other targets
+    voter_weight_record.weight_action = Some(voter_weight_action);
+    voter_weight_record.weight_action_target = Some(target.key());
+
+    // The nonce only needs to differ from the previous one and hence it wraps around
+    voter_weight_record.record_nonce = voter_weight_record.record_nonce.wrapping_add(1);
+
+    if registrar_extension.config.require_same_transaction_consumption {
+        assert_voter_weight_record_consumed_in_transaction(
+            &ctx.accounts.instructions,
+            &registrar.governance_program_id,
+            &voter_weight_record.key()
+        )?;
+    }
+
+    VoterWeightReturnData::new(voter_weight_record.voter_weight, contributions.len()).set()
+}
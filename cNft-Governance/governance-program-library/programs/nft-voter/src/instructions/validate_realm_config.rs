@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use spl_governance::state::{ realm, realm_config };
+
+use crate::error::NftVoterError;
+use crate::state::Registrar;
+
+/// Validates the spl-governance RealmConfig registers the plugin for the Registrar governing_token_mint
+/// It's a read-only instruction meant to be simulated before a Realm goes live because spl-gov silently
+/// uses the deposited tokens when the plugin isn't configured for the mint
+///
+/// voter_weight_addin and max_voter_weight_addin select the addin roles which must be assigned to the plugin
+/// and each of the roles can be validated independently
+#[derive(Accounts)]
+pub struct ValidateRealmConfig<'info> {
+    /// The Registrar the RealmConfig is validated for
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+       address = registrar.realm @ NftVoterError::InvalidRealmForRegistrar,
+       owner = registrar.governance_program_id
+    )]
+    /// CHECK: Owned by spl-governance instance specified in registrar.governance_program_id
+    pub realm: UncheckedAccount<'info>,
+
+    /// RealmConfig account of the Realm
+    /// CHECK: PDA validated by spl-governance get_realm_config_data_for_realm
+    pub realm_config: UncheckedAccount<'info>,
+}
+
+pub fn validate_realm_config(
+    ctx: Context<ValidateRealmConfig>,
+    voter_weight_addin: bool,
+    max_voter_weight_addin: bool
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+
+    let realm = realm::get_realm_data_for_governing_token_mint(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm,
+        &registrar.governing_token_mint
+    )?;
+
+    let realm_config = realm_config::get_realm_config_data_for_realm(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm_config,
+        &ctx.accounts.realm.key()
+    )?;
+
+    let token_config = realm_config.get_token_config(&realm, &registrar.governing_token_mint)?;
+
+    if voter_weight_addin {
+        require!(
+            token_config.voter_weight_addin == Some(crate::id()),
+            NftVoterError::PluginNotVoterWeightAddin
+        );
+    }
+
+    if max_voter_weight_addin {
+        require!(
+            token_config.max_voter_weight_addin == Some(crate::id()),
+            NftVoterError::PluginNotMaxVoterWeightAddin
+        );
+    }
+
+    Ok(())
+}
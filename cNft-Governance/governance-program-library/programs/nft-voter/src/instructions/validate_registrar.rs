@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::{ get_registrar_warnings, Registrar, RegistrarExtension, RegistrarWarnings };
+
+/// Checks the Registrar for misconfigurations and sets RegistrarWarnings as the return data
+/// It's a read-only instruction meant to be simulated before a Realm goes live, the same way as validate_realm_config
+/// The instruction doesn't fail when there are warnings and each warning is also logged
+#[derive(Accounts)]
+pub struct ValidateRegistrar<'info> {
+    /// The Registrar to validate
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+}
+
+pub fn validate_registrar(ctx: Context<ValidateRegistrar>) -> Result<()> {
+    let warnings = get_registrar_warnings(
+        &ctx.accounts.registrar,
+        &ctx.accounts.registrar_extension,
+        Clock::get()?.unix_timestamp
+    );
+
+    if warnings.contains(RegistrarWarnings::ZERO_WEIGHT_COLLECTION) {
+        msg!("Registrar has a collection with zero weight");
+    }
+
+    if warnings.contains(RegistrarWarnings::ZERO_SIZE_COLLECTION) {
+        msg!("Registrar has a collection with zero size and nonzero weight");
+    }
+
+    if warnings.contains(RegistrarWarnings::MAX_VOTER_WEIGHT_OVERFLOW) {
+        msg!("Registrar max voter weight overflows u64");
+    }
+
+    if warnings.contains(RegistrarWarnings::EXPIRED_COLLECTION) {
+        msg!("Registrar has an expired collection");
+    }
+
+    warnings.set()
+}
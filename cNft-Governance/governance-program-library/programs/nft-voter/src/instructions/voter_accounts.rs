@@ -0,0 +1,27 @@
+use crate::error::NftVoterError;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Registrar, RegistrarExtension, VoterWeightRecord and authority of the voter shared by the voter instructions
+/// The VoterWeightRecord is checked to belong to the realm and the governing token mint of the Registrar
+///
+/// New instructions acting on behalf of a voter should embed VoterAccounts instead of declaring the accounts
+/// so the realm and mint checks can't be omitted
+#[derive(Accounts)]
+pub struct VoterAccounts<'info> {
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        mut,
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    pub voter_authority: Signer<'info>,
+}
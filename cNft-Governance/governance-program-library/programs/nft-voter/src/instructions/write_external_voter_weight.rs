@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use solana_program::program::invoke_signed;
+
+use crate::error::NftVoterError;
+use crate::state::*;
+
+/// Exports the voter weight computed by the plugin into the VoterWeightRecord of another (consumer) plugin
+/// It lets plugins be chained, ex. a vote aggregator combining the weights of several plugins
+/// into the single VoterWeightRecord configured for the Realm
+///
+/// This plugin can't write accounts it doesn't own and hence the weight is passed to the consumer plugin
+/// by invoking its write_voter_weight instruction signed by the weight export authority PDA of the Registrar
+/// The consumer plugin consents to the export by accepting the signature of the authority
+///
+/// The VoterWeightRecord must be Finalized in the current slot, ex. by update_voter_weight_record
+/// executed in the same transaction, and the consumer plugin must be RegistrarExtension.config.weight_export_program
+#[derive(Accounts)]
+pub struct WriteExternalVoterWeight<'info> {
+    pub registrar: Account<'info, Registrar>,
+
+    /// RegistrarExtension with the voting configuration of the Registrar
+    #[account(has_one = registrar @ NftVoterError::InvalidRegistrarExtension)]
+    pub registrar_extension: Account<'info, RegistrarExtension>,
+
+    #[account(
+        constraint = voter_weight_record.realm == registrar.realm
+        @ NftVoterError::InvalidVoterWeightRecordRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint
+        @ NftVoterError::InvalidVoterWeightRecordMint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: VoterWeightRecord of the consumer plugin validated in the handler
+    #[account(
+        mut,
+        owner = registrar_extension.config.weight_export_program @ NftVoterError::InvalidExternalVoterWeightRecord
+    )]
+    pub external_voter_weight_record: UncheckedAccount<'info>,
+
+    /// CHECK: PDA signing the export to the consumer plugin
+    #[account(seeds = [b"weight-export-authority".as_ref(), registrar.key().as_ref()], bump)]
+    pub weight_export_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The consumer plugin program which must be RegistrarExtension.config.weight_export_program
+    #[account(
+        executable,
+        address = registrar_extension.config.weight_export_program @ NftVoterError::WeightExportNotAllowed
+    )]
+    pub weight_export_program: UncheckedAccount<'info>,
+}
+
+pub fn write_external_voter_weight(ctx: Context<WriteExternalVoterWeight>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let registrar_extension = &ctx.accounts.registrar_extension;
+
+    require!(
+        registrar_extension.config.weight_export_program != Pubkey::default(),
+        NftVoterError::WeightExportNotAllowed
+    );
+
+    let voter_weight_record = &ctx.accounts.voter_weight_record;
+
+    // The consumer record must be of the same voter and it must use the spl-governance-addin-api layout
+    let external_voter_weight_record = VoterWeightRecord::try_deserialize(
+        &mut &ctx.accounts.external_voter_weight_record.try_borrow_data()?[..]
+    ).map_err(|_| NftVoterError::InvalidExternalVoterWeightRecord)?;
+
+    require!(
+        external_voter_weight_record.realm == voter_weight_record.realm &&
+            external_voter_weight_record.governing_token_mint ==
+                voter_weight_record.governing_token_mint &&
+            external_voter_weight_record.governing_token_owner ==
+                voter_weight_record.governing_token_owner,
+        NftVoterError::InvalidExternalVoterWeightRecord
+    );
+
+    // Only the weight evaluated in the current slot can be exported
+    require!(
+        voter_weight_record.get_weight_state(Clock::get()?.slot) == VoterWeightState::Finalized,
+        NftVoterError::VoterWeightRecordExpired
+    );
+
+    let instruction = get_write_voter_weight_instruction(
+        &ctx.accounts.weight_export_program.key(),
+        &ctx.accounts.external_voter_weight_record.key(),
+        &voter_weight_record.key(),
+        &registrar.key(),
+        &(ExternalVoterWeight {
+            voter_weight: voter_weight_record.voter_weight,
+            voter_weight_expiry: voter_weight_record.voter_weight_expiry,
+            weight_action: voter_weight_record.weight_action,
+            weight_action_target: voter_weight_record.weight_action_target,
+        })
+    )?;
+
+    let registrar_key = registrar.key();
+    let bump = [ctx.bumps.weight_export_authority];
+    let mut signer_seeds = get_weight_export_authority_seeds(&registrar_key).to_vec();
+    signer_seeds.push(&bump);
+
+    invoke_signed(
+        &instruction,
+        &[
+            ctx.accounts.external_voter_weight_record.to_account_info(),
+            voter_weight_record.to_account_info(),
+            ctx.accounts.weight_export_authority.to_account_info(),
+            registrar.to_account_info(),
+        ],
+        &[&signer_seeds[..]]
+    )?;
+
+    Ok(())
+}
@@ -2,9 +2,13 @@ use anchor_lang::prelude::*;
 
 pub mod error;
 
+pub mod events;
+
 mod instructions;
 use instructions::*;
 
+pub mod sanity;
+
 pub mod state;
 
 pub mod tools;
@@ -22,6 +26,33 @@ pub mod nft_voter {
         log_version();
         instructions::create_registrar(ctx, max_collections)
     }
+    pub fn migrate_registrar(ctx: Context<MigrateRegistrar>, max_collections: u8) -> Result<()> {
+        log_version();
+        instructions::migrate_registrar(ctx, max_collections)
+    }
+    pub fn create_registrar_template(
+        ctx: Context<CreateRegistrarTemplate>,
+        template_id: u64,
+        max_collections: u8
+    ) -> Result<()> {
+        log_version();
+        instructions::create_registrar_template(ctx, template_id, max_collections)
+    }
+    pub fn configure_registrar_template<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ConfigureRegistrarTemplate<'info>>,
+        config: RegistrarConfig,
+        collections: Vec<CollectionConfigArgs>
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_registrar_template(ctx, config, collections)
+    }
+    pub fn create_registrar_from_template(
+        ctx: Context<CreateRegistrarFromTemplate>,
+        max_collections: u8
+    ) -> Result<()> {
+        log_version();
+        instructions::create_registrar_from_template(ctx, max_collections)
+    }
     pub fn create_voter_weight_record(
         ctx: Context<CreateVoterWeightRecord>,
         governing_token_owner: Pubkey
@@ -29,54 +60,453 @@ pub mod nft_voter {
         log_version();
         instructions::create_voter_weight_record(ctx, governing_token_owner)
     }
+    pub fn create_voter_weight_detail(
+        ctx: Context<CreateVoterWeightDetail>,
+        max_collections: u8
+    ) -> Result<()> {
+        log_version();
+        instructions::create_voter_weight_detail(ctx, max_collections)
+    }
     pub fn create_max_voter_weight_record(ctx: Context<CreateMaxVoterWeightRecord>) -> Result<()> {
         log_version();
         instructions::create_max_voter_weight_record(ctx)
     }
     pub fn update_voter_weight_record(
         ctx: Context<UpdateVoterWeightRecord>,
-        voter_weight_action: VoterWeightAction
+        voter_weight_action: VoterWeightAction,
+        use_voter_group: bool,
+        expected_record_nonce: u32
+    ) -> Result<()> {
+        log_version();
+        instructions::update_voter_weight_record(
+            ctx,
+            voter_weight_action,
+            use_voter_group,
+            expected_record_nonce
+        )
+    }
+    pub fn update_voter_weight_record_for_target(
+        ctx: Context<UpdateVoterWeightRecordForTarget>,
+        voter_weight_action: VoterWeightAction,
+        expected_record_nonce: u32
     ) -> Result<()> {
         log_version();
-        instructions::update_voter_weight_record(ctx, voter_weight_action)
+        instructions::update_voter_weight_record_for_target(
+            ctx,
+            voter_weight_action,
+            expected_record_nonce
+        )
     }
 
     pub fn relinquish_nft_vote(ctx: Context<RelinquishNftVote>) -> Result<()> {
         log_version();
         instructions::relinquish_nft_vote(ctx)
     }
-    pub fn configure_collection(
-        ctx: Context<ConfigureCollection>,
+    pub fn reclaim_nft_vote_records(ctx: Context<ReclaimNftVoteRecords>) -> Result<()> {
+        log_version();
+        instructions::reclaim_nft_vote_records(ctx)
+    }
+    pub fn configure_collection<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ConfigureCollection<'info>>,
         weight: u64,
         size: u32
     ) -> Result<()> {
         log_version();
         instructions::configure_collection(ctx, weight, size)
     }
+    pub fn configure_collections_batch<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ConfigureCollectionsBatch<'info>>,
+        collections: Vec<CollectionConfigArgs>
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_collections_batch(ctx, collections)
+    }
+    pub fn configure_creator_boosts(
+        ctx: Context<ConfigureCreatorBoosts>,
+        collection: Pubkey,
+        creator_boosts: Vec<CreatorBoost>
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_creator_boosts(ctx, collection, creator_boosts)
+    }
+    pub fn configure_collection_expiry(
+        ctx: Context<ConfigureCollectionExpiry>,
+        collection: Pubkey,
+        expires_at: i64
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_collection_expiry(ctx, collection, expires_at)
+    }
+    pub fn configure_collection_weight_strategy(
+        ctx: Context<ConfigureCollectionWeightStrategy>,
+        collection: Pubkey,
+        weight_strategy: CollectionWeightStrategy
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_collection_weight_strategy(ctx, collection, weight_strategy)
+    }
+    pub fn configure_collection_uses_limit(
+        ctx: Context<ConfigureCollectionUsesLimit>,
+        collection: Pubkey,
+        uses_limited: bool
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_collection_uses_limit(ctx, collection, uses_limited)
+    }
+    pub fn prune_expired_collections(ctx: Context<PruneExpiredCollections>) -> Result<()> {
+        log_version();
+        instructions::prune_expired_collections(ctx)
+    }
+    pub fn preview_voter_weight<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, PreviewVoterWeight<'info>>,
+        governing_token_owner: Pubkey,
+        nft_count: u8,
+        params: Vec<CompressedNftAsset>
+    ) -> Result<()> {
+        log_version();
+        instructions::preview_voter_weight(ctx, governing_token_owner, nft_count, params)
+    }
+    pub fn configure_registrar(
+        ctx: Context<ConfigureRegistrar>,
+        config: RegistrarConfig
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_registrar(ctx, config)
+    }
+    pub fn extend_account(ctx: Context<ExtendAccount>, extra_space: u32) -> Result<()> {
+        log_version();
+        instructions::extend_account(ctx, extra_space)
+    }
+    pub fn configure_governance_weight(
+        ctx: Context<ConfigureGovernanceWeight>,
+        weight_multiplier_bps: u32
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_governance_weight(ctx, weight_multiplier_bps)
+    }
+
+    pub fn configure_sub_registrar(
+        ctx: Context<ConfigureSubRegistrar>,
+        weight_scale_bps: u32
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_sub_registrar(ctx, weight_scale_bps)
+    }
+
+    pub fn sync_parent_weight(
+        ctx: Context<SyncParentWeight>,
+        voter_weight_action: VoterWeightAction,
+        weight_action_target: Option<Pubkey>
+    ) -> Result<()> {
+        log_version();
+        instructions::sync_parent_weight(ctx, voter_weight_action, weight_action_target)
+    }
+
+    pub fn freeze_voter(ctx: Context<FreezeVoter>, governing_token_owner: Pubkey) -> Result<()> {
+        log_version();
+        instructions::freeze_voter(ctx, governing_token_owner)
+    }
+    pub fn unfreeze_voter(ctx: Context<UnfreezeVoter>) -> Result<()> {
+        log_version();
+        instructions::unfreeze_voter(ctx)
+    }
 
     pub fn cast_nft_vote<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CastNftVote<'info>>,
-        proposal: Pubkey
+        proposal: Pubkey,
+        simulate_only: bool,
+        memo: Option<String>,
+        use_voter_rent_escrow: bool,
+        use_voting_session: bool,
+        vote_kind: NftVoteKind,
+        use_voter_group: bool
     ) -> Result<()> {
         log_version();
-        instructions::cast_nft_vote(ctx, proposal)
+        instructions::cast_nft_vote(
+            ctx,
+            proposal,
+            simulate_only,
+            memo,
+            use_voter_rent_escrow,
+            use_voting_session,
+            vote_kind,
+            use_voter_group
+        )
+    }
+
+    pub fn finalize_nft_vote(ctx: Context<FinalizeNftVote>, proposal: Pubkey) -> Result<()> {
+        log_version();
+        instructions::finalize_nft_vote(ctx, proposal)
     }
 
     pub fn create_nft_action_ticket<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CreateNftActionTicket<'info>>,
-        voter_weight_action: VoterWeightAction
+        voter_weight_action: VoterWeightAction,
+        voter_allowlist_proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        log_version();
+        instructions::create_nft_action_ticket(ctx, voter_weight_action, voter_allowlist_proof)
+    }
+
+    pub fn create_core_asset_action_ticket<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreateCoreAssetActionTicket<'info>>,
+        voter_weight_action: VoterWeightAction,
+        voter_allowlist_proof: Vec<[u8; 32]>
     ) -> Result<()> {
         log_version();
-        instructions::create_nft_action_ticket(ctx, voter_weight_action)
+        instructions::create_core_asset_action_ticket(
+            ctx,
+            voter_weight_action,
+            voter_allowlist_proof
+        )
     }
 
     pub fn create_cnft_action_ticket<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CreateCnftActionTicket<'info>>,
         voter_weight_action: VoterWeightAction,
-        params: Vec<CompressedNftAsset>
+        params: Vec<CompressedNftAsset>,
+        voter_allowlist_proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        log_version();
+        instructions::create_cnft_action_ticket(
+            ctx,
+            voter_weight_action,
+            params,
+            voter_allowlist_proof
+        )
+    }
+
+    pub fn create_proposal_receipt<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CreateProposalReceipt<'info>>
+    ) -> Result<()> {
+        log_version();
+        instructions::create_proposal_receipt(ctx)
+    }
+
+    pub fn consume_proposal_creation_ticket(
+        ctx: Context<ConsumeProposalCreationTicket>
+    ) -> Result<()> {
+        log_version();
+        instructions::consume_proposal_creation_ticket(ctx)
+    }
+
+    pub fn link_cold_wallet(
+        ctx: Context<LinkColdWallet>,
+        cold_wallet: Pubkey,
+        expires_at: i64
     ) -> Result<()> {
         log_version();
-        instructions::create_cnft_action_ticket(ctx, voter_weight_action, params)
+        instructions::link_cold_wallet(ctx, cold_wallet, expires_at)
+    }
+
+    pub fn revoke_cold_wallet_link(ctx: Context<RevokeColdWalletLink>) -> Result<()> {
+        log_version();
+        instructions::revoke_cold_wallet_link(ctx)
+    }
+
+    pub fn close_expired_delegation(ctx: Context<CloseExpiredDelegation>) -> Result<()> {
+        log_version();
+        instructions::close_expired_delegation(ctx)
+    }
+
+    pub fn create_voter_rent_escrow(
+        ctx: Context<CreateVoterRentEscrow>,
+        lamports: u64
+    ) -> Result<()> {
+        log_version();
+        instructions::create_voter_rent_escrow(ctx, lamports)
+    }
+
+    pub fn close_voter_rent_escrow(ctx: Context<CloseVoterRentEscrow>) -> Result<()> {
+        log_version();
+        instructions::close_voter_rent_escrow(ctx)
+    }
+
+    pub fn create_voting_session(
+        ctx: Context<CreateVotingSession>,
+        session_authority: Pubkey,
+        expires_at_slot: u64
+    ) -> Result<()> {
+        log_version();
+        instructions::create_voting_session(ctx, session_authority, expires_at_slot)
+    }
+
+    pub fn revoke_voting_session(ctx: Context<RevokeVotingSession>) -> Result<()> {
+        log_version();
+        instructions::revoke_voting_session(ctx)
+    }
+
+    pub fn validate_realm_config(
+        ctx: Context<ValidateRealmConfig>,
+        voter_weight_addin: bool,
+        max_voter_weight_addin: bool
+    ) -> Result<()> {
+        log_version();
+        instructions::validate_realm_config(ctx, voter_weight_addin, max_voter_weight_addin)
+    }
+
+    pub fn cast_nft_vote_multi<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CastNftVoteMulti<'info>>,
+        proposals: Vec<Pubkey>,
+        vote_kind: NftVoteKind
+    ) -> Result<()> {
+        log_version();
+        instructions::cast_nft_vote_multi(ctx, proposals, vote_kind)
+    }
+
+    pub fn select_multi_proposal_vote(
+        ctx: Context<SelectMultiProposalVote>,
+        proposal: Pubkey
+    ) -> Result<()> {
+        log_version();
+        instructions::select_multi_proposal_vote(ctx, proposal)
+    }
+
+    pub fn migrate_voter<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigrateVoter<'info>>,
+        cold_wallet_links_count: u8
+    ) -> Result<()> {
+        log_version();
+        instructions::migrate_voter(ctx, cold_wallet_links_count)
+    }
+
+    pub fn create_voter_group(ctx: Context<CreateVoterGroup>) -> Result<()> {
+        log_version();
+        instructions::create_voter_group(ctx)
+    }
+
+    pub fn join_voter_group(ctx: Context<JoinVoterGroup>) -> Result<()> {
+        log_version();
+        instructions::join_voter_group(ctx)
+    }
+
+    pub fn leave_voter_group(ctx: Context<LeaveVoterGroup>, member: Pubkey) -> Result<()> {
+        log_version();
+        instructions::leave_voter_group(ctx, member)
+    }
+
+    pub fn validate_registrar(ctx: Context<ValidateRegistrar>) -> Result<()> {
+        log_version();
+        instructions::validate_registrar(ctx)
+    }
+
+    pub fn create_ballot_tree(
+        ctx: Context<CreateBallotTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+        name: String,
+        symbol: String,
+        uri: String
+    ) -> Result<()> {
+        log_version();
+        instructions::create_ballot_tree(ctx, max_depth, max_buffer_size, name, symbol, uri)
+    }
+
+    pub fn mint_ballot(ctx: Context<MintBallot>) -> Result<()> {
+        log_version();
+        instructions::mint_ballot(ctx)
+    }
+
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        proposal: Pubkey,
+        allowlist_root: [u8; 32],
+        ballot_weight: u64
+    ) -> Result<()> {
+        log_version();
+        instructions::create_campaign(ctx, proposal, allowlist_root, ballot_weight)
+    }
+
+    pub fn claim_ballot(ctx: Context<ClaimBallot>, allowlist_proof: Vec<[u8; 32]>) -> Result<()> {
+        log_version();
+        instructions::claim_ballot(ctx, allowlist_proof)
+    }
+
+    pub fn cast_ballot_vote<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CastBallotVote<'info>>,
+        proposal: Pubkey,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32
+    ) -> Result<()> {
+        log_version();
+        instructions::cast_ballot_vote(ctx, proposal, root, nonce, index)
+    }
+
+    pub fn create_weight_snapshot(
+        ctx: Context<CreateWeightSnapshot>,
+        proposal: Pubkey,
+        snapshot_slot: u64,
+        holdings_root: [u8; 32],
+        total_weight: u64
+    ) -> Result<()> {
+        log_version();
+        instructions::create_weight_snapshot(ctx, proposal, snapshot_slot, holdings_root, total_weight)
+    }
+
+    pub fn cast_snapshot_vote(
+        ctx: Context<CastSnapshotVote>,
+        proposal: Pubkey,
+        voter_weight: u64,
+        proof: Vec<[u8; 32]>
+    ) -> Result<()> {
+        log_version();
+        instructions::cast_snapshot_vote(ctx, proposal, voter_weight, proof)
+    }
+
+    pub fn write_external_voter_weight(ctx: Context<WriteExternalVoterWeight>) -> Result<()> {
+        log_version();
+        instructions::write_external_voter_weight(ctx)
+    }
+
+    pub fn rent_voting_power<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RentVotingPower<'info>>,
+        asset_id: Pubkey,
+        params: CompressedNftAsset,
+        renter: Pubkey,
+        expires_at_slot: u64
+    ) -> Result<()> {
+        log_version();
+        instructions::rent_voting_power(ctx, asset_id, params, renter, expires_at_slot)
+    }
+
+    pub fn reclaim_voting_power(ctx: Context<ReclaimVotingPower>) -> Result<()> {
+        log_version();
+        instructions::reclaim_voting_power(ctx)
+    }
+
+    pub fn map_collection_alias(
+        ctx: Context<MapCollectionAlias>,
+        collection: Pubkey,
+        target_collection: Pubkey,
+        weight_bps: u16
+    ) -> Result<()> {
+        log_version();
+        instructions::map_collection_alias(ctx, collection, target_collection, weight_bps)
+    }
+
+    pub fn register_voter(ctx: Context<RegisterVoter>) -> Result<()> {
+        log_version();
+        instructions::register_voter(ctx)
+    }
+
+    pub fn close_orphaned_nft_vote_records(ctx: Context<CloseOrphanedNftVoteRecords>) -> Result<()> {
+        log_version();
+        instructions::close_orphaned_nft_vote_records(ctx)
+    }
+
+    pub fn configure_wallet_weight_override(
+        ctx: Context<ConfigureWalletWeightOverride>,
+        wallet: Pubkey,
+        weight_bps: u16
+    ) -> Result<()> {
+        log_version();
+        instructions::configure_wallet_weight_override(ctx, wallet, weight_bps)
+    }
+
+    pub fn mint_vote_receipt(ctx: Context<MintVoteReceipt>) -> Result<()> {
+        log_version();
+        instructions::mint_vote_receipt(ctx)
     }
 }
 
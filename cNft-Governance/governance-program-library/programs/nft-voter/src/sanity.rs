@@ -0,0 +1,218 @@
+//! Invariant checks of the program state
+//!
+//! The checks are compiled in only with the `strict-checks` feature and are no-ops otherwise
+//! They are meant for devnet deployments to catch logic bugs (and misconfigured Registrars) before mainnet
+//! The invariants are evaluated independently of the program logic they guard and fail with InvariantViolated
+
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+use crate::state::{ CollectionWeightMode, Registrar, RegistrarExtension, MAX_BASIS_POINTS };
+
+/// Returns true if the invariant checks are compiled in
+pub fn is_enabled() -> bool {
+    cfg!(feature = "strict-checks")
+}
+
+/// Checks the max voter weight of the Registrar doesn't overflow u64
+/// The weight is evaluated using u128 arithmetic to detect overflows hidden by truncation
+pub fn check_max_voter_weight(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension
+) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    verify_max_voter_weight(registrar, registrar_extension)
+}
+
+/// Checks the voter weight doesn't exceed the max voter weight of the Registrar
+/// It would mean the configured collection sizes (or the weight strategies) don't reflect the actual collections
+pub fn check_voter_weight(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
+    voter_weight: u64
+) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    verify_voter_weight(registrar, registrar_extension, voter_weight)
+}
+
+/// Checks the expiry of VoterWeightRecord doesn't move backwards when the weight of the same action is updated
+pub fn check_voter_weight_expiry(
+    previous_expiry: Option<u64>,
+    expiry: Option<u64>
+) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    verify_voter_weight_expiry(previous_expiry, expiry)
+}
+
+fn verify_max_voter_weight(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension
+) -> Result<()> {
+    let max_voter_weight = registrar.collection_configs
+        .iter()
+        .map(|cc| {
+            let max_boost_bps = registrar_extension
+                .get_collection_extension(&cc.collection)
+                .creator_boosts.iter()
+                .filter(|cb| cb.creator != Pubkey::default())
+                .map(|cb| cb.boost_bps)
+                .max()
+                .unwrap_or(0);
+
+            let asset_weight =
+                (cc.weight as u128) +
+                ((cc.weight as u128) * (max_boost_bps as u128)) / (MAX_BASIS_POINTS as u128);
+
+            let asset_weight = if
+                registrar_extension.config.collection_weight_mode == CollectionWeightMode::Normalized &&
+                cc.size > 0
+            {
+                (asset_weight * (registrar_extension.config.normalized_collection_weight as u128)) /
+                    (cc.size as u128)
+            } else {
+                asset_weight
+            };
+
+            asset_weight * (cc.size as u128)
+        })
+        .fold(registrar_extension.sub_registrars_max_voter_weight as u128, |sum, weight| sum + weight);
+
+    if max_voter_weight > (u64::MAX as u128) {
+        msg!("Invariant violated: max voter weight {} overflows u64", max_voter_weight);
+        return err!(NftVoterError::InvariantViolated);
+    }
+
+    Ok(())
+}
+
+fn verify_voter_weight(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
+    voter_weight: u64
+) -> Result<()> {
+    let max_voter_weight = registrar.get_max_voter_weight(registrar_extension)?;
+
+    if voter_weight > max_voter_weight {
+        msg!(
+            "Invariant violated: voter weight {} exceeds max voter weight {}",
+            voter_weight,
+            max_voter_weight
+        );
+        return err!(NftVoterError::InvariantViolated);
+    }
+
+    Ok(())
+}
+
+fn verify_voter_weight_expiry(previous_expiry: Option<u64>, expiry: Option<u64>) -> Result<()> {
+    // None means the weight never expires and it can only be followed by None
+    let is_monotonic = match (previous_expiry, expiry) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some(previous_expiry), Some(expiry)) => expiry >= previous_expiry,
+    };
+
+    if !is_monotonic {
+        msg!(
+            "Invariant violated: voter weight expiry {:?} precedes the previous expiry {:?}",
+            expiry,
+            previous_expiry
+        );
+        return err!(NftVoterError::InvariantViolated);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::{ CollectionConfig, CollectionExtension, CreatorBoost };
+
+    fn create_registrar(collection_configs: Vec<CollectionConfig>) -> Registrar {
+        Registrar {
+            governance_program_id: Pubkey::new_unique(),
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            collection_configs,
+            reserved: [0; 128],
+        }
+    }
+
+    #[test]
+    fn test_verify_max_voter_weight() {
+        // Arrange
+        let registrar = create_registrar(
+            vec![CollectionConfig {
+                weight: u64::MAX / 10,
+                size: 10,
+                ..Default::default()
+            }]
+        );
+
+        // Act + Assert
+        assert!(verify_max_voter_weight(&registrar, &RegistrarExtension::new(Pubkey::new_unique())).is_ok());
+    }
+
+    #[test]
+    fn test_verify_max_voter_weight_with_overflow() {
+        // Arrange
+        let collection_config = CollectionConfig {
+            weight: u64::MAX / 10,
+            size: 10,
+            ..Default::default()
+        };
+
+        let mut collection_extension = CollectionExtension::new(collection_config.collection);
+
+        collection_extension.creator_boosts[0] = CreatorBoost {
+            creator: Pubkey::new_unique(),
+            boost_bps: 1_000,
+        };
+
+        let registrar = create_registrar(vec![collection_config]);
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::new_unique());
+        registrar_extension.set_collection_extension(collection_extension);
+
+        // Act + Assert
+        assert!(verify_max_voter_weight(&registrar, &registrar_extension).is_err());
+    }
+
+    #[test]
+    fn test_verify_voter_weight() {
+        // Arrange
+        let registrar = create_registrar(
+            vec![CollectionConfig {
+                weight: 2,
+                size: 10,
+                ..Default::default()
+            }]
+        );
+
+        let registrar_extension = RegistrarExtension::new(Pubkey::new_unique());
+
+        // Act + Assert
+        assert!(verify_voter_weight(&registrar, &registrar_extension, 20).is_ok());
+        assert!(verify_voter_weight(&registrar, &registrar_extension, 21).is_err());
+    }
+
+    #[test]
+    fn test_verify_voter_weight_expiry() {
+        // Act + Assert
+        assert!(verify_voter_weight_expiry(Some(10), Some(10)).is_ok());
+        assert!(verify_voter_weight_expiry(Some(10), Some(11)).is_ok());
+        assert!(verify_voter_weight_expiry(Some(10), None).is_ok());
+        assert!(verify_voter_weight_expiry(Some(10), Some(9)).is_err());
+        assert!(verify_voter_weight_expiry(None, Some(10)).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
+use solana_program::program_pack::IsInitialized;
+
+use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
+
+use crate::{ error::NftVoterError, id };
+
+/// Counter of the uses consumed by a compressed NFT with Metaplex Uses
+/// The PDA of the record is ["asset-uses-record",asset_id]
+/// cNFT metadata can't be mutated on-chain to decrement Uses.remaining and hence
+/// the consumed uses are tracked by the record for the whole lifetime of the asset
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct AssetUsesRecord {
+    /// AssetUsesRecord discriminator sha256("account:AssetUsesRecord")[..8]
+    /// Note: The discriminator is used explicitly because AssetUsesRecords
+    /// are created and consumed dynamically using remaining_accounts
+    pub account_discriminator: [u8; 8],
+
+    /// The compressed NFT the uses are tracked for
+    pub asset_id: Pubkey,
+
+    /// The number of uses consumed by the asset
+    pub used: u64,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 8],
+}
+
+impl AssetUsesRecord {
+    /// sha256("account:AssetUsesRecord")[..8]
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [65, 88, 192, 11, 51, 233, 165, 134];
+
+    pub fn new(asset_id: Pubkey) -> Self {
+        Self {
+            account_discriminator: AssetUsesRecord::ACCOUNT_DISCRIMINATOR,
+            asset_id,
+            used: 0,
+            reserved: [0; 8],
+        }
+    }
+}
+
+impl AccountMaxSize for AssetUsesRecord {}
+
+impl IsInitialized for AssetUsesRecord {
+    fn is_initialized(&self) -> bool {
+        self.account_discriminator == AssetUsesRecord::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+/// Returns AssetUsesRecord PDA seeds
+pub fn get_asset_uses_record_seeds(asset_id: &Pubkey) -> [&[u8]; 2] {
+    [b"asset-uses-record", asset_id.as_ref()]
+}
+
+/// Returns AssetUsesRecord PDA address
+pub fn get_asset_uses_record_address(asset_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_asset_uses_record_seeds(asset_id), &id()).0
+}
+
+/// Deserializes account and checks owner program
+pub fn get_asset_uses_record_data(asset_uses_record_info: &AccountInfo) -> Result<AssetUsesRecord> {
+    Ok(get_account_data::<AssetUsesRecord>(&id(), asset_uses_record_info)?)
+}
+
+pub fn get_asset_uses_record_data_for_asset(
+    asset_uses_record_info: &AccountInfo,
+    asset_id: &Pubkey
+) -> Result<AssetUsesRecord> {
+    let asset_uses_record = get_asset_uses_record_data(asset_uses_record_info)?;
+
+    require!(asset_uses_record.asset_id == *asset_id, NftVoterError::InvalidAssetUsesRecord);
+
+    Ok(asset_uses_record)
+}
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::instructions::{ MintV1Cpi, MintV1CpiAccounts, MintV1InstructionArgs };
+use mpl_bubblegum::types::{ Creator, MetadataArgs, TokenProgramVersion, TokenStandard };
+use mpl_token_metadata::{ MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH };
+use solana_program::keccak::{ hash, hashv };
+use solana_program::pubkey::PUBKEY_BYTES;
+use spl_governance::state::vote_record::Vote;
+
+use crate::{ error::NftVoterError, id, state::get_vote_receipt_uri, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// Bubblegum tree owned by the program to mint ballot cNFTs to the members of the DAO
+/// The BallotTree PDA is the tree creator and the tree delegate of the Bubblegum tree
+/// and it's the only verified creator of the ballots so they can't be minted outside of mint_ballot
+/// The tree set as RegistrarConfig.vote_receipt_tree is also used to mint the vote receipts by mint_vote_receipt
+/// The PDA of the BallotTree is ["ballot-tree",registrar,merkle_tree]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct BallotTree {
+    /// The Registrar the tree belongs to
+    pub registrar: Pubkey,
+
+    /// The Bubblegum merkle tree the ballots are minted to
+    pub merkle_tree: Pubkey,
+
+    /// The name of the ballots
+    pub name: String,
+
+    /// The symbol of the ballots
+    pub symbol: String,
+
+    /// The uri of the ballots metadata
+    pub uri: String,
+
+    /// The number of ballots and vote receipts minted to the tree
+    pub ballots_minted: u64,
+}
+
+impl BallotTree {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE +
+            PUBKEY_BYTES * 2 +
+            4 +
+            MAX_NAME_LENGTH +
+            4 +
+            MAX_SYMBOL_LENGTH +
+            4 +
+            MAX_URI_LENGTH +
+            8
+    }
+
+    /// Asserts the ballot metadata fits the Metaplex limits
+    pub fn assert_valid_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+        require!(
+            name.len() <= MAX_NAME_LENGTH &&
+                symbol.len() <= MAX_SYMBOL_LENGTH &&
+                uri.len() <= MAX_URI_LENGTH,
+            NftVoterError::InvalidBallotMetadata
+        );
+
+        Ok(())
+    }
+
+    /// Returns the metadata of the ballots minted to the tree
+    /// The BallotTree is the only creator and it's verified by signing the mint
+    pub fn get_ballot_metadata(&self, ballot_tree: &Pubkey) -> MetadataArgs {
+        MetadataArgs {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: vec![Creator {
+                address: *ballot_tree,
+                verified: true,
+                share: 100,
+            }],
+        }
+    }
+
+    /// Returns the metadata of the vote receipt of the given Vote on the given Proposal
+    /// The receipt is a ballot whose uri encodes the Proposal and the Vote
+    /// and hence its leaf doesn't match the ballot hashes and it can't be burnt by cast_ballot_vote
+    pub fn get_vote_receipt_metadata(
+        &self,
+        ballot_tree: &Pubkey,
+        proposal: &Pubkey,
+        vote: &Vote
+    ) -> Result<MetadataArgs> {
+        let mut metadata = self.get_ballot_metadata(ballot_tree);
+        metadata.uri = get_vote_receipt_uri(&self.uri, proposal, vote);
+
+        require!(metadata.uri.len() <= MAX_URI_LENGTH, NftVoterError::InvalidBallotMetadata);
+
+        Ok(metadata)
+    }
+}
+
+/// Returns the data_hash and the creator_hash of the ballots hashed into their leaves
+/// The hashes are computed the same way as Bubblegum and they are required to burn the ballots
+pub fn get_ballot_hashes(ballot_tree: &BallotTree, ballot_tree_address: &Pubkey) -> Result<([u8; 32], [u8; 32])> {
+    let metadata = ballot_tree.get_ballot_metadata(ballot_tree_address);
+
+    let data_hash = hashv(
+        &[hash(&metadata.try_to_vec()?).as_ref(), &metadata.seller_fee_basis_points.to_le_bytes()]
+    ).to_bytes();
+
+    let creator_data = metadata.creators
+        .iter()
+        .map(|creator| [creator.address.as_ref(), &[creator.verified as u8, creator.share]].concat())
+        .collect::<Vec<Vec<u8>>>();
+
+    let creator_hash = hashv(
+        &creator_data
+            .iter()
+            .map(|data| data.as_slice())
+            .collect::<Vec<&[u8]>>()
+    ).to_bytes();
+
+    Ok((data_hash, creator_hash))
+}
+
+/// Mints a ballot of the BallotTree with the given metadata to the leaf owner
+/// The metadata is the ballot metadata or the vote receipt metadata of the BallotTree
+/// The BallotTree signs the Bubblegum mint as the tree delegate and as the creator of the ballot
+pub fn mint_ballot_to<'info>(
+    ballot_tree: &mut Account<'info, BallotTree>,
+    ballot_tree_bump: u8,
+    metadata: MetadataArgs,
+    tree_authority: &AccountInfo<'info>,
+    leaf_owner: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    bubblegum_program: &AccountInfo<'info>,
+    log_wrapper: &AccountInfo<'info>,
+    compression_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>
+) -> Result<()> {
+    let bump = [ballot_tree_bump];
+    let mut signer_seeds = get_ballot_tree_seeds(&ballot_tree.registrar, &ballot_tree.merkle_tree).to_vec();
+    signer_seeds.push(&bump);
+
+    let ballot_tree_info = ballot_tree.to_account_info();
+
+    MintV1Cpi::new(
+        bubblegum_program,
+        MintV1CpiAccounts {
+            tree_config: tree_authority,
+            leaf_owner,
+            leaf_delegate: leaf_owner,
+            merkle_tree,
+            payer,
+            tree_creator_or_delegate: &ballot_tree_info,
+            log_wrapper,
+            compression_program,
+            system_program,
+        },
+        MintV1InstructionArgs { metadata }
+    ).invoke_signed(&[&signer_seeds[..]])?;
+
+    ballot_tree.ballots_minted = ballot_tree.ballots_minted
+        .checked_add(1)
+        .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Returns BallotTree PDA seeds
+pub fn get_ballot_tree_seeds<'a>(registrar: &'a Pubkey, merkle_tree: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"ballot-tree", registrar.as_ref(), merkle_tree.as_ref()]
+}
+
+/// Returns BallotTree PDA address
+pub fn get_ballot_tree_address(registrar: &Pubkey, merkle_tree: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_ballot_tree_seeds(registrar, merkle_tree), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_ballot_tree() -> BallotTree {
+        BallotTree {
+            registrar: Pubkey::new_unique(),
+            merkle_tree: Pubkey::new_unique(),
+            name: "a".repeat(MAX_NAME_LENGTH),
+            symbol: "b".repeat(MAX_SYMBOL_LENGTH),
+            uri: "c".repeat(MAX_URI_LENGTH),
+            ballots_minted: 0,
+        }
+    }
+
+    #[test]
+    fn test_assert_valid_metadata() {
+        // Act + Assert
+        assert!(BallotTree::assert_valid_metadata("Ballot", "BLT", "https://ballot").is_ok());
+        assert!(
+            BallotTree::assert_valid_metadata(&"a".repeat(MAX_NAME_LENGTH + 1), "BLT", "").is_err()
+        );
+        assert!(
+            BallotTree::assert_valid_metadata("Ballot", &"b".repeat(MAX_SYMBOL_LENGTH + 1), "").is_err()
+        );
+        assert!(
+            BallotTree::assert_valid_metadata("Ballot", "BLT", &"c".repeat(MAX_URI_LENGTH + 1)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_get_ballot_metadata() {
+        // Arrange
+        let ballot_tree = create_test_ballot_tree();
+        let ballot_tree_address = Pubkey::new_unique();
+
+        // Act
+        let metadata = ballot_tree.get_ballot_metadata(&ballot_tree_address);
+
+        // Assert
+        assert_eq!(metadata.name, ballot_tree.name);
+        assert_eq!(metadata.creators.len(), 1);
+        assert_eq!(metadata.creators[0].address, ballot_tree_address);
+        assert!(metadata.creators[0].verified);
+    }
+
+    #[test]
+    fn test_get_vote_receipt_metadata() {
+        // Arrange
+        let mut ballot_tree = create_test_ballot_tree();
+        ballot_tree.uri = "https://receipts".to_string();
+
+        let ballot_tree_address = Pubkey::new_unique();
+        let proposal = Pubkey::new_unique();
+
+        // Act
+        let metadata = ballot_tree
+            .get_vote_receipt_metadata(&ballot_tree_address, &proposal, &Vote::Deny)
+            .unwrap();
+
+        // Assert
+        assert_eq!(metadata.uri, format!("https://receipts?proposal={}&vote=deny", proposal));
+        assert_eq!(metadata.creators[0].address, ballot_tree_address);
+        assert_ne!(metadata, ballot_tree.get_ballot_metadata(&ballot_tree_address));
+    }
+
+    #[test]
+    fn test_get_vote_receipt_metadata_with_uri_too_long_error() {
+        // Arrange
+        let ballot_tree = create_test_ballot_tree();
+
+        // Act
+        let err = ballot_tree
+            .get_vote_receipt_metadata(&Pubkey::new_unique(), &Pubkey::new_unique(), &Vote::Deny)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::InvalidBallotMetadata.into());
+    }
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    error::NftVoterError,
+    id,
+    tools::{ anchor::DISCRIMINATOR_SIZE, merkle_tree::{ get_voter_allowlist_leaf, verify_sorted_merkle_proof } },
+};
+
+/// One-off referendum on a Proposal run with the ballots of a BallotTree
+/// The wallets in the allowlist claim a ballot each with claim_ballot
+/// and cast_ballot_vote burns the ballot to set the voter weight for the Proposal
+/// The PDA of the campaign is ["campaign",registrar,proposal]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct Campaign {
+    /// The Registrar the campaign belongs to
+    pub registrar: Pubkey,
+
+    /// The BallotTree the ballots are minted to
+    pub ballot_tree: Pubkey,
+
+    /// The Proposal the ballots can be used to vote on
+    pub proposal: Pubkey,
+
+    /// Root of the sorted merkle tree of get_voter_allowlist_leaf(wallet) of the wallets which can claim a ballot
+    pub allowlist_root: [u8; 32],
+
+    /// The voter weight of a single ballot
+    pub ballot_weight: u64,
+
+    /// The number of claimed ballots
+    pub ballots_claimed: u64,
+
+    /// The number of ballots burnt by voting
+    pub ballots_cast: u64,
+}
+
+impl Campaign {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 32 + 8 * 3
+    }
+
+    /// Asserts the wallet is in the allowlist of the campaign
+    pub fn assert_claimant_allowed(&self, claimant: &Pubkey, allowlist_proof: &[[u8; 32]]) -> Result<()> {
+        require!(
+            verify_sorted_merkle_proof(
+                get_voter_allowlist_leaf(claimant),
+                allowlist_proof,
+                self.allowlist_root
+            ),
+            NftVoterError::CampaignClaimNotAllowed
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns Campaign PDA seeds
+pub fn get_campaign_seeds<'a>(registrar: &'a Pubkey, proposal: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"campaign", registrar.as_ref(), proposal.as_ref()]
+}
+
+/// Returns Campaign PDA address
+pub fn get_campaign_address(registrar: &Pubkey, proposal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_campaign_seeds(registrar, proposal), &id()).0
+}
+
+/// Record of the ballot claimed by a wallet of the Campaign allowlist
+/// It prevents the wallet from claiming another ballot
+/// The PDA of the claim is ["campaign-claim",campaign,claimant]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct CampaignClaim {
+    /// The Campaign the ballot was claimed from
+    pub campaign: Pubkey,
+
+    /// The wallet which claimed the ballot
+    pub claimant: Pubkey,
+
+    /// The slot the ballot was claimed at
+    pub claimed_at: u64,
+}
+
+impl CampaignClaim {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 8
+    }
+}
+
+/// Returns CampaignClaim PDA seeds
+pub fn get_campaign_claim_seeds<'a>(campaign: &'a Pubkey, claimant: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"campaign-claim", campaign.as_ref(), claimant.as_ref()]
+}
+
+/// Returns CampaignClaim PDA address
+pub fn get_campaign_claim_address(campaign: &Pubkey, claimant: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_campaign_claim_seeds(campaign, claimant), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::keccak::hashv;
+
+    fn create_test_campaign() -> Campaign {
+        Campaign {
+            registrar: Pubkey::new_unique(),
+            ballot_tree: Pubkey::new_unique(),
+            proposal: Pubkey::new_unique(),
+            allowlist_root: [0; 32],
+            ballot_weight: 1,
+            ballots_claimed: 0,
+            ballots_cast: 0,
+        }
+    }
+
+    #[test]
+    fn test_get_campaign_claim_space() {
+        // Arrange
+        let expected_space = CampaignClaim::get_space();
+
+        let campaign_claim = CampaignClaim {
+            campaign: Pubkey::new_unique(),
+            claimant: Pubkey::new_unique(),
+            claimed_at: 0,
+        };
+
+        // Act
+        let actual_space = DISCRIMINATOR_SIZE + campaign_claim.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(expected_space, actual_space);
+    }
+
+    #[test]
+    fn test_assert_claimant_allowed() {
+        // Arrange
+        let claimant = Pubkey::new_unique();
+        let claimant_leaf = get_voter_allowlist_leaf(&claimant);
+        let other_leaf = get_voter_allowlist_leaf(&Pubkey::new_unique());
+
+        let mut campaign = create_test_campaign();
+        campaign.allowlist_root = if claimant_leaf <= other_leaf {
+            hashv(&[&claimant_leaf, &other_leaf]).to_bytes()
+        } else {
+            hashv(&[&other_leaf, &claimant_leaf]).to_bytes()
+        };
+
+        // Act + Assert
+        assert!(campaign.assert_claimant_allowed(&claimant, &[other_leaf]).is_ok());
+        assert!(campaign.assert_claimant_allowed(&claimant, &[]).is_err());
+        assert!(campaign.assert_claimant_allowed(&Pubkey::new_unique(), &[other_leaf]).is_err());
+    }
+}
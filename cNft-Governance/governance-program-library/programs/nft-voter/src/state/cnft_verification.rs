@@ -1,21 +1,47 @@
 // use crate::error::CompressedNftVoterError;
+use crate::{ error::{ log_verify_leaf_context, NftVoterError }, state::RegistrarExtension };
+use crate::tools::ed25519::assert_ed25519_signature;
+use crate::tools::merkle_tree::{
+    fast_forward_merkle_proof,
+    get_merkle_tree_changelog_usage,
+    get_merkle_tree_current_root,
+    get_merkle_tree_leaf_schema_version,
+    get_merkle_tree_max_depth,
+    get_merkle_tree_root_age,
+};
 use anchor_lang::prelude::*;
-use mpl_bubblegum::{ hash_metadata, hash_creators };
-use mpl_bubblegum::state::leaf_schema::LeafSchema;
-use mpl_bubblegum::state::metaplex_adapter::{ MetadataArgs, TokenProgramVersion, TokenStandard };
-use mpl_bubblegum::state::metaplex_adapter::{
+use solana_program::hash::Hash;
+use cnft_verification::{
+    get_leaf_node,
+    get_leaf_node_v2,
+    hash_collection,
+    hash_creators,
+    hash_metadata,
+    hash_metadata_v2,
+    verify_merkle_proof,
+    MetadataArgs,
+    MetadataArgsV2,
+    TokenProgramVersion,
+    TokenStandard,
+};
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::types::{
     Creator as MetaplexCreator,
     Collection as MetaplexCollection,
+    UseMethod as MetaplexUseMethod,
+    Uses as MetaplexUses,
 };
 // use spl_account_compression::AccountCompressionError;
 use spl_account_compression::cpi::accounts::VerifyLeaf;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Collection {
-    /// the key to check if a NFT is verified by the collection.
-    /// maybe this should forced to be true on chain instead of providing by client.
-    /// TODO: fix it.
-    pub verified: bool, //
+    /// The key to check if a NFT is verified by the collection.
+    /// It must match the current leaf metadata and only verified collections are accepted.
+    /// Note: When the collection is verified after mint using Bubblegum verify_collection or
+    /// set_and_verify_collection the leaf is rehashed with verified = true and hence the metadata
+    /// of the verified collection must be supplied and not the metadata the asset was minted with.
+    pub verified: bool,
 
     /// NFT collection address.
     pub key: Pubkey,
@@ -31,9 +57,9 @@ impl Default for Collection {
 }
 
 impl Collection {
-    /// Convert the Collection to MetaplexCollection to match mpl-bubblegum program.
-    pub fn to_bubblegum(&self) -> MetaplexCollection {
-        MetaplexCollection {
+    /// Convert the Collection to the cnft-verification Collection hashed into the leaf metadata.
+    pub fn to_verification(&self) -> cnft_verification::Collection {
+        cnft_verification::Collection {
             verified: self.verified,
             key: self.key,
         }
@@ -65,8 +91,9 @@ impl Default for Creator {
 }
 
 impl Creator {
-    pub fn to_bubblegum(&self) -> MetaplexCreator {
-        MetaplexCreator {
+    /// Convert the Creator to the cnft-verification Creator hashed into the leaf creators.
+    pub fn to_verification(&self) -> cnft_verification::Creator {
+        cnft_verification::Creator {
             address: self.address,
             verified: self.verified,
             share: self.share,
@@ -82,10 +109,93 @@ impl Creator {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+impl Uses {
+    /// Convert the Uses to the cnft-verification Uses hashed into the leaf metadata.
+    pub fn to_verification(&self) -> cnft_verification::Uses {
+        cnft_verification::Uses {
+            use_method: match self.use_method {
+                UseMethod::Burn => cnft_verification::UseMethod::Burn,
+                UseMethod::Multiple => cnft_verification::UseMethod::Multiple,
+                UseMethod::Single => cnft_verification::UseMethod::Single,
+            },
+            remaining: self.remaining,
+            total: self.total,
+        }
+    }
+
+    /// Convert the MetaplexUses to Uses.
+    pub fn from_bubblegum(uses: &MetaplexUses) -> Self {
+        Self {
+            use_method: match uses.use_method {
+                MetaplexUseMethod::Burn => UseMethod::Burn,
+                MetaplexUseMethod::Multiple => UseMethod::Multiple,
+                MetaplexUseMethod::Single => UseMethod::Single,
+            },
+            remaining: uses.remaining,
+            total: uses.total,
+        }
+    }
+}
+
+/// Bubblegum leaf schema the compressed NFT was minted with
+/// It must match the version of the tree: spl-account-compression trees hold V1 leaves
+/// and mpl-account-compression trees hold V2 leaves
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum LeafSchema {
+    /// mpl-bubblegum LeafSchema::V1
+    V1,
+
+    /// Bubblegum LeafSchema::V2 which additionally hashes the collection, the asset data and the flags
+    /// The collection is hashed into the leaf and it's verified by the leaf itself
+    /// Note: V2 metadata has no uses, edition_nonce and collection verified flag
+    V2 {
+        asset_data_hash: [u8; 32],
+        flags: u8,
+    },
+}
+
+impl Default for LeafSchema {
+    fn default() -> Self {
+        LeafSchema::V1
+    }
+}
+
+impl LeafSchema {
+    /// Returns the version of the leaf schema
+    pub fn version(&self) -> u8 {
+        match self {
+            LeafSchema::V1 => cnft_verification::LEAF_SCHEMA_V1,
+            LeafSchema::V2 { .. } => cnft_verification::LEAF_SCHEMA_V2,
+        }
+    }
+}
+
 /// CompressedNftAsset is the minimal data needed to verify a leaf in the merkle tree.
 /// These parameters is also the only data we can get from Helius DAS API.
 /// Why just not provide data_hash from client?
 /// Since by just given data_hash, we can verify the ownership but no the collection.
+///
+/// When RegistrarExtension.config.allow_precomputed_metadata_hashes is set then data_hash and creator_hash
+/// (as returned by DAS API) can be supplied instead of the metadata and creators to reduce the instruction size.
+/// In that case the collection can't be verified and the Registrar relies on the allowed tree authorities instead.
+///
+/// When RegistrarExtension.config.leaf_hash_verifier is set then the final leaf_hash can be supplied instead
+/// together with the ed25519 attestation of the verifier. The collection is attested by the verifier
+/// and the leaf is still verified by the merkle proof.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub struct CompressedNftAsset {
     pub name: String,
@@ -97,39 +207,283 @@ pub struct CompressedNftAsset {
     pub is_mutable: bool,
     pub edition_nonce: Option<u8>,
     pub creators: Vec<Creator>,
+
+    /// Metaplex Uses of the asset
+    /// When set each vote consumes one use tracked by AssetUsesRecord
+    pub uses: Option<Uses>,
+
     pub root: [u8; 32],
     pub leaf_owner: Pubkey,
     pub leaf_delegate: Pubkey,
     pub index: u32,
     pub nonce: u64,
     pub proof_len: u8,
+
+    /// The slot the asset was last transferred at
+    /// It's only required when RegistrarExtension.config.min_holding_slots is set and must be attested
+    /// by RegistrarExtension.config.holding_attestation_authority
+    pub last_transfer_slot: Option<u64>,
+
+    /// Precomputed hash of the metadata
+    /// When set name, symbol, uri, seller_fee_basis_points, primary_sale_happened, is_mutable,
+    /// edition_nonce and uses are not used to verify the leaf
+    pub data_hash: Option<[u8; 32]>,
+
+    /// Precomputed hash of the creators
+    /// When set creators are not used to verify the leaf
+    pub creator_hash: Option<[u8; 32]>,
+
+    /// Merkle proof of the leaf supplied as instruction data instead of remaining accounts
+    /// Each node costs 32 bytes as opposed to 34 bytes of an account meta and it's useful when the nodes are unique
+    /// and can't be deduplicated using address lookup tables
+    /// When set proof_len must be 0, the proof must be the full proof (canopy is not used)
+    /// and it must be for root which can be any root still in the changelog of the tree
+    pub proof_nodes: Option<Vec<[u8; 32]>>,
+
+    /// Whether the leaf is verified using the fresh ProofAttestation of the asset instead of the merkle proof
+    /// When set proof_len must be 0 and proof_nodes must be None
+    /// It requires RegistrarExtension.config.proof_attestation_freshness_slots to be set
+    pub use_proof_attestation: bool,
+
+    /// Leaf schema the asset was minted with (V1 by default)
+    /// V2 assets of mpl-account-compression trees must supply the proof as proof_nodes
+    pub leaf_schema: LeafSchema,
+
+    /// Leaf hash precomputed by the client and attested by RegistrarExtension.config.leaf_hash_verifier
+    /// When set the metadata, creators, data_hash and creator_hash are not hashed on-chain
+    /// and the creators (and creator boosts) and uses of the asset are not taken into account
+    pub leaf_hash: Option<[u8; 32]>,
 }
 
 impl CompressedNftAsset {
+    /// Returns true if the asset is supplied with precomputed data_hash or creator_hash
+    pub fn has_precomputed_hashes(&self) -> bool {
+        self.data_hash.is_some() || self.creator_hash.is_some()
+    }
+
     /// Convert the CompressedNftAsset to MetadataArgs to match mpl-bubblegum program.
     pub fn to_metadata_args(&self) -> MetadataArgs {
-        let mut creators = vec![];
-        for creator in self.creators.clone().iter() {
-            creators.push(creator.to_bubblegum());
-        }
         MetadataArgs {
             name: self.name.clone(),
             symbol: self.symbol.clone(),
             uri: self.uri.clone(),
             seller_fee_basis_points: self.seller_fee_basis_points,
-            creators,
+            creators: self.creators.iter().map(Creator::to_verification).collect(),
             primary_sale_happened: self.primary_sale_happened,
             is_mutable: self.is_mutable,
             edition_nonce: self.edition_nonce,
-            collection: Some(self.collection.clone().unwrap_or_default().to_bubblegum()),
-            uses: None,
+            collection: Some(self.collection.clone().unwrap_or_default().to_verification()),
+            uses: self.uses.as_ref().map(Uses::to_verification),
             token_program_version: TokenProgramVersion::Original,
             token_standard: Some(TokenStandard::NonFungible),
         }
     }
+
+    /// Convert the CompressedNftAsset to MetadataArgsV2 to match the LeafSchema::V2 assets.
+    pub fn to_metadata_args_v2(&self) -> MetadataArgsV2 {
+        MetadataArgsV2 {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            primary_sale_happened: self.primary_sale_happened,
+            is_mutable: self.is_mutable,
+            token_standard: Some(TokenStandard::NonFungible),
+            creators: self.creators.iter().map(Creator::to_verification).collect(),
+            collection: self.collection.as_ref().map(|collection| collection.key),
+        }
+    }
+}
+
+/// Returns the message signed by the holding attestation authority
+/// to attest the slot the given asset was last transferred to its owner at
+pub fn get_holding_attestation_message(
+    asset_id: &Pubkey,
+    leaf_owner: &Pubkey,
+    last_transfer_slot: u64
+) -> Vec<u8> {
+    [asset_id.as_ref(), leaf_owner.as_ref(), &last_transfer_slot.to_le_bytes()].concat()
+}
+
+/// Returns the message signed by the leaf hash verifier
+/// to attest the leaf hash of the given asset held by the given owner and verified in the given collection
+pub fn get_leaf_hash_attestation_message(
+    asset_id: &Pubkey,
+    leaf_owner: &Pubkey,
+    leaf_hash: &[u8; 32],
+    collection: &Pubkey
+) -> Vec<u8> {
+    [asset_id.as_ref(), leaf_owner.as_ref(), leaf_hash.as_ref(), collection.as_ref()].concat()
+}
+
+/// Asserts the leaf hash supplied with the given compressed nft asset is attested by RegistrarExtension.config.leaf_hash_verifier
+/// The attestation is an ed25519 program instruction preceding the current instruction
+pub fn assert_leaf_hash_attested(
+    registrar_extension: &RegistrarExtension,
+    instructions_info: &AccountInfo,
+    asset_id: &Pubkey,
+    params: &CompressedNftAsset
+) -> Result<()> {
+    let leaf_hash = params.leaf_hash.as_ref().ok_or(NftVoterError::InvalidLeafHashData)?;
+    let collection = params.collection.as_ref().ok_or(NftVoterError::MissingMetadataCollection)?;
+
+    require!(
+        registrar_extension.config.is_leaf_hash_verification_enabled(),
+        NftVoterError::LeafHashVerificationNotAllowed
+    );
+
+    assert_ed25519_signature(
+        instructions_info,
+        &registrar_extension.config.leaf_hash_verifier,
+        &get_leaf_hash_attestation_message(asset_id, &params.leaf_owner, leaf_hash, &collection.key)
+    )
+}
+
+/// Asserts the given tree was created by one of the tree authorities allowed by the Registrar
+/// Public trees are rejected because anyone can mint into them
+pub fn assert_tree_authority_allowed(
+    registrar_extension: &RegistrarExtension,
+    tree_account: &AccountInfo,
+    tree_config_info: &AccountInfo
+) -> Result<()> {
+    let bubblegum_program_id = registrar_extension.config.get_bubblegum_program_id();
+
+    let tree_config_address = Pubkey::find_program_address(
+        &[tree_account.key.as_ref()],
+        &bubblegum_program_id
+    ).0;
+
+    require_keys_eq!(tree_config_address, *tree_config_info.key, NftVoterError::InvalidTreeConfig);
+
+    // The owner is checked against the Bubblegum program of the Registrar which can be overridden
+    require_keys_eq!(bubblegum_program_id, *tree_config_info.owner, NftVoterError::InvalidTreeConfig);
+
+    let tree_config = TreeConfig::from_bytes(&tree_config_info.try_borrow_data()?).map_err(
+        |_| error!(NftVoterError::InvalidTreeConfig)
+    )?;
+
+    require!(
+        registrar_extension.config.is_tree_authority_allowed(&tree_config.tree_creator) &&
+            !tree_config.is_public,
+        NftVoterError::TreeAuthorityNotAllowed
+    );
+
+    Ok(())
+}
+
+/// Asserts the given root is still in the changelog of the tree and it's at most max_root_age changes old
+/// The offending root is logged to let the clients refetch the proof instead of hitting an opaque compression error
+///
+/// A root missing from a full changelog buffer was evicted by the changes made to the tree since the proof was fetched
+/// (ex. a high mint rate during voting) and it's rejected with TreeBufferChurn instead of StaleTreeRoot
+/// because it's caused by the tree activity and not by the client or the plugin
+pub fn assert_tree_root_fresh(
+    tree_account: &AccountInfo,
+    root: &[u8; 32],
+    max_root_age: u32
+) -> Result<()> {
+    let is_fresh = match get_merkle_tree_root_age(tree_account, root)? {
+        Some(root_age) => max_root_age == 0 || root_age <= max_root_age,
+        None => {
+            let (sequence_number, is_buffer_full) = get_merkle_tree_changelog_usage(tree_account)?;
+
+            if is_buffer_full {
+                msg!(
+                    "Tree root {} was evicted from the changelog buffer by the tree changes since the proof was fetched (tree sequence number: {})",
+                    Hash::new_from_array(*root),
+                    sequence_number
+                );
+                msg!(
+                    "The tree changes faster than its buffer size allows. Refetch the proof and submit it in the same slot or retry once the tree activity slows down"
+                );
+                return err!(NftVoterError::TreeBufferChurn);
+            }
+
+            false
+        }
+    };
+
+    if !is_fresh {
+        msg!("Stale tree root: {}", Hash::new_from_array(*root));
+        return err!(NftVoterError::StaleTreeRoot);
+    }
+
+    Ok(())
+}
+
+/// Asserts the leaf schema of the given compressed nft asset matches the version of its tree
+pub fn assert_leaf_schema_valid(tree_account: &AccountInfo, params: &CompressedNftAsset) -> Result<()> {
+    require_eq!(
+        params.leaf_schema.version(),
+        get_merkle_tree_leaf_schema_version(tree_account)?,
+        NftVoterError::LeafSchemaVersionMismatch
+    );
+
+    // V2 metadata has no uses and edition_nonce and they wouldn't be verified by the leaf
+    if let LeafSchema::V2 { .. } = params.leaf_schema {
+        require!(
+            params.uses.is_none() && params.edition_nonce.is_none(),
+            NftVoterError::InvalidLeafSchemaData
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the leaf node of the given compressed nft asset
+/// The attested leaf_hash is returned as is when it's supplied
+pub fn get_compressed_nft_leaf_node(asset_id: &Pubkey, params: &CompressedNftAsset) -> [u8; 32] {
+    if let Some(leaf_hash) = params.leaf_hash {
+        return leaf_hash;
+    }
+
+    let data_hash = match (params.data_hash, &params.leaf_schema) {
+        (Some(data_hash), _) => data_hash,
+        (None, LeafSchema::V1) => hash_metadata(&params.to_metadata_args()),
+        (None, LeafSchema::V2 { .. }) => hash_metadata_v2(&params.to_metadata_args_v2()),
+    };
+
+    let creator_hash = match params.creator_hash {
+        Some(creator_hash) => creator_hash,
+        None => {
+            let creators = params.creators
+                .iter()
+                .map(Creator::to_verification)
+                .collect::<Vec<_>>();
+            hash_creators(&creators)
+        }
+    };
+
+    match &params.leaf_schema {
+        LeafSchema::V1 =>
+            get_leaf_node(
+                asset_id,
+                &params.leaf_owner,
+                &params.leaf_delegate,
+                params.nonce,
+                &data_hash,
+                &creator_hash
+            ),
+        LeafSchema::V2 { asset_data_hash, flags } =>
+            get_leaf_node_v2(
+                asset_id,
+                &params.leaf_owner,
+                &params.leaf_delegate,
+                params.nonce,
+                &data_hash,
+                &creator_hash,
+                &hash_collection(params.collection.as_ref().map(|collection| &collection.key)),
+                asset_data_hash,
+                *flags
+            ),
+    }
 }
 
 /// Verify the given compressed nft asset with the given proofs.
+/// If the proof is supplied as instruction data (params.proof_nodes) then the leaf is verified
+/// without CPI to spl-account-compression. The proof can be taken against any root still in the changelog
+/// of the tree and it's fast-forwarded to the current root like spl-account-compression verify_leaf does
+/// LeafSchema::V2 assets of mpl-account-compression trees can only be verified using proof_nodes
 pub fn verify_compressed_nft<'info>(
     tree_account: &AccountInfo<'info>,
     asset_id: &Pubkey,
@@ -138,33 +492,42 @@ pub fn verify_compressed_nft<'info>(
     compression_program: &AccountInfo<'info>
 ) -> Result<()> {
     let root = &params.root;
-    let leaf_owner = &params.leaf_owner;
-    let leaf_delegate = &params.leaf_delegate;
-    let nonce = params.nonce;
     let index = params.index;
+    let leaf = get_compressed_nft_leaf_node(asset_id, params);
+
+    if let Some(proof_nodes) = &params.proof_nodes {
+        require!(proofs.is_empty(), NftVoterError::InvalidProofData);
+        require!(
+            proof_nodes.len() == (get_merkle_tree_max_depth(tree_account)? as usize),
+            NftVoterError::InvalidProofData
+        );
 
-    let mut creators = vec![];
-    for creator in params.creators.clone().iter() {
-        creators.push(creator.to_bubblegum());
+        // The leaf must be unchanged since the root and the fast-forwarded proof must lead to the current root
+        let mut proof_nodes = proof_nodes.clone();
+
+        require!(
+            fast_forward_merkle_proof(tree_account, root, &leaf, index, &mut proof_nodes)? &&
+                verify_merkle_proof(
+                    get_merkle_tree_current_root(tree_account)?,
+                    leaf,
+                    &proof_nodes,
+                    index
+                ),
+            NftVoterError::NftFailedVerification
+        );
+
+        return Ok(());
     }
 
-    let metadata = params.to_metadata_args();
-    let data_hash = hash_metadata(&metadata).unwrap();
-    let creator_hash = hash_creators(&creators).unwrap();
+    // mpl-account-compression trees can't be verified by spl-account-compression
+    require!(params.leaf_schema == LeafSchema::V1, NftVoterError::InvalidProofData);
 
-    let leaf = LeafSchema::new_v0(
-        *asset_id,
-        *leaf_owner,
-        *leaf_delegate,
-        nonce,
-        data_hash,
-        creator_hash
-    );
+    log_verify_leaf_context(tree_account.key, asset_id, root, proofs.len());
 
     let cpi_ctx = CpiContext::new(compression_program.clone(), VerifyLeaf {
         merkle_tree: tree_account.clone(),
     }).with_remaining_accounts(proofs);
-    spl_account_compression::cpi::verify_leaf(cpi_ctx, *root, leaf.to_node(), index)?;
+    spl_account_compression::cpi::verify_leaf(cpi_ctx, *root, leaf, index)?;
 
     Ok(())
 }
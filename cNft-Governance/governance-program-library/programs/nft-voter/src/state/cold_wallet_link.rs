@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ error::NftVoterError, id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// The longest period a cold wallet can authorize a hot wallet for (90 days)
+pub const MAX_COLD_WALLET_LINK_DURATION: i64 = 90 * 24 * 60 * 60;
+
+/// Authorization of a hot wallet to vote with the compressed NFTs held by a cold wallet
+/// The link is created using an ed25519 signature of the cold wallet and the hardware wallet
+/// doesn't have to sign any transaction until the link expires
+/// The PDA of the link is ["cold-wallet-link",registrar,cold_wallet]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct ColdWalletLink {
+    /// The Registrar the link belongs to
+    pub registrar: Pubkey,
+
+    /// The cold wallet which holds the compressed NFTs
+    pub cold_wallet: Pubkey,
+
+    /// The hot wallet authorized to vote with the compressed NFTs of the cold wallet
+    pub hot_wallet: Pubkey,
+
+    /// The unix timestamp the link expires at
+    /// Note: It's the expiry signed by the cold wallet and it's kept when the link is revoked
+    pub expires_at: i64,
+
+    /// Whether the link was revoked before its expiry
+    pub revoked: bool,
+}
+
+impl ColdWalletLink {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 8 + 1
+    }
+
+    /// Asserts the link authorizes the given hot wallet to vote with the compressed NFTs of the given cold wallet
+    pub fn assert_can_vote_for(
+        &self,
+        registrar: &Pubkey,
+        cold_wallet: &Pubkey,
+        hot_wallet: &Pubkey,
+        unix_timestamp: i64
+    ) -> Result<()> {
+        require!(
+            self.registrar == *registrar &&
+                self.cold_wallet == *cold_wallet &&
+                self.hot_wallet == *hot_wallet,
+            NftVoterError::InvalidColdWalletLink
+        );
+
+        require!(
+            !self.revoked && unix_timestamp < self.expires_at,
+            NftVoterError::ColdWalletLinkExpired
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the message signed by the cold wallet to authorize the hot wallet until the given expiry
+pub fn get_cold_wallet_link_message(
+    registrar: &Pubkey,
+    hot_wallet: &Pubkey,
+    expires_at: i64
+) -> Vec<u8> {
+    [
+        b"cold-wallet-link".as_ref(),
+        registrar.as_ref(),
+        hot_wallet.as_ref(),
+        &expires_at.to_le_bytes(),
+    ].concat()
+}
+
+/// Returns ColdWalletLink PDA seeds
+pub fn get_cold_wallet_link_seeds<'a>(registrar: &'a Pubkey, cold_wallet: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"cold-wallet-link", registrar.as_ref(), cold_wallet.as_ref()]
+}
+
+/// Returns ColdWalletLink PDA address
+pub fn get_cold_wallet_link_address(registrar: &Pubkey, cold_wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_cold_wallet_link_seeds(registrar, cold_wallet), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_cold_wallet_link() -> ColdWalletLink {
+        ColdWalletLink {
+            registrar: Pubkey::new_unique(),
+            cold_wallet: Pubkey::new_unique(),
+            hot_wallet: Pubkey::new_unique(),
+            expires_at: 100,
+            revoked: false,
+        }
+    }
+
+    #[test]
+    fn test_assert_can_vote_for() {
+        // Arrange
+        let mut link = create_test_cold_wallet_link();
+
+        // Act + Assert
+        assert!(
+            link.assert_can_vote_for(&link.registrar, &link.cold_wallet, &link.hot_wallet, 99).is_ok()
+        );
+        assert!(
+            link.assert_can_vote_for(&link.registrar, &link.cold_wallet, &link.hot_wallet, 100).is_err()
+        );
+        assert!(
+            link
+                .assert_can_vote_for(&link.registrar, &link.cold_wallet, &Pubkey::new_unique(), 99)
+                .is_err()
+        );
+
+        link.revoked = true;
+        assert!(
+            link.assert_can_vote_for(&link.registrar, &link.cold_wallet, &link.hot_wallet, 99).is_err()
+        );
+    }
+}
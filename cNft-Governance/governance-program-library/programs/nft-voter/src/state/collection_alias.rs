@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ error::NftVoterError, id, state::MAX_BASIS_POINTS, tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE } };
+
+/// Alias of a collection migrated to (merged into) a collection configured on the Registrar
+/// The compressed NFTs of the aliased collection count under the config of the target collection
+/// and they keep their governance power while the holders migrate the assets to the new collection
+/// The PDA of the alias is ["collection-alias",registrar,collection]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct CollectionAlias {
+    /// The Registrar the alias belongs to
+    pub registrar: Pubkey,
+
+    /// The previous collection which isn't configured on the Registrar
+    pub collection: Pubkey,
+
+    /// The configured collection the assets of the previous collection count under
+    pub target_collection: Pubkey,
+
+    /// The weight of the assets of the previous collection expressed in basis points of the target collection weight
+    /// Ex: 5000 makes the assets of the previous collection count half of the assets of the target collection
+    pub weight_bps: u16,
+}
+
+impl CollectionAlias {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 2
+    }
+
+    /// Returns the weight of an asset of the previous collection for the given weight of the target collection asset
+    pub fn get_weight(&self, target_collection_weight: u64) -> u64 {
+        (((target_collection_weight as u128) * (self.weight_bps as u128)) /
+            (MAX_BASIS_POINTS as u128)) as u64
+    }
+}
+
+/// Returns CollectionAlias PDA seeds
+pub fn get_collection_alias_seeds<'a>(registrar: &'a Pubkey, collection: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"collection-alias", registrar.as_ref(), collection.as_ref()]
+}
+
+/// Returns CollectionAlias PDA address
+pub fn get_collection_alias_address(registrar: &Pubkey, collection: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_collection_alias_seeds(registrar, collection), &id()).0
+}
+
+/// Deserializes CollectionAlias account and checks it's the alias of the given collection for the given Registrar
+pub fn get_collection_alias_data_for_collection(
+    collection_alias_info: &AccountInfo,
+    registrar: &Pubkey,
+    collection: &Pubkey
+) -> Result<CollectionAlias> {
+    let collection_alias = get_anchor_account_data::<CollectionAlias>(collection_alias_info)?;
+
+    require!(
+        collection_alias.registrar == *registrar && collection_alias.collection == *collection,
+        NftVoterError::InvalidCollectionAlias
+    );
+
+    Ok(collection_alias)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_collection_alias() -> CollectionAlias {
+        CollectionAlias {
+            registrar: Pubkey::new_unique(),
+            collection: Pubkey::new_unique(),
+            target_collection: Pubkey::new_unique(),
+            weight_bps: 5000,
+        }
+    }
+
+    #[test]
+    fn test_get_weight() {
+        // Arrange
+        let mut collection_alias = create_test_collection_alias();
+
+        // Act + Assert
+        assert_eq!(collection_alias.get_weight(10), 5);
+
+        collection_alias.weight_bps = MAX_BASIS_POINTS;
+        assert_eq!(collection_alias.get_weight(10), 10);
+    }
+}
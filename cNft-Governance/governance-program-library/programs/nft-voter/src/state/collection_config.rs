@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
+/// The max number of collections which can be configured using configure_collections_batch
+pub const MAX_COLLECTIONS_PER_BATCH: usize = 10;
+
 /// Configuration of an NFT collection used for governance power
+/// The layout is the same as in the previous versions of the program
+/// and the extra collection settings are stored in the CollectionExtension of the RegistrarExtension
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Default)]
 pub struct CollectionConfig {
     /// The NFT collection used for governance
@@ -22,7 +27,30 @@ pub struct CollectionConfig {
 }
 
 impl CollectionConfig {
-    pub fn get_max_weight(&self) -> u64 {
-        (self.size as u64).checked_mul(self.weight).unwrap()
+    pub const fn get_space() -> usize {
+        32 + 4 + 8 + 8
+    }
+
+    /// Returns the config of the given collection with the given weight and size
+    pub fn new(collection: Pubkey, weight: u64, size: u32) -> Self {
+        CollectionConfig {
+            collection,
+            size,
+            weight,
+            reserved: [0; 8],
+        }
     }
 }
+
+/// Collection configuration supplied to configure_collections_batch and configure_registrar_template
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CollectionConfigArgs {
+    /// The NFT collection used for governance
+    pub collection: Pubkey,
+
+    /// Governance power weight of the collection
+    pub weight: u64,
+
+    /// The size of the NFT collection used to calculate max voter weight
+    pub size: u32,
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+use crate::error::NftVoterError;
+use crate::state::{ CollectionWeightStrategy, MAX_BASIS_POINTS };
+
+/// The max number of creator boosts which can be configured for a collection
+pub const MAX_CREATOR_BOOSTS: usize = 3;
+
+/// Extra weight of the NFTs of a collection created by the given creator
+/// It allows to weight artist-curated sub-series inside one collection
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct CreatorBoost {
+    /// The creator which must be a verified creator of the NFT for the boost to apply
+    /// Pubkey::default() means the entry is not used
+    pub creator: Pubkey,
+
+    /// The extra weight expressed in basis points of the collection weight
+    /// Ex: 5000 makes the NFT 1.5 times the collection weight
+    pub boost_bps: u16,
+}
+
+impl CreatorBoost {
+    pub const fn get_space() -> usize {
+        32 + 2
+    }
+}
+
+/// Collection settings which don't fit the original CollectionConfig layout
+/// They are stored in RegistrarExtension.collection_extensions and a collection without an extension
+/// has no creator boosts, never expires, uses the Linear weight strategy and isn't Uses limited
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct CollectionExtension {
+    /// The collection of the Registrar the settings are for
+    pub collection: Pubkey,
+
+    /// Extra weight of the NFTs with the given verified creators
+    /// If the NFT has several boosted creators then only the highest boost applies
+    pub creator_boosts: [CreatorBoost; MAX_CREATOR_BOOSTS],
+
+    /// The unix timestamp at which the collection expires and its NFTs no longer have governance power
+    /// 0 means the collection never expires
+    /// Expired collections can be removed from the Registrar using permissionless prune_expired_collections
+    pub expires_at: i64,
+
+    /// How the weight of the NFTs of the collection held by a single voter is aggregated
+    /// The strategy applies on top of the collection weight, creator boosts and collection_weight_mode
+    pub weight_strategy: CollectionWeightStrategy,
+
+    /// Whether the NFTs of the collection are minted with Metaplex Uses which limit the number of their votes
+    /// The Uses of compressed NFTs are only verified when the metadata is hashed on-chain
+    /// and hence such assets can't be supplied with precomputed metadata hashes or attested leaf hashes
+    pub uses_limited: bool,
+
+    /// Reserved for future collection settings
+    /// The extensions are stored back to back in RegistrarExtension and a new field would shift all of them
+    pub reserved: [u8; 32],
+}
+
+impl CollectionExtension {
+    pub const fn get_space() -> usize {
+        32 + CreatorBoost::get_space() * MAX_CREATOR_BOOSTS + 8 + CollectionWeightStrategy::get_space() + 1 + 32
+    }
+
+    /// Returns the extension of the given collection with the settings of a collection without extension
+    pub fn new(collection: Pubkey) -> Self {
+        CollectionExtension {
+            collection,
+            ..Default::default()
+        }
+    }
+
+    /// Returns true if the extension holds only the default settings and doesn't have to be stored
+    pub fn is_default(&self) -> bool {
+        *self == CollectionExtension::new(self.collection)
+    }
+
+    /// Returns true if the collection is expired at the given unix timestamp
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        self.expires_at > 0 && unix_timestamp >= self.expires_at
+    }
+
+    /// Returns the weight of an NFT of the collection with the highest creator boost
+    pub fn get_max_asset_weight(&self, weight: u64) -> Result<u64> {
+        get_boosted_weight(weight, self.get_max_boost_bps())
+    }
+
+    /// Returns the highest creator boost of the collection
+    pub fn get_max_boost_bps(&self) -> u16 {
+        self.creator_boosts
+            .iter()
+            .filter(|cb| cb.creator != Pubkey::default())
+            .map(|cb| cb.boost_bps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the weight of an NFT of the collection with the given verified creators
+    /// weight is the weight of the collection from its CollectionConfig
+    pub fn get_weight(&self, weight: u64, verified_creators: &[Pubkey]) -> Result<u64> {
+        let boost_bps = self.creator_boosts
+            .iter()
+            .filter(|cb| cb.creator != Pubkey::default() && verified_creators.contains(&cb.creator))
+            .map(|cb| cb.boost_bps)
+            .max()
+            .unwrap_or(0);
+
+        get_boosted_weight(weight, boost_bps)
+    }
+}
+
+fn get_boosted_weight(weight: u64, boost_bps: u16) -> Result<u64> {
+    let boost = ((weight as u128) * (boost_bps as u128)) / (MAX_BASIS_POINTS as u128);
+    let boost = u64::try_from(boost).map_err(|_| NftVoterError::CastError)?;
+
+    weight.checked_add(boost).ok_or_else(|| NftVoterError::ArithmeticOverflow.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_expired() {
+        // Arrange
+        let collection_extension = CollectionExtension {
+            expires_at: 100,
+            ..Default::default()
+        };
+
+        // Act
+        let is_expired_before = collection_extension.is_expired(99);
+        let is_expired_at = collection_extension.is_expired(100);
+
+        // Assert
+        assert!(!is_expired_before);
+        assert!(is_expired_at);
+    }
+
+    #[test]
+    fn test_get_weight_with_overflow_error() {
+        // Arrange
+        let creator = Pubkey::new_unique();
+        let mut collection_extension = CollectionExtension::default();
+        collection_extension.creator_boosts[0] = CreatorBoost {
+            creator,
+            boost_bps: 1,
+        };
+
+        // Act
+        let err = collection_extension.get_weight(u64::MAX, &[creator]).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::ArithmeticOverflow.into());
+        assert_eq!(collection_extension.get_weight(u64::MAX, &[]).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_is_expired_without_expiry() {
+        // Arrange
+        let collection_extension = CollectionExtension::new(Pubkey::new_unique());
+
+        // Act
+        let is_expired = collection_extension.is_expired(i64::MAX);
+
+        // Assert
+        assert!(!is_expired);
+        assert!(collection_extension.is_default());
+    }
+}
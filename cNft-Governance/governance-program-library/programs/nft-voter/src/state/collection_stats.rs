@@ -0,0 +1,191 @@
+use std::convert::TryInto;
+
+use anchor_lang::prelude::*;
+use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
+use solana_program::hash::hashv;
+use solana_program::program_pack::IsInitialized;
+
+use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
+
+use crate::{ error::NftVoterError, id };
+
+/// The number of registers of the unique voters counter
+pub const COLLECTION_STATS_VOTER_REGISTERS: usize = 32;
+
+/// Vote participation statistics of the NFTs of the given collection
+/// The PDA of the record is ["collection-stats",registrar,collection]
+/// It's updated by cast_nft_vote when RegistrarExtension.config.track_collection_stats is set
+/// and gives DAOs engagement metrics per collection without an indexer
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CollectionStats {
+    /// CollectionStats discriminator sha256("account:CollectionStats")[..8]
+    /// Note: The discriminator is used explicitly because CollectionStats
+    /// are created and consumed dynamically using remaining_accounts
+    pub account_discriminator: [u8; 8],
+
+    /// The Registrar the statistics are for
+    pub registrar: Pubkey,
+
+    /// The collection the statistics are for
+    pub collection: Pubkey,
+
+    /// Total number of votes cast by the NFTs of the collection across all Proposals
+    pub total_votes: u64,
+
+    /// The slot the last vote with an NFT of the collection was cast at
+    pub last_vote_slot: u64,
+
+    /// HyperLogLog registers approximating the number of unique voters of the collection
+    /// Use estimate_unique_voters() to read the approximation
+    pub voter_registers: [u8; COLLECTION_STATS_VOTER_REGISTERS],
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 8],
+}
+
+impl CollectionStats {
+    /// sha256("account:CollectionStats")[..8]
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [166, 9, 59, 63, 107, 224, 74, 90];
+
+    pub fn new(registrar: Pubkey, collection: Pubkey) -> Self {
+        Self {
+            account_discriminator: CollectionStats::ACCOUNT_DISCRIMINATOR,
+            registrar,
+            collection,
+            total_votes: 0,
+            last_vote_slot: 0,
+            voter_registers: [0; COLLECTION_STATS_VOTER_REGISTERS],
+            reserved: [0; 8],
+        }
+    }
+
+    /// Records a vote cast by the given voter with an NFT of the collection at the given slot
+    pub fn record_vote(&mut self, voter: &Pubkey, slot: u64) -> Result<()> {
+        self.total_votes = self.total_votes.checked_add(1).ok_or(NftVoterError::ArithmeticOverflow)?;
+        self.last_vote_slot = slot;
+
+        // The first byte of the hash selects the register and the rank is the position of the first set bit of the rest
+        let hash = hashv(&[voter.as_ref()]).to_bytes();
+        let register = (hash[0] as usize) % COLLECTION_STATS_VOTER_REGISTERS;
+        let rank = (u64::from_le_bytes(hash[1..9].try_into().unwrap()).leading_zeros() + 1) as u8;
+
+        self.voter_registers[register] = self.voter_registers[register].max(rank);
+
+        Ok(())
+    }
+
+    /// Returns the approximate number of unique voters of the collection
+    /// The standard error of the approximation is ~18%
+    /// Note: It uses floating point arithmetic and it's intended for clients and not on-chain use
+    pub fn estimate_unique_voters(&self) -> u64 {
+        let registers = COLLECTION_STATS_VOTER_REGISTERS as f64;
+
+        let sum: f64 = self.voter_registers
+            .iter()
+            .map(|rank| (2f64).powi(-(*rank as i32)))
+            .sum();
+        let estimate = (0.697 * registers * registers) / sum;
+
+        // Use linear counting for small cardinalities
+        let empty_registers = self.voter_registers
+            .iter()
+            .filter(|rank| **rank == 0)
+            .count();
+
+        if estimate <= 2.5 * registers && empty_registers > 0 {
+            return (registers * (registers / (empty_registers as f64)).ln()).round() as u64;
+        }
+
+        estimate.round() as u64
+    }
+}
+
+impl AccountMaxSize for CollectionStats {}
+
+impl IsInitialized for CollectionStats {
+    fn is_initialized(&self) -> bool {
+        self.account_discriminator == CollectionStats::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+/// Returns CollectionStats PDA seeds
+pub fn get_collection_stats_seeds<'a>(
+    registrar: &'a Pubkey,
+    collection: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"collection-stats", registrar.as_ref(), collection.as_ref()]
+}
+
+/// Returns CollectionStats PDA address
+pub fn get_collection_stats_address(registrar: &Pubkey, collection: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_collection_stats_seeds(registrar, collection), &id()).0
+}
+
+/// Deserializes account and checks owner program
+pub fn get_collection_stats_data(collection_stats_info: &AccountInfo) -> Result<CollectionStats> {
+    Ok(get_account_data::<CollectionStats>(&id(), collection_stats_info)?)
+}
+
+pub fn get_collection_stats_data_for_registrar_and_collection(
+    collection_stats_info: &AccountInfo,
+    registrar: &Pubkey,
+    collection: &Pubkey
+) -> Result<CollectionStats> {
+    let collection_stats = get_collection_stats_data(collection_stats_info)?;
+
+    require!(
+        collection_stats.registrar == *registrar && collection_stats.collection == *collection,
+        NftVoterError::InvalidCollectionStats
+    );
+
+    Ok(collection_stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_estimate_unique_voters_with_repeated_voter() {
+        // Arrange
+        let mut collection_stats = CollectionStats::new(Pubkey::new_unique(), Pubkey::new_unique());
+        let voter = Pubkey::new_unique();
+
+        // Act
+        collection_stats.record_vote(&voter, 10).unwrap();
+        collection_stats.record_vote(&voter, 20).unwrap();
+
+        // Assert
+        assert_eq!(collection_stats.total_votes, 2);
+        assert_eq!(collection_stats.last_vote_slot, 20);
+        assert_eq!(collection_stats.estimate_unique_voters(), 1);
+    }
+
+    #[test]
+    fn test_estimate_unique_voters() {
+        // Arrange
+        let mut collection_stats = CollectionStats::new(Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Act
+        for slot in 0..100 {
+            collection_stats.record_vote(&Pubkey::new_unique(), slot).unwrap();
+        }
+
+        // Assert
+        let unique_voters = collection_stats.estimate_unique_voters();
+        assert!(unique_voters >= 60 && unique_voters <= 140, "estimate: {}", unique_voters);
+    }
+
+    #[test]
+    fn test_record_vote_with_overflow_error() {
+        // Arrange
+        let mut collection_stats = CollectionStats::new(Pubkey::new_unique(), Pubkey::new_unique());
+        collection_stats.total_votes = u64::MAX;
+
+        // Act
+        let err = collection_stats.record_vote(&Pubkey::new_unique(), 10).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::ArithmeticOverflow.into());
+    }
+}
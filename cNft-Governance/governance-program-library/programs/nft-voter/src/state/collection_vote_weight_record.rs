@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
+use solana_program::program_pack::IsInitialized;
+
+use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
+
+use crate::{ error::NftVoterError, id };
+
+/// Accumulator of the vote weight cast by NFTs of the given collection on the Proposal
+/// The PDA of the record is ["collection-vote-weight-record",registrar,proposal,collection]
+/// It's used to cap the total weight a single collection can contribute to a Proposal
+/// when RegistrarExtension.config.max_collection_weight_per_proposal is set
+///
+/// The weight each NFT contributed is stored in its NftVoteRecord and it's released by relinquish_nft_vote
+/// so a relinquished vote doesn't keep consuming the cap of the collection
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CollectionVoteWeightRecord {
+    /// CollectionVoteWeightRecord discriminator sha256("account:CollectionVoteWeightRecord")[..8]
+    /// Note: The discriminator is used explicitly because CollectionVoteWeightRecords
+    /// are created and consumed dynamically using remaining_accounts
+    pub account_discriminator: [u8; 8],
+
+    /// The Registrar the weight was cast with
+    pub registrar: Pubkey,
+
+    /// Proposal which was voted on
+    pub proposal: Pubkey,
+
+    /// The collection the accumulated weight is for
+    pub collection: Pubkey,
+
+    /// Total weight cast by the NFTs of the collection on the Proposal
+    pub voter_weight: u64,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 8],
+}
+
+impl CollectionVoteWeightRecord {
+    /// sha256("account:CollectionVoteWeightRecord")[..8]
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [15, 253, 134, 39, 164, 141, 155, 231];
+
+    pub fn new(registrar: Pubkey, proposal: Pubkey, collection: Pubkey) -> Self {
+        Self {
+            account_discriminator: CollectionVoteWeightRecord::ACCOUNT_DISCRIMINATOR,
+            registrar,
+            proposal,
+            collection,
+            voter_weight: 0,
+            reserved: [0; 8],
+        }
+    }
+
+    /// Releases the weight cast by a relinquished NFT vote
+    /// The weight saturates at 0 instead of failing the relinquish
+    pub fn release_voter_weight(&mut self, voter_weight: u64) {
+        self.voter_weight = self.voter_weight.saturating_sub(voter_weight);
+    }
+}
+
+impl AccountMaxSize for CollectionVoteWeightRecord {}
+
+impl IsInitialized for CollectionVoteWeightRecord {
+    fn is_initialized(&self) -> bool {
+        self.account_discriminator == CollectionVoteWeightRecord::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+/// Returns CollectionVoteWeightRecord PDA seeds
+pub fn get_collection_vote_weight_record_seeds<'a>(
+    registrar: &'a Pubkey,
+    proposal: &'a Pubkey,
+    collection: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [b"collection-vote-weight-record", registrar.as_ref(), proposal.as_ref(), collection.as_ref()]
+}
+
+/// Returns CollectionVoteWeightRecord PDA address
+pub fn get_collection_vote_weight_record_address(
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    collection: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_collection_vote_weight_record_seeds(registrar, proposal, collection),
+        &id()
+    ).0
+}
+
+/// Deserializes account and checks owner program
+pub fn get_collection_vote_weight_record_data(
+    collection_vote_weight_record_info: &AccountInfo
+) -> Result<CollectionVoteWeightRecord> {
+    Ok(get_account_data::<CollectionVoteWeightRecord>(&id(), collection_vote_weight_record_info)?)
+}
+
+pub fn get_collection_vote_weight_record_data_for_registrar_proposal_and_collection(
+    collection_vote_weight_record_info: &AccountInfo,
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    collection: &Pubkey
+) -> Result<CollectionVoteWeightRecord> {
+    let collection_vote_weight_record = get_collection_vote_weight_record_data(
+        collection_vote_weight_record_info
+    )?;
+
+    require!(
+        collection_vote_weight_record.registrar == *registrar &&
+            collection_vote_weight_record.proposal == *proposal &&
+            collection_vote_weight_record.collection == *collection,
+        NftVoterError::InvalidCollectionVoteWeightRecord
+    );
+
+    Ok(collection_vote_weight_record)
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use solana_program::{ hash::hash, instruction::{ AccountMeta, Instruction } };
+
+use crate::{ id, state::VoterWeightAction };
+
+/// Name of the instruction the consumer plugin must implement to accept the weight exported by write_external_voter_weight
+/// The instruction is invoked with the Anchor discriminator sha256("global:write_voter_weight")[..8]
+/// followed by ExternalVoterWeight and the accounts:
+///   0. `[writable]` VoterWeightRecord of the consumer plugin
+///   1. `[]` VoterWeightRecord of this plugin the weight is exported from
+///   2. `[signer]` Weight export authority PDA ["weight-export-authority",registrar] of this plugin
+///   3. `[]` Registrar of this plugin
+pub const WRITE_VOTER_WEIGHT_IX_NAME: &str = "write_voter_weight";
+
+/// The voter weight exported to the consumer plugin
+/// The consumer plugin consents to the export by verifying the weight export authority signer
+/// and it decides how the weight is combined with the weight it computes itself
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ExternalVoterWeight {
+    /// The voter weight computed by this plugin
+    pub voter_weight: u64,
+
+    /// The slot the weight expires at
+    pub voter_weight_expiry: Option<u64>,
+
+    /// The governance action the weight was computed for
+    pub weight_action: Option<VoterWeightAction>,
+
+    /// The target of the governance action the weight was computed for
+    pub weight_action_target: Option<Pubkey>,
+}
+
+/// Returns the Anchor discriminator of the write_voter_weight instruction of the consumer plugin
+pub fn get_write_voter_weight_discriminator() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(
+        &hash(format!("global:{}", WRITE_VOTER_WEIGHT_IX_NAME).as_bytes()).to_bytes()[..8]
+    );
+    discriminator
+}
+
+/// Returns the write_voter_weight instruction of the consumer plugin
+pub fn get_write_voter_weight_instruction(
+    consumer_program_id: &Pubkey,
+    external_voter_weight_record: &Pubkey,
+    voter_weight_record: &Pubkey,
+    registrar: &Pubkey,
+    external_voter_weight: &ExternalVoterWeight
+) -> Result<Instruction> {
+    let mut data = get_write_voter_weight_discriminator().to_vec();
+    data.extend(external_voter_weight.try_to_vec()?);
+
+    Ok(Instruction {
+        program_id: *consumer_program_id,
+        accounts: vec![
+            AccountMeta::new(*external_voter_weight_record, false),
+            AccountMeta::new_readonly(*voter_weight_record, false),
+            AccountMeta::new_readonly(get_weight_export_authority_address(registrar), true),
+            AccountMeta::new_readonly(*registrar, false)
+        ],
+        data,
+    })
+}
+
+/// Returns weight export authority PDA seeds
+pub fn get_weight_export_authority_seeds(registrar: &Pubkey) -> [&[u8]; 2] {
+    [b"weight-export-authority", registrar.as_ref()]
+}
+
+/// Returns weight export authority PDA address
+pub fn get_weight_export_authority_address(registrar: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_weight_export_authority_seeds(registrar), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_write_voter_weight_instruction() {
+        // Arrange
+        let registrar = Pubkey::new_unique();
+        let external_voter_weight = ExternalVoterWeight {
+            voter_weight: 10,
+            voter_weight_expiry: Some(100),
+            weight_action: Some(VoterWeightAction::CreateProposal),
+            weight_action_target: Some(Pubkey::new_unique()),
+        };
+
+        // Act
+        let instruction = get_write_voter_weight_instruction(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &registrar,
+            &external_voter_weight
+        ).unwrap();
+
+        // Assert
+        assert_eq!(instruction.data[..8], hash(b"global:write_voter_weight").to_bytes()[..8]);
+        assert_eq!(
+            ExternalVoterWeight::try_from_slice(&instruction.data[8..]).unwrap(),
+            external_voter_weight
+        );
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[2].is_signer);
+        assert_eq!(instruction.accounts[2].pubkey, get_weight_export_authority_address(&registrar));
+    }
+}
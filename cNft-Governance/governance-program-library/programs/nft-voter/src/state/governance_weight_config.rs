@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ id, state::MAX_BASIS_POINTS, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// The max weight multiplier which can be configured for a Governance (10x) expressed in basis points
+pub const MAX_WEIGHT_MULTIPLIER_BPS: u32 = 10 * (MAX_BASIS_POINTS as u32);
+
+/// Weight multiplier applied to the voter weight for actions targeting the given Governance
+/// It allows to amplify the voice of NFT holders on selected governances (ex. grants committee)
+/// while the standard weight is used elsewhere
+/// The PDA of the config is ["governance-weight-config",registrar,governance]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct GovernanceWeightConfig {
+    /// The Registrar the config belongs to
+    pub registrar: Pubkey,
+
+    /// The Governance the multiplier applies to
+    pub governance: Pubkey,
+
+    /// The weight multiplier expressed in basis points where 10_000 means 1x
+    pub weight_multiplier_bps: u32,
+}
+
+impl GovernanceWeightConfig {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 4
+    }
+
+    /// Applies the weight multiplier to the given voter weight
+    pub fn apply_weight_multiplier(&self, voter_weight: u64) -> u64 {
+        (((voter_weight as u128) * (self.weight_multiplier_bps as u128)) /
+            (MAX_BASIS_POINTS as u128)) as u64
+    }
+}
+
+/// Returns GovernanceWeightConfig PDA seeds
+pub fn get_governance_weight_config_seeds<'a>(
+    registrar: &'a Pubkey,
+    governance: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"governance-weight-config", registrar.as_ref(), governance.as_ref()]
+}
+
+/// Returns GovernanceWeightConfig PDA address
+pub fn get_governance_weight_config_address(registrar: &Pubkey, governance: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_governance_weight_config_seeds(registrar, governance), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply_weight_multiplier() {
+        // Arrange
+        let governance_weight_config = GovernanceWeightConfig {
+            registrar: Pubkey::default(),
+            governance: Pubkey::default(),
+            weight_multiplier_bps: 25_000,
+        };
+
+        // Act
+        let voter_weight = governance_weight_config.apply_weight_multiplier(3);
+
+        // Assert
+        assert_eq!(voter_weight, 7);
+    }
+}
@@ -1,6 +1,8 @@
 //! IDL only types which are required in IDL but not exported automatically by Anchor
 use anchor_lang::prelude::*;
 
+use crate::state::{ NftVoteKind, NftVoteRecordVersion };
+
 /// NftVoteRecord exported to IDL without account_discriminator
 /// TODO: Once we can support these accounts in Anchor via remaining_accounts then it should be possible to remove it
 #[account]
@@ -14,6 +16,76 @@ pub struct NftVoteRecord {
     /// The voter who casted this vote
     /// It's a Realm member pubkey corresponding to TokenOwnerRecord.governing_token_owner
     pub governing_token_owner: Pubkey,
+
+    /// Layout version of the record
+    pub version: NftVoteRecordVersion,
+
+    /// Kind of the spl-gov Vote the NFT was used for
+    pub vote_kind: NftVoteKind,
+}
+
+/// CollectionVoteWeightRecord exported to IDL without account_discriminator
+#[account]
+pub struct CollectionVoteWeightRecord {
+    /// The Registrar the weight was cast with
+    pub registrar: Pubkey,
+
+    /// Proposal which was voted on
+    pub proposal: Pubkey,
+
+    /// The collection the accumulated weight is for
+    pub collection: Pubkey,
+
+    /// Total weight cast by the NFTs of the collection on the Proposal
+    pub voter_weight: u64,
+}
+
+/// CollectionStats exported to IDL without account_discriminator
+#[account]
+pub struct CollectionStats {
+    /// The Registrar the statistics are for
+    pub registrar: Pubkey,
+
+    /// The collection the statistics are for
+    pub collection: Pubkey,
+
+    /// Total number of votes cast by the NFTs of the collection across all Proposals
+    pub total_votes: u64,
+
+    /// The slot the last vote with an NFT of the collection was cast at
+    pub last_vote_slot: u64,
+
+    /// HyperLogLog registers approximating the number of unique voters of the collection
+    pub voter_registers: [u8; 32],
+}
+
+/// VoterCheckpoint exported to IDL without account_discriminator
+#[account]
+pub struct VoterCheckpoint {
+    /// The Registrar the vote was cast through
+    pub registrar: Pubkey,
+
+    /// The Proposal the vote was cast on
+    pub proposal: Pubkey,
+
+    /// The voter who cast the vote
+    pub governing_token_owner: Pubkey,
+
+    /// The voter weight of the vote
+    pub voter_weight: u64,
+
+    /// The slot the voter weight was last recorded at
+    pub slot: u64,
+}
+
+/// AssetUsesRecord exported to IDL without account_discriminator
+#[account]
+pub struct AssetUsesRecord {
+    /// The compressed NFT the uses are tracked for
+    pub asset_id: Pubkey,
+
+    /// The number of uses consumed by the asset
+    pub used: u64,
 }
 
 /// NftActionTicket exported to IDL without account_discriminator
@@ -32,6 +104,16 @@ pub struct NftActionTicket {
     /// The weight for the NFT, which is configured in the registrar's collections.
     pub weight: u64,
 
-    /// The expiration time for the NFT, which is the created slot + 10.
+    /// The verified collection the NFT belongs to
+    pub collection: Pubkey,
+
+    /// The expiration slot of the ticket
     pub expiry: Option<u64>,
+
+    /// The unix timestamp the delegation (ColdWalletLink) the ticket was created with expires at
+    /// It's 0 when the asset is held by the governing_token_owner
+    pub delegation_expires_at: i64,
+
+    /// The slot the ticket was (re)created at
+    pub created_at_slot: u64,
 }
@@ -47,12 +47,13 @@ impl Default for MaxVoterWeightRecord {
 }
 
 impl MaxVoterWeightRecord {
-    pub fn get_space() -> usize {
+    pub const fn get_space() -> usize {
         DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 8 + 1 + 8 + 8
     }
 }
 
 /// Returns MaxVoterWeightRecord PDA seeds
+/// Note: The seeds are the same as used by the nft-voter plugin
 pub fn get_max_voter_weight_record_seeds<'a>(
     realm: &'a Pubkey,
     governing_token_mint: &'a Pubkey,
@@ -4,9 +4,33 @@ pub mod registrar;
 pub use collection_config::*;
 pub mod collection_config;
 
+pub use collection_extension::*;
+pub mod collection_extension;
+
+pub use weight_strategy::*;
+pub mod weight_strategy;
+
+pub use registrar_config::*;
+pub mod registrar_config;
+
+pub use registrar_extension::*;
+pub mod registrar_extension;
+
+pub use registrar_metrics::*;
+pub mod registrar_metrics;
+
+pub use registrar_template::*;
+pub mod registrar_template;
+
 pub use nft_vote_record::*;
 pub mod nft_vote_record;
 
+pub use collection_vote_weight_record::*;
+pub mod collection_vote_weight_record;
+
+pub use collection_stats::*;
+pub mod collection_stats;
+
 pub mod max_voter_weight_record;
 
 pub use voter_weight_record::*;
@@ -18,4 +42,88 @@ pub mod cnft_verification;
 pub use nft_action_ticket::*;
 pub mod nft_action_ticket;
 
+pub use proposal_creation_ticket::*;
+pub mod proposal_creation_ticket;
+
+pub use asset_uses_record::*;
+pub mod asset_uses_record;
+
+pub use tree_rate_limit_record::*;
+pub mod tree_rate_limit_record;
+
+pub use proof_attestation::*;
+pub mod proof_attestation;
+
+pub use governance_weight_config::*;
+pub mod governance_weight_config;
+
+pub use sub_registrar_config::*;
+pub mod sub_registrar_config;
+
+pub use voter_weight_detail::*;
+pub mod voter_weight_detail;
+
+pub use voter_freeze_record::*;
+pub mod voter_freeze_record;
+
+pub use cold_wallet_link::*;
+pub mod cold_wallet_link;
+
+pub use voter_rent_escrow::*;
+pub mod voter_rent_escrow;
+
+pub use voting_session::*;
+pub mod voting_session;
+
+pub use multi_proposal_vote::*;
+pub mod multi_proposal_vote;
+
+pub use voter_migration::*;
+pub mod voter_migration;
+
+pub use voter_checkpoint::*;
+pub mod voter_checkpoint;
+
+pub use voter_group::*;
+pub mod voter_group;
+
+pub use voter_group_membership::*;
+pub mod voter_group_membership;
+
+pub use ballot_tree::*;
+pub mod ballot_tree;
+
+pub use campaign::*;
+pub mod campaign;
+
+pub use weight_snapshot::*;
+pub mod weight_snapshot;
+
+pub use external_voter_weight::*;
+pub mod external_voter_weight;
+
+pub use weight_lease::*;
+pub mod weight_lease;
+
+pub use collection_alias::*;
+pub mod collection_alias;
+
+pub use voter_registration::*;
+pub mod voter_registration;
+
+pub use wallet_weight_override::*;
+pub mod wallet_weight_override;
+
+pub use voter_weight_adjustment::*;
+pub mod voter_weight_adjustment;
+
+pub use registrar_warnings::*;
+pub mod registrar_warnings;
+
+pub use voter_weight_return_data::*;
+pub mod voter_weight_return_data;
+
+pub use vote_receipt::*;
+pub mod vote_receipt;
+
 pub mod idl_types;
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ error::NftVoterError, id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// The max number of Proposals the NFTs can be voted on by a single cast_nft_vote_multi
+pub const MAX_MULTI_VOTE_PROPOSALS: usize = 5;
+
+/// Voter weight counted once by cast_nft_vote_multi for a batch of Proposals
+/// The NftVoteRecords of all the Proposals are created by cast_nft_vote_multi and the weight is moved
+/// to VoterWeightRecord for one Proposal at a time by select_multi_proposal_vote
+/// The PDA of the vote is ["multi-proposal-vote",registrar,governing_token_owner]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct MultiProposalVote {
+    /// The Registrar the vote belongs to
+    pub registrar: Pubkey,
+
+    /// The voter who cast the vote
+    pub governing_token_owner: Pubkey,
+
+    /// The voter weight of the NFTs counted for all the Proposals
+    pub voter_weight: u64,
+
+    /// The Proposals the weight wasn't selected for yet
+    pub proposals: Vec<Pubkey>,
+}
+
+impl MultiProposalVote {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 8 + 4 + PUBKEY_BYTES * MAX_MULTI_VOTE_PROPOSALS
+    }
+
+    /// Removes the given Proposal from the Proposals the weight wasn't selected for yet
+    pub fn take_proposal(&mut self, proposal: &Pubkey) -> Result<()> {
+        let proposal_idx = self.proposals
+            .iter()
+            .position(|p| p == proposal)
+            .ok_or(NftVoterError::InvalidMultiProposalVote)?;
+
+        self.proposals.remove(proposal_idx);
+
+        Ok(())
+    }
+}
+
+/// Returns MultiProposalVote PDA seeds
+pub fn get_multi_proposal_vote_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"multi-proposal-vote", registrar.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns MultiProposalVote PDA address
+pub fn get_multi_proposal_vote_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_multi_proposal_vote_seeds(registrar, governing_token_owner),
+        &id()
+    ).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_take_proposal() {
+        // Arrange
+        let proposal = Pubkey::new_unique();
+
+        let mut multi_proposal_vote = MultiProposalVote {
+            registrar: Pubkey::new_unique(),
+            governing_token_owner: Pubkey::new_unique(),
+            voter_weight: 10,
+            proposals: vec![Pubkey::new_unique(), proposal],
+        };
+
+        // Act
+        multi_proposal_vote.take_proposal(&proposal).unwrap();
+
+        // Assert
+        assert_eq!(multi_proposal_vote.proposals.len(), 1);
+        assert!(multi_proposal_vote.take_proposal(&proposal).is_err());
+    }
+}
@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::error::NftVoterError;
+use crate::state::{ CollectionWeightTally, Registrar, RegistrarExtension, VoterWeightAction };
+use crate::tools::accounts::close_nft_action_ticket_account;
 use crate::tools::anchor::DISCRIMINATOR_SIZE;
 use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
 use solana_program::program_pack::IsInitialized;
 use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
 
-pub const NFT_ACTION_TICKET_SIZE: usize = DISCRIMINATOR_SIZE + 32 + 32 + 32 + 8 + 1 + 8;
+pub const NFT_ACTION_TICKET_SIZE: usize = DISCRIMINATOR_SIZE + 32 + 32 + 32 + 8 + 32 + 1 + 8 + 8 + 8;
+
+/// The number of slots an NftActionTicket can be used for once it becomes consumable
+pub const NFT_ACTION_TICKET_VALIDITY_SLOTS: u64 = 10;
 
 /// NFT ticket indicating the given NFT can be considered as a voting power
 /// The PDA of the record is ["nft-{action}-ticket",registrar,owner,nft_mint]
@@ -26,9 +32,19 @@ pub struct NftActionTicket {
     /// The weight for the NFT, which is configured in the registrar's collections.
     pub weight: u64,
 
-    /// The expiration time for the NFT, which is the created slot + 10.
-    /// Is 10 a good number?
+    /// The verified collection the NFT belongs to
+    pub collection: Pubkey,
+
+    /// The expiration slot of the ticket
+    /// It's the created slot + RegistrarExtension.config.weight_record_min_age_slots + NFT_ACTION_TICKET_VALIDITY_SLOTS
     pub expiry: Option<u64>,
+
+    /// The unix timestamp the delegation (ColdWalletLink) the ticket was created with expires at
+    /// It's 0 when the asset is held by the governing_token_owner
+    pub delegation_expires_at: i64,
+
+    /// The slot the ticket was (re)created at
+    pub created_at_slot: u64,
 }
 
 impl NftActionTicket {
@@ -37,7 +53,10 @@ impl NftActionTicket {
         owner: Pubkey,
         nft_mint: Pubkey,
         weight: u64,
-        expiry: Option<u64>
+        collection: Pubkey,
+        expiry: Option<u64>,
+        delegation_expires_at: i64,
+        created_at_slot: u64
     ) -> Self {
         Self {
             account_discriminator: NftActionTicket::ACCOUNT_DISCRIMINATOR,
@@ -45,13 +64,42 @@ impl NftActionTicket {
             governing_token_owner: owner,
             nft_mint,
             weight,
+            collection,
             expiry,
+            delegation_expires_at,
+            created_at_slot,
         }
     }
 
     pub fn get_weight(&self) -> u64 {
         self.weight
     }
+
+    /// Asserts the ticket can be consumed at the given slot
+    /// The ticket must not be expired and it must be at least min_age_slots old
+    /// (RegistrarExtension.config.weight_record_min_age_slots) to prevent votes with assets borrowed within the same transaction
+    pub fn assert_consumable(&self, current_slot: u64, min_age_slots: u64) -> Result<()> {
+        let expiry = self.expiry.ok_or(NftVoterError::InvalidNftTicket)?;
+
+        require!(expiry >= current_slot, NftVoterError::NftTicketExpired);
+        require!(
+            current_slot >= self.created_at_slot.saturating_add(min_age_slots),
+            NftVoterError::NftTicketTooRecent
+        );
+
+        Ok(())
+    }
+
+    /// Asserts the delegation the ticket was created with hasn't expired
+    /// The ticket outlives the ColdWalletLink check done when the ticket was created and the check is repeated when it's used
+    pub fn assert_delegation_not_expired(&self, unix_timestamp: i64) -> Result<()> {
+        require!(
+            self.delegation_expires_at == 0 || unix_timestamp < self.delegation_expires_at,
+            NftVoterError::ColdWalletLinkExpired
+        );
+
+        Ok(())
+    }
 }
 
 impl NftActionTicket {
@@ -95,3 +143,66 @@ pub fn get_nft_action_ticket_address(
 pub fn get_nft_action_ticket_data(nft_vote_ticket_info: &AccountInfo) -> Result<NftActionTicket> {
     Ok(get_account_data::<NftActionTicket>(&crate::id(), nft_vote_ticket_info)?)
 }
+
+/// Consumes (disposes) the given NFT action tickets of the governing_token_owners for the given action
+/// and returns the total weight of the tickets with the (collection, weight) contribution of each ticket
+/// The rent of the disposed tickets is transferred to the beneficiary
+///
+/// Note: governing_token_owners is the voter alone unless the tickets of its VoterGroup members are aggregated
+pub fn consume_nft_action_tickets(
+    registrar: &Account<Registrar>,
+    registrar_extension: &RegistrarExtension,
+    governing_token_owners: &[Pubkey],
+    voter_weight_action: &VoterWeightAction,
+    nft_action_tickets: &[AccountInfo],
+    beneficiary: &AccountInfo
+) -> Result<(u64, Vec<(Pubkey, u64)>)> {
+    let mut voter_weight = 0u64;
+    let mut contributions = vec![];
+    let mut unique_nft_action_tickets = vec![];
+    let mut collection_weight_tally = CollectionWeightTally::default();
+
+    for nft_action_ticket in nft_action_tickets.iter() {
+        if unique_nft_action_tickets.contains(&nft_action_ticket.key) {
+            return Err(NftVoterError::DuplicatedNftDetected.into());
+        }
+
+        require!(nft_action_ticket.data_is_empty() == false, NftVoterError::NftFailedVerification);
+        require!(*nft_action_ticket.owner == crate::id(), NftVoterError::InvalidAccountOwner);
+
+        let data_bytes = nft_action_ticket.data.clone();
+        let data = NftActionTicket::try_from_slice(&data_bytes.borrow())?;
+
+        let ticket_type = format!("nft-{}-ticket", voter_weight_action).to_string();
+        let nft_action_ticket_address = get_nft_action_ticket_address(
+            &ticket_type,
+            &registrar.key(),
+            &data.governing_token_owner,
+            &data.nft_mint
+        ).0;
+
+        require!(
+            governing_token_owners.contains(&data.governing_token_owner) &&
+                nft_action_ticket_address == *nft_action_ticket.key,
+            NftVoterError::InvalidNftTicket
+        );
+        data.assert_consumable(Clock::get()?.slot, registrar_extension.config.weight_record_min_age_slots)?;
+        data.assert_delegation_not_expired(Clock::get()?.unix_timestamp)?;
+
+        close_nft_action_ticket_account(nft_action_ticket, beneficiary)?;
+        unique_nft_action_tickets.push(&nft_action_ticket.key);
+
+        let nft_weight = collection_weight_tally.add_asset(
+            registrar_extension,
+            &data.collection,
+            data.weight
+        );
+
+        voter_weight = voter_weight
+            .checked_add(nft_weight)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+        contributions.push((data.collection, nft_weight));
+    }
+
+    Ok((voter_weight, contributions))
+}
@@ -2,13 +2,85 @@ use anchor_lang::prelude::*;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::program_pack::IsInitialized;
 
-use spl_governance_tools::account::{get_account_data, AccountMaxSize};
+use spl_governance::state::vote_record::Vote;
+use spl_governance_tools::account::AccountMaxSize;
+
+use cnft_verification::{ get_cnft_vote_record_address, get_cnft_vote_record_seeds };
 
 use crate::{error::NftVoterError, id};
 
+/// Layout version of NftVoteRecord
+/// The version is stored in the first byte following the V1 fields which used to be reserved
+/// and hence records created before versioning was introduced are read as V1
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, BorshSchema)]
+pub enum NftVoteRecordVersion {
+    /// proposal, nft_mint and governing_token_owner
+    V1,
+
+    /// V1 + vote_kind
+    V2,
+
+    /// V2 + NftVoteRecordCollectionWeight stored after the record
+    /// It's used for the NFTs whose weight was counted in a CollectionVoteWeightRecord
+    V3,
+}
+
+impl Default for NftVoteRecordVersion {
+    fn default() -> Self {
+        NftVoteRecordVersion::V1
+    }
+}
+
+/// Kind of the spl-gov Vote the NFT was used for
+/// It's supplied by the voter to cast_nft_vote and cast_nft_vote_multi because the spl-gov Vote is cast after the plugin instruction
+/// and it's validated against the spl-gov VoteRecord when the vote is relinquished
+///
+/// Note: Unknown is the first variant because the kind is stored in the first byte of the previously reserved space
+/// and hence V1 records are read as Unknown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, BorshSchema)]
+pub enum NftVoteKind {
+    /// The kind of the vote wasn't recorded
+    Unknown,
+
+    /// Vote approving choices of the Proposal
+    Approve,
+
+    /// Vote rejecting the Proposal
+    Deny,
+
+    /// Vote abstaining from the Proposal
+    Abstain,
+
+    /// Vote vetoing the Proposal
+    Veto,
+}
+
+impl Default for NftVoteKind {
+    fn default() -> Self {
+        NftVoteKind::Unknown
+    }
+}
+
+impl From<&Vote> for NftVoteKind {
+    fn from(vote: &Vote) -> Self {
+        match vote {
+            Vote::Approve(_) => NftVoteKind::Approve,
+            Vote::Deny => NftVoteKind::Deny,
+            Vote::Abstain => NftVoteKind::Abstain,
+            Vote::Veto => NftVoteKind::Veto,
+        }
+    }
+}
+
 /// Vote record indicating the given NFT voted on the Proposal
-/// The PDA of the record is ["nft-vote-record",proposal,nft_mint]
+/// The PDA of the record is ["nft-vote-record",registrar,proposal,nft_mint]
 /// It guarantees uniques and ensures the same NFT can't vote twice
+/// Note: Records created before the registrar was added to the seeds have ["nft-vote-record",proposal,nft_mint] PDAs
+/// and they can still be relinquished and reclaimed because these instructions don't derive the PDA
+/// cast_nft_vote checks the legacy PDA is empty for Proposals which could be voted on before the Registrar was migrated
+///
+/// The record is versioned (spl-governance AccountType style) and new layouts must only append fields
+/// after the existing ones so historical records can still be read using get_nft_vote_record_data
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct NftVoteRecord {
     /// NftVoteRecord discriminator sha256("account:NftVoteRecord")[..8]
@@ -28,17 +100,94 @@ pub struct NftVoteRecord {
     /// It's a Realm member pubkey corresponding to TokenOwnerRecord.governing_token_owner
     pub governing_token_owner: Pubkey,
 
+    /// Layout version of the record
+    pub version: NftVoteRecordVersion,
+
+    /// Kind of the spl-gov Vote the NFT was used for
+    /// It's Unknown for V1 records
+    pub vote_kind: NftVoteKind,
+
     /// Reserved for future upgrades
-    pub reserved: [u8; 8],
+    pub reserved: [u8; 6],
 }
 
 impl NftVoteRecord {
     /// sha256("account:NftVoteRecord")[..8]
     pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [137, 6, 55, 139, 251, 126, 254, 99];
+
+    /// The version of newly created records without NftVoteRecordCollectionWeight
+    pub const CURRENT_VERSION: NftVoteRecordVersion = NftVoteRecordVersion::V2;
+
+    pub fn new(
+        proposal: Pubkey,
+        nft_mint: Pubkey,
+        governing_token_owner: Pubkey,
+        vote_kind: NftVoteKind
+    ) -> Self {
+        Self {
+            account_discriminator: NftVoteRecord::ACCOUNT_DISCRIMINATOR,
+            proposal,
+            nft_mint,
+            governing_token_owner,
+            version: NftVoteRecord::CURRENT_VERSION,
+            vote_kind,
+            reserved: [0; 6],
+        }
+    }
+
+    /// Asserts the vote kind of the record matches the given spl-gov Vote
+    /// Records without the kind (V1 or Unknown) match any Vote
+    pub fn assert_vote_kind(&self, vote: &Vote) -> Result<()> {
+        require!(
+            self.vote_kind == NftVoteKind::Unknown || self.vote_kind == NftVoteKind::from(vote),
+            NftVoterError::NftVoteKindMismatch
+        );
+
+        Ok(())
+    }
 }
 
 impl AccountMaxSize for NftVoteRecord {}
 
+/// The weight an NFT contributed to the CollectionVoteWeightRecord of its collection
+/// It's stored after the fields of V3 NftVoteRecords so relinquish_nft_vote can release the weight
+/// Note: It's not a field of NftVoteRecord to keep the layout of V1 and V2 records readable
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct NftVoteRecordCollectionWeight {
+    /// The collection of the NFT
+    pub collection: Pubkey,
+
+    /// The weight added to CollectionVoteWeightRecord.voter_weight by the NFT
+    pub voter_weight: u64,
+}
+
+/// V3 NftVoteRecord followed by the weight the NFT contributed to its CollectionVoteWeightRecord
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct NftVoteRecordWithCollectionWeight {
+    pub nft_vote_record: NftVoteRecord,
+    pub collection_weight: NftVoteRecordCollectionWeight,
+}
+
+impl NftVoteRecordWithCollectionWeight {
+    pub fn new(
+        proposal: Pubkey,
+        nft_mint: Pubkey,
+        governing_token_owner: Pubkey,
+        vote_kind: NftVoteKind,
+        collection_weight: NftVoteRecordCollectionWeight
+    ) -> Self {
+        Self {
+            nft_vote_record: NftVoteRecord {
+                version: NftVoteRecordVersion::V3,
+                ..NftVoteRecord::new(proposal, nft_mint, governing_token_owner, vote_kind)
+            },
+            collection_weight,
+        }
+    }
+}
+
+impl AccountMaxSize for NftVoteRecordWithCollectionWeight {}
+
 impl IsInitialized for NftVoteRecord {
     fn is_initialized(&self) -> bool {
         self.account_discriminator == NftVoteRecord::ACCOUNT_DISCRIMINATOR
@@ -46,21 +195,98 @@ impl IsInitialized for NftVoteRecord {
 }
 
 /// Returns NftVoteRecord PDA seeds
-pub fn get_nft_vote_record_seeds<'a>(proposal: &'a Pubkey, nft_mint: &'a Pubkey) -> [&'a [u8]; 3] {
-    [b"nft-vote-record", proposal.as_ref(), nft_mint.as_ref()]
+pub fn get_nft_vote_record_seeds<'a>(
+    registrar: &'a Pubkey,
+    proposal: &'a Pubkey,
+    nft_mint: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    get_cnft_vote_record_seeds(registrar, proposal, nft_mint)
 }
 
 /// Returns NftVoteRecord PDA address
-pub fn get_nft_vote_record_address(proposal: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
-    Pubkey::find_program_address(&get_nft_vote_record_seeds(proposal, nft_mint), &id()).0
+pub fn get_nft_vote_record_address(
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    nft_mint: &Pubkey
+) -> Pubkey {
+    get_cnft_vote_record_address(&id(), registrar, proposal, nft_mint)
+}
+
+/// Returns the PDA address of the NftVoteRecords created before the registrar was added to the seeds
+/// The legacy PDA is ["nft-vote-record",proposal,nft_mint]
+pub fn get_legacy_nft_vote_record_address(proposal: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"nft-vote-record", proposal.as_ref(), nft_mint.as_ref()], &id()).0
+}
+
+/// Asserts the NFT hasn't voted on the Proposal with a legacy NftVoteRecord
+/// The legacy record must be empty or owned by the system program (never created or relinquished)
+pub fn assert_legacy_nft_vote_record_is_empty(
+    legacy_nft_vote_record_info: &AccountInfo,
+    proposal: &Pubkey,
+    nft_mint: &Pubkey
+) -> Result<()> {
+    require_keys_eq!(
+        get_legacy_nft_vote_record_address(proposal, nft_mint),
+        *legacy_nft_vote_record_info.key,
+        NftVoterError::InvalidLegacyNftVoteRecord
+    );
+
+    require!(
+        legacy_nft_vote_record_info.data_is_empty() ||
+            *legacy_nft_vote_record_info.owner == anchor_lang::system_program::ID,
+        NftVoterError::NftAlreadyVoted
+    );
+
+    Ok(())
 }
 
 /// Deserializes account and checks owner program
+/// The account data is read according to the stored version and records of older versions
+/// are upgraded in memory to the current layout
 pub fn get_nft_vote_record_data(nft_vote_record_info: &AccountInfo) -> Result<NftVoteRecord> {
-    Ok(get_account_data::<NftVoteRecord>(
-        &id(),
-        nft_vote_record_info,
-    )?)
+    require_keys_eq!(
+        *nft_vote_record_info.owner,
+        id(),
+        NftVoterError::InvalidNftVoteRecord
+    );
+
+    let data = nft_vote_record_info.try_borrow_data()?;
+
+    // Note: deserialize() is used instead of try_from_slice() to tolerate records of different lengths
+    let nft_vote_record = NftVoteRecord::deserialize(&mut &data[..])
+        .map_err(|_| NftVoterError::InvalidNftVoteRecord)?;
+
+    require!(
+        nft_vote_record.is_initialized(),
+        NftVoterError::InvalidNftVoteRecord
+    );
+
+    match nft_vote_record.version {
+        NftVoteRecordVersion::V1 | NftVoteRecordVersion::V2 | NftVoteRecordVersion::V3 => {
+            Ok(nft_vote_record)
+        }
+    }
+}
+
+/// Returns the weight the NFT of the given record contributed to the CollectionVoteWeightRecord of its collection
+/// The weight is only stored by V3 records and None is returned for the other versions
+pub fn get_nft_vote_record_collection_weight(
+    nft_vote_record_info: &AccountInfo,
+    nft_vote_record: &NftVoteRecord
+) -> Result<Option<NftVoteRecordCollectionWeight>> {
+    if nft_vote_record.version != NftVoteRecordVersion::V3 {
+        return Ok(None);
+    }
+
+    let data = nft_vote_record_info.try_borrow_data()?;
+    let offset = nft_vote_record.try_to_vec()?.len();
+
+    let collection_weight = data
+        .get(offset..)
+        .and_then(|mut data| NftVoteRecordCollectionWeight::deserialize(&mut data).ok())
+        .ok_or(NftVoterError::InvalidNftVoteRecord)?;
+
+    Ok(Some(collection_weight))
 }
 
 pub fn get_nft_vote_record_data_for_proposal_and_token_owner(
@@ -82,3 +308,105 @@ pub fn get_nft_vote_record_data_for_proposal_and_token_owner(
 
     Ok(nft_vote_record)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_nft_vote_record_created_before_versioning() {
+        // Arrange
+        let proposal = Pubkey::new_unique();
+        let nft_mint = Pubkey::new_unique();
+        let governing_token_owner = Pubkey::new_unique();
+
+        // Layout before versioning: discriminator, proposal, nft_mint, governing_token_owner, reserved: [u8; 8]
+        let data = [
+            &NftVoteRecord::ACCOUNT_DISCRIMINATOR[..],
+            proposal.as_ref(),
+            nft_mint.as_ref(),
+            governing_token_owner.as_ref(),
+            &[0; 8],
+        ].concat();
+
+        // Act
+        let nft_vote_record = NftVoteRecord::try_from_slice(&data).unwrap();
+
+        // Assert
+        assert_eq!(
+            nft_vote_record,
+            NftVoteRecord {
+                version: NftVoteRecordVersion::V1,
+                ..NftVoteRecord::new(proposal, nft_mint, governing_token_owner, NftVoteKind::Unknown)
+            }
+        );
+        assert_eq!(nft_vote_record.version, NftVoteRecordVersion::V1);
+        assert_eq!(nft_vote_record.vote_kind, NftVoteKind::Unknown);
+    }
+
+    #[test]
+    fn test_get_nft_vote_record_collection_weight() {
+        // Arrange
+        let collection_weight = NftVoteRecordCollectionWeight {
+            collection: Pubkey::new_unique(),
+            voter_weight: 10,
+        };
+        let nft_vote_record_with_collection_weight = NftVoteRecordWithCollectionWeight::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            NftVoteKind::Approve,
+            collection_weight
+        );
+
+        let key = Pubkey::new_unique();
+        let owner = id();
+        let mut lamports = 0;
+        let mut data = nft_vote_record_with_collection_weight.try_to_vec().unwrap();
+        let nft_vote_record_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0
+        );
+
+        // Act
+        let nft_vote_record = get_nft_vote_record_data(&nft_vote_record_info).unwrap();
+        let actual_collection_weight = get_nft_vote_record_collection_weight(
+            &nft_vote_record_info,
+            &nft_vote_record
+        ).unwrap();
+
+        // Assert
+        assert_eq!(nft_vote_record, nft_vote_record_with_collection_weight.nft_vote_record);
+        assert_eq!(nft_vote_record.version, NftVoteRecordVersion::V3);
+        assert_eq!(actual_collection_weight, Some(collection_weight));
+    }
+
+    #[test]
+    fn test_assert_vote_kind() {
+        // Arrange
+        let new_nft_vote_record = |vote_kind| {
+            NftVoteRecord::new(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                vote_kind
+            )
+        };
+
+        // Act + Assert
+        assert!(new_nft_vote_record(NftVoteKind::Deny).assert_vote_kind(&Vote::Deny).is_ok());
+        assert!(new_nft_vote_record(NftVoteKind::Veto).assert_vote_kind(&Vote::Deny).is_err());
+        assert!(
+            new_nft_vote_record(NftVoteKind::Approve).assert_vote_kind(&Vote::Approve(vec![])).is_ok()
+        );
+
+        // Records without the kind match any Vote
+        assert!(new_nft_vote_record(NftVoteKind::Unknown).assert_vote_kind(&Vote::Abstain).is_ok());
+    }
+}
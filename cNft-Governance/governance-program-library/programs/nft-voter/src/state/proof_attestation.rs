@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
+use solana_program::program_pack::IsInitialized;
+
+use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
+
+use crate::{ error::NftVoterError, id };
+
+/// Attestation of a compressed NFT leaf verified by create_cnft_action_ticket
+/// The PDA of the record is ["proof-attestation",registrar,asset_id]
+/// It's written when the merkle proof of the asset verifies and RegistrarExtension.config.proof_attestation_freshness_slots is set
+/// Within the freshness window the following tickets of the asset can reference the attestation
+/// instead of resubmitting the proof nodes
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ProofAttestation {
+    /// ProofAttestation discriminator sha256("account:ProofAttestation")[..8]
+    /// Note: The discriminator is used explicitly because ProofAttestations
+    /// are created and consumed dynamically using remaining_accounts
+    pub account_discriminator: [u8; 8],
+
+    /// The Registrar the attestation is for
+    pub registrar: Pubkey,
+
+    /// The compressed NFT the attestation is for
+    pub asset_id: Pubkey,
+
+    /// The owner of the leaf at the time of the verification
+    pub leaf_owner: Pubkey,
+
+    /// The root of the tree the proof was verified against
+    pub root: [u8; 32],
+
+    /// The verified leaf node
+    /// It binds the attestation to the exact owner, delegate, nonce and metadata of the asset
+    pub leaf: [u8; 32],
+
+    /// The slot the proof was verified at
+    pub slot: u64,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 8],
+}
+
+impl ProofAttestation {
+    /// sha256("account:ProofAttestation")[..8]
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [97, 149, 129, 131, 133, 179, 202, 69];
+
+    pub fn new(
+        registrar: Pubkey,
+        asset_id: Pubkey,
+        leaf_owner: Pubkey,
+        root: [u8; 32],
+        leaf: [u8; 32],
+        slot: u64
+    ) -> Self {
+        Self {
+            account_discriminator: ProofAttestation::ACCOUNT_DISCRIMINATOR,
+            registrar,
+            asset_id,
+            leaf_owner,
+            root,
+            leaf,
+            slot,
+            reserved: [0; 8],
+        }
+    }
+
+    /// Returns true if the attestation is still fresh at the given slot
+    pub fn is_fresh(&self, slot: u64, freshness_slots: u64) -> bool {
+        slot.saturating_sub(self.slot) <= freshness_slots
+    }
+
+    /// Asserts the attestation is fresh and it attests the given leaf of the given owner
+    pub fn assert_leaf_attested(
+        &self,
+        leaf: &[u8; 32],
+        leaf_owner: &Pubkey,
+        slot: u64,
+        freshness_slots: u64
+    ) -> Result<()> {
+        require!(
+            self.leaf == *leaf && self.leaf_owner == *leaf_owner,
+            NftVoterError::InvalidProofAttestation
+        );
+
+        require!(self.is_fresh(slot, freshness_slots), NftVoterError::ProofAttestationExpired);
+
+        Ok(())
+    }
+}
+
+impl AccountMaxSize for ProofAttestation {}
+
+impl IsInitialized for ProofAttestation {
+    fn is_initialized(&self) -> bool {
+        self.account_discriminator == ProofAttestation::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+/// Returns ProofAttestation PDA seeds
+pub fn get_proof_attestation_seeds<'a>(
+    registrar: &'a Pubkey,
+    asset_id: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"proof-attestation", registrar.as_ref(), asset_id.as_ref()]
+}
+
+/// Returns ProofAttestation PDA address
+pub fn get_proof_attestation_address(registrar: &Pubkey, asset_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_proof_attestation_seeds(registrar, asset_id), &id()).0
+}
+
+/// Deserializes account and checks owner program
+pub fn get_proof_attestation_data(proof_attestation_info: &AccountInfo) -> Result<ProofAttestation> {
+    Ok(get_account_data::<ProofAttestation>(&id(), proof_attestation_info)?)
+}
+
+pub fn get_proof_attestation_data_for_asset(
+    proof_attestation_info: &AccountInfo,
+    registrar: &Pubkey,
+    asset_id: &Pubkey
+) -> Result<ProofAttestation> {
+    let proof_attestation = get_proof_attestation_data(proof_attestation_info)?;
+
+    require!(
+        proof_attestation.registrar == *registrar && proof_attestation.asset_id == *asset_id,
+        NftVoterError::InvalidProofAttestation
+    );
+
+    Ok(proof_attestation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_assert_leaf_attested() {
+        // Arrange
+        let leaf_owner = Pubkey::new_unique();
+        let proof_attestation = ProofAttestation::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            leaf_owner,
+            [1; 32],
+            [2; 32],
+            100
+        );
+
+        // Act
+        let fresh_result = proof_attestation.assert_leaf_attested(&[2; 32], &leaf_owner, 110, 10);
+        let expired_result = proof_attestation.assert_leaf_attested(&[2; 32], &leaf_owner, 111, 10);
+        let invalid_leaf_result = proof_attestation.assert_leaf_attested(
+            &[3; 32],
+            &leaf_owner,
+            110,
+            10
+        );
+        let invalid_owner_result = proof_attestation.assert_leaf_attested(
+            &[2; 32],
+            &Pubkey::new_unique(),
+            110,
+            10
+        );
+
+        // Assert
+        assert!(fresh_result.is_ok());
+        assert!(expired_result.is_err());
+        assert!(invalid_leaf_result.is_err());
+        assert!(invalid_owner_result.is_err());
+    }
+}
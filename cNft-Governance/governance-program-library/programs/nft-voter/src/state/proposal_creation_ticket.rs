@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// The number of slots a ProposalCreationTicket is valid for after it's created
+pub const PROPOSAL_CREATION_TICKET_LIFETIME_SLOTS: u64 = 150;
+
+/// Receipt proving the voter holds NFTs with the given weight and can create a single Proposal for the Governance
+/// The PDA of the ticket is ["proposal-creation-ticket",registrar,governing_token_owner,governance]
+/// The ticket is created by create_proposal_receipt and disposed by consume_proposal_creation_ticket
+/// which must be executed in the same transaction as spl-gov create_proposal
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct ProposalCreationTicket {
+    /// The Registrar the ticket was created for
+    pub registrar: Pubkey,
+
+    /// The voter who owns the ticket
+    pub governing_token_owner: Pubkey,
+
+    /// The Governance the voter can create a Proposal for
+    pub governance: Pubkey,
+
+    /// The total weight of the NFTs the voter proved to hold
+    pub voter_weight: u64,
+
+    /// The slot after which the ticket can't be used
+    pub expiry: u64,
+}
+
+impl ProposalCreationTicket {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 8 + 8
+    }
+}
+
+/// Returns ProposalCreationTicket PDA seeds
+pub fn get_proposal_creation_ticket_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey,
+    governance: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [
+        b"proposal-creation-ticket",
+        registrar.as_ref(),
+        governing_token_owner.as_ref(),
+        governance.as_ref(),
+    ]
+}
+
+/// Returns ProposalCreationTicket PDA address
+pub fn get_proposal_creation_ticket_address(
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    governance: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_proposal_creation_ticket_seeds(registrar, governing_token_owner, governance),
+        &id()
+    ).0
+}
@@ -1,20 +1,41 @@
 use crate::{
     error::NftVoterError,
     id,
-    state::{ CollectionConfig, VoterWeightRecord, CompressedNftAsset, verify_compressed_nft },
+    state::{
+        CollectionAlias,
+        CollectionConfig,
+        CollectionWeightMode,
+        RegistrarConfig,
+        RegistrarExtension,
+        VoterWeightRecord,
+        VotingSession,
+        WeightStrategy,
+        CompressedNftAsset,
+        ProofAttestation,
+        assert_leaf_schema_valid,
+        assert_tree_root_fresh,
+        get_compressed_nft_leaf_node,
+        verify_compressed_nft,
+    },
     tools::{
-        anchor::DISCRIMINATOR_SIZE,
+        anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE },
+        merkle_tree::assert_bubblegum_merkle_tree,
         spl_token::get_spl_token_amount,
         token_metadata::get_token_metadata_for_mint,
     },
 };
 use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
 use solana_program::pubkey::PUBKEY_BYTES;
-use spl_governance::state::token_owner_record;
+use spl_governance::state::token_owner_record::{ self, TokenOwnerRecordV2 };
 use spl_governance::tools::spl_token::{ get_spl_token_mint, get_spl_token_owner };
-use mpl_bubblegum::utils::get_asset_id;
+use cnft_verification::get_asset_id_with_program;
+
+#[cfg(feature = "metaplex-core")]
+use crate::tools::metaplex_core::{ get_core_asset, is_core_collection };
 
 /// Registrar which stores NFT voting configuration for the given Realm
+/// The Registrar level voting configuration is stored in the RegistrarExtension of the Registrar
 #[account]
 #[derive(Debug, PartialEq)]
 pub struct Registrar {
@@ -38,7 +59,9 @@ pub struct Registrar {
 }
 
 impl Registrar {
-    pub fn get_space(max_collections: u8) -> usize {
+    /// Returns the size of Registrar with capacity for the given number of collections
+    /// It's used by create_registrar and by the clients to estimate the rent
+    pub const fn space_for(max_collections: u8) -> usize {
         DISCRIMINATOR_SIZE +
             PUBKEY_BYTES * 3 +
             4 +
@@ -61,12 +84,141 @@ pub fn get_registrar_address(realm: &Pubkey, governing_token_mint: &Pubkey) -> P
 }
 
 impl Registrar {
-    pub fn get_collection_config(&self, collection: Pubkey) -> Result<&CollectionConfig> {
-        return self.collection_configs
+    /// Returns the max voting power of all configured collections, the rolled up sub-DAO Registrars
+    /// and the floor weight of the registered voters
+    pub fn get_max_voter_weight(&self, registrar_extension: &RegistrarExtension) -> Result<u64> {
+        let collections_max_voter_weight = self.collection_configs
+            .iter()
+            .try_fold(registrar_extension.sub_registrars_max_voter_weight, |sum, cc| -> Result<u64> {
+                sum.checked_add(self.get_collection_max_weight(registrar_extension, cc)?).ok_or_else(||
+                    NftVoterError::ArithmeticOverflow.into()
+                )
+            })?;
+
+        collections_max_voter_weight
+            .checked_add(registrar_extension.get_registered_voters_max_weight()?)
+            .ok_or_else(|| NftVoterError::ArithmeticOverflow.into())
+    }
+
+    /// Returns the max voting power of the given collection according to the collection weight mode
+    /// and the weight strategy of the collection
+    pub fn get_collection_max_weight(
+        &self,
+        registrar_extension: &RegistrarExtension,
+        collection_config: &CollectionConfig
+    ) -> Result<u64> {
+        let collection_extension = registrar_extension.get_collection_extension(&collection_config.collection);
+
+        collection_extension.weight_strategy.get_max_weight(
+            registrar_extension.config.normalize_weight(
+                collection_extension.get_max_asset_weight(collection_config.weight)?,
+                collection_config.size
+            )?,
+            collection_config.size
+        )
+    }
+
+    /// Returns true if the voter weight of NFTs supplied to separate cast_nft_vote instructions can be accumulated
+    /// The weight can't be accumulated in UniqueCollections mode or when any collection uses a non linear strategy
+    /// because the NFTs counted by the previous instructions are not tracked
+    pub fn is_voter_weight_accumulative(&self, registrar_extension: &RegistrarExtension) -> bool {
+        registrar_extension.config.collection_weight_mode != CollectionWeightMode::UniqueCollections &&
+            self.collection_configs.iter().all(|cc| {
+                registrar_extension
+                    .get_collection_extension(&cc.collection)
+                    .weight_strategy.is_accumulative()
+            })
+    }
+
+    /// Returns the weight of an NFT of the given collection with the given verified creators
+    /// NFTs of expired collections have no weight
+    pub fn get_nft_weight(
+        &self,
+        registrar_extension: &RegistrarExtension,
+        collection_config: &CollectionConfig,
+        verified_creators: &[Pubkey],
+        unix_timestamp: i64
+    ) -> Result<u64> {
+        let collection_extension = registrar_extension.get_collection_extension(&collection_config.collection);
+
+        if collection_extension.is_expired(unix_timestamp) {
+            return Ok(0);
+        }
+
+        registrar_extension.config.normalize_weight(
+            collection_extension.get_weight(collection_config.weight, verified_creators)?,
+            collection_config.size
+        )
+    }
+
+    /// Adds the given collection config or replaces the existing config of the same collection
+    pub fn set_collection_config(&mut self, collection_config: CollectionConfig) {
+        let collection_idx = self.collection_configs
             .iter()
-            .find(|cc| cc.collection == collection)
-            .ok_or_else(|| NftVoterError::CollectionNotFound.into());
+            .position(|cc| cc.collection == collection_config.collection);
+
+        if let Some(collection_idx) = collection_idx {
+            self.collection_configs[collection_idx] = collection_config;
+        } else {
+            // Note: In the current runtime version push() would throw an error if we exceed
+            // max_collections specified when the Registrar was created
+            self.collection_configs.push(collection_config);
+        }
+    }
+
+    /// Returns true if the given collection is configured and not expired at the given unix timestamp
+    /// Collections removed by prune_expired_collections are not active
+    pub fn is_collection_active(
+        &self,
+        registrar_extension: &RegistrarExtension,
+        collection: &Pubkey,
+        unix_timestamp: i64
+    ) -> bool {
+        self.find_collection_config(collection).is_some() &&
+            !registrar_extension.get_collection_extension(collection).is_expired(unix_timestamp)
+    }
+
+    /// Returns the config of the given collection or None if the collection is not configured
+    pub fn find_collection_config(&self, collection: &Pubkey) -> Option<&CollectionConfig> {
+        self.collection_configs.iter().find(|cc| cc.collection == *collection)
+    }
+
+    /// Returns true if the given compressed NFT is resolved through the CollectionAlias of its collection
+    /// The alias is used when the aliases are allowed and the collection isn't configured on the Registrar
+    pub fn uses_collection_alias(
+        &self,
+        registrar_extension: &RegistrarExtension,
+        params: &CompressedNftAsset
+    ) -> bool {
+        registrar_extension.config.allow_collection_aliases &&
+            params.collection
+                .as_ref()
+                .map_or(false, |collection| self.find_collection_config(&collection.key).is_none())
+    }
+
+    /// Returns the config of the given collection or CollectionNotFound error if the collection is not configured
+    pub fn get_collection_config(&self, collection: &Pubkey) -> Result<&CollectionConfig> {
+        self.find_collection_config(collection).ok_or_else(|| NftVoterError::CollectionNotFound.into())
+    }
+}
+
+/// Asserts the given account is an MPL collection Mint
+/// or a Metaplex Core collection when allowed by the given config
+pub fn assert_collection_account(
+    config: &RegistrarConfig,
+    collection_info: &AccountInfo
+) -> Result<()> {
+    #[cfg(feature = "metaplex-core")]
+    if config.allow_core_assets && is_core_collection(collection_info) {
+        return Ok(());
     }
+
+    #[cfg(not(feature = "metaplex-core"))]
+    let _ = config;
+
+    let _collection = get_anchor_account_data::<Mint>(collection_info)?;
+
+    Ok(())
 }
 
 // Resolves governing_token_owner from voter TokenOwnerRecord and
@@ -78,6 +230,51 @@ pub fn resolve_governing_token_owner(
     voter_authority_info: &AccountInfo,
     voter_weight_record: &VoterWeightRecord
 ) -> Result<Pubkey> {
+    let voter_token_owner_record = get_voter_token_owner_record_data(
+        registrar,
+        voter_token_owner_record_info,
+        voter_weight_record
+    )?;
+
+    voter_token_owner_record.assert_token_owner_or_delegate_is_signer(voter_authority_info)?;
+
+    Ok(voter_token_owner_record.governing_token_owner)
+}
+
+// Resolves governing_token_owner from voter TokenOwnerRecord and
+// 1) asserts it matches the given Registrar and VoterWeightRecord
+// 2) asserts the session authority of the VotingSession of governing_token_owner is a signer
+pub fn resolve_governing_token_owner_for_voting_session(
+    registrar: &Account<Registrar>,
+    voter_token_owner_record_info: &AccountInfo,
+    voter_authority_info: &AccountInfo,
+    voter_weight_record: &VoterWeightRecord,
+    voting_session_info: &AccountInfo
+) -> Result<Pubkey> {
+    let voter_token_owner_record = get_voter_token_owner_record_data(
+        registrar,
+        voter_token_owner_record_info,
+        voter_weight_record
+    )?;
+
+    let voting_session = get_anchor_account_data::<VotingSession>(voting_session_info)?;
+
+    voting_session.assert_can_vote_for(
+        &registrar.key(),
+        &voter_token_owner_record.governing_token_owner,
+        voter_authority_info,
+        Clock::get()?.slot
+    )?;
+
+    Ok(voter_token_owner_record.governing_token_owner)
+}
+
+// Deserializes voter TokenOwnerRecord and asserts it matches the given Registrar and VoterWeightRecord
+fn get_voter_token_owner_record_data(
+    registrar: &Registrar,
+    voter_token_owner_record_info: &AccountInfo,
+    voter_weight_record: &VoterWeightRecord
+) -> Result<TokenOwnerRecordV2> {
     let voter_token_owner_record =
         token_owner_record::get_token_owner_record_data_for_realm_and_governing_mint(
             &registrar.governance_program_id,
@@ -86,8 +283,6 @@ pub fn resolve_governing_token_owner(
             &registrar.governing_token_mint
         )?;
 
-    voter_token_owner_record.assert_token_owner_or_delegate_is_signer(voter_authority_info)?;
-
     // Assert voter TokenOwnerRecord and VoterWeightRecord are for the same governing_token_owner
     require_eq!(
         voter_token_owner_record.governing_token_owner,
@@ -95,17 +290,18 @@ pub fn resolve_governing_token_owner(
         NftVoterError::InvalidTokenOwnerForVoterWeightRecord
     );
 
-    Ok(voter_token_owner_record.governing_token_owner)
+    Ok(voter_token_owner_record)
 }
 
-/// Resolves vote weight and voting mint for the given NFT
+/// Resolves vote weight, voting mint and collection for the given NFT
 pub fn resolve_nft_vote_weight_and_mint(
     registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
     governing_token_owner: &Pubkey,
     nft_info: &AccountInfo,
     nft_metadata_info: &AccountInfo,
     unique_nft_mints: &mut Vec<Pubkey>
-) -> Result<(u64, Pubkey)> {
+) -> Result<(u64, Pubkey, Pubkey)> {
     let nft_owner = get_spl_token_owner(nft_info)?;
 
     // voter_weight_record.governing_token_owner must be the owner of the NFT
@@ -131,57 +327,212 @@ pub fn resolve_nft_vote_weight_and_mint(
 
     require!(collection.verified, NftVoterError::CollectionMustBeVerified);
 
-    let collection_config = registrar.get_collection_config(collection.key)?;
+    let collection_config = registrar.get_collection_config(&collection.key)?;
+
+    let verified_creators = nft_metadata.creators
+        .iter()
+        .flatten()
+        .filter(|creator| creator.verified)
+        .map(|creator| creator.address)
+        .collect::<Vec<_>>();
+
+    let nft_weight = registrar.get_nft_weight(
+        registrar_extension,
+        collection_config,
+        &verified_creators,
+        Clock::get()?.unix_timestamp
+    )?;
+
+    Ok((nft_weight, nft_mint, collection.key))
+}
+
+/// Resolves vote weight, asset and collection for the given Metaplex Core asset
+#[cfg(feature = "metaplex-core")]
+pub fn resolve_core_asset_vote_weight(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
+    governing_token_owner: &Pubkey,
+    asset_info: &AccountInfo,
+    unique_assets: &mut Vec<Pubkey>
+) -> Result<(u64, Pubkey, Pubkey)> {
+    require!(registrar_extension.config.allow_core_assets, NftVoterError::CoreAssetsNotAllowed);
+
+    let core_asset = get_core_asset(asset_info)?;
+
+    require!(core_asset.owner == *governing_token_owner, NftVoterError::VoterDoesNotOwnNft);
+
+    if unique_assets.contains(asset_info.key) {
+        return Err(NftVoterError::DuplicatedNftDetected.into());
+    }
+    unique_assets.push(*asset_info.key);
+
+    // Core assets can only be added to a collection by the collection authority and hence the collection is verified
+    let collection = core_asset.get_collection().ok_or(NftVoterError::MissingMetadataCollection)?;
+
+    let collection_config = registrar.get_collection_config(&collection)?;
+
+    // Core creators are defined by the Royalties plugin and they are not verified hence creator boosts don't apply
+    let asset_weight = registrar.get_nft_weight(
+        registrar_extension,
+        collection_config,
+        &[],
+        Clock::get()?.unix_timestamp
+    )?;
+
+    Ok((asset_weight, *asset_info.key, collection))
+}
 
-    Ok((collection_config.weight, nft_mint))
+/// Metaplex Core assets can't be resolved without the metaplex-core feature
+#[cfg(not(feature = "metaplex-core"))]
+pub fn resolve_core_asset_vote_weight(
+    _registrar: &Registrar,
+    _registrar_extension: &RegistrarExtension,
+    _governing_token_owner: &Pubkey,
+    _asset_info: &AccountInfo,
+    _unique_assets: &mut Vec<Pubkey>
+) -> Result<(u64, Pubkey, Pubkey)> {
+    err!(NftVoterError::CoreAssetsNotSupported)
 }
 
+/// Resolves vote weight, asset id and collection for the given compressed NFT
+/// The assets of an aliased collection resolve to the target collection of the given CollectionAlias
 pub fn resolve_cnft_vote_weight<'info>(
     registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
     governing_token_owner: &Pubkey,
     tree_account: &AccountInfo<'info>,
     unique_asset_ids: &mut Vec<Pubkey>,
     params: &CompressedNftAsset,
     proofs: Vec<AccountInfo<'info>>,
-    compression_program: &AccountInfo<'info>
-) -> Result<(u64, Pubkey)> {
-    let asset_id = get_asset_id(&tree_account.key(), params.nonce);
+    compression_program: &AccountInfo<'info>,
+    proof_attestation: Option<&ProofAttestation>,
+    collection_alias: Option<&CollectionAlias>
+) -> Result<(u64, Pubkey, Pubkey)> {
+    let bubblegum_program_id = registrar_extension.config.get_bubblegum_program_id();
+    let asset_id = get_asset_id_with_program(&tree_account.key(), params.nonce, &bubblegum_program_id);
 
     require_eq!(*governing_token_owner, params.leaf_owner, NftVoterError::VoterDoesNotOwnNft);
 
+    assert_leaf_schema_valid(tree_account, params)?;
+
+    // The collection verified flag of the leaf can only be trusted in Bubblegum trees
+    assert_bubblegum_merkle_tree(tree_account, &bubblegum_program_id)?;
+
+    // The attested leaf hash replaces the metadata and the uses which aren't hashed can't be tracked
+    // Note: The attestation itself is verified by the instructions which have access to the instructions sysvar
+    if params.leaf_hash.is_some() {
+        require!(
+            registrar_extension.config.is_leaf_hash_verification_enabled(),
+            NftVoterError::LeafHashVerificationNotAllowed
+        );
+        require!(params.uses.is_none(), NftVoterError::InvalidLeafHashData);
+    }
+
+    // The collection can't be verified using precomputed hashes and hence they must be explicitly allowed
+    if params.has_precomputed_hashes() {
+        require!(
+            registrar_extension.config.allow_precomputed_metadata_hashes,
+            NftVoterError::PrecomputedMetadataHashesNotAllowed
+        );
+    }
+
     let collection = params.collection.as_ref().ok_or(NftVoterError::MissingMetadataCollection)?;
 
     require!(collection.verified, NftVoterError::CollectionMustBeVerified);
 
-    verify_compressed_nft(tree_account, &asset_id, params, proofs, compression_program)?;
+    if params.use_proof_attestation {
+        // The leaf was verified by a recent proof and only the attested leaf is compared
+        let proof_attestation = proof_attestation.ok_or(NftVoterError::InvalidProofAttestation)?;
+
+        require!(
+            proofs.is_empty() && params.proof_nodes.is_none(),
+            NftVoterError::InvalidProofData
+        );
+
+        proof_attestation.assert_leaf_attested(
+            &get_compressed_nft_leaf_node(&asset_id, params),
+            &params.leaf_owner,
+            Clock::get()?.slot,
+            registrar_extension.config.proof_attestation_freshness_slots
+        )?;
+    } else {
+        assert_tree_root_fresh(tree_account, &params.root, registrar_extension.config.max_tree_root_age)?;
+
+        verify_compressed_nft(tree_account, &asset_id, params, proofs, compression_program)?;
+    }
 
     if unique_asset_ids.contains(&asset_id) {
         return Err(NftVoterError::DuplicatedNftDetected.into());
     }
     unique_asset_ids.push(asset_id);
 
-    let collection_config = registrar.get_collection_config(collection.key)?;
-    Ok((collection_config.weight, asset_id))
+    // The assets of a migrated collection count under the config of the collection it's mapped to
+    let collection_key = match collection_alias {
+        Some(collection_alias) => {
+            require_keys_eq!(
+                collection_alias.collection,
+                collection.key,
+                NftVoterError::InvalidCollectionAlias
+            );
+            collection_alias.target_collection
+        }
+        None => collection.key,
+    };
+
+    let collection_config = registrar.get_collection_config(&collection_key)?;
+
+    // The Uses are only verified by the leaf when the metadata is hashed on-chain
+    // and without them the asset would skip AssetUsesRecord and could vote without limit
+    if registrar_extension.get_collection_extension(&collection_key).uses_limited {
+        require!(
+            params.uses.is_some() && !params.has_precomputed_hashes() && params.leaf_hash.is_none(),
+            NftVoterError::AssetUsesRequired
+        );
+    }
+
+    // The creators are only verified by the leaf when neither creator_hash nor leaf_hash is precomputed
+    let verified_creators = if params.creator_hash.is_none() && params.leaf_hash.is_none() {
+        params.creators
+            .iter()
+            .filter(|creator| creator.verified)
+            .map(|creator| creator.address)
+            .collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    let asset_weight = registrar.get_nft_weight(
+        registrar_extension,
+        collection_config,
+        &verified_creators,
+        Clock::get()?.unix_timestamp
+    )?;
+
+    let asset_weight = match collection_alias {
+        Some(collection_alias) => collection_alias.get_weight(asset_weight),
+        None => asset_weight,
+    };
+
+    Ok((asset_weight, asset_id, collection_key))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::state::CollectionExtension;
 
     #[test]
     fn test_get_space() {
         // Arrange
-        let expected_space = Registrar::get_space(3);
+        let expected_space = Registrar::space_for(3);
+
+        let collection_config = CollectionConfig::default();
 
         let registrar = Registrar {
             governance_program_id: Pubkey::default(),
             realm: Pubkey::default(),
             governing_token_mint: Pubkey::default(),
-            collection_configs: vec![
-                CollectionConfig::default(),
-                CollectionConfig::default(),
-                CollectionConfig::default()
-            ],
+            collection_configs: vec![collection_config, collection_config, collection_config],
             reserved: [0; 128],
         };
 
@@ -191,4 +542,117 @@ mod test {
         // Assert
         assert_eq!(expected_space, actual_space);
     }
+
+    fn create_registrar(collection_configs: Vec<CollectionConfig>) -> Registrar {
+        Registrar {
+            governance_program_id: Pubkey::default(),
+            realm: Pubkey::default(),
+            governing_token_mint: Pubkey::default(),
+            collection_configs,
+            reserved: [0; 128],
+        }
+    }
+
+    #[test]
+    fn test_get_collection_config() {
+        // Arrange
+        let collection_config = CollectionConfig {
+            collection: Pubkey::new_unique(),
+            weight: 3,
+            ..Default::default()
+        };
+
+        let registrar = create_registrar(vec![CollectionConfig::default(), collection_config]);
+
+        // Act
+        let actual_collection_config = registrar
+            .get_collection_config(&collection_config.collection)
+            .unwrap();
+
+        // Assert
+        assert_eq!(*actual_collection_config, collection_config);
+    }
+
+    #[test]
+    fn test_get_collection_config_with_collection_not_found_error() {
+        // Arrange
+        let registrar = create_registrar(vec![CollectionConfig::default()]);
+
+        // Act
+        let err = registrar.get_collection_config(&Pubkey::new_unique()).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::CollectionNotFound.into());
+        assert!(registrar.find_collection_config(&Pubkey::new_unique()).is_none());
+    }
+
+    #[test]
+    fn test_is_collection_active() {
+        // Arrange
+        let collection = Pubkey::new_unique();
+        let registrar = create_registrar(
+            vec![CollectionConfig { collection, ..Default::default() }]
+        );
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+        registrar_extension.set_collection_extension(CollectionExtension {
+            expires_at: 100,
+            ..CollectionExtension::new(collection)
+        });
+
+        // Act + Assert
+        assert!(registrar.is_collection_active(&registrar_extension, &collection, 99));
+        assert!(!registrar.is_collection_active(&registrar_extension, &collection, 100));
+        assert!(!registrar.is_collection_active(&registrar_extension, &Pubkey::new_unique(), 99));
+    }
+
+    #[test]
+    fn test_get_max_voter_weight_with_overflow_error() {
+        // Arrange
+        let registrar = create_registrar(
+            vec![CollectionConfig {
+                collection: Pubkey::new_unique(),
+                weight: u64::MAX / 2,
+                size: 2,
+                ..Default::default()
+            }]
+        );
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+
+        assert_eq!(registrar.get_max_voter_weight(&registrar_extension).unwrap(), u64::MAX - 1);
+
+        registrar_extension.sub_registrars_max_voter_weight = 2;
+
+        // Act
+        let err = registrar.get_max_voter_weight(&registrar_extension).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::ArithmeticOverflow.into());
+    }
+
+    #[test]
+    fn test_get_max_voter_weight_with_registered_voters() {
+        // Arrange
+        let registrar = create_registrar(
+            vec![CollectionConfig {
+                collection: Pubkey::new_unique(),
+                weight: 3,
+                size: 10,
+                ..Default::default()
+            }]
+        );
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+        registrar_extension.registered_voters = 2;
+        registrar_extension.config.min_weight_if_registered_before_slot = 5;
+
+        // Act
+        let max_voter_weight = registrar.get_max_voter_weight(&registrar_extension).unwrap();
+
+        // Assert
+
+        // Each registered voter can have the floor weight on top of the weight of the collection
+        assert_eq!(max_voter_weight, 10 * 3 + 2 * 5);
+    }
 }
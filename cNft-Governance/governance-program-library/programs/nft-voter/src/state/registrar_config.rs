@@ -0,0 +1,416 @@
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+use crate::error::NftVoterError;
+use crate::state::NFT_ACTION_TICKET_VALIDITY_SLOTS;
+use crate::tools::merkle_tree::{ get_voter_allowlist_leaf, verify_sorted_merkle_proof };
+use crate::tools::program_ids::BUBBLEGUM_ID;
+
+/// 100% expressed in basis points
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// The max number of Bubblegum tree authorities which can be allowed by the Registrar
+pub const MAX_TREE_AUTHORITIES: usize = 4;
+
+/// Defines how the weight of the NFTs held by a voter is aggregated
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionWeightMode {
+    /// Each NFT contributes the weight of its collection
+    /// voter_weight = sum(collection.weight) over all NFTs
+    PerAsset,
+
+    /// Each configured collection contributes its weight only once regardless of the number of NFTs held
+    /// voter_weight = sum(collection.weight) over distinct collections
+    /// Note: In this mode cast_nft_vote is not accumulative and all NFTs must be supplied in a single instruction
+    UniqueCollections,
+
+    /// Each NFT contributes the weight of its collection scaled by normalized_collection_weight / collection.size
+    /// It makes collections of vastly different sizes contribute equal aggregate power
+    /// voter_weight = sum(collection.weight * normalized_collection_weight / collection.size) over all NFTs
+    /// Note: MaxVoterWeightRecord is only recomputed when the collections are configured
+    Normalized,
+}
+
+impl Default for CollectionWeightMode {
+    fn default() -> Self {
+        CollectionWeightMode::PerAsset
+    }
+}
+
+/// Registrar level voting configuration
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct RegistrarConfig {
+    /// How the voter weight is aggregated from the NFTs held by the voter
+    pub collection_weight_mode: CollectionWeightMode,
+
+    /// The max share of the total voting power NFTs of a single collection can cast on any Proposal
+    /// It's expressed in basis points of MaxVoterWeightRecord.max_voter_weight and 0 means no cap
+    /// When set cast_nft_vote tracks the weight cast per collection using CollectionVoteWeightRecord accounts
+    pub max_collection_weight_per_proposal: u16,
+
+    /// The min number of slots a compressed NFT must be held by the voter to be used for voting
+    /// 0 means there is no min holding period
+    /// When set the last transfer slot of each compressed NFT must be attested by holding_attestation_authority
+    pub min_holding_slots: u64,
+
+    /// The authority (oracle) which attests the last transfer slot of compressed NFTs
+    /// The attestation is an ed25519 signature over get_holding_attestation_message()
+    pub holding_attestation_authority: Pubkey,
+
+    /// Whether voters must use ProposalCreationTicket to create Proposals
+    /// When set update_voter_weight_record can't be used for CreateProposal action
+    /// and the weight is provided by consume_proposal_creation_ticket instead
+    pub require_proposal_creation_ticket: bool,
+
+    /// Whether the NFTs which produced the voter weight are summarized in VoterWeightDetail
+    /// When set VoterWeightDetail must be supplied as the first remaining account
+    /// to update_voter_weight_record, update_voter_weight_record_for_target and cast_nft_vote
+    pub track_voter_weight_detail: bool,
+
+    /// Bubblegum tree creators (authorities) whose trees can hold compressed NFTs used for voting
+    /// It prevents copycat assets with the same collection key minted into rogue trees from being used
+    /// Pubkey::default() entries are ignored and if all entries are empty then assets from any tree are accepted
+    /// When set the tree TreeConfig account must be supplied for each compressed NFT
+    pub tree_authority_allowlist: [Pubkey; MAX_TREE_AUTHORITIES],
+
+    /// Whether compressed NFTs can be supplied with precomputed data_hash and creator_hash instead of the full metadata
+    /// The collection of such assets can't be verified and hence it can only be enabled together with tree_authority_allowlist
+    /// Note: Metaplex Uses can't be verified for such assets either and hence they are rejected for Uses limited collections
+    pub allow_precomputed_metadata_hashes: bool,
+
+    /// Merkle root of the wallets (governing_token_owner) allowed to vote, ex. KYC verified members
+    /// The leaves are keccak(wallet) and pairs are hashed in sorted order
+    /// [0; 32] means the voters are not restricted
+    /// When set the voter must supply the merkle proof of its wallet to create NFT action tickets
+    pub voter_allowlist_root: [u8; 32],
+
+    /// The treasury which receives the share of the rent reclaimed from NftVoteRecords disposed by cranks
+    /// Pubkey::default() means reclaim_nft_vote_records is disabled and only voters can relinquish their votes
+    /// Note: The treasury must be an existing (rent exempt) account, ex. the Realm native treasury
+    pub rent_reclaim_treasury: Pubkey,
+
+    /// The share of the reclaimed rent which goes to rent_reclaim_treasury expressed in basis points
+    /// The remainder goes to the crank as an incentive for the permissionless cleanup
+    pub rent_reclaim_treasury_share_bps: u16,
+
+    /// Whether the votes cast by NFTs are counted in CollectionStats of their collections
+    /// When set the CollectionStats of the collection must be supplied as the last account of each NFT to cast_nft_vote
+    pub track_collection_stats: bool,
+
+    /// The parent Registrar the voter weight of this (sub-DAO) Registrar rolls up into
+    /// Pubkey::default() means there is no parent
+    /// The parent Realm authority must approve the link using configure_sub_registrar
+    pub parent_registrar: Pubkey,
+
+    /// Whether VoterWeightRecord updated for non voting actions must be consumed in the same transaction
+    /// When set update_voter_weight_record and update_voter_weight_record_for_target use instruction introspection
+    /// to ensure an spl-gov instruction following them in the transaction takes the VoterWeightRecord
+    /// It prevents a generic record created for one purpose from being reused for another one within the slot
+    pub require_same_transaction_consumption: bool,
+
+    /// The aggregate weight of each collection (for collection.weight 1) in Normalized collection_weight_mode
+    pub normalized_collection_weight: u64,
+
+    /// Whether Metaplex Core assets of the configured collections can be used for voting
+    /// alongside the NFTs and compressed NFTs using create_core_asset_action_ticket
+    /// Note: It requires the program to be built with the metaplex-core feature
+    pub allow_core_assets: bool,
+
+    /// The max number of assets (NFT action tickets) a single cast_nft_vote instruction can take or 0 if not limited
+    /// Voters with more assets split them across several cast_nft_vote transactions which accumulate the weight
+    /// and complete the vote using finalize_nft_vote bundled with spl-gov CastVote
+    pub max_assets_per_tx: u16,
+
+    /// Whether the weight accumulated by cast_nft_vote must be finalized using finalize_nft_vote before spl-gov can take it
+    /// When set cast_nft_vote leaves VoterWeightRecord Accumulating and expired
+    /// and finalize_nft_vote must be bundled with spl-gov CastVote which takes the record in the same transaction
+    /// It ensures multi-transaction votes can't be half-applied
+    pub require_vote_finalization: bool,
+
+    /// The max number of compressed NFTs of a single tree a voter can create tickets for in a slot or 0 if not limited
+    /// The submissions are tracked by TreeRateLimitRecord of the tree and the voter supplied to create_cnft_action_ticket
+    /// It mitigates bulk submissions of malformed or adversarial proofs exhausting the vote pipelines
+    pub max_tree_assets_per_slot: u16,
+
+    /// The number of slots a ProofAttestation written by create_cnft_action_ticket stays fresh or 0 if not used
+    /// When set the ProofAttestation of each compressed NFT is supplied to create_cnft_action_ticket
+    /// and within the window the assets can reference it instead of resubmitting the proof nodes
+    pub proof_attestation_freshness_slots: u64,
+
+    /// The max number of changes of a tree since the root referenced by a compressed NFT proof or 0 if not limited
+    /// The age is validated against the changelog of the tree and roots which are no longer in the changelog
+    /// are always rejected with StaleTreeRoot
+    pub max_tree_root_age: u32,
+
+    /// Whether voters can move their open NftVoteRecords and ColdWalletLinks to a new wallet using migrate_voter
+    /// The migrated wallet keeps the holding period of the compressed NFTs moved from the previous wallet
+    pub allow_voter_migration: bool,
+
+    /// The verifier service which attests the leaf hashes of compressed NFTs precomputed by the clients
+    /// Pubkey::default() means the leaf hashes are always computed on-chain from the metadata
+    /// The attestation is an ed25519 signature over get_leaf_hash_attestation_message()
+    /// and it lets assets with very large creator arrays be verified within the transaction size limits
+    pub leaf_hash_verifier: Pubkey,
+
+    /// The min number of slots between the creation of an NFT action ticket and its consumption or 0 if not required
+    /// It prevents flash acquisitions where assets are borrowed, used for voting and returned within a single transaction
+    /// The tickets stay valid for NFT_ACTION_TICKET_VALIDITY_SLOTS once they become consumable
+    pub weight_record_min_age_slots: u64,
+
+    /// Whether cast_nft_vote and cast_nft_vote_multi write the VoterCheckpoint of each vote
+    /// The checkpoints record the voting power of past votes and let DAOs reward the participation retroactively
+    pub record_voter_checkpoints: bool,
+
+    /// Whether every VoterWeightRecord must have weight_action_target of a validated Realm, Governance or Proposal
+    /// When set update_voter_weight_record takes the target of the action as the first remaining account
+    /// after the optional VoterWeightDetail and it can't produce generic records without a target
+    pub require_weight_action_target: bool,
+
+    /// The consumer plugin program write_external_voter_weight exports the voter weight to
+    /// Pubkey::default() means the voter weight can't be exported
+    pub weight_export_program: Pubkey,
+
+    /// Whether compressed NFTs of collections not configured on the Registrar are resolved through their CollectionAlias
+    /// When set the CollectionAlias of each such asset is supplied before its tree TreeConfig
+    pub allow_collection_aliases: bool,
+
+    /// The floor weight of the voters registered with register_voter before registration_cutoff_slot or 0 if not used
+    /// The voter weight of such voters is never lower than the floor even if they later reduce their holdings
+    /// When set the VoterRegistration PDA of the voter is supplied to every instruction which finalizes the voter weight
+    /// Note: It requires require_vote_finalization because the floor can only be applied to the complete vote weight
+    pub min_weight_if_registered_before_slot: u64,
+
+    /// The slot the voters must have registered before to get min_weight_if_registered_before_slot
+    pub registration_cutoff_slot: u64,
+
+    /// The Bubblegum program the compressed NFTs are verified against instead of the program of the cluster
+    /// the plugin was built for (see tools::program_ids)
+    /// Pubkey::default() means the Bubblegum program of the cluster is used
+    pub bubblegum_program_override: Pubkey,
+
+    /// Whether the voter weight of wallets with WalletWeightOverride set by the realm authority is scaled by the override
+    /// It lets a realm neutralize voting power of sanctioned or treasury wallets holding member NFTs through a Proposal
+    /// When set the WalletWeightOverride PDA of the voter is supplied to every instruction which finalizes the voter weight
+    /// after the optional VoterRegistration
+    /// Note: It requires require_vote_finalization because the override can only be applied to the complete vote weight
+    pub allow_wallet_weight_overrides: bool,
+
+    /// Whether the owners of compressed NFTs can rent their voter weight to other wallets using rent_voting_power
+    /// While a WeightLease is active the owner can't use the asset and hence the WeightLease PDA of each compressed NFT
+    /// is supplied to create_cnft_action_ticket unless the asset is used by the renter
+    pub allow_weight_leases: bool,
+
+    /// The merkle tree of the BallotTree mint_vote_receipt mints the vote receipt cNFTs to
+    /// The receipts encode the Proposal and the Vote of the voter in their uri and let DAOs reward the participation
+    /// Pubkey::default() means the vote receipts aren't minted
+    pub vote_receipt_tree: Pubkey,
+}
+
+impl RegistrarConfig {
+    pub const fn get_space() -> usize {
+        1 + 2 + 8 + 32 + 1 + 1 + 32 * MAX_TREE_AUTHORITIES + 1 + 32 + 32 + 2 + 1 + 32 + 1 + 8 + 1 +
+            2 + 1 + 2 + 8 + 4 + 1 + 32 + 8 + 1 + 1 + 32 + 1 + 8 + 8 + 32 + 1 + 1 + 32
+    }
+
+    /// Asserts the config options are consistent
+    /// Note: parent_registrar is validated by the instructions against the configured Registrar
+    pub fn assert_is_valid(&self) -> Result<()> {
+        require!(
+            self.max_collection_weight_per_proposal <= MAX_BASIS_POINTS,
+            NftVoterError::InvalidMaxCollectionWeightPerProposal
+        );
+
+        // Assets with precomputed hashes can only be trusted when they come from the DAO trees
+        require!(
+            !self.allow_precomputed_metadata_hashes || self.is_tree_authority_allowlist_enabled(),
+            NftVoterError::InvalidRegistrarConfig
+        );
+
+        require!(
+            self.rent_reclaim_treasury_share_bps <= MAX_BASIS_POINTS,
+            NftVoterError::InvalidRegistrarConfig
+        );
+
+        require!(
+            self.collection_weight_mode != CollectionWeightMode::Normalized ||
+                self.normalized_collection_weight > 0,
+            NftVoterError::InvalidRegistrarConfig
+        );
+
+        // Core assets can only be verified when the program is built with the Metaplex Core adapter
+        require!(
+            !self.allow_core_assets || cfg!(feature = "metaplex-core"),
+            NftVoterError::CoreAssetsNotSupported
+        );
+
+        // cast_nft_vote accumulates the weight and the floor is applied to the complete weight by finalize_nft_vote
+        require!(
+            !self.is_min_voter_weight_enabled() || self.require_vote_finalization,
+            NftVoterError::InvalidRegistrarConfig
+        );
+
+        require!(
+            !self.allow_wallet_weight_overrides || self.require_vote_finalization,
+            NftVoterError::InvalidRegistrarConfig
+        );
+
+        Ok(())
+    }
+
+    /// Returns the max weight NFTs of a single collection can cast on a Proposal or None if it's not capped
+    pub fn get_max_collection_weight_per_proposal(&self, max_voter_weight: u64) -> Option<u64> {
+        if self.max_collection_weight_per_proposal == 0 {
+            return None;
+        }
+
+        Some(
+            (((max_voter_weight as u128) * (self.max_collection_weight_per_proposal as u128)) /
+                (MAX_BASIS_POINTS as u128)) as u64
+        )
+    }
+
+    /// Returns the weight of an NFT of a collection of the given size normalized according to collection_weight_mode
+    pub fn normalize_weight(&self, weight: u64, collection_size: u32) -> Result<u64> {
+        if self.collection_weight_mode != CollectionWeightMode::Normalized || collection_size == 0 {
+            return Ok(weight);
+        }
+
+        let normalized_weight =
+            ((weight as u128) * (self.normalized_collection_weight as u128)) /
+            (collection_size as u128);
+
+        u64::try_from(normalized_weight).map_err(|_| NftVoterError::CastError.into())
+    }
+
+    /// Returns true if compressed NFTs are restricted to trees of the allowed tree authorities
+    pub fn is_tree_authority_allowlist_enabled(&self) -> bool {
+        self.tree_authority_allowlist.iter().any(|authority| *authority != Pubkey::default())
+    }
+
+    /// Returns true if the given tree authority is allowed by the Registrar
+    pub fn is_tree_authority_allowed(&self, tree_authority: &Pubkey) -> bool {
+        *tree_authority != Pubkey::default() &&
+            self.tree_authority_allowlist.contains(tree_authority)
+    }
+
+    /// Returns true if verified compressed NFT proofs are attested using ProofAttestation
+    pub fn is_proof_attestation_enabled(&self) -> bool {
+        self.proof_attestation_freshness_slots > 0
+    }
+
+    /// Returns true if the voters registered before registration_cutoff_slot have a floor weight
+    pub fn is_min_voter_weight_enabled(&self) -> bool {
+        self.min_weight_if_registered_before_slot > 0
+    }
+
+    /// Returns true if the vote receipts are minted to the voters by mint_vote_receipt
+    pub fn is_vote_receipts_enabled(&self) -> bool {
+        self.vote_receipt_tree != Pubkey::default()
+    }
+
+    /// Returns the Bubblegum program the compressed NFTs are verified against
+    pub fn get_bubblegum_program_id(&self) -> Pubkey {
+        if self.bubblegum_program_override != Pubkey::default() {
+            self.bubblegum_program_override
+        } else {
+            BUBBLEGUM_ID
+        }
+    }
+
+    /// Returns true if compressed NFTs can be supplied with leaf hashes attested by leaf_hash_verifier
+    pub fn is_leaf_hash_verification_enabled(&self) -> bool {
+        self.leaf_hash_verifier != Pubkey::default()
+    }
+
+    /// Returns the expiry of an NFT action ticket created at the given slot
+    pub fn get_nft_action_ticket_expiry(&self, slot: u64) -> u64 {
+        slot.saturating_add(self.weight_record_min_age_slots).saturating_add(
+            NFT_ACTION_TICKET_VALIDITY_SLOTS
+        )
+    }
+
+    /// Returns true if NftVoteRecords of finished Proposals can be disposed by cranks
+    pub fn is_rent_reclaim_enabled(&self) -> bool {
+        self.rent_reclaim_treasury != Pubkey::default()
+    }
+
+    /// Returns true if the voters are restricted to the wallets of voter_allowlist_root
+    pub fn is_voter_allowlist_enabled(&self) -> bool {
+        self.voter_allowlist_root != [0; 32]
+    }
+
+    /// Asserts the given voter is allowed to vote using the given merkle proof of voter_allowlist_root
+    pub fn assert_voter_allowed(
+        &self,
+        voter: &Pubkey,
+        voter_allowlist_proof: &[[u8; 32]]
+    ) -> Result<()> {
+        if !self.is_voter_allowlist_enabled() {
+            return Ok(());
+        }
+
+        require!(
+            verify_sorted_merkle_proof(
+                get_voter_allowlist_leaf(voter),
+                voter_allowlist_proof,
+                self.voter_allowlist_root
+            ),
+            NftVoterError::VoterNotAllowed
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_bubblegum_program_id() {
+        // Arrange
+        let mut config = RegistrarConfig::default();
+        let bubblegum_program_override = Pubkey::new_unique();
+
+        // Act + Assert
+        assert_eq!(config.get_bubblegum_program_id(), mpl_bubblegum::ID);
+
+        config.bubblegum_program_override = bubblegum_program_override;
+        assert_eq!(config.get_bubblegum_program_id(), bubblegum_program_override);
+    }
+
+    #[test]
+    fn test_normalize_weight() {
+        // Arrange
+        let config = RegistrarConfig {
+            collection_weight_mode: CollectionWeightMode::Normalized,
+            normalized_collection_weight: 1_000,
+            ..Default::default()
+        };
+
+        // Act
+        let small_collection_weight = config.normalize_weight(2, 10).unwrap();
+        let large_collection_weight = config.normalize_weight(2, 1_000).unwrap();
+
+        // Assert
+        assert_eq!(small_collection_weight, 200);
+        assert_eq!(large_collection_weight, 2);
+        assert_eq!(RegistrarConfig::default().normalize_weight(2, 10).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_normalize_weight_with_cast_error() {
+        // Arrange
+        let config = RegistrarConfig {
+            collection_weight_mode: CollectionWeightMode::Normalized,
+            normalized_collection_weight: 1_000,
+            ..Default::default()
+        };
+
+        // Act
+        let err = config.normalize_weight(u64::MAX, 10).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::CastError.into());
+    }
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    error::NftVoterError,
+    id,
+    state::{ CollectionExtension, RegistrarConfig },
+    tools::anchor::DISCRIMINATOR_SIZE,
+};
+
+/// The current version of the RegistrarExtension layout
+pub const REGISTRAR_EXTENSION_VERSION: u8 = 1;
+
+/// Registrar level configuration which doesn't fit the original Registrar layout
+///
+/// The Registrar layout is kept unchanged so Registrars created by the previous versions of the program
+/// can be deserialized without migration of the Registrar itself
+/// Every Registrar has a single RegistrarExtension created by create_registrar
+/// or by migrate_registrar for Registrars created before the extension was introduced
+///
+/// The PDA of the extension is ["registrar-extension",registrar]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct RegistrarExtension {
+    /// The Registrar the extension is for
+    pub registrar: Pubkey,
+
+    /// The version of the extension layout
+    pub version: u8,
+
+    /// Voting configuration of the Registrar
+    pub config: RegistrarConfig,
+
+    /// Total scaled max voter weight of the sub-DAO Registrars rolled up into the Registrar
+    /// It's maintained by sync_parent_weight
+    pub sub_registrars_max_voter_weight: u64,
+
+    /// The unix timestamp the Registrar was migrated at by migrate_registrar, 0 for Registrars created with the extension
+    /// Proposals which started voting before the cutoff can have NftVoteRecords created by the previous versions
+    /// of the program with the legacy ["nft-vote-record",proposal,nft_mint] PDAs
+    pub legacy_nft_vote_records_cutoff: i64,
+
+    /// The number of voters registered with register_voter
+    /// Each of them can have the floor weight config.min_weight_if_registered_before_slot and it's included in the max voter weight
+    pub registered_voters: u64,
+
+    /// Extra settings of the collections configured on the Registrar
+    /// Only the collections with non default settings have an entry
+    pub collection_extensions: Vec<CollectionExtension>,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 128],
+}
+
+impl RegistrarExtension {
+    /// Returns the extension of the given Registrar with the default config
+    /// The default config preserves the voting behaviour of the original Registrar
+    pub fn new(registrar: Pubkey) -> Self {
+        RegistrarExtension {
+            registrar,
+            version: REGISTRAR_EXTENSION_VERSION,
+            config: RegistrarConfig::default(),
+            sub_registrars_max_voter_weight: 0,
+            legacy_nft_vote_records_cutoff: 0,
+            registered_voters: 0,
+            collection_extensions: vec![],
+            reserved: [0; 128],
+        }
+    }
+
+    /// Returns the size of RegistrarExtension with capacity for the given number of collections
+    pub const fn space_for(max_collections: u8) -> usize {
+        DISCRIMINATOR_SIZE +
+            PUBKEY_BYTES +
+            1 +
+            RegistrarConfig::get_space() +
+            8 +
+            8 +
+            8 +
+            4 +
+            (max_collections as usize) * CollectionExtension::get_space() +
+            128
+    }
+
+    /// Returns true if NftVoteRecords with the legacy PDAs can exist for a Proposal which started voting at the given unix timestamp
+    /// The votes cast by the previous versions of the program predate the migration of the Registrar
+    pub fn has_legacy_nft_vote_records(&self, proposal_voting_at: Option<i64>) -> bool {
+        matches!(proposal_voting_at, Some(voting_at) if voting_at <= self.legacy_nft_vote_records_cutoff)
+    }
+
+    /// Returns the max total floor weight of the registered voters
+    /// A registered voter has the higher of its NFT weight and the floor hence the floor bounds the weight it adds on top of its NFTs
+    pub fn get_registered_voters_max_weight(&self) -> Result<u64> {
+        self.registered_voters
+            .checked_mul(self.config.min_weight_if_registered_before_slot)
+            .ok_or_else(|| NftVoterError::ArithmeticOverflow.into())
+    }
+
+    /// Returns the extension of the given collection or the default settings if the collection has no extension
+    pub fn get_collection_extension(&self, collection: &Pubkey) -> CollectionExtension {
+        self.collection_extensions
+            .iter()
+            .find(|ce| ce.collection == *collection)
+            .copied()
+            .unwrap_or_else(|| CollectionExtension::new(*collection))
+    }
+
+    /// Adds the given collection extension or replaces the existing extension of the same collection
+    /// Extensions with the default settings are removed to keep the space for the other collections
+    pub fn set_collection_extension(&mut self, collection_extension: CollectionExtension) {
+        self.remove_collection_extension(&collection_extension.collection);
+
+        if !collection_extension.is_default() {
+            // Note: In the current runtime version push() would throw an error if we exceed
+            // max_collections specified when the RegistrarExtension was created
+            self.collection_extensions.push(collection_extension);
+        }
+    }
+
+    /// Removes the extension of the given collection
+    pub fn remove_collection_extension(&mut self, collection: &Pubkey) {
+        self.collection_extensions.retain(|ce| ce.collection != *collection);
+    }
+}
+
+/// Returns RegistrarExtension PDA seeds
+pub fn get_registrar_extension_seeds(registrar: &Pubkey) -> [&[u8]; 2] {
+    [b"registrar-extension", registrar.as_ref()]
+}
+
+/// Returns RegistrarExtension PDA address
+pub fn get_registrar_extension_address(registrar: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_registrar_extension_seeds(registrar), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set_collection_extension() {
+        // Arrange
+        let collection = Pubkey::new_unique();
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+
+        let collection_extension = CollectionExtension {
+            expires_at: 100,
+            ..CollectionExtension::new(collection)
+        };
+
+        // Act
+        registrar_extension.set_collection_extension(collection_extension);
+        registrar_extension.set_collection_extension(collection_extension);
+
+        // Assert
+        assert_eq!(registrar_extension.collection_extensions, vec![collection_extension]);
+        assert_eq!(registrar_extension.get_collection_extension(&collection), collection_extension);
+        assert_eq!(
+            registrar_extension.get_collection_extension(&Pubkey::default()),
+            CollectionExtension::default()
+        );
+    }
+
+    #[test]
+    fn test_has_legacy_nft_vote_records() {
+        // Arrange
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+
+        // Act
+        let has_legacy_nft_vote_records = registrar_extension.has_legacy_nft_vote_records(Some(100));
+
+        registrar_extension.legacy_nft_vote_records_cutoff = 100;
+
+        // Assert
+        assert!(!has_legacy_nft_vote_records);
+        assert!(registrar_extension.has_legacy_nft_vote_records(Some(100)));
+        assert!(!registrar_extension.has_legacy_nft_vote_records(Some(101)));
+        assert!(!registrar_extension.has_legacy_nft_vote_records(None));
+    }
+
+    #[test]
+    fn test_get_registered_voters_max_weight() {
+        // Arrange
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+        registrar_extension.registered_voters = 3;
+        registrar_extension.config.min_weight_if_registered_before_slot = 10;
+
+        // Act
+        let registered_voters_max_weight = registrar_extension.get_registered_voters_max_weight().unwrap();
+
+        // Assert
+        assert_eq!(registered_voters_max_weight, 30);
+
+        registrar_extension.config.min_weight_if_registered_before_slot = u64::MAX;
+        assert_eq!(
+            registrar_extension.get_registered_voters_max_weight().err().unwrap(),
+            NftVoterError::ArithmeticOverflow.into()
+        );
+    }
+
+    #[test]
+    fn test_set_collection_extension_with_default_settings() {
+        // Arrange
+        let collection = Pubkey::new_unique();
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+
+        registrar_extension.set_collection_extension(CollectionExtension {
+            expires_at: 100,
+            ..CollectionExtension::new(collection)
+        });
+
+        // Act
+        registrar_extension.set_collection_extension(CollectionExtension::new(collection));
+
+        // Assert
+        assert!(registrar_extension.collection_extensions.is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// The number of RegistrarMetrics shards the voters of a Registrar are spread across
+pub const REGISTRAR_METRICS_SHARDS: u8 = 16;
+
+/// Cumulative activity counters of a shard of the voters of the Registrar
+/// They are updated by the vote instructions and let Realms display the plugin usage without an indexer
+/// and cranks prioritize busy Registrars
+///
+/// The counters are kept out of the Registrar and spread across shards so the vote instructions
+/// don't write lock the Registrar or a single account shared by all the voters
+/// The activity of the Registrar is the sum of the counters (and the max of last_activity_slot) of all its shards
+///
+/// The PDA of the shard is ["registrar-metrics",registrar,shard] where shard is get_registrar_metrics_shard(governing_token_owner)
+#[account]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RegistrarMetrics {
+    /// The Registrar the counters are for
+    pub registrar: Pubkey,
+
+    /// The shard of the voters the counters are for
+    pub shard: u8,
+
+    /// Total number of NFT votes (NftVoteRecords) cast across all Proposals
+    pub total_votes_cast: u64,
+
+    /// Total voter weight granted by the vote instructions across all Proposals
+    pub total_weight_granted: u64,
+
+    /// The slot of the last vote instruction
+    pub last_activity_slot: u64,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 16],
+}
+
+impl RegistrarMetrics {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES + 1 + 8 + 8 + 8 + 16
+    }
+
+    /// Records the given number of NFT votes and the weight they granted at the given slot
+    /// The counters saturate instead of failing the vote
+    pub fn record_votes(&mut self, votes: u64, weight: u64, slot: u64) {
+        self.total_votes_cast = self.total_votes_cast.saturating_add(votes);
+        self.total_weight_granted = self.total_weight_granted.saturating_add(weight);
+        self.last_activity_slot = slot;
+    }
+}
+
+/// Returns the RegistrarMetrics shard the votes of the given voter are counted in
+pub fn get_registrar_metrics_shard(governing_token_owner: &Pubkey) -> u8 {
+    governing_token_owner.to_bytes()[0] % REGISTRAR_METRICS_SHARDS
+}
+
+/// Returns RegistrarMetrics PDA seeds
+pub fn get_registrar_metrics_seeds<'a>(registrar: &'a Pubkey, shard: &'a [u8; 1]) -> [&'a [u8]; 3] {
+    [b"registrar-metrics", registrar.as_ref(), shard]
+}
+
+/// Returns RegistrarMetrics PDA address of the shard of the given voter
+pub fn get_registrar_metrics_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    let shard = [get_registrar_metrics_shard(governing_token_owner)];
+
+    Pubkey::find_program_address(&get_registrar_metrics_seeds(registrar, &shard), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_votes() {
+        // Arrange
+        let mut metrics = RegistrarMetrics {
+            total_votes_cast: 2,
+            total_weight_granted: u64::MAX - 1,
+            last_activity_slot: 10,
+            ..Default::default()
+        };
+
+        // Act
+        metrics.record_votes(3, 5, 20);
+
+        // Assert
+        assert_eq!(metrics, RegistrarMetrics {
+            total_votes_cast: 5,
+            total_weight_granted: u64::MAX,
+            last_activity_slot: 20,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_get_registrar_metrics_shard() {
+        // Arrange
+        let mut voter_bytes = [0u8; 32];
+        voter_bytes[0] = REGISTRAR_METRICS_SHARDS + 3;
+
+        // Act
+        let shard = get_registrar_metrics_shard(&Pubkey::new_from_array(voter_bytes));
+
+        // Assert
+        assert_eq!(shard, 3);
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    id,
+    state::{ CollectionConfig, RegistrarConfig },
+    tools::anchor::DISCRIMINATOR_SIZE,
+};
+
+/// Template of NFT voting configuration which Registrars of many Realms can be created from
+/// It allows protocols which spin up many sub-realms to gate them with identical collections
+/// The template is maintained by its authority and changes don't affect already created Registrars
+/// The PDA of the template is ["registrar-template",authority,template_id]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct RegistrarTemplate {
+    /// The authority which can configure the template
+    pub authority: Pubkey,
+
+    /// The id of the template which allows an authority to maintain many templates
+    pub template_id: u64,
+
+    /// The max number of collections the template account was allocated for
+    pub max_collections: u8,
+
+    /// The collections copied to the Registrars created from the template
+    pub collection_configs: Vec<CollectionConfig>,
+
+    /// The voting configuration copied to the Registrars created from the template
+    pub config: RegistrarConfig,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 64],
+}
+
+impl RegistrarTemplate {
+    /// Returns the size of RegistrarTemplate with capacity for the given number of collections
+    pub const fn space_for(max_collections: u8) -> usize {
+        DISCRIMINATOR_SIZE +
+            PUBKEY_BYTES +
+            8 +
+            1 +
+            4 +
+            (max_collections as usize) * CollectionConfig::get_space() +
+            RegistrarConfig::get_space() +
+            64
+    }
+}
+
+/// Returns RegistrarTemplate PDA seeds
+pub fn get_registrar_template_seeds<'a>(
+    authority: &'a Pubkey,
+    template_id: &'a [u8; 8]
+) -> [&'a [u8]; 3] {
+    [b"registrar-template", authority.as_ref(), template_id]
+}
+
+/// Returns RegistrarTemplate PDA address
+pub fn get_registrar_template_address(authority: &Pubkey, template_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_registrar_template_seeds(authority, &template_id.to_le_bytes()),
+        &id()
+    ).0
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use solana_program::program::set_return_data;
+
+use crate::state::{ Registrar, RegistrarExtension };
+
+/// Bitmask of the misconfigurations of a Registrar returned by validate_registrar
+/// The warnings don't prevent the Registrar from being used but they likely make the voting power
+/// different from what the realm authority intended
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegistrarWarnings {
+    pub bits: u32,
+}
+
+impl RegistrarWarnings {
+    /// A collection is configured with weight 0 and its NFTs have no voting power
+    pub const ZERO_WEIGHT_COLLECTION: u32 = 1 << 0;
+
+    /// A collection is configured with size 0 and a nonzero weight
+    /// Its NFTs have voting power which isn't included in the max voter weight
+    pub const ZERO_SIZE_COLLECTION: u32 = 1 << 1;
+
+    /// The max voter weight of the collections and the sub-DAO Registrars doesn't fit into u64
+    pub const MAX_VOTER_WEIGHT_OVERFLOW: u32 = 1 << 2;
+
+    /// A collection is expired and should be removed with prune_expired_collections
+    pub const EXPIRED_COLLECTION: u32 = 1 << 3;
+
+    pub fn contains(&self, warning: u32) -> bool {
+        self.bits & warning == warning
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Sets the warnings as the instruction return data
+    pub fn set(&self) -> Result<()> {
+        set_return_data(&self.try_to_vec()?);
+        Ok(())
+    }
+}
+
+/// Returns the warnings of the Registrar configuration at the given unix timestamp
+pub fn get_registrar_warnings(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
+    unix_timestamp: i64
+) -> RegistrarWarnings {
+    let mut bits = 0;
+
+    for collection_config in registrar.collection_configs.iter() {
+        if collection_config.weight == 0 {
+            bits |= RegistrarWarnings::ZERO_WEIGHT_COLLECTION;
+        } else if collection_config.size == 0 {
+            bits |= RegistrarWarnings::ZERO_SIZE_COLLECTION;
+        }
+
+        let collection_extension = registrar_extension.get_collection_extension(&collection_config.collection);
+
+        if collection_extension.is_expired(unix_timestamp) {
+            bits |= RegistrarWarnings::EXPIRED_COLLECTION;
+        }
+    }
+
+    if registrar.get_max_voter_weight(registrar_extension).is_err() {
+        bits |= RegistrarWarnings::MAX_VOTER_WEIGHT_OVERFLOW;
+    }
+
+    RegistrarWarnings { bits }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::{ CollectionConfig, CollectionExtension };
+
+    fn create_test_registrar(collection_configs: Vec<CollectionConfig>) -> Registrar {
+        Registrar {
+            governance_program_id: Pubkey::new_unique(),
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            collection_configs,
+            reserved: [0; 128],
+        }
+    }
+
+    #[test]
+    fn test_get_registrar_warnings() {
+        // Arrange
+        let registrar = create_test_registrar(
+            vec![CollectionConfig {
+                collection: Pubkey::new_unique(),
+                size: 10,
+                weight: 1,
+                ..Default::default()
+            }]
+        );
+
+        // Act
+        let warnings = get_registrar_warnings(
+            &registrar,
+            &RegistrarExtension::new(Pubkey::new_unique()),
+            0
+        );
+
+        // Assert
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_get_registrar_warnings_with_misconfigured_collections() {
+        // Arrange
+        let expired_collection = Pubkey::new_unique();
+
+        let registrar = create_test_registrar(
+            vec![
+                CollectionConfig {
+                    collection: Pubkey::new_unique(),
+                    size: 10,
+                    weight: 0,
+                    ..Default::default()
+                },
+                CollectionConfig {
+                    collection: expired_collection,
+                    size: 0,
+                    weight: 1,
+                    ..Default::default()
+                }
+            ]
+        );
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::new_unique());
+        registrar_extension.set_collection_extension(CollectionExtension {
+            expires_at: 100,
+            ..CollectionExtension::new(expired_collection)
+        });
+
+        // Act
+        let warnings = get_registrar_warnings(&registrar, &registrar_extension, 100);
+
+        // Assert
+        assert_eq!(
+            warnings.bits,
+            RegistrarWarnings::ZERO_WEIGHT_COLLECTION |
+                RegistrarWarnings::ZERO_SIZE_COLLECTION |
+                RegistrarWarnings::EXPIRED_COLLECTION
+        );
+    }
+
+    #[test]
+    fn test_get_registrar_warnings_with_max_voter_weight_overflow() {
+        // Arrange
+        let mut registrar = create_test_registrar(
+            vec![CollectionConfig {
+                collection: Pubkey::new_unique(),
+                size: 2,
+                weight: u64::MAX / 2 + 1,
+                ..Default::default()
+            }]
+        );
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::new_unique());
+
+        // Act
+        let warnings = get_registrar_warnings(&registrar, &registrar_extension, 0);
+
+        registrar.collection_configs[0].size = 1;
+        registrar_extension.sub_registrars_max_voter_weight = u64::MAX;
+
+        let sub_registrars_warnings = get_registrar_warnings(&registrar, &registrar_extension, 0);
+
+        // Assert
+        assert!(warnings.contains(RegistrarWarnings::MAX_VOTER_WEIGHT_OVERFLOW));
+        assert!(sub_registrars_warnings.contains(RegistrarWarnings::MAX_VOTER_WEIGHT_OVERFLOW));
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ id, state::MAX_BASIS_POINTS, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// Link of a sub-DAO Registrar whose voter weight rolls up into the parent Registrar
+/// The link is approved by the parent Realm authority using configure_sub_registrar
+/// and the sub-DAO Registrar must point to the parent using RegistrarExtension.config.parent_registrar
+/// The PDA of the config is ["sub-registrar-config",parent_registrar,sub_registrar]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct SubRegistrarConfig {
+    /// The parent Registrar the weight rolls up into
+    pub parent_registrar: Pubkey,
+
+    /// The sub-DAO Registrar the weight is earned in
+    pub sub_registrar: Pubkey,
+
+    /// The scale applied to the sub-DAO weight expressed in basis points where 10_000 means 1x
+    pub weight_scale_bps: u32,
+
+    /// The scaled max voter weight of the sub-DAO last synced into the parent Registrar
+    pub max_voter_weight: u64,
+}
+
+impl SubRegistrarConfig {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 4 + 8
+    }
+
+    /// Applies the weight scale to the given sub-DAO weight
+    pub fn apply_weight_scale(&self, weight: u64) -> u64 {
+        (((weight as u128) * (self.weight_scale_bps as u128)) / (MAX_BASIS_POINTS as u128)) as u64
+    }
+}
+
+/// Returns SubRegistrarConfig PDA seeds
+pub fn get_sub_registrar_config_seeds<'a>(
+    parent_registrar: &'a Pubkey,
+    sub_registrar: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"sub-registrar-config", parent_registrar.as_ref(), sub_registrar.as_ref()]
+}
+
+/// Returns SubRegistrarConfig PDA address
+pub fn get_sub_registrar_config_address(parent_registrar: &Pubkey, sub_registrar: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_sub_registrar_config_seeds(parent_registrar, sub_registrar),
+        &id()
+    ).0
+}
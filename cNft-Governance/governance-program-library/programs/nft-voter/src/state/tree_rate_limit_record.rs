@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
+use solana_program::program_pack::IsInitialized;
+
+use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
+
+use crate::{ error::NftVoterError, id };
+
+/// The number of the most recent slots tracked by TreeRateLimitRecord
+pub const TREE_RATE_LIMIT_SLOTS: usize = 8;
+
+/// The number of compressed NFTs a voter submitted from a tree in the given slot
+#[derive(Clone, Copy, Debug, PartialEq, Default, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TreeSlotCount {
+    /// The slot the compressed NFTs were submitted at
+    pub slot: u64,
+
+    /// The number of compressed NFTs submitted in the slot
+    pub count: u16,
+}
+
+/// Rate limit of the compressed NFTs a voter can submit from the given tree
+/// The PDA of the record is ["tree-rate-limit-record",registrar,tree,governing_token_owner]
+/// It's updated by create_cnft_action_ticket when RegistrarExtension.config.max_tree_assets_per_slot is set
+/// and it caps the number of merkle proofs a voter can make the program verify for a tree within a slot
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TreeRateLimitRecord {
+    /// TreeRateLimitRecord discriminator sha256("account:TreeRateLimitRecord")[..8]
+    /// Note: The discriminator is used explicitly because TreeRateLimitRecords
+    /// are created and consumed dynamically using remaining_accounts
+    pub account_discriminator: [u8; 8],
+
+    /// The Registrar the rate limit is for
+    pub registrar: Pubkey,
+
+    /// The merkle tree the rate limit is for
+    pub tree: Pubkey,
+
+    /// The voter the rate limit is for
+    pub governing_token_owner: Pubkey,
+
+    /// Ring buffer of the most recent slots the voter submitted compressed NFTs of the tree at
+    pub recent_slots: [TreeSlotCount; TREE_RATE_LIMIT_SLOTS],
+
+    /// The index of the most recent slot in recent_slots
+    pub head: u8,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 8],
+}
+
+impl TreeRateLimitRecord {
+    /// sha256("account:TreeRateLimitRecord")[..8]
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [35, 48, 72, 185, 193, 157, 255, 59];
+
+    pub fn new(registrar: Pubkey, tree: Pubkey, governing_token_owner: Pubkey) -> Self {
+        Self {
+            account_discriminator: TreeRateLimitRecord::ACCOUNT_DISCRIMINATOR,
+            registrar,
+            tree,
+            governing_token_owner,
+            recent_slots: [TreeSlotCount::default(); TREE_RATE_LIMIT_SLOTS],
+            head: 0,
+            reserved: [0; 8],
+        }
+    }
+
+    /// Returns the number of compressed NFTs submitted in the given slot
+    pub fn get_slot_count(&self, slot: u64) -> u16 {
+        let head = &self.recent_slots[self.head as usize];
+
+        if head.slot == slot {
+            head.count
+        } else {
+            0
+        }
+    }
+
+    /// Records a compressed NFT submitted in the given slot and asserts the slot cap isn't exceeded
+    pub fn record_submission(&mut self, slot: u64, max_per_slot: u16) -> Result<()> {
+        require!(
+            self.get_slot_count(slot) < max_per_slot,
+            NftVoterError::TreeRateLimitExceeded
+        );
+
+        if self.recent_slots[self.head as usize].slot != slot {
+            self.head = ((self.head as usize + 1) % TREE_RATE_LIMIT_SLOTS) as u8;
+            self.recent_slots[self.head as usize] = TreeSlotCount { slot, count: 0 };
+        }
+
+        let head = &mut self.recent_slots[self.head as usize];
+        head.count = head.count.checked_add(1).ok_or(NftVoterError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
+
+impl AccountMaxSize for TreeRateLimitRecord {}
+
+impl IsInitialized for TreeRateLimitRecord {
+    fn is_initialized(&self) -> bool {
+        self.account_discriminator == TreeRateLimitRecord::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+/// Returns TreeRateLimitRecord PDA seeds
+pub fn get_tree_rate_limit_record_seeds<'a>(
+    registrar: &'a Pubkey,
+    tree: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [
+        b"tree-rate-limit-record",
+        registrar.as_ref(),
+        tree.as_ref(),
+        governing_token_owner.as_ref(),
+    ]
+}
+
+/// Returns TreeRateLimitRecord PDA address
+pub fn get_tree_rate_limit_record_address(
+    registrar: &Pubkey,
+    tree: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_tree_rate_limit_record_seeds(registrar, tree, governing_token_owner),
+        &id()
+    ).0
+}
+
+/// Deserializes account and checks owner program
+pub fn get_tree_rate_limit_record_data(
+    tree_rate_limit_record_info: &AccountInfo
+) -> Result<TreeRateLimitRecord> {
+    Ok(get_account_data::<TreeRateLimitRecord>(&id(), tree_rate_limit_record_info)?)
+}
+
+pub fn get_tree_rate_limit_record_data_for_tree_and_voter(
+    tree_rate_limit_record_info: &AccountInfo,
+    registrar: &Pubkey,
+    tree: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Result<TreeRateLimitRecord> {
+    let tree_rate_limit_record = get_tree_rate_limit_record_data(tree_rate_limit_record_info)?;
+
+    require!(
+        tree_rate_limit_record.registrar == *registrar &&
+            tree_rate_limit_record.tree == *tree &&
+            tree_rate_limit_record.governing_token_owner == *governing_token_owner,
+        NftVoterError::InvalidTreeRateLimitRecord
+    );
+
+    Ok(tree_rate_limit_record)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_submission_within_slot_cap() {
+        // Arrange
+        let mut tree_rate_limit_record = TreeRateLimitRecord::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique()
+        );
+
+        // Act
+        tree_rate_limit_record.record_submission(10, 2).unwrap();
+        tree_rate_limit_record.record_submission(10, 2).unwrap();
+        let result = tree_rate_limit_record.record_submission(10, 2);
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(tree_rate_limit_record.get_slot_count(10), 2);
+        assert_eq!(tree_rate_limit_record.get_slot_count(11), 0);
+    }
+
+    #[test]
+    fn test_record_submission_wraps_recent_slots() {
+        // Arrange
+        let mut tree_rate_limit_record = TreeRateLimitRecord::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique()
+        );
+
+        // Act
+        for slot in 1..=(TREE_RATE_LIMIT_SLOTS as u64) + 2 {
+            tree_rate_limit_record.record_submission(slot, 1).unwrap();
+        }
+
+        // Assert
+        let mut slots = tree_rate_limit_record.recent_slots
+            .iter()
+            .map(|s| s.slot)
+            .collect::<Vec<_>>();
+        slots.sort_unstable();
+
+        assert_eq!(slots, (3..=(TREE_RATE_LIMIT_SLOTS as u64) + 2).collect::<Vec<_>>());
+        assert_eq!(tree_rate_limit_record.get_slot_count((TREE_RATE_LIMIT_SLOTS as u64) + 2), 1);
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+use spl_governance::state::vote_record::Vote;
+
+use crate::{ id, state::NftVoteKind, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// Record of the vote receipt cNFT minted to the voter by mint_vote_receipt
+/// The record prevents minting more than a single receipt for the vote of the voter on the Proposal
+/// The PDA of the receipt is ["vote-receipt",registrar,proposal,governing_token_owner]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoteReceipt {
+    /// The Registrar the receipt was minted for
+    pub registrar: Pubkey,
+
+    /// The Proposal the voter voted on
+    pub proposal: Pubkey,
+
+    /// The voter the receipt was minted to
+    pub governing_token_owner: Pubkey,
+
+    /// The kind of the Vote of the voter
+    pub vote_kind: NftVoteKind,
+
+    /// The id of the receipt cNFT
+    pub asset_id: Pubkey,
+}
+
+impl VoteReceipt {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 1 + PUBKEY_BYTES
+    }
+}
+
+/// Returns the uri of the vote receipt which encodes the Proposal and the Vote as query parameters of the given uri
+/// The Vote is encoded as approve-<option indexes>, deny, abstain or veto
+/// ex. https://receipts.dao.com/?proposal=<proposal>&vote=approve-0-2
+pub fn get_vote_receipt_uri(uri: &str, proposal: &Pubkey, vote: &Vote) -> String {
+    let vote = match vote {
+        Vote::Approve(choices) => {
+            // Only the options the voter gave weight to are encoded
+            let options = choices
+                .iter()
+                .enumerate()
+                .filter(|(_, choice)| choice.weight_percentage > 0)
+                .map(|(index, _)| format!("-{}", index))
+                .collect::<String>();
+
+            format!("approve{}", options)
+        }
+        Vote::Deny => "deny".to_string(),
+        Vote::Abstain => "abstain".to_string(),
+        Vote::Veto => "veto".to_string(),
+    };
+
+    let separator = if uri.contains('?') { '&' } else { '?' };
+
+    format!("{}{}proposal={}&vote={}", uri, separator, proposal, vote)
+}
+
+/// Returns VoteReceipt PDA seeds
+pub fn get_vote_receipt_seeds<'a>(
+    registrar: &'a Pubkey,
+    proposal: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [b"vote-receipt", registrar.as_ref(), proposal.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns VoteReceipt PDA address
+pub fn get_vote_receipt_address(
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_vote_receipt_seeds(registrar, proposal, governing_token_owner),
+        &id()
+    ).0
+}
+
+#[cfg(test)]
+mod test {
+    use spl_governance::state::vote_record::VoteChoice;
+
+    use super::*;
+
+    #[test]
+    fn test_get_vote_receipt_uri_with_approve_vote() {
+        // Arrange
+        let proposal = Pubkey::new_unique();
+
+        let vote = Vote::Approve(
+            vec![
+                VoteChoice { rank: 0, weight_percentage: 100 },
+                VoteChoice { rank: 0, weight_percentage: 0 },
+                VoteChoice { rank: 0, weight_percentage: 100 }
+            ]
+        );
+
+        // Act
+        let uri = get_vote_receipt_uri("https://receipts.dao.com/", &proposal, &vote);
+
+        // Assert
+        assert_eq!(uri, format!("https://receipts.dao.com/?proposal={}&vote=approve-0-2", proposal));
+    }
+
+    #[test]
+    fn test_get_vote_receipt_uri_with_query() {
+        // Arrange
+        let proposal = Pubkey::new_unique();
+
+        // Act
+        let uri = get_vote_receipt_uri("https://receipts.dao.com/?dao=1", &proposal, &Vote::Veto);
+
+        // Assert
+        assert_eq!(uri, format!("https://receipts.dao.com/?dao=1&proposal={}&vote=veto", proposal));
+    }
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use borsh::{ BorshDeserialize, BorshSchema, BorshSerialize };
+use solana_program::program_pack::IsInitialized;
+
+use spl_governance_tools::account::{ get_account_data, AccountMaxSize };
+
+use crate::state::{ create_and_serialize_account_with_rent_escrow, RegistrarExtension };
+use crate::{ error::NftVoterError, id };
+
+/// Checkpoint of the voting power the governing_token_owner voted with on the given Proposal
+/// The PDA of the record is ["voter-checkpoint",registrar,proposal,governing_token_owner]
+/// It's written by cast_nft_vote and cast_nft_vote_multi when RegistrarExtension.config.record_voter_checkpoints is set
+/// and lets DAOs reward past participation (ex. airdrops proportional to the historical voting power)
+///
+/// The account has a fixed size and the registrar follows the discriminator so all checkpoints of a Registrar
+/// can be scanned cheaply using getProgramAccounts with dataSize and memcmp filters
+///
+/// Note: The checkpoint isn't removed when the vote is relinquished and the spl-gov VoteRecord
+/// should be checked to exclude withdrawn votes
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct VoterCheckpoint {
+    /// VoterCheckpoint discriminator sha256("account:VoterCheckpoint")[..8]
+    /// Note: The discriminator is used explicitly because VoterCheckpoints
+    /// are created and consumed dynamically using remaining_accounts
+    pub account_discriminator: [u8; 8],
+
+    /// The Registrar the vote was cast through
+    pub registrar: Pubkey,
+
+    /// The Proposal the vote was cast on
+    pub proposal: Pubkey,
+
+    /// The voter who cast the vote
+    pub governing_token_owner: Pubkey,
+
+    /// The voter weight of the vote
+    /// When the vote is accumulated by several cast_nft_vote instructions it's the total weight
+    pub voter_weight: u64,
+
+    /// The slot the voter weight was last recorded at
+    pub slot: u64,
+
+    /// Reserved for future upgrades
+    pub reserved: [u8; 8],
+}
+
+impl VoterCheckpoint {
+    /// sha256("account:VoterCheckpoint")[..8]
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [124, 107, 25, 194, 248, 68, 155, 110];
+
+    pub fn new(registrar: Pubkey, proposal: Pubkey, governing_token_owner: Pubkey) -> Self {
+        Self {
+            account_discriminator: VoterCheckpoint::ACCOUNT_DISCRIMINATOR,
+            registrar,
+            proposal,
+            governing_token_owner,
+            voter_weight: 0,
+            slot: 0,
+            reserved: [0; 8],
+        }
+    }
+
+    pub const fn get_space() -> usize {
+        8 + 32 + 32 + 32 + 8 + 8 + 8
+    }
+}
+
+impl AccountMaxSize for VoterCheckpoint {}
+
+impl IsInitialized for VoterCheckpoint {
+    fn is_initialized(&self) -> bool {
+        self.account_discriminator == VoterCheckpoint::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+/// Returns VoterCheckpoint PDA seeds
+pub fn get_voter_checkpoint_seeds<'a>(
+    registrar: &'a Pubkey,
+    proposal: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [b"voter-checkpoint", registrar.as_ref(), proposal.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns VoterCheckpoint PDA address
+pub fn get_voter_checkpoint_address(
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_checkpoint_seeds(registrar, proposal, governing_token_owner),
+        &id()
+    ).0
+}
+
+/// Deserializes account and checks owner program
+pub fn get_voter_checkpoint_data(voter_checkpoint_info: &AccountInfo) -> Result<VoterCheckpoint> {
+    Ok(get_account_data::<VoterCheckpoint>(&id(), voter_checkpoint_info)?)
+}
+
+pub fn get_voter_checkpoint_data_for_vote(
+    voter_checkpoint_info: &AccountInfo,
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Result<VoterCheckpoint> {
+    let voter_checkpoint = get_voter_checkpoint_data(voter_checkpoint_info)?;
+
+    require!(
+        voter_checkpoint.registrar == *registrar &&
+            voter_checkpoint.proposal == *proposal &&
+            voter_checkpoint.governing_token_owner == *governing_token_owner,
+        NftVoterError::InvalidVoterCheckpoint
+    );
+
+    Ok(voter_checkpoint)
+}
+
+/// Splits the VoterCheckpoints of the given number of Proposals from the front of the remaining accounts
+/// when RegistrarExtension.config.record_voter_checkpoints is set
+pub fn split_voter_checkpoint_accounts<'a, 'info>(
+    registrar_extension: &RegistrarExtension,
+    proposals_count: usize,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    if !registrar_extension.config.record_voter_checkpoints {
+        return Ok((&[], remaining_accounts));
+    }
+
+    require!(
+        remaining_accounts.len() >= proposals_count,
+        NftVoterError::MissingVoterCheckpoint
+    );
+
+    Ok(remaining_accounts.split_at(proposals_count))
+}
+
+/// Records the voter weight of the governing_token_owner's vote on the Proposal at the given slot
+/// The VoterCheckpoint is created by the first vote and overwritten by the following votes
+/// Note: The correct PDA of a new VoterCheckpoint is validated in create_and_serialize_account_with_rent_escrow
+pub fn record_voter_checkpoint<'a>(
+    payer_info: &AccountInfo<'a>,
+    voter_rent_escrow_info: Option<&AccountInfo<'a>>,
+    voter_checkpoint_info: &AccountInfo<'a>,
+    registrar: &Pubkey,
+    proposal: &Pubkey,
+    governing_token_owner: &Pubkey,
+    voter_weight: u64,
+    slot: u64,
+    system_program_info: &AccountInfo<'a>,
+    rent: &Rent
+) -> Result<()> {
+    let mut voter_checkpoint = if voter_checkpoint_info.data_is_empty() {
+        let voter_checkpoint = VoterCheckpoint::new(*registrar, *proposal, *governing_token_owner);
+
+        create_and_serialize_account_with_rent_escrow(
+            payer_info,
+            voter_rent_escrow_info,
+            voter_checkpoint_info,
+            &voter_checkpoint,
+            &get_voter_checkpoint_seeds(registrar, proposal, governing_token_owner),
+            system_program_info,
+            rent
+        )?;
+
+        voter_checkpoint
+    } else {
+        get_voter_checkpoint_data_for_vote(
+            voter_checkpoint_info,
+            registrar,
+            proposal,
+            governing_token_owner
+        )?
+    };
+
+    voter_checkpoint.voter_weight = voter_weight;
+    voter_checkpoint.slot = slot;
+
+    voter_checkpoint_info.data.borrow_mut().copy_from_slice(&voter_checkpoint.try_to_vec()?);
+
+    Ok(())
+}
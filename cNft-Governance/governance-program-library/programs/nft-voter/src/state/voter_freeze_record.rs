@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// Emergency freeze of the voter created by the Realm authority
+/// While the record exists the voter can't cast votes or update its voter weight
+/// It's an incident response tool for wallets which were compromised and whose NFTs could be used to vote
+/// The PDA of the record is ["voter-freeze-record",registrar,governing_token_owner]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterFreezeRecord {
+    /// The Registrar the record belongs to
+    pub registrar: Pubkey,
+
+    /// The frozen voter
+    pub governing_token_owner: Pubkey,
+
+    /// The slot the voter was frozen at
+    pub frozen_at: u64,
+}
+
+impl VoterFreezeRecord {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 8
+    }
+}
+
+/// Returns VoterFreezeRecord PDA seeds
+pub fn get_voter_freeze_record_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"voter-freeze-record", registrar.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns VoterFreezeRecord PDA address
+pub fn get_voter_freeze_record_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_freeze_record_seeds(registrar, governing_token_owner),
+        &id()
+    ).0
+}
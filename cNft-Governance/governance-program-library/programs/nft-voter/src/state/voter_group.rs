@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::state::get_voter_freeze_record_address;
+use crate::{ error::NftVoterError, id, tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE } };
+
+/// The max number of wallets which can join a VoterGroup besides the voting wallet
+pub const MAX_VOTER_GROUP_MEMBERS: usize = 8;
+
+/// Group of wallets (household) whose NFT action tickets are aggregated into the VoterWeightRecord of the voting wallet
+/// Every member joins the group by signing together with the voting wallet and can leave it at any time
+/// The PDA of the group is ["voter-group",registrar,voting_wallet]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterGroup {
+    /// The Registrar the group belongs to
+    pub registrar: Pubkey,
+
+    /// The wallet which votes with the weight of the group
+    pub voting_wallet: Pubkey,
+
+    /// The wallets whose tickets can be consumed by the voting wallet
+    pub members: Vec<Pubkey>,
+}
+
+impl VoterGroup {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 4 + PUBKEY_BYTES * MAX_VOTER_GROUP_MEMBERS
+    }
+
+    /// Adds the member to the group
+    pub fn add_member(&mut self, member: &Pubkey) -> Result<()> {
+        require!(
+            *member != self.voting_wallet && !self.members.contains(member),
+            NftVoterError::InvalidVoterGroupMember
+        );
+        require!(self.members.len() < MAX_VOTER_GROUP_MEMBERS, NftVoterError::VoterGroupFull);
+
+        self.members.push(*member);
+
+        Ok(())
+    }
+
+    /// Removes the member from the group
+    pub fn remove_member(&mut self, member: &Pubkey) -> Result<()> {
+        let index = self.members
+            .iter()
+            .position(|m| m == member)
+            .ok_or(NftVoterError::InvalidVoterGroupMember)?;
+
+        self.members.remove(index);
+
+        Ok(())
+    }
+}
+
+/// Returns VoterGroup PDA seeds
+pub fn get_voter_group_seeds<'a>(registrar: &'a Pubkey, voting_wallet: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"voter-group", registrar.as_ref(), voting_wallet.as_ref()]
+}
+
+/// Returns VoterGroup PDA address
+pub fn get_voter_group_address(registrar: &Pubkey, voting_wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_voter_group_seeds(registrar, voting_wallet), &id()).0
+}
+
+/// Splits the VoterGroup of the voting wallet followed by the VoterFreezeRecords of its members
+/// from the front of the remaining accounts when use_voter_group is set
+/// Returns the wallets whose tickets can be consumed for the voting wallet (the voting wallet and the group members)
+///
+/// Note: The VoterFreezeRecords must not exist, the same way as the VoterFreezeRecord of the voting wallet,
+/// so frozen wallets can't contribute weight through a group
+pub fn split_voter_group_accounts<'a, 'info>(
+    use_voter_group: bool,
+    registrar: &Pubkey,
+    voting_wallet: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(Vec<Pubkey>, &'a [AccountInfo<'info>])> {
+    if !use_voter_group {
+        return Ok((vec![*voting_wallet], remaining_accounts));
+    }
+
+    let (voter_group_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::InvalidVoterGroup)?;
+
+    let voter_group = get_anchor_account_data::<VoterGroup>(voter_group_info)?;
+
+    require!(
+        voter_group.registrar == *registrar &&
+            voter_group.voting_wallet == *voting_wallet &&
+            *voter_group_info.key == get_voter_group_address(registrar, voting_wallet),
+        NftVoterError::InvalidVoterGroup
+    );
+
+    require!(
+        remaining_accounts.len() >= voter_group.members.len(),
+        NftVoterError::InvalidVoterGroup
+    );
+
+    let (voter_freeze_records, remaining_accounts) = remaining_accounts.split_at(
+        voter_group.members.len()
+    );
+
+    for (member, voter_freeze_record_info) in voter_group.members.iter().zip(voter_freeze_records) {
+        require!(
+            *voter_freeze_record_info.key == get_voter_freeze_record_address(registrar, member),
+            NftVoterError::InvalidVoterGroup
+        );
+        require!(voter_freeze_record_info.data_is_empty(), NftVoterError::VoterFrozen);
+    }
+
+    let mut governing_token_owners = vec![*voting_wallet];
+    governing_token_owners.extend_from_slice(&voter_group.members);
+
+    Ok((governing_token_owners, remaining_accounts))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_voter_group() -> VoterGroup {
+        VoterGroup {
+            registrar: Pubkey::new_unique(),
+            voting_wallet: Pubkey::new_unique(),
+            members: vec![],
+        }
+    }
+
+    #[test]
+    fn test_add_member() {
+        // Arrange
+        let mut voter_group = create_test_voter_group();
+        let member = Pubkey::new_unique();
+        let voting_wallet = voter_group.voting_wallet;
+
+        // Act + Assert
+        assert!(voter_group.add_member(&member).is_ok());
+        assert!(voter_group.add_member(&member).is_err());
+        assert!(voter_group.add_member(&voting_wallet).is_err());
+
+        for _ in 1..MAX_VOTER_GROUP_MEMBERS {
+            assert!(voter_group.add_member(&Pubkey::new_unique()).is_ok());
+        }
+
+        assert!(voter_group.add_member(&Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_remove_member() {
+        // Arrange
+        let mut voter_group = create_test_voter_group();
+        let member = Pubkey::new_unique();
+        voter_group.add_member(&member).unwrap();
+
+        // Act + Assert
+        assert!(voter_group.remove_member(&member).is_ok());
+        assert!(voter_group.remove_member(&member).is_err());
+        assert!(voter_group.members.is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// Membership of a wallet in a VoterGroup created by join_voter_group and closed by leave_voter_group
+/// The PDA is keyed by the wallet alone and hence a wallet can be a member of only one VoterGroup of the Registrar
+/// The PDA of the membership is ["voter-group-membership",registrar,member]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterGroupMembership {
+    /// The Registrar the membership belongs to
+    pub registrar: Pubkey,
+
+    /// The VoterGroup the wallet is a member of
+    pub voter_group: Pubkey,
+
+    /// The member wallet
+    pub member: Pubkey,
+}
+
+impl VoterGroupMembership {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3
+    }
+}
+
+/// Returns VoterGroupMembership PDA seeds
+pub fn get_voter_group_membership_seeds<'a>(registrar: &'a Pubkey, member: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"voter-group-membership", registrar.as_ref(), member.as_ref()]
+}
+
+/// Returns VoterGroupMembership PDA address
+pub fn get_voter_group_membership_address(registrar: &Pubkey, member: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_voter_group_membership_seeds(registrar, member), &id()).0
+}
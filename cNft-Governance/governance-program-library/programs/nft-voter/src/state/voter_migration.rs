@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ error::NftVoterError, id, tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE } };
+
+/// Record of a voter who moved to a new wallet using migrate_voter
+/// The record links the new wallet to the previous wallet and lets the compressed NFTs moved
+/// from the previous wallet keep the holding period attested for the previous wallet
+/// The PDA of the record is ["voter-migration",registrar,new_wallet]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterMigration {
+    /// The Registrar the voter migrated within
+    pub registrar: Pubkey,
+
+    /// The wallet the voter migrated from
+    pub previous_wallet: Pubkey,
+
+    /// The wallet the voter migrated to
+    pub new_wallet: Pubkey,
+
+    /// The slot the voter migrated at
+    pub migrated_at_slot: u64,
+}
+
+impl VoterMigration {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 8
+    }
+
+    /// Returns the previous wallet if the asset held by leaf_owner can be attested as held by the previous wallet
+    /// Only assets received by the previous wallet before the migration keep its holding period
+    pub fn get_previous_holder(&self, leaf_owner: &Pubkey, last_transfer_slot: u64) -> Option<Pubkey> {
+        if self.new_wallet == *leaf_owner && last_transfer_slot <= self.migrated_at_slot {
+            Some(self.previous_wallet)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns VoterMigration PDA seeds
+pub fn get_voter_migration_seeds<'a>(registrar: &'a Pubkey, new_wallet: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"voter-migration", registrar.as_ref(), new_wallet.as_ref()]
+}
+
+/// Returns VoterMigration PDA address
+pub fn get_voter_migration_address(registrar: &Pubkey, new_wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_voter_migration_seeds(registrar, new_wallet), &id()).0
+}
+
+/// Deserializes VoterMigration of the given wallet or returns None if the wallet wasn't migrated to
+pub fn get_voter_migration_data_for_new_wallet(
+    voter_migration_info: &AccountInfo,
+    registrar: &Pubkey,
+    new_wallet: &Pubkey
+) -> Result<Option<VoterMigration>> {
+    require_keys_eq!(
+        *voter_migration_info.key,
+        get_voter_migration_address(registrar, new_wallet),
+        NftVoterError::InvalidVoterMigration
+    );
+
+    if voter_migration_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let voter_migration = get_anchor_account_data::<VoterMigration>(voter_migration_info)?;
+
+    Ok(Some(voter_migration))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_previous_holder() {
+        // Arrange
+        let voter_migration = VoterMigration {
+            registrar: Pubkey::new_unique(),
+            previous_wallet: Pubkey::new_unique(),
+            new_wallet: Pubkey::new_unique(),
+            migrated_at_slot: 10,
+        };
+
+        // Act + Assert
+        assert_eq!(
+            voter_migration.get_previous_holder(&voter_migration.new_wallet, 10),
+            Some(voter_migration.previous_wallet)
+        );
+        assert_eq!(voter_migration.get_previous_holder(&voter_migration.new_wallet, 11), None);
+        assert_eq!(voter_migration.get_previous_holder(&Pubkey::new_unique(), 5), None);
+    }
+}
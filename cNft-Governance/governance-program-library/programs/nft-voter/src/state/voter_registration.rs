@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    error::NftVoterError,
+    id,
+    state::{ Registrar, RegistrarConfig, RegistrarExtension },
+    tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE },
+};
+
+/// Record of the first registration of a voter with the Registrar
+/// Voters registered before RegistrarConfig.registration_cutoff_slot keep the floor weight
+/// RegistrarConfig.min_weight_if_registered_before_slot even if they later reduce their holdings
+/// The PDA of the registration is ["voter-registration",registrar,governing_token_owner]
+///
+/// Note: The registration can't be closed or re-created and hence registered_at_slot is the slot of the first registration
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterRegistration {
+    /// The Registrar the voter is registered with
+    pub registrar: Pubkey,
+
+    /// The registered voter
+    pub governing_token_owner: Pubkey,
+
+    /// The slot the voter registered at
+    pub registered_at_slot: u64,
+}
+
+impl VoterRegistration {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 8
+    }
+
+    /// Returns the floor weight of the voter or 0 if the voter registered at or after the cutoff slot
+    pub fn get_min_voter_weight(&self, config: &RegistrarConfig) -> u64 {
+        if self.registered_at_slot < config.registration_cutoff_slot {
+            config.min_weight_if_registered_before_slot
+        } else {
+            0
+        }
+    }
+}
+
+/// Returns VoterRegistration PDA seeds
+pub fn get_voter_registration_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"voter-registration", registrar.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns VoterRegistration PDA address
+pub fn get_voter_registration_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_registration_seeds(registrar, governing_token_owner),
+        &id()
+    ).0
+}
+
+/// Splits the VoterRegistration PDA of the voter from the remaining accounts when the floor weight is enabled
+/// and returns the floor weight of the voter
+/// The PDA must be supplied even if the voter never registered in which case the floor weight is 0
+pub fn split_voter_registration_account<'a, 'info>(
+    registrar: &Account<Registrar>,
+    registrar_extension: &RegistrarExtension,
+    governing_token_owner: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(u64, &'a [AccountInfo<'info>])> {
+    if !registrar_extension.config.is_min_voter_weight_enabled() {
+        return Ok((0, remaining_accounts));
+    }
+
+    let (voter_registration_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::InvalidVoterRegistration)?;
+
+    require!(
+        *voter_registration_info.key ==
+            get_voter_registration_address(&registrar.key(), governing_token_owner),
+        NftVoterError::InvalidVoterRegistration
+    );
+
+    if voter_registration_info.data_is_empty() {
+        return Ok((0, remaining_accounts));
+    }
+
+    let voter_registration = get_anchor_account_data::<VoterRegistration>(voter_registration_info)?;
+
+    Ok((voter_registration.get_min_voter_weight(&registrar_extension.config), remaining_accounts))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_voter_registration() -> VoterRegistration {
+        VoterRegistration {
+            registrar: Pubkey::new_unique(),
+            governing_token_owner: Pubkey::new_unique(),
+            registered_at_slot: 10,
+        }
+    }
+
+    #[test]
+    fn test_get_min_voter_weight() {
+        // Arrange
+        let voter_registration = create_test_voter_registration();
+
+        let mut config = RegistrarConfig {
+            min_weight_if_registered_before_slot: 5,
+            registration_cutoff_slot: 11,
+            ..Default::default()
+        };
+
+        // Act + Assert
+        assert_eq!(voter_registration.get_min_voter_weight(&config), 5);
+
+        config.registration_cutoff_slot = 10;
+        assert_eq!(voter_registration.get_min_voter_weight(&config), 0);
+    }
+}
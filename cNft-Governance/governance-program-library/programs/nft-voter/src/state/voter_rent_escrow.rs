@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use solana_program::program::invoke_signed;
+use solana_program::pubkey::PUBKEY_BYTES;
+use solana_program::system_instruction;
+use spl_governance_tools::account::{ create_and_serialize_account_signed, AccountMaxSize };
+
+use crate::{ error::NftVoterError, id, tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE } };
+
+/// Lamports prefunded by a voter to pay the rent of the accounts created when the voter casts votes
+/// Once funded the votes can be submitted by any fee payer (ex. a session key of a voting UI)
+/// without the voter wallet paying for the NftVoteRecords each time
+///
+/// The escrow is funded by create_voter_rent_escrow and it can be topped up by plain system transfers
+/// The PDA of the escrow is ["voter-rent-escrow",registrar,governing_token_owner]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterRentEscrow {
+    /// The Registrar the escrow belongs to
+    pub registrar: Pubkey,
+
+    /// The voter who funded the escrow and who can withdraw the lamports
+    pub governing_token_owner: Pubkey,
+}
+
+impl VoterRentEscrow {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2
+    }
+}
+
+/// Returns the lamports of the escrow above its own rent exemption
+pub fn get_voter_rent_escrow_available_lamports(
+    voter_rent_escrow_info: &AccountInfo,
+    rent: &Rent
+) -> u64 {
+    voter_rent_escrow_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(voter_rent_escrow_info.data_len()))
+}
+
+/// Returns VoterRentEscrow PDA seeds
+pub fn get_voter_rent_escrow_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"voter-rent-escrow", registrar.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns VoterRentEscrow PDA address
+pub fn get_voter_rent_escrow_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_rent_escrow_seeds(registrar, governing_token_owner),
+        &id()
+    ).0
+}
+
+/// Splits the VoterRentEscrow of the governing_token_owner from the front of the remaining accounts when it's used
+pub fn split_voter_rent_escrow_account<'a, 'info>(
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    use_voter_rent_escrow: bool,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>])> {
+    if !use_voter_rent_escrow {
+        return Ok((None, remaining_accounts));
+    }
+
+    let (voter_rent_escrow_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::InvalidVoterRentEscrow)?;
+
+    let voter_rent_escrow = get_anchor_account_data::<VoterRentEscrow>(voter_rent_escrow_info)?;
+
+    require!(
+        voter_rent_escrow.registrar == *registrar &&
+            voter_rent_escrow.governing_token_owner == *governing_token_owner,
+        NftVoterError::InvalidVoterRentEscrow
+    );
+
+    Ok((Some(voter_rent_escrow_info), remaining_accounts))
+}
+
+/// Creates the PDA account for the given seeds and serializes the data into it
+/// The rent is paid by the VoterRentEscrow when supplied and by the payer otherwise
+pub fn create_and_serialize_account_with_rent_escrow<'a, T: AnchorSerialize + AccountMaxSize>(
+    payer_info: &AccountInfo<'a>,
+    voter_rent_escrow_info: Option<&AccountInfo<'a>>,
+    account_info: &AccountInfo<'a>,
+    account_data: &T,
+    account_address_seeds: &[&[u8]],
+    system_program_info: &AccountInfo<'a>,
+    rent: &Rent
+) -> Result<()> {
+    let voter_rent_escrow_info = if let Some(voter_rent_escrow_info) = voter_rent_escrow_info {
+        voter_rent_escrow_info
+    } else {
+        create_and_serialize_account_signed(
+            payer_info,
+            account_info,
+            account_data,
+            account_address_seeds,
+            &id(),
+            system_program_info,
+            rent,
+            0
+        )?;
+
+        return Ok(());
+    };
+
+    let (account_address, bump_seed) = Pubkey::find_program_address(account_address_seeds, &id());
+
+    if account_address != *account_info.key {
+        msg!(
+            "Create account with PDA: {:?} was requested while PDA: {:?} was expected",
+            account_info.key,
+            account_address
+        );
+        return Err(ProgramError::InvalidSeeds.into());
+    }
+
+    let serialized_data = account_data.try_to_vec()?;
+    let account_size = account_data.get_max_size().unwrap_or(serialized_data.len());
+
+    // The escrow is owned by the program and its lamports are moved directly to the new account
+    // Note: The address could have been prefunded by anybody and only the missing rent is taken
+    let rent_lamports = rent.minimum_balance(account_size).saturating_sub(account_info.lamports());
+
+    require!(
+        get_voter_rent_escrow_available_lamports(voter_rent_escrow_info, rent) >= rent_lamports,
+        NftVoterError::VoterRentEscrowInsufficientFunds
+    );
+
+    **voter_rent_escrow_info.try_borrow_mut_lamports()? -= rent_lamports;
+    **account_info.try_borrow_mut_lamports()? += rent_lamports;
+
+    let mut signers_seeds = account_address_seeds.to_vec();
+    let bump = &[bump_seed];
+    signers_seeds.push(bump);
+
+    invoke_signed(
+        &system_instruction::allocate(account_info.key, account_size as u64),
+        &[account_info.clone(), system_program_info.clone()],
+        &[&signers_seeds[..]]
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(account_info.key, &id()),
+        &[account_info.clone(), system_program_info.clone()],
+        &[&signers_seeds[..]]
+    )?;
+
+    account_info.data.borrow_mut()[..serialized_data.len()].copy_from_slice(&serialized_data);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    apply_wallet_weight_override,
+    split_voter_registration_account,
+    split_wallet_weight_override_account,
+    Registrar,
+    RegistrarExtension,
+    VoterWeightRecord,
+    VoterWeightState,
+    MAX_BASIS_POINTS,
+};
+
+/// Adjustment of the final voter weight of a voter set by the Registrar
+/// It combines the floor weight of the voter registered with register_voter and the WalletWeightOverride of the voter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoterWeightAdjustment {
+    /// The floor weight of the voter or 0 if the voter has no floor weight
+    pub min_voter_weight: u64,
+
+    /// The weight_bps of WalletWeightOverride of the voter or MAX_BASIS_POINTS if the weight isn't overridden
+    pub wallet_weight_bps: u16,
+}
+
+impl Default for VoterWeightAdjustment {
+    fn default() -> Self {
+        Self {
+            min_voter_weight: 0,
+            wallet_weight_bps: MAX_BASIS_POINTS,
+        }
+    }
+}
+
+impl VoterWeightAdjustment {
+    /// Returns the given voter weight adjusted by the floor weight and the wallet override
+    pub fn apply(&self, voter_weight: u64) -> u64 {
+        // Voters registered before the cutoff keep the floor weight even with fewer NFTs
+        // and the wallet override set by the realm applies to the floor weight too
+        apply_wallet_weight_override(voter_weight.max(self.min_voter_weight), self.wallet_weight_bps)
+    }
+}
+
+/// Splits the VoterRegistration and WalletWeightOverride PDAs of the voter from the remaining accounts
+/// and returns the adjustment of the final voter weight of the voter
+/// Each PDA is supplied only when the floor weight or the wallet overrides are enabled by the Registrar
+pub fn split_voter_weight_adjustment_accounts<'a, 'info>(
+    registrar: &Account<Registrar>,
+    registrar_extension: &RegistrarExtension,
+    governing_token_owner: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(VoterWeightAdjustment, &'a [AccountInfo<'info>])> {
+    let (min_voter_weight, remaining_accounts) = split_voter_registration_account(
+        registrar,
+        registrar_extension,
+        governing_token_owner,
+        remaining_accounts
+    )?;
+
+    let (wallet_weight_bps, remaining_accounts) = split_wallet_weight_override_account(
+        registrar,
+        registrar_extension,
+        governing_token_owner,
+        remaining_accounts
+    )?;
+
+    Ok((
+        VoterWeightAdjustment {
+            min_voter_weight,
+            wallet_weight_bps,
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Sets the final voter weight of VoterWeightRecord which can be taken by spl-gov until the given slot
+/// The weight is adjusted by the floor weight and the wallet override of the voter
+///
+/// Note: Every instruction which finalizes VoterWeightRecord sets the weight using this function
+/// so the adjustment can't be bypassed by any of the voting paths
+pub fn finalize_voter_weight(
+    voter_weight_record: &mut VoterWeightRecord,
+    voter_weight: u64,
+    voter_weight_expiry: u64,
+    voter_weight_adjustment: &VoterWeightAdjustment
+) {
+    voter_weight_record.voter_weight = voter_weight_adjustment.apply(voter_weight);
+    voter_weight_record.voter_weight_expiry = Some(voter_weight_expiry);
+    voter_weight_record.weight_state = VoterWeightState::Finalized;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply_voter_weight_adjustment() {
+        // Arrange
+        let voter_weight_adjustment = VoterWeightAdjustment {
+            min_voter_weight: 10,
+            wallet_weight_bps: 5_000,
+        };
+
+        // Act + Assert
+        assert_eq!(voter_weight_adjustment.apply(4), 5);
+        assert_eq!(voter_weight_adjustment.apply(30), 15);
+        assert_eq!(VoterWeightAdjustment::default().apply(30), 30);
+    }
+
+    #[test]
+    fn test_finalize_voter_weight() {
+        // Arrange
+        let mut voter_weight_record = VoterWeightRecord::default();
+
+        let voter_weight_adjustment = VoterWeightAdjustment {
+            min_voter_weight: 0,
+            wallet_weight_bps: 0,
+        };
+
+        // Act
+        finalize_voter_weight(&mut voter_weight_record, 30, 100, &voter_weight_adjustment);
+
+        // Assert
+        assert_eq!(voter_weight_record.voter_weight, 0);
+        assert_eq!(voter_weight_record.voter_weight_expiry, Some(100));
+        assert_eq!(voter_weight_record.weight_state, VoterWeightState::Finalized);
+    }
+}
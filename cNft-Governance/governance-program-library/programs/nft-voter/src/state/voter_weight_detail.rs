@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ error::NftVoterError, id, state::RegistrarExtension, tools::anchor::{ get_anchor_account_data, set_anchor_account_data, DISCRIMINATOR_SIZE } };
+
+/// The weight contributed by the NFTs of a single collection
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Default)]
+pub struct CollectionContribution {
+    /// The collection of the NFTs
+    pub collection: Pubkey,
+
+    /// The number of NFTs of the collection used by the voter
+    pub asset_count: u32,
+
+    /// The weight contributed by the NFTs of the collection
+    pub voter_weight: u64,
+}
+
+/// Read optimized summary of the NFTs which produced VoterWeightRecord.voter_weight
+/// It allows UIs to display the weight breakdown (ex. weight = 3 x CollectionA + 1 x CollectionB)
+/// without re-running the NFT verification
+/// The PDA of the detail is ["voter-weight-detail",voter_weight_record]
+///
+/// Note: VoterWeightRecord layout is defined by spl-governance-addin-api and can't be extended
+/// and hence the summary is stored in a sibling account
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VoterWeightDetail {
+    /// The VoterWeightRecord the detail is for
+    pub voter_weight_record: Pubkey,
+
+    /// The max number of collections the detail can track
+    pub max_collections: u8,
+
+    /// The total weight of the NFTs before Governance weight multipliers are applied
+    pub voter_weight: u64,
+
+    /// The total number of NFTs used by the voter
+    pub asset_count: u32,
+
+    /// The weight contributed by each collection
+    pub collection_contributions: Vec<CollectionContribution>,
+}
+
+impl VoterWeightDetail {
+    /// Returns the size of VoterWeightDetail with capacity for the given number of collections
+    pub const fn space_for(max_collections: u8) -> usize {
+        DISCRIMINATOR_SIZE +
+            PUBKEY_BYTES +
+            1 +
+            8 +
+            4 +
+            4 +
+            (max_collections as usize) * (PUBKEY_BYTES + 4 + 8)
+    }
+
+    /// Clears the summary when VoterWeightRecord is evaluated from scratch
+    pub fn reset(&mut self) {
+        self.voter_weight = 0;
+        self.asset_count = 0;
+        self.collection_contributions = vec![];
+    }
+
+    /// Adds the weight contributed by a single NFT of the given collection
+    pub fn add_contribution(&mut self, collection: Pubkey, voter_weight: u64) -> Result<()> {
+        self.voter_weight = self.voter_weight
+            .checked_add(voter_weight)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+        self.asset_count = self.asset_count
+            .checked_add(1)
+            .ok_or(NftVoterError::ArithmeticOverflow)?;
+
+        match self.collection_contributions.iter_mut().find(|cc| cc.collection == collection) {
+            Some(contribution) => {
+                contribution.asset_count = contribution.asset_count
+                    .checked_add(1)
+                    .ok_or(NftVoterError::ArithmeticOverflow)?;
+                contribution.voter_weight = contribution.voter_weight
+                    .checked_add(voter_weight)
+                    .ok_or(NftVoterError::ArithmeticOverflow)?;
+            }
+            None => {
+                require!(
+                    self.collection_contributions.len() < (self.max_collections as usize),
+                    NftVoterError::VoterWeightDetailCollectionsLimitReached
+                );
+
+                self.collection_contributions.push(CollectionContribution {
+                    collection,
+                    asset_count: 1,
+                    voter_weight,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns VoterWeightDetail PDA seeds
+pub fn get_voter_weight_detail_seeds(voter_weight_record: &Pubkey) -> [&[u8]; 2] {
+    [b"voter-weight-detail", voter_weight_record.as_ref()]
+}
+
+/// Returns VoterWeightDetail PDA address
+pub fn get_voter_weight_detail_address(voter_weight_record: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_voter_weight_detail_seeds(voter_weight_record), &id()).0
+}
+
+/// Splits VoterWeightDetail account from the given remaining accounts
+/// When RegistrarExtension.config.track_voter_weight_detail is set then VoterWeightDetail must be the first remaining account
+pub fn split_voter_weight_detail_account<'a, 'info>(
+    registrar_extension: &RegistrarExtension,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>])> {
+    if !registrar_extension.config.track_voter_weight_detail {
+        return Ok((None, remaining_accounts));
+    }
+
+    let (voter_weight_detail_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::MissingVoterWeightDetail)?;
+
+    Ok((Some(voter_weight_detail_info), remaining_accounts))
+}
+
+/// Records the given (collection, voter_weight) contributions in VoterWeightDetail
+/// If accumulate is false then the previous summary is replaced
+pub fn record_voter_weight_detail(
+    voter_weight_detail_info: &AccountInfo,
+    voter_weight_record: &Pubkey,
+    contributions: &[(Pubkey, u64)],
+    accumulate: bool
+) -> Result<()> {
+    let mut voter_weight_detail = get_anchor_account_data::<VoterWeightDetail>(voter_weight_detail_info)?;
+
+    require!(
+        voter_weight_detail.voter_weight_record == *voter_weight_record,
+        NftVoterError::InvalidVoterWeightDetail
+    );
+
+    if !accumulate {
+        voter_weight_detail.reset();
+    }
+
+    for (collection, voter_weight) in contributions {
+        voter_weight_detail.add_contribution(*collection, *voter_weight)?;
+    }
+
+    set_anchor_account_data(voter_weight_detail_info, &voter_weight_detail)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_add_contribution() {
+        // Arrange
+        let collection_a = Pubkey::new_unique();
+        let collection_b = Pubkey::new_unique();
+
+        let mut voter_weight_detail = VoterWeightDetail {
+            voter_weight_record: Pubkey::default(),
+            max_collections: 2,
+            voter_weight: 0,
+            asset_count: 0,
+            collection_contributions: vec![],
+        };
+
+        // Act
+        voter_weight_detail.add_contribution(collection_a, 3).unwrap();
+        voter_weight_detail.add_contribution(collection_a, 3).unwrap();
+        voter_weight_detail.add_contribution(collection_b, 1).unwrap();
+
+        // Assert
+        assert_eq!(voter_weight_detail.voter_weight, 7);
+        assert_eq!(voter_weight_detail.asset_count, 3);
+        assert_eq!(voter_weight_detail.collection_contributions, vec![
+            CollectionContribution {
+                collection: collection_a,
+                asset_count: 2,
+                voter_weight: 6,
+            },
+            CollectionContribution {
+                collection: collection_b,
+                asset_count: 1,
+                voter_weight: 1,
+            }
+        ]);
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use solana_program::pubkey::PUBKEY_BYTES;
+use crate::id;
 use crate::tools::anchor::DISCRIMINATOR_SIZE;
 use std::fmt;
 
@@ -36,6 +37,30 @@ impl fmt::Display for VoterWeightAction {
     }
 }
 
+/// State of the voter weight accumulated by the plugin
+/// Multi-transaction votes move from Accumulating (cast_nft_vote) to Finalized (finalize_nft_vote)
+/// and the Finalized weight is Consumed once the slot it was finalized at has passed
+///
+/// Note: Consumed is the first variant because the state is stored in the first byte of the previously reserved space
+/// and hence records created before the states were introduced are read as Consumed
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoterWeightState {
+    /// The weight was taken by spl-gov or expired and it can't be used anymore
+    Consumed,
+
+    /// The weight is being accumulated by cast_nft_vote and it can't be taken by spl-gov yet
+    Accumulating,
+
+    /// The weight can be taken by spl-gov in the slot it was finalized at
+    Finalized,
+}
+
+impl Default for VoterWeightState {
+    fn default() -> Self {
+        VoterWeightState::Consumed
+    }
+}
+
 /// VoterWeightRecord account as defined in spl-governance-addin-api
 /// It's redefined here without account_discriminator for Anchor to treat it as native account
 ///
@@ -77,13 +102,34 @@ pub struct VoterWeightRecord {
     /// When the target is provided then the governance program asserts the target is the same as specified by the addin
     pub weight_action_target: Option<Pubkey>,
 
+    /// State of the weight accumulation
+    /// Note: It's stored in the space reserved by spl-governance-addin-api and it's not read by spl-gov
+    pub weight_state: VoterWeightState,
+
+    /// Nonce incremented by every update_voter_weight_record and update_voter_weight_record_for_target
+    /// Clients pass the nonce they expect to update_voter_weight_record so concurrent updates of the record
+    /// for different targets fail instead of silently overwriting each other
+    /// Note: It's stored in the space reserved by spl-governance-addin-api and records created before it was introduced read 0
+    pub record_nonce: u32,
+
     /// Reserved space for future versions
-    pub reserved: [u8; 8],
+    pub reserved: [u8; 3],
 }
 
 impl VoterWeightRecord {
-    pub fn get_space() -> usize {
-        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 4 + 8 + 1 + 8 + 1 + 1 + 1 + 8
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 4 + 8 + 1 + 8 + 1 + 1 + 1 + 1 + 4 + 3
+    }
+
+    /// Returns the weight state as of the given slot
+    /// Finalized weight is only valid in the slot it was finalized at and it's Consumed afterwards
+    pub fn get_weight_state(&self, slot: u64) -> VoterWeightState {
+        match self.weight_state {
+            VoterWeightState::Finalized if self.voter_weight_expiry < Some(slot) => {
+                VoterWeightState::Consumed
+            }
+            weight_state => weight_state,
+        }
     }
 }
 
@@ -97,11 +143,40 @@ impl Default for VoterWeightRecord {
             voter_weight_expiry: Some(0),
             weight_action: Some(VoterWeightAction::CastVote),
             weight_action_target: Some(Default::default()),
+            weight_state: Default::default(),
+            record_nonce: 0,
             reserved: Default::default(),
         }
     }
 }
 
+/// Returns VoterWeightRecord PDA seeds
+/// Note: The seeds are the same as used by the nft-voter plugin and the Realms UI derives the records of both plugins the same way
+pub fn get_voter_weight_record_seeds<'a>(
+    realm: &'a Pubkey,
+    governing_token_mint: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [
+        b"voter-weight-record",
+        realm.as_ref(),
+        governing_token_mint.as_ref(),
+        governing_token_owner.as_ref(),
+    ]
+}
+
+/// Returns VoterWeightRecord PDA address
+pub fn get_voter_weight_record_address(
+    realm: &Pubkey,
+    governing_token_mint: &Pubkey,
+    governing_token_owner: &Pubkey
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_voter_weight_record_seeds(realm, governing_token_mint, governing_token_owner),
+        &id()
+    ).0
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -118,4 +193,38 @@ mod test {
         // Assert
         assert_eq!(expected_space, actual_space);
     }
+
+    #[test]
+    fn test_get_weight_state() {
+        // Arrange
+        let voter_weight_record = VoterWeightRecord {
+            voter_weight_expiry: Some(10),
+            weight_state: VoterWeightState::Finalized,
+            ..Default::default()
+        };
+
+        // Act
+        let current_slot_state = voter_weight_record.get_weight_state(10);
+        let next_slot_state = voter_weight_record.get_weight_state(11);
+
+        // Assert
+        assert_eq!(current_slot_state, VoterWeightState::Finalized);
+        assert_eq!(next_slot_state, VoterWeightState::Consumed);
+    }
+
+    #[test]
+    fn test_deserialize_voter_weight_record_created_before_weight_state() {
+        // Arrange
+        let mut data = VoterWeightRecord::default().try_to_vec().unwrap();
+        let weight_state_offset = data.len() - 8;
+        data[weight_state_offset..].copy_from_slice(&[0; 8]);
+
+        // Act
+        let voter_weight_record = VoterWeightRecord::try_from_slice(&data).unwrap();
+
+        // Assert
+        assert_eq!(voter_weight_record.weight_state, VoterWeightState::Consumed);
+        assert_eq!(voter_weight_record.record_nonce, 0);
+        assert_eq!(voter_weight_record.reserved, [0; 3]);
+    }
 }
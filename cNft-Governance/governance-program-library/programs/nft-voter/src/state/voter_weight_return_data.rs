@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use solana_program::program::set_return_data;
+
+/// Return data set by the instructions which update VoterWeightRecord
+/// It allows composing programs and clients to confirm the effects without fetching the accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VoterWeightReturnData {
+    /// The resulting VoterWeightRecord.voter_weight
+    /// For accumulating cast_nft_vote it's the accumulated weight
+    pub voter_weight: u64,
+
+    /// The number of NFTs counted by the instruction
+    pub asset_count: u32,
+}
+
+impl VoterWeightReturnData {
+    pub fn new(voter_weight: u64, asset_count: usize) -> Self {
+        Self {
+            voter_weight,
+            asset_count: asset_count as u32,
+        }
+    }
+
+    /// Sets the data as the instruction return data
+    pub fn set(&self) -> Result<()> {
+        set_return_data(&self.try_to_vec()?);
+        Ok(())
+    }
+}
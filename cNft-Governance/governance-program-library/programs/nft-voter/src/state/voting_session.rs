@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{ error::NftVoterError, id, tools::anchor::DISCRIMINATOR_SIZE };
+
+/// The longest period a voter can authorize a session key for (about 7 days of 400ms slots)
+pub const MAX_VOTING_SESSION_DURATION_SLOTS: u64 = 7 * 24 * 60 * 60 * 5 / 2;
+
+/// Authorization of a temporary session key to cast votes on behalf of the voter
+/// The voter signs once to create the session and the session key signs cast_nft_vote until the session expires
+/// The session key can't be used for any other instruction which requires the voter signature
+/// The PDA of the session is ["voting-session",registrar,governing_token_owner]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct VotingSession {
+    /// The Registrar the session belongs to
+    pub registrar: Pubkey,
+
+    /// The voter who created the session
+    pub governing_token_owner: Pubkey,
+
+    /// The temporary key authorized to cast votes for the voter
+    pub session_authority: Pubkey,
+
+    /// The slot the session expires at
+    pub expires_at_slot: u64,
+}
+
+impl VotingSession {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 8
+    }
+
+    /// Asserts the session authorizes the given signer to cast votes for the given governing_token_owner
+    pub fn assert_can_vote_for(
+        &self,
+        registrar: &Pubkey,
+        governing_token_owner: &Pubkey,
+        session_authority_info: &AccountInfo,
+        slot: u64
+    ) -> Result<()> {
+        require!(
+            self.registrar == *registrar &&
+                self.governing_token_owner == *governing_token_owner &&
+                self.session_authority == *session_authority_info.key,
+            NftVoterError::InvalidVotingSession
+        );
+
+        require!(session_authority_info.is_signer, NftVoterError::InvalidVotingSession);
+        require!(slot < self.expires_at_slot, NftVoterError::VotingSessionExpired);
+
+        Ok(())
+    }
+}
+
+/// Returns VotingSession PDA seeds
+pub fn get_voting_session_seeds<'a>(
+    registrar: &'a Pubkey,
+    governing_token_owner: &'a Pubkey
+) -> [&'a [u8]; 3] {
+    [b"voting-session", registrar.as_ref(), governing_token_owner.as_ref()]
+}
+
+/// Returns VotingSession PDA address
+pub fn get_voting_session_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_voting_session_seeds(registrar, governing_token_owner), &id()).0
+}
+
+/// Splits the VotingSession from the front of the remaining accounts when it's used
+pub fn split_voting_session_account<'a, 'info>(
+    use_voting_session: bool,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>])> {
+    if !use_voting_session {
+        return Ok((None, remaining_accounts));
+    }
+
+    let (voting_session_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::InvalidVotingSession)?;
+
+    Ok((Some(voting_session_info), remaining_accounts))
+}
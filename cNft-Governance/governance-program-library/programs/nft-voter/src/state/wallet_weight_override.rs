@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    error::NftVoterError,
+    id,
+    state::{ Registrar, RegistrarExtension, MAX_BASIS_POINTS },
+    tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE },
+};
+
+/// Override of the voter weight of a specific wallet set by the realm authority (ex. through a governance Proposal)
+/// It lets a realm neutralize or reduce the voting power of sanctioned or treasury wallets holding member NFTs
+/// The PDA of the override is ["wallet-weight-override",registrar,wallet]
+///
+/// Note: The override is applied by every instruction which finalizes the voter weight (see finalize_voter_weight)
+/// when RegistrarExtension.config.allow_wallet_weight_overrides is set
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct WalletWeightOverride {
+    /// The Registrar the override belongs to
+    pub registrar: Pubkey,
+
+    /// The wallet (governing_token_owner) the override is for
+    pub wallet: Pubkey,
+
+    /// The voter weight of the wallet expressed in basis points of the weight of its NFTs
+    /// Ex: 0 neutralizes the voting power of the wallet and 10000 restores it
+    pub weight_bps: u16,
+}
+
+impl WalletWeightOverride {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 2 + 2
+    }
+}
+
+/// Returns the given voter weight scaled by the given weight_bps of WalletWeightOverride
+pub fn apply_wallet_weight_override(voter_weight: u64, weight_bps: u16) -> u64 {
+    (((voter_weight as u128) * (weight_bps as u128)) / (MAX_BASIS_POINTS as u128)) as u64
+}
+
+/// Returns WalletWeightOverride PDA seeds
+pub fn get_wallet_weight_override_seeds<'a>(registrar: &'a Pubkey, wallet: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"wallet-weight-override", registrar.as_ref(), wallet.as_ref()]
+}
+
+/// Returns WalletWeightOverride PDA address
+pub fn get_wallet_weight_override_address(registrar: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_wallet_weight_override_seeds(registrar, wallet), &id()).0
+}
+
+/// Splits the WalletWeightOverride PDA of the voter from the remaining accounts when the overrides are enabled
+/// and returns the weight_bps of the voter
+/// The PDA must be supplied even if the wallet has no override in which case the weight isn't changed
+pub fn split_wallet_weight_override_account<'a, 'info>(
+    registrar: &Account<Registrar>,
+    registrar_extension: &RegistrarExtension,
+    governing_token_owner: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(u16, &'a [AccountInfo<'info>])> {
+    if !registrar_extension.config.allow_wallet_weight_overrides {
+        return Ok((MAX_BASIS_POINTS, remaining_accounts));
+    }
+
+    let (wallet_weight_override_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::InvalidWalletWeightOverride)?;
+
+    require!(
+        *wallet_weight_override_info.key ==
+            get_wallet_weight_override_address(&registrar.key(), governing_token_owner),
+        NftVoterError::InvalidWalletWeightOverride
+    );
+
+    if wallet_weight_override_info.data_is_empty() {
+        return Ok((MAX_BASIS_POINTS, remaining_accounts));
+    }
+
+    let wallet_weight_override = get_anchor_account_data::<WalletWeightOverride>(
+        wallet_weight_override_info
+    )?;
+
+    Ok((wallet_weight_override.weight_bps, remaining_accounts))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply_wallet_weight_override() {
+        // Act + Assert
+        assert_eq!(apply_wallet_weight_override(10, 0), 0);
+        assert_eq!(apply_wallet_weight_override(10, 5_000), 5);
+        assert_eq!(apply_wallet_weight_override(10, MAX_BASIS_POINTS), 10);
+        assert_eq!(apply_wallet_weight_override(u64::MAX, MAX_BASIS_POINTS), u64::MAX);
+    }
+}
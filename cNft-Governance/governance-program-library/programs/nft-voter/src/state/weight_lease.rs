@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    error::NftVoterError,
+    id,
+    state::RegistrarConfig,
+    tools::anchor::{ get_anchor_account_data, DISCRIMINATOR_SIZE },
+};
+
+/// Rental of the voter weight of a compressed NFT to another wallet until a slot deadline
+/// The renter can create action tickets for the asset while the owner holds it and the lease is active
+/// and the owner can't use the asset until the lease expires
+/// Once the lease expires the tickets of the renter are rejected and the weight reverts to the owner
+/// The PDA of the lease is ["weight-lease",registrar,asset_id,owner]
+///
+/// Note: The lease is keyed by the owner and it stops authorizing the renter when the asset is transferred
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct WeightLease {
+    /// The Registrar the lease belongs to
+    pub registrar: Pubkey,
+
+    /// The id of the compressed NFT whose weight is rented
+    pub asset_id: Pubkey,
+
+    /// The wallet which holds the compressed NFT
+    pub owner: Pubkey,
+
+    /// The wallet the weight of the compressed NFT is rented to
+    pub renter: Pubkey,
+
+    /// The slot the renter can use the asset from
+    /// It's past the expiry of the tickets the owner created before the lease
+    pub starts_at_slot: u64,
+
+    /// The slot the lease expires at
+    pub expires_at_slot: u64,
+}
+
+impl WeightLease {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 4 + 8 + 8
+    }
+
+    /// Returns true if the weight of the asset is rented out at the given slot
+    /// The lease is active from its creation (and not from starts_at_slot) to exclude the asset from the new tickets of the owner
+    pub fn is_active(&self, slot: u64) -> bool {
+        slot < self.expires_at_slot
+    }
+
+    /// Asserts the lease authorizes the given renter to vote with the given asset of the given owner at the given slot
+    pub fn assert_can_vote_for(
+        &self,
+        registrar: &Pubkey,
+        asset_id: &Pubkey,
+        owner: &Pubkey,
+        renter: &Pubkey,
+        slot: u64
+    ) -> Result<()> {
+        require!(
+            self.registrar == *registrar &&
+                self.asset_id == *asset_id &&
+                self.owner == *owner &&
+                self.renter == *renter,
+            NftVoterError::InvalidWeightLease
+        );
+
+        require!(self.is_active(slot), NftVoterError::WeightLeaseExpired);
+        require!(slot >= self.starts_at_slot, NftVoterError::WeightLeaseNotStarted);
+
+        Ok(())
+    }
+
+    /// Returns the last slot the tickets created with the lease can be consumed at
+    /// Capping the ticket expiry reverts the weight to the owner at vote time once the lease expires
+    pub fn get_ticket_expiry(&self, ticket_expiry: u64) -> u64 {
+        ticket_expiry.min(self.expires_at_slot.saturating_sub(1))
+    }
+
+    /// Ends the lease returned by the renter at the given slot once the tickets the renter already created expire
+    /// The owner can't use the asset before then and the weight isn't counted for both wallets
+    pub fn end_early(&mut self, config: &RegistrarConfig, slot: u64) {
+        self.expires_at_slot = self.expires_at_slot.min(
+            config.get_nft_action_ticket_expiry(slot).saturating_add(1)
+        );
+    }
+}
+
+/// Returns WeightLease PDA seeds
+pub fn get_weight_lease_seeds<'a>(
+    registrar: &'a Pubkey,
+    asset_id: &'a Pubkey,
+    owner: &'a Pubkey
+) -> [&'a [u8]; 4] {
+    [b"weight-lease", registrar.as_ref(), asset_id.as_ref(), owner.as_ref()]
+}
+
+/// Returns WeightLease PDA address
+pub fn get_weight_lease_address(registrar: &Pubkey, asset_id: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_weight_lease_seeds(registrar, asset_id, owner), &id()).0
+}
+
+/// Asserts the weight of the given asset of the given owner isn't rented out by an active WeightLease at the given slot
+/// The WeightLease PDA must be supplied even if the asset was never rented
+pub fn assert_asset_not_leased(
+    weight_lease_info: &AccountInfo,
+    registrar: &Pubkey,
+    asset_id: &Pubkey,
+    owner: &Pubkey,
+    slot: u64
+) -> Result<()> {
+    require_keys_eq!(
+        *weight_lease_info.key,
+        get_weight_lease_address(registrar, asset_id, owner),
+        NftVoterError::InvalidWeightLease
+    );
+
+    if weight_lease_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let weight_lease = get_anchor_account_data::<WeightLease>(weight_lease_info)?;
+
+    require!(!weight_lease.is_active(slot), NftVoterError::WeightLeaseActive);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_weight_lease() -> WeightLease {
+        WeightLease {
+            registrar: Pubkey::new_unique(),
+            asset_id: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            renter: Pubkey::new_unique(),
+            starts_at_slot: 10,
+            expires_at_slot: 100,
+        }
+    }
+
+    #[test]
+    fn test_assert_can_vote_for() {
+        // Arrange
+        let lease = create_test_weight_lease();
+
+        // Act + Assert
+        assert!(
+            lease
+                .assert_can_vote_for(&lease.registrar, &lease.asset_id, &lease.owner, &lease.renter, 99)
+                .is_ok()
+        );
+        assert!(
+            lease
+                .assert_can_vote_for(&lease.registrar, &lease.asset_id, &lease.owner, &lease.renter, 100)
+                .is_err()
+        );
+        assert_eq!(
+            lease
+                .assert_can_vote_for(&lease.registrar, &lease.asset_id, &lease.owner, &lease.renter, 9)
+                .err()
+                .unwrap(),
+            NftVoterError::WeightLeaseNotStarted.into()
+        );
+        assert!(
+            lease
+                .assert_can_vote_for(
+                    &lease.registrar,
+                    &Pubkey::new_unique(),
+                    &lease.owner,
+                    &lease.renter,
+                    99
+                )
+                .is_err()
+        );
+        assert!(
+            lease
+                .assert_can_vote_for(
+                    &lease.registrar,
+                    &lease.asset_id,
+                    &lease.owner,
+                    &Pubkey::new_unique(),
+                    99
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_get_ticket_expiry() {
+        // Arrange
+        let lease = create_test_weight_lease();
+
+        // Act + Assert
+        assert_eq!(lease.get_ticket_expiry(50), 50);
+        assert_eq!(lease.get_ticket_expiry(120), 99);
+    }
+
+    #[test]
+    fn test_end_early() {
+        // Arrange
+        let mut lease = create_test_weight_lease();
+        let config = RegistrarConfig::default();
+
+        // Act
+        lease.end_early(&config, 50);
+
+        // Assert
+        assert_eq!(lease.expires_at_slot, config.get_nft_action_ticket_expiry(50) + 1);
+        assert!(lease.is_active(config.get_nft_action_ticket_expiry(50)));
+
+        lease.end_early(&config, 95);
+        assert_eq!(lease.expires_at_slot, config.get_nft_action_ticket_expiry(50) + 1);
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey::PUBKEY_BYTES;
+
+use crate::{
+    error::NftVoterError,
+    id,
+    tools::{ anchor::DISCRIMINATOR_SIZE, merkle_tree::{ get_weight_snapshot_leaf, verify_sorted_merkle_proof } },
+};
+
+/// Voter weights of the wallets as of a snapshot slot attested for a Proposal
+/// Voters prove the inclusion of (wallet, weight) in holdings_root with cast_snapshot_vote
+/// instead of supplying the NFTs they hold at the time of voting
+/// The PDA of the snapshot is ["weight-snapshot",registrar,proposal]
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct WeightSnapshot {
+    /// The Registrar the snapshot belongs to
+    pub registrar: Pubkey,
+
+    /// The Proposal the snapshot can be used to vote on
+    pub proposal: Pubkey,
+
+    /// The slot the holdings were taken at
+    pub snapshot_slot: u64,
+
+    /// Root of the sorted merkle tree of get_weight_snapshot_leaf(wallet, weight) of the wallets in the snapshot
+    pub holdings_root: [u8; 32],
+
+    /// The total voter weight of the wallets in the snapshot
+    /// Note: It's informational only and MaxVoterWeightRecord is not changed by the snapshot
+    pub total_weight: u64,
+
+    /// The realm authority or holding attestation authority which posted the snapshot
+    pub authority: Pubkey,
+}
+
+impl WeightSnapshot {
+    pub const fn get_space() -> usize {
+        DISCRIMINATOR_SIZE + PUBKEY_BYTES * 3 + 8 + 32 + 8
+    }
+
+    /// Asserts the wallet holds the given voter weight in the snapshot
+    pub fn assert_voter_weight(&self, voter: &Pubkey, voter_weight: u64, proof: &[[u8; 32]]) -> Result<()> {
+        require!(
+            verify_sorted_merkle_proof(
+                get_weight_snapshot_leaf(voter, voter_weight),
+                proof,
+                self.holdings_root
+            ),
+            NftVoterError::InvalidWeightSnapshotProof
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns WeightSnapshot PDA seeds
+pub fn get_weight_snapshot_seeds<'a>(registrar: &'a Pubkey, proposal: &'a Pubkey) -> [&'a [u8]; 3] {
+    [b"weight-snapshot", registrar.as_ref(), proposal.as_ref()]
+}
+
+/// Returns WeightSnapshot PDA address
+pub fn get_weight_snapshot_address(registrar: &Pubkey, proposal: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_weight_snapshot_seeds(registrar, proposal), &id()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::keccak::hashv;
+
+    fn create_test_weight_snapshot() -> WeightSnapshot {
+        WeightSnapshot {
+            registrar: Pubkey::new_unique(),
+            proposal: Pubkey::new_unique(),
+            snapshot_slot: 0,
+            holdings_root: [0; 32],
+            total_weight: 0,
+            authority: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_assert_voter_weight() {
+        // Arrange
+        let voter = Pubkey::new_unique();
+        let voter_leaf = get_weight_snapshot_leaf(&voter, 10);
+        let other_leaf = get_weight_snapshot_leaf(&Pubkey::new_unique(), 5);
+
+        let mut weight_snapshot = create_test_weight_snapshot();
+        weight_snapshot.holdings_root = if voter_leaf <= other_leaf {
+            hashv(&[&voter_leaf, &other_leaf]).to_bytes()
+        } else {
+            hashv(&[&other_leaf, &voter_leaf]).to_bytes()
+        };
+
+        // Act + Assert
+        assert!(weight_snapshot.assert_voter_weight(&voter, 10, &[other_leaf]).is_ok());
+        assert!(weight_snapshot.assert_voter_weight(&voter, 11, &[other_leaf]).is_err());
+        assert!(weight_snapshot.assert_voter_weight(&voter, 10, &[]).is_err());
+        assert!(weight_snapshot.assert_voter_weight(&Pubkey::new_unique(), 10, &[other_leaf]).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+
+use crate::{ error::NftVoterError, state::{ CollectionWeightMode, RegistrarExtension } };
+
+/// Calculates the weight the NFTs of a single collection held by a voter contribute to the voter weight
+/// The NFTs are counted one by one and each NFT adds its marginal weight given the number of NFTs
+/// of the same collection which were already counted for the voter
+pub trait WeightStrategy {
+    /// Returns the weight the NFT with the given index (0 based) among the NFTs of the collection
+    /// counted for the voter adds to the voter weight
+    /// asset_weight is the weight of the NFT itself (collection weight with creator boost and normalization)
+    fn get_asset_weight(&self, asset_weight: u64, asset_index: u32) -> u64;
+
+    /// Returns the max weight of a collection of the given size with the given max NFT weight
+    /// The default assumes every NFT is held by a different voter and contributes its full weight
+    fn get_max_weight(&self, max_asset_weight: u64, size: u32) -> Result<u64> {
+        (size as u64)
+            .checked_mul(max_asset_weight)
+            .ok_or_else(|| NftVoterError::ArithmeticOverflow.into())
+    }
+
+    /// Returns true if the weight of NFTs counted separately (ex. by several cast_nft_vote instructions) can be summed up
+    fn is_accumulative(&self) -> bool {
+        false
+    }
+}
+
+/// Each NFT contributes its weight
+/// weight(n) = asset_weight * n
+pub struct LinearWeight;
+
+impl WeightStrategy for LinearWeight {
+    fn get_asset_weight(&self, asset_weight: u64, _asset_index: u32) -> u64 {
+        asset_weight
+    }
+
+    fn is_accumulative(&self) -> bool {
+        true
+    }
+}
+
+/// The NFTs contribute the weight of the square root of their count
+/// weight(n) = asset_weight * floor(sqrt(n))
+pub struct QuadraticWeight;
+
+impl WeightStrategy for QuadraticWeight {
+    fn get_asset_weight(&self, asset_weight: u64, asset_index: u32) -> u64 {
+        // The square root grows by at most 1 with each NFT hence the NFT adds either its full weight or nothing
+        let weighted_assets = isqrt(asset_index + 1) - isqrt(asset_index);
+        if weighted_assets > 0 { asset_weight } else { 0 }
+    }
+}
+
+/// Only the first NFT contributes its weight regardless of the number of NFTs held
+/// weight(n) = asset_weight for n > 0
+pub struct MembershipWeight;
+
+impl WeightStrategy for MembershipWeight {
+    fn get_asset_weight(&self, asset_weight: u64, asset_index: u32) -> u64 {
+        if asset_index == 0 { asset_weight } else { 0 }
+    }
+}
+
+/// Each NFT contributes its weight up to the given number of NFTs
+/// weight(n) = asset_weight * min(n, max_assets)
+pub struct CappedWeight {
+    pub max_assets: u16,
+}
+
+impl WeightStrategy for CappedWeight {
+    fn get_asset_weight(&self, asset_weight: u64, asset_index: u32) -> u64 {
+        if asset_index < (self.max_assets as u32) { asset_weight } else { 0 }
+    }
+}
+
+/// The WeightStrategy of a collection stored in CollectionConfig
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionWeightStrategy {
+    /// LinearWeight
+    Linear,
+
+    /// QuadraticWeight
+    Quadratic,
+
+    /// MembershipWeight
+    Membership,
+
+    /// CappedWeight with the given max number of weighted NFTs per voter
+    Capped {
+        max_assets: u16,
+    },
+}
+
+impl Default for CollectionWeightStrategy {
+    fn default() -> Self {
+        CollectionWeightStrategy::Linear
+    }
+}
+
+impl CollectionWeightStrategy {
+    /// The max serialized size of the strategy
+    pub const fn get_space() -> usize {
+        1 + 2
+    }
+
+    /// Returns true if the strategy parameters are valid
+    pub fn is_valid(&self) -> bool {
+        match self {
+            CollectionWeightStrategy::Capped { max_assets } => *max_assets > 0,
+            | CollectionWeightStrategy::Linear
+            | CollectionWeightStrategy::Quadratic
+            | CollectionWeightStrategy::Membership => true,
+        }
+    }
+
+    /// Invokes the given function with the WeightStrategy of the discriminant
+    fn with_strategy<R>(&self, f: impl FnOnce(&dyn WeightStrategy) -> R) -> R {
+        match self {
+            CollectionWeightStrategy::Linear => f(&LinearWeight),
+            CollectionWeightStrategy::Quadratic => f(&QuadraticWeight),
+            CollectionWeightStrategy::Membership => f(&MembershipWeight),
+            CollectionWeightStrategy::Capped { max_assets } =>
+                f(&(CappedWeight { max_assets: *max_assets })),
+        }
+    }
+}
+
+impl WeightStrategy for CollectionWeightStrategy {
+    fn get_asset_weight(&self, asset_weight: u64, asset_index: u32) -> u64 {
+        self.with_strategy(|strategy| strategy.get_asset_weight(asset_weight, asset_index))
+    }
+
+    fn get_max_weight(&self, max_asset_weight: u64, size: u32) -> Result<u64> {
+        self.with_strategy(|strategy| strategy.get_max_weight(max_asset_weight, size))
+    }
+
+    fn is_accumulative(&self) -> bool {
+        self.with_strategy(|strategy| strategy.is_accumulative())
+    }
+}
+
+/// Counts the NFTs of each collection supplied by a voter and applies the weight strategies of the collections
+/// In UniqueCollections mode each collection counts only once regardless of its own strategy
+#[derive(Default)]
+pub struct CollectionWeightTally {
+    /// The number of NFTs already counted for each collection
+    asset_counts: Vec<(Pubkey, u32)>,
+}
+
+impl CollectionWeightTally {
+    /// Counts the NFT of the given collection and returns the weight it adds to the voter weight
+    pub fn add_asset(
+        &mut self,
+        registrar_extension: &RegistrarExtension,
+        collection: &Pubkey,
+        asset_weight: u64
+    ) -> u64 {
+        let asset_index = match self.asset_counts.iter_mut().find(|(c, _)| c == collection) {
+            Some((_, asset_count)) => {
+                *asset_count += 1;
+                *asset_count - 1
+            }
+            None => {
+                self.asset_counts.push((*collection, 1));
+                0
+            }
+        };
+
+        if registrar_extension.config.collection_weight_mode == CollectionWeightMode::UniqueCollections {
+            return MembershipWeight.get_asset_weight(asset_weight, asset_index);
+        }
+
+        registrar_extension
+            .get_collection_extension(collection)
+            .weight_strategy.get_asset_weight(asset_weight, asset_index)
+    }
+}
+
+/// Returns floor(sqrt(value))
+fn isqrt(value: u32) -> u32 {
+    let mut root = 0u32;
+
+    while ((root + 1) as u64) * ((root + 1) as u64) <= (value as u64) {
+        root += 1;
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_weight(strategy: &dyn WeightStrategy, asset_weight: u64, asset_count: u32) -> u64 {
+        (0..asset_count).map(|asset_index| strategy.get_asset_weight(asset_weight, asset_index)).sum()
+    }
+
+    #[test]
+    fn test_linear_weight() {
+        // Act + Assert
+        assert_eq!(get_weight(&LinearWeight, 3, 0), 0);
+        assert_eq!(get_weight(&LinearWeight, 3, 5), 15);
+        assert!(LinearWeight.is_accumulative());
+    }
+
+    #[test]
+    fn test_quadratic_weight() {
+        // Act + Assert
+        assert_eq!(get_weight(&QuadraticWeight, 3, 1), 3);
+        assert_eq!(get_weight(&QuadraticWeight, 3, 3), 3);
+        assert_eq!(get_weight(&QuadraticWeight, 3, 4), 6);
+        assert_eq!(get_weight(&QuadraticWeight, 3, 9), 9);
+        assert_eq!(get_weight(&QuadraticWeight, 3, 100), 30);
+        assert!(!QuadraticWeight.is_accumulative());
+    }
+
+    #[test]
+    fn test_membership_weight() {
+        // Act + Assert
+        assert_eq!(get_weight(&MembershipWeight, 3, 1), 3);
+        assert_eq!(get_weight(&MembershipWeight, 3, 10), 3);
+    }
+
+    #[test]
+    fn test_capped_weight() {
+        // Arrange
+        let strategy = CappedWeight { max_assets: 2 };
+
+        // Act + Assert
+        assert_eq!(get_weight(&strategy, 3, 1), 3);
+        assert_eq!(get_weight(&strategy, 3, 2), 6);
+        assert_eq!(get_weight(&strategy, 3, 10), 6);
+    }
+
+    #[test]
+    fn test_max_weight() {
+        // Act + Assert
+        assert_eq!(CollectionWeightStrategy::Linear.get_max_weight(3, 10).unwrap(), 30);
+        assert_eq!(CollectionWeightStrategy::Quadratic.get_max_weight(3, 10).unwrap(), 30);
+        assert_eq!(
+            CollectionWeightStrategy::Capped { max_assets: 1 }.get_max_weight(3, 10).unwrap(),
+            30
+        );
+    }
+
+    #[test]
+    fn test_max_weight_with_overflow_error() {
+        // Act
+        let err = CollectionWeightStrategy::Linear.get_max_weight(u64::MAX, 2).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::ArithmeticOverflow.into());
+    }
+
+    #[test]
+    fn test_collection_weight_strategy_get_space() {
+        // Arrange
+        let strategy = CollectionWeightStrategy::Capped { max_assets: 5 };
+
+        // Act
+        let actual_space = strategy.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(CollectionWeightStrategy::get_space(), actual_space);
+        assert!(!(CollectionWeightStrategy::Capped { max_assets: 0 }).is_valid());
+    }
+}
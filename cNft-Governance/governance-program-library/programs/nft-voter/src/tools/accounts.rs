@@ -2,9 +2,9 @@ use solana_program::account_info::AccountInfo;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 use solana_program::{ rent::Rent, sysvar::Sysvar };
-use solana_program::system_instruction::create_account;
+use solana_program::system_instruction::{ create_account, transfer };
 use solana_program::system_program;
-use solana_program::program::invoke_signed;
+use solana_program::program::{ invoke, invoke_signed };
 use solana_program::msg;
 use crate::state::*;
 
@@ -64,13 +64,28 @@ pub fn create_nft_action_ticket_account<'a>(
     Ok(())
 }
 
-/// this function will serialize the given nft action ticket data and store it in the given account.
-/// Should be removed? so the ticket can just be created w/o serialized from other programs?
-/// The we can check if the PDA is serialized correctly.
-pub fn serialize_nft_action_ticket_account(
+/// Serializes the given NftActionTicket data into the ticket account
+/// Tickets created with a previous (shorter) layout and never consumed are resized to the current layout
+/// and the payer tops up the rent exemption of the new size
+pub fn serialize_nft_action_ticket_account<'a>(
+    payer: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
     serialized_data: &Vec<u8>,
-    account_info: &AccountInfo
+    system_program: &AccountInfo<'a>
 ) -> Result<(), ProgramError> {
+    if account_info.data_len() != serialized_data.len() {
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(serialized_data.len());
+
+        if rent_exempt_lamports > account_info.lamports() {
+            invoke(
+                &transfer(payer.key, account_info.key, rent_exempt_lamports - account_info.lamports()),
+                &[payer.clone(), account_info.clone(), system_program.clone()]
+            )?;
+        }
+
+        account_info.realloc(serialized_data.len(), false)?;
+    }
+
     account_info.data.borrow_mut().copy_from_slice(&serialized_data);
     Ok(())
 }
@@ -91,3 +106,31 @@ pub fn close_nft_action_ticket_account(
     account_info.assign(&system_program::id());
     account_info.realloc(0, false)
 }
+
+/// Disposes the given program owned account and splits its lamports between the beneficiary and the treasury
+/// The treasury receives treasury_share_bps of the lamports (in basis points) and the beneficiary the remainder
+pub fn dispose_account_with_treasury_share(
+    account_info: &AccountInfo,
+    beneficiary_info: &AccountInfo,
+    treasury_info: &AccountInfo,
+    treasury_share_bps: u16
+) -> Result<(), ProgramError> {
+    let account_lamports = account_info.lamports();
+    let treasury_lamports = (((account_lamports as u128) * (treasury_share_bps as u128)) /
+        (MAX_BASIS_POINTS as u128)) as u64;
+
+    **account_info.lamports.borrow_mut() = 0;
+
+    **treasury_info.lamports.borrow_mut() = treasury_info
+        .lamports()
+        .checked_add(treasury_lamports)
+        .unwrap();
+
+    **beneficiary_info.lamports.borrow_mut() = beneficiary_info
+        .lamports()
+        .checked_add(account_lamports - treasury_lamports)
+        .unwrap();
+
+    account_info.assign(&system_program::id());
+    account_info.realloc(0, false)
+}
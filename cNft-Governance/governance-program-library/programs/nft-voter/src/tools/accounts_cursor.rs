@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+
+/// Consumes remaining_accounts section by section in the order they are supplied
+/// Each section is named to log which accounts are missing when the accounts are under or over supplied
+pub struct AccountsCursor<'a, 'info> {
+    accounts: &'a [AccountInfo<'info>],
+    position: usize,
+}
+
+impl<'a, 'info> AccountsCursor<'a, 'info> {
+    pub fn new(accounts: &'a [AccountInfo<'info>]) -> Self {
+        Self { accounts, position: 0 }
+    }
+
+    /// Returns the number of accounts which were not consumed yet
+    pub fn remaining_len(&self) -> usize {
+        self.accounts.len() - self.position
+    }
+
+    /// Returns true if all the accounts were consumed
+    pub fn is_empty(&self) -> bool {
+        self.remaining_len() == 0
+    }
+
+    /// Consumes the next len accounts of the given section
+    pub fn take(&mut self, len: usize, section: &str) -> Result<&'a [AccountInfo<'info>]> {
+        if self.remaining_len() < len {
+            msg!(
+                "Expected {} {} account(s) at position {} but only {} remaining account(s) were supplied",
+                len,
+                section,
+                self.position,
+                self.remaining_len()
+            );
+            return err!(NftVoterError::NotEnoughRemainingAccounts);
+        }
+
+        let accounts = &self.accounts[self.position..self.position + len];
+        self.position += len;
+
+        Ok(accounts)
+    }
+
+    /// Consumes the next account of the given section
+    pub fn next(&mut self, section: &str) -> Result<&'a AccountInfo<'info>> {
+        Ok(&self.take(1, section)?[0])
+    }
+
+    /// Consumes the next account of the given section when the section is supplied
+    pub fn next_if(&mut self, is_supplied: bool, section: &str) -> Result<Option<&'a AccountInfo<'info>>> {
+        if !is_supplied {
+            return Ok(None);
+        }
+
+        Ok(Some(self.next(section)?))
+    }
+
+    /// Asserts all the accounts were consumed
+    pub fn finish(&self) -> Result<()> {
+        if !self.is_empty() {
+            msg!(
+                "Expected {} remaining account(s) but {} were supplied",
+                self.position,
+                self.accounts.len()
+            );
+            return err!(NftVoterError::TooManyRemainingAccounts);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_accounts_cursor() {
+        // Arrange
+        let keys = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; 4];
+        let mut data = vec![vec![]; 4];
+
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect::<Vec<_>>();
+
+        let mut cursor = AccountsCursor::new(&accounts);
+
+        // Act
+        let tree_info = cursor.next("tree").unwrap();
+        let proof_infos = cursor.take(2, "proof").unwrap();
+        let skipped_info = cursor.next_if(false, "asset_uses_record").unwrap();
+
+        // Assert
+        assert_eq!(*tree_info.key, keys[0]);
+        assert_eq!(proof_infos.len(), 2);
+        assert_eq!(*proof_infos[1].key, keys[2]);
+        assert!(skipped_info.is_none());
+
+        assert_eq!(cursor.finish().err().unwrap(), NftVoterError::TooManyRemainingAccounts.into());
+        assert_eq!(
+            cursor.take(2, "nft_action_ticket").err().unwrap(),
+            NftVoterError::NotEnoughRemainingAccounts.into()
+        );
+
+        cursor.next("nft_action_ticket").unwrap();
+        assert!(cursor.finish().is_ok());
+    }
+}
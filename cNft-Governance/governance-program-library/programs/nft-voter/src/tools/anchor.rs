@@ -1 +1,31 @@
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::{ AccountDeserialize, AccountSerialize, Owner };
+
 pub const DISCRIMINATOR_SIZE: usize = 8;
+
+/// Deserializes Anchor account data of type T and asserts the account is owned by T's program
+/// It performs the same checks as Account::try_from which requires the AccountInfo to live for 'info
+pub fn get_anchor_account_data<T: AccountDeserialize + Owner>(account_info: &AccountInfo) -> Result<T> {
+    if account_info.owner == &anchor_lang::system_program::ID && account_info.lamports() == 0 {
+        return err!(ErrorCode::AccountNotInitialized);
+    }
+
+    if account_info.owner != &T::owner() {
+        return Err(
+            Error::from(ErrorCode::AccountOwnedByWrongProgram).with_pubkeys((
+                *account_info.owner,
+                T::owner(),
+            ))
+        );
+    }
+
+    T::try_deserialize(&mut &account_info.try_borrow_data()?[..])
+}
+
+/// Serializes Anchor account data of type T back into the account
+/// The account must have been read using get_anchor_account_data() which asserts its owner
+pub fn set_anchor_account_data<T: AccountSerialize>(account_info: &AccountInfo, data: &T) -> Result<()> {
+    let mut account_data = account_info.try_borrow_mut_data()?;
+    data.try_serialize(&mut &mut account_data[..])
+}
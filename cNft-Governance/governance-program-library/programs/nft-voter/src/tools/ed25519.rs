@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use solana_program::ed25519_program;
+use solana_program::sysvar::instructions::{ load_current_index_checked, load_instruction_at_checked };
+
+use crate::error::NftVoterError;
+
+/// Offset of the first Ed25519SignatureOffsets in ed25519 program instruction data
+/// Layout: num_signatures(1), padding(1), Ed25519SignatureOffsets(14) * num_signatures, ...
+const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// Ed25519SignatureOffsets layout: signature_offset(2), signature_instruction_index(2), public_key_offset(2),
+/// public_key_instruction_index(2), message_data_offset(2), message_data_size(2), message_instruction_index(2)
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+
+/// Instruction index used by the ed25519 program to reference data of the ed25519 instruction itself
+const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// Asserts an ed25519 program instruction preceding the current instruction verified a signature of the given signer over the given message
+/// The signature itself is verified by the ed25519 native program and the transaction fails if it's invalid
+/// Note: Only signatures with all the data embedded in the ed25519 instruction itself are accepted
+pub fn assert_ed25519_signature(
+    instructions_info: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8]
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_info)?;
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_info)?;
+
+        if instruction.program_id != ed25519_program::id() {
+            continue;
+        }
+
+        if is_ed25519_signature_for(&instruction.data, signer, message) {
+            return Ok(());
+        }
+    }
+
+    err!(NftVoterError::Ed25519SignatureNotFound)
+}
+
+fn is_ed25519_signature_for(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let num_signatures = match data.first() {
+        Some(num_signatures) => *num_signatures as usize,
+        None => {
+            return false;
+        }
+    };
+
+    for i in 0..num_signatures {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+
+        let offsets = match data.get(start..start + SIGNATURE_OFFSETS_SIZE) {
+            Some(offsets) => offsets,
+            None => {
+                return false;
+            }
+        };
+
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        if
+            read_u16(2) != CURRENT_INSTRUCTION_INDEX ||
+            read_u16(6) != CURRENT_INSTRUCTION_INDEX ||
+            read_u16(12) != CURRENT_INSTRUCTION_INDEX
+        {
+            continue;
+        }
+
+        let public_key_offset = read_u16(4) as usize;
+        let message_data_offset = read_u16(8) as usize;
+        let message_data_size = read_u16(10) as usize;
+
+        if
+            data.get(public_key_offset..public_key_offset + 32) == Some(signer.as_ref()) &&
+            data.get(message_data_offset..message_data_offset + message_data_size) == Some(message)
+        {
+            return true;
+        }
+    }
+
+    false
+}
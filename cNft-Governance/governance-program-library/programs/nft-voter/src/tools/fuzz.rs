@@ -0,0 +1,249 @@
+//! arbitrary::Arbitrary implementations of the instruction arguments used by the fuzz targets
+//!
+//! The implementations are written by hand because Pubkey doesn't implement Arbitrary
+//! They are compiled only with the `fuzz` feature and must list every field to keep up with the argument layouts
+
+use anchor_lang::prelude::Pubkey;
+use arbitrary::{ Arbitrary, Result, Unstructured };
+
+use crate::state::{
+    Collection,
+    CollectionConfigArgs,
+    CollectionWeightMode,
+    CollectionWeightStrategy,
+    CompressedNftAsset,
+    Creator,
+    CreatorBoost,
+    LeafSchema,
+    NftVoteKind,
+    RegistrarConfig,
+    UseMethod,
+    Uses,
+    VoterWeightAction,
+    MAX_TREE_AUTHORITIES,
+};
+
+/// Returns an arbitrary Pubkey
+pub fn arbitrary_pubkey(u: &mut Unstructured) -> Result<Pubkey> {
+    Ok(Pubkey::new_from_array(u.arbitrary()?))
+}
+
+impl<'a> Arbitrary<'a> for VoterWeightAction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => VoterWeightAction::CastVote,
+            1 => VoterWeightAction::CommentProposal,
+            2 => VoterWeightAction::CreateGovernance,
+            3 => VoterWeightAction::CreateProposal,
+            _ => VoterWeightAction::SignOffProposal,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for NftVoteKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => NftVoteKind::Unknown,
+            1 => NftVoteKind::Approve,
+            2 => NftVoteKind::Deny,
+            3 => NftVoteKind::Abstain,
+            _ => NftVoteKind::Veto,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CollectionWeightMode {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => CollectionWeightMode::PerAsset,
+            1 => CollectionWeightMode::UniqueCollections,
+            _ => CollectionWeightMode::Normalized,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CollectionWeightStrategy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => CollectionWeightStrategy::Linear,
+            1 => CollectionWeightStrategy::Quadratic,
+            2 => CollectionWeightStrategy::Membership,
+            _ => CollectionWeightStrategy::Capped { max_assets: u.arbitrary()? },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CreatorBoost {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(CreatorBoost {
+            creator: arbitrary_pubkey(u)?,
+            boost_bps: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CollectionConfigArgs {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(CollectionConfigArgs {
+            collection: arbitrary_pubkey(u)?,
+            weight: u.arbitrary()?,
+            size: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RegistrarConfig {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut tree_authority_allowlist = [Pubkey::default(); MAX_TREE_AUTHORITIES];
+
+        for tree_authority in tree_authority_allowlist.iter_mut() {
+            *tree_authority = arbitrary_pubkey(u)?;
+        }
+
+        Ok(RegistrarConfig {
+            collection_weight_mode: u.arbitrary()?,
+            max_collection_weight_per_proposal: u.arbitrary()?,
+            min_holding_slots: u.arbitrary()?,
+            holding_attestation_authority: arbitrary_pubkey(u)?,
+            require_proposal_creation_ticket: u.arbitrary()?,
+            track_voter_weight_detail: u.arbitrary()?,
+            tree_authority_allowlist,
+            allow_precomputed_metadata_hashes: u.arbitrary()?,
+            voter_allowlist_root: u.arbitrary()?,
+            rent_reclaim_treasury: arbitrary_pubkey(u)?,
+            rent_reclaim_treasury_share_bps: u.arbitrary()?,
+            track_collection_stats: u.arbitrary()?,
+            parent_registrar: arbitrary_pubkey(u)?,
+            require_same_transaction_consumption: u.arbitrary()?,
+            normalized_collection_weight: u.arbitrary()?,
+            allow_core_assets: u.arbitrary()?,
+            max_assets_per_tx: u.arbitrary()?,
+            require_vote_finalization: u.arbitrary()?,
+            max_tree_assets_per_slot: u.arbitrary()?,
+            proof_attestation_freshness_slots: u.arbitrary()?,
+            max_tree_root_age: u.arbitrary()?,
+            allow_voter_migration: u.arbitrary()?,
+            leaf_hash_verifier: arbitrary_pubkey(u)?,
+            weight_record_min_age_slots: u.arbitrary()?,
+            record_voter_checkpoints: u.arbitrary()?,
+            require_weight_action_target: u.arbitrary()?,
+            weight_export_program: arbitrary_pubkey(u)?,
+            allow_collection_aliases: u.arbitrary()?,
+            min_weight_if_registered_before_slot: u.arbitrary()?,
+            registration_cutoff_slot: u.arbitrary()?,
+            bubblegum_program_override: arbitrary_pubkey(u)?,
+            allow_wallet_weight_overrides: u.arbitrary()?,
+            allow_weight_leases: u.arbitrary()?,
+            vote_receipt_tree: arbitrary_pubkey(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Collection {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Collection {
+            verified: u.arbitrary()?,
+            key: arbitrary_pubkey(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Creator {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Creator {
+            address: arbitrary_pubkey(u)?,
+            verified: u.arbitrary()?,
+            share: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for UseMethod {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => UseMethod::Burn,
+            1 => UseMethod::Multiple,
+            _ => UseMethod::Single,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Uses {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Uses {
+            use_method: u.arbitrary()?,
+            remaining: u.arbitrary()?,
+            total: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for LeafSchema {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            LeafSchema::V2 {
+                asset_data_hash: u.arbitrary()?,
+                flags: u.arbitrary()?,
+            }
+        } else {
+            LeafSchema::V1
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CompressedNftAsset {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(CompressedNftAsset {
+            name: u.arbitrary()?,
+            symbol: u.arbitrary()?,
+            uri: u.arbitrary()?,
+            collection: u.arbitrary()?,
+            seller_fee_basis_points: u.arbitrary()?,
+            primary_sale_happened: u.arbitrary()?,
+            is_mutable: u.arbitrary()?,
+            edition_nonce: u.arbitrary()?,
+            creators: u.arbitrary()?,
+            uses: u.arbitrary()?,
+            root: u.arbitrary()?,
+            leaf_owner: arbitrary_pubkey(u)?,
+            leaf_delegate: arbitrary_pubkey(u)?,
+            index: u.arbitrary()?,
+            nonce: u.arbitrary()?,
+            proof_len: u.arbitrary()?,
+            last_transfer_slot: u.arbitrary()?,
+            data_hash: u.arbitrary()?,
+            creator_hash: u.arbitrary()?,
+            proof_nodes: u.arbitrary()?,
+            use_proof_attestation: u.arbitrary()?,
+            leaf_schema: u.arbitrary()?,
+            leaf_hash: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::{ AnchorDeserialize, AnchorSerialize };
+
+    #[test]
+    fn test_arbitrary_compressed_nft_asset_roundtrip() {
+        // Arrange
+        let data = (0..4096).map(|i| (i * 31 + 7) as u8).collect::<Vec<u8>>();
+        let mut u = Unstructured::new(&data);
+
+        let compressed_nft_asset = CompressedNftAsset::arbitrary(&mut u).unwrap();
+        let config = RegistrarConfig::arbitrary(&mut u).unwrap();
+
+        // Act
+        let compressed_nft_asset_data = compressed_nft_asset.try_to_vec().unwrap();
+        let config_data = config.try_to_vec().unwrap();
+
+        // Assert
+        assert_eq!(
+            CompressedNftAsset::try_from_slice(&compressed_nft_asset_data).unwrap(),
+            compressed_nft_asset
+        );
+        assert_eq!(RegistrarConfig::try_from_slice(&config_data).unwrap(), config);
+    }
+}
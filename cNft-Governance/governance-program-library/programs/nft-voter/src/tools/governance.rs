@@ -1,5 +1,15 @@
-use anchor_lang::prelude::Pubkey;
-use spl_governance::state::{token_owner_record, vote_record};
+use anchor_lang::prelude::*;
+use solana_program::sysvar::instructions::{ load_current_index_checked, load_instruction_at_checked };
+use spl_governance::state::{
+    enums::ProposalState,
+    governance::{ self, GovernanceConfig },
+    proposal::{ self, ProposalV2 },
+    token_owner_record,
+    vote_record,
+};
+
+use crate::error::NftVoterError;
+use crate::state::{ Registrar, RegistrarExtension, VoterWeightAction, VoterWeightRecord };
 
 pub fn get_vote_record_address(
     program_id: &Pubkey,
@@ -17,3 +27,202 @@ pub fn get_vote_record_address(
 
     vote_record::get_vote_record_address(program_id, proposal, &token_owner_record_key)
 }
+
+/// Asserts the deadline of the given action on the Proposal hasn't passed yet
+/// Without the check the plugin would accept stale weight updates which are rejected downstream by spl-gov
+///
+/// SignOffProposal is only possible before the voting starts
+/// CastVote and CommentProposal are possible until the voting time (including cool off time) ends
+pub fn assert_proposal_action_deadline(
+    proposal: &ProposalV2,
+    governance_config: &GovernanceConfig,
+    voter_weight_action: &VoterWeightAction,
+) -> Result<()> {
+    match voter_weight_action {
+        VoterWeightAction::SignOffProposal => {
+            require!(
+                proposal.state == ProposalState::Draft
+                    || proposal.state == ProposalState::SigningOff,
+                NftVoterError::ProposalActionDeadlinePassed
+            );
+        }
+        VoterWeightAction::CastVote | VoterWeightAction::CommentProposal => {
+            if proposal.voting_at.is_some() {
+                require!(
+                    !proposal.has_voting_max_time_ended(
+                        governance_config,
+                        Clock::get()?.unix_timestamp
+                    ),
+                    NftVoterError::ProposalActionDeadlinePassed
+                );
+            }
+        }
+        VoterWeightAction::CreateProposal | VoterWeightAction::CreateGovernance => {}
+    }
+
+    Ok(())
+}
+
+/// Asserts an instruction of the given spl-governance instance following the current instruction
+/// in the transaction takes the given VoterWeightRecord
+pub fn assert_voter_weight_record_consumed_in_transaction(
+    instructions_info: &AccountInfo,
+    governance_program_id: &Pubkey,
+    voter_weight_record: &Pubkey,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_info)? as usize;
+
+    // load_instruction_at_checked fails once we go past the last instruction of the transaction
+    let mut index = current_index + 1;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_info) {
+        if instruction.program_id == *governance_program_id
+            && instruction
+                .accounts
+                .iter()
+                .any(|account_meta| account_meta.pubkey == *voter_weight_record)
+        {
+            return Ok(());
+        }
+
+        index += 1;
+    }
+
+    err!(NftVoterError::VoterWeightRecordNotConsumed)
+}
+
+/// Asserts VoterWeightRecord can be updated for the given non voting action
+/// It's shared by update_voter_weight_record and update_voter_weight_record_for_target so both paths apply the same checks
+///
+/// expected_record_nonce must match VoterWeightRecord.record_nonce so concurrent updates of the record
+/// for different targets fail instead of silently overwriting each other
+/// CastVote weight can't be evaluated by the update instructions and CreateProposal weight must be evaluated
+/// using ProposalCreationTicket when it's required by the Registrar
+pub fn assert_voter_weight_record_update(
+    voter_weight_record: &VoterWeightRecord,
+    registrar_extension: &RegistrarExtension,
+    voter_weight_action: &VoterWeightAction,
+    expected_record_nonce: u32
+) -> Result<()> {
+    require!(
+        voter_weight_record.record_nonce == expected_record_nonce,
+        NftVoterError::InvalidVoterWeightRecordNonce
+    );
+
+    match voter_weight_action {
+        VoterWeightAction::CastVote => err!(NftVoterError::CastVoteIsNotAllowed),
+        VoterWeightAction::CreateProposal => {
+            require!(
+                !registrar_extension.config.require_proposal_creation_ticket,
+                NftVoterError::ProposalCreationTicketRequired
+            );
+
+            Ok(())
+        }
+        | VoterWeightAction::CommentProposal
+        | VoterWeightAction::CreateGovernance
+        | VoterWeightAction::SignOffProposal => Ok(()),
+    }
+}
+
+/// Splits the target of the given action from the front of the remaining accounts
+/// when RegistrarExtension.config.require_weight_action_target is set and validates its account type
+///
+/// The target is the Realm of the Registrar for CreateGovernance (it's not taken from the remaining accounts),
+/// a Governance of the Realm for CreateProposal and a Proposal of the governing token mint
+/// for CommentProposal and SignOffProposal
+/// Returns None and the unchanged remaining accounts when the target is not required
+pub fn split_weight_action_target_account<'a, 'info>(
+    registrar: &Registrar,
+    registrar_extension: &RegistrarExtension,
+    voter_weight_action: &VoterWeightAction,
+    remaining_accounts: &'a [AccountInfo<'info>]
+) -> Result<(Option<Pubkey>, &'a [AccountInfo<'info>])> {
+    if !registrar_extension.config.require_weight_action_target {
+        return Ok((None, remaining_accounts));
+    }
+
+    if *voter_weight_action == VoterWeightAction::CreateGovernance {
+        return Ok((Some(registrar.realm), remaining_accounts));
+    }
+
+    let (target_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(NftVoterError::WeightActionTargetRequired)?;
+
+    match voter_weight_action {
+        VoterWeightAction::CreateProposal => {
+            governance::get_governance_data_for_realm(
+                &registrar.governance_program_id,
+                target_info,
+                &registrar.realm
+            )?;
+        }
+        VoterWeightAction::CommentProposal | VoterWeightAction::SignOffProposal => {
+            let proposal = proposal::get_proposal_data(&registrar.governance_program_id, target_info)?;
+
+            require!(
+                proposal.governing_token_mint == registrar.governing_token_mint,
+                NftVoterError::InvalidVoterWeightActionTarget
+            );
+        }
+        VoterWeightAction::CastVote | VoterWeightAction::CreateGovernance => {
+            return err!(NftVoterError::InvalidVoterWeightActionTarget);
+        }
+    }
+
+    Ok((Some(target_info.key()), remaining_accounts))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_assert_voter_weight_record_update() {
+        // Arrange
+        let voter_weight_record = VoterWeightRecord {
+            record_nonce: 2,
+            ..Default::default()
+        };
+
+        let mut registrar_extension = RegistrarExtension::new(Pubkey::default());
+        registrar_extension.config.require_proposal_creation_ticket = true;
+
+        // Act + Assert
+        assert!(
+            assert_voter_weight_record_update(
+                &voter_weight_record,
+                &registrar_extension,
+                &VoterWeightAction::CommentProposal,
+                2
+            ).is_ok()
+        );
+        assert_eq!(
+            assert_voter_weight_record_update(
+                &voter_weight_record,
+                &registrar_extension,
+                &VoterWeightAction::CommentProposal,
+                1
+            ).err().unwrap(),
+            NftVoterError::InvalidVoterWeightRecordNonce.into()
+        );
+        assert_eq!(
+            assert_voter_weight_record_update(
+                &voter_weight_record,
+                &registrar_extension,
+                &VoterWeightAction::CastVote,
+                2
+            ).err().unwrap(),
+            NftVoterError::CastVoteIsNotAllowed.into()
+        );
+        assert_eq!(
+            assert_voter_weight_record_update(
+                &voter_weight_record,
+                &registrar_extension,
+                &VoterWeightAction::CreateProposal,
+                2
+            ).err().unwrap(),
+            NftVoterError::ProposalCreationTicketRequired.into()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::convert::TryInto;
+use anchor_lang::prelude::*;
+use solana_program::keccak::hashv;
+use spl_account_compression::state::{
+    ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
+
+use crate::error::NftVoterError;
+use crate::tools::program_ids::SPL_ACCOUNT_COMPRESSION_ID;
+use crate::tools::tree_shapes::assert_supported_tree_shape;
+use cnft_verification::{ LEAF_SCHEMA_V1, LEAF_SCHEMA_V2 };
+
+/// Size of ConcurrentMerkleTree fields preceding change_logs: sequence_number(8), active_index(8), buffer_size(8)
+const CHANGE_LOGS_OFFSET: usize = 24;
+
+/// Offset of ConcurrentMerkleTree.sequence_number
+const SEQUENCE_NUMBER_OFFSET: usize = 0;
+
+/// Offset of ConcurrentMerkleTree.active_index
+const ACTIVE_INDEX_OFFSET: usize = 8;
+
+/// Offset of ConcurrentMerkleTree.buffer_size
+const BUFFER_SIZE_OFFSET: usize = 16;
+
+/// The account layout of the mpl-account-compression merkle trees is the same as spl-account-compression
+pub use crate::tools::program_ids::MPL_ACCOUNT_COMPRESSION_ID;
+
+/// Returns the version of the leaf schema of the assets minted into the given merkle tree
+/// spl-account-compression trees hold LeafSchema::V1 leaves and mpl-account-compression trees LeafSchema::V2 leaves
+pub fn get_merkle_tree_leaf_schema_version(tree_account: &AccountInfo) -> Result<u8> {
+    if *tree_account.owner == SPL_ACCOUNT_COMPRESSION_ID {
+        Ok(LEAF_SCHEMA_V1)
+    } else if *tree_account.owner == MPL_ACCOUNT_COMPRESSION_ID {
+        Ok(LEAF_SCHEMA_V2)
+    } else {
+        err!(NftVoterError::InvalidMerkleTree)
+    }
+}
+
+/// Returns the header of the given spl-account-compression or mpl-account-compression merkle tree
+/// The shape of the tree is validated before the tree data is read using the max_depth and max_buffer_size of the header
+fn get_merkle_tree_header(tree_account: &AccountInfo) -> Result<ConcurrentMerkleTreeHeader> {
+    get_merkle_tree_leaf_schema_version(tree_account)?;
+
+    let data = tree_account.try_borrow_data()?;
+
+    let header = ConcurrentMerkleTreeHeader::try_from_slice(
+        data.get(..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1).ok_or(NftVoterError::InvalidMerkleTree)?
+    )?;
+
+    assert_supported_tree_shape(header.get_max_depth(), header.get_max_buffer_size())?;
+
+    Ok(header)
+}
+
+/// Asserts the given merkle tree is managed by Bubblegum and its authority is the Bubblegum TreeConfig PDA of the tree
+/// The authority of any other tree can append arbitrary leaves directly through the compression program
+/// including leaves of assets claiming a collection they were never verified in
+/// Bubblegum only hashes collection.verified = true into the leaf when the collection authority signs the mint or verification
+pub fn assert_bubblegum_merkle_tree(tree_account: &AccountInfo, bubblegum_program_id: &Pubkey) -> Result<()> {
+    let header = get_merkle_tree_header(tree_account)?;
+
+    let tree_config = Pubkey::find_program_address(&[tree_account.key.as_ref()], bubblegum_program_id).0;
+
+    header
+        .assert_valid_authority(&tree_config)
+        .map_err(|_| error!(NftVoterError::InvalidMerkleTreeAuthority))
+}
+
+/// Reads u64 field of ConcurrentMerkleTree at the given offset
+fn read_merkle_tree_u64(tree_bytes: &[u8], offset: usize) -> Result<u64> {
+    Ok(
+        u64::from_le_bytes(
+            tree_bytes
+                .get(offset..offset + 8)
+                .ok_or(NftVoterError::InvalidMerkleTree)?
+                .try_into()
+                .unwrap()
+        )
+    )
+}
+
+/// Returns the max depth of the given spl-account-compression merkle tree
+pub fn get_merkle_tree_max_depth(tree_account: &AccountInfo) -> Result<u32> {
+    Ok(get_merkle_tree_header(tree_account)?.get_max_depth())
+}
+
+/// Returns the current root of the given spl-account-compression merkle tree
+/// The root is read from the active ChangeLog<MAX_DEPTH> { root, path: [Node; MAX_DEPTH], index: u32, _padding: u32 }
+pub fn get_merkle_tree_current_root(tree_account: &AccountInfo) -> Result<[u8; 32]> {
+    let max_depth = get_merkle_tree_max_depth(tree_account)? as usize;
+
+    let data = tree_account.try_borrow_data()?;
+    let tree_bytes = &data[CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..];
+
+    let active_index = read_merkle_tree_u64(tree_bytes, ACTIVE_INDEX_OFFSET)? as usize;
+
+    let change_log_size = 32 + 32 * max_depth + 4 + 4;
+    let root_offset = CHANGE_LOGS_OFFSET + active_index * change_log_size;
+
+    let root = tree_bytes
+        .get(root_offset..root_offset + 32)
+        .ok_or(NftVoterError::InvalidMerkleTree)?;
+
+    Ok(root.try_into().unwrap())
+}
+
+/// Returns the number of changes of the given spl-account-compression merkle tree since the given root
+/// or None if the root is no longer in the changelog buffer of the tree
+/// The current root has age 0
+pub fn get_merkle_tree_root_age(tree_account: &AccountInfo, root: &[u8; 32]) -> Result<Option<u32>> {
+    let header = get_merkle_tree_header(tree_account)?;
+    let max_depth = header.get_max_depth() as usize;
+    let max_buffer_size = header.get_max_buffer_size() as usize;
+
+    let data = tree_account.try_borrow_data()?;
+    let tree_bytes = &data[CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..];
+
+    let active_index = read_merkle_tree_u64(tree_bytes, ACTIVE_INDEX_OFFSET)? as usize;
+    let buffer_size = read_merkle_tree_u64(tree_bytes, BUFFER_SIZE_OFFSET)? as usize;
+
+    let change_log_size = 32 + 32 * max_depth + 4 + 4;
+
+    // The changelogs are a circular buffer and the older roots precede the active one
+    for age in 0..buffer_size.min(max_buffer_size) {
+        let index = (active_index + max_buffer_size - age) % max_buffer_size;
+        let root_offset = CHANGE_LOGS_OFFSET + index * change_log_size;
+
+        let change_log_root = tree_bytes
+            .get(root_offset..root_offset + 32)
+            .ok_or(NftVoterError::InvalidMerkleTree)?;
+
+        if change_log_root == root {
+            return Ok(Some(age as u32));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Fast-forwards the given proof of the leaf at the given index from the given historical root to the current root
+/// of the tree using the changelogs recorded since the root, the same way spl-account-compression verify_leaf does
+/// Each changelog replaces the node of the proof at the level where the path of the changed leaf diverges
+/// Returns false if the root is no longer in the changelog or the leaf itself was changed since the root
+pub fn fast_forward_merkle_proof(
+    tree_account: &AccountInfo,
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    index: u32,
+    proof: &mut [[u8; 32]]
+) -> Result<bool> {
+    let root_age = match get_merkle_tree_root_age(tree_account, root)? {
+        Some(root_age) => root_age as usize,
+        None => {
+            return Ok(false);
+        }
+    };
+
+    let header = get_merkle_tree_header(tree_account)?;
+    let max_depth = header.get_max_depth() as usize;
+    let max_buffer_size = header.get_max_buffer_size() as usize;
+
+    require!(proof.len() == max_depth, NftVoterError::InvalidProofData);
+
+    let data = tree_account.try_borrow_data()?;
+    let tree_bytes = &data[CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..];
+
+    let active_index = read_merkle_tree_u64(tree_bytes, ACTIVE_INDEX_OFFSET)? as usize;
+
+    let change_log_size = 32 + 32 * max_depth + 4 + 4;
+    let read_node = |offset: usize| -> Result<[u8; 32]> {
+        Ok(
+            tree_bytes
+                .get(offset..offset + 32)
+                .ok_or(NftVoterError::InvalidMerkleTree)?
+                .try_into()
+                .unwrap()
+        )
+    };
+
+    let mut updated_leaf = *leaf;
+
+    // The changelogs recorded after the root are applied from the oldest to the active one
+    for age in (0..root_age).rev() {
+        let change_log_index = (active_index + max_buffer_size - age) % max_buffer_size;
+        let path_offset = CHANGE_LOGS_OFFSET + change_log_index * change_log_size + 32;
+        let index_offset = path_offset + 32 * max_depth;
+
+        let change_log_leaf_index = u32::from_le_bytes(
+            tree_bytes
+                .get(index_offset..index_offset + 4)
+                .ok_or(NftVoterError::InvalidMerkleTree)?
+                .try_into()
+                .unwrap()
+        );
+
+        if change_log_leaf_index == index {
+            // The first node of the changelog path is the new leaf
+            updated_leaf = read_node(path_offset)?;
+        } else {
+            let common_path_len = ((index ^ change_log_leaf_index) << (32 - max_depth)).leading_zeros() as usize;
+            let critbit_index = max_depth - 1 - common_path_len;
+
+            proof[critbit_index] = read_node(path_offset + 32 * critbit_index)?;
+        }
+    }
+
+    Ok(updated_leaf == *leaf)
+}
+
+/// Returns the sequence number (the number of changes) of the given spl-account-compression merkle tree
+/// and whether its changelog buffer is full
+/// Once the buffer is full every change of the tree evicts the oldest root from the changelog
+pub fn get_merkle_tree_changelog_usage(tree_account: &AccountInfo) -> Result<(u64, bool)> {
+    let max_buffer_size = get_merkle_tree_header(tree_account)?.get_max_buffer_size() as u64;
+
+    let data = tree_account.try_borrow_data()?;
+    let tree_bytes = &data[CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..];
+
+    let sequence_number = read_merkle_tree_u64(tree_bytes, SEQUENCE_NUMBER_OFFSET)?;
+    let buffer_size = read_merkle_tree_u64(tree_bytes, BUFFER_SIZE_OFFSET)?;
+
+    Ok((sequence_number, buffer_size >= max_buffer_size))
+}
+
+/// Returns the leaf of the given wallet in the voter allowlist merkle tree
+pub fn get_voter_allowlist_leaf(voter: &Pubkey) -> [u8; 32] {
+    hashv(&[voter.as_ref()]).to_bytes()
+}
+
+/// Returns the leaf of the given wallet and its voter weight in the WeightSnapshot holdings merkle tree
+/// The leaf is keccak(wallet, weight as little endian u64)
+pub fn get_weight_snapshot_leaf(voter: &Pubkey, voter_weight: u64) -> [u8; 32] {
+    hashv(&[voter.as_ref(), &voter_weight.to_le_bytes()]).to_bytes()
+}
+
+/// Verifies the given leaf belongs to the merkle tree with the given root
+/// The pairs of nodes are hashed in sorted order and hence the proof doesn't depend on the leaf index
+pub fn verify_sorted_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: [u8; 32]) -> bool {
+    let mut node = leaf;
+
+    for sibling in proof.iter() {
+        node = if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+
+    node == root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_verify_sorted_merkle_proof() {
+        // Arrange
+        let leaf1 = get_voter_allowlist_leaf(&Pubkey::new_unique());
+        let leaf2 = get_voter_allowlist_leaf(&Pubkey::new_unique());
+
+        let root = if leaf1 <= leaf2 {
+            hashv(&[&leaf1, &leaf2]).to_bytes()
+        } else {
+            hashv(&[&leaf2, &leaf1]).to_bytes()
+        };
+
+        // Act
+        let is_leaf1_valid = verify_sorted_merkle_proof(leaf1, &[leaf2], root);
+        let is_leaf2_valid = verify_sorted_merkle_proof(leaf2, &[leaf1], root);
+        let is_leaf1_without_proof_valid = verify_sorted_merkle_proof(leaf1, &[], root);
+
+        // Assert
+        assert!(is_leaf1_valid);
+        assert!(is_leaf2_valid);
+        assert!(!is_leaf1_without_proof_valid);
+    }
+}
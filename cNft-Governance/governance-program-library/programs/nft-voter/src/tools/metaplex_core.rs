@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+
+/// Metaplex Core program
+pub mod mpl_core_program {
+    use anchor_lang::declare_id;
+    declare_id!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+}
+
+/// Metaplex Core account keys (the first byte of the account data)
+pub const CORE_ASSET_V1_KEY: u8 = 1;
+pub const CORE_COLLECTION_V1_KEY: u8 = 5;
+
+/// Update authority of a Metaplex Core asset
+/// Assets which belong to a collection have Collection update authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum CoreUpdateAuthority {
+    None,
+    Address(Pubkey),
+    Collection(Pubkey),
+}
+
+/// The base part of Metaplex Core AssetV1 account
+/// Note: The plugins stored after the base part are not decoded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CoreAsset {
+    pub key: u8,
+    pub owner: Pubkey,
+    pub update_authority: CoreUpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    pub seq: Option<u64>,
+}
+
+/// The base part of Metaplex Core CollectionV1 account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CoreCollection {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub uri: String,
+    pub num_minted: u32,
+    pub current_size: u32,
+}
+
+impl CoreAsset {
+    /// Returns the collection of the asset
+    /// Core assets can only be added to a collection by its authority and hence the collection is always verified
+    pub fn get_collection(&self) -> Option<Pubkey> {
+        match self.update_authority {
+            CoreUpdateAuthority::Collection(collection) => Some(collection),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_core_asset(account_info: &AccountInfo) -> Result<CoreAsset> {
+    if *account_info.owner != mpl_core_program::ID {
+        return Err(NftVoterError::InvalidAccountOwner.into());
+    }
+
+    let data = account_info.try_borrow_data()?;
+    let core_asset = CoreAsset::deserialize(&mut &data[..]).map_err(
+        |_| NftVoterError::InvalidCoreAsset
+    )?;
+
+    require!(core_asset.key == CORE_ASSET_V1_KEY, NftVoterError::InvalidCoreAsset);
+
+    Ok(core_asset)
+}
+
+/// Returns true if the given account is Metaplex Core CollectionV1
+pub fn is_core_collection(account_info: &AccountInfo) -> bool {
+    if *account_info.owner != mpl_core_program::ID {
+        return false;
+    }
+
+    match account_info.try_borrow_data() {
+        Ok(data) =>
+            matches!(
+                CoreCollection::deserialize(&mut &data[..]),
+                Ok(collection) if collection.key == CORE_COLLECTION_V1_KEY
+            ),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_core_asset(update_authority: CoreUpdateAuthority) -> CoreAsset {
+        CoreAsset {
+            key: CORE_ASSET_V1_KEY,
+            owner: Pubkey::new_unique(),
+            update_authority,
+            name: "Core Asset".to_string(),
+            uri: "https://core.metaplex.com/".to_string(),
+            seq: None,
+        }
+    }
+
+    #[test]
+    fn test_get_core_asset_with_plugins_data() {
+        // Arrange
+        let collection = Pubkey::new_unique();
+        let core_asset = create_core_asset(CoreUpdateAuthority::Collection(collection));
+
+        let mut data = core_asset.try_to_vec().unwrap();
+        // Plugin header and registry follow the base asset data
+        data.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &mpl_core_program::ID,
+            false,
+            0
+        );
+
+        // Act
+        let actual_core_asset = get_core_asset(&account_info).unwrap();
+
+        // Assert
+        assert_eq!(actual_core_asset, core_asset);
+        assert_eq!(actual_core_asset.get_collection(), Some(collection));
+    }
+
+    #[test]
+    fn test_get_core_asset_with_invalid_owner_error() {
+        // Arrange
+        let core_asset = create_core_asset(CoreUpdateAuthority::None);
+        let mut data = core_asset.try_to_vec().unwrap();
+
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0
+        );
+
+        // Act
+        let err = get_core_asset(&account_info).err().unwrap();
+
+        // Assert
+        assert_eq!(err, NftVoterError::InvalidAccountOwner.into());
+    }
+
+    #[test]
+    fn test_is_core_collection() {
+        // Arrange
+        let core_collection = CoreCollection {
+            key: CORE_COLLECTION_V1_KEY,
+            update_authority: Pubkey::new_unique(),
+            name: "Core Collection".to_string(),
+            uri: "https://core.metaplex.com/".to_string(),
+            num_minted: 10,
+            current_size: 10,
+        };
+        let mut data = core_collection.try_to_vec().unwrap();
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &mpl_core_program::ID,
+            false,
+            0
+        );
+
+        // Act
+        let is_collection = is_core_collection(&account_info);
+
+        // Assert
+        assert!(is_collection);
+    }
+}
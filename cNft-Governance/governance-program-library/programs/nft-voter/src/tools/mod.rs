@@ -3,3 +3,12 @@ pub mod governance;
 pub mod spl_token;
 pub mod token_metadata;
 pub mod accounts;
+pub mod accounts_cursor;
+pub mod ed25519;
+pub mod merkle_tree;
+pub mod tree_shapes;
+pub mod program_ids;
+#[cfg(feature = "metaplex-core")]
+pub mod metaplex_core;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
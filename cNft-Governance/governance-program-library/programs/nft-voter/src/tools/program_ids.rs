@@ -0,0 +1,68 @@
+//! Program IDs of the programs the plugin interacts with on the cluster selected by the devnet or mainnet feature
+//! Without either feature the mainnet program IDs are used
+//!
+//! Note: The programs are currently deployed at the same addresses on both clusters
+//! and a redeployment on one of the clusters only changes the module of that cluster
+//! The plugin itself is deployed at the address of declare_id! in lib.rs on both clusters
+
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("The devnet and mainnet features are mutually exclusive");
+
+#[cfg(not(feature = "devnet"))]
+mod cluster {
+    use anchor_lang::prelude::Pubkey;
+
+    /// Bubblegum program the compressed NFTs are minted with
+    pub const BUBBLEGUM_ID: Pubkey = mpl_bubblegum::ID;
+
+    /// spl-account-compression program which owns the Bubblegum trees of LeafSchema::V1 assets
+    pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = spl_account_compression::ID;
+
+    /// mpl-account-compression program which owns the Bubblegum trees of LeafSchema::V2 assets
+    pub const MPL_ACCOUNT_COMPRESSION_ID: Pubkey = solana_program::pubkey!(
+        "mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW"
+    );
+
+    /// spl-governance program instance of the cluster
+    /// Note: Registrars store the governance program of their Realm and it's only used as a default by clients
+    pub const SPL_GOVERNANCE_ID: Pubkey = spl_governance::ID;
+}
+
+#[cfg(feature = "devnet")]
+mod cluster {
+    use anchor_lang::prelude::Pubkey;
+
+    /// Bubblegum program the compressed NFTs are minted with
+    pub const BUBBLEGUM_ID: Pubkey = solana_program::pubkey!(
+        "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+    );
+
+    /// spl-account-compression program which owns the Bubblegum trees of LeafSchema::V1 assets
+    pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = solana_program::pubkey!(
+        "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK"
+    );
+
+    /// mpl-account-compression program which owns the Bubblegum trees of LeafSchema::V2 assets
+    pub const MPL_ACCOUNT_COMPRESSION_ID: Pubkey = solana_program::pubkey!(
+        "mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW"
+    );
+
+    /// spl-governance program instance of the cluster
+    /// Note: Registrars store the governance program of their Realm and it's only used as a default by clients
+    pub const SPL_GOVERNANCE_ID: Pubkey = solana_program::pubkey!(
+        "GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw"
+    );
+}
+
+pub use cluster::*;
+
+/// Bubblegum program type for Program<'info, Bubblegum> accounts
+/// Note: The mpl-bubblegum client crate doesn't export Anchor program types
+#[derive(Clone)]
+pub struct Bubblegum;
+
+impl anchor_lang::Id for Bubblegum {
+    fn id() -> anchor_lang::prelude::Pubkey {
+        BUBBLEGUM_ID
+    }
+}
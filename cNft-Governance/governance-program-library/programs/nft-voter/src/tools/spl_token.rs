@@ -1,6 +1,23 @@
 use anchor_lang::prelude::*;
 use arrayref::array_ref;
+use solana_program::program_pack::{ IsInitialized, Pack };
 use spl_governance::tools::spl_token::assert_is_valid_spl_token_account;
+use spl_token::state::Mint;
+
+use crate::error::NftVoterError;
+
+/// spl-token-2022 program
+pub mod spl_token_2022_program {
+    use anchor_lang::declare_id;
+    declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+}
+
+/// The offset of the account type of spl-token-2022 accounts with extensions
+/// Mints are padded to the size of token accounts before the account type and the extensions TLV data
+pub const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = spl_token::state::Account::LEN;
+
+/// spl-token-2022 AccountType::Mint
+pub const TOKEN_2022_ACCOUNT_TYPE_MINT: u8 = 1;
 
 /// Computationally cheap method to get amount from a token account
 /// It reads amount without deserializing full account data
@@ -13,3 +30,35 @@ pub fn get_spl_token_amount(token_account_info: &AccountInfo) -> Result<u64> {
 
     Ok(u64::from_le_bytes(*amount_bytes))
 }
+
+/// Asserts the given account is an initialized governing token mint of either spl-token or spl-token-2022
+/// Token-2022 mints can have extensions (ex. transfer hook, metadata pointer) and only the base mint is validated
+/// Note: The governing token mint is only used as identity of the voting population and its extensions are ignored
+pub fn assert_is_valid_governing_token_mint(mint_info: &AccountInfo) -> Result<()> {
+    let is_token_2022 = *mint_info.owner == spl_token_2022_program::ID;
+
+    require!(
+        *mint_info.owner == spl_token::ID || is_token_2022,
+        NftVoterError::InvalidGoverningTokenMint
+    );
+
+    let data = mint_info.try_borrow_data()?;
+
+    if is_token_2022 && data.len() > Mint::LEN {
+        require!(
+            data.len() > TOKEN_2022_ACCOUNT_TYPE_OFFSET &&
+                data[TOKEN_2022_ACCOUNT_TYPE_OFFSET] == TOKEN_2022_ACCOUNT_TYPE_MINT,
+            NftVoterError::InvalidGoverningTokenMint
+        );
+    } else {
+        require!(data.len() == Mint::LEN, NftVoterError::InvalidGoverningTokenMint);
+    }
+
+    let mint = Mint::unpack_from_slice(&data[..Mint::LEN]).map_err(
+        |_| NftVoterError::InvalidGoverningTokenMint
+    )?;
+
+    require!(mint.is_initialized(), NftVoterError::InvalidGoverningTokenMint);
+
+    Ok(())
+}
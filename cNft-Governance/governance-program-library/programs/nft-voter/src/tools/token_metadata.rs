@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use mpl_token_metadata::{accounts::Metadata, types::Key};
 
 use crate::error::NftVoterError;
 
@@ -9,11 +9,14 @@ pub fn get_token_metadata(account_info: &AccountInfo) -> Result<Metadata> {
         return Err(NftVoterError::InvalidAccountOwner.into());
     }
 
-    let metadata = Metadata::from_account_info(account_info)?;
+    // safe_deserialize tolerates the legacy Metadata layouts without the trailing optional fields
+    let metadata = Metadata::safe_deserialize(&account_info.try_borrow_data()?).map_err(
+        |_| NftVoterError::InvalidTokenMetadataAccount
+    )?;
 
-    // I'm not sure if this is needed but try_from_slice_checked in from_account_info
+    // I'm not sure if this is needed but safe_deserialize
     // ignores Key::Uninitialized and hence checking for the exact Key match here
-    if metadata.key != mpl_token_metadata::state::Key::MetadataV1 {
+    if metadata.key != Key::MetadataV1 {
         return Err(NftVoterError::InvalidTokenMetadataAccount.into());
     }
 
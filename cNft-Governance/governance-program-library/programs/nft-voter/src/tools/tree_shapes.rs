@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::error::NftVoterError;
+
+/// (max_depth, max_buffer_size) shapes of ConcurrentMerkleTree supported by spl-account-compression
+/// and mpl-account-compression
+/// Trees can't be created with any other shape and the headers of other shapes are rejected
+/// before the tree data is read
+pub const TREE_SHAPES: [(u32, u32); 26] = [
+    (3, 8),
+    (5, 8),
+    (14, 64),
+    (14, 256),
+    (14, 1024),
+    (14, 2048),
+    (15, 64),
+    (16, 64),
+    (17, 64),
+    (18, 64),
+    (19, 64),
+    (20, 64),
+    (20, 256),
+    (20, 1024),
+    (20, 2048),
+    (24, 64),
+    (24, 256),
+    (24, 512),
+    (24, 1024),
+    (24, 2048),
+    (26, 512),
+    (26, 1024),
+    (26, 2048),
+    (30, 512),
+    (30, 1024),
+    (30, 2048),
+];
+
+/// Returns true if the tree shape is one of TREE_SHAPES
+pub fn is_supported_tree_shape(max_depth: u32, max_buffer_size: u32) -> bool {
+    TREE_SHAPES.contains(&(max_depth, max_buffer_size))
+}
+
+/// Asserts the tree shape is one of TREE_SHAPES
+pub fn assert_supported_tree_shape(max_depth: u32, max_buffer_size: u32) -> Result<()> {
+    if !is_supported_tree_shape(max_depth, max_buffer_size) {
+        msg!(
+            "Unsupported tree shape max_depth: {} max_buffer_size: {}",
+            max_depth,
+            max_buffer_size
+        );
+        return err!(NftVoterError::UnsupportedMerkleTreeShape);
+    }
+
+    Ok(())
+}
+
+/// Returns the size of ConcurrentMerkleTree<max_depth, max_buffer_size> without the header and the canopy
+/// sequence_number(8), active_index(8), buffer_size(8), change_logs: [ChangeLog; max_buffer_size], rightmost_proof: Path
+/// ChangeLog { root, path: [Node; max_depth], index: u32, _padding: u32 } and Path { proof: [Node; max_depth], leaf, index: u32, _padding: u32 }
+/// have the same size
+pub fn get_concurrent_merkle_tree_size(max_depth: usize, max_buffer_size: usize) -> usize {
+    let change_log_size = 32 + 32 * max_depth + 4 + 4;
+
+    24 + (max_buffer_size + 1) * change_log_size
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use spl_account_compression::ConcurrentMerkleTree;
+    use std::mem::size_of;
+
+    #[test]
+    fn test_get_concurrent_merkle_tree_size() {
+        // Act + Assert
+        assert_eq!(get_concurrent_merkle_tree_size(3, 8), size_of::<ConcurrentMerkleTree<3, 8>>());
+        assert_eq!(
+            get_concurrent_merkle_tree_size(14, 64),
+            size_of::<ConcurrentMerkleTree<14, 64>>()
+        );
+        assert_eq!(
+            get_concurrent_merkle_tree_size(30, 2048),
+            size_of::<ConcurrentMerkleTree<30, 2048>>()
+        );
+    }
+
+    #[test]
+    fn test_assert_supported_tree_shape() {
+        // Act + Assert
+        assert!(assert_supported_tree_shape(14, 64).is_ok());
+        assert!(assert_supported_tree_shape(14, 65).is_err());
+        assert!(assert_supported_tree_shape(0, 0).is_err());
+    }
+}
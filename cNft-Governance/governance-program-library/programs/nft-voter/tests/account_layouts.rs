@@ -0,0 +1,475 @@
+//! Account layout and PDA compatibility tests
+//!
+//! The accounts are replayed from raw bytes encoded field by field (as stored on chain) and not using the program types.
+//! They must keep passing unchanged across Anchor, Solana and mpl-bubblegum upgrades to guarantee
+//! the existing Registrars, records and tickets can still be read and their PDAs are still derived the same way.
+//!
+//! The accounts created by the program before the Anchor 0.29 port are frozen in fixtures/legacy_accounts/legacy_accounts.json
+//! produced by generate.py. Their bytes must never be regenerated from the program types.
+
+use std::str::FromStr;
+
+use anchor_lang::{ AccountDeserialize, AccountSerialize };
+use borsh::{ BorshDeserialize, BorshSerialize };
+use serde_json::Value;
+use gpl_nft_voter::state::max_voter_weight_record::{
+    get_max_voter_weight_record_address,
+    MaxVoterWeightRecord,
+};
+use gpl_nft_voter::state::*;
+use solana_program::pubkey::Pubkey;
+
+fn find_program_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &gpl_nft_voter::id()).0
+}
+
+const LEGACY_ACCOUNTS: &str = include_str!("fixtures/legacy_accounts/legacy_accounts.json");
+
+/// Returns the frozen legacy account fixture of the given account type
+fn get_legacy_account(account: &str) -> Value {
+    serde_json::from_str::<Value>(LEGACY_ACCOUNTS)
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|legacy_account| legacy_account["account"] == account)
+        .unwrap()
+        .clone()
+}
+
+fn to_pubkey(value: &Value) -> Pubkey {
+    Pubkey::from_str(value.as_str().unwrap()).unwrap()
+}
+
+fn to_data(value: &Value) -> Vec<u8> {
+    let hex = value.as_str().unwrap();
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Asserts the decoded account serializes back to the exact bytes it was decoded from
+/// Bytes beyond the serialized account must be the zeroed space allocated for the account to grow
+fn assert_reserializes_to<T: AccountSerialize>(account: &T, data: &[u8]) {
+    let mut serialized = vec![];
+    account.try_serialize(&mut serialized).unwrap();
+
+    assert_eq!(serialized[..], data[..serialized.len()]);
+    assert!(data[serialized.len()..].iter().all(|b| *b == 0));
+}
+
+#[test]
+fn test_program_id() {
+    assert_eq!(
+        gpl_nft_voter::id(),
+        Pubkey::from_str("GnftVc21v2BRchsRa9dGdrVmJPLZiRHe9j2offnFTZFg").unwrap()
+    );
+}
+
+#[test]
+fn test_replay_legacy_registrar() {
+    // Arrange
+    let legacy_account = get_legacy_account("Registrar");
+    let fields = &legacy_account["fields"];
+    let data = to_data(&legacy_account["data"]);
+
+    // Act
+    let registrar = Registrar::try_deserialize(&mut &data[..]).unwrap();
+
+    // Assert
+    assert_eq!(registrar.governance_program_id, to_pubkey(&fields["governance_program_id"]));
+    assert_eq!(registrar.realm, to_pubkey(&fields["realm"]));
+    assert_eq!(registrar.governing_token_mint, to_pubkey(&fields["governing_token_mint"]));
+    assert_eq!(registrar.reserved, [0; 128]);
+
+    let collection_configs = fields["collection_configs"].as_array().unwrap();
+    assert_eq!(registrar.collection_configs.len(), collection_configs.len());
+
+    for (collection_config, expected) in registrar.collection_configs.iter().zip(collection_configs) {
+        assert_eq!(
+            *collection_config,
+            CollectionConfig::new(
+                to_pubkey(&expected["collection"]),
+                expected["weight"].as_u64().unwrap(),
+                expected["size"].as_u64().unwrap() as u32
+            )
+        );
+    }
+
+    let max_collections = legacy_account["max_collections"].as_u64().unwrap() as u8;
+    assert_eq!(data.len(), Registrar::space_for(max_collections));
+    assert_reserializes_to(&registrar, &data);
+
+    assert_eq!(
+        get_registrar_address(&registrar.realm, &registrar.governing_token_mint),
+        to_pubkey(&legacy_account["address"])
+    );
+
+    // Legacy Registrars have no RegistrarExtension until migrate_registrar and vote with the default config
+    let registrar_extension = RegistrarExtension::new(to_pubkey(&legacy_account["address"]));
+    assert_eq!(registrar.get_max_voter_weight(&registrar_extension).unwrap(), 10 * 3 + 10_000);
+}
+
+#[test]
+fn test_replay_legacy_voter_weight_record() {
+    // Arrange
+    let legacy_account = get_legacy_account("VoterWeightRecord");
+    let fields = &legacy_account["fields"];
+    let data = to_data(&legacy_account["data"]);
+
+    // Act
+    let voter_weight_record = VoterWeightRecord::try_deserialize(&mut &data[..]).unwrap();
+
+    // Assert
+    assert_eq!(voter_weight_record.realm, to_pubkey(&fields["realm"]));
+    assert_eq!(voter_weight_record.governing_token_mint, to_pubkey(&fields["governing_token_mint"]));
+    assert_eq!(voter_weight_record.governing_token_owner, to_pubkey(&fields["governing_token_owner"]));
+    assert_eq!(voter_weight_record.voter_weight, fields["voter_weight"].as_u64().unwrap());
+    assert_eq!(voter_weight_record.voter_weight_expiry, fields["voter_weight_expiry"].as_u64());
+    assert_eq!(voter_weight_record.weight_action, Some(VoterWeightAction::CastVote));
+    assert_eq!(
+        voter_weight_record.weight_action_target,
+        Some(to_pubkey(&fields["weight_action_target"]))
+    );
+
+    // The fields stored in the previously reserved space are read with their default values
+    assert_eq!(voter_weight_record.weight_state, VoterWeightState::Consumed);
+    assert_eq!(voter_weight_record.record_nonce, 0);
+    assert_eq!(voter_weight_record.reserved, [0; 3]);
+
+    assert_eq!(data.len(), VoterWeightRecord::get_space());
+    assert_reserializes_to(&voter_weight_record, &data);
+
+    assert_eq!(
+        get_voter_weight_record_address(
+            &voter_weight_record.realm,
+            &voter_weight_record.governing_token_mint,
+            &voter_weight_record.governing_token_owner
+        ),
+        to_pubkey(&legacy_account["address"])
+    );
+}
+
+#[test]
+fn test_replay_legacy_max_voter_weight_record() {
+    // Arrange
+    let legacy_account = get_legacy_account("MaxVoterWeightRecord");
+    let fields = &legacy_account["fields"];
+    let data = to_data(&legacy_account["data"]);
+
+    // Act
+    let max_voter_weight_record = MaxVoterWeightRecord::try_deserialize(&mut &data[..]).unwrap();
+
+    // Assert
+    assert_eq!(max_voter_weight_record.realm, to_pubkey(&fields["realm"]));
+    assert_eq!(max_voter_weight_record.governing_token_mint, to_pubkey(&fields["governing_token_mint"]));
+    assert_eq!(max_voter_weight_record.max_voter_weight, fields["max_voter_weight"].as_u64().unwrap());
+    assert_eq!(max_voter_weight_record.max_voter_weight_expiry, None);
+
+    assert_eq!(data.len(), MaxVoterWeightRecord::get_space());
+    assert_reserializes_to(&max_voter_weight_record, &data);
+
+    assert_eq!(
+        get_max_voter_weight_record_address(
+            &max_voter_weight_record.realm,
+            &max_voter_weight_record.governing_token_mint
+        ),
+        to_pubkey(&legacy_account["address"])
+    );
+}
+
+#[test]
+fn test_replay_legacy_nft_vote_record() {
+    // Arrange
+    let legacy_account = get_legacy_account("NftVoteRecord");
+    let fields = &legacy_account["fields"];
+    let data = to_data(&legacy_account["data"]);
+
+    let proposal = to_pubkey(&fields["proposal"]);
+    let nft_mint = to_pubkey(&fields["nft_mint"]);
+
+    // Act
+    let nft_vote_record = NftVoteRecord::try_from_slice(&data).unwrap();
+
+    // Assert
+    assert_eq!(nft_vote_record, NftVoteRecord {
+        version: NftVoteRecordVersion::V1,
+        ..NftVoteRecord::new(
+            proposal,
+            nft_mint,
+            to_pubkey(&fields["governing_token_owner"]),
+            NftVoteKind::Unknown
+        )
+    });
+    assert_eq!(nft_vote_record.try_to_vec().unwrap(), data);
+
+    // The legacy records were derived without the registrar
+    assert_eq!(
+        find_program_address(&[b"nft-vote-record", proposal.as_ref(), nft_mint.as_ref()]),
+        to_pubkey(&legacy_account["address"])
+    );
+}
+
+#[test]
+fn test_replay_legacy_nft_action_ticket() {
+    // Arrange
+    let legacy_account = get_legacy_account("NftActionTicket");
+    let fields = &legacy_account["fields"];
+    let data = to_data(&legacy_account["data"]);
+
+    // Act
+    let result = NftActionTicket::deserialize(&mut &data[..]);
+
+    // Assert
+    // The tickets are valid for NFT_ACTION_TICKET_VALIDITY_SLOTS only and the legacy layout without
+    // the collection can't be read, the legacy tickets must be rejected and not read with shifted fields
+    assert!(result.is_err());
+    assert!(data.len() < NFT_ACTION_TICKET_SIZE);
+    assert_eq!(data[..8], NftActionTicket::ACCOUNT_DISCRIMINATOR);
+
+    assert_eq!(
+        get_nft_action_ticket_address(
+            "nft-castVote-ticket",
+            &to_pubkey(&fields["registrar"]),
+            &to_pubkey(&fields["governing_token_owner"]),
+            &to_pubkey(&fields["nft_mint"])
+        ).0,
+        to_pubkey(&legacy_account["address"])
+    );
+}
+
+#[test]
+fn test_replay_registrar_extension() {
+    // Arrange
+    let registrar = Pubkey::new_unique();
+    let tree_authority = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+
+    let data = [
+        // sha256("account:RegistrarExtension")[..8]
+        &[3, 188, 98, 234, 34, 172, 23, 222][..],
+        registrar.as_ref(),
+        &[1], // version
+        // config: RegistrarConfig
+        &[1], // collection_weight_mode: UniqueCollections
+        &1_000u16.to_le_bytes(), // max_collection_weight_per_proposal
+        &100u64.to_le_bytes(), // min_holding_slots
+        &[0; 32], // holding_attestation_authority
+        &[1], // require_proposal_creation_ticket
+        &[0], // track_voter_weight_detail
+        tree_authority.as_ref(),
+        &[0; 32 * 3], // unused tree_authority_allowlist
+        &[0], // allow_precomputed_metadata_hashes
+        &[0; 32], // voter_allowlist_root
+        &[0; 32], // rent_reclaim_treasury
+        &0u16.to_le_bytes(), // rent_reclaim_treasury_share_bps
+        &[0], // track_collection_stats
+        &[0; 32], // parent_registrar
+        &[0], // require_same_transaction_consumption
+        &0u64.to_le_bytes(), // normalized_collection_weight
+        &[0], // allow_core_assets
+        &5u16.to_le_bytes(), // max_assets_per_tx
+        &[0], // require_vote_finalization
+        &0u16.to_le_bytes(), // max_tree_assets_per_slot
+        &0u64.to_le_bytes(), // proof_attestation_freshness_slots
+        &0u32.to_le_bytes(), // max_tree_root_age
+        &[0], // allow_voter_migration
+        &[0; 32], // leaf_hash_verifier
+        &0u64.to_le_bytes(), // weight_record_min_age_slots
+        &[0], // record_voter_checkpoints
+        &[0], // require_weight_action_target
+        &[0; 32], // weight_export_program
+        &[0], // allow_collection_aliases
+        &0u64.to_le_bytes(), // min_weight_if_registered_before_slot
+        &0u64.to_le_bytes(), // registration_cutoff_slot
+        &[0; 32], // bubblegum_program_override
+        &[0], // allow_wallet_weight_overrides
+        &[0], // allow_weight_leases
+        &[0; 32], // vote_receipt_tree
+        &7u64.to_le_bytes(), // sub_registrars_max_voter_weight
+        &0i64.to_le_bytes(), // legacy_nft_vote_records_cutoff
+        &0u64.to_le_bytes(), // registered_voters
+        // collection_extensions: Vec<CollectionExtension>
+        &1u32.to_le_bytes(),
+        collection.as_ref(),
+        creator.as_ref(),
+        &5_000u16.to_le_bytes(),
+        &[0; 34 * 2], // unused creator_boosts
+        &1_000i64.to_le_bytes(), // expires_at
+        &[3], // weight_strategy: Capped
+        &2u16.to_le_bytes(), // max_assets
+        &[1], // uses_limited
+        &[0; 32], // collection extension reserved
+        &[0; 128], // reserved
+    ].concat();
+
+    // Act
+    let registrar_extension = RegistrarExtension::try_deserialize(&mut &data[..]).unwrap();
+
+    // Assert
+    assert_eq!(data.len(), RegistrarExtension::space_for(1));
+    assert_eq!(registrar_extension.registrar, registrar);
+    assert_eq!(registrar_extension.version, REGISTRAR_EXTENSION_VERSION);
+
+    let config = &registrar_extension.config;
+    assert_eq!(config.collection_weight_mode, CollectionWeightMode::UniqueCollections);
+    assert_eq!(config.max_collection_weight_per_proposal, 1_000);
+    assert_eq!(config.min_holding_slots, 100);
+    assert!(config.require_proposal_creation_ticket);
+    assert!(config.is_tree_authority_allowed(&tree_authority));
+    assert_eq!(config.max_assets_per_tx, 5);
+    assert_eq!(registrar_extension.sub_registrars_max_voter_weight, 7);
+
+    let collection_extension = registrar_extension.get_collection_extension(&collection);
+    assert_eq!(collection_extension.creator_boosts[0], CreatorBoost {
+        creator,
+        boost_bps: 5_000,
+    });
+    assert_eq!(collection_extension.expires_at, 1_000);
+    assert_eq!(collection_extension.weight_strategy, CollectionWeightStrategy::Capped { max_assets: 2 });
+    assert!(collection_extension.uses_limited);
+
+    assert_eq!(
+        get_registrar_extension_address(&registrar),
+        find_program_address(&[b"registrar-extension", registrar.as_ref()])
+    );
+}
+
+#[test]
+fn test_replay_nft_action_ticket() {
+    // Arrange
+    let registrar = Pubkey::new_unique();
+    let governing_token_owner = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+
+    let data = [
+        &NftActionTicket::ACCOUNT_DISCRIMINATOR[..],
+        registrar.as_ref(),
+        governing_token_owner.as_ref(),
+        nft_mint.as_ref(),
+        &3u64.to_le_bytes(), // weight
+        collection.as_ref(),
+        &[1],
+        &110u64.to_le_bytes(), // expiry
+        &1_700_000_000i64.to_le_bytes(), // delegation_expires_at
+        &100u64.to_le_bytes(), // created_at_slot
+    ].concat();
+
+    // Act
+    let nft_action_ticket = NftActionTicket::try_from_slice(&data).unwrap();
+
+    // Assert
+    assert_eq!(nft_action_ticket.account_discriminator, [170, 179, 4, 130, 24, 148, 185, 97]);
+    assert_eq!(nft_action_ticket.registrar, registrar);
+    assert_eq!(nft_action_ticket.governing_token_owner, governing_token_owner);
+    assert_eq!(nft_action_ticket.nft_mint, nft_mint);
+    assert_eq!(nft_action_ticket.weight, 3);
+    assert_eq!(nft_action_ticket.collection, collection);
+    assert_eq!(nft_action_ticket.expiry, Some(110));
+    assert_eq!(nft_action_ticket.delegation_expires_at, 1_700_000_000);
+    assert_eq!(nft_action_ticket.created_at_slot, 100);
+    assert_eq!(data.len(), NFT_ACTION_TICKET_SIZE);
+}
+
+#[test]
+fn test_replay_nft_vote_record_v2() {
+    // Arrange
+    let proposal = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let governing_token_owner = Pubkey::new_unique();
+
+    let data = [
+        // sha256("account:NftVoteRecord")[..8]
+        &[137, 6, 55, 139, 251, 126, 254, 99][..],
+        proposal.as_ref(),
+        nft_mint.as_ref(),
+        governing_token_owner.as_ref(),
+        &[1], // version: V2
+        &[4], // vote_kind: Veto
+        &[0; 6], // reserved
+    ].concat();
+
+    // Act
+    let nft_vote_record = NftVoteRecord::try_from_slice(&data).unwrap();
+
+    // Assert
+    assert_eq!(
+        nft_vote_record,
+        NftVoteRecord::new(proposal, nft_mint, governing_token_owner, NftVoteKind::Veto)
+    );
+}
+
+#[test]
+fn test_pda_seeds() {
+    // Arrange
+    let realm = Pubkey::new_unique();
+    let governing_token_mint = Pubkey::new_unique();
+    let registrar = Pubkey::new_unique();
+    let governing_token_owner = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let proposal = Pubkey::new_unique();
+
+    // Act + Assert
+    assert_eq!(
+        get_registrar_address(&realm, &governing_token_mint),
+        find_program_address(&[b"registrar", realm.as_ref(), governing_token_mint.as_ref()])
+    );
+
+    assert_eq!(
+        get_max_voter_weight_record_address(&realm, &governing_token_mint),
+        find_program_address(
+            &[b"max-voter-weight-record", realm.as_ref(), governing_token_mint.as_ref()]
+        )
+    );
+
+    assert_eq!(
+        get_voter_weight_record_address(&realm, &governing_token_mint, &governing_token_owner),
+        find_program_address(
+            &[
+                b"voter-weight-record",
+                realm.as_ref(),
+                governing_token_mint.as_ref(),
+                governing_token_owner.as_ref(),
+            ]
+        )
+    );
+
+    assert_eq!(
+        get_nft_vote_record_address(&registrar, &proposal, &nft_mint),
+        find_program_address(
+            &[b"nft-vote-record", registrar.as_ref(), proposal.as_ref(), nft_mint.as_ref()]
+        )
+    );
+
+    // Note: The misspelled SignOffProposal ticket type is part of the PDA seeds and it must be preserved
+    for (action, ticket_type) in [
+        (VoterWeightAction::CastVote, "nft-castVote-ticket"),
+        (VoterWeightAction::CommentProposal, "nft-commentProposal-ticket"),
+        (VoterWeightAction::CreateGovernance, "nft-createGovernance-ticket"),
+        (VoterWeightAction::CreateProposal, "nft-createProposal-ticket"),
+        (VoterWeightAction::SignOffProposal, "nft-cignOffProposal-ticket"),
+    ] {
+        assert_eq!(format!("nft-{}-ticket", action), ticket_type);
+
+        assert_eq!(
+            get_nft_action_ticket_address(
+                ticket_type,
+                &registrar,
+                &governing_token_owner,
+                &nft_mint
+            ).0,
+            find_program_address(
+                &[
+                    ticket_type.as_bytes(),
+                    registrar.as_ref(),
+                    governing_token_owner.as_ref(),
+                    nft_mint.as_ref(),
+                ]
+            )
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use anchor_lang::error::ErrorCode;
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::account_substitution::{
+    with_attacker_owned_copy,
+    AccountSubstitution,
+    SubstitutionError::{ Anchor, NftVoter },
+};
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+mod program_test;
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_substituted_accounts_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CreateProposal;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // The voter is also registered in another realm
+    let other_realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let other_registrar_cookie = nft_voter_test.with_registrar(&other_realm_cookie).await?;
+    let other_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &other_registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    let bench = &nft_voter_test.bench;
+
+    let substitutions = [
+        AccountSubstitution::new(
+            "registrar of other realm",
+            registrar_cookie.address,
+            other_registrar_cookie.address,
+            NftVoter(NftVoterError::InvalidVoterWeightRecordRealm)
+        ),
+        AccountSubstitution::new(
+            "registrar owned by attacker",
+            registrar_cookie.address,
+            with_attacker_owned_copy(bench, &registrar_cookie.address).await,
+            Anchor(ErrorCode::AccountOwnedByWrongProgram)
+        ),
+        AccountSubstitution::new(
+            "voter_weight_record of other realm",
+            voter_weight_record_cookie.address,
+            other_voter_weight_record_cookie.address,
+            NftVoter(NftVoterError::InvalidVoterWeightRecordRealm)
+        ),
+        AccountSubstitution::new(
+            "voter_weight_record owned by attacker",
+            voter_weight_record_cookie.address,
+            with_attacker_owned_copy(bench, &voter_weight_record_cookie.address).await,
+            Anchor(ErrorCode::AccountOwnedByWrongProgram)
+        ),
+        AccountSubstitution::new(
+            "realm",
+            realm_cookie.address,
+            other_realm_cookie.address,
+            NftVoter(NftVoterError::InvalidRealmForRegistrar)
+        ),
+        AccountSubstitution::new(
+            "instructions sysvar",
+            solana_sdk::sysvar::instructions::id(),
+            solana_sdk::sysvar::clock::id(),
+            Anchor(ErrorCode::ConstraintAddress)
+        ),
+        AccountSubstitution::new(
+            "nft_action_ticket owned by attacker",
+            nft_action_ticket_cookies[0].address,
+            with_attacker_owned_copy(bench, &nft_action_ticket_cookies[0].address).await,
+            NftVoter(NftVoterError::InvalidAccountOwner)
+        ),
+    ];
+
+    // Act + Assert
+    for substitution in substitutions.iter() {
+        let result = nft_voter_test.update_voter_weight_record_using_ix(
+            &registrar_cookie,
+            &mut voter_weight_record_cookie,
+            action.clone(),
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None,
+            substitution.apply()
+        ).await;
+
+        substitution.assert_err(result);
+    }
+
+    // The instruction without substitutions succeeds
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_substituted_accounts_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let other_realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let other_registrar_cookie = nft_voter_test.with_registrar(&other_realm_cookie).await?;
+    let other_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &other_registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Another tree of the same collection with a different root
+    let mut other_tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut other_tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let bench = &nft_voter_test.bench;
+
+    let substitutions = [
+        AccountSubstitution::new(
+            "registrar of other realm",
+            registrar_cookie.address,
+            other_registrar_cookie.address,
+            NftVoter(NftVoterError::InvalidVoterWeightRecordRealm)
+        ),
+        AccountSubstitution::new(
+            "voter_weight_record of other realm",
+            voter_weight_record_cookie.address,
+            other_voter_weight_record_cookie.address,
+            NftVoter(NftVoterError::InvalidVoterWeightRecordRealm)
+        ),
+        AccountSubstitution::new(
+            "compression_program",
+            spl_account_compression::id(),
+            spl_noop::id(),
+            Anchor(ErrorCode::InvalidProgramId)
+        ),
+        AccountSubstitution::new(
+            "tree of the same collection",
+            tree_cookie.address,
+            other_tree_cookie.address,
+            NftVoter(NftVoterError::StaleTreeRoot)
+        ),
+        AccountSubstitution::new(
+            "tree owned by attacker",
+            tree_cookie.address,
+            with_attacker_owned_copy(bench, &tree_cookie.address).await,
+            NftVoter(NftVoterError::InvalidMerkleTree)
+        ),
+    ];
+
+    // Act + Assert
+    for substitution in substitutions.iter() {
+        let result = nft_voter_test.with_create_cnft_action_ticket_using_ix(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action,
+            substitution.apply(),
+            None
+        ).await;
+
+        substitution.assert_err(result);
+    }
+
+    // The instruction without substitutions succeeds
+    nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_substituted_accounts_error() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let other_proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let other_realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let other_registrar_cookie = nft_voter_test.with_registrar(&other_realm_cookie).await?;
+    let other_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &other_registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    let bench = &nft_voter_test.bench;
+
+    let substitutions = [
+        AccountSubstitution::new(
+            "voter_weight_record of other realm",
+            voter_weight_record_cookie.address,
+            other_voter_weight_record_cookie.address,
+            NftVoter(NftVoterError::InvalidVoterWeightRecordRealm)
+        ),
+        AccountSubstitution::new(
+            "voter_token_owner_record owned by attacker",
+            voter_token_owner_record_cookie.address,
+            with_attacker_owned_copy(bench, &voter_token_owner_record_cookie.address).await,
+            Anchor(ErrorCode::ConstraintOwner)
+        ),
+        AccountSubstitution::new(
+            "governance owned by attacker",
+            proposal_cookie.account.governance,
+            with_attacker_owned_copy(bench, &proposal_cookie.account.governance).await,
+            Anchor(ErrorCode::ConstraintOwner)
+        ),
+        AccountSubstitution::new(
+            "other proposal",
+            proposal_cookie.address,
+            other_proposal_cookie.address,
+            NftVoter(NftVoterError::InvalidProposal)
+        ),
+        AccountSubstitution::new(
+            "nft_action_ticket owned by attacker",
+            nft_action_ticket_cookies[0].address,
+            with_attacker_owned_copy(bench, &nft_action_ticket_cookies[0].address).await,
+            NftVoter(NftVoterError::InvalidAccountOwner)
+        ),
+    ];
+
+    // Act + Assert
+    for substitution in substitutions.iter() {
+        let result = nft_voter_test.cast_nft_vote_using_ix(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None,
+            substitution.apply()
+        ).await;
+
+        substitution.assert_err(result);
+    }
+
+    // The instruction without substitutions succeeds
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Attacker-created trees impersonating the collection of the DAO
+//!
+//! The attacker controls the tree and mints (or appends) compressed NFTs whose metadata claims the collection
+//! of the DAO without being verified by the collection authority. The votes must fail because the collection
+//! is trusted only by the verified flag hashed into a Bubblegum leaf and never by the collection key alone
+
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use crate::program_test::tools::{ assert_compression_err, assert_nft_voter_err };
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use spl_account_compression::AccountCompressionError;
+mod program_test;
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_unverified_collection_in_attacker_tree_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let attacker_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &attacker_cookie
+    ).await?;
+
+    // The attacker mints into their own tree claiming the collection of the DAO without verifying it
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &attacker_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    assert_eq!(
+        leaf_verification_cookie.collection,
+        Some(Collection {
+            verified: false,
+            key: nft_collection_cookie.mint,
+        })
+    );
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &attacker_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CollectionMustBeVerified);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_verified_collection_claim_in_attacker_tree_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let attacker_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &attacker_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &attacker_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The attacker claims the collection is verified but Bubblegum hashed the leaf with verified = false
+    if let Some(collection) = leaf_verification_cookie.collection.as_mut() {
+        collection.verified = true;
+    }
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &attacker_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_compression_err(err, AccountCompressionError::ConcurrentMerkleTreeError);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_forged_leaf_in_non_bubblegum_tree_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let attacker_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &attacker_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &attacker_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, _, asset_id) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The attacker is the authority of the tree and appends the leaf hashed with verified = true
+    // directly through the compression program bypassing Bubblegum
+    if let Some(collection) = leaf_verification_cookie.collection.as_mut() {
+        collection.verified = true;
+    }
+
+    let (root, proof_nodes) = nft_voter_test.merkle_tree.with_forged_leaf_tree(
+        &tree_cookie,
+        &attacker_cookie.address,
+        leaf_cookie.index,
+        get_compressed_nft_leaf_node(&asset_id, &leaf_verification_cookie)
+    ).await?;
+
+    leaf_verification_cookie.root = root;
+    leaf_verification_cookie.proof_nodes = Some(proof_nodes);
+    leaf_verification_cookie.proof_len = 0;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &attacker_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&vec![]],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidMerkleTreeAuthority);
+
+    Ok(())
+}
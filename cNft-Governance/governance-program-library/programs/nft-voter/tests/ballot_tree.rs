@@ -0,0 +1,94 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use mpl_bubblegum::accounts::TreeConfig;
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_create_ballot_tree() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    // Assert
+    let ballot_tree = nft_voter_test.get_ballot_tree(&ballot_tree_cookie.address).await;
+    assert_eq!(ballot_tree, ballot_tree_cookie.account);
+
+    let tree_authority = nft_voter_test.bench.get_account(&ballot_tree_cookie.tree_authority).await.unwrap();
+    let tree_config = TreeConfig::from_bytes(&tree_authority.data).unwrap();
+
+    assert_eq!(tree_config.tree_creator, ballot_tree_cookie.address);
+    assert_eq!(tree_config.tree_delegate, ballot_tree_cookie.address);
+    assert!(!tree_config.is_public);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_ballot() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    let member_cookie1 = nft_voter_test.bench.with_wallet().await;
+    let member_cookie2 = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    nft_voter_test.mint_ballot(&registrar_cookie, &mut ballot_tree_cookie, &member_cookie1).await?;
+    let ballot = nft_voter_test.mint_ballot(
+        &registrar_cookie,
+        &mut ballot_tree_cookie,
+        &member_cookie2
+    ).await?;
+
+    // Assert
+    let ballot_tree = nft_voter_test.get_ballot_tree(&ballot_tree_cookie.address).await;
+    assert_eq!(ballot_tree.ballots_minted, 2);
+
+    let root = nft_voter_test.merkle_tree.decode_root(&ballot_tree_cookie.merkle_tree, 5, 8).await?;
+    assert_eq!(root, ballot_tree_cookie.proof_tree.get_root());
+
+    assert_eq!(ballot.owner, member_cookie2.address);
+    assert_eq!(ballot.metadata.creators[0].address, ballot_tree_cookie.address);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_ballot_with_invalid_realm_authority_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let realm_authority = Keypair::new();
+
+    // Act
+    let err = nft_voter_test
+        .mint_ballot_using_ix(
+            &registrar_cookie,
+            &mut ballot_tree_cookie,
+            &member_cookie,
+            |i| {
+                i.accounts[2].pubkey = realm_authority.pubkey();
+            }, // realm_authority
+            Some(&[&realm_authority])
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRealmAuthority);
+
+    Ok(())
+}
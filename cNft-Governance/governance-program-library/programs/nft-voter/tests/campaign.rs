@@ -0,0 +1,288 @@
+use gpl_nft_voter::tools::merkle_tree::get_voter_allowlist_leaf;
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program::instruction::InstructionError;
+use solana_program::keccak::hashv;
+use solana_program_test::*;
+use solana_sdk::{ pubkey::Pubkey, transport::TransportError };
+use spl_account_compression::AccountCompressionError;
+use crate::program_test::tools::{ assert_compression_err, assert_ix_err, assert_nft_voter_err };
+mod program_test;
+
+/// Returns the allowlist root of the claimant and another wallet and the proof of the claimant
+fn get_allowlist(claimant: &Pubkey) -> ([u8; 32], Vec<[u8; 32]>) {
+    let claimant_leaf = get_voter_allowlist_leaf(claimant);
+    let other_leaf = get_voter_allowlist_leaf(&Pubkey::new_unique());
+
+    let allowlist_root = if claimant_leaf <= other_leaf {
+        hashv(&[&claimant_leaf, &other_leaf]).to_bytes()
+    } else {
+        hashv(&[&other_leaf, &claimant_leaf]).to_bytes()
+    };
+
+    (allowlist_root, vec![other_leaf])
+}
+
+#[tokio::test]
+async fn test_create_campaign() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // Act
+    let campaign_cookie = nft_voter_test.with_campaign(
+        &registrar_cookie,
+        &ballot_tree_cookie,
+        &proposal_cookie,
+        [1; 32],
+        10
+    ).await?;
+
+    // Assert
+    let campaign = nft_voter_test.get_campaign(&campaign_cookie.address).await;
+    assert_eq!(campaign, campaign_cookie.account);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_ballot() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let claimant_cookie = nft_voter_test.bench.with_wallet().await;
+    let (allowlist_root, allowlist_proof) = get_allowlist(&claimant_cookie.address);
+
+    let campaign_cookie = nft_voter_test.with_campaign(
+        &registrar_cookie,
+        &ballot_tree_cookie,
+        &proposal_cookie,
+        allowlist_root,
+        10
+    ).await?;
+
+    // Act
+    let ballot = nft_voter_test.claim_ballot(
+        &campaign_cookie,
+        &mut ballot_tree_cookie,
+        &claimant_cookie,
+        allowlist_proof
+    ).await?;
+
+    // Assert
+    let campaign = nft_voter_test.get_campaign(&campaign_cookie.address).await;
+    assert_eq!(campaign.ballots_claimed, 1);
+
+    let root = nft_voter_test.merkle_tree.decode_root(&ballot_tree_cookie.merkle_tree, 5, 8).await?;
+    assert_eq!(root, ballot_tree_cookie.proof_tree.get_root());
+
+    assert_eq!(ballot.owner, claimant_cookie.address);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_ballot_with_claimant_not_in_allowlist_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let (allowlist_root, allowlist_proof) = get_allowlist(&member_cookie.address);
+
+    let campaign_cookie = nft_voter_test.with_campaign(
+        &registrar_cookie,
+        &ballot_tree_cookie,
+        &proposal_cookie,
+        allowlist_root,
+        10
+    ).await?;
+
+    let claimant_cookie = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    let err = nft_voter_test
+        .claim_ballot(&campaign_cookie, &mut ballot_tree_cookie, &claimant_cookie, allowlist_proof).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CampaignClaimNotAllowed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_ballot_twice_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let claimant_cookie = nft_voter_test.bench.with_wallet().await;
+    let (allowlist_root, allowlist_proof) = get_allowlist(&claimant_cookie.address);
+
+    let campaign_cookie = nft_voter_test.with_campaign(
+        &registrar_cookie,
+        &ballot_tree_cookie,
+        &proposal_cookie,
+        allowlist_root,
+        10
+    ).await?;
+
+    nft_voter_test.claim_ballot(
+        &campaign_cookie,
+        &mut ballot_tree_cookie,
+        &claimant_cookie,
+        allowlist_proof.clone()
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .claim_ballot(&campaign_cookie, &mut ballot_tree_cookie, &claimant_cookie, allowlist_proof).await
+        .err()
+        .unwrap();
+
+    // Assert
+    // CampaignClaim of the claimant already exists
+    assert_ix_err(err, InstructionError::Custom(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_ballot_vote() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let (allowlist_root, allowlist_proof) = get_allowlist(&voter_cookie.address);
+
+    let campaign_cookie = nft_voter_test.with_campaign(
+        &registrar_cookie,
+        &ballot_tree_cookie,
+        &proposal_cookie,
+        allowlist_root,
+        10
+    ).await?;
+
+    let ballot = nft_voter_test.claim_ballot(
+        &campaign_cookie,
+        &mut ballot_tree_cookie,
+        &voter_cookie,
+        allowlist_proof
+    ).await?;
+
+    // Act
+    nft_voter_test.cast_ballot_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &campaign_cookie,
+        &mut ballot_tree_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &ballot
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 10);
+    assert_eq!(voter_weight_record.weight_action, Some(VoterWeightAction::CastVote));
+    assert_eq!(voter_weight_record.weight_action_target, Some(proposal_cookie.address));
+
+    let campaign = nft_voter_test.get_campaign(&campaign_cookie.address).await;
+    assert_eq!(campaign.ballots_cast, 1);
+
+    let root = nft_voter_test.merkle_tree.decode_root(&ballot_tree_cookie.merkle_tree, 5, 8).await?;
+    assert_eq!(root, ballot_tree_cookie.proof_tree.get_root());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_ballot_vote_with_burnt_ballot_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let (allowlist_root, allowlist_proof) = get_allowlist(&voter_cookie.address);
+
+    let campaign_cookie = nft_voter_test.with_campaign(
+        &registrar_cookie,
+        &ballot_tree_cookie,
+        &proposal_cookie,
+        allowlist_root,
+        10
+    ).await?;
+
+    let ballot = nft_voter_test.claim_ballot(
+        &campaign_cookie,
+        &mut ballot_tree_cookie,
+        &voter_cookie,
+        allowlist_proof
+    ).await?;
+
+    nft_voter_test.cast_ballot_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &campaign_cookie,
+        &mut ballot_tree_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &ballot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .cast_ballot_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &campaign_cookie,
+            &mut ballot_tree_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &ballot
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_compression_err(err, AccountCompressionError::ConcurrentMerkleTreeError);
+
+    Ok(())
+}
@@ -0,0 +1,364 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use program_test::tools::assert_nft_voter_err;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_cast_nft_vote_multi() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let proposal_cookie1 = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let proposal_cookie2 = nft_voter_test.governance.with_governance_proposal(
+        &realm_cookie,
+        &proposal_cookie1
+    ).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1, &nft_cookie2],
+        &action
+    ).await?;
+
+    // Act
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote_multi(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &[&proposal_cookie1, &proposal_cookie2],
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    for proposal_cookie in [&proposal_cookie1, &proposal_cookie2] {
+        nft_voter_test.select_multi_proposal_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie
+        ).await?;
+    }
+
+    // Assert
+    assert_eq!(nft_vote_record_cookies.len(), 4);
+
+    for nft_vote_record_cookie in nft_vote_record_cookies.iter() {
+        let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+            &nft_vote_record_cookie.address
+        ).await;
+
+        assert_eq!(nft_vote_record_cookie.account, nft_vote_record);
+    }
+
+    for proposal_cookie in [&proposal_cookie1, &proposal_cookie2] {
+        let proposal = nft_voter_test.governance.get_proposal(&proposal_cookie.address).await;
+
+        assert_eq!(proposal.options[0].vote_weight, 6);
+    }
+
+    // The MultiProposalVote is closed once the weight was selected for all the Proposals
+    let multi_proposal_vote_account = nft_voter_test.bench.get_account(
+        &get_multi_proposal_vote_address(
+            &registrar_cookie.address,
+            &voter_weight_record_cookie.account.governing_token_owner
+        )
+    ).await;
+
+    assert!(multi_proposal_vote_account.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_select_multi_proposal_vote_twice_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let proposal_cookie1 = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let proposal_cookie2 = nft_voter_test.governance.with_governance_proposal(
+        &realm_cookie,
+        &proposal_cookie1
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.cast_nft_vote_multi(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &[&proposal_cookie1, &proposal_cookie2],
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    nft_voter_test.select_multi_proposal_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie1,
+        &voter_cookie,
+        &voter_token_owner_record_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .select_multi_proposal_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie1,
+            &voter_cookie,
+            &voter_token_owner_record_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidMultiProposalVote);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_multi_with_track_collection_stats_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        track_collection_stats: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote_multi(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &[&proposal_cookie],
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::MultiProposalVoteNotSupported);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_multi_records_voter_checkpoints() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        record_voter_checkpoints: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let proposal_cookie1 = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let proposal_cookie2 = nft_voter_test.governance.with_governance_proposal(
+        &realm_cookie,
+        &proposal_cookie1
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.cast_nft_vote_multi(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &[&proposal_cookie1, &proposal_cookie2],
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    for proposal_cookie in [&proposal_cookie1, &proposal_cookie2] {
+        let voter_checkpoint = nft_voter_test.get_voter_checkpoint(
+            &get_voter_checkpoint_address(
+                &registrar_cookie.address,
+                &proposal_cookie.address,
+                &voter_cookie.address
+            )
+        ).await;
+
+        assert_eq!(voter_checkpoint.proposal, proposal_cookie.address);
+        assert_eq!(voter_checkpoint.voter_weight, 3);
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
+use borsh::BorshDeserialize;
 use gpl_nft_voter::{ error::NftVoterError, state::* };
+use gpl_nft_voter::events::{ NftVoteMemo, MAX_VOTE_MEMO_LENGTH };
 use program_test::nft_voter_test::*;
 use program_test::tools::assert_gov_err;
 use solana_program_test::*;
@@ -186,6 +188,112 @@ async fn test_cast_nft_vote_with_cnft() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cast_nft_vote_with_cnft_in_realms_sharing_tree_and_collection() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let action = VoterWeightAction::CastVote;
+    let mut nft_vote_records = vec![];
+
+    // Act
+    for _ in 0..2 {
+        let realm_cookie = nft_voter_test.governance.with_realm().await?;
+        let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+        let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+            &registrar_cookie
+        ).await?;
+
+        nft_voter_test.with_collection(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            &max_voter_weight_record_cookie,
+            Some(ConfigureCollectionArgs {
+                weight: 3,
+                size: 11,
+            })
+        ).await?;
+
+        let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+            &realm_cookie,
+            &voter_cookie
+        ).await?;
+        let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+            &registrar_cookie,
+            &voter_cookie
+        ).await?;
+        let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+        nft_voter_test.bench.advance_clock().await;
+
+        let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await?;
+
+        let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await?;
+
+        nft_vote_records.push((
+            registrar_cookie.address,
+            proposal_cookie.address,
+            nft_vote_record_cookies[0].address,
+        ));
+    }
+
+    // Assert
+    assert_ne!(nft_vote_records[0].2, nft_vote_records[1].2);
+
+    for (registrar, proposal, nft_vote_record_address) in nft_vote_records {
+        assert_eq!(
+            nft_vote_record_address,
+            get_nft_vote_record_address(&registrar, &proposal, &leaf_cookie.asset_id)
+        );
+
+        let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+            &nft_vote_record_address
+        ).await;
+
+        assert_eq!(nft_vote_record.proposal, proposal);
+        assert_eq!(nft_vote_record.nft_mint, leaf_cookie.asset_id);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cast_nft_vote_with_nft_and_cnft() -> Result<(), TransportError> {
     let mut nft_voter_test = NftVoterTest::start_new().await;
@@ -671,8 +779,8 @@ async fn test_cast_nft_vote_with_multiple_trees_and_different_size() -> Result<(
         nft_voter_test.merkle_tree.get_leaf_verification_info(
             &mut tree_cookie2,
             &leaf_cookie2,
-            5,
-            8
+            14,
+            64
         ).await?;
 
     let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
@@ -717,6 +825,170 @@ async fn test_cast_nft_vote_with_multiple_trees_and_different_size() -> Result<(
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cast_nft_vote_with_collection_spanning_trees_of_different_depths() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // The collection is minted into three trees of different depths
+    let mut tree_cookie1 = nft_voter_test.merkle_tree.with_merkle_tree(
+        Some(MerkleTreeArgs {
+            max_depth: 3,
+            max_buffer_size: 8,
+            public: Some(false),
+        })
+    ).await?;
+    let mut tree_cookie2 = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let mut tree_cookie3 = nft_voter_test.merkle_tree.with_merkle_tree(
+        Some(MerkleTreeArgs {
+            max_depth: 14,
+            max_buffer_size: 64,
+            public: Some(false),
+        })
+    ).await?;
+
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie1,
+        &voter_cookie
+    ).await?;
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie2,
+        &voter_cookie
+    ).await?;
+    let leaf_cookie3 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie3,
+        &voter_cookie
+    ).await?;
+    let leaf_cookie4 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie1,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let (leaf_verification_cookie1, proofs1, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie1,
+            &leaf_cookie1,
+            3,
+            8
+        ).await?;
+    let (leaf_verification_cookie2, proofs2, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie2,
+            &leaf_cookie2,
+            5,
+            8
+        ).await?;
+    let (mut leaf_verification_cookie3, proofs3, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie3,
+            &leaf_cookie3,
+            14,
+            64
+        ).await?;
+    let (leaf_verification_cookie4, proofs4, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie1,
+            &leaf_cookie4,
+            3,
+            8
+        ).await?;
+
+    // The proof of the deep tree is passed as instruction data and the other proofs as accounts
+    leaf_verification_cookie3.proof_nodes = Some(
+        proofs3
+            .iter()
+            .map(|proof| proof.pubkey.to_bytes())
+            .collect()
+    );
+    leaf_verification_cookie3.proof_len = 0;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie1, &leaf_cookie2, &leaf_cookie3, &leaf_cookie4],
+        &[
+            &leaf_verification_cookie1,
+            &leaf_verification_cookie2,
+            &leaf_verification_cookie3,
+            &leaf_verification_cookie4,
+        ],
+        &[&proofs1, &proofs2, &vec![], &proofs4],
+        &action
+    ).await?;
+
+    let clock = nft_voter_test.bench.get_clock().await;
+
+    // Act
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Assert
+    assert_eq!(nft_vote_record_cookies.len(), 4);
+
+    for nft_vote_record_cookie in nft_vote_record_cookies.iter() {
+        let cnft_vote_record = nft_voter_test.get_nft_vote_record_account(
+            &nft_vote_record_cookie.address
+        ).await;
+        assert_eq!(nft_vote_record_cookie.account, cnft_vote_record);
+    }
+
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 12);
+    assert_eq!(voter_weight_record.voter_weight_expiry, Some(clock.slot));
+    assert_eq!(voter_weight_record.weight_action_target, Some(proposal_cookie.address));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cast_nft_vote_with_nft_already_voted_error() -> Result<(), TransportError> {
     let mut nft_voter_test = NftVoterTest::start_new().await;
@@ -1155,6 +1427,7 @@ async fn test_cast_nft_vote_using_multiple_instructions_with_nft_attempted_sandw
 
     let args = CastNftVoteArgs {
         cast_spl_gov_vote: false,
+        ..Default::default()
     };
 
     let nft_vote_record_cookiess = nft_voter_test.cast_nft_vote(
@@ -1272,6 +1545,7 @@ async fn test_cast_nft_vote_using_multiple_instructions_with_cnft_attempted_sand
 
     let args = CastNftVoteArgs {
         cast_spl_gov_vote: false,
+        ..Default::default()
     };
 
     let nft_vote_record_cookiess = nft_voter_test.cast_nft_vote(
@@ -1746,3 +2020,1722 @@ async fn test_cast_nft_vote_with_max_5_nfts() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_max_collection_weight_per_proposal() -> Result<
+    (),
+    TransportError
+> {
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    // 15% of max_voter_weight 33 caps the collection at 4
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_collection_weight_per_proposal: 1500,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie, &nft_cookie2],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        capped_collection: Some(nft_collection_cookie.mint),
+        ..Default::default()
+    };
+
+    // Act
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 4);
+
+    let collection_vote_weight_record = nft_voter_test.get_collection_vote_weight_record(
+        &get_collection_vote_weight_record_address(
+            &registrar_cookie.address,
+            &proposal_cookie.address,
+            &nft_collection_cookie.mint
+        )
+    ).await;
+
+    assert_eq!(collection_vote_weight_record.voter_weight, 4);
+    assert_eq!(collection_vote_weight_record.collection, nft_collection_cookie.mint);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_simulate_only() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1, &nft_cookie2],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        cast_spl_gov_vote: false,
+        simulate_only: true,
+        ..Default::default()
+    };
+
+    // Act
+    let (nft_vote_record_cookies, return_data) = nft_voter_test.cast_nft_vote_with_return_data(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let return_data = VoterWeightReturnData::try_from_slice(&return_data.unwrap()).unwrap();
+    assert_eq!(return_data, VoterWeightReturnData { voter_weight: 6, asset_count: 2 });
+
+    // Nothing is written and the tickets can still be used to vote
+    let nft_vote_record = nft_voter_test.bench.get_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+    assert_eq!(None, nft_vote_record);
+
+    let nft_action_ticket = nft_voter_test.bench.get_account(
+        &nft_action_ticket_cookies[0].address
+    ).await;
+    assert!(nft_action_ticket.is_some());
+
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+    assert_eq!(voter_weight_record.voter_weight, 0);
+    assert_eq!(voter_weight_record.weight_action_target, None);
+
+    let registrar_metrics = nft_voter_test.bench.get_account(
+        &get_registrar_metrics_address(&registrar_cookie.address, &voter_cookie.address)
+    ).await;
+    assert_eq!(None, registrar_metrics);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_collection_stats() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        track_collection_stats: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let clock = nft_voter_test.bench.get_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie, &nft_cookie2],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        stats_collection: Some(nft_collection_cookie.mint),
+        ..Default::default()
+    };
+
+    // Act
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let collection_stats = nft_voter_test.get_collection_stats(
+        &get_collection_stats_address(&registrar_cookie.address, &nft_collection_cookie.mint)
+    ).await;
+
+    assert_eq!(collection_stats.registrar, registrar_cookie.address);
+    assert_eq!(collection_stats.collection, nft_collection_cookie.mint);
+    assert_eq!(collection_stats.total_votes, 2);
+    assert_eq!(collection_stats.last_vote_slot, clock.slot);
+    assert_eq!(collection_stats.estimate_unique_voters(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_after_voting_time_ended_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // The test Governance voting_base_time is 600 seconds
+    nft_voter_test.bench.advance_clock_by_seconds(601).await;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ProposalActionDeadlinePassed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_max_assets_per_tx_exceeded_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_assets_per_tx: 2,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let mut nft_cookies = vec![];
+
+    for _ in 0..3 {
+        nft_voter_test.bench.advance_clock().await;
+        let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+            &nft_collection_cookie,
+            &voter_cookie,
+            None
+        ).await?;
+
+        nft_cookies.push(nft_cookie);
+    }
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &nft_cookies.iter().collect::<Vec<_>>(),
+        &action
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::TooManyAssetsPerTransaction);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_expired_collection() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie1,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie2,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 5,
+            size: 11,
+        })
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 100;
+
+    nft_voter_test.with_collection_expiry(
+        &registrar_cookie,
+        &nft_collection_cookie1,
+        expires_at
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie1,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie2,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1, &nft_cookie2],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    // The tickets were created before the collection expired
+    nft_voter_test.bench.advance_clock_by_seconds(100).await;
+
+    // Act
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_cnft_and_memo() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    let memo = "Voting yes because the treasury needs diversification 🌲".to_string();
+
+    let args = CastNftVoteArgs {
+        memo: Some(memo.clone()),
+        ..Default::default()
+    };
+
+    // Act
+    let logs = nft_voter_test.cast_nft_vote_with_logs(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let nft_vote_memos = logs.get_events::<NftVoteMemo>();
+
+    assert_eq!(nft_vote_memos.len(), 1);
+    assert_eq!(nft_vote_memos[0].registrar, registrar_cookie.address);
+    assert_eq!(nft_vote_memos[0].proposal, proposal_cookie.address);
+    assert_eq!(nft_vote_memos[0].governing_token_owner, voter_cookie.address);
+    assert_eq!(nft_vote_memos[0].memo, memo);
+
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_memo_too_long_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        memo: Some("a".repeat(MAX_VOTE_MEMO_LENGTH + 1)),
+        ..Default::default()
+    };
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            Some(args)
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoteMemoTooLong);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_cnft_of_expired_cold_wallet_link_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 60;
+
+    nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &voter_cookie,
+        expires_at
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &cold_wallet_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // The link expires while the ticket is still valid
+    nft_voter_test.bench.advance_clock_by_seconds(60).await;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ColdWalletLinkExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_voter_rent_escrow() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let voter_rent_escrow = nft_voter_test.with_voter_rent_escrow(
+        &registrar_cookie,
+        &voter_cookie,
+        1_000_000_000
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    let voter_rent_escrow_lamports = nft_voter_test.bench
+        .get_account(&voter_rent_escrow).await
+        .unwrap().lamports;
+
+    let args = CastNftVoteArgs {
+        voter_rent_escrow: Some(voter_rent_escrow),
+        ..Default::default()
+    };
+
+    // Act
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(nft_vote_record_cookies[0].account, nft_vote_record);
+
+    // The rent of the NftVoteRecord is drawn from the escrow
+    let nft_vote_record_lamports = nft_voter_test.bench
+        .get_account(&nft_vote_record_cookies[0].address).await
+        .unwrap().lamports;
+    let voter_rent_escrow_account = nft_voter_test.bench
+        .get_account(&voter_rent_escrow).await
+        .unwrap();
+
+    assert_eq!(
+        voter_rent_escrow_account.lamports,
+        voter_rent_escrow_lamports - nft_vote_record_lamports
+    );
+
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_voter_rent_escrow_insufficient_funds_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    // The escrow holds only its own rent
+    let voter_rent_escrow = nft_voter_test.with_voter_rent_escrow(
+        &registrar_cookie,
+        &voter_cookie,
+        0
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        voter_rent_escrow: Some(voter_rent_escrow),
+        ..Default::default()
+    };
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            Some(args)
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterRentEscrowInsufficientFunds);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_voting_session() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 100;
+
+    let voting_session = nft_voter_test.with_voting_session(
+        &registrar_cookie,
+        &voter_cookie,
+        &session_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    // spl-gov CastVote must still be signed by the voter or its delegate
+    let args = CastNftVoteArgs {
+        cast_spl_gov_vote: false,
+        voting_session: Some(voting_session),
+        ..Default::default()
+    };
+
+    // Act
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &session_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(nft_vote_record_cookies[0].account, nft_vote_record);
+
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 3);
+    assert_eq!(voter_weight_record.weight_action_target, Some(proposal_cookie.address));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_expired_voting_session_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1;
+
+    let voting_session = nft_voter_test.with_voting_session(
+        &registrar_cookie,
+        &voter_cookie,
+        &session_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let args = CastNftVoteArgs {
+        cast_spl_gov_vote: false,
+        voting_session: Some(voting_session),
+        ..Default::default()
+    };
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &session_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            Some(args)
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VotingSessionExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_voting_session_of_other_session_authority_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+    let other_session_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    let voting_session = nft_voter_test.with_voting_session(
+        &registrar_cookie,
+        &voter_cookie,
+        &session_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        cast_spl_gov_vote: false,
+        voting_session: Some(voting_session),
+        ..Default::default()
+    };
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &other_session_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            Some(args)
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    // The session only authorizes the session key the voter created it for
+    assert_nft_voter_err(err, NftVoterError::InvalidVotingSession);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_records_registrar_metrics() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let clock = nft_voter_test.bench.get_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1, &nft_cookie2],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Assert
+    let registrar_metrics = nft_voter_test.bench.get_anchor_account::<RegistrarMetrics>(
+        get_registrar_metrics_address(&registrar_cookie.address, &voter_cookie.address)
+    ).await;
+
+    assert_eq!(registrar_metrics, RegistrarMetrics {
+        registrar: registrar_cookie.address,
+        shard: get_registrar_metrics_shard(&voter_cookie.address),
+        total_votes_cast: 2,
+        total_weight_granted: 6,
+        last_activity_slot: clock.slot,
+        reserved: [0; 16],
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_without_registrar_metrics_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote_using_ix(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None,
+            |i| {
+                // The program id stands for an omitted optional account
+                i.accounts[10].pubkey = gpl_nft_voter::id();
+                i.accounts[10].is_writable = false;
+            } // registrar_metrics
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::RegistrarMetricsRequired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_too_recent_nft_action_ticket_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        weight_record_min_age_slots: 3,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::NftTicketTooRecent);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_after_weight_record_min_age() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        weight_record_min_age_slots: 3,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_records_voter_checkpoint() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        record_voter_checkpoints: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let action = VoterWeightAction::CastVote;
+    let nft_action_ticket_cookies1 = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        cast_spl_gov_vote: false,
+        ..Default::default()
+    };
+
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies1.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let clock = nft_voter_test.bench.get_clock().await;
+
+    let nft_action_ticket_cookies2 = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie2],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies2.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Assert
+    let voter_checkpoint = nft_voter_test.get_voter_checkpoint(
+        &get_voter_checkpoint_address(
+            &registrar_cookie.address,
+            &proposal_cookie.address,
+            &voter_cookie.address
+        )
+    ).await;
+
+    // The checkpoint of the accumulated vote holds the total weight
+    assert_eq!(voter_checkpoint.registrar, registrar_cookie.address);
+    assert_eq!(voter_checkpoint.proposal, proposal_cookie.address);
+    assert_eq!(voter_checkpoint.governing_token_owner, voter_cookie.address);
+    assert_eq!(voter_checkpoint.voter_weight, 6);
+    assert_eq!(voter_checkpoint.slot, clock.slot);
+
+    Ok(())
+}
@@ -5,7 +5,12 @@ use program_test::{
 };
 
 use solana_program_test::*;
-use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::Keypair,
+    signer::Signer,
+    transport::TransportError,
+};
 
 use crate::program_test::nft_voter_test::ConfigureCollectionArgs;
 
@@ -394,3 +399,195 @@ async fn test_configure_collection_with_invalid_max_voter_weight_mint_error() ->
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_configure_collections_batch() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Act
+    let collection_config_cookies = nft_voter_test.with_collections_batch(
+        &registrar_cookie,
+        &[&nft_collection_cookie1, &nft_collection_cookie2],
+        &max_voter_weight_record_cookie,
+        &[
+            ConfigureCollectionArgs { weight: 1, size: 5 },
+            ConfigureCollectionArgs { weight: 2, size: 10 },
+        ]
+    ).await?;
+
+    // Assert
+    let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    assert_eq!(registrar.collection_configs.len(), 2);
+    assert_eq!(registrar.collection_configs[0], collection_config_cookies[0].collection_config);
+    assert_eq!(registrar.collection_configs[1], collection_config_cookies[1].collection_config);
+
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight_expiry, None);
+    assert_eq!(max_voter_weight_record.max_voter_weight, 25);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collections_batch_with_invalid_size_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collections_batch(
+            &registrar_cookie,
+            &[&nft_collection_cookie1, &nft_collection_cookie2],
+            &max_voter_weight_record_cookie,
+            &[
+                ConfigureCollectionArgs { weight: 1, size: 5 },
+                ConfigureCollectionArgs { weight: 2, size: 0 },
+            ]
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCollectionSize);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_with_collection_details_size() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(25)).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Act
+    nft_voter_test.with_collection_using_ix(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs { weight: 2, size: 0 }),
+        |i| i.accounts.push(AccountMeta::new_readonly(nft_collection_cookie.metadata, false)),
+        None
+    ).await?;
+
+    // Assert
+    let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    assert_eq!(registrar.collection_configs[0].size, 25);
+
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight, 50);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_with_missing_collection_details_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // The collection is not sized
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_using_ix(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            &max_voter_weight_record_cookie,
+            Some(ConfigureCollectionArgs { weight: 2, size: 0 }),
+            |i| i.accounts.push(AccountMeta::new_readonly(nft_collection_cookie.metadata, false)),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::MissingCollectionDetails);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_with_max_voter_weight_overflow_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            &max_voter_weight_record_cookie,
+            Some(ConfigureCollectionArgs { weight: u64::MAX, size: 2 })
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ArithmeticOverflow);
+
+    Ok(())
+}
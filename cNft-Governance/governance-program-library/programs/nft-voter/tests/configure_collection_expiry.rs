@@ -0,0 +1,212 @@
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program_test::*;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+
+mod program_test;
+
+#[tokio::test]
+async fn test_configure_collection_expiry() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 2,
+            size: 10,
+        })
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 1_000;
+
+    // Act
+    nft_voter_test.with_collection_expiry(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        expires_at
+    ).await?;
+
+    // Assert
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    assert_eq!(
+        registrar_extension.get_collection_extension(&nft_collection_cookie.mint).expires_at,
+        expires_at
+    );
+
+    // The collection keeps its weight until it expires
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight, 20);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_expiry_is_preserved_by_configure_collection() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 1_000;
+
+    nft_voter_test.with_collection_expiry(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        expires_at
+    ).await?;
+
+    // Act
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 5,
+            size: 10,
+        })
+    ).await?;
+
+    // Assert
+    let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    assert_eq!(registrar.collection_configs[0].weight, 5);
+
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    assert_eq!(
+        registrar_extension.get_collection_extension(&nft_collection_cookie.mint).expires_at,
+        expires_at
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_expiry_with_invalid_realm_authority_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let realm_authority = Keypair::new();
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_expiry_using_ix(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            1_000,
+            |i| {
+                i.accounts[2].pubkey = realm_authority.pubkey();
+            }, // realm_authority
+            Some(&[&realm_authority])
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRealmAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_expiry_with_negative_expiry_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_expiry(&registrar_cookie, &nft_collection_cookie, -1).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCollectionExpiry);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_expiry_with_not_configured_collection_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_expiry(&registrar_cookie, &nft_collection_cookie, 1_000).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CollectionNotFound);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use gpl_nft_voter::error::NftVoterError;
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_configure_collection_uses_limit() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // Act
+    nft_voter_test.with_collection_uses_limit(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        true
+    ).await?;
+
+    // Assert
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    assert!(registrar_extension.get_collection_extension(&nft_collection_cookie.mint).uses_limited);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_uses_limit_with_collection_not_found_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_uses_limit(&registrar_cookie, &nft_collection_cookie, true).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CollectionNotFound);
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_configure_collection_weight_strategy() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    // Act
+    nft_voter_test.with_collection_weight_strategy(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        CollectionWeightStrategy::Capped { max_assets: 2 }
+    ).await?;
+
+    // Assert
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    assert_eq!(
+        registrar_extension.get_collection_extension(&nft_collection_cookie.mint).weight_strategy,
+        CollectionWeightStrategy::Capped { max_assets: 2 }
+    );
+
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight, 33);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_weight_strategy_with_invalid_strategy_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_weight_strategy(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            &max_voter_weight_record_cookie,
+            CollectionWeightStrategy::Capped { max_assets: 0 }
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCollectionWeightStrategy);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_collection_weight_strategy_with_collection_not_found_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_collection_weight_strategy(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            &max_voter_weight_record_cookie,
+            CollectionWeightStrategy::Membership
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CollectionNotFound);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_membership_weight_strategy() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_collection_weight_strategy(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        CollectionWeightStrategy::Membership
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (leaf_verification_cookie1, proofs1, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie1,
+            5,
+            8
+        ).await?;
+
+    let (leaf_verification_cookie2, proofs2, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie2,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie1, &leaf_cookie2],
+        &[&leaf_verification_cookie1, &leaf_verification_cookie2],
+        &[&proofs1, &proofs2],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    // Only the first NFT of the collection is weighted
+    assert_eq!(voter_weight_record.voter_weight, 3);
+
+    Ok(())
+}
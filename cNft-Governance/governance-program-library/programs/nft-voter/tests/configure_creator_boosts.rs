@@ -0,0 +1,190 @@
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program_test::*;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+
+mod program_test;
+
+#[tokio::test]
+async fn test_configure_creator_boosts() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 2,
+            size: 10,
+        })
+    ).await?;
+
+    let creator_boost = CreatorBoost {
+        creator: Keypair::new().pubkey(),
+        boost_bps: 5_000,
+    };
+
+    // Act
+    nft_voter_test.with_creator_boosts(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        &[creator_boost]
+    ).await?;
+
+    // Assert
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    let collection_extension = registrar_extension.get_collection_extension(&nft_collection_cookie.mint);
+
+    assert_eq!(collection_extension.creator_boosts[0], creator_boost);
+    assert_eq!(collection_extension.creator_boosts[1], CreatorBoost::default());
+
+    // The max voter weight assumes all NFTs get the highest boost
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight, 30);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_creator_boosts_with_duplicated_creator_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let creator_boost = CreatorBoost {
+        creator: Keypair::new().pubkey(),
+        boost_bps: 5_000,
+    };
+
+    // Act
+    let err = nft_voter_test
+        .with_creator_boosts(
+            &registrar_cookie,
+            &nft_collection_cookie,
+            &max_voter_weight_record_cookie,
+            &[creator_boost, creator_boost]
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCreatorBoosts);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_creator_boost() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 2,
+            size: 11,
+        })
+    ).await?;
+
+    // The compressed NFTs are minted with the bench payer as the verified creator
+    nft_voter_test.with_creator_boosts(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        &[
+            CreatorBoost {
+                creator: nft_voter_test.bench.payer.pubkey(),
+                boost_bps: 5_000,
+            },
+        ]
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 3);
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_update_voter_weight_record_for_target_with_governance_weight() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let governance = proposal_cookie.account.governance;
+
+    // 2.5x weight on the Governance
+    let governance_weight_config = nft_voter_test.with_governance_weight_config(
+        &registrar_cookie,
+        &governance,
+        25_000
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record_for_target(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        &governance_weight_config,
+        &governance,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 7);
+    assert_eq!(voter_weight_record.weight_action, Some(VoterWeightAction::CreateProposal));
+    assert_eq!(voter_weight_record.weight_action_target, Some(governance));
+    assert_eq!(voter_weight_record.record_nonce, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_governance_weight_with_invalid_weight_multiplier_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_governance_weight_config(
+            &registrar_cookie,
+            &proposal_cookie.account.governance,
+            MAX_WEIGHT_MULTIPLIER_BPS + 1
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidWeightMultiplier);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_for_target_with_sign_off_deadline_passed_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // The Proposal is already signed off and in Voting state
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let governance = proposal_cookie.account.governance;
+
+    let governance_weight_config = nft_voter_test.with_governance_weight_config(
+        &registrar_cookie,
+        &governance,
+        10_000
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::SignOffProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .update_voter_weight_record_for_target(
+            &registrar_cookie,
+            &mut voter_weight_record_cookie,
+            &governance_weight_config,
+            &proposal_cookie.address,
+            action,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ProposalActionDeadlinePassed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_for_target_with_stale_record_nonce_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let governance = proposal_cookie.account.governance;
+
+    let governance_weight_config = nft_voter_test.with_governance_weight_config(
+        &registrar_cookie,
+        &governance,
+        25_000
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // The client expects the nonce of an update it hasn't seen yet
+    voter_weight_record_cookie.account.record_nonce = 1;
+
+    // Act
+    let err = nft_voter_test
+        .update_voter_weight_record_for_target(
+            &registrar_cookie,
+            &mut voter_weight_record_cookie,
+            &governance_weight_config,
+            &governance,
+            action,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidVoterWeightRecordNonce);
+
+    Ok(())
+}
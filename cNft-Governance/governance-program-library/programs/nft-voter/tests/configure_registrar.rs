@@ -0,0 +1,339 @@
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program_test::*;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+
+mod program_test;
+
+#[tokio::test]
+async fn test_configure_registrar() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let config = RegistrarConfig {
+        collection_weight_mode: CollectionWeightMode::UniqueCollections,
+        max_collection_weight_per_proposal: 1000,
+        min_holding_slots: 100,
+        holding_attestation_authority: Keypair::new().pubkey(),
+        require_proposal_creation_ticket: true,
+        track_voter_weight_detail: true,
+        tree_authority_allowlist: [
+            Keypair::new().pubkey(),
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+        ],
+        allow_precomputed_metadata_hashes: true,
+        voter_allowlist_root: [1; 32],
+        rent_reclaim_treasury: Keypair::new().pubkey(),
+        rent_reclaim_treasury_share_bps: 8_000,
+        track_collection_stats: true,
+        parent_registrar: Keypair::new().pubkey(),
+        require_same_transaction_consumption: true,
+        normalized_collection_weight: 1_000,
+        allow_core_assets: cfg!(feature = "metaplex-core"),
+        max_assets_per_tx: 5,
+        require_vote_finalization: true,
+        max_tree_assets_per_slot: 4,
+        proof_attestation_freshness_slots: 150,
+        max_tree_root_age: 16,
+        allow_voter_migration: true,
+        leaf_hash_verifier: Pubkey::new_unique(),
+        weight_record_min_age_slots: 2,
+        record_voter_checkpoints: true,
+        require_weight_action_target: true,
+        weight_export_program: Pubkey::new_unique(),
+        allow_collection_aliases: true,
+        min_weight_if_registered_before_slot: 1,
+        registration_cutoff_slot: 1_000,
+        bubblegum_program_override: Pubkey::new_unique(),
+        allow_wallet_weight_overrides: true,
+        allow_weight_leases: true,
+        vote_receipt_tree: Pubkey::new_unique(),
+    };
+
+    // Act
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, config).await?;
+
+    // Assert
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    assert_eq!(registrar_extension.config, config);
+    assert_eq!(registrar_extension, registrar_cookie.extension);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_with_invalid_realm_authority_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let realm_authority = Keypair::new();
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_config_using_ix(
+            &mut registrar_cookie,
+            RegistrarConfig::default(),
+            |i| {
+                i.accounts[2].pubkey = realm_authority.pubkey();
+            }, // realm_authority
+            Some(&[&realm_authority])
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRealmAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_unique_collections_mode() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        collection_weight_mode: CollectionWeightMode::UniqueCollections,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1, &nft_cookie2],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    // Both NFTs are from the same collection and hence the collection weight is counted only once
+    assert_eq!(voter_weight_record.voter_weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_with_invalid_max_collection_weight_per_proposal_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+            max_collection_weight_per_proposal: MAX_BASIS_POINTS + 1,
+            ..Default::default()
+        }).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidMaxCollectionWeightPerProposal);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_with_precomputed_metadata_hashes_without_tree_authorities_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+            allow_precomputed_metadata_hashes: true,
+            ..Default::default()
+        }).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRegistrarConfig);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_normalized_mode() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        collection_weight_mode: CollectionWeightMode::Normalized,
+        normalized_collection_weight: 1_000,
+        ..Default::default()
+    }).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 2,
+            size: 10,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    // The collection of 10 NFTs with weight 2 contributes 2 * 1000 in total and 200 per NFT
+    assert_eq!(voter_weight_record.voter_weight, 200);
+
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight, 2_000);
+
+    Ok(())
+}
+
+#[cfg(not(feature = "metaplex-core"))]
+#[tokio::test]
+async fn test_configure_registrar_with_core_assets_not_supported_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+            allow_core_assets: true,
+            ..Default::default()
+        }).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CoreAssetsNotSupported);
+
+    Ok(())
+}
@@ -0,0 +1,447 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use program_test::tools::assert_nft_voter_err;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_configure_wallet_weight_override() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    let wallet_weight_override = nft_voter_test.configure_wallet_weight_override(
+        &registrar_cookie,
+        &voter_cookie.address,
+        0
+    ).await?;
+
+    // Assert
+    let wallet_weight_override_account = nft_voter_test.bench.get_anchor_account::<WalletWeightOverride>(
+        wallet_weight_override
+    ).await;
+
+    assert_eq!(wallet_weight_override_account, WalletWeightOverride {
+        registrar: registrar_cookie.address,
+        wallet: voter_cookie.address,
+        weight_bps: 0,
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_wallet_weight_override_with_invalid_weight_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    let err = nft_voter_test
+        .configure_wallet_weight_override(
+            &registrar_cookie,
+            &voter_cookie.address,
+            MAX_BASIS_POINTS + 1
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidWalletWeightOverrideWeight);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_wallet_weight_override() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        require_vote_finalization: true,
+        allow_wallet_weight_overrides: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // The treasury wallet holding a member NFT is neutralized by the realm
+    nft_voter_test.configure_wallet_weight_override(
+        &registrar_cookie,
+        &voter_cookie.address,
+        0
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_finalize_nft_vote_with_wallet_weight_override() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 10,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        require_vote_finalization: true,
+        allow_wallet_weight_overrides: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    nft_voter_test.configure_wallet_weight_override(
+        &registrar_cookie,
+        &voter_cookie.address,
+        5_000
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            cast_spl_gov_vote: false,
+            ..Default::default()
+        })
+    ).await?;
+
+    // Act
+    nft_voter_test.finalize_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        true
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 5);
+
+    let proposal = nft_voter_test.governance.get_proposal(&proposal_cookie.address).await;
+
+    assert_eq!(proposal.options[0].vote_weight, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_with_wallet_weight_overrides_without_vote_finalization_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+            allow_wallet_weight_overrides: true,
+            ..Default::default()
+        }).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRegistrarConfig);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_select_multi_proposal_vote_with_wallet_weight_override() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 10,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        require_vote_finalization: true,
+        allow_wallet_weight_overrides: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    nft_voter_test.configure_wallet_weight_override(
+        &registrar_cookie,
+        &voter_cookie.address,
+        5_000
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    nft_voter_test.cast_nft_vote_multi(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &[&proposal_cookie],
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Act
+    nft_voter_test.select_multi_proposal_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 5);
+
+    let proposal = nft_voter_test.governance.get_proposal(&proposal_cookie.address).await;
+
+    assert_eq!(proposal.options[0].vote_weight, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_for_target_with_wallet_weight_override() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        require_vote_finalization: true,
+        allow_wallet_weight_overrides: true,
+        ..Default::default()
+    }).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+    let governance = proposal_cookie.account.governance;
+
+    let governance_weight_config = nft_voter_test.with_governance_weight_config(
+        &registrar_cookie,
+        &governance,
+        25_000
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // The treasury wallet holding a member NFT is neutralized by the realm
+    nft_voter_test.configure_wallet_weight_override(
+        &registrar_cookie,
+        &voter_cookie.address,
+        0
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // Act
+    nft_voter_test.update_voter_weight_record_for_target(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        &governance_weight_config,
+        &governance,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 0);
+    assert_eq!(voter_weight_record.weight_action_target, Some(governance));
+
+    Ok(())
+}
@@ -1,8 +1,20 @@
 use gpl_nft_voter::{ state::*, error::NftVoterError };
 use program_test::nft_voter_test::*;
-use crate::program_test::tools::{ assert_nft_voter_err, assert_compression_err };
+use crate::program_test::tools::{
+    assert_nft_voter_err,
+    assert_compression_err,
+    new_ed25519_signature_ix,
+    NopOverride,
+};
+use mpl_bubblegum::types::{
+    UseMethod as MetaplexUseMethod,
+    Uses as MetaplexUses,
+};
+use ::cnft_verification::get_asset_id;
+use solana_program::pubkey::Pubkey;
 use solana_program_test::*;
-use solana_sdk::transport::TransportError;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
 use spl_account_compression::AccountCompressionError;
 mod program_test;
 
@@ -70,7 +82,8 @@ async fn test_create_cnft_action_ticket() -> Result<(), TransportError> {
 }
 
 #[tokio::test]
-async fn test_create_cnft_action_ticket_with_multiple_nfts() -> Result<(), TransportError> {
+async fn test_create_cnft_action_ticket_with_proof_nodes() -> Result<(), TransportError> {
+    // Arrange
     let action = VoterWeightAction::CastVote;
     let mut nft_voter_test = NftVoterTest::start_new().await;
     let realm_cookie = nft_voter_test.governance.with_realm().await?;
@@ -96,15 +109,85 @@ async fn test_create_cnft_action_ticket_with_multiple_nfts() -> Result<(), Trans
         &voter_cookie
     ).await?;
 
-    // mint compressed nft
     let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
-    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
         &nft_collection_cookie,
         &mut tree_cookie,
         &voter_cookie
     ).await?;
 
-    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Pass the proof as instruction data instead of accounts
+    leaf_verification_cookie.proof_nodes = Some(
+        proofs
+            .iter()
+            .map(|proof| proof.pubkey.to_bytes())
+            .collect()
+    );
+    leaf_verification_cookie.proof_len = 0;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&vec![]],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket = &cnft_action_ticket_cookies[0].address;
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&cnft_action_ticket).await;
+
+    assert!(cnft_action_ticket_info.weight == 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_proof_nodes_and_historical_root() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
         &nft_collection_cookie,
         &mut tree_cookie,
         &voter_cookie
@@ -112,32 +195,45 @@ async fn test_create_cnft_action_ticket_with_multiple_nfts() -> Result<(), Trans
 
     nft_voter_test.bench.advance_clock().await;
 
-    let (leaf_verification_cookie1, proofs1, _) =
+    let (mut leaf_verification_cookie, proofs, _) =
         nft_voter_test.merkle_tree.get_leaf_verification_info(
             &mut tree_cookie,
-            &leaf_cookie1,
+            &leaf_cookie,
             5,
             8
         ).await?;
 
-    let (leaf_verification_cookie2, proofs2, _) =
-        nft_voter_test.merkle_tree.get_leaf_verification_info(
+    leaf_verification_cookie.proof_nodes = Some(
+        proofs
+            .iter()
+            .map(|proof| proof.pubkey.to_bytes())
+            .collect()
+    );
+    leaf_verification_cookie.proof_len = 0;
+
+    // Change the siblings of the leaf after the proof was fetched
+    for _ in 0..2 {
+        nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+            &nft_collection_cookie,
             &mut tree_cookie,
-            &leaf_cookie2,
-            5,
-            8
+            &voter_cookie
         ).await?;
+    }
 
+    // Act
     let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
         &registrar_cookie,
         &voter_weight_record_cookie,
         &voter_cookie,
-        &[&leaf_cookie1, &leaf_cookie2],
-        &[&leaf_verification_cookie1, &leaf_verification_cookie2],
-        &[&proofs1, &proofs2],
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&vec![]],
         &action
     ).await?;
 
+    // Assert
+
+    // The proof of the historical root is fast-forwarded to the current root
     let cnft_action_ticket = &cnft_action_ticket_cookies[0].address;
     let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&cnft_action_ticket).await;
 
@@ -147,10 +243,11 @@ async fn test_create_cnft_action_ticket_with_multiple_nfts() -> Result<(), Trans
 }
 
 #[tokio::test]
-async fn test_create_cnft_action_ticket_with_unverified_collection_error() -> Result<
+async fn test_create_cnft_action_ticket_with_proof_nodes_and_proof_accounts_error() -> Result<
     (),
     TransportError
 > {
+    // Arrange
     let action = VoterWeightAction::CastVote;
     let mut nft_voter_test = NftVoterTest::start_new().await;
     let realm_cookie = nft_voter_test.governance.with_realm().await?;
@@ -193,10 +290,14 @@ async fn test_create_cnft_action_ticket_with_unverified_collection_error() -> Re
             8
         ).await?;
 
-    if let Some(collection) = leaf_verification_cookie.collection.as_mut() {
-        collection.verified = false;
-    }
+    leaf_verification_cookie.proof_nodes = Some(
+        proofs
+            .iter()
+            .map(|proof| proof.pubkey.to_bytes())
+            .collect()
+    );
 
+    // Act
     let err = nft_voter_test
         .with_create_cnft_action_ticket(
             &registrar_cookie,
@@ -210,15 +311,14 @@ async fn test_create_cnft_action_ticket_with_unverified_collection_error() -> Re
         .err()
         .unwrap();
 
-    assert_nft_voter_err(err, NftVoterError::CollectionMustBeVerified);
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidProofData);
+
     Ok(())
 }
 
 #[tokio::test]
-async fn test_create_cnft_action_ticket_with_invalid_metadata_error() -> Result<
-    (),
-    TransportError
-> {
+async fn test_create_cnft_action_ticket_with_multiple_nfts() -> Result<(), TransportError> {
     let action = VoterWeightAction::CastVote;
     let mut nft_voter_test = NftVoterTest::start_new().await;
     let realm_cookie = nft_voter_test.governance.with_realm().await?;
@@ -246,8 +346,13 @@ async fn test_create_cnft_action_ticket_with_invalid_metadata_error() -> Result<
 
     // mint compressed nft
     let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
 
-    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
         &nft_collection_cookie,
         &mut tree_cookie,
         &voter_cookie
@@ -255,52 +360,42 @@ async fn test_create_cnft_action_ticket_with_invalid_metadata_error() -> Result<
 
     nft_voter_test.bench.advance_clock().await;
 
-    let (mut leaf_verification_cookie, proofs, _) =
+    let (leaf_verification_cookie1, proofs1, _) =
         nft_voter_test.merkle_tree.get_leaf_verification_info(
             &mut tree_cookie,
-            &leaf_cookie,
+            &leaf_cookie1,
             5,
             8
         ).await?;
 
-    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
-        &nft_collection_cookie,
-        &mut tree_cookie,
-        &voter_cookie
+    let (leaf_verification_cookie2, proofs2, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie2,
+            5,
+            8
+        ).await?;
+
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie1, &leaf_cookie2],
+        &[&leaf_verification_cookie1, &leaf_verification_cookie2],
+        &[&proofs1, &proofs2],
+        &action
     ).await?;
 
-    leaf_verification_cookie = CompressedNftAsset {
-        name: leaf_cookie2.metadata.name.clone(),
-        symbol: leaf_cookie2.metadata.symbol.clone(),
-        uri: leaf_cookie2.metadata.uri.clone(),
-        seller_fee_basis_points: leaf_cookie2.metadata.seller_fee_basis_points,
-        primary_sale_happened: leaf_cookie2.metadata.primary_sale_happened,
-        is_mutable: leaf_cookie2.metadata.is_mutable,
-        edition_nonce: leaf_cookie2.metadata.edition_nonce,
-        nonce: leaf_cookie2.nonce,
-        index: leaf_cookie2.index,
-        ..leaf_verification_cookie
-    };
+    let cnft_action_ticket = &cnft_action_ticket_cookies[0].address;
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&cnft_action_ticket).await;
 
-    let err = nft_voter_test
-        .with_create_cnft_action_ticket(
-            &registrar_cookie,
-            &voter_weight_record_cookie,
-            &voter_cookie,
-            &[&leaf_cookie],
-            &[&leaf_verification_cookie],
-            &[&proofs],
-            &action
-        ).await
-        .err()
-        .unwrap();
+    assert!(cnft_action_ticket_info.weight == 3);
 
-    assert_compression_err(err, AccountCompressionError::ConcurrentMerkleTreeError);
     Ok(())
 }
 
 #[tokio::test]
-async fn test_create_cnft_action_ticket_with_invalid_collection_error() -> Result<
+async fn test_create_cnft_action_ticket_with_unverified_collection_error() -> Result<
     (),
     TransportError
 > {
@@ -329,18 +424,16 @@ async fn test_create_cnft_action_ticket_with_invalid_collection_error() -> Resul
         &voter_cookie
     ).await?;
 
-    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
-
     let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
     let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
-        &nft_collection_cookie2,
+        &nft_collection_cookie,
         &mut tree_cookie,
         &voter_cookie
     ).await?;
 
     nft_voter_test.bench.advance_clock().await;
 
-    let (leaf_verification_cookie, proofs, _) =
+    let (mut leaf_verification_cookie, proofs, _) =
         nft_voter_test.merkle_tree.get_leaf_verification_info(
             &mut tree_cookie,
             &leaf_cookie,
@@ -348,6 +441,10 @@ async fn test_create_cnft_action_ticket_with_invalid_collection_error() -> Resul
             8
         ).await?;
 
+    if let Some(collection) = leaf_verification_cookie.collection.as_mut() {
+        collection.verified = false;
+    }
+
     let err = nft_voter_test
         .with_create_cnft_action_ticket(
             &registrar_cookie,
@@ -361,12 +458,16 @@ async fn test_create_cnft_action_ticket_with_invalid_collection_error() -> Resul
         .err()
         .unwrap();
 
-    assert_nft_voter_err(err, NftVoterError::CollectionNotFound);
+    assert_nft_voter_err(err, NftVoterError::CollectionMustBeVerified);
     Ok(())
 }
 
 #[tokio::test]
-async fn test_create_cnft_action_ticket_using_delegate() -> Result<(), TransportError> {
+async fn test_create_cnft_action_ticket_with_collection_verified_after_mint() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
     let action = VoterWeightAction::CastVote;
     let mut nft_voter_test = NftVoterTest::start_new().await;
     let realm_cookie = nft_voter_test.governance.with_realm().await?;
@@ -387,23 +488,26 @@ async fn test_create_cnft_action_ticket_using_delegate() -> Result<(), Transport
     ).await?;
 
     let voter_cookie = nft_voter_test.bench.with_wallet().await;
-    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
-        &realm_cookie,
-        &voter_cookie
-    ).await?;
     let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
         &registrar_cookie,
         &voter_cookie
     ).await?;
 
-    // mint compressed nft
+    // Mint the compressed nft with unverified collection and verify it afterwards
     let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
-    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+    let mut leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft(
         &nft_collection_cookie,
         &mut tree_cookie,
         &voter_cookie
     ).await?;
 
+    nft_voter_test.token_metadata.with_compressed_nft_collection_verification(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &mut leaf_cookie,
+        false
+    ).await?;
+
     nft_voter_test.bench.advance_clock().await;
 
     let (leaf_verification_cookie, proofs, _) =
@@ -414,34 +518,2433 @@ async fn test_create_cnft_action_ticket_using_delegate() -> Result<(), Transport
             8
         ).await?;
 
-    let delegate_cookie = nft_voter_test.bench.with_wallet().await;
-    nft_voter_test.governance.set_governance_delegate(
-        &realm_cookie,
-        &voter_token_owner_record_cookie,
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
         &voter_cookie,
-        &Some(delegate_cookie.address)
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
     ).await;
 
-    let delegate_signers = &[&delegate_cookie.signer];
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+    assert_eq!(cnft_action_ticket_info.collection, nft_collection_cookie.mint);
 
-    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_ix(
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_collection_verified_after_mint_using_set_and_verify() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Mint the compressed nft with unverified collection and verify it afterwards
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let mut leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.token_metadata.with_compressed_nft_collection_verification(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &mut leaf_cookie,
+        true
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
         &registrar_cookie,
         &voter_weight_record_cookie,
         &voter_cookie,
         &[&leaf_cookie],
         &[&leaf_verification_cookie],
         &[&proofs],
-        &action,
-        |i| {
-            i.accounts[2].pubkey = delegate_cookie.address;
-        },
-        Some(delegate_signers)
+        &action
     ).await?;
 
-    let cnft_action_ticket = &cnft_action_ticket_cookies[0].address;
-    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&cnft_action_ticket).await;
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+    assert_eq!(cnft_action_ticket_info.collection, nft_collection_cookie.mint);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_invalid_metadata_error() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // mint compressed nft
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    leaf_verification_cookie = CompressedNftAsset {
+        name: leaf_cookie2.metadata.name.clone(),
+        symbol: leaf_cookie2.metadata.symbol.clone(),
+        uri: leaf_cookie2.metadata.uri.clone(),
+        seller_fee_basis_points: leaf_cookie2.metadata.seller_fee_basis_points,
+        primary_sale_happened: leaf_cookie2.metadata.primary_sale_happened,
+        is_mutable: leaf_cookie2.metadata.is_mutable,
+        edition_nonce: leaf_cookie2.metadata.edition_nonce,
+        nonce: leaf_cookie2.nonce,
+        index: leaf_cookie2.index,
+        ..leaf_verification_cookie
+    };
+
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    assert_compression_err(err, AccountCompressionError::ConcurrentMerkleTreeError);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_invalid_collection_error() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie2,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    assert_nft_voter_err(err, NftVoterError::CollectionNotFound);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_using_delegate() -> Result<(), TransportError> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // mint compressed nft
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let delegate_cookie = nft_voter_test.bench.with_wallet().await;
+    nft_voter_test.governance.set_governance_delegate(
+        &realm_cookie,
+        &voter_token_owner_record_cookie,
+        &voter_cookie,
+        &Some(delegate_cookie.address)
+    ).await;
+
+    let delegate_signers = &[&delegate_cookie.signer];
+
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_ix(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        |i| {
+            i.accounts[2].pubkey = delegate_cookie.address;
+        },
+        Some(delegate_signers)
+    ).await?;
+
+    let cnft_action_ticket = &cnft_action_ticket_cookies[0].address;
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&cnft_action_ticket).await;
+
+    assert!(cnft_action_ticket_info.weight == 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_holding_attestation() -> Result<(), TransportError> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let attestation_authority = Keypair::new();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        min_holding_slots: 100,
+        holding_attestation_authority: attestation_authority.pubkey(),
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let clock = nft_voter_test.bench.get_clock().await;
+    let last_transfer_slot = clock.slot.saturating_sub(100);
+    leaf_verification_cookie.last_transfer_slot = Some(last_transfer_slot);
+
+    let attestation_ix = new_ed25519_signature_ix(
+        &attestation_authority,
+        &get_holding_attestation_message(
+            &get_asset_id(&tree_cookie.address, leaf_cookie.nonce),
+            &voter_cookie.address,
+            last_transfer_slot
+        )
+    );
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_pre_ixs(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        vec![attestation_ix],
+        NopOverride,
+        None
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_holding_attestation_of_previous_wallet() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let attestation_authority = Keypair::new();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        min_holding_slots: 100,
+        holding_attestation_authority: attestation_authority.pubkey(),
+        allow_voter_migration: true,
+        ..Default::default()
+    }).await?;
+
+    let previous_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let previous_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &previous_wallet_cookie
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // The compressed NFT is moved from the previous wallet to the new wallet after the migration
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    nft_voter_test.migrate_voter(
+        &registrar_cookie,
+        &previous_wallet_cookie,
+        &previous_token_owner_record_cookie,
+        &voter_cookie,
+        &[],
+        &[]
+    ).await?;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let clock = nft_voter_test.bench.get_clock().await;
+    let last_transfer_slot = clock.slot.saturating_sub(100);
+    leaf_verification_cookie.last_transfer_slot = Some(last_transfer_slot);
+
+    // The holding period is attested for the previous wallet
+    let attestation_ix = new_ed25519_signature_ix(
+        &attestation_authority,
+        &get_holding_attestation_message(
+            &get_asset_id(&tree_cookie.address, leaf_cookie.nonce),
+            &previous_wallet_cookie.address,
+            last_transfer_slot
+        )
+    );
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_pre_ixs(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        vec![attestation_ix],
+        NopOverride,
+        None
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_missing_holding_attestation_error() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        min_holding_slots: 100,
+        holding_attestation_authority: Keypair::new().pubkey(),
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::MissingHoldingAttestation);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_holding_period_not_met_error() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let attestation_authority = Keypair::new();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        min_holding_slots: 100,
+        holding_attestation_authority: attestation_authority.pubkey(),
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The asset was transferred in the current slot
+    let last_transfer_slot = nft_voter_test.bench.get_clock().await.slot;
+    leaf_verification_cookie.last_transfer_slot = Some(last_transfer_slot);
+
+    let attestation_ix = new_ed25519_signature_ix(
+        &attestation_authority,
+        &get_holding_attestation_message(
+            &get_asset_id(&tree_cookie.address, leaf_cookie.nonce),
+            &voter_cookie.address,
+            last_transfer_slot
+        )
+    );
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket_using_pre_ixs(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action,
+            vec![attestation_ix],
+            NopOverride,
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::HoldingPeriodNotMet);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_uses_exhausted_error() -> Result<(), TransportError> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // mint compressed nft which can be used only once
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection_and_uses(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie,
+        Some(MetaplexUses {
+            use_method: MetaplexUseMethod::Single,
+            remaining: 1,
+            total: 1,
+        })
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    let asset_uses_record = nft_voter_test.get_asset_uses_record(
+        &get_asset_uses_record_address(&leaf_cookie.asset_id)
+    ).await;
+
+    assert_eq!(asset_uses_record.used, 1);
+
+    // Act
+    // Request more compute units to make the transaction distinct from the previous one
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket_using_pre_ixs(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action,
+            vec![ComputeBudgetInstruction::set_compute_unit_limit(400_000)],
+            NopOverride,
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::NftUsesExhausted);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_allowed_tree_authority() -> Result<(), TransportError> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_authority_allowlist = [Pubkey::default(); MAX_TREE_AUTHORITIES];
+    tree_authority_allowlist[0] = tree_cookie.tree_creator.pubkey();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        tree_authority_allowlist,
+        ..Default::default()
+    }).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_tree_authority_not_allowed_error() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    // Only trees of the DAO tree authority are allowed
+    let mut tree_authority_allowlist = [Pubkey::default(); MAX_TREE_AUTHORITIES];
+    tree_authority_allowlist[0] = Keypair::new().pubkey();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        tree_authority_allowlist,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::TreeAuthorityNotAllowed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_precomputed_metadata_hashes() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_authority_allowlist = [Pubkey::default(); MAX_TREE_AUTHORITIES];
+    tree_authority_allowlist[0] = tree_cookie.tree_creator.pubkey();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        tree_authority_allowlist,
+        allow_precomputed_metadata_hashes: true,
+        ..Default::default()
+    }).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Supply the hashes the same way DAS API returns them and drop the long form metadata
+    leaf_verification_cookie.data_hash = Some(leaf_cookie.data_hash());
+    leaf_verification_cookie.creator_hash = Some(leaf_cookie.creator_hash());
+    leaf_verification_cookie.name = String::new();
+    leaf_verification_cookie.symbol = String::new();
+    leaf_verification_cookie.uri = String::new();
+    leaf_verification_cookie.creators = vec![];
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_precomputed_metadata_hashes_not_allowed_error() -> Result<
+    (),
+    TransportError
+> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    leaf_verification_cookie.data_hash = Some(leaf_cookie.data_hash());
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::PrecomputedMetadataHashesNotAllowed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_attested_leaf_hash() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let leaf_hash_verifier = Keypair::new();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        leaf_hash_verifier: leaf_hash_verifier.pubkey(),
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The verifier service computes the leaf hash and the client drops the long form metadata
+    let asset_id = get_asset_id(&tree_cookie.address, leaf_cookie.nonce);
+    let leaf_hash = get_compressed_nft_leaf_node(&asset_id, &leaf_verification_cookie);
+
+    leaf_verification_cookie.leaf_hash = Some(leaf_hash);
+    leaf_verification_cookie.name = String::new();
+    leaf_verification_cookie.symbol = String::new();
+    leaf_verification_cookie.uri = String::new();
+    leaf_verification_cookie.creators = vec![];
+
+    let attestation_ix = new_ed25519_signature_ix(
+        &leaf_hash_verifier,
+        &get_leaf_hash_attestation_message(
+            &asset_id,
+            &voter_cookie.address,
+            &leaf_hash,
+            &nft_collection_cookie.mint
+        )
+    );
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_pre_ixs(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        vec![attestation_ix],
+        NopOverride,
+        None
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket_info.weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_unattested_leaf_hash_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        leaf_hash_verifier: Keypair::new().pubkey(),
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let asset_id = get_asset_id(&tree_cookie.address, leaf_cookie.nonce);
+    leaf_verification_cookie.leaf_hash = Some(
+        get_compressed_nft_leaf_node(&asset_id, &leaf_verification_cookie)
+    );
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::Ed25519SignatureNotFound);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_tree_rate_limit() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_tree_assets_per_slot: 2,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie1, proofs1, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie1,
+            5,
+            8
+        ).await?;
+
+    let (leaf_verification_cookie2, proofs2, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie2,
+            5,
+            8
+        ).await?;
+
+    // Act
+    nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie1, &leaf_cookie2],
+        &[&leaf_verification_cookie1, &leaf_verification_cookie2],
+        &[&proofs1, &proofs2],
+        &action
+    ).await?;
+
+    // Assert
+    let tree_rate_limit_record = nft_voter_test.get_tree_rate_limit_record(
+        &get_tree_rate_limit_record_address(
+            &registrar_cookie.address,
+            &leaf_cookie1.tree_address,
+            &voter_cookie.address
+        )
+    ).await;
+
+    let clock = nft_voter_test.bench.get_clock().await;
+
+    assert_eq!(tree_rate_limit_record.tree, leaf_cookie1.tree_address);
+    assert_eq!(tree_rate_limit_record.governing_token_owner, voter_cookie.address);
+    assert_eq!(tree_rate_limit_record.get_slot_count(clock.slot), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_tree_rate_limit_exceeded_error() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_tree_assets_per_slot: 1,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie1, proofs1, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie1,
+            5,
+            8
+        ).await?;
+
+    let (leaf_verification_cookie2, proofs2, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie2,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie1, &leaf_cookie2],
+            &[&leaf_verification_cookie1, &leaf_verification_cookie2],
+            &[&proofs1, &proofs2],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::TreeRateLimitExceeded);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_proof_attestation() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        proof_attestation_freshness_slots: 100,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Reference the attestation instead of the proof
+    let attested_leaf_verification_cookie = CompressedNftAsset {
+        proof_len: 0,
+        use_proof_attestation: true,
+        ..leaf_verification_cookie.clone()
+    };
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&attested_leaf_verification_cookie],
+        &[&vec![]],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket.weight, 3);
+
+    let proof_attestation = nft_voter_test.get_proof_attestation(
+        &get_proof_attestation_address(&registrar_cookie.address, &leaf_cookie.asset_id)
+    ).await;
+
+    assert_eq!(proof_attestation.asset_id, leaf_cookie.asset_id);
+    assert_eq!(proof_attestation.leaf_owner, voter_cookie.address);
+    assert_eq!(proof_attestation.root, leaf_verification_cookie.root);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_expired_proof_attestation_error() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        proof_attestation_freshness_slots: 1,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Reference the attestation instead of the proof
+    let attested_leaf_verification_cookie = CompressedNftAsset {
+        proof_len: 0,
+        use_proof_attestation: true,
+        ..leaf_verification_cookie.clone()
+    };
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&attested_leaf_verification_cookie],
+            &[&vec![]],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ProofAttestationExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_tree_root_within_max_age() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_tree_root_age: 1,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Change the tree after the proof was fetched
+    nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket.weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_stale_tree_root_error() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_tree_root_age: 1,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Change the tree after the proof was fetched
+    nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::StaleTreeRoot);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_tree_buffer_churn_error() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Mint as many assets as the tree buffer size (8) after the proof was fetched to evict its root from the changelog
+    for _ in 0..8 {
+        nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+            &nft_collection_cookie,
+            &mut tree_cookie,
+            &voter_cookie
+        ).await?;
+    }
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::TreeBufferChurn);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_bubblegum_program_override_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    // The tree was created by the Bubblegum program of the cluster and not by the overriding program
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        bubblegum_program_override: Pubkey::new_unique(),
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidMerkleTreeAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_cold_wallet_link() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &voter_cookie,
+        expires_at
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &cold_wallet_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket.governing_token_owner, voter_cookie.address);
+    assert_eq!(cnft_action_ticket.weight, 3);
+    assert_eq!(cnft_action_ticket.delegation_expires_at, expires_at);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_revoked_cold_wallet_link_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &voter_cookie,
+        expires_at
+    ).await?;
+
+    nft_voter_test.revoke_cold_wallet_link(&cold_wallet_link, &voter_cookie).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &cold_wallet_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ColdWalletLinkExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_leaf_schema_v2() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, _, asset_id) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Mint the asset as LeafSchema::V2 into mpl-account-compression tree
+    leaf_verification_cookie.leaf_schema = LeafSchema::V2 {
+        asset_data_hash: [0; 32],
+        flags: 0,
+    };
+    leaf_verification_cookie.edition_nonce = None;
+
+    let (root, proof_nodes) = nft_voter_test.merkle_tree.with_leaf_schema_v2_tree(
+        &tree_cookie,
+        leaf_cookie.index,
+        get_compressed_nft_leaf_node(&asset_id, &leaf_verification_cookie)
+    ).await?;
+
+    leaf_verification_cookie.root = root;
+    leaf_verification_cookie.proof_nodes = Some(proof_nodes);
+    leaf_verification_cookie.proof_len = 0;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&vec![]],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket = &cnft_action_ticket_cookies[0].address;
+    let cnft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&cnft_action_ticket).await;
 
     assert!(cnft_action_ticket_info.weight == 3);
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_leaf_schema_version_mismatch_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, _, asset_id) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The tree holds LeafSchema::V1 leaf but it's owned by mpl-account-compression
+    let (root, proof_nodes) = nft_voter_test.merkle_tree.with_leaf_schema_v2_tree(
+        &tree_cookie,
+        leaf_cookie.index,
+        get_compressed_nft_leaf_node(&asset_id, &leaf_verification_cookie)
+    ).await?;
+
+    leaf_verification_cookie.root = root;
+    leaf_verification_cookie.proof_nodes = Some(proof_nodes);
+    leaf_verification_cookie.proof_len = 0;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&vec![]],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::LeafSchemaVersionMismatch);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_logs_verify_leaf_context() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The metadata doesn't match the leaf and hence VerifyLeaf fails
+    leaf_verification_cookie.name = "other".to_string();
+
+    let (instructions, _) = nft_voter_test.create_cnft_action_ticket_ixs(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        vec![],
+        vec![],
+        NopOverride
+    );
+
+    // Act
+    let failure = nft_voter_test.bench
+        .process_transaction_with_logs(&instructions, Some(&[&voter_cookie.signer]))
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    let log_messages = &failure.logs.log_messages;
+    let context_idx = log_messages
+        .iter()
+        .position(|log_message| {
+            log_message == "Program log: VerifyLeaf tree, asset_id, root, proof_len: 5"
+        })
+        .unwrap();
+
+    assert_eq!(log_messages[context_idx + 1], format!("Program log: {}", tree_cookie.address));
+    assert_eq!(log_messages[context_idx + 2], format!("Program log: {}", leaf_cookie.asset_id));
+    assert_eq!(
+        log_messages[context_idx + 3],
+        format!("Program log: {}", Pubkey::new_from_array(leaf_verification_cookie.root))
+    );
+
+    assert_compression_err(failure.error, AccountCompressionError::ConcurrentMerkleTreeError);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_uses_limited_collection_and_precomputed_metadata_hashes_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_collection_uses_limit(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        true
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection_and_uses(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie,
+        Some(MetaplexUses {
+            use_method: MetaplexUseMethod::Single,
+            remaining: 1,
+            total: 1,
+        })
+    ).await?;
+
+    let mut tree_authority_allowlist = [Pubkey::default(); MAX_TREE_AUTHORITIES];
+    tree_authority_allowlist[0] = tree_cookie.tree_creator.pubkey();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        tree_authority_allowlist,
+        allow_precomputed_metadata_hashes: true,
+        ..Default::default()
+    }).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Omit the Uses and supply the precomputed data hash which still matches the leaf
+    leaf_verification_cookie.data_hash = Some(leaf_cookie.data_hash());
+    leaf_verification_cookie.creator_hash = Some(leaf_cookie.creator_hash());
+    leaf_verification_cookie.uses = None;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::AssetUsesRequired);
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "metaplex-core")]
+
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_create_core_asset_action_ticket() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_core_assets: true,
+        ..Default::default()
+    }).await?;
+
+    let core_collection_cookie = nft_voter_test.token_metadata.with_core_collection().await;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &core_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let core_asset = nft_voter_test.token_metadata.with_core_asset(
+        &core_collection_cookie,
+        &voter_cookie
+    ).await;
+
+    // Act
+    let nft_action_ticket_cookies = nft_voter_test.with_create_core_asset_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&core_asset],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    // Assert
+    let nft_action_ticket = nft_voter_test.get_nft_action_ticket(
+        &nft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(nft_action_ticket.weight, 3);
+    assert_eq!(nft_action_ticket.nft_mint, core_asset);
+    assert_eq!(nft_action_ticket.collection, core_collection_cookie.mint);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_core_asset_action_ticket_with_core_assets_not_allowed_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_core_assets: true,
+        ..Default::default()
+    }).await?;
+
+    let core_collection_cookie = nft_voter_test.token_metadata.with_core_collection().await;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &core_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // Disable Core assets after the collection was configured
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig::default()).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let core_asset = nft_voter_test.token_metadata.with_core_asset(
+        &core_collection_cookie,
+        &voter_cookie
+    ).await;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_core_asset_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&core_asset],
+            &VoterWeightAction::CastVote
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CoreAssetsNotAllowed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_core_asset_action_ticket_with_voter_does_not_own_asset_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_core_assets: true,
+        ..Default::default()
+    }).await?;
+
+    let core_collection_cookie = nft_voter_test.token_metadata.with_core_collection().await;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &core_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let voter_cookie2 = nft_voter_test.bench.with_wallet().await;
+    let core_asset = nft_voter_test.token_metadata.with_core_asset(
+        &core_collection_cookie,
+        &voter_cookie2
+    ).await;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_core_asset_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&core_asset],
+            &VoterWeightAction::CastVote
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterDoesNotOwnNft);
+
+    Ok(())
+}
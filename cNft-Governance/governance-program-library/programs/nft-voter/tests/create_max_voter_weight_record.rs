@@ -31,6 +31,31 @@ async fn test_create_max_voter_weight_record() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_create_max_voter_weight_record_with_token_2022_mint() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_token_2022_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Assert
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record_cookie.account, max_voter_weight_record);
+    assert_eq!(max_voter_weight_record.governing_token_mint, realm_cookie.account.community_mint);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_max_voter_weight_record_with_invalid_realm_error() -> Result<
     (),
@@ -1,7 +1,13 @@
-use crate::program_test::{ tools::assert_nft_voter_err, token_metadata_test::CreateNftArgs };
-use gpl_nft_voter::state::VoterWeightAction;
+use crate::program_test::{
+    tools::{ assert_nft_voter_err, NopOverride },
+    token_metadata_test::CreateNftArgs,
+};
+use gpl_nft_voter::state::{ RegistrarConfig, VoterWeightAction };
 use gpl_nft_voter::error::NftVoterError;
+use gpl_nft_voter::tools::merkle_tree::get_voter_allowlist_leaf;
 use program_test::nft_voter_test::*;
+use solana_program::keccak::hashv;
+use solana_program::pubkey::Pubkey;
 use solana_program_test::*;
 use solana_sdk::transport::TransportError;
 mod program_test;
@@ -402,3 +408,141 @@ async fn test_create_nft_action_ticket_using_delegate() -> Result<(), TransportE
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_create_nft_action_ticket_with_voter_allowlist() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Allowlist of the voter and another member
+    let voter_leaf = get_voter_allowlist_leaf(&voter_cookie.address);
+    let member_leaf = get_voter_allowlist_leaf(&Pubkey::new_unique());
+    let voter_allowlist_root = if voter_leaf <= member_leaf {
+        hashv(&[&voter_leaf, &member_leaf]).to_bytes()
+    } else {
+        hashv(&[&member_leaf, &voter_leaf]).to_bytes()
+    };
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        voter_allowlist_root,
+        ..Default::default()
+    }).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    // Act
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket_using_voter_allowlist_proof(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action,
+        vec![member_leaf],
+        NopOverride,
+        None
+    ).await?;
+
+    // Assert
+    let nft_action_ticket = &nft_action_ticket_cookies[0].address;
+    let nft_action_ticket_info = nft_voter_test.get_nft_action_ticket(&nft_action_ticket).await;
+
+    assert!(nft_action_ticket_info.weight == 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_nft_action_ticket_with_voter_not_in_allowlist_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Allowlist of the voter and another member
+    let voter_leaf = get_voter_allowlist_leaf(&voter_cookie.address);
+    let member_leaf = get_voter_allowlist_leaf(&Pubkey::new_unique());
+    let voter_allowlist_root = if voter_leaf <= member_leaf {
+        hashv(&[&voter_leaf, &member_leaf]).to_bytes()
+    } else {
+        hashv(&[&member_leaf, &voter_leaf]).to_bytes()
+    };
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        voter_allowlist_root,
+        ..Default::default()
+    }).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_nft_action_ticket(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&nft_cookie],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterNotAllowed);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 mod program_test;
 
 use anchor_lang::prelude::{ ErrorCode, Pubkey };
+use borsh::BorshSerialize;
 use gpl_nft_voter::error::NftVoterError;
 use program_test::nft_voter_test::NftVoterTest;
 
@@ -24,6 +25,65 @@ async fn test_create_registrar() -> Result<(), TransportError> {
 
     assert_eq!(registrar, registrar_cookie.account);
 
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    assert_eq!(registrar_extension, registrar_cookie.extension);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_registrar_with_token_2022_mint() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_token_2022_realm().await?;
+
+    // Act
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Assert
+    let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    assert_eq!(registrar, registrar_cookie.account);
+    assert_eq!(registrar.governing_token_mint, realm_cookie.account.community_mint);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_registrar_with_governing_token_mint_owned_by_invalid_program_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let mut realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    // Replace the community mint with an account which has the Mint layout but isn't owned by a token program
+    let mint_data = nft_voter_test.bench.get_account_data(
+        realm_cookie.account.community_mint
+    ).await;
+    realm_cookie.account.community_mint = nft_voter_test.bench.with_account_data(
+        &Pubkey::new_unique(),
+        mint_data
+    ).await;
+
+    nft_voter_test.bench.set_account_data(
+        &realm_cookie.address,
+        &nft_voter_test.governance.program_id,
+        realm_cookie.account.try_to_vec().unwrap()
+    ).await;
+
+    // Act
+    let err = nft_voter_test.with_registrar(&realm_cookie).await.err().unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidGoverningTokenMint);
+
     Ok(())
 }
 
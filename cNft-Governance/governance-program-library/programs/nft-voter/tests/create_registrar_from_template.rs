@@ -0,0 +1,247 @@
+mod program_test;
+
+use gpl_nft_voter::error::NftVoterError;
+use gpl_nft_voter::state::max_voter_weight_record::get_max_voter_weight_record_address;
+use gpl_nft_voter::state::{ CollectionWeightMode, RegistrarConfig };
+use program_test::nft_voter_test::{ ConfigureCollectionArgs, NftVoterTest };
+
+use solana_program_test::*;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+
+use program_test::tools::{ assert_nft_voter_err, NopOverride };
+
+#[tokio::test]
+async fn test_create_registrar_from_template() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let mut registrar_template_cookie = nft_voter_test.with_registrar_template(5).await?;
+
+    nft_voter_test.with_registrar_template_config(
+        &mut registrar_template_cookie,
+        RegistrarConfig {
+            collection_weight_mode: CollectionWeightMode::UniqueCollections,
+            ..Default::default()
+        },
+        &[&nft_collection_cookie1, &nft_collection_cookie2],
+        &[
+            ConfigureCollectionArgs { weight: 1, size: 5 },
+            ConfigureCollectionArgs { weight: 2, size: 10 },
+        ]
+    ).await?;
+
+    let realm_cookie1 = nft_voter_test.governance.with_realm().await?;
+    let realm_cookie2 = nft_voter_test.governance.with_realm().await?;
+
+    // Act
+    let registrar_cookie1 = nft_voter_test.with_registrar_from_template(
+        &realm_cookie1,
+        &registrar_template_cookie
+    ).await?;
+
+    let registrar_cookie2 = nft_voter_test.with_registrar_from_template(
+        &realm_cookie2,
+        &registrar_template_cookie
+    ).await?;
+
+    // Assert
+    for registrar_cookie in [&registrar_cookie1, &registrar_cookie2] {
+        let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+        assert_eq!(registrar, registrar_cookie.account);
+        assert_eq!(registrar.collection_configs.len(), 2);
+
+        let registrar_extension = nft_voter_test.get_registrar_extension_account(
+            &registrar_cookie.extension_address
+        ).await;
+
+        assert_eq!(registrar_extension, registrar_cookie.extension);
+        assert_eq!(
+            registrar_extension.config.collection_weight_mode,
+            CollectionWeightMode::UniqueCollections
+        );
+
+        let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+            &get_max_voter_weight_record_address(
+                &registrar.realm,
+                &registrar.governing_token_mint
+            )
+        ).await;
+
+        assert_eq!(max_voter_weight_record.max_voter_weight_expiry, None);
+        assert_eq!(max_voter_weight_record.max_voter_weight, 25);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_registrar_from_template_with_insufficient_max_collections_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let mut registrar_template_cookie = nft_voter_test.with_registrar_template(5).await?;
+
+    nft_voter_test.with_registrar_template_config(
+        &mut registrar_template_cookie,
+        RegistrarConfig::default(),
+        &[&nft_collection_cookie1, &nft_collection_cookie2],
+        &[
+            ConfigureCollectionArgs { weight: 1, size: 5 },
+            ConfigureCollectionArgs { weight: 2, size: 10 },
+        ]
+    ).await?;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_from_template_using_ix(
+            &realm_cookie,
+            &registrar_template_cookie,
+            1,
+            NopOverride,
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRegistrarTemplate);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_registrar_from_template_with_invalid_realm_authority_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let registrar_template_cookie = nft_voter_test.with_registrar_template(5).await?;
+
+    let mut realm_cookie = nft_voter_test.governance.with_realm().await?;
+    realm_cookie.realm_authority = Keypair::new();
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_from_template(&realm_cookie, &registrar_template_cookie).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRealmAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_template_with_invalid_authority_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let mut registrar_template_cookie = nft_voter_test.with_registrar_template(5).await?;
+
+    let invalid_authority = Keypair::new();
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_template_config_using_ix(
+            &mut registrar_template_cookie,
+            RegistrarConfig::default(),
+            &[&nft_collection_cookie],
+            &[ConfigureCollectionArgs { weight: 1, size: 5 }],
+            |i| {
+                i.accounts[1].pubkey = invalid_authority.pubkey(); // authority
+            },
+            Some(&[&invalid_authority])
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRegistrarTemplateAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_template_with_duplicate_collections_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let mut registrar_template_cookie = nft_voter_test.with_registrar_template(5).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_template_config(
+            &mut registrar_template_cookie,
+            RegistrarConfig::default(),
+            &[&nft_collection_cookie, &nft_collection_cookie],
+            &[
+                ConfigureCollectionArgs { weight: 1, size: 5 },
+                ConfigureCollectionArgs { weight: 2, size: 10 },
+            ]
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRegistrarTemplate);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configure_registrar_template_with_too_many_collections_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(None).await?;
+
+    let mut registrar_template_cookie = nft_voter_test.with_registrar_template(1).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_registrar_template_config(
+            &mut registrar_template_cookie,
+            RegistrarConfig::default(),
+            &[&nft_collection_cookie1, &nft_collection_cookie2],
+            &[
+                ConfigureCollectionArgs { weight: 1, size: 5 },
+                ConfigureCollectionArgs { weight: 2, size: 10 },
+            ]
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRegistrarTemplate);
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_create_voter_rent_escrow() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    let voter_rent_escrow = nft_voter_test.with_voter_rent_escrow(
+        &registrar_cookie,
+        &voter_cookie,
+        1_000_000
+    ).await?;
+
+    // Assert
+    let voter_rent_escrow_account = nft_voter_test.bench
+        .get_account(&voter_rent_escrow).await
+        .unwrap();
+
+    assert_eq!(
+        voter_rent_escrow_account.lamports,
+        nft_voter_test.bench.rent.minimum_balance(VoterRentEscrow::get_space()) + 1_000_000
+    );
+
+    let voter_rent_escrow = nft_voter_test.get_voter_rent_escrow(&voter_rent_escrow).await;
+
+    assert_eq!(voter_rent_escrow, VoterRentEscrow {
+        registrar: registrar_cookie.address,
+        governing_token_owner: voter_cookie.address,
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_voter_rent_escrow() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let beneficiary_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let voter_rent_escrow = nft_voter_test.with_voter_rent_escrow(
+        &registrar_cookie,
+        &voter_cookie,
+        1_000_000
+    ).await?;
+
+    let voter_rent_escrow_lamports = nft_voter_test.bench
+        .get_account(&voter_rent_escrow).await
+        .unwrap().lamports;
+    let beneficiary_lamports = nft_voter_test.bench
+        .get_account(&beneficiary_cookie.address).await
+        .unwrap().lamports;
+
+    // Act
+    nft_voter_test.close_voter_rent_escrow(
+        &voter_rent_escrow,
+        &voter_cookie,
+        &beneficiary_cookie.address
+    ).await?;
+
+    // Assert
+    assert_eq!(None, nft_voter_test.bench.get_account(&voter_rent_escrow).await);
+
+    let beneficiary = nft_voter_test.bench.get_account(&beneficiary_cookie.address).await.unwrap();
+    assert_eq!(beneficiary.lamports, beneficiary_lamports + voter_rent_escrow_lamports);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_voter_rent_escrow_with_other_voter_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let other_voter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let voter_rent_escrow = nft_voter_test.with_voter_rent_escrow(
+        &registrar_cookie,
+        &voter_cookie,
+        1_000_000
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .close_voter_rent_escrow(
+            &voter_rent_escrow,
+            &other_voter_cookie,
+            &other_voter_cookie.address
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidVoterRentEscrow);
+
+    Ok(())
+}
@@ -35,6 +35,34 @@ async fn test_create_voter_weight_record() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_create_voter_weight_record_with_token_2022_mint() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_token_2022_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record_cookie.account, voter_weight_record);
+    assert_eq!(voter_weight_record.governing_token_mint, realm_cookie.account.community_mint);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_voter_weight_record_with_invalid_realm_error() -> Result<(), TransportError> {
     // Arrange
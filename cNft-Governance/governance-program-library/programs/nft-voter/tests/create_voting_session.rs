@@ -0,0 +1,129 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_create_voting_session() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 100;
+
+    // Act
+    let voting_session = nft_voter_test.with_voting_session(
+        &registrar_cookie,
+        &voter_cookie,
+        &session_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    // Assert
+    let voting_session = nft_voter_test.get_voting_session(&voting_session).await;
+
+    assert_eq!(voting_session, VotingSession {
+        registrar: registrar_cookie.address,
+        governing_token_owner: voter_cookie.address,
+        session_authority: session_cookie.address,
+        expires_at_slot,
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_voting_session_with_too_long_expiry_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at_slot =
+        nft_voter_test.bench.get_clock().await.slot + MAX_VOTING_SESSION_DURATION_SLOTS + 1;
+
+    // Act
+    let err = nft_voter_test
+        .with_voting_session(
+            &registrar_cookie,
+            &voter_cookie,
+            &session_cookie.address,
+            expires_at_slot
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidVotingSessionExpiry);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revoke_voting_session() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 100;
+
+    let voting_session = nft_voter_test.with_voting_session(
+        &registrar_cookie,
+        &voter_cookie,
+        &session_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    // Act
+    nft_voter_test.revoke_voting_session(&voting_session, &voter_cookie).await?;
+
+    // Assert
+    assert_eq!(None, nft_voter_test.bench.get_account(&voting_session).await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revoke_voting_session_with_other_authority_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let session_cookie = nft_voter_test.bench.with_wallet().await;
+    let other_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 100;
+
+    let voting_session = nft_voter_test.with_voting_session(
+        &registrar_cookie,
+        &voter_cookie,
+        &session_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .revoke_voting_session(&voting_session, &other_cookie).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidVotingSession);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use gpl_nft_voter::error::NftVoterError;
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program_test::*;
+use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
+
+mod program_test;
+
+#[tokio::test]
+async fn test_extend_registrar() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let registrar_data_len = nft_voter_test.bench.get_account_data(registrar_cookie.address).await.len();
+
+    // Act
+    nft_voter_test.extend_account(&registrar_cookie, &registrar_cookie.address, 256).await?;
+
+    // Assert
+    let registrar_data = nft_voter_test.bench.get_account_data(registrar_cookie.address).await;
+
+    assert_eq!(registrar_data.len(), registrar_data_len + 256);
+    assert!(registrar_data[registrar_data_len..].iter().all(|b| *b == 0));
+
+    let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    assert_eq!(registrar, registrar_cookie.account);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extend_account_with_invalid_realm_authority_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let realm_authority = Keypair::new();
+
+    // Act
+    let err = nft_voter_test
+        .extend_account_using_ix(
+            &registrar_cookie,
+            &registrar_cookie.address,
+            256,
+            |i| {
+                i.accounts[2].pubkey = realm_authority.pubkey();
+            }, // realm_authority
+            Some(&[&realm_authority])
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRealmAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extend_account_with_max_voter_weight_record_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .extend_account(&registrar_cookie, &max_voter_weight_record_cookie.address, 256).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::AccountCannotBeExtended);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extend_account_with_zero_extra_space_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .extend_account(&registrar_cookie, &registrar_cookie.address, 0).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidAccountExtension);
+
+    Ok(())
+}
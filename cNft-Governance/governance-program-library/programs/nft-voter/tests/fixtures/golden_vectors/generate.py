@@ -0,0 +1,309 @@
+#!/usr/bin/env python3
+"""Generates golden_vectors.json for tests/golden_vectors.rs
+
+The asset ids and leaf hashes are derived with a standalone implementation of the Bubblegum
+(mpl-bubblegum 0.7, LeafSchema V1) derivation which doesn't share any code with the Rust crates:
+
+  asset_id     = find_program_address(["asset", tree, nonce_le], bubblegum)
+  data_hash    = keccak(keccak(borsh(MetadataArgs)) || seller_fee_basis_points_le)
+  creator_hash = keccak(address || verified || share ...)
+  leaf_hash    = keccak(1 || asset_id || owner || delegate || nonce_le || data_hash || creator_hash)
+
+Vectors taken from a cluster (e.g. using DAS getAsset / getAssetProof) can be appended to the json file
+in the same format.
+
+Usage: python3 generate.py > golden_vectors.json
+"""
+
+import hashlib
+import json
+import struct
+
+BUBBLEGUM_PROGRAM_ID = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+
+# Keccak-256 (original padding as used by solana_program::keccak)
+
+KECCAK_ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+
+KECCAK_ROTATIONS = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+
+MASK_64 = (1 << 64) - 1
+
+
+def rotl64(value, shift):
+    return ((value << shift) | (value >> (64 - shift))) & MASK_64 if shift else value
+
+
+def keccak_f(state):
+    for round_constant in KECCAK_ROUND_CONSTANTS:
+        c = [state[x][0] ^ state[x][1] ^ state[x][2] ^ state[x][3] ^ state[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ rotl64(c[(x + 1) % 5], 1) for x in range(5)]
+        state = [[state[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = rotl64(state[x][y], KECCAK_ROTATIONS[x][y])
+
+        state = [
+            [b[x][y] ^ ((~b[(x + 1) % 5][y]) & b[(x + 2) % 5][y]) for y in range(5)]
+            for x in range(5)
+        ]
+        state[0][0] ^= round_constant
+    return state
+
+
+def keccak256(*data):
+    rate = 136
+    message = bytearray(b"".join(data))
+    message.append(0x01)
+    while len(message) % rate:
+        message.append(0)
+    message[-1] |= 0x80
+
+    state = [[0] * 5 for _ in range(5)]
+    for offset in range(0, len(message), rate):
+        block = message[offset:offset + rate]
+        for i in range(rate // 8):
+            x, y = i % 5, i // 5
+            state[x][y] ^= struct.unpack("<Q", block[i * 8:i * 8 + 8])[0]
+        state = keccak_f(state)
+
+    output = b""
+    for i in range(4):
+        output += struct.pack("<Q", state[i % 5][i // 5])
+    return output
+
+
+# Base58
+
+BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def b58encode(data):
+    number = int.from_bytes(data, "big")
+    encoded = ""
+    while number:
+        number, remainder = divmod(number, 58)
+        encoded = BASE58_ALPHABET[remainder] + encoded
+    return "1" * (len(data) - len(data.lstrip(b"\0"))) + encoded
+
+
+def b58decode(text):
+    number = 0
+    for char in text:
+        number = number * 58 + BASE58_ALPHABET.index(char)
+    data = number.to_bytes((number.bit_length() + 7) // 8, "big")
+    return (b"\0" * (len(text) - len(text.lstrip("1"))) + data).rjust(32, b"\0")
+
+
+# Program derived addresses
+
+ED25519_P = 2 ** 255 - 19
+ED25519_D = (-121665 * pow(121666, ED25519_P - 2, ED25519_P)) % ED25519_P
+
+
+def is_on_curve(point):
+    y = (int.from_bytes(point, "little") & ((1 << 255) - 1)) % ED25519_P
+    u = (y * y - 1) % ED25519_P
+    v = (ED25519_D * y * y + 1) % ED25519_P
+    x2 = (u * pow(v, ED25519_P - 2, ED25519_P)) % ED25519_P
+    if x2 == 0:
+        return True
+    return pow(x2, (ED25519_P - 1) // 2, ED25519_P) == 1
+
+
+def find_program_address(seeds, program_id):
+    for bump in range(255, -1, -1):
+        address = hashlib.sha256(
+            b"".join(seeds) + bytes([bump]) + program_id + b"ProgramDerivedAddress"
+        ).digest()
+        if not is_on_curve(address):
+            return address
+    raise ValueError("Unable to find a viable program address bump seed")
+
+
+# Borsh encoding of mpl_bubblegum::state::metaplex_adapter::MetadataArgs
+
+def borsh_string(value):
+    encoded = value.encode("utf-8")
+    return struct.pack("<I", len(encoded)) + encoded
+
+
+def borsh_option(value, encode):
+    return b"\0" if value is None else b"\1" + encode(value)
+
+
+USE_METHODS = {"Burn": 0, "Multiple": 1, "Single": 2}
+
+
+def encode_metadata_args(metadata):
+    return b"".join([
+        borsh_string(metadata["name"]),
+        borsh_string(metadata["symbol"]),
+        borsh_string(metadata["uri"]),
+        struct.pack("<H", metadata["seller_fee_basis_points"]),
+        bytes([metadata["primary_sale_happened"]]),
+        bytes([metadata["is_mutable"]]),
+        borsh_option(metadata["edition_nonce"], lambda nonce: bytes([nonce])),
+        # token_standard: Some(NonFungible)
+        b"\1\0",
+        borsh_option(
+            metadata["collection"],
+            lambda collection: bytes([collection["verified"]]) + b58decode(collection["key"]),
+        ),
+        borsh_option(
+            metadata["uses"],
+            lambda uses: bytes([USE_METHODS[uses["use_method"]]])
+            + struct.pack("<QQ", uses["remaining"], uses["total"]),
+        ),
+        # token_program_version: Original
+        b"\0",
+        struct.pack("<I", len(metadata["creators"])),
+        b"".join(encode_creator(creator) for creator in metadata["creators"]),
+    ])
+
+
+def encode_creator(creator):
+    return b58decode(creator["address"]) + bytes([creator["verified"], creator["share"]])
+
+
+def derive(vector):
+    metadata = vector["metadata"]
+    tree = b58decode(vector["tree"])
+    nonce = struct.pack("<Q", vector["nonce"])
+
+    asset_id = find_program_address([b"asset", tree, nonce], b58decode(BUBBLEGUM_PROGRAM_ID))
+    data_hash = keccak256(
+        keccak256(encode_metadata_args(metadata)),
+        struct.pack("<H", metadata["seller_fee_basis_points"]),
+    )
+    creator_hash = keccak256(*[encode_creator(creator) for creator in metadata["creators"]])
+    leaf_hash = keccak256(
+        b"\1",
+        asset_id,
+        b58decode(vector["leaf_owner"]),
+        b58decode(vector["leaf_delegate"]),
+        nonce,
+        data_hash,
+        creator_hash,
+    )
+
+    return {
+        **vector,
+        "asset_id": b58encode(asset_id),
+        "data_hash": data_hash.hex(),
+        "creator_hash": creator_hash.hex(),
+        "leaf_hash": leaf_hash.hex(),
+    }
+
+
+def test_address(name):
+    return b58encode(hashlib.sha256(name.encode("utf-8")).digest())
+
+
+VECTORS = [
+    {
+        "description": "Verified collection and a single verified creator",
+        "tree": test_address("golden-tree-1"),
+        "nonce": 0,
+        "leaf_owner": test_address("golden-owner-1"),
+        "leaf_delegate": test_address("golden-owner-1"),
+        "metadata": {
+            "name": "Governance cNFT #0",
+            "symbol": "GOV",
+            "uri": "https://arweave.net/governance/0.json",
+            "seller_fee_basis_points": 500,
+            "primary_sale_happened": False,
+            "is_mutable": True,
+            "edition_nonce": None,
+            "collection": {"verified": True, "key": test_address("golden-collection-1")},
+            "uses": None,
+            "creators": [
+                {"address": test_address("golden-creator-1"), "verified": True, "share": 100},
+            ],
+        },
+    },
+    {
+        "description": "Delegated leaf with edition nonce, uses and multiple creators",
+        "tree": test_address("golden-tree-1"),
+        "nonce": 1337,
+        "leaf_owner": test_address("golden-owner-2"),
+        "leaf_delegate": test_address("golden-delegate-2"),
+        "metadata": {
+            "name": "Governance cNFT #1337",
+            "symbol": "GOV",
+            "uri": "https://arweave.net/governance/1337.json",
+            "seller_fee_basis_points": 0,
+            "primary_sale_happened": True,
+            "is_mutable": False,
+            "edition_nonce": 254,
+            "collection": {"verified": True, "key": test_address("golden-collection-1")},
+            "uses": {"use_method": "Multiple", "remaining": 7, "total": 10},
+            "creators": [
+                {"address": test_address("golden-creator-1"), "verified": True, "share": 60},
+                {"address": test_address("golden-creator-2"), "verified": False, "share": 40},
+            ],
+        },
+    },
+    {
+        "description": "Unverified collection, no creators and large nonce in a different tree",
+        "tree": test_address("golden-tree-2"),
+        "nonce": 16383,
+        "leaf_owner": test_address("golden-owner-3"),
+        "leaf_delegate": test_address("golden-owner-3"),
+        "metadata": {
+            "name": "",
+            "symbol": "",
+            "uri": "https://example.com/été.json",
+            "seller_fee_basis_points": 10000,
+            "primary_sale_happened": False,
+            "is_mutable": True,
+            "edition_nonce": 0,
+            "collection": {"verified": False, "key": test_address("golden-collection-2")},
+            "uses": {"use_method": "Burn", "remaining": 1, "total": 1},
+            "creators": [],
+        },
+    },
+    {
+        "description": "Maximum nonce of a depth 30 tree with five creators",
+        "tree": test_address("golden-tree-3"),
+        "nonce": 2 ** 30 - 1,
+        "leaf_owner": test_address("golden-owner-4"),
+        "leaf_delegate": test_address("golden-delegate-4"),
+        "metadata": {
+            "name": "Governance cNFT with a long name",
+            "symbol": "GOVERNANCE",
+            "uri": "https://arweave.net/" + "x" * 180,
+            "seller_fee_basis_points": 250,
+            "primary_sale_happened": True,
+            "is_mutable": True,
+            "edition_nonce": None,
+            "collection": {"verified": True, "key": test_address("golden-collection-3")},
+            "uses": {"use_method": "Single", "remaining": 0, "total": 1},
+            "creators": [
+                {"address": test_address("golden-creator-%d" % i), "verified": i % 2 == 0, "share": 20}
+                for i in range(5)
+            ],
+        },
+    },
+]
+
+if __name__ == "__main__":
+    assert keccak256(b"").hex() == "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    assert keccak256(b"abc").hex() == "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+
+    print(json.dumps([derive(vector) for vector in VECTORS], indent=2, ensure_ascii=False))
@@ -0,0 +1,216 @@
+#!/usr/bin/env python3
+"""Generates legacy_accounts.json for tests/account_layouts.rs
+
+The accounts are encoded with the layouts of the program before the Anchor 0.29 / Solana 1.17 port
+(the layouts deployed on chain). The encoding is written out field by field and doesn't share any code
+with the Rust crates, so the fixtures keep pinning the old format when the program types change:
+
+  Registrar            = disc || governance_program_id || realm || governing_token_mint
+                         || vec(CollectionConfig { collection, size u32, weight u64, reserved [u8; 8] })
+                         || reserved [u8; 128] || zeroed space of the collections not configured yet
+  VoterWeightRecord    = disc || realm || governing_token_mint || governing_token_owner || voter_weight u64
+                         || option(voter_weight_expiry u64) || option(weight_action u8)
+                         || option(weight_action_target) || reserved [u8; 8]
+  MaxVoterWeightRecord = disc || realm || governing_token_mint || max_voter_weight u64
+                         || option(max_voter_weight_expiry u64) || reserved [u8; 8] || zeroed space of the expiry
+  NftVoteRecord        = disc || proposal || nft_mint || governing_token_owner || reserved [u8; 8]
+  NftActionTicket      = disc || registrar || governing_token_owner || nft_mint || weight u64 || option(expiry u64)
+
+where disc is sha256("account:<AccountName>")[..8] except for NftActionTicket which has a fixed discriminator
+The json file is committed and must not be regenerated when the program layouts change.
+
+Usage: python3 generate.py > legacy_accounts.json
+"""
+
+import hashlib
+import json
+import os
+import struct
+import sys
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), "..", "golden_vectors"))
+
+from generate import b58decode, b58encode, find_program_address, test_address  # noqa: E402
+
+NFT_VOTER_PROGRAM_ID = "GnftVc21v2BRchsRa9dGdrVmJPLZiRHe9j2offnFTZFg"
+
+VOTER_WEIGHT_ACTIONS = {
+    "CastVote": 0,
+    "CommentProposal": 1,
+    "CreateGovernance": 2,
+    "CreateProposal": 3,
+    "SignOffProposal": 4,
+}
+
+
+def discriminator(account_name):
+    return hashlib.sha256(("account:%s" % account_name).encode("utf-8")).digest()[:8]
+
+
+# NftActionTicket isn't an Anchor account and its discriminator isn't derived from the account name
+NFT_ACTION_TICKET_DISCRIMINATOR = bytes([170, 179, 4, 130, 24, 148, 185, 97])
+
+
+def key(address):
+    return b58decode(address)
+
+
+def pda(*seeds):
+    return b58encode(find_program_address(list(seeds), key(NFT_VOTER_PROGRAM_ID)))
+
+
+def u32(value):
+    return struct.pack("<I", value)
+
+
+def u64(value):
+    return struct.pack("<Q", value)
+
+
+def option(value, encode):
+    return b"\0" if value is None else b"\1" + encode(value)
+
+
+def encode_registrar(fields, max_collections):
+    data = discriminator("Registrar")
+    data += key(fields["governance_program_id"]) + key(fields["realm"]) + key(fields["governing_token_mint"])
+    data += u32(len(fields["collection_configs"]))
+    for collection_config in fields["collection_configs"]:
+        data += key(collection_config["collection"])
+        data += u32(collection_config["size"]) + u64(collection_config["weight"]) + bytes(8)
+    data += bytes(128)
+    # Registrar::get_space(max_collections) of the old program
+    space = 8 + 32 * 3 + 4 + max_collections * (32 + 4 + 8 + 8) + 128
+    return data + bytes(space - len(data))
+
+
+def encode_voter_weight_record(fields):
+    data = discriminator("VoterWeightRecord")
+    data += key(fields["realm"]) + key(fields["governing_token_mint"]) + key(fields["governing_token_owner"])
+    data += u64(fields["voter_weight"])
+    data += option(fields["voter_weight_expiry"], u64)
+    data += option(fields["weight_action"], lambda action: bytes([VOTER_WEIGHT_ACTIONS[action]]))
+    data += option(fields["weight_action_target"], key)
+    return data + bytes(8)
+
+
+def encode_max_voter_weight_record(fields):
+    data = discriminator("MaxVoterWeightRecord")
+    data += key(fields["realm"]) + key(fields["governing_token_mint"])
+    data += u64(fields["max_voter_weight"])
+    data += option(fields["max_voter_weight_expiry"], u64)
+    data += bytes(8)
+    # MaxVoterWeightRecord::get_space() of the old program allocates the space for the expiry
+    space = 8 + 32 * 2 + 8 + 1 + 8 + 8
+    return data + bytes(space - len(data))
+
+
+def encode_nft_vote_record(fields):
+    data = discriminator("NftVoteRecord")
+    data += key(fields["proposal"]) + key(fields["nft_mint"]) + key(fields["governing_token_owner"])
+    return data + bytes(8)
+
+
+def encode_nft_action_ticket(fields):
+    data = NFT_ACTION_TICKET_DISCRIMINATOR
+    data += key(fields["registrar"]) + key(fields["governing_token_owner"]) + key(fields["nft_mint"])
+    data += u64(fields["weight"])
+    return data + option(fields["expiry"], u64)
+
+
+REALM = test_address("legacy-realm")
+GOVERNING_TOKEN_MINT = test_address("legacy-governing-token-mint")
+GOVERNING_TOKEN_OWNER = test_address("legacy-governing-token-owner")
+PROPOSAL = test_address("legacy-proposal")
+NFT_MINT = test_address("legacy-nft-mint")
+REGISTRAR = pda(b"registrar", key(REALM), key(GOVERNING_TOKEN_MINT))
+
+
+def generate():
+    registrar = {
+        "governance_program_id": "GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw",
+        "realm": REALM,
+        "governing_token_mint": GOVERNING_TOKEN_MINT,
+        "collection_configs": [
+            {"collection": test_address("legacy-collection-1"), "size": 10, "weight": 3},
+            {"collection": test_address("legacy-collection-2"), "size": 10_000, "weight": 1},
+        ],
+    }
+
+    voter_weight_record = {
+        "realm": REALM,
+        "governing_token_mint": GOVERNING_TOKEN_MINT,
+        "governing_token_owner": GOVERNING_TOKEN_OWNER,
+        "voter_weight": 4,
+        "voter_weight_expiry": 250_000_000,
+        "weight_action": "CastVote",
+        "weight_action_target": PROPOSAL,
+    }
+
+    max_voter_weight_record = {
+        "realm": REALM,
+        "governing_token_mint": GOVERNING_TOKEN_MINT,
+        "max_voter_weight": 10_030,
+        "max_voter_weight_expiry": None,
+    }
+
+    nft_vote_record = {
+        "proposal": PROPOSAL,
+        "nft_mint": NFT_MINT,
+        "governing_token_owner": GOVERNING_TOKEN_OWNER,
+    }
+
+    nft_action_ticket = {
+        "registrar": REGISTRAR,
+        "governing_token_owner": GOVERNING_TOKEN_OWNER,
+        "nft_mint": NFT_MINT,
+        "weight": 3,
+        "expiry": 250_000_010,
+    }
+
+    return [
+        {
+            "account": "Registrar",
+            "address": REGISTRAR,
+            "max_collections": 3,
+            "fields": registrar,
+            "data": encode_registrar(registrar, 3).hex(),
+        },
+        {
+            "account": "VoterWeightRecord",
+            "address": pda(
+                b"voter-weight-record",
+                key(REALM),
+                key(GOVERNING_TOKEN_MINT),
+                key(GOVERNING_TOKEN_OWNER),
+            ),
+            "fields": voter_weight_record,
+            "data": encode_voter_weight_record(voter_weight_record).hex(),
+        },
+        {
+            "account": "MaxVoterWeightRecord",
+            "address": pda(b"max-voter-weight-record", key(REALM), key(GOVERNING_TOKEN_MINT)),
+            "fields": max_voter_weight_record,
+            "data": encode_max_voter_weight_record(max_voter_weight_record).hex(),
+        },
+        {
+            "account": "NftVoteRecord",
+            # The old program derived the record from the proposal and the NFT only
+            "address": pda(b"nft-vote-record", key(PROPOSAL), key(NFT_MINT)),
+            "fields": nft_vote_record,
+            "data": encode_nft_vote_record(nft_vote_record).hex(),
+        },
+        {
+            "account": "NftActionTicket",
+            "address": pda(b"nft-castVote-ticket", key(REGISTRAR), key(GOVERNING_TOKEN_OWNER), key(NFT_MINT)),
+            "fields": nft_action_ticket,
+            "data": encode_nft_action_ticket(nft_action_ticket).hex(),
+        },
+    ]
+
+
+if __name__ == "__main__":
+    assert discriminator("Registrar") == bytes([193, 202, 205, 51, 78, 168, 150, 128])
+    assert discriminator("NftVoteRecord") == bytes([137, 6, 55, 139, 251, 126, 254, 99])
+
+    print(json.dumps(generate(), indent=2))
@@ -0,0 +1,151 @@
+//! Golden vectors for the compressed NFT asset id and leaf hash derivation
+//!
+//! The expected values in fixtures/golden_vectors/golden_vectors.json are produced by generate.py which implements
+//! the Bubblegum derivation independently of mpl-bubblegum and solana-program.
+//! The tests catch hashing or encoding changes introduced by upgrades of the external crates.
+
+use std::convert::TryInto;
+use std::str::FromStr;
+
+use gpl_nft_voter::state::{
+    get_compressed_nft_leaf_node,
+    Collection,
+    CompressedNftAsset,
+    Creator,
+    LeafSchema,
+    UseMethod,
+    Uses,
+};
+use cnft_verification::get_asset_id;
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+
+const GOLDEN_VECTORS: &str = include_str!("fixtures/golden_vectors/golden_vectors.json");
+
+fn get_golden_vectors() -> Vec<Value> {
+    serde_json::from_str::<Value>(GOLDEN_VECTORS).unwrap().as_array().unwrap().clone()
+}
+
+fn to_pubkey(value: &Value) -> Pubkey {
+    Pubkey::from_str(value.as_str().unwrap()).unwrap()
+}
+
+fn to_hash(value: &Value) -> [u8; 32] {
+    let hex = value.as_str().unwrap();
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect::<Vec<u8>>()
+        .try_into()
+        .unwrap()
+}
+
+fn to_compressed_nft_asset(vector: &Value) -> CompressedNftAsset {
+    let metadata = &vector["metadata"];
+
+    let collection = &metadata["collection"];
+    let uses = &metadata["uses"];
+
+    CompressedNftAsset {
+        name: metadata["name"].as_str().unwrap().to_string(),
+        symbol: metadata["symbol"].as_str().unwrap().to_string(),
+        uri: metadata["uri"].as_str().unwrap().to_string(),
+        collection: Some(Collection {
+            verified: collection["verified"].as_bool().unwrap(),
+            key: to_pubkey(&collection["key"]),
+        }),
+        seller_fee_basis_points: metadata["seller_fee_basis_points"].as_u64().unwrap() as u16,
+        primary_sale_happened: metadata["primary_sale_happened"].as_bool().unwrap(),
+        is_mutable: metadata["is_mutable"].as_bool().unwrap(),
+        edition_nonce: metadata["edition_nonce"].as_u64().map(|nonce| nonce as u8),
+        creators: metadata["creators"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|creator| Creator {
+                address: to_pubkey(&creator["address"]),
+                verified: creator["verified"].as_bool().unwrap(),
+                share: creator["share"].as_u64().unwrap() as u8,
+            })
+            .collect(),
+        uses: if uses.is_null() {
+            None
+        } else {
+            Some(Uses {
+                use_method: match uses["use_method"].as_str().unwrap() {
+                    "Burn" => UseMethod::Burn,
+                    "Multiple" => UseMethod::Multiple,
+                    "Single" => UseMethod::Single,
+                    use_method => panic!("Invalid use_method {}", use_method),
+                },
+                remaining: uses["remaining"].as_u64().unwrap(),
+                total: uses["total"].as_u64().unwrap(),
+            })
+        },
+        root: [0; 32],
+        leaf_owner: to_pubkey(&vector["leaf_owner"]),
+        leaf_delegate: to_pubkey(&vector["leaf_delegate"]),
+        index: 0,
+        nonce: vector["nonce"].as_u64().unwrap(),
+        proof_len: 0,
+        last_transfer_slot: None,
+        data_hash: None,
+        creator_hash: None,
+        proof_nodes: None,
+        use_proof_attestation: false,
+        leaf_schema: LeafSchema::V1,
+        leaf_hash: None,
+    }
+}
+
+#[test]
+fn test_golden_vectors_asset_id() {
+    for vector in get_golden_vectors() {
+        // Arrange
+        let tree = to_pubkey(&vector["tree"]);
+        let nonce = vector["nonce"].as_u64().unwrap();
+
+        // Act
+        let asset_id = get_asset_id(&tree, nonce);
+
+        // Assert
+        assert_eq!(asset_id, to_pubkey(&vector["asset_id"]), "{}", vector["description"]);
+    }
+}
+
+#[test]
+fn test_golden_vectors_leaf_hash() {
+    for vector in get_golden_vectors() {
+        // Arrange
+        let asset_id = to_pubkey(&vector["asset_id"]);
+        let compressed_nft_asset = to_compressed_nft_asset(&vector);
+
+        // Act
+        let leaf_hash = get_compressed_nft_leaf_node(&asset_id, &compressed_nft_asset);
+
+        // Assert
+        assert_eq!(leaf_hash, to_hash(&vector["leaf_hash"]), "{}", vector["description"]);
+    }
+}
+
+#[test]
+fn test_golden_vectors_leaf_hash_with_precomputed_hashes() {
+    for vector in get_golden_vectors() {
+        // Arrange
+        let asset_id = to_pubkey(&vector["asset_id"]);
+        let mut compressed_nft_asset = to_compressed_nft_asset(&vector);
+
+        // Use metadata which doesn't match the hashes to ensure they are not recomputed
+        compressed_nft_asset.name = "Ignored".to_string();
+        compressed_nft_asset.creators = vec![];
+        compressed_nft_asset.data_hash = Some(to_hash(&vector["data_hash"]));
+        compressed_nft_asset.creator_hash = Some(to_hash(&vector["creator_hash"]));
+
+        // Act
+        let leaf_hash = get_compressed_nft_leaf_node(&asset_id, &compressed_nft_asset);
+
+        // Assert
+        assert_eq!(leaf_hash, to_hash(&vector["leaf_hash"]), "{}", vector["description"]);
+    }
+}
@@ -0,0 +1,281 @@
+use gpl_nft_voter::state::*;
+use program_test::governance_test::{ RealmCookie, TokenOwnerRecordCookie };
+use program_test::nft_voter_test::*;
+use program_test::tools::assert_gov_err;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use spl_governance::error::GovernanceError;
+mod program_test;
+
+/// Creates a voter with a single NFT of weight 3 and returns the cookies required to update and consume its weight
+async fn setup_voter(
+    nft_voter_test: &mut NftVoterTest,
+    action: &VoterWeightAction
+) -> Result<
+    (
+        RealmCookie,
+        RegistrarCookie,
+        MaxVoterWeightRecordCookie,
+        VoterWeightRecordCookie,
+        TokenOwnerRecordCookie,
+        Vec<NftVoteTicketCookie>,
+    ),
+    TransportError
+> {
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        action
+    ).await?;
+
+    Ok((
+        realm_cookie,
+        registrar_cookie,
+        max_voter_weight_record_cookie,
+        voter_weight_record_cookie,
+        voter_token_owner_record_cookie,
+        nft_action_ticket_cookies,
+    ))
+}
+
+#[tokio::test]
+async fn test_governance_addin_mock_consume_voter_weight() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (
+        realm_cookie,
+        registrar_cookie,
+        _,
+        voter_weight_record_cookie,
+        voter_token_owner_record_cookie,
+        nft_action_ticket_cookies,
+    ) = setup_voter(&mut nft_voter_test, &action).await?;
+
+    let update_voter_weight_record_ix = nft_voter_test.update_voter_weight_record_ix(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    );
+
+    // Act
+    let voter_weight = nft_voter_test.governance_addin_mock.consume_voter_weight(
+        vec![update_voter_weight_record_ix],
+        &realm_cookie,
+        &voter_token_owner_record_cookie,
+        &voter_weight_record_cookie.address,
+        action,
+        &realm_cookie.address
+    ).await?;
+
+    // Assert
+    assert_eq!(voter_weight, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_governance_addin_mock_consume_voter_weight_with_invalid_action_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (
+        realm_cookie,
+        registrar_cookie,
+        _,
+        voter_weight_record_cookie,
+        voter_token_owner_record_cookie,
+        nft_action_ticket_cookies,
+    ) = setup_voter(&mut nft_voter_test, &action).await?;
+
+    let update_voter_weight_record_ix = nft_voter_test.update_voter_weight_record_ix(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    );
+
+    // Act
+    let err = nft_voter_test.governance_addin_mock
+        .consume_voter_weight(
+            vec![update_voter_weight_record_ix],
+            &realm_cookie,
+            &voter_token_owner_record_cookie,
+            &voter_weight_record_cookie.address,
+            VoterWeightAction::CastVote,
+            &realm_cookie.address
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_gov_err(err, GovernanceError::VoterWeightRecordInvalidAction);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_governance_addin_mock_consume_voter_weight_with_expired_record_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (
+        realm_cookie,
+        registrar_cookie,
+        _,
+        mut voter_weight_record_cookie,
+        voter_token_owner_record_cookie,
+        nft_action_ticket_cookies,
+    ) = setup_voter(&mut nft_voter_test, &action).await?;
+
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test.governance_addin_mock
+        .consume_voter_weight(
+            vec![],
+            &realm_cookie,
+            &voter_token_owner_record_cookie,
+            &voter_weight_record_cookie.address,
+            action,
+            &realm_cookie.address
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_gov_err(err, GovernanceError::VoterWeightRecordExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_governance_addin_mock_consume_voter_weight_with_token_owner_record_of_other_voter_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (
+        realm_cookie,
+        registrar_cookie,
+        _,
+        voter_weight_record_cookie,
+        _,
+        nft_action_ticket_cookies,
+    ) = setup_voter(&mut nft_voter_test, &action).await?;
+
+    let voter2_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter2_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter2_cookie
+    ).await?;
+
+    let update_voter_weight_record_ix = nft_voter_test.update_voter_weight_record_ix(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    );
+
+    // Act
+    let err = nft_voter_test.governance_addin_mock
+        .consume_voter_weight(
+            vec![update_voter_weight_record_ix],
+            &realm_cookie,
+            &voter2_token_owner_record_cookie,
+            &voter_weight_record_cookie.address,
+            action,
+            &realm_cookie.address
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_gov_err(err, GovernanceError::InvalidVoterWeightRecordForTokenOwner);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_governance_addin_mock_consume_max_voter_weight() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let (realm_cookie, _, max_voter_weight_record_cookie, _, _, _) = setup_voter(
+        &mut nft_voter_test,
+        &action
+    ).await?;
+
+    // Act
+    let max_voter_weight = nft_voter_test.governance_addin_mock.consume_max_voter_weight(
+        vec![],
+        &realm_cookie,
+        &max_voter_weight_record_cookie.address
+    ).await?;
+
+    // Assert
+    assert_eq!(max_voter_weight, 33);
+
+    Ok(())
+}
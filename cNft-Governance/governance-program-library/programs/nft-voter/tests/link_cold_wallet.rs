@@ -0,0 +1,292 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::{ assert_nft_voter_err, new_ed25519_signature_ix };
+mod program_test;
+
+#[tokio::test]
+async fn test_link_cold_wallet() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    // Act
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &hot_wallet_cookie,
+        expires_at
+    ).await?;
+
+    // Assert
+    let cold_wallet_link = nft_voter_test.get_cold_wallet_link(&cold_wallet_link).await;
+
+    assert_eq!(cold_wallet_link, ColdWalletLink {
+        registrar: registrar_cookie.address,
+        cold_wallet: cold_wallet_cookie.address,
+        hot_wallet: hot_wallet_cookie.address,
+        expires_at,
+        revoked: false,
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_cold_wallet_with_signature_of_other_wallet_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let other_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let message = get_cold_wallet_link_message(
+        &registrar_cookie.address,
+        &hot_wallet_cookie.address,
+        expires_at
+    );
+
+    // Act
+    let err = nft_voter_test
+        .link_cold_wallet_using_ix(
+            &registrar_cookie,
+            &cold_wallet_cookie,
+            &hot_wallet_cookie,
+            expires_at,
+            |i| {
+                *i = new_ed25519_signature_ix(&other_wallet_cookie.signer, &message);
+            }
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::Ed25519SignatureNotFound);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_cold_wallet_with_expiry_beyond_max_duration_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at =
+        nft_voter_test.bench.get_clock().await.unix_timestamp + MAX_COLD_WALLET_LINK_DURATION + 1;
+
+    // Act
+    let err = nft_voter_test
+        .link_cold_wallet(&registrar_cookie, &cold_wallet_cookie, &hot_wallet_cookie, expires_at).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidColdWalletLinkExpiry);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_cold_wallet_with_replayed_signature_after_revoke_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &hot_wallet_cookie,
+        expires_at
+    ).await?;
+
+    nft_voter_test.revoke_cold_wallet_link(&cold_wallet_link, &cold_wallet_cookie).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .link_cold_wallet(&registrar_cookie, &cold_wallet_cookie, &hot_wallet_cookie, expires_at).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidColdWalletLinkExpiry);
+
+    let cold_wallet_link = nft_voter_test.get_cold_wallet_link(&cold_wallet_link).await;
+    assert!(cold_wallet_link.revoked);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_expired_delegation() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &hot_wallet_cookie,
+        expires_at
+    ).await?;
+
+    let cold_wallet_link_lamports = nft_voter_test.bench
+        .get_account(&cold_wallet_link).await
+        .unwrap().lamports;
+    let hot_wallet_lamports = nft_voter_test.bench
+        .get_account(&hot_wallet_cookie.address).await
+        .unwrap().lamports;
+
+    nft_voter_test.bench.advance_clock_by_seconds(3600).await;
+
+    // Act
+    nft_voter_test.close_expired_delegation(&cold_wallet_link, &hot_wallet_cookie.address).await?;
+
+    // Assert
+    assert_eq!(None, nft_voter_test.bench.get_account(&cold_wallet_link).await);
+
+    let hot_wallet = nft_voter_test.bench.get_account(&hot_wallet_cookie.address).await.unwrap();
+    assert_eq!(hot_wallet.lamports, hot_wallet_lamports + cold_wallet_link_lamports);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_expired_delegation_before_expiry_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &hot_wallet_cookie,
+        expires_at
+    ).await?;
+
+    // Revoked links are kept until they expire
+    nft_voter_test.revoke_cold_wallet_link(&cold_wallet_link, &cold_wallet_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .close_expired_delegation(&cold_wallet_link, &hot_wallet_cookie.address).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ColdWalletLinkNotExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_expired_delegation_with_other_hot_wallet_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let other_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &hot_wallet_cookie,
+        expires_at
+    ).await?;
+
+    nft_voter_test.bench.advance_clock_by_seconds(3600).await;
+
+    // Act
+    let err = nft_voter_test
+        .close_expired_delegation(&cold_wallet_link, &other_wallet_cookie.address).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidColdWalletLink);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_cold_wallet_with_replayed_signature_after_close_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let hot_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &hot_wallet_cookie,
+        expires_at
+    ).await?;
+
+    nft_voter_test.bench.advance_clock_by_seconds(3600).await;
+    nft_voter_test.close_expired_delegation(&cold_wallet_link, &hot_wallet_cookie.address).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .link_cold_wallet(&registrar_cookie, &cold_wallet_cookie, &hot_wallet_cookie, expires_at).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidColdWalletLinkExpiry);
+
+    Ok(())
+}
@@ -0,0 +1,331 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_map_collection_alias() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let previous_collection = Pubkey::new_unique();
+
+    // Act
+    let collection_alias = nft_voter_test.map_collection_alias(
+        &registrar_cookie,
+        &previous_collection,
+        &nft_collection_cookie.mint,
+        5000
+    ).await?;
+
+    // Assert
+    let collection_alias_account = nft_voter_test.bench.get_anchor_account::<CollectionAlias>(
+        collection_alias
+    ).await;
+
+    assert_eq!(collection_alias_account, CollectionAlias {
+        registrar: registrar_cookie.address,
+        collection: previous_collection,
+        target_collection: nft_collection_cookie.mint,
+        weight_bps: 5000,
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_map_collection_alias_with_configured_collection_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie1,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie2,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .map_collection_alias(
+            &registrar_cookie,
+            &nft_collection_cookie1.mint,
+            &nft_collection_cookie2.mint,
+            5000
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCollectionAlias);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_map_collection_alias_with_not_configured_target_collection_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .map_collection_alias(&registrar_cookie, &Pubkey::new_unique(), &Pubkey::new_unique(), 5000).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CollectionNotFound);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_map_collection_alias_with_invalid_weight_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .map_collection_alias(
+            &registrar_cookie,
+            &Pubkey::new_unique(),
+            &nft_collection_cookie.mint,
+            MAX_BASIS_POINTS + 1
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCollectionAliasWeight);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_collection_alias() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let previous_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(
+        Some(10)
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 10,
+            size: 20,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_collection_aliases: true,
+        ..Default::default()
+    }).await?;
+
+    nft_voter_test.map_collection_alias(
+        &registrar_cookie,
+        &previous_collection_cookie.mint,
+        &nft_collection_cookie.mint,
+        5000
+    ).await?;
+
+    registrar_cookie.account = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &previous_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action
+    ).await?;
+
+    // Assert
+    let cnft_action_ticket = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket.weight, 5);
+    assert_eq!(cnft_action_ticket.collection, nft_collection_cookie.mint);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_collection_alias_of_other_collection_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let previous_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(
+        Some(10)
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 10,
+            size: 20,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_collection_aliases: true,
+        ..Default::default()
+    }).await?;
+
+    // The alias of another collection is supplied for the asset of the previous collection
+    let other_collection_alias = nft_voter_test.map_collection_alias(
+        &registrar_cookie,
+        &Pubkey::new_unique(),
+        &nft_collection_cookie.mint,
+        MAX_BASIS_POINTS
+    ).await?;
+
+    registrar_cookie.account = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &previous_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let collection_alias = get_collection_alias_address(
+        &registrar_cookie.address,
+        &previous_collection_cookie.mint
+    );
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket_using_ix(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action,
+            |i| {
+                for account in i.accounts.iter_mut().filter(|a| a.pubkey == collection_alias) {
+                    account.pubkey = other_collection_alias;
+                }
+            },
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidCollectionAlias);
+
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use borsh::BorshSerialize;
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::{
+    nft_voter_test::*,
+    tools::{ assert_ix_err, assert_nft_voter_err },
+};
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_migrate_registrar() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_legacy_registrar(&realm_cookie).await;
+
+    let registrar_data = nft_voter_test.bench.get_account_data(registrar_cookie.address).await;
+
+    // Act
+    nft_voter_test.migrate_registrar(&registrar_cookie).await?;
+
+    // Assert
+    let registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &registrar_cookie.extension_address
+    ).await;
+
+    let clock = nft_voter_test.bench.get_clock().await;
+
+    assert_eq!(registrar_extension, RegistrarExtension {
+        legacy_nft_vote_records_cutoff: clock.unix_timestamp,
+        ..registrar_cookie.extension
+    });
+
+    // The Registrar itself must be left untouched
+    assert_eq!(
+        nft_voter_test.bench.get_account_data(registrar_cookie.address).await,
+        registrar_data
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_registrar_with_existing_extension_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test.migrate_registrar(&registrar_cookie).await.err().unwrap();
+
+    // Assert
+
+    // The extension is created by create_registrar and InstructionError::Custom(0) is returned for AccountInUse
+    assert_ix_err(err, InstructionError::Custom(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_on_proposal_created_before_migration() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_legacy_registrar(&realm_cookie).await;
+
+    // The Proposal could be voted on by the previous version of the program
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    nft_voter_test.migrate_registrar(&registrar_cookie).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    // Act
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            legacy_nft_vote_records: true,
+            ..Default::default()
+        })
+    ).await?;
+
+    // Assert
+    let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(nft_vote_record_cookies[0].account, nft_vote_record);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_on_proposal_created_before_migration_with_legacy_nft_vote_record_error()
+-> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_legacy_registrar(&realm_cookie).await;
+
+    // The Proposal could be voted on by the previous version of the program
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    nft_voter_test.migrate_registrar(&registrar_cookie).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    // The NFT voted on the Proposal before the upgrade
+    let nft_mint = nft_action_ticket_cookies[0].nft_mint;
+    let legacy_nft_vote_record = NftVoteRecord {
+        version: NftVoteRecordVersion::V1,
+        ..NftVoteRecord::new(
+            proposal_cookie.address,
+            nft_mint,
+            voter_cookie.address,
+            NftVoteKind::Unknown
+        )
+    };
+
+    nft_voter_test.bench.set_account_data(
+        &get_legacy_nft_vote_record_address(&proposal_cookie.address, &nft_mint),
+        &gpl_nft_voter::id(),
+        legacy_nft_vote_record.try_to_vec().unwrap()
+    ).await;
+
+    // Act
+    let err = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            legacy_nft_vote_records: true,
+            ..Default::default()
+        })
+    ).await.err().unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::NftAlreadyVoted);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_on_proposal_created_before_migration_with_invalid_legacy_nft_vote_record_error()
+-> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+
+    let registrar_cookie = nft_voter_test.with_legacy_registrar(&realm_cookie).await;
+
+    // The Proposal could be voted on by the previous version of the program
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    nft_voter_test.migrate_registrar(&registrar_cookie).await?;
+
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    // Act
+    // The empty NftVoteRecord with the current PDA is supplied in place of the legacy NftVoteRecord
+    let err = nft_voter_test.cast_nft_vote_using_ix(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            legacy_nft_vote_records: true,
+            ..Default::default()
+        }),
+        |i| {
+            let nft_vote_record_idx = i.accounts.len() - 2;
+            i.accounts[nft_vote_record_idx + 1].pubkey = i.accounts[nft_vote_record_idx].pubkey;
+        }
+    ).await.err().unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidLegacyNftVoteRecord);
+
+    Ok(())
+}
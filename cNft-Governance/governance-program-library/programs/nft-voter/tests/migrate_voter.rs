@@ -0,0 +1,239 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use program_test::tools::assert_nft_voter_err;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_migrate_voter() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_voter_migration: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let new_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let cold_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 3600;
+
+    let cold_wallet_link = nft_voter_test.link_cold_wallet(
+        &registrar_cookie,
+        &cold_wallet_cookie,
+        &voter_cookie,
+        expires_at
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // The vote isn't cast in spl-gov and the NftVoteRecord can be moved while the Proposal is in Voting state
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            cast_spl_gov_vote: false,
+            ..Default::default()
+        })
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let clock = nft_voter_test.bench.get_clock().await;
+
+    // Act
+    let voter_migration = nft_voter_test.migrate_voter(
+        &registrar_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &new_wallet_cookie,
+        &[cold_wallet_link],
+        &nft_vote_record_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    // Assert
+    let voter_migration = nft_voter_test.get_voter_migration(&voter_migration).await;
+
+    assert_eq!(voter_migration, VoterMigration {
+        registrar: registrar_cookie.address,
+        previous_wallet: voter_cookie.address,
+        new_wallet: new_wallet_cookie.address,
+        migrated_at_slot: clock.slot,
+    });
+
+    let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(nft_vote_record.governing_token_owner, new_wallet_cookie.address);
+
+    let cold_wallet_link = nft_voter_test.get_cold_wallet_link(&cold_wallet_link).await;
+
+    assert_eq!(cold_wallet_link.hot_wallet, new_wallet_cookie.address);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_voter_with_vote_not_withdrawn_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_voter_migration: true,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let new_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .migrate_voter(
+            &registrar_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &new_wallet_cookie,
+            &[],
+            &nft_vote_record_cookies.iter().collect::<Vec<_>>()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoteRecordMustBeWithdrawn);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_voter_when_not_allowed_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let new_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+
+    // Act
+    let err = nft_voter_test
+        .migrate_voter(
+            &registrar_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &new_wallet_cookie,
+            &[],
+            &[]
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterMigrationNotAllowed);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::ERROR_CODE_OFFSET;
+use gpl_nft_voter::error::NftVoterError;
+use solana_program::instruction::{ Instruction, InstructionError };
+use solana_program::pubkey::Pubkey;
+use solana_program_test::BanksClientError;
+use solana_sdk::transaction::TransactionError;
+
+use crate::program_test::program_test_bench::ProgramTestBench;
+
+/// Typed error the instruction must fail with when an account is substituted
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub enum SubstitutionError {
+    NftVoter(NftVoterError),
+    Anchor(anchor_lang::error::ErrorCode),
+}
+
+impl SubstitutionError {
+    /// Returns the custom program error code of the error
+    #[allow(dead_code)]
+    pub fn code(&self) -> u32 {
+        match self {
+            SubstitutionError::NftVoter(error) => (*error as u32) + ERROR_CODE_OFFSET,
+            SubstitutionError::Anchor(error) => *error as u32,
+        }
+    }
+}
+
+/// Substitution of an account of a valid instruction with a plausible wrong account
+/// ex. the Registrar of another realm, VoterWeightRecord of another realm, a different tree
+/// or a copy of the record owned by an attacker program
+#[allow(dead_code)]
+pub struct AccountSubstitution {
+    /// Name of the substituted account used in the failure messages
+    pub name: &'static str,
+
+    /// The account of the valid instruction; all its occurrences are substituted
+    pub account: Pubkey,
+
+    /// The wrong account
+    pub substitute: Pubkey,
+
+    /// The error the instruction must fail with
+    pub expected_error: SubstitutionError,
+}
+
+impl AccountSubstitution {
+    #[allow(dead_code)]
+    pub fn new(
+        name: &'static str,
+        account: Pubkey,
+        substitute: Pubkey,
+        expected_error: SubstitutionError
+    ) -> Self {
+        Self {
+            name,
+            account,
+            substitute,
+            expected_error,
+        }
+    }
+
+    /// Returns the instruction override substituting the account
+    #[allow(dead_code)]
+    pub fn apply(&self) -> impl Fn(&mut Instruction) + '_ {
+        move |instruction: &mut Instruction| {
+            for account_meta in instruction.accounts.iter_mut() {
+                if account_meta.pubkey == self.account {
+                    account_meta.pubkey = self.substitute;
+                }
+            }
+        }
+    }
+
+    /// Asserts the instruction with the substituted account failed with the expected error
+    #[allow(dead_code)]
+    pub fn assert_err(&self, result: Result<impl std::fmt::Debug, BanksClientError>) {
+        let banks_client_error = match result {
+            Ok(_) => panic!("Substituted {} was accepted", self.name),
+            Err(banks_client_error) => banks_client_error,
+        };
+
+        match banks_client_error.unwrap() {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) =>
+                assert_eq!(
+                    code,
+                    self.expected_error.code(),
+                    "Substituted {} failed with {} instead of {:?}",
+                    self.name,
+                    code,
+                    self.expected_error
+                ),
+            tx_error =>
+                panic!(
+                    "Substituted {} failed with {:?} instead of {:?}",
+                    self.name,
+                    tx_error,
+                    self.expected_error
+                ),
+        }
+    }
+}
+
+/// Returns the program which plays the attacker owning copies of the program accounts
+#[allow(dead_code)]
+pub fn attacker_program_id() -> Pubkey {
+    Pubkey::new_from_array([7; 32])
+}
+
+/// Creates a copy of the given account with the same data owned by the attacker program
+#[allow(dead_code)]
+pub async fn with_attacker_owned_copy(bench: &ProgramTestBench, address: &Pubkey) -> Pubkey {
+    let account = bench.get_account(address).await.unwrap();
+
+    bench.with_account_data(&attacker_program_id(), account.data).await
+}
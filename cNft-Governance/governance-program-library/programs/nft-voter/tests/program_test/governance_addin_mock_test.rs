@@ -0,0 +1,127 @@
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use anchor_lang::prelude::Pubkey;
+use gpl_governance_addin_mock::VoterWeightAction as MockVoterWeightAction;
+use gpl_nft_voter::state::VoterWeightAction;
+use solana_program_test::{ BanksClientError, ProgramTest };
+use solana_sdk::instruction::Instruction;
+use spl_governance::state::realm_config::get_realm_config_address;
+
+use crate::program_test::governance_test::{ GovernanceTest, RealmCookie, TokenOwnerRecordCookie };
+use crate::program_test::program_test_bench::ProgramTestBench;
+
+/// Test bench of the governance addin mock which consumes the plugin records the same way spl-governance does
+/// The plugin instructions must be passed as pre_ixs because the records are only valid in the slot they were updated in
+pub struct GovernanceAddinMockTest {
+    pub bench: Arc<ProgramTestBench>,
+    pub program_id: Pubkey,
+}
+
+impl GovernanceAddinMockTest {
+    #[allow(dead_code)]
+    pub fn add_program(program_test: &mut ProgramTest) {
+        program_test.add_program("gpl_governance_addin_mock", gpl_governance_addin_mock::id(), None);
+    }
+
+    #[allow(dead_code)]
+    pub fn new(bench: Arc<ProgramTestBench>) -> Self {
+        GovernanceAddinMockTest {
+            bench,
+            program_id: gpl_governance_addin_mock::id(),
+        }
+    }
+
+    /// Executes pre_ixs followed by consume_voter_weight and returns the voter weight accepted by the mock
+    #[allow(dead_code)]
+    pub async fn consume_voter_weight(
+        &self,
+        pre_ixs: Vec<Instruction>,
+        realm_cookie: &RealmCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+        voter_weight_record: &Pubkey,
+        weight_action: VoterWeightAction,
+        weight_action_target: &Pubkey
+    ) -> Result<u64, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_governance_addin_mock::instruction::ConsumeVoterWeight {
+                weight_action: to_mock_voter_weight_action(weight_action),
+                weight_action_target: *weight_action_target,
+            })
+        );
+
+        let accounts = gpl_governance_addin_mock::accounts::ConsumeVoterWeight {
+            realm: realm_cookie.address,
+            realm_config: get_realm_config_address(
+                &GovernanceTest::program_id(),
+                &realm_cookie.address
+            ),
+            token_owner_record: token_owner_record_cookie.address,
+            voter_weight_record: *voter_weight_record,
+        };
+
+        let consume_voter_weight_ix = Instruction {
+            program_id: self.program_id,
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.process_consume_ix(pre_ixs, consume_voter_weight_ix).await
+    }
+
+    /// Executes pre_ixs followed by consume_max_voter_weight for the Realm community mint
+    /// and returns the max voter weight accepted by the mock
+    #[allow(dead_code)]
+    pub async fn consume_max_voter_weight(
+        &self,
+        pre_ixs: Vec<Instruction>,
+        realm_cookie: &RealmCookie,
+        max_voter_weight_record: &Pubkey
+    ) -> Result<u64, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &gpl_governance_addin_mock::instruction::ConsumeMaxVoterWeight {}
+        );
+
+        let accounts = gpl_governance_addin_mock::accounts::ConsumeMaxVoterWeight {
+            realm: realm_cookie.address,
+            realm_config: get_realm_config_address(
+                &GovernanceTest::program_id(),
+                &realm_cookie.address
+            ),
+            governing_token_mint: realm_cookie.account.community_mint,
+            max_voter_weight_record: *max_voter_weight_record,
+        };
+
+        let consume_max_voter_weight_ix = Instruction {
+            program_id: self.program_id,
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.process_consume_ix(pre_ixs, consume_max_voter_weight_ix).await
+    }
+
+    async fn process_consume_ix(
+        &self,
+        mut pre_ixs: Vec<Instruction>,
+        consume_ix: Instruction
+    ) -> Result<u64, BanksClientError> {
+        pre_ixs.push(consume_ix);
+
+        let return_data = self.bench
+            .process_transaction_with_return_data(&pre_ixs, None).await?
+            .expect("The mock must return the consumed weight");
+
+        Ok(u64::from_le_bytes(return_data.as_slice().try_into().unwrap()))
+    }
+}
+
+fn to_mock_voter_weight_action(weight_action: VoterWeightAction) -> MockVoterWeightAction {
+    match weight_action {
+        VoterWeightAction::CastVote => MockVoterWeightAction::CastVote,
+        VoterWeightAction::CommentProposal => MockVoterWeightAction::CommentProposal,
+        VoterWeightAction::CreateGovernance => MockVoterWeightAction::CreateGovernance,
+        VoterWeightAction::CreateProposal => MockVoterWeightAction::CreateProposal,
+        VoterWeightAction::SignOffProposal => MockVoterWeightAction::SignOffProposal,
+    }
+}
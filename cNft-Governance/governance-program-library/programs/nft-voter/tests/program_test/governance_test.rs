@@ -1,12 +1,14 @@
 use std::{str::FromStr, sync::Arc};
 
 use anchor_lang::prelude::Pubkey;
+use borsh::BorshSerialize;
 use solana_program_test::ProgramTest;
 use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
 use spl_governance::{
     instruction::{
         create_governance, create_proposal, create_realm, create_token_owner_record,
-        deposit_governing_tokens, relinquish_vote, set_governance_delegate, sign_off_proposal,
+        deposit_governing_tokens, finalize_vote, relinquish_vote, set_governance_delegate,
+        sign_off_proposal,
     },
     state::{
         enums::{
@@ -20,6 +22,7 @@ use spl_governance::{
         token_owner_record::{
             get_token_owner_record_address, TokenOwnerRecordV2, TOKEN_OWNER_RECORD_LAYOUT_VERSION,
         },
+        vote_record::{get_vote_record_address, VoteRecordV2},
     },
 };
 
@@ -87,6 +90,20 @@ impl GovernanceTest {
 
     #[allow(dead_code)]
     pub async fn with_realm(&mut self) -> Result<RealmCookie, TransportError> {
+        self.with_realm_using_addins(
+            self.community_voter_weight_addin,
+            self.max_community_voter_weight_addin,
+        )
+        .await
+    }
+
+    /// Creates Realm with the given community voter weight and max voter weight addins
+    #[allow(dead_code)]
+    pub async fn with_realm_using_addins(
+        &mut self,
+        voter_weight_addin: Option<Pubkey>,
+        max_voter_weight_addin: Option<Pubkey>,
+    ) -> Result<RealmCookie, TransportError> {
         let realm_authority = Keypair::new();
 
         let community_mint_cookie = self.bench.with_mint().await?;
@@ -101,8 +118,8 @@ impl GovernanceTest {
         let realm_key = get_realm_address(&self.program_id, &realm_name);
 
         let community_token_config_args = GoverningTokenConfigAccountArgs {
-            voter_weight_addin: self.community_voter_weight_addin,
-            max_voter_weight_addin: self.max_community_voter_weight_addin,
+            voter_weight_addin,
+            max_voter_weight_addin,
             token_type: GoverningTokenType::default(),
         };
 
@@ -151,6 +168,26 @@ impl GovernanceTest {
         })
     }
 
+    /// Creates Realm with spl-token-2022 community mint
+    /// spl-gov doesn't support Token-2022 mints and hence the community mint of a regular Realm is replaced
+    /// Note: community_mint_cookie of the returned Realm is the replaced spl-token mint
+    #[allow(dead_code)]
+    pub async fn with_token_2022_realm(&mut self) -> Result<RealmCookie, TransportError> {
+        let mut realm_cookie = self.with_realm().await?;
+
+        realm_cookie.account.community_mint = self.bench.with_token_2022_mint().await;
+
+        self.bench
+            .set_account_data(
+                &realm_cookie.address,
+                &self.program_id,
+                realm_cookie.account.try_to_vec().unwrap(),
+            )
+            .await;
+
+        Ok(realm_cookie)
+    }
+
     #[allow(dead_code)]
     pub async fn with_proposal(
         &mut self,
@@ -211,7 +248,7 @@ impl GovernanceTest {
         let create_governance_ix = create_governance(
             &self.program_id,
             &realm_cookie.address,
-            Some(&token_account_cookie.address),
+            &token_account_cookie.address,
             &proposal_owner_record_key,
             &self.bench.payer.pubkey(),
             &realm_cookie.realm_authority.pubkey(),
@@ -317,6 +354,66 @@ impl GovernanceTest {
         })
     }
 
+    /// Creates another Proposal for the Governance of the given Proposal
+    #[allow(dead_code)]
+    pub async fn with_governance_proposal(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        proposal_cookie: &ProposalCookie,
+    ) -> Result<ProposalCookie, TransportError> {
+        let token_owner = self.bench.payer.pubkey();
+        let governance_key = proposal_cookie.account.governance;
+        let proposal_owner_record_key = proposal_cookie.account.token_owner_record;
+        let proposal_governing_token_mint = realm_cookie.account.community_mint;
+        let proposal_seed = Pubkey::new_unique();
+
+        let proposal_key = get_proposal_address(
+            &self.program_id,
+            &governance_key,
+            &proposal_governing_token_mint,
+            &proposal_seed,
+        );
+
+        let create_proposal_ix = create_proposal(
+            &self.program_id,
+            &governance_key,
+            &proposal_owner_record_key,
+            &token_owner,
+            &self.bench.payer.pubkey(),
+            None,
+            &realm_cookie.address,
+            String::from("Proposal #2"),
+            String::from("Proposal #2 link"),
+            &proposal_governing_token_mint,
+            spl_governance::state::proposal::VoteType::SingleChoice,
+            vec!["Yes".to_string()],
+            true,
+            &proposal_seed,
+        );
+
+        let sign_off_proposal_ix = sign_off_proposal(
+            &self.program_id,
+            &realm_cookie.address,
+            &governance_key,
+            &proposal_key,
+            &token_owner,
+            Some(&proposal_owner_record_key),
+        );
+
+        self.bench
+            .process_transaction(&[create_proposal_ix, sign_off_proposal_ix], None)
+            .await?;
+
+        let mut account = proposal_cookie.account.clone();
+        account.name = String::from("Proposal #2");
+        account.description_link = String::from("Proposal #2 link");
+
+        Ok(ProposalCookie {
+            address: proposal_key,
+            account,
+        })
+    }
+
     #[allow(dead_code)]
     pub async fn with_token_owner_record(
         &mut self,
@@ -352,8 +449,9 @@ impl GovernanceTest {
             outstanding_proposal_count: 0,
             reserved: [0; 6],
             governance_delegate: None,
-            reserved_v2: [0; 128],
+            reserved_v2: [0; 124],
             version: TOKEN_OWNER_RECORD_LAYOUT_VERSION,
+            locks: vec![],
         };
 
         Ok(TokenOwnerRecordCookie {
@@ -387,6 +485,31 @@ impl GovernanceTest {
         Ok(())
     }
 
+    /// Finalizes the vote of the Proposal once its voting time has ended
+    #[allow(dead_code)]
+    pub async fn finalize_vote(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        proposal_cookie: &ProposalCookie,
+        max_voter_weight_record: Option<Pubkey>,
+    ) -> Result<(), TransportError> {
+        let finalize_vote_ix = finalize_vote(
+            &self.program_id,
+            &realm_cookie.address,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_cookie.account.token_owner_record,
+            &proposal_cookie.account.governing_token_mint,
+            max_voter_weight_record,
+        );
+
+        self.bench
+            .process_transaction(&[finalize_vote_ix], None)
+            .await?;
+
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub async fn set_governance_delegate(
         &mut self,
@@ -420,6 +543,23 @@ impl GovernanceTest {
             .await
     }
 
+    #[allow(dead_code)]
+    pub async fn get_vote_record(
+        &mut self,
+        proposal_cookie: &ProposalCookie,
+        token_owner_record_cookie: &TokenOwnerRecordCookie,
+    ) -> VoteRecordV2 {
+        let vote_record_key = get_vote_record_address(
+            &self.program_id,
+            &proposal_cookie.address,
+            &token_owner_record_cookie.address,
+        );
+
+        self.bench
+            .get_borsh_account::<VoteRecordV2>(&vote_record_key)
+            .await
+    }
+
     #[allow(dead_code)]
     pub async fn get_token_owner_record(
         &mut self,
@@ -1,62 +1,42 @@
-use std::{ str::FromStr, sync::Arc, convert::TryFrom };
+use std::{ str::FromStr, sync::Arc, convert::{ TryFrom, TryInto }, rc::Rc };
 use crate::program_test::program_test_bench::ProgramTestBench;
-use crate::program_test::tools::clone_keypair;
 use anchor_lang::err;
 use anchor_lang::error::Error;
 use anchor_lang::prelude::Pubkey;
-use bytemuck::try_from_bytes;
+use borsh::{ BorshDeserialize, BorshSerialize };
+use bytemuck::try_from_bytes_mut;
 use gpl_nft_voter::state::CompressedNftAsset as LeafVerificationCookie;
-use mpl_bubblegum::state::metaplex_adapter::MetadataArgs;
-use mpl_bubblegum::state::TreeConfig;
-use mpl_bubblegum::utils::get_asset_id;
-use solana_program::instruction::{ AccountMeta, Instruction };
+use gpl_nft_voter::tools::merkle_tree::MPL_ACCOUNT_COMPRESSION_ID;
+use gpl_nft_voter::tools::tree_shapes::{ get_concurrent_merkle_tree_size, is_supported_tree_shape };
+use mpl_bubblegum::types::MetadataArgs;
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::instructions::CreateTreeConfigBuilder;
+use cnft_verification::{ get_asset_id, get_leaf_node, hash_creators, hash_metadata };
+use solana_program::instruction::AccountMeta;
 use solana_program::{ msg, system_instruction, system_program };
 use solana_program_test::ProgramTest;
 use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
-use spl_account_compression::state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1;
+use spl_account_compression::state::{
+    ConcurrentMerkleTreeHeader,
+    CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
+};
 use spl_account_compression::{ AccountCompressionError, ConcurrentMerkleTree };
 use spl_merkle_tree_reference::{ MerkleTree, Node };
-use gpl_nft_voter::state::{ Collection, Creator };
-use std::mem::size_of;
+use gpl_nft_voter::state::{ Collection, Creator, LeafSchema, Uses };
 
+/// Returns the size of the ConcurrentMerkleTree of the given shape without the header and the canopy
+/// The supported shapes are the program TREE_SHAPES
 pub fn merkle_tree_get_size(max_depth: usize, max_buffer_size: usize) -> Result<usize, Error> {
-    // Note: max_buffer_size MUST be a power of 2
-    match (max_depth, max_buffer_size) {
-        (3, 8) => Ok(size_of::<ConcurrentMerkleTree<3, 8>>()),
-        (5, 8) => Ok(size_of::<ConcurrentMerkleTree<5, 8>>()),
-        (14, 64) => Ok(size_of::<ConcurrentMerkleTree<14, 64>>()),
-        (14, 256) => Ok(size_of::<ConcurrentMerkleTree<14, 256>>()),
-        (14, 1024) => Ok(size_of::<ConcurrentMerkleTree<14, 1024>>()),
-        (14, 2048) => Ok(size_of::<ConcurrentMerkleTree<14, 2048>>()),
-        (15, 64) => Ok(size_of::<ConcurrentMerkleTree<15, 64>>()),
-        (16, 64) => Ok(size_of::<ConcurrentMerkleTree<16, 64>>()),
-        (17, 64) => Ok(size_of::<ConcurrentMerkleTree<17, 64>>()),
-        (18, 64) => Ok(size_of::<ConcurrentMerkleTree<18, 64>>()),
-        (19, 64) => Ok(size_of::<ConcurrentMerkleTree<19, 64>>()),
-        (20, 64) => Ok(size_of::<ConcurrentMerkleTree<20, 64>>()),
-        (20, 256) => Ok(size_of::<ConcurrentMerkleTree<20, 256>>()),
-        (20, 1024) => Ok(size_of::<ConcurrentMerkleTree<20, 1024>>()),
-        (20, 2048) => Ok(size_of::<ConcurrentMerkleTree<20, 2048>>()),
-        (24, 64) => Ok(size_of::<ConcurrentMerkleTree<24, 64>>()),
-        (24, 256) => Ok(size_of::<ConcurrentMerkleTree<24, 256>>()),
-        (24, 512) => Ok(size_of::<ConcurrentMerkleTree<24, 512>>()),
-        (24, 1024) => Ok(size_of::<ConcurrentMerkleTree<24, 1024>>()),
-        (24, 2048) => Ok(size_of::<ConcurrentMerkleTree<24, 2048>>()),
-        (26, 512) => Ok(size_of::<ConcurrentMerkleTree<26, 512>>()),
-        (26, 1024) => Ok(size_of::<ConcurrentMerkleTree<26, 1024>>()),
-        (26, 2048) => Ok(size_of::<ConcurrentMerkleTree<26, 2048>>()),
-        (30, 512) => Ok(size_of::<ConcurrentMerkleTree<30, 512>>()),
-        (30, 1024) => Ok(size_of::<ConcurrentMerkleTree<30, 1024>>()),
-        (30, 2048) => Ok(size_of::<ConcurrentMerkleTree<30, 2048>>()),
-        _ => {
-            msg!(
-                "Failed to get size of max depth {} and max buffer size {}",
-                max_depth,
-                max_buffer_size
-            );
-            err!(AccountCompressionError::ConcurrentMerkleTreeConstantsError)
-        }
+    if !is_supported_tree_shape(max_depth as u32, max_buffer_size as u32) {
+        msg!(
+            "Failed to get size of max depth {} and max buffer size {}",
+            max_depth,
+            max_buffer_size
+        );
+        return err!(AccountCompressionError::ConcurrentMerkleTreeConstantsError);
     }
+
+    Ok(get_concurrent_merkle_tree_size(max_depth, max_buffer_size))
 }
 
 pub struct MerkleTreeArgs {
@@ -68,8 +48,8 @@ pub struct MerkleTreeArgs {
 pub struct MerkleTreeCookie {
     pub address: Pubkey,
     pub tree_authority: Pubkey,
-    pub tree_delegate: Keypair,
-    pub tree_creator: Keypair,
+    pub tree_delegate: Rc<Keypair>,
+    pub tree_creator: Rc<Keypair>,
     pub canopy_depth: u32,
     pub proof_tree: MerkleTree,
     pub num_minted: u64,
@@ -149,8 +129,9 @@ impl MerkleTreeTest {
     ) -> Result<MerkleTreeCookie, TransportError> {
         let merkle_tree = Keypair::new();
         let tree_authority = self.get_tree_authority_address(&merkle_tree.pubkey());
-        let tree_creator = clone_keypair(&self.bench.payer); //payer or random???
-        let tree_delegate = clone_keypair(&tree_creator);
+        // The tree creator is also the tree delegate of new trees
+        let tree_creator = self.bench.signers.next("tree-creator");
+        let tree_delegate = tree_creator.clone();
         let payer = &self.bench.payer;
         let args = args.unwrap_or_default();
 
@@ -161,33 +142,26 @@ impl MerkleTreeTest {
             &payer
         ).await?;
 
-        let accounts = mpl_bubblegum::accounts::CreateTree {
-            tree_authority: tree_authority,
-            payer: payer.pubkey(),
-            tree_creator: tree_creator.pubkey(),
-            log_wrapper: spl_noop::id(),
-            system_program: system_program::id(),
-            compression_program: spl_account_compression::id(),
-            merkle_tree: merkle_tree.pubkey(),
-        };
-
-        let data = anchor_lang::InstructionData::data(
-            &(mpl_bubblegum::instruction::CreateTree {
-                max_depth: args.max_depth,
-                max_buffer_size: args.max_buffer_size,
-                public: args.public,
-            })
-        );
-
-        let create_merkle_tree_ix = Instruction {
-            program_id: self.program_id,
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
-            data,
-        };
+        let mut create_merkle_tree_builder = CreateTreeConfigBuilder::new();
+
+        create_merkle_tree_builder
+            .tree_config(tree_authority)
+            .merkle_tree(merkle_tree.pubkey())
+            .payer(payer.pubkey())
+            .tree_creator(tree_creator.pubkey())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .system_program(system_program::id())
+            .max_depth(args.max_depth)
+            .max_buffer_size(args.max_buffer_size);
+
+        if let Some(public) = args.public {
+            create_merkle_tree_builder.public(public);
+        }
 
-        // let signers = &[payer];
+        let create_merkle_tree_ix = create_merkle_tree_builder.instruction();
 
-        self.bench.process_transaction(&[create_merkle_tree_ix], None).await?;
+        self.bench.process_transaction(&[create_merkle_tree_ix], Some(&[&*tree_creator])).await?;
 
         let proof_tree = MerkleTree::new(vec![Node::default(); 1 << args.max_depth].as_slice());
         Ok(MerkleTreeCookie {
@@ -212,11 +186,14 @@ impl MerkleTreeTest {
         &self,
         tree_cookie: &mut MerkleTreeCookie
     ) -> Result<TreeConfig, TransportError> {
-        let tree_authority = &tree_cookie.tree_authority;
-        let tree_config = self.bench.get_anchor_account::<TreeConfig>(*tree_authority).await;
+        let tree_authority = self.bench.get_account(&tree_cookie.tree_authority).await.unwrap();
+        let tree_config = TreeConfig::from_bytes(&tree_authority.data).unwrap();
         Ok(tree_config)
     }
 
+    /// Returns the current root of the tree of any supported max depth and max buffer size
+    /// The root is read from the active ChangeLog<MAX_DEPTH> { root, path: [Node; MAX_DEPTH], index: u32, _padding: u32 }
+    /// of ConcurrentMerkleTree { sequence_number: u64, active_index: u64, buffer_size: u64, change_logs, .. }
     #[allow(dead_code)]
     pub async fn decode_root(
         &self,
@@ -224,21 +201,92 @@ impl MerkleTreeTest {
         max_depth: usize,
         max_buffer_size: usize
     ) -> Result<[u8; 32], TransportError> {
-        let mut tree_account = self.bench.get_account(tree_mint).await.unwrap();
-
-        let merkle_tree_bytes = tree_account.data.as_mut_slice();
-        let (_header_bytes, rest) = merkle_tree_bytes.split_at_mut(
-            CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1
-        );
+        let tree_account = self.bench.get_account(tree_mint).await.unwrap();
 
         let merkle_tree_size = merkle_tree_get_size(max_depth, max_buffer_size).unwrap();
-        let (tree_bytes, _) = &mut rest.split_at_mut(merkle_tree_size);
-        // println!("{}", canopy_bytes.len());
+        let tree_bytes = &tree_account.data[
+            CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1..CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 +
+                merkle_tree_size
+        ];
+
+        let active_index = u64::from_le_bytes(tree_bytes[8..16].try_into().unwrap()) as usize;
+        let change_log_size = 32 + 32 * max_depth + 4 + 4;
+        let root_offset = 24 + active_index * change_log_size;
+
+        Ok(tree_bytes[root_offset..root_offset + 32].try_into().unwrap())
+    }
+
+    /// Converts the given tree into mpl-account-compression tree of LeafSchema::V2 assets holding the given leaf
+    /// The current root of the tree is replaced with the root of the tree with the leaf at the given index
+    /// Returns the new root and the proof of the leaf
+    #[allow(dead_code)]
+    pub async fn with_leaf_schema_v2_tree(
+        &self,
+        tree_cookie: &MerkleTreeCookie,
+        index: u32,
+        leaf_node: [u8; 32]
+    ) -> Result<([u8; 32], Vec<[u8; 32]>), TransportError> {
+        let mut leaves = vec![Node::default(); 1 << 5];
+        leaves[index as usize] = leaf_node;
+        let proof_tree = MerkleTree::new(leaves.as_slice());
+        let root = proof_tree.get_root();
+
+        let mut tree_account = self.bench.get_account(&tree_cookie.address).await.unwrap();
+
+        let (_header_bytes, rest) = tree_account.data.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+        let (tree_bytes, _) = rest.split_at_mut(merkle_tree_get_size(5, 8).unwrap());
+
+        // fixed ConcurrentMerkleTree<5, 8> for now
+        let tree = try_from_bytes_mut::<ConcurrentMerkleTree<5, 8>>(tree_bytes).unwrap();
+        tree.change_logs[tree.active_index as usize].root = root;
+
+        self.bench.set_account_data(
+            &tree_cookie.address,
+            &MPL_ACCOUNT_COMPRESSION_ID,
+            tree_account.data
+        ).await;
+
+        Ok((root, proof_tree.get_proof_of_leaf(index as usize)))
+    }
+
+    /// Converts the given tree into a tree of the given authority holding the given leaf
+    /// It simulates a tree created directly through spl-account-compression whose authority can append any leaf
+    /// The current root of the tree is replaced with the root of the tree with the leaf at the given index
+    /// Returns the new root and the proof of the leaf
+    #[allow(dead_code)]
+    pub async fn with_forged_leaf_tree(
+        &self,
+        tree_cookie: &MerkleTreeCookie,
+        authority: &Pubkey,
+        index: u32,
+        leaf_node: [u8; 32]
+    ) -> Result<([u8; 32], Vec<[u8; 32]>), TransportError> {
+        let mut leaves = vec![Node::default(); 1 << 5];
+        leaves[index as usize] = leaf_node;
+        let proof_tree = MerkleTree::new(leaves.as_slice());
+        let root = proof_tree.get_root();
+
+        let mut tree_account = self.bench.get_account(&tree_cookie.address).await.unwrap();
+
+        let (header_bytes, rest) = tree_account.data.split_at_mut(CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1);
+
+        let mut header = ConcurrentMerkleTreeHeader::try_from_slice(header_bytes).unwrap();
+        header.set_new_authority(authority);
+        header.serialize(&mut &mut header_bytes[..]).unwrap();
+
+        let (tree_bytes, _) = rest.split_at_mut(merkle_tree_get_size(5, 8).unwrap());
 
         // fixed ConcurrentMerkleTree<5, 8> for now
-        let tree = try_from_bytes::<ConcurrentMerkleTree<5, 8>>(tree_bytes).unwrap();
-        let root = tree.change_logs[tree.active_index as usize].root;
-        Ok(root)
+        let tree = try_from_bytes_mut::<ConcurrentMerkleTree<5, 8>>(tree_bytes).unwrap();
+        tree.change_logs[tree.active_index as usize].root = root;
+
+        self.bench.set_account_data(
+            &tree_cookie.address,
+            &spl_account_compression::id(),
+            tree_account.data
+        ).await;
+
+        Ok((root, proof_tree.get_proof_of_leaf(index as usize)))
     }
 
     #[allow(dead_code)]
@@ -281,12 +329,20 @@ impl MerkleTreeTest {
                 is_mutable: args.metadata.is_mutable,
                 edition_nonce: args.metadata.edition_nonce,
                 creators,
+                uses: args.metadata.uses.as_ref().map(Uses::from_bubblegum),
                 root,
-                leaf_owner: args.owner.pubkey(),
-                leaf_delegate: args.delegate.pubkey(),
+                leaf_owner: args.owner,
+                leaf_delegate: args.delegate,
                 nonce: args.nonce,
                 index: args.index,
                 proof_len: proofs.len() as u8,
+                last_transfer_slot: None,
+                data_hash: None,
+                creator_hash: None,
+                proof_nodes: None,
+                use_proof_attestation: false,
+                leaf_schema: LeafSchema::V1,
+                leaf_hash: None,
             },
             proofs,
             asset_id,
@@ -294,42 +350,57 @@ impl MerkleTreeTest {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LeafArgs {
     pub tree_address: Pubkey,
     pub asset_id: Pubkey,
-    pub owner: Keypair,
-    pub delegate: Keypair,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
     pub metadata: MetadataArgs,
     pub nonce: u64,
     pub index: u32,
 }
 
-impl Clone for LeafArgs {
-    fn clone(&self) -> Self {
-        LeafArgs {
-            tree_address: self.tree_address.clone(),
-            asset_id: self.asset_id.clone(),
-            owner: clone_keypair(&self.owner),
-            delegate: clone_keypair(&self.delegate),
-            metadata: self.metadata.clone(),
-            nonce: self.nonce,
-            index: self.index,
-        }
-    }
-}
-
 impl LeafArgs {
     // Creates a new object with some default values.
-    pub fn new(owner: &Keypair, tree_address: &Pubkey, metadata: MetadataArgs) -> Self {
+    pub fn new(owner: &Pubkey, tree_address: &Pubkey, metadata: MetadataArgs) -> Self {
         LeafArgs {
             tree_address: tree_address.clone(),
             asset_id: get_asset_id(tree_address, 0),
-            owner: clone_keypair(owner),
-            delegate: clone_keypair(owner),
+            owner: *owner,
+            delegate: *owner,
             metadata,
             nonce: 0,
             index: 0,
         }
     }
+    /// Returns the data_hash of the leaf metadata
+    #[allow(dead_code)]
+    pub fn data_hash(&self) -> [u8; 32] {
+        hash_metadata(&(&self.metadata).into())
+    }
+
+    /// Returns the creator_hash of the leaf metadata
+    #[allow(dead_code)]
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creators = self.metadata.creators
+            .iter()
+            .map(cnft_verification::Creator::from)
+            .collect::<Vec<_>>();
+
+        hash_creators(&creators)
+    }
+
+    /// Returns the leaf node of the asset as stored in the merkle tree
+    #[allow(dead_code)]
+    pub fn leaf_node(&self) -> [u8; 32] {
+        get_leaf_node(
+            &self.asset_id,
+            &self.owner,
+            &self.delegate,
+            self.nonce,
+            &self.data_hash(),
+            &self.creator_hash()
+        )
+    }
 }
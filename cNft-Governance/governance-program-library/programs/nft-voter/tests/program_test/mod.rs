@@ -1,6 +1,9 @@
+pub mod account_substitution;
+pub mod governance_addin_mock_test;
 pub mod governance_test;
 pub mod nft_voter_test;
 pub mod merkle_tree_test;
 pub mod program_test_bench;
+pub mod signer_registry;
 pub mod token_metadata_test;
 pub mod tools;
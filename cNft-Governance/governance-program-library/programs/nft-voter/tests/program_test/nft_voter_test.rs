@@ -1,6 +1,7 @@
-use std::sync::Arc;
+use std::{ convert::TryFrom, sync::Arc };
 
 use anchor_lang::prelude::{ AccountMeta, Pubkey };
+use borsh::BorshDeserialize;
 
 use gpl_nft_voter::state::max_voter_weight_record::{
     get_max_voter_weight_record_address,
@@ -9,6 +10,7 @@ use gpl_nft_voter::state::max_voter_weight_record::{
 use gpl_nft_voter::state::*;
 
 use spl_governance::instruction::cast_vote;
+use spl_governance::state::realm_config::get_realm_config_address;
 use spl_governance::state::vote_record::{ self, Vote, VoteChoice };
 
 use gpl_nft_voter::state::{
@@ -17,30 +19,44 @@ use gpl_nft_voter::state::{
     CollectionConfig,
     NftVoteRecord,
     Registrar,
+    RegistrarConfig,
     CompressedNftAsset as LeafVerificationCookie,
 };
 
 use solana_program_test::{ BanksClientError, ProgramTest };
 use solana_sdk::instruction::Instruction;
 use solana_sdk::signature::Keypair;
+use solana_sdk::system_instruction;
+use spl_merkle_tree_reference::{ MerkleTree, Node };
 use solana_sdk::signer::Signer;
+use crate::program_test::governance_addin_mock_test::GovernanceAddinMockTest;
 use crate::program_test::governance_test::GovernanceTest;
 use crate::program_test::program_test_bench::ProgramTestBench;
 use crate::program_test::merkle_tree_test::{ LeafArgs, MerkleTreeTest };
 use crate::program_test::governance_test::{ ProposalCookie, RealmCookie, TokenOwnerRecordCookie };
-use crate::program_test::program_test_bench::WalletCookie;
+use crate::program_test::program_test_bench::{ TransactionFailure, TransactionLogs, WalletCookie };
 use crate::program_test::token_metadata_test::{ NftCollectionCookie, NftCookie, TokenMetadataTest };
-use crate::program_test::tools::NopOverride;
+use crate::program_test::tools::{ new_ed25519_signature_ix, NopOverride };
 
 #[derive(Debug, PartialEq)]
 pub struct RegistrarCookie {
     pub address: Pubkey,
     pub account: Registrar,
 
+    pub extension_address: Pubkey,
+    pub extension: RegistrarExtension,
+
     pub realm_authority: Keypair,
     pub max_collections: u8,
 }
 
+pub struct RegistrarTemplateCookie {
+    pub address: Pubkey,
+    pub account: RegistrarTemplate,
+
+    pub authority: Keypair,
+}
+
 pub struct VoterWeightRecordCookie {
     pub address: Pubkey,
     pub account: VoterWeightRecord,
@@ -70,31 +86,133 @@ impl Default for ConfigureCollectionArgs {
 pub struct NftVoteRecordCookie {
     pub address: Pubkey,
     pub account: NftVoteRecord,
+
+    /// The CollectionVoteWeightRecord the NFT weight was counted in, it's supplied to relinquish_nft_vote
+    pub collection_vote_weight_record: Option<Pubkey>,
 }
 
 pub struct CastNftVoteArgs {
     pub cast_spl_gov_vote: bool,
+
+    /// When set the CollectionVoteWeightRecord of the collection is supplied with each NFT
+    pub capped_collection: Option<Pubkey>,
+
+    /// When set cast_nft_vote only validates the NFTs and returns the voter weight without any writes
+    pub simulate_only: bool,
+
+    /// When set the CollectionStats of the collection is supplied with each NFT
+    pub stats_collection: Option<Pubkey>,
+
+    /// Memo emitted by cast_nft_vote as NftVoteMemo event
+    pub memo: Option<String>,
+
+    /// When set the rent of the created accounts is paid from the given VoterRentEscrow
+    pub voter_rent_escrow: Option<Pubkey>,
+
+    /// When set the vote is signed by the session authority of the given VotingSession
+    pub voting_session: Option<Pubkey>,
+
+    /// The vote kind recorded in NftVoteRecords and cast to spl-gov (Unknown is cast as Approve)
+    pub vote_kind: NftVoteKind,
+
+    /// When set the legacy ["nft-vote-record",proposal,nft_mint] NftVoteRecord is supplied with each NFT
+    pub legacy_nft_vote_records: bool,
+
+    /// When set the CastVote tickets of the members of the VoterGroup are counted for the voter
+    pub voter_group: Option<VoterGroupCookie>,
 }
 
 impl Default for CastNftVoteArgs {
     fn default() -> Self {
         Self {
             cast_spl_gov_vote: true,
+            capped_collection: None,
+            simulate_only: false,
+            stats_collection: None,
+            memo: None,
+            voter_rent_escrow: None,
+            voting_session: None,
+            vote_kind: NftVoteKind::Approve,
+            legacy_nft_vote_records: false,
+            voter_group: None,
         }
     }
 }
 
+#[derive(Debug)]
 pub struct NftVoteTicketCookie {
     pub address: Pubkey,
     pub nft_mint: Pubkey,
 }
 
+#[derive(Clone)]
+pub struct VoterGroupCookie {
+    pub address: Pubkey,
+    pub registrar: Pubkey,
+    pub members: Vec<Pubkey>,
+}
+
+pub struct BallotTreeCookie {
+    pub address: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub tree_authority: Pubkey,
+    pub proof_tree: MerkleTree,
+    pub account: BallotTree,
+}
+
+impl BallotTreeCookie {
+    /// Records the ballot minted to the owner in the proof tree and returns its leaf args
+    #[allow(dead_code)]
+    pub fn add_ballot(&mut self, owner: &Pubkey) -> LeafArgs {
+        let ballots_minted = self.account.ballots_minted;
+        let metadata = self.account.get_ballot_metadata(&self.address);
+
+        let mut args = LeafArgs::new(owner, &self.merkle_tree, metadata);
+        args.index = u32::try_from(ballots_minted).unwrap();
+        args.nonce = ballots_minted;
+        args.asset_id = ::cnft_verification::get_asset_id(&self.merkle_tree, args.nonce);
+
+        self.proof_tree.add_leaf(args.leaf_node(), args.index as usize);
+        self.account.ballots_minted += 1;
+
+        args
+    }
+
+    /// Records the vote receipt minted to the voter in the proof tree and returns its leaf args
+    #[allow(dead_code)]
+    pub fn add_vote_receipt(&mut self, voter: &Pubkey, proposal: &Pubkey, vote: &Vote) -> LeafArgs {
+        let ballots_minted = self.account.ballots_minted;
+        let metadata = self.account.get_vote_receipt_metadata(&self.address, proposal, vote).unwrap();
+
+        let mut args = LeafArgs::new(voter, &self.merkle_tree, metadata);
+        args.index = u32::try_from(ballots_minted).unwrap();
+        args.nonce = ballots_minted;
+        args.asset_id = ::cnft_verification::get_asset_id(&self.merkle_tree, args.nonce);
+
+        self.proof_tree.add_leaf(args.leaf_node(), args.index as usize);
+        self.account.ballots_minted += 1;
+
+        args
+    }
+}
+
+pub struct CampaignCookie {
+    pub address: Pubkey,
+    pub account: Campaign,
+}
+
+pub struct WeightSnapshotCookie {
+    pub address: Pubkey,
+    pub account: WeightSnapshot,
+}
+
 pub struct NftVoterTest {
     pub program_id: Pubkey,
     pub bench: Arc<ProgramTestBench>,
     pub governance: GovernanceTest,
     pub token_metadata: TokenMetadataTest,
     pub merkle_tree: MerkleTreeTest,
+    pub governance_addin_mock: GovernanceAddinMockTest,
 }
 
 impl NftVoterTest {
@@ -111,6 +229,7 @@ impl NftVoterTest {
         GovernanceTest::add_program(&mut program_test);
         TokenMetadataTest::add_program(&mut program_test);
         MerkleTreeTest::add_program(&mut program_test);
+        GovernanceAddinMockTest::add_program(&mut program_test);
 
         let program_id = gpl_nft_voter::id();
 
@@ -124,6 +243,7 @@ impl NftVoterTest {
         );
         let token_metadata_bench = TokenMetadataTest::new(bench_rc.clone());
         let merkle_tree_bench = MerkleTreeTest::new(bench_rc.clone());
+        let governance_addin_mock_bench = GovernanceAddinMockTest::new(bench_rc.clone());
 
         Self {
             program_id,
@@ -131,6 +251,7 @@ impl NftVoterTest {
             governance: governance_bench,
             token_metadata: token_metadata_bench,
             merkle_tree: merkle_tree_bench,
+            governance_addin_mock: governance_addin_mock_bench,
         }
     }
 
@@ -153,6 +274,7 @@ impl NftVoterTest {
             &realm_cookie.address,
             &realm_cookie.account.community_mint
         );
+        let registrar_extension_key = get_registrar_extension_address(&registrar_key);
 
         let max_collections = 10;
         let data = anchor_lang::InstructionData::data(
@@ -164,6 +286,7 @@ impl NftVoterTest {
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
             &(gpl_nft_voter::accounts::CreateRegistrar {
                 registrar: registrar_key,
+                registrar_extension: registrar_extension_key,
                 realm: realm_cookie.address,
                 governance_program_id: self.governance.program_id,
                 governing_token_mint: realm_cookie.account.community_mint,
@@ -198,6 +321,313 @@ impl NftVoterTest {
         Ok(RegistrarCookie {
             address: registrar_key,
             account,
+            extension_address: registrar_extension_key,
+            extension: RegistrarExtension::new(registrar_key),
+            realm_authority: realm_cookie.get_realm_authority(),
+            max_collections,
+        })
+    }
+
+    /// Creates a Registrar as it was created by the program versions without RegistrarExtension
+    /// The account is written directly and the returned cookie expects the extension migrate_registrar creates
+    #[allow(dead_code)]
+    pub async fn with_legacy_registrar(&mut self, realm_cookie: &RealmCookie) -> RegistrarCookie {
+        let registrar_key = get_registrar_address(
+            &realm_cookie.address,
+            &realm_cookie.account.community_mint
+        );
+        let registrar_extension_key = get_registrar_extension_address(&registrar_key);
+
+        let max_collections = 10;
+
+        let account = Registrar {
+            governance_program_id: self.governance.program_id,
+            realm: realm_cookie.address,
+            governing_token_mint: realm_cookie.account.community_mint,
+            collection_configs: vec![],
+            reserved: [0; 128],
+        };
+
+        let mut data = vec![];
+        anchor_lang::AccountSerialize::try_serialize(&account, &mut data).unwrap();
+        data.resize(Registrar::space_for(max_collections), 0);
+
+        self.bench.set_account_data(&registrar_key, &gpl_nft_voter::id(), data).await;
+
+        RegistrarCookie {
+            address: registrar_key,
+            account,
+            extension_address: registrar_extension_key,
+            extension: RegistrarExtension::new(registrar_key),
+            realm_authority: realm_cookie.get_realm_authority(),
+            max_collections,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn migrate_registrar(
+        &mut self,
+        registrar_cookie: &RegistrarCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::MigrateRegistrar {
+                max_collections: registrar_cookie.max_collections,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::MigrateRegistrar {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let migrate_registrar_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[migrate_registrar_ix], None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar_template(
+        &mut self,
+        max_collections: u8
+    ) -> Result<RegistrarTemplateCookie, BanksClientError> {
+        let authority = Keypair::new();
+        let template_id = 1;
+
+        let registrar_template_key = get_registrar_template_address(
+            &authority.pubkey(),
+            template_id
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateRegistrarTemplate {
+                template_id,
+                max_collections,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateRegistrarTemplate {
+            registrar_template: registrar_template_key,
+            authority: authority.pubkey(),
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_registrar_template_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[create_registrar_template_ix], Some(&[&authority])).await?;
+
+        let account = RegistrarTemplate {
+            authority: authority.pubkey(),
+            template_id,
+            max_collections,
+            collection_configs: vec![],
+            config: RegistrarConfig::default(),
+            reserved: [0; 64],
+        };
+
+        Ok(RegistrarTemplateCookie {
+            address: registrar_template_key,
+            account,
+            authority,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar_template_config(
+        &mut self,
+        registrar_template_cookie: &mut RegistrarTemplateCookie,
+        config: RegistrarConfig,
+        nft_collection_cookies: &[&NftCollectionCookie],
+        args: &[ConfigureCollectionArgs]
+    ) -> Result<(), BanksClientError> {
+        self.with_registrar_template_config_using_ix(
+            registrar_template_cookie,
+            config,
+            nft_collection_cookies,
+            args,
+            NopOverride,
+            None
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar_template_config_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_template_cookie: &mut RegistrarTemplateCookie,
+        config: RegistrarConfig,
+        nft_collection_cookies: &[&NftCollectionCookie],
+        args: &[ConfigureCollectionArgs],
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<(), BanksClientError> {
+        let collections = nft_collection_cookies
+            .iter()
+            .zip(args.iter())
+            .map(|(nft_collection_cookie, args)| CollectionConfigArgs {
+                collection: nft_collection_cookie.mint,
+                weight: args.weight,
+                size: args.size,
+            })
+            .collect::<Vec<_>>();
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureRegistrarTemplate {
+                config,
+                collections: collections.clone(),
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureRegistrarTemplate {
+            registrar_template: registrar_template_cookie.address,
+            authority: registrar_template_cookie.authority.pubkey(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        for nft_collection_cookie in nft_collection_cookies {
+            account_metas.push(AccountMeta::new_readonly(nft_collection_cookie.mint, false));
+        }
+
+        let mut configure_registrar_template_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        instruction_override(&mut configure_registrar_template_ix);
+
+        let default_signers = &[&registrar_template_cookie.authority];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&[configure_registrar_template_ix], Some(signers)).await?;
+
+        registrar_template_cookie.account.config = config;
+        registrar_template_cookie.account.collection_configs = collections
+            .iter()
+            .map(|args| CollectionConfig::new(args.collection, args.weight, args.size))
+            .collect();
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar_from_template(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        registrar_template_cookie: &RegistrarTemplateCookie
+    ) -> Result<RegistrarCookie, BanksClientError> {
+        self.with_registrar_from_template_using_ix(
+            realm_cookie,
+            registrar_template_cookie,
+            10,
+            NopOverride,
+            None
+        ).await
+    }
+
+    /// Creates MaxVoterWeightRecord and Registrar from the given template in a single transaction
+    #[allow(dead_code)]
+    pub async fn with_registrar_from_template_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        realm_cookie: &RealmCookie,
+        registrar_template_cookie: &RegistrarTemplateCookie,
+        max_collections: u8,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<RegistrarCookie, BanksClientError> {
+        let registrar_key = get_registrar_address(
+            &realm_cookie.address,
+            &realm_cookie.account.community_mint
+        );
+        let registrar_extension_key = get_registrar_extension_address(&registrar_key);
+
+        let max_voter_weight_record_key = get_max_voter_weight_record_address(
+            &realm_cookie.address,
+            &realm_cookie.account.community_mint
+        );
+
+        let create_max_voter_weight_record_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(
+                &(gpl_nft_voter::accounts::CreateMaxVoterWeightRecord {
+                    governance_program_id: self.governance.program_id,
+                    realm: realm_cookie.address,
+                    realm_governing_token_mint: realm_cookie.account.community_mint,
+                    max_voter_weight_record: max_voter_weight_record_key,
+                    payer: self.bench.payer.pubkey(),
+                    system_program: solana_sdk::system_program::id(),
+                }),
+                None
+            ),
+            data: anchor_lang::InstructionData::data(
+                &(gpl_nft_voter::instruction::CreateMaxVoterWeightRecord {})
+            ),
+        };
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateRegistrarFromTemplate {
+                max_collections,
+            })
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &(gpl_nft_voter::accounts::CreateRegistrarFromTemplate {
+                registrar: registrar_key,
+                registrar_extension: registrar_extension_key,
+                registrar_template: registrar_template_cookie.address,
+                governance_program_id: self.governance.program_id,
+                realm: realm_cookie.address,
+                governing_token_mint: realm_cookie.account.community_mint,
+                realm_authority: realm_cookie.get_realm_authority().pubkey(),
+                max_voter_weight_record: max_voter_weight_record_key,
+                payer: self.bench.payer.pubkey(),
+                system_program: solana_sdk::system_program::id(),
+            }),
+            None
+        );
+
+        let mut create_registrar_from_template_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts,
+            data,
+        };
+
+        instruction_override(&mut create_registrar_from_template_ix);
+
+        let default_signers = &[&realm_cookie.realm_authority];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(
+            &[create_max_voter_weight_record_ix, create_registrar_from_template_ix],
+            Some(signers)
+        ).await?;
+
+        let account = Registrar {
+            governance_program_id: self.governance.program_id,
+            realm: realm_cookie.address,
+            governing_token_mint: realm_cookie.account.community_mint,
+            collection_configs: registrar_template_cookie.account.collection_configs.clone(),
+            reserved: [0; 128],
+        };
+
+        Ok(RegistrarCookie {
+            address: registrar_key,
+            account,
+            extension_address: registrar_extension_key,
+            extension: RegistrarExtension {
+                config: registrar_template_cookie.account.config,
+                ..RegistrarExtension::new(registrar_key)
+            },
             realm_authority: realm_cookie.get_realm_authority(),
             max_collections,
         })
@@ -221,14 +651,10 @@ impl NftVoterTest {
     ) -> Result<VoterWeightRecordCookie, BanksClientError> {
         let governing_token_owner = voter_cookie.address;
 
-        let (voter_weight_record_key, _) = Pubkey::find_program_address(
-            &[
-                b"voter-weight-record".as_ref(),
-                registrar_cookie.account.realm.as_ref(),
-                registrar_cookie.account.governing_token_mint.as_ref(),
-                governing_token_owner.as_ref(),
-            ],
-            &gpl_nft_voter::id()
+        let voter_weight_record_key = get_voter_weight_record_address(
+            &registrar_cookie.account.realm,
+            &registrar_cookie.account.governing_token_mint,
+            &governing_token_owner
         );
 
         let data = anchor_lang::InstructionData::data(
@@ -264,7 +690,9 @@ impl NftVoterTest {
             voter_weight_expiry: Some(0),
             weight_action: None,
             weight_action_target: None,
-            reserved: [0; 8],
+            weight_state: VoterWeightState::Consumed,
+            record_nonce: 0,
+            reserved: [0; 3],
         };
 
         Ok(VoterWeightRecordCookie {
@@ -273,6 +701,42 @@ impl NftVoterTest {
         })
     }
 
+    #[allow(dead_code)]
+    pub async fn with_voter_weight_detail(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_collections: u8
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateVoterWeightDetail {
+                max_collections,
+            })
+        );
+
+        let voter_weight_detail = get_voter_weight_detail_address(
+            &voter_weight_record_cookie.address
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateVoterWeightDetail {
+            registrar: registrar_cookie.address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            voter_weight_detail,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_voter_weight_detail_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[create_voter_weight_detail_ix], None).await?;
+
+        Ok(voter_weight_detail)
+    }
+
     #[allow(dead_code)]
     pub async fn with_max_voter_weight_record(
         &mut self,
@@ -337,25 +801,363 @@ impl NftVoterTest {
         voter_weight_action: VoterWeightAction,
         nft_action_ticket_cookies: &[&NftVoteTicketCookie]
     ) -> Result<(), BanksClientError> {
-        let data = anchor_lang::InstructionData::data(
-            &(gpl_nft_voter::instruction::UpdateVoterWeightRecord {
+        self.update_voter_weight_record_with_detail(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            None
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record_with_detail(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &mut VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        voter_weight_detail: Option<&Pubkey>
+    ) -> Result<(), BanksClientError> {
+        self.update_voter_weight_record_with_return_data(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            voter_weight_detail
+        ).await?;
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record_with_return_data(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &mut VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        voter_weight_detail: Option<&Pubkey>
+    ) -> Result<Option<Vec<u8>>, BanksClientError> {
+        self.update_voter_weight_record_using_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            voter_weight_detail,
+            NopOverride
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record_using_ix<F: Fn(&mut Instruction)>(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &mut VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        voter_weight_detail: Option<&Pubkey>,
+        instruction_override: F
+    ) -> Result<Option<Vec<u8>>, BanksClientError> {
+        let mut update_voter_weight_record_ix = self.update_voter_weight_record_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            voter_weight_detail
+        );
+
+        instruction_override(&mut update_voter_weight_record_ix);
+
+        let return_data = self.bench.process_transaction_with_return_data(
+            &[update_voter_weight_record_ix],
+            None
+        ).await?;
+
+        voter_weight_record_cookie.account.record_nonce += 1;
+
+        Ok(return_data)
+    }
+
+    /// Returns update_voter_weight_record instruction to be executed with other instructions in the same transaction
+    #[allow(dead_code)]
+    pub fn update_voter_weight_record_ix(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        voter_weight_detail: Option<&Pubkey>
+    ) -> Instruction {
+        self.update_voter_weight_record_with_voter_group_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            voter_weight_detail,
+            None
+        )
+    }
+
+    /// Updates VoterWeightRecord for the given weight_action_target required by RegistrarExtension.config.require_weight_action_target
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record_with_target(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        weight_action_target: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let mut update_voter_weight_record_ix = self.update_voter_weight_record_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            None
+        );
+
+        // The target is the first remaining account
+        let target_index = update_voter_weight_record_ix.accounts.len() - nft_action_ticket_cookies.len();
+        update_voter_weight_record_ix.accounts.insert(
+            target_index,
+            AccountMeta::new_readonly(*weight_action_target, false)
+        );
+
+        self.bench.process_transaction(&[update_voter_weight_record_ix], None).await
+    }
+
+    /// Updates VoterWeightRecord with the tickets of the voter and the members of the given VoterGroup
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record_with_voter_group(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        voter_group: &VoterGroupCookie
+    ) -> Result<(), BanksClientError> {
+        let update_voter_weight_record_ix = self.update_voter_weight_record_with_voter_group_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_weight_action,
+            nft_action_ticket_cookies,
+            None,
+            Some(voter_group)
+        );
+
+        self.bench.process_transaction(&[update_voter_weight_record_ix], None).await
+    }
+
+    /// Returns update_voter_weight_record instruction with use_voter_group set when the VoterGroup is given
+    /// The VoterGroup is followed by the VoterFreezeRecords of its members
+    #[allow(dead_code)]
+    pub fn update_voter_weight_record_with_voter_group_ix(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        voter_weight_detail: Option<&Pubkey>,
+        voter_group: Option<&VoterGroupCookie>
+    ) -> Instruction {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::UpdateVoterWeightRecord {
                 voter_weight_action,
+                use_voter_group: voter_group.is_some(),
+                expected_record_nonce: voter_weight_record_cookie.account.record_nonce,
             })
         );
 
         let accounts = gpl_nft_voter::accounts::UpdateVoterWeightRecord {
             registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
             voter_weight_record: voter_weight_record_cookie.address,
             payer: self.bench.payer.pubkey(),
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            ),
+            instructions: solana_sdk::sysvar::instructions::id(),
         };
 
         let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
 
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(
+                registrar_cookie,
+                &voter_weight_record_cookie.account.governing_token_owner
+            )
+        );
+
+        if let Some(voter_weight_detail) = voter_weight_detail {
+            account_metas.push(AccountMeta::new(*voter_weight_detail, false));
+        }
+
+        if let Some(voter_group) = voter_group {
+            account_metas.push(AccountMeta::new_readonly(voter_group.address, false));
+
+            for member in &voter_group.members {
+                account_metas.push(
+                    AccountMeta::new_readonly(
+                        get_voter_freeze_record_address(&registrar_cookie.address, member),
+                        false
+                    )
+                );
+            }
+        }
+
         for nft_action_ticket_cookie in nft_action_ticket_cookies {
             let nft_action_ticket = nft_action_ticket_cookie.address;
             account_metas.push(AccountMeta::new(nft_action_ticket, false));
         }
 
+        Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record_for_target(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &mut VoterWeightRecordCookie,
+        governance_weight_config: &Pubkey,
+        target: &Pubkey,
+        voter_weight_action: VoterWeightAction,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie]
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::UpdateVoterWeightRecordForTarget {
+                voter_weight_action,
+                expected_record_nonce: voter_weight_record_cookie.account.record_nonce,
+            })
+        );
+
+        let governance = self.bench
+            .get_anchor_account::<GovernanceWeightConfig>(*governance_weight_config).await
+            .governance;
+
+        let accounts = gpl_nft_voter::accounts::UpdateVoterWeightRecordForTarget {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            governance_weight_config: *governance_weight_config,
+            target: *target,
+            payer: self.bench.payer.pubkey(),
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            ),
+            governance,
+            instructions: solana_sdk::sysvar::instructions::id(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(
+                registrar_cookie,
+                &voter_weight_record_cookie.account.governing_token_owner
+            )
+        );
+
+        for nft_action_ticket_cookie in nft_action_ticket_cookies {
+            account_metas.push(AccountMeta::new(nft_action_ticket_cookie.address, false));
+        }
+
+        let instructions = vec![Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        }];
+
+        self.bench.process_transaction(&instructions, None).await?;
+
+        voter_weight_record_cookie.account.record_nonce += 1;
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_proposal_receipt(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        governance: &Pubkey,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie]
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateProposalReceipt {})
+        );
+
+        let proposal_creation_ticket = get_proposal_creation_ticket_address(
+            &registrar_cookie.address,
+            &voter_weight_record_cookie.account.governing_token_owner,
+            governance
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateProposalReceipt {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            governance: *governance,
+            proposal_creation_ticket,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        for nft_action_ticket_cookie in nft_action_ticket_cookies {
+            account_metas.push(AccountMeta::new(nft_action_ticket_cookie.address, false));
+        }
+
+        let instructions = vec![Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        }];
+
+        self.bench.process_transaction(&instructions, None).await?;
+
+        Ok(proposal_creation_ticket)
+    }
+
+    #[allow(dead_code)]
+    pub async fn consume_proposal_creation_ticket(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        proposal_creation_ticket: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConsumeProposalCreationTicket {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConsumeProposalCreationTicket {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            proposal_creation_ticket: *proposal_creation_ticket,
+            beneficiary: self.bench.payer.pubkey(),
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            ),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(
+                registrar_cookie,
+                &voter_weight_record_cookie.account.governing_token_owner
+            )
+        );
+
         let instructions = vec![Instruction {
             program_id: gpl_nft_voter::id(),
             accounts: account_metas,
@@ -400,6 +1202,10 @@ impl NftVoterTest {
 
         for nft_vote_record_cookie in nft_vote_record_cookies {
             account_metas.push(AccountMeta::new(nft_vote_record_cookie.address, false));
+
+            if let Some(collection_vote_weight_record) = nft_vote_record_cookie.collection_vote_weight_record {
+                account_metas.push(AccountMeta::new(collection_vote_weight_record, false));
+            }
         }
 
         let relinquish_nft_vote_ix = Instruction {
@@ -416,6 +1222,82 @@ impl NftVoterTest {
         Ok(())
     }
 
+    #[allow(dead_code)]
+    pub async fn reclaim_nft_vote_records(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_vote_record_cookies: &Vec<NftVoteRecordCookie>,
+        beneficiary: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ReclaimNftVoteRecords {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::ReclaimNftVoteRecords {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            governance: proposal_cookie.account.governance,
+            proposal: proposal_cookie.address,
+            treasury: registrar_cookie.extension.config.rent_reclaim_treasury,
+            beneficiary: *beneficiary,
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        for nft_vote_record_cookie in nft_vote_record_cookies {
+            account_metas.push(AccountMeta::new(nft_vote_record_cookie.address, false));
+        }
+
+        let reclaim_nft_vote_records_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(&[reclaim_nft_vote_records_ix], None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn close_orphaned_nft_vote_records(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_vote_record_cookies: &Vec<NftVoteRecordCookie>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CloseOrphanedNftVoteRecords {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::CloseOrphanedNftVoteRecords {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            governance: proposal_cookie.account.governance,
+            proposal: proposal_cookie.address,
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        for nft_vote_record_cookie in nft_vote_record_cookies {
+            account_metas.push(AccountMeta::new(nft_vote_record_cookie.address, false));
+            account_metas.push(
+                AccountMeta::new(nft_vote_record_cookie.account.governing_token_owner, false)
+            );
+        }
+
+        let close_orphaned_nft_vote_records_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[close_orphaned_nft_vote_records_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await
+    }
+
     #[allow(dead_code)]
     pub async fn with_collection(
         &mut self,
@@ -455,6 +1337,7 @@ impl NftVoterTest {
 
         let accounts = gpl_nft_voter::accounts::ConfigureCollection {
             registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
             realm: registrar_cookie.account.realm,
             realm_authority: registrar_cookie.realm_authority.pubkey(),
             collection: nft_collection_cookie.mint,
@@ -474,19 +1357,1855 @@ impl NftVoterTest {
 
         self.bench.process_transaction(&[configure_collection_ix], Some(signers)).await?;
 
-        let collection_config = CollectionConfig {
-            collection: nft_collection_cookie.mint,
-            size: args.size,
-            weight: args.weight,
-            reserved: [0; 8],
+        let collection_config = CollectionConfig::new(
+            nft_collection_cookie.mint,
+            args.weight,
+            args.size
+        );
+
+        Ok(CollectionConfigCookie { collection_config })
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_creator_boosts(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        nft_collection_cookie: &NftCollectionCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        creator_boosts: &[CreatorBoost]
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureCreatorBoosts {
+                collection: nft_collection_cookie.mint,
+                creator_boosts: creator_boosts.to_vec(),
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureCreatorBoosts {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            max_voter_weight_record: max_voter_weight_record_cookie.address,
+        };
+
+        let configure_creator_boosts_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_creator_boosts_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_collection_expiry(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        nft_collection_cookie: &NftCollectionCookie,
+        expires_at: i64
+    ) -> Result<(), BanksClientError> {
+        self.with_collection_expiry_using_ix(
+            registrar_cookie,
+            nft_collection_cookie,
+            expires_at,
+            NopOverride,
+            None
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_collection_expiry_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        nft_collection_cookie: &NftCollectionCookie,
+        expires_at: i64,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureCollectionExpiry {
+                collection: nft_collection_cookie.mint,
+                expires_at,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureCollectionExpiry {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+        };
+
+        let mut configure_collection_expiry_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        instruction_override(&mut configure_collection_expiry_ix);
+
+        let default_signers = &[&registrar_cookie.realm_authority];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&[configure_collection_expiry_ix], Some(signers)).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_collection_weight_strategy(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        nft_collection_cookie: &NftCollectionCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        weight_strategy: CollectionWeightStrategy
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureCollectionWeightStrategy {
+                collection: nft_collection_cookie.mint,
+                weight_strategy,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureCollectionWeightStrategy {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            max_voter_weight_record: max_voter_weight_record_cookie.address,
+        };
+
+        let configure_collection_weight_strategy_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_collection_weight_strategy_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_collection_uses_limit(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        nft_collection_cookie: &NftCollectionCookie,
+        uses_limited: bool
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureCollectionUsesLimit {
+                collection: nft_collection_cookie.mint,
+                uses_limited,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureCollectionUsesLimit {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+        };
+
+        let configure_collection_uses_limit_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_collection_uses_limit_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn prune_expired_collections(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::PruneExpiredCollections {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::PruneExpiredCollections {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            max_voter_weight_record: max_voter_weight_record_cookie.address,
+        };
+
+        let prune_expired_collections_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[prune_expired_collections_ix], None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_collections_batch(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        nft_collection_cookies: &[&NftCollectionCookie],
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        args: &[ConfigureCollectionArgs]
+    ) -> Result<Vec<CollectionConfigCookie>, BanksClientError> {
+        let collections = nft_collection_cookies
+            .iter()
+            .zip(args.iter())
+            .map(|(nft_collection_cookie, args)| CollectionConfigArgs {
+                collection: nft_collection_cookie.mint,
+                weight: args.weight,
+                size: args.size,
+            })
+            .collect::<Vec<_>>();
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureCollectionsBatch {
+                collections: collections.clone(),
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureCollectionsBatch {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            max_voter_weight_record: max_voter_weight_record_cookie.address,
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        for nft_collection_cookie in nft_collection_cookies {
+            account_metas.push(AccountMeta::new_readonly(nft_collection_cookie.mint, false));
+        }
+
+        let configure_collections_batch_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_collections_batch_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(
+            collections
+                .iter()
+                .map(|args| CollectionConfigCookie {
+                    collection_config: CollectionConfig::new(
+                        args.collection,
+                        args.weight,
+                        args.size
+                    ),
+                })
+                .collect()
+        )
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_governance_weight_config(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        governance: &Pubkey,
+        weight_multiplier_bps: u32
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureGovernanceWeight {
+                weight_multiplier_bps,
+            })
+        );
+
+        let governance_weight_config = get_governance_weight_config_address(
+            &registrar_cookie.address,
+            governance
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureGovernanceWeight {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            governance: *governance,
+            governance_weight_config,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let configure_governance_weight_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_governance_weight_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(governance_weight_config)
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_sub_registrar(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        sub_registrar_cookie: &RegistrarCookie,
+        weight_scale_bps: u32
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureSubRegistrar {
+                weight_scale_bps,
+            })
+        );
+
+        let sub_registrar_config = get_sub_registrar_config_address(
+            &registrar_cookie.address,
+            &sub_registrar_cookie.address
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureSubRegistrar {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            sub_registrar: sub_registrar_cookie.address,
+            sub_registrar_config,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let configure_sub_registrar_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_sub_registrar_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(sub_registrar_config)
+    }
+
+    #[allow(dead_code)]
+    pub async fn sync_parent_weight(
+        &self,
+        sub_registrar_cookie: &RegistrarCookie,
+        parent_registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        parent_voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        parent_max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        voter_weight_action: VoterWeightAction,
+        weight_action_target: Option<Pubkey>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::SyncParentWeight {
+                voter_weight_action,
+                weight_action_target,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::SyncParentWeight {
+            registrar: sub_registrar_cookie.address,
+            registrar_extension: sub_registrar_cookie.extension_address,
+            parent_registrar: parent_registrar_cookie.address,
+            parent_registrar_extension: parent_registrar_cookie.extension_address,
+            sub_registrar_config: get_sub_registrar_config_address(
+                &parent_registrar_cookie.address,
+                &sub_registrar_cookie.address
+            ),
+            voter_weight_record: voter_weight_record_cookie.address,
+            parent_voter_weight_record: parent_voter_weight_record_cookie.address,
+            max_voter_weight_record: max_voter_weight_record_cookie.address,
+            parent_max_voter_weight_record: parent_max_voter_weight_record_cookie.address,
+            parent_voter_freeze_record: get_voter_freeze_record_address(
+                &parent_registrar_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            ),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(
+                parent_registrar_cookie,
+                &voter_weight_record_cookie.account.governing_token_owner
+            )
+        );
+
+        let sync_parent_weight_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(&[sync_parent_weight_ix], None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn freeze_voter(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        governing_token_owner: &Pubkey
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::FreezeVoter {
+                governing_token_owner: *governing_token_owner,
+            })
+        );
+
+        let voter_freeze_record = get_voter_freeze_record_address(
+            &registrar_cookie.address,
+            governing_token_owner
+        );
+
+        let accounts = gpl_nft_voter::accounts::FreezeVoter {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            voter_freeze_record,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let freeze_voter_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[freeze_voter_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(voter_freeze_record)
+    }
+
+    #[allow(dead_code)]
+    pub async fn unfreeze_voter(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_freeze_record: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::UnfreezeVoter {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::UnfreezeVoter {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            voter_freeze_record: *voter_freeze_record,
+            beneficiary: self.bench.payer.pubkey(),
+        };
+
+        let unfreeze_voter_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[unfreeze_voter_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn link_cold_wallet(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        cold_wallet_cookie: &WalletCookie,
+        hot_wallet_cookie: &WalletCookie,
+        expires_at: i64
+    ) -> Result<Pubkey, BanksClientError> {
+        self.link_cold_wallet_using_ix(
+            registrar_cookie,
+            cold_wallet_cookie,
+            hot_wallet_cookie,
+            expires_at,
+            NopOverride
+        ).await
+    }
+
+    /// Links the cold wallet to the hot wallet using the ed25519 signature of the cold wallet
+    /// The instruction override is applied to the ed25519 signature instruction
+    #[allow(dead_code)]
+    pub async fn link_cold_wallet_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        cold_wallet_cookie: &WalletCookie,
+        hot_wallet_cookie: &WalletCookie,
+        expires_at: i64,
+        instruction_override: F
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::LinkColdWallet {
+                cold_wallet: cold_wallet_cookie.address,
+                expires_at,
+            })
+        );
+
+        let cold_wallet_link = get_cold_wallet_link_address(
+            &registrar_cookie.address,
+            &cold_wallet_cookie.address
+        );
+
+        let accounts = gpl_nft_voter::accounts::LinkColdWallet {
+            registrar: registrar_cookie.address,
+            cold_wallet_link,
+            hot_wallet: hot_wallet_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            instructions: solana_sdk::sysvar::instructions::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let link_cold_wallet_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        let mut ed25519_signature_ix = new_ed25519_signature_ix(
+            &cold_wallet_cookie.signer,
+            &get_cold_wallet_link_message(
+                &registrar_cookie.address,
+                &hot_wallet_cookie.address,
+                expires_at
+            )
+        );
+
+        instruction_override(&mut ed25519_signature_ix);
+
+        self.bench.process_transaction(
+            &[ed25519_signature_ix, link_cold_wallet_ix],
+            Some(&[&hot_wallet_cookie.signer])
+        ).await?;
+
+        Ok(cold_wallet_link)
+    }
+
+    #[allow(dead_code)]
+    pub async fn revoke_cold_wallet_link(
+        &mut self,
+        cold_wallet_link: &Pubkey,
+        authority_cookie: &WalletCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::RevokeColdWalletLink {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::RevokeColdWalletLink {
+            cold_wallet_link: *cold_wallet_link,
+            authority: authority_cookie.address,
+        };
+
+        let revoke_cold_wallet_link_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[revoke_cold_wallet_link_ix],
+            Some(&[&authority_cookie.signer])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn rent_voting_power(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        owner_cookie: &WalletCookie,
+        leaf_cookie: &LeafArgs,
+        leaf_verification_cookie: &LeafVerificationCookie,
+        proofs: &[AccountMeta],
+        renter: &Pubkey,
+        expires_at_slot: u64
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::RentVotingPower {
+                asset_id: leaf_cookie.asset_id,
+                params: leaf_verification_cookie.clone(),
+                renter: *renter,
+                expires_at_slot,
+            })
+        );
+
+        let weight_lease = get_weight_lease_address(
+            &registrar_cookie.address,
+            &leaf_cookie.asset_id,
+            &owner_cookie.address
+        );
+
+        let accounts = gpl_nft_voter::accounts::RentVotingPower {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            weight_lease,
+            owner: owner_cookie.address,
+            tree: leaf_cookie.tree_address,
+            payer: self.bench.payer.pubkey(),
+            compression_program: spl_account_compression::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut rent_voting_power_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        rent_voting_power_ix.accounts.extend_from_slice(proofs);
+
+        self.bench.process_transaction(
+            &[rent_voting_power_ix],
+            Some(&[&owner_cookie.signer])
+        ).await?;
+
+        Ok(weight_lease)
+    }
+
+    #[allow(dead_code)]
+    pub async fn reclaim_voting_power(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        weight_lease: &Pubkey,
+        owner: &Pubkey,
+        authority_cookie: &WalletCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ReclaimVotingPower {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::ReclaimVotingPower {
+            weight_lease: *weight_lease,
+            registrar_extension: registrar_cookie.extension_address,
+            owner: *owner,
+            authority: authority_cookie.address,
+        };
+
+        let reclaim_voting_power_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[reclaim_voting_power_ix],
+            Some(&[&authority_cookie.signer])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn map_collection_alias(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        collection: &Pubkey,
+        target_collection: &Pubkey,
+        weight_bps: u16
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::MapCollectionAlias {
+                collection: *collection,
+                target_collection: *target_collection,
+                weight_bps,
+            })
+        );
+
+        let collection_alias = get_collection_alias_address(&registrar_cookie.address, collection);
+
+        let accounts = gpl_nft_voter::accounts::MapCollectionAlias {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            collection_alias,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let map_collection_alias_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[map_collection_alias_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(collection_alias)
+    }
+
+    #[allow(dead_code)]
+    pub async fn register_voter(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        voter_cookie: &WalletCookie
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::RegisterVoter {})
+        );
+
+        let voter_registration = get_voter_registration_address(
+            &registrar_cookie.address,
+            &voter_cookie.address
+        );
+
+        let accounts = gpl_nft_voter::accounts::RegisterVoter {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            voter_registration,
+            governing_token_owner: voter_cookie.address,
+            max_voter_weight_record: max_voter_weight_record_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let register_voter_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[register_voter_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(voter_registration)
+    }
+
+    #[allow(dead_code)]
+    pub async fn configure_wallet_weight_override(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        wallet: &Pubkey,
+        weight_bps: u16
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureWalletWeightOverride {
+                wallet: *wallet,
+                weight_bps,
+            })
+        );
+
+        let wallet_weight_override = get_wallet_weight_override_address(
+            &registrar_cookie.address,
+            wallet
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureWalletWeightOverride {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            wallet_weight_override,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let configure_wallet_weight_override_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[configure_wallet_weight_override_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(wallet_weight_override)
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_voter_group(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voting_wallet_cookie: &WalletCookie
+    ) -> Result<VoterGroupCookie, BanksClientError> {
+        let voter_group = get_voter_group_address(
+            &registrar_cookie.address,
+            &voting_wallet_cookie.address
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateVoterGroup {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateVoterGroup {
+            registrar: registrar_cookie.address,
+            voter_group,
+            voting_wallet_membership: get_voter_group_membership_address(
+                &registrar_cookie.address,
+                &voting_wallet_cookie.address
+            ),
+            voting_wallet: voting_wallet_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_voter_group_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[create_voter_group_ix],
+            Some(&[&voting_wallet_cookie.signer])
+        ).await?;
+
+        Ok(VoterGroupCookie {
+            address: voter_group,
+            registrar: registrar_cookie.address,
+            members: vec![],
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn join_voter_group(
+        &mut self,
+        voter_group_cookie: &mut VoterGroupCookie,
+        voting_wallet_cookie: &WalletCookie,
+        member_cookie: &WalletCookie
+    ) -> Result<(), BanksClientError> {
+        self.join_voter_group_using_ix(
+            voter_group_cookie,
+            voting_wallet_cookie,
+            member_cookie,
+            NopOverride,
+            None
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn join_voter_group_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        voter_group_cookie: &mut VoterGroupCookie,
+        voting_wallet_cookie: &WalletCookie,
+        member_cookie: &WalletCookie,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::JoinVoterGroup {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::JoinVoterGroup {
+            voter_group: voter_group_cookie.address,
+            voting_wallet: voting_wallet_cookie.address,
+            member: member_cookie.address,
+            voter_group_membership: get_voter_group_membership_address(
+                &voter_group_cookie.registrar,
+                &member_cookie.address
+            ),
+            member_voter_group: get_voter_group_address(
+                &voter_group_cookie.registrar,
+                &member_cookie.address
+            ),
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut join_voter_group_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        instruction_override(&mut join_voter_group_ix);
+
+        let default_signers = &[&voting_wallet_cookie.signer, &member_cookie.signer];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&[join_voter_group_ix], Some(signers)).await?;
+
+        voter_group_cookie.members.push(member_cookie.address);
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn leave_voter_group(
+        &mut self,
+        voter_group_cookie: &mut VoterGroupCookie,
+        member: &Pubkey,
+        authority_cookie: &WalletCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::LeaveVoterGroup { member: *member })
+        );
+
+        let accounts = gpl_nft_voter::accounts::LeaveVoterGroup {
+            voter_group: voter_group_cookie.address,
+            voter_group_membership: get_voter_group_membership_address(
+                &voter_group_cookie.registrar,
+                member
+            ),
+            authority: authority_cookie.address,
+            beneficiary: self.bench.payer.pubkey(),
+        };
+
+        let leave_voter_group_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[leave_voter_group_ix],
+            Some(&[&authority_cookie.signer])
+        ).await?;
+
+        voter_group_cookie.members.retain(|m| m != member);
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_voter_rent_escrow(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &WalletCookie,
+        lamports: u64
+    ) -> Result<Pubkey, BanksClientError> {
+        let voter_rent_escrow = get_voter_rent_escrow_address(
+            &registrar_cookie.address,
+            &voter_cookie.address
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateVoterRentEscrow { lamports })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateVoterRentEscrow {
+            registrar: registrar_cookie.address,
+            voter_rent_escrow,
+            governing_token_owner: voter_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_voter_rent_escrow_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[create_voter_rent_escrow_ix],
+            Some(&[&voter_cookie.signer])
+        ).await?;
+
+        Ok(voter_rent_escrow)
+    }
+
+    #[allow(dead_code)]
+    pub async fn close_voter_rent_escrow(
+        &mut self,
+        voter_rent_escrow: &Pubkey,
+        voter_cookie: &WalletCookie,
+        beneficiary: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CloseVoterRentEscrow {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::CloseVoterRentEscrow {
+            voter_rent_escrow: *voter_rent_escrow,
+            governing_token_owner: voter_cookie.address,
+            beneficiary: *beneficiary,
+        };
+
+        let close_voter_rent_escrow_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[close_voter_rent_escrow_ix],
+            Some(&[&voter_cookie.signer])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_voting_session(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &WalletCookie,
+        session_authority: &Pubkey,
+        expires_at_slot: u64
+    ) -> Result<Pubkey, BanksClientError> {
+        let voting_session = get_voting_session_address(
+            &registrar_cookie.address,
+            &voter_cookie.address
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateVotingSession {
+                session_authority: *session_authority,
+                expires_at_slot,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateVotingSession {
+            registrar: registrar_cookie.address,
+            voting_session,
+            governing_token_owner: voter_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_voting_session_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[create_voting_session_ix],
+            Some(&[&voter_cookie.signer])
+        ).await?;
+
+        Ok(voting_session)
+    }
+
+    #[allow(dead_code)]
+    pub async fn revoke_voting_session(
+        &mut self,
+        voting_session: &Pubkey,
+        authority_cookie: &WalletCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::RevokeVotingSession {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::RevokeVotingSession {
+            voting_session: *voting_session,
+            authority: authority_cookie.address,
+            beneficiary: authority_cookie.address,
+        };
+
+        let revoke_voting_session_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[revoke_voting_session_ix],
+            Some(&[&authority_cookie.signer])
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn close_expired_delegation(
+        &mut self,
+        cold_wallet_link: &Pubkey,
+        hot_wallet: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CloseExpiredDelegation {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::CloseExpiredDelegation {
+            cold_wallet_link: *cold_wallet_link,
+            hot_wallet: *hot_wallet,
+        };
+
+        let close_expired_delegation_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[close_expired_delegation_ix], None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn validate_realm_config(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_addin: bool,
+        max_voter_weight_addin: bool
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ValidateRealmConfig {
+                voter_weight_addin,
+                max_voter_weight_addin,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ValidateRealmConfig {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_config: get_realm_config_address(
+                &self.governance.program_id,
+                &registrar_cookie.account.realm
+            ),
+        };
+
+        let validate_realm_config_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[validate_realm_config_ix], None).await
+    }
+
+    /// Validates the Registrar and returns the RegistrarWarnings return data
+    #[allow(dead_code)]
+    pub async fn validate_registrar(
+        &self,
+        registrar_cookie: &RegistrarCookie
+    ) -> Result<RegistrarWarnings, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ValidateRegistrar {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::ValidateRegistrar {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+        };
+
+        let validate_registrar_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        let return_data = self.bench
+            .process_transaction_with_return_data(&[validate_registrar_ix], None).await?
+            .expect("validate_registrar must return the warnings");
+
+        Ok(RegistrarWarnings::try_from_slice(&return_data).unwrap())
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_ballot_tree(
+        &mut self,
+        registrar_cookie: &RegistrarCookie
+    ) -> Result<BallotTreeCookie, BanksClientError> {
+        let max_depth = 5;
+        let max_buffer_size = 8;
+
+        let merkle_tree = Keypair::new();
+        let tree_authority = self.merkle_tree.get_tree_authority_address(&merkle_tree.pubkey());
+        let ballot_tree = get_ballot_tree_address(&registrar_cookie.address, &merkle_tree.pubkey());
+
+        let merkle_tree_size = self.merkle_tree.merkle_tree_account_size(
+            max_depth as usize,
+            max_buffer_size as usize
+        );
+
+        let tree_alloc_ix = system_instruction::create_account(
+            &self.bench.payer.pubkey(),
+            &merkle_tree.pubkey(),
+            self.bench.rent.minimum_balance(merkle_tree_size),
+            merkle_tree_size as u64,
+            &spl_account_compression::id()
+        );
+
+        let name = "Ballot".to_string();
+        let symbol = "BLT".to_string();
+        let uri = "https://www.ballot-nfts.com/".to_string();
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateBallotTree {
+                max_depth,
+                max_buffer_size,
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateBallotTree {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            ballot_tree,
+            tree_authority,
+            merkle_tree: merkle_tree.pubkey(),
+            payer: self.bench.payer.pubkey(),
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_ballot_tree_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        // The merkle tree is allocated in the same transaction
+        self.bench.process_transaction(
+            &[tree_alloc_ix, create_ballot_tree_ix],
+            Some(&[&merkle_tree, &registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(BallotTreeCookie {
+            address: ballot_tree,
+            merkle_tree: merkle_tree.pubkey(),
+            tree_authority,
+            proof_tree: MerkleTree::new(vec![Node::default(); 1 << max_depth].as_slice()),
+            account: BallotTree {
+                registrar: registrar_cookie.address,
+                merkle_tree: merkle_tree.pubkey(),
+                name,
+                symbol,
+                uri,
+                ballots_minted: 0,
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn mint_ballot(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        ballot_tree_cookie: &mut BallotTreeCookie,
+        member_cookie: &WalletCookie
+    ) -> Result<LeafArgs, BanksClientError> {
+        self.mint_ballot_using_ix(
+            registrar_cookie,
+            ballot_tree_cookie,
+            member_cookie,
+            NopOverride,
+            None
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn mint_ballot_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        ballot_tree_cookie: &mut BallotTreeCookie,
+        member_cookie: &WalletCookie,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<LeafArgs, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(&(gpl_nft_voter::instruction::MintBallot {}));
+
+        let accounts = gpl_nft_voter::accounts::MintBallot {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            ballot_tree: ballot_tree_cookie.address,
+            tree_authority: ballot_tree_cookie.tree_authority,
+            merkle_tree: ballot_tree_cookie.merkle_tree,
+            leaf_owner: member_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut mint_ballot_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        instruction_override(&mut mint_ballot_ix);
+
+        let default_signers = &[&registrar_cookie.realm_authority];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&[mint_ballot_ix], Some(signers)).await?;
+
+        Ok(ballot_tree_cookie.add_ballot(&member_cookie.address))
+    }
+
+    #[allow(dead_code)]
+    pub async fn mint_vote_receipt(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        ballot_tree_cookie: &mut BallotTreeCookie,
+        proposal_cookie: &ProposalCookie,
+        voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie
+    ) -> Result<(Pubkey, LeafArgs), BanksClientError> {
+        self.mint_vote_receipt_using_ix(
+            registrar_cookie,
+            ballot_tree_cookie,
+            proposal_cookie,
+            voter_cookie,
+            voter_token_owner_record_cookie,
+            NopOverride
+        ).await
+    }
+
+    /// Mints the vote receipt of the voter and returns the VoteReceipt address and the leaf args of the receipt
+    #[allow(dead_code)]
+    pub async fn mint_vote_receipt_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        ballot_tree_cookie: &mut BallotTreeCookie,
+        proposal_cookie: &ProposalCookie,
+        voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        instruction_override: F
+    ) -> Result<(Pubkey, LeafArgs), BanksClientError> {
+        let vote_receipt = get_vote_receipt_address(
+            &registrar_cookie.address,
+            &proposal_cookie.address,
+            &voter_cookie.address
+        );
+
+        let vote_record = vote_record::get_vote_record_address(
+            &self.governance.program_id,
+            &proposal_cookie.address,
+            &voter_token_owner_record_cookie.address
+        );
+
+        let data = anchor_lang::InstructionData::data(&(gpl_nft_voter::instruction::MintVoteReceipt {}));
+
+        let accounts = gpl_nft_voter::accounts::MintVoteReceipt {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            governance: proposal_cookie.account.governance,
+            proposal: proposal_cookie.address,
+            vote_record,
+            governing_token_owner: voter_cookie.address,
+            vote_receipt,
+            ballot_tree: ballot_tree_cookie.address,
+            tree_authority: ballot_tree_cookie.tree_authority,
+            merkle_tree: ballot_tree_cookie.merkle_tree,
+            payer: self.bench.payer.pubkey(),
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut mint_vote_receipt_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        instruction_override(&mut mint_vote_receipt_ix);
+
+        self.bench.process_transaction(&[mint_vote_receipt_ix], None).await?;
+
+        let vote = self.governance
+            .get_vote_record(proposal_cookie, voter_token_owner_record_cookie).await.vote;
+
+        Ok((
+            vote_receipt,
+            ballot_tree_cookie.add_vote_receipt(&voter_cookie.address, &proposal_cookie.address, &vote),
+        ))
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_vote_receipt(&self, vote_receipt: &Pubkey) -> VoteReceipt {
+        self.bench.get_anchor_account::<VoteReceipt>(*vote_receipt).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_campaign(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        ballot_tree_cookie: &BallotTreeCookie,
+        proposal_cookie: &ProposalCookie,
+        allowlist_root: [u8; 32],
+        ballot_weight: u64
+    ) -> Result<CampaignCookie, BanksClientError> {
+        let campaign = get_campaign_address(&registrar_cookie.address, &proposal_cookie.address);
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateCampaign {
+                proposal: proposal_cookie.address,
+                allowlist_root,
+                ballot_weight,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateCampaign {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            ballot_tree: ballot_tree_cookie.address,
+            campaign,
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_campaign_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[create_campaign_ix],
+            Some(&[&registrar_cookie.realm_authority])
+        ).await?;
+
+        Ok(CampaignCookie {
+            address: campaign,
+            account: Campaign {
+                registrar: registrar_cookie.address,
+                ballot_tree: ballot_tree_cookie.address,
+                proposal: proposal_cookie.address,
+                allowlist_root,
+                ballot_weight,
+                ballots_claimed: 0,
+                ballots_cast: 0,
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn claim_ballot(
+        &mut self,
+        campaign_cookie: &CampaignCookie,
+        ballot_tree_cookie: &mut BallotTreeCookie,
+        claimant_cookie: &WalletCookie,
+        allowlist_proof: Vec<[u8; 32]>
+    ) -> Result<LeafArgs, BanksClientError> {
+        let campaign_claim = get_campaign_claim_address(
+            &campaign_cookie.address,
+            &claimant_cookie.address
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ClaimBallot { allowlist_proof })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ClaimBallot {
+            campaign: campaign_cookie.address,
+            ballot_tree: ballot_tree_cookie.address,
+            campaign_claim,
+            claimant: claimant_cookie.address,
+            tree_authority: ballot_tree_cookie.tree_authority,
+            merkle_tree: ballot_tree_cookie.merkle_tree,
+            payer: self.bench.payer.pubkey(),
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let claim_ballot_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[claim_ballot_ix], Some(&[&claimant_cookie.signer])).await?;
+
+        Ok(ballot_tree_cookie.add_ballot(&claimant_cookie.address))
+    }
+
+    #[allow(dead_code)]
+    pub async fn cast_ballot_vote(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        campaign_cookie: &CampaignCookie,
+        ballot_tree_cookie: &mut BallotTreeCookie,
+        proposal_cookie: &ProposalCookie,
+        voter_cookie: &WalletCookie,
+        ballot: &LeafArgs
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CastBallotVote {
+                proposal: proposal_cookie.address,
+                root: ballot_tree_cookie.proof_tree.get_root(),
+                nonce: ballot.nonce,
+                index: ballot.index,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CastBallotVote {
+            voter: gpl_nft_voter::accounts::VoterAccounts {
+                registrar: registrar_cookie.address,
+                registrar_extension: registrar_cookie.extension_address,
+                voter_weight_record: voter_weight_record_cookie.address,
+                voter_authority: voter_cookie.address,
+            },
+            campaign: campaign_cookie.address,
+            ballot_tree: ballot_tree_cookie.address,
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_cookie.address
+            ),
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+            tree_authority: ballot_tree_cookie.tree_authority,
+            merkle_tree: ballot_tree_cookie.merkle_tree,
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(registrar_cookie, &voter_cookie.address)
+        );
+
+        let proof = ballot_tree_cookie.proof_tree.get_proof_of_leaf(ballot.index as usize);
+        account_metas.extend(
+            proof.iter().map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+        );
+
+        let cast_ballot_vote_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(&[cast_ballot_vote_ix], Some(&[&voter_cookie.signer])).await?;
+
+        // The burnt ballot leaf is replaced with the empty node
+        ballot_tree_cookie.proof_tree.add_leaf(Node::default(), ballot.index as usize);
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_weight_snapshot(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        proposal_cookie: &ProposalCookie,
+        authority: &Keypair,
+        snapshot_slot: u64,
+        holdings_root: [u8; 32],
+        total_weight: u64
+    ) -> Result<WeightSnapshotCookie, BanksClientError> {
+        let weight_snapshot = get_weight_snapshot_address(
+            &registrar_cookie.address,
+            &proposal_cookie.address
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateWeightSnapshot {
+                proposal: proposal_cookie.address,
+                snapshot_slot,
+                holdings_root,
+                total_weight,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CreateWeightSnapshot {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            authority: authority.pubkey(),
+            weight_snapshot,
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let create_weight_snapshot_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[create_weight_snapshot_ix], Some(&[authority])).await?;
+
+        Ok(WeightSnapshotCookie {
+            address: weight_snapshot,
+            account: WeightSnapshot {
+                registrar: registrar_cookie.address,
+                proposal: proposal_cookie.address,
+                snapshot_slot,
+                holdings_root,
+                total_weight,
+                authority: authority.pubkey(),
+            },
+        })
+    }
+
+    #[allow(dead_code)]
+    pub async fn cast_snapshot_vote(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        weight_snapshot_cookie: &WeightSnapshotCookie,
+        proposal_cookie: &ProposalCookie,
+        voter_cookie: &WalletCookie,
+        voter_weight: u64,
+        proof: Vec<[u8; 32]>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CastSnapshotVote {
+                proposal: proposal_cookie.address,
+                voter_weight,
+                proof,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::CastSnapshotVote {
+            voter: gpl_nft_voter::accounts::VoterAccounts {
+                registrar: registrar_cookie.address,
+                registrar_extension: registrar_cookie.extension_address,
+                voter_weight_record: voter_weight_record_cookie.address,
+                voter_authority: voter_cookie.address,
+            },
+            weight_snapshot: weight_snapshot_cookie.address,
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_cookie.address
+            ),
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(registrar_cookie, &voter_cookie.address)
+        );
+
+        let cast_snapshot_vote_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(&[cast_snapshot_vote_ix], Some(&[&voter_cookie.signer])).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn write_external_voter_weight(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        external_voter_weight_record: &Pubkey,
+        weight_export_program: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::WriteExternalVoterWeight {})
+        );
+
+        let accounts = gpl_nft_voter::accounts::WriteExternalVoterWeight {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            external_voter_weight_record: *external_voter_weight_record,
+            weight_export_authority: get_weight_export_authority_address(&registrar_cookie.address),
+            weight_export_program: *weight_export_program,
+        };
+
+        let write_external_voter_weight_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        self.bench.process_transaction(&[write_external_voter_weight_ix], None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar_config(
+        &mut self,
+        registrar_cookie: &mut RegistrarCookie,
+        config: RegistrarConfig
+    ) -> Result<(), BanksClientError> {
+        self.with_registrar_config_using_ix(registrar_cookie, config, NopOverride, None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_registrar_config_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &mut RegistrarCookie,
+        config: RegistrarConfig,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ConfigureRegistrar { config })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ConfigureRegistrar {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+        };
+
+        let mut configure_registrar_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        instruction_override(&mut configure_registrar_ix);
+
+        let default_signers = &[&registrar_cookie.realm_authority];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&[configure_registrar_ix], Some(signers)).await?;
+
+        registrar_cookie.extension.config = config;
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn extend_account(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        account: &Pubkey,
+        extra_space: u32
+    ) -> Result<(), BanksClientError> {
+        self.extend_account_using_ix(registrar_cookie, account, extra_space, NopOverride, None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn extend_account_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        account: &Pubkey,
+        extra_space: u32,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::ExtendAccount { extra_space })
+        );
+
+        let accounts = gpl_nft_voter::accounts::ExtendAccount {
+            registrar: registrar_cookie.address,
+            realm: registrar_cookie.account.realm,
+            realm_authority: registrar_cookie.realm_authority.pubkey(),
+            account: *account,
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut extend_account_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
         };
 
-        Ok(CollectionConfigCookie { collection_config })
+        instruction_override(&mut extend_account_ix);
+
+        let default_signers = &[&registrar_cookie.realm_authority];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&[extend_account_ix], Some(signers)).await
+    }
+
+    /// Casts NFT Vote and spl-gov Vote
+    #[allow(dead_code)]
+    pub async fn cast_nft_vote(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        args: Option<CastNftVoteArgs>
+    ) -> Result<Vec<NftVoteRecordCookie>, BanksClientError> {
+        let (nft_vote_record_cookies, _) = self.cast_nft_vote_with_return_data(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            max_voter_weight_record_cookie,
+            proposal_cookie,
+            nft_voter_cookie,
+            voter_token_owner_record_cookie,
+            nft_action_ticket_cookies,
+            args
+        ).await?;
+
+        Ok(nft_vote_record_cookies)
+    }
+
+    /// Casts NFT vote and returns the return data set by the transaction
+    #[allow(dead_code)]
+    pub async fn cast_nft_vote_with_return_data(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        args: Option<CastNftVoteArgs>
+    ) -> Result<(Vec<NftVoteRecordCookie>, Option<Vec<u8>>), BanksClientError> {
+        self.cast_nft_vote_using_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            max_voter_weight_record_cookie,
+            proposal_cookie,
+            nft_voter_cookie,
+            voter_token_owner_record_cookie,
+            nft_action_ticket_cookies,
+            args,
+            NopOverride
+        ).await
+    }
+
+    /// Casts NFT vote with the NFT voter instruction overridden by the given instruction_override
+    #[allow(dead_code)]
+    pub async fn cast_nft_vote_using_ix<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        args: Option<CastNftVoteArgs>,
+        instruction_override: F
+    ) -> Result<(Vec<NftVoteRecordCookie>, Option<Vec<u8>>), BanksClientError> {
+        let (instructions, nft_vote_record_cookies) = self.cast_nft_vote_ixs(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            max_voter_weight_record_cookie,
+            proposal_cookie,
+            nft_voter_cookie,
+            voter_token_owner_record_cookie,
+            nft_action_ticket_cookies,
+            &args.unwrap_or_default(),
+            instruction_override
+        );
+
+        let return_data = self.bench.process_transaction_with_return_data(
+            &instructions,
+            Some(&[&nft_voter_cookie.signer])
+        ).await?;
+
+        Ok((nft_vote_record_cookies, return_data))
     }
 
-    /// Casts NFT Vote and spl-gov Vote
+    /// Casts NFT vote and returns the program logs of the transaction
     #[allow(dead_code)]
-    pub async fn cast_nft_vote(
+    pub async fn cast_nft_vote_with_logs(
         &mut self,
         registrar_cookie: &RegistrarCookie,
         voter_weight_record_cookie: &VoterWeightRecordCookie,
@@ -496,62 +3215,272 @@ impl NftVoterTest {
         voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
         nft_action_ticket_cookies: &[&NftVoteTicketCookie],
         args: Option<CastNftVoteArgs>
-    ) -> Result<Vec<NftVoteRecordCookie>, BanksClientError> {
-        let args = args.unwrap_or_default();
+    ) -> Result<TransactionLogs, TransactionFailure> {
+        let (instructions, _) = self.cast_nft_vote_ixs(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            max_voter_weight_record_cookie,
+            proposal_cookie,
+            nft_voter_cookie,
+            voter_token_owner_record_cookie,
+            nft_action_ticket_cookies,
+            &args.unwrap_or_default(),
+            NopOverride
+        );
+
+        self.bench.process_transaction_with_logs(
+            &instructions,
+            Some(&[&nft_voter_cookie.signer])
+        ).await
+    }
+
+    /// Returns cast_nft_vote instruction followed by spl-gov CastVote when args.cast_spl_gov_vote is set
+    #[allow(dead_code)]
+    fn cast_nft_vote_ixs<F: Fn(&mut Instruction)>(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie],
+        args: &CastNftVoteArgs,
+        instruction_override: F
+    ) -> (Vec<Instruction>, Vec<NftVoteRecordCookie>) {
         let data = anchor_lang::InstructionData::data(
             &(gpl_nft_voter::instruction::CastNftVote {
                 proposal: proposal_cookie.address,
+                simulate_only: args.simulate_only,
+                memo: args.memo.clone(),
+                use_voter_rent_escrow: args.voter_rent_escrow.is_some(),
+                use_voting_session: args.voting_session.is_some(),
+                vote_kind: args.vote_kind,
+                use_voter_group: args.voter_group.is_some(),
             })
         );
 
         let accounts = gpl_nft_voter::accounts::CastNftVote {
             registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
             voter_weight_record: voter_weight_record_cookie.address,
             voter_token_owner_record: voter_token_owner_record_cookie.address,
             voter_authority: nft_voter_cookie.address,
             payer: self.bench.payer.pubkey(),
             system_program: solana_sdk::system_program::id(),
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            ),
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+            // Simulated votes don't create or update the RegistrarMetrics
+            registrar_metrics: (!args.simulate_only).then(|| {
+                get_registrar_metrics_address(
+                    &registrar_cookie.address,
+                    &voter_weight_record_cookie.account.governing_token_owner
+                )
+            }),
         };
 
         let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
         let mut nft_vote_record_cookies = vec![];
 
+        if let Some(voting_session) = args.voting_session {
+            account_metas.push(AccountMeta::new_readonly(voting_session, false));
+        }
+
+        if let Some(voter_group) = &args.voter_group {
+            account_metas.push(AccountMeta::new_readonly(voter_group.address, false));
+
+            for member in &voter_group.members {
+                account_metas.push(
+                    AccountMeta::new_readonly(
+                        get_voter_freeze_record_address(&registrar_cookie.address, member),
+                        false
+                    )
+                );
+            }
+        }
+
+        if let Some(voter_rent_escrow) = args.voter_rent_escrow {
+            account_metas.push(AccountMeta::new(voter_rent_escrow, false));
+        }
+
+        if registrar_cookie.extension.config.record_voter_checkpoints {
+            let voter_checkpoint = get_voter_checkpoint_address(
+                &registrar_cookie.address,
+                &proposal_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            );
+            account_metas.push(AccountMeta::new(voter_checkpoint, false));
+        }
+
         for nft_action_ticket_cookie in nft_action_ticket_cookies {
             let nft_mint = &nft_action_ticket_cookie.nft_mint;
 
             let nft_action_ticket = nft_action_ticket_cookie.address;
             let nft_action_ticket_info = AccountMeta::new(nft_action_ticket, false);
 
-            let nft_vote_record = get_nft_vote_record_address(&proposal_cookie.address, &nft_mint);
+            let nft_vote_record = get_nft_vote_record_address(
+                &registrar_cookie.address,
+                &proposal_cookie.address,
+                &nft_mint
+            );
             let nft_vote_record_info = AccountMeta::new(nft_vote_record, false);
 
             account_metas.push(nft_action_ticket_info);
             account_metas.push(nft_vote_record_info);
 
-            let account = NftVoteRecord {
-                proposal: proposal_cookie.address,
-                nft_mint: nft_mint.clone(),
-                governing_token_owner: voter_weight_record_cookie.account.governing_token_owner,
-                account_discriminator: NftVoteRecord::ACCOUNT_DISCRIMINATOR,
-                reserved: [0; 8],
+            if args.legacy_nft_vote_records {
+                let legacy_nft_vote_record = get_legacy_nft_vote_record_address(
+                    &proposal_cookie.address,
+                    &nft_mint
+                );
+                account_metas.push(AccountMeta::new_readonly(legacy_nft_vote_record, false));
+            }
+
+            let collection_vote_weight_record = args.capped_collection.map(|collection| {
+                get_collection_vote_weight_record_address(
+                    &registrar_cookie.address,
+                    &proposal_cookie.address,
+                    &collection
+                )
+            });
+
+            if let Some(collection_vote_weight_record) = collection_vote_weight_record {
+                account_metas.push(AccountMeta::new(collection_vote_weight_record, false));
+            }
+
+            if let Some(collection) = args.stats_collection {
+                let collection_stats = get_collection_stats_address(
+                    &registrar_cookie.address,
+                    &collection
+                );
+                account_metas.push(AccountMeta::new(collection_stats, false));
+            }
+
+            let account = NftVoteRecord::new(
+                proposal_cookie.address,
+                nft_mint.clone(),
+                voter_weight_record_cookie.account.governing_token_owner,
+                args.vote_kind
+            );
+
+            // The records of the NFTs counted in a CollectionVoteWeightRecord keep the weight they contributed
+            let account = if collection_vote_weight_record.is_some() {
+                NftVoteRecord {
+                    version: NftVoteRecordVersion::V3,
+                    ..account
+                }
+            } else {
+                account
             };
 
             nft_vote_record_cookies.push(NftVoteRecordCookie {
                 address: nft_vote_record,
                 account,
+                collection_vote_weight_record,
             });
         }
 
-        let cast_nft_vote_ix = Instruction {
+        let mut cast_nft_vote_ix = Instruction {
             program_id: gpl_nft_voter::id(),
             accounts: account_metas,
             data,
         };
 
+        instruction_override(&mut cast_nft_vote_ix);
+
         let mut instruction = vec![cast_nft_vote_ix];
 
         if args.cast_spl_gov_vote {
             // spl-gov cast vote
+            let vote = match args.vote_kind {
+                NftVoteKind::Deny => Vote::Deny,
+                NftVoteKind::Abstain => Vote::Abstain,
+                NftVoteKind::Veto => Vote::Veto,
+                NftVoteKind::Approve | NftVoteKind::Unknown =>
+                    Vote::Approve(
+                        vec![VoteChoice {
+                            rank: 0,
+                            weight_percentage: 100,
+                        }]
+                    ),
+            };
+
+            let cast_vote_ix = cast_vote(
+                &self.governance.program_id,
+                &registrar_cookie.account.realm,
+                &proposal_cookie.account.governance,
+                &proposal_cookie.address,
+                &proposal_cookie.account.token_owner_record,
+                &voter_token_owner_record_cookie.address,
+                &nft_voter_cookie.address,
+                &proposal_cookie.account.governing_token_mint,
+                &self.bench.payer.pubkey(),
+                Some(voter_weight_record_cookie.address),
+                Some(max_voter_weight_record_cookie.address),
+                vote
+            );
+
+            instruction.push(cast_vote_ix);
+        }
+
+        (instruction, nft_vote_record_cookies)
+    }
+
+    /// Finalizes NFT vote accumulated by cast_nft_vote and casts spl-gov Vote in the same transaction
+    #[allow(dead_code)]
+    pub async fn finalize_nft_vote(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        cast_spl_gov_vote: bool
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::FinalizeNftVote {
+                proposal: proposal_cookie.address,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::FinalizeNftVote {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            voter_token_owner_record: voter_token_owner_record_cookie.address,
+            voter_authority: nft_voter_cookie.address,
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &voter_weight_record_cookie.account.governing_token_owner
+            ),
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+            instructions: solana_sdk::sysvar::instructions::id(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(
+                registrar_cookie,
+                &voter_weight_record_cookie.account.governing_token_owner
+            )
+        );
+
+        let finalize_nft_vote_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        let mut instruction = vec![finalize_nft_vote_ix];
+
+        if cast_spl_gov_vote {
             let vote = Vote::Approve(
                 vec![VoteChoice {
                     rank: 0,
@@ -577,46 +3506,326 @@ impl NftVoterTest {
             instruction.push(cast_vote_ix);
         }
 
-        self.bench.process_transaction(&instruction, Some(&[&nft_voter_cookie.signer])).await?;
+        self.bench.process_transaction(&instruction, Some(&[&nft_voter_cookie.signer])).await
+    }
+
+    /// Casts NFT vote on the given Proposals and returns the NftVoteRecords created for all of them
+    #[allow(dead_code)]
+    pub async fn cast_nft_vote_multi(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        proposal_cookies: &[&ProposalCookie],
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        nft_action_ticket_cookies: &[&NftVoteTicketCookie]
+    ) -> Result<Vec<NftVoteRecordCookie>, BanksClientError> {
+        let proposals = proposal_cookies
+            .iter()
+            .map(|pc| pc.address)
+            .collect::<Vec<Pubkey>>();
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CastNftVoteMulti {
+                proposals: proposals.clone(),
+                vote_kind: NftVoteKind::Approve,
+            })
+        );
+
+        let governing_token_owner = voter_weight_record_cookie.account.governing_token_owner;
+
+        let accounts = gpl_nft_voter::accounts::CastNftVoteMulti {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            voter_token_owner_record: voter_token_owner_record_cookie.address,
+            voter_authority: nft_voter_cookie.address,
+            multi_proposal_vote: get_multi_proposal_vote_address(
+                &registrar_cookie.address,
+                &governing_token_owner
+            ),
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &governing_token_owner
+            ),
+            governance: proposal_cookies[0].account.governance,
+            registrar_metrics: get_registrar_metrics_address(
+                &registrar_cookie.address,
+                &governing_token_owner
+            ),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+        let mut nft_vote_record_cookies = vec![];
+
+        for proposal in proposals.iter() {
+            account_metas.push(AccountMeta::new_readonly(*proposal, false));
+        }
+
+        if registrar_cookie.extension.config.record_voter_checkpoints {
+            for proposal in proposals.iter() {
+                let voter_checkpoint = get_voter_checkpoint_address(
+                    &registrar_cookie.address,
+                    proposal,
+                    &governing_token_owner
+                );
+                account_metas.push(AccountMeta::new(voter_checkpoint, false));
+            }
+        }
+
+        for nft_action_ticket_cookie in nft_action_ticket_cookies {
+            let nft_mint = &nft_action_ticket_cookie.nft_mint;
+
+            account_metas.push(AccountMeta::new(nft_action_ticket_cookie.address, false));
+
+            for proposal in proposals.iter() {
+                let nft_vote_record = get_nft_vote_record_address(
+                    &registrar_cookie.address,
+                    proposal,
+                    nft_mint
+                );
+                account_metas.push(AccountMeta::new(nft_vote_record, false));
+
+                nft_vote_record_cookies.push(NftVoteRecordCookie {
+                    address: nft_vote_record,
+                    account: NftVoteRecord::new(
+                        *proposal,
+                        *nft_mint,
+                        governing_token_owner,
+                        NftVoteKind::Approve
+                    ),
+                    collection_vote_weight_record: None,
+                });
+            }
+        }
+
+        let cast_nft_vote_multi_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[cast_nft_vote_multi_ix],
+            Some(&[&nft_voter_cookie.signer])
+        ).await?;
 
         Ok(nft_vote_record_cookies)
     }
 
+    /// Selects the weight of MultiProposalVote for the Proposal and casts spl-gov Vote in the same transaction
+    #[allow(dead_code)]
+    pub async fn select_multi_proposal_vote(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+        proposal_cookie: &ProposalCookie,
+        nft_voter_cookie: &WalletCookie,
+        voter_token_owner_record_cookie: &TokenOwnerRecordCookie
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::SelectMultiProposalVote {
+                proposal: proposal_cookie.address,
+            })
+        );
+
+        let governing_token_owner = voter_weight_record_cookie.account.governing_token_owner;
+
+        let accounts = gpl_nft_voter::accounts::SelectMultiProposalVote {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            voter_weight_record: voter_weight_record_cookie.address,
+            voter_token_owner_record: voter_token_owner_record_cookie.address,
+            voter_authority: nft_voter_cookie.address,
+            multi_proposal_vote: get_multi_proposal_vote_address(
+                &registrar_cookie.address,
+                &governing_token_owner
+            ),
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &governing_token_owner
+            ),
+            governance: proposal_cookie.account.governance,
+            target_proposal: proposal_cookie.address,
+            beneficiary: self.bench.payer.pubkey(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        account_metas.extend(
+            get_voter_weight_adjustment_account_metas(registrar_cookie, &governing_token_owner)
+        );
+
+        let select_multi_proposal_vote_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        let vote = Vote::Approve(
+            vec![VoteChoice {
+                rank: 0,
+                weight_percentage: 100,
+            }]
+        );
+
+        let cast_vote_ix = cast_vote(
+            &self.governance.program_id,
+            &registrar_cookie.account.realm,
+            &proposal_cookie.account.governance,
+            &proposal_cookie.address,
+            &proposal_cookie.account.token_owner_record,
+            &voter_token_owner_record_cookie.address,
+            &nft_voter_cookie.address,
+            &proposal_cookie.account.governing_token_mint,
+            &self.bench.payer.pubkey(),
+            Some(voter_weight_record_cookie.address),
+            Some(max_voter_weight_record_cookie.address),
+            vote
+        );
+
+        self.bench.process_transaction(
+            &[select_multi_proposal_vote_ix, cast_vote_ix],
+            Some(&[&nft_voter_cookie.signer])
+        ).await
+    }
+
+    /// Migrates the voter from the previous wallet to the new wallet with the given ColdWalletLinks and NftVoteRecords
+    #[allow(dead_code)]
+    pub async fn migrate_voter(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        previous_wallet_cookie: &WalletCookie,
+        previous_token_owner_record_cookie: &TokenOwnerRecordCookie,
+        new_wallet_cookie: &WalletCookie,
+        cold_wallet_links: &[Pubkey],
+        nft_vote_record_cookies: &[&NftVoteRecordCookie]
+    ) -> Result<Pubkey, BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::MigrateVoter {
+                cold_wallet_links_count: cold_wallet_links.len() as u8,
+            })
+        );
+
+        let voter_migration = get_voter_migration_address(
+            &registrar_cookie.address,
+            &new_wallet_cookie.address
+        );
+
+        let accounts = gpl_nft_voter::accounts::MigrateVoter {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            previous_wallet: previous_wallet_cookie.address,
+            new_wallet: new_wallet_cookie.address,
+            voter_migration,
+            voter_freeze_record: get_voter_freeze_record_address(
+                &registrar_cookie.address,
+                &previous_wallet_cookie.address
+            ),
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let mut account_metas = anchor_lang::ToAccountMetas::to_account_metas(&accounts, None);
+
+        for cold_wallet_link in cold_wallet_links {
+            account_metas.push(AccountMeta::new(*cold_wallet_link, false));
+        }
+
+        for nft_vote_record_cookie in nft_vote_record_cookies {
+            let proposal = nft_vote_record_cookie.account.proposal;
+
+            let vote_record_key = vote_record::get_vote_record_address(
+                &self.governance.program_id,
+                &proposal,
+                &previous_token_owner_record_cookie.address
+            );
+
+            account_metas.push(AccountMeta::new(nft_vote_record_cookie.address, false));
+            account_metas.push(AccountMeta::new_readonly(proposal, false));
+            account_metas.push(AccountMeta::new_readonly(vote_record_key, false));
+        }
+
+        let migrate_voter_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: account_metas,
+            data,
+        };
+
+        self.bench.process_transaction(
+            &[migrate_voter_ix],
+            Some(&[&previous_wallet_cookie.signer, &new_wallet_cookie.signer])
+        ).await?;
+
+        Ok(voter_migration)
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_create_nft_action_ticket(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_cookie: &WalletCookie,
+        nft_cookies: &[&NftCookie],
+        action: &VoterWeightAction
+    ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
+        self.with_create_nft_action_ticket_using_ix(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_cookie,
+            nft_cookies,
+            action,
+            NopOverride,
+            None
+        ).await
+    }
+
     #[allow(dead_code)]
-    pub async fn with_create_nft_action_ticket(
+    pub async fn with_create_nft_action_ticket_using_ix<F: Fn(&mut Instruction)>(
         &mut self,
         registrar_cookie: &RegistrarCookie,
         voter_weight_record_cookie: &VoterWeightRecordCookie,
         voter_cookie: &WalletCookie,
         nft_cookies: &[&NftCookie],
-        action: &VoterWeightAction
+        action: &VoterWeightAction,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
     ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
-        self.with_create_nft_action_ticket_using_ix(
+        self.with_create_nft_action_ticket_using_voter_allowlist_proof(
             registrar_cookie,
             voter_weight_record_cookie,
             voter_cookie,
             nft_cookies,
             action,
-            NopOverride,
-            None
+            vec![],
+            instruction_override,
+            signers_override
         ).await
     }
 
+    /// Creates NFT action tickets with the given merkle proof of the voter in RegistrarExtension.config.voter_allowlist_root
     #[allow(dead_code)]
-    pub async fn with_create_nft_action_ticket_using_ix<F: Fn(&mut Instruction)>(
+    pub async fn with_create_nft_action_ticket_using_voter_allowlist_proof<F: Fn(&mut Instruction)>(
         &mut self,
         registrar_cookie: &RegistrarCookie,
         voter_weight_record_cookie: &VoterWeightRecordCookie,
         voter_cookie: &WalletCookie,
         nft_cookies: &[&NftCookie],
         action: &VoterWeightAction,
+        voter_allowlist_proof: Vec<[u8; 32]>,
         instruction_override: F,
         signers_override: Option<&[&Keypair]>
     ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
         let accounts = gpl_nft_voter::accounts::CreateNftActionTicket {
-            registrar: registrar_cookie.address,
-            voter_weight_record: voter_weight_record_cookie.address,
-            voter_authority: voter_cookie.address,
+            voter: gpl_nft_voter::accounts::VoterAccounts {
+                registrar: registrar_cookie.address,
+                registrar_extension: registrar_cookie.extension_address,
+                voter_weight_record: voter_weight_record_cookie.address,
+                voter_authority: voter_cookie.address,
+            },
             payer: self.bench.payer.pubkey(),
             system_program: solana_sdk::system_program::id(),
         };
@@ -624,6 +3833,7 @@ impl NftVoterTest {
         let data = anchor_lang::InstructionData::data(
             &(gpl_nft_voter::instruction::CreateNftActionTicket {
                 voter_weight_action: action.clone(),
+                voter_allowlist_proof,
             })
         );
 
@@ -662,6 +3872,71 @@ impl NftVoterTest {
         Ok(nft_action_ticket_cookies)
     }
 
+    /// Creates NFT action tickets for the given Metaplex Core assets
+    #[allow(dead_code)]
+    pub async fn with_create_core_asset_action_ticket(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_cookie: &WalletCookie,
+        core_assets: &[&Pubkey],
+        action: &VoterWeightAction
+    ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
+        let accounts = gpl_nft_voter::accounts::CreateCoreAssetActionTicket {
+            voter: gpl_nft_voter::accounts::VoterAccounts {
+                registrar: registrar_cookie.address,
+                registrar_extension: registrar_cookie.extension_address,
+                voter_weight_record: voter_weight_record_cookie.address,
+                voter_authority: voter_cookie.address,
+            },
+            payer: self.bench.payer.pubkey(),
+            system_program: solana_sdk::system_program::id(),
+        };
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::CreateCoreAssetActionTicket {
+                voter_weight_action: action.clone(),
+                voter_allowlist_proof: vec![],
+            })
+        );
+
+        let mut create_core_asset_action_ticket_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        let mut nft_action_ticket_cookies = vec![];
+        let ticket_type = format!("nft-{}-ticket", &action).to_string();
+        for core_asset in core_assets {
+            let nft_action_ticket = get_nft_action_ticket_address(
+                &ticket_type,
+                &registrar_cookie.address,
+                &voter_cookie.address,
+                core_asset
+            ).0;
+
+            create_core_asset_action_ticket_ix.accounts.push(
+                AccountMeta::new_readonly(**core_asset, false)
+            );
+            create_core_asset_action_ticket_ix.accounts.push(
+                AccountMeta::new(nft_action_ticket, false)
+            );
+
+            nft_action_ticket_cookies.push(NftVoteTicketCookie {
+                nft_mint: **core_asset,
+                address: nft_action_ticket,
+            });
+        }
+
+        self.bench.process_transaction(
+            &[create_core_asset_action_ticket_ix],
+            Some(&[&voter_cookie.signer])
+        ).await?;
+
+        Ok(nft_action_ticket_cookies)
+    }
+
     #[allow(dead_code)]
     pub async fn with_create_cnft_action_ticket(
         &mut self,
@@ -699,6 +3974,105 @@ impl NftVoterTest {
         instruction_override: F,
         signers_override: Option<&[&Keypair]>
     ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
+        self.with_create_cnft_action_ticket_using_pre_ixs(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_cookie,
+            leaf_cookies,
+            leaf_verification_cookies,
+            proofs,
+            action,
+            vec![],
+            instruction_override,
+            signers_override
+        ).await
+    }
+
+    /// Creates cNFT action tickets with the given instructions (ex. ed25519 attestations)
+    /// executed before create_cnft_action_ticket in the same transaction
+    #[allow(dead_code)]
+    pub async fn with_create_cnft_action_ticket_using_pre_ixs<F: Fn(&mut Instruction)>(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_cookie: &WalletCookie,
+        leaf_cookies: &[&LeafArgs],
+        leaf_verification_cookies: &[&LeafVerificationCookie],
+        proofs: &[&Vec<AccountMeta>],
+        action: &VoterWeightAction,
+        pre_instructions: Vec<Instruction>,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
+        self.with_create_cnft_action_ticket_using_voter_allowlist_proof(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_cookie,
+            leaf_cookies,
+            leaf_verification_cookies,
+            proofs,
+            action,
+            pre_instructions,
+            vec![],
+            instruction_override,
+            signers_override
+        ).await
+    }
+
+    /// Creates cNFT action tickets with the given merkle proof of the voter in RegistrarExtension.config.voter_allowlist_root
+    #[allow(dead_code)]
+    pub async fn with_create_cnft_action_ticket_using_voter_allowlist_proof<
+        F: Fn(&mut Instruction)
+    >(
+        &mut self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_cookie: &WalletCookie,
+        leaf_cookies: &[&LeafArgs],
+        leaf_verification_cookies: &[&LeafVerificationCookie],
+        proofs: &[&Vec<AccountMeta>],
+        action: &VoterWeightAction,
+        pre_instructions: Vec<Instruction>,
+        voter_allowlist_proof: Vec<[u8; 32]>,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>
+    ) -> Result<Vec<NftVoteTicketCookie>, BanksClientError> {
+        let (instructions, nft_action_ticket_cookies) = self.create_cnft_action_ticket_ixs(
+            registrar_cookie,
+            voter_weight_record_cookie,
+            voter_cookie,
+            leaf_cookies,
+            leaf_verification_cookies,
+            proofs,
+            action,
+            pre_instructions,
+            voter_allowlist_proof,
+            instruction_override
+        );
+
+        let default_signers = &[&voter_cookie.signer];
+        let signers = signers_override.unwrap_or(default_signers);
+
+        self.bench.process_transaction(&instructions, Some(signers)).await?;
+
+        Ok(nft_action_ticket_cookies)
+    }
+
+    /// Returns the given instructions followed by create_cnft_action_ticket and the cookies of the created tickets
+    #[allow(dead_code)]
+    pub fn create_cnft_action_ticket_ixs<F: Fn(&mut Instruction)>(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_weight_record_cookie: &VoterWeightRecordCookie,
+        voter_cookie: &WalletCookie,
+        leaf_cookies: &[&LeafArgs],
+        leaf_verification_cookies: &[&LeafVerificationCookie],
+        proofs: &[&Vec<AccountMeta>],
+        action: &VoterWeightAction,
+        pre_instructions: Vec<Instruction>,
+        voter_allowlist_proof: Vec<[u8; 32]>,
+        instruction_override: F
+    ) -> (Vec<Instruction>, Vec<NftVoteTicketCookie>) {
         let params: Vec<LeafVerificationCookie> = leaf_verification_cookies
             .to_vec()
             .into_iter()
@@ -706,18 +4080,23 @@ impl NftVoterTest {
             .collect();
 
         let accounts = gpl_nft_voter::accounts::CreateCnftActionTicket {
-            registrar: registrar_cookie.address,
-            voter_weight_record: voter_weight_record_cookie.address,
-            voter_authority: voter_cookie.address,
+            voter: gpl_nft_voter::accounts::VoterAccounts {
+                registrar: registrar_cookie.address,
+                registrar_extension: registrar_cookie.extension_address,
+                voter_weight_record: voter_weight_record_cookie.address,
+                voter_authority: voter_cookie.address,
+            },
             payer: self.bench.payer.pubkey(),
             compression_program: spl_account_compression::id(),
             system_program: solana_sdk::system_program::id(),
+            instructions: solana_sdk::sysvar::instructions::id(),
         };
 
         let data = anchor_lang::InstructionData::data(
             &(gpl_nft_voter::instruction::CreateCnftActionTicket {
                 voter_weight_action: action.clone(),
                 params,
+                voter_allowlist_proof,
             })
         );
 
@@ -727,6 +4106,16 @@ impl NftVoterTest {
             data,
         };
 
+        let config = &registrar_cookie.extension.config;
+
+        if config.allow_voter_migration && config.min_holding_slots > 0 {
+            let voter_migration = get_voter_migration_address(
+                &registrar_cookie.address,
+                &voter_cookie.address
+            );
+            verify_cnft_info_ix.accounts.push(AccountMeta::new_readonly(voter_migration, false));
+        }
+
         let mut nft_action_ticket_cookies = vec![];
         let ticket_type = format!("nft-{}-ticket", &action).to_string();
         for i in 0..leaf_verification_cookies.len() {
@@ -747,6 +4136,71 @@ impl NftVoterTest {
             verify_cnft_info_ix.accounts.append(proof);
             verify_cnft_info_ix.accounts.push(cnft_action_ticket_info);
 
+            if
+                leaf_verification_cookies[i].uses.is_some() &&
+                *action == VoterWeightAction::CastVote
+            {
+                let asset_uses_record = get_asset_uses_record_address(asset_id);
+                verify_cnft_info_ix.accounts.push(AccountMeta::new(asset_uses_record, false));
+            }
+
+            if registrar_cookie.extension.config.max_tree_assets_per_slot > 0 {
+                let tree_rate_limit_record = get_tree_rate_limit_record_address(
+                    &registrar_cookie.address,
+                    &tree_address,
+                    &voter_cookie.address
+                );
+                verify_cnft_info_ix.accounts.push(AccountMeta::new(tree_rate_limit_record, false));
+            }
+
+            if registrar_cookie.extension.config.is_proof_attestation_enabled() {
+                let proof_attestation = get_proof_attestation_address(
+                    &registrar_cookie.address,
+                    asset_id
+                );
+                verify_cnft_info_ix.accounts.push(AccountMeta::new(proof_attestation, false));
+            }
+
+            if leaf_verification_cookies[i].leaf_owner != voter_cookie.address {
+                let cold_wallet_link = get_cold_wallet_link_address(
+                    &registrar_cookie.address,
+                    &leaf_verification_cookies[i].leaf_owner
+                );
+                verify_cnft_info_ix.accounts.push(AccountMeta::new_readonly(cold_wallet_link, false));
+            }
+
+            // Note: The tickets of the renter replace the ColdWalletLink with the WeightLease using instruction_override
+            if registrar_cookie.extension.config.allow_weight_leases {
+                let weight_lease = get_weight_lease_address(
+                    &registrar_cookie.address,
+                    asset_id,
+                    &leaf_verification_cookies[i].leaf_owner
+                );
+                verify_cnft_info_ix.accounts.push(AccountMeta::new_readonly(weight_lease, false));
+            }
+
+            // Note: The collections of the RegistrarCookie must be up to date to resolve the aliased collections
+            if
+                registrar_cookie.account.uses_collection_alias(
+                    &registrar_cookie.extension,
+                    leaf_verification_cookies[i]
+                )
+            {
+                let collection_alias = get_collection_alias_address(
+                    &registrar_cookie.address,
+                    &leaf_verification_cookies[i].collection.as_ref().unwrap().key
+                );
+                verify_cnft_info_ix.accounts.push(AccountMeta::new_readonly(collection_alias, false));
+            }
+
+            if registrar_cookie.extension.config.is_tree_authority_allowlist_enabled() {
+                let tree_config = Pubkey::find_program_address(
+                    &[tree_address.as_ref()],
+                    &mpl_bubblegum::ID
+                ).0;
+                verify_cnft_info_ix.accounts.push(AccountMeta::new_readonly(tree_config, false));
+            }
+
             nft_action_ticket_cookies.push(NftVoteTicketCookie {
                 nft_mint: asset_id.clone(),
                 address: cnft_action_ticket.clone(),
@@ -754,12 +4208,92 @@ impl NftVoterTest {
         }
 
         instruction_override(&mut verify_cnft_info_ix);
-        let default_signers = &[&voter_cookie.signer];
-        let signers = signers_override.unwrap_or(default_signers);
 
-        self.bench.process_transaction(&[verify_cnft_info_ix], Some(signers)).await?;
+        let mut instructions = pre_instructions;
+        instructions.push(verify_cnft_info_ix);
 
-        Ok(nft_action_ticket_cookies)
+        (instructions, nft_action_ticket_cookies)
+    }
+
+    /// Previews the voter weight of the given NFTs and compressed NFTs without any voter accounts
+    #[allow(dead_code)]
+    pub async fn preview_voter_weight(
+        &self,
+        registrar_cookie: &RegistrarCookie,
+        voter_cookie: &WalletCookie,
+        nft_cookies: &[&NftCookie],
+        leaf_cookies: &[&LeafArgs],
+        leaf_verification_cookies: &[&LeafVerificationCookie],
+        proofs: &[&Vec<AccountMeta>]
+    ) -> Result<Option<Vec<u8>>, BanksClientError> {
+        let params: Vec<LeafVerificationCookie> = leaf_verification_cookies
+            .iter()
+            .map(|v| (*v).clone())
+            .collect();
+
+        let data = anchor_lang::InstructionData::data(
+            &(gpl_nft_voter::instruction::PreviewVoterWeight {
+                governing_token_owner: voter_cookie.address,
+                nft_count: nft_cookies.len() as u8,
+                params,
+            })
+        );
+
+        let accounts = gpl_nft_voter::accounts::PreviewVoterWeight {
+            registrar: registrar_cookie.address,
+            registrar_extension: registrar_cookie.extension_address,
+            compression_program: spl_account_compression::id(),
+        };
+
+        let mut preview_voter_weight_ix = Instruction {
+            program_id: gpl_nft_voter::id(),
+            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
+            data,
+        };
+
+        for nft_cookie in nft_cookies {
+            preview_voter_weight_ix.accounts.push(
+                AccountMeta::new_readonly(nft_cookie.address, false)
+            );
+            preview_voter_weight_ix.accounts.push(
+                AccountMeta::new_readonly(nft_cookie.metadata, false)
+            );
+        }
+
+        for ((leaf_cookie, leaf_verification_cookie), proof) in leaf_cookies
+            .iter()
+            .zip(leaf_verification_cookies.iter())
+            .zip(proofs.iter()) {
+            let tree_address = leaf_cookie.tree_address;
+
+            preview_voter_weight_ix.accounts.push(AccountMeta::new_readonly(tree_address, false));
+            preview_voter_weight_ix.accounts.extend(proof.iter().cloned());
+
+            if
+                registrar_cookie.account.uses_collection_alias(
+                    &registrar_cookie.extension,
+                    leaf_verification_cookie
+                )
+            {
+                let collection_alias = get_collection_alias_address(
+                    &registrar_cookie.address,
+                    &leaf_verification_cookie.collection.as_ref().unwrap().key
+                );
+                preview_voter_weight_ix.accounts.push(
+                    AccountMeta::new_readonly(collection_alias, false)
+                );
+            }
+
+            if registrar_cookie.extension.config.is_tree_authority_allowlist_enabled() {
+                let tree_config = Pubkey::find_program_address(
+                    &[tree_address.as_ref()],
+                    &mpl_bubblegum::ID
+                ).0;
+                preview_voter_weight_ix.accounts.push(AccountMeta::new_readonly(tree_config, false));
+            }
+        }
+
+        self.bench.process_transaction_with_return_data(&[preview_voter_weight_ix], None).await
     }
 
     #[allow(dead_code)]
@@ -767,6 +4301,14 @@ impl NftVoterTest {
         self.bench.get_anchor_account::<Registrar>(*registrar).await
     }
 
+    #[allow(dead_code)]
+    pub async fn get_registrar_extension_account(
+        &mut self,
+        registrar_extension: &Pubkey
+    ) -> RegistrarExtension {
+        self.bench.get_anchor_account::<RegistrarExtension>(*registrar_extension).await
+    }
+
     #[allow(dead_code)]
     pub async fn get_nft_vote_record_account(&mut self, nft_vote_record: &Pubkey) -> NftVoteRecord {
         self.bench.get_borsh_account::<NftVoteRecord>(nft_vote_record).await
@@ -785,8 +4327,144 @@ impl NftVoterTest {
         self.bench.get_anchor_account(*voter_weight_record).await
     }
 
+    #[allow(dead_code)]
+    pub async fn get_collection_vote_weight_record(
+        &self,
+        collection_vote_weight_record: &Pubkey
+    ) -> CollectionVoteWeightRecord {
+        self.bench.get_borsh_account::<CollectionVoteWeightRecord>(collection_vote_weight_record).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_collection_stats(&self, collection_stats: &Pubkey) -> CollectionStats {
+        self.bench.get_borsh_account::<CollectionStats>(collection_stats).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_checkpoint(&self, voter_checkpoint: &Pubkey) -> VoterCheckpoint {
+        self.bench.get_borsh_account::<VoterCheckpoint>(voter_checkpoint).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proposal_creation_ticket(
+        &self,
+        proposal_creation_ticket: &Pubkey
+    ) -> ProposalCreationTicket {
+        self.bench.get_anchor_account(*proposal_creation_ticket).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_asset_uses_record(&self, asset_uses_record: &Pubkey) -> AssetUsesRecord {
+        self.bench.get_borsh_account::<AssetUsesRecord>(asset_uses_record).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_proof_attestation(&self, proof_attestation: &Pubkey) -> ProofAttestation {
+        self.bench.get_borsh_account::<ProofAttestation>(proof_attestation).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_cold_wallet_link(&self, cold_wallet_link: &Pubkey) -> ColdWalletLink {
+        self.bench.get_anchor_account::<ColdWalletLink>(*cold_wallet_link).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_group(&self, voter_group: &Pubkey) -> VoterGroup {
+        self.bench.get_anchor_account::<VoterGroup>(*voter_group).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_group_membership(
+        &self,
+        voter_group_membership: &Pubkey
+    ) -> VoterGroupMembership {
+        self.bench.get_anchor_account::<VoterGroupMembership>(*voter_group_membership).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_ballot_tree(&self, ballot_tree: &Pubkey) -> BallotTree {
+        self.bench.get_anchor_account::<BallotTree>(*ballot_tree).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_campaign(&self, campaign: &Pubkey) -> Campaign {
+        self.bench.get_anchor_account::<Campaign>(*campaign).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_weight_snapshot(&self, weight_snapshot: &Pubkey) -> WeightSnapshot {
+        self.bench.get_anchor_account::<WeightSnapshot>(*weight_snapshot).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_weight_lease(&self, weight_lease: &Pubkey) -> WeightLease {
+        self.bench.get_anchor_account::<WeightLease>(*weight_lease).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voting_session(&self, voting_session: &Pubkey) -> VotingSession {
+        self.bench.get_anchor_account::<VotingSession>(*voting_session).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_migration(&self, voter_migration: &Pubkey) -> VoterMigration {
+        self.bench.get_anchor_account::<VoterMigration>(*voter_migration).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_multi_proposal_vote(&self, multi_proposal_vote: &Pubkey) -> MultiProposalVote {
+        self.bench.get_anchor_account::<MultiProposalVote>(*multi_proposal_vote).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_rent_escrow(&self, voter_rent_escrow: &Pubkey) -> VoterRentEscrow {
+        self.bench.get_anchor_account::<VoterRentEscrow>(*voter_rent_escrow).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_tree_rate_limit_record(
+        &self,
+        tree_rate_limit_record: &Pubkey
+    ) -> TreeRateLimitRecord {
+        self.bench.get_borsh_account::<TreeRateLimitRecord>(tree_rate_limit_record).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_voter_weight_detail(&self, voter_weight_detail: &Pubkey) -> VoterWeightDetail {
+        self.bench.get_anchor_account(*voter_weight_detail).await
+    }
+
     #[allow(dead_code)]
     pub async fn get_nft_action_ticket(&mut self, cnft_action_ticket: &Pubkey) -> NftActionTicket {
         self.bench.get_borsh_account::<NftActionTicket>(cnft_action_ticket).await
     }
 }
+
+/// Returns the VoterRegistration and WalletWeightOverride PDAs of the voter supplied to the instructions
+/// which finalize the voter weight when the floor weight or the wallet overrides are enabled by the Registrar
+pub fn get_voter_weight_adjustment_account_metas(
+    registrar_cookie: &RegistrarCookie,
+    governing_token_owner: &Pubkey
+) -> Vec<AccountMeta> {
+    let mut account_metas = vec![];
+
+    if registrar_cookie.extension.config.is_min_voter_weight_enabled() {
+        account_metas.push(
+            AccountMeta::new_readonly(
+                get_voter_registration_address(&registrar_cookie.address, governing_token_owner),
+                false
+            )
+        );
+    }
+
+    if registrar_cookie.extension.config.allow_wallet_weight_overrides {
+        account_metas.push(
+            AccountMeta::new_readonly(
+                get_wallet_weight_override_address(&registrar_cookie.address, governing_token_owner),
+                false
+            )
+        );
+    }
+
+    account_metas
+}
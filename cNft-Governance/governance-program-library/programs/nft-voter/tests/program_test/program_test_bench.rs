@@ -8,19 +8,30 @@ use anchor_lang::{
 use solana_program::{borsh::try_from_slice_unchecked, system_program};
 use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
 use solana_sdk::{
-    account::{Account, ReadableAccount},
+    account::{Account, AccountSharedData, ReadableAccount},
+    compute_budget::ComputeBudgetInstruction,
     instruction::Instruction,
     program_pack::Pack,
     signature::Keypair,
     signer::Signer,
     system_instruction,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
     transport::TransportError,
 };
 
 use borsh::BorshDeserialize;
+use gpl_nft_voter::tools::spl_token::{spl_token_2022_program, TOKEN_2022_ACCOUNT_TYPE_MINT};
+use solana_program::program_option::COption;
 
+use crate::program_test::signer_registry::SignerRegistry;
 use crate::program_test::tools::clone_keypair;
+use std::rc::Rc;
+
+/// spl-token-2022 ExtensionType::TransferHook
+pub const TRANSFER_HOOK_EXTENSION_TYPE: u16 = 14;
+
+/// spl-token-2022 ExtensionType::MetadataPointer
+pub const METADATA_POINTER_EXTENSION_TYPE: u16 = 18;
 
 pub struct MintCookie {
     pub address: Pubkey,
@@ -39,10 +50,65 @@ pub struct WalletCookie {
     pub signer: Keypair,
 }
 
+/// Program logs and compute units consumed by a processed transaction
+#[derive(Debug, Default)]
+pub struct TransactionLogs {
+    pub log_messages: Vec<String>,
+    pub compute_units_consumed: u64,
+}
+
+impl TransactionLogs {
+    /// Returns the Anchor events of type T emitted by the transaction
+    /// The events are logged by emit! as base64 encoded "Program data:" messages prefixed with the event discriminator
+    #[allow(dead_code)]
+    pub fn get_events<T: anchor_lang::Event>(&self) -> Vec<T> {
+        self.log_messages
+            .iter()
+            .filter_map(|log_message| log_message.strip_prefix("Program data: "))
+            .filter_map(|data| base64::decode(data).ok())
+            .filter(|data| data.len() >= 8 && data[..8] == T::discriminator())
+            .map(|data| T::try_from_slice(&data[8..]).unwrap())
+            .collect()
+    }
+}
+
+/// Error of a failed transaction together with its program logs and compute units
+/// The Debug output prints the logs to make the failures of unwrapped transactions easy to debug
+pub struct TransactionFailure {
+    pub error: BanksClientError,
+    pub logs: TransactionLogs,
+}
+
+impl std::fmt::Debug for TransactionFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:?}", self.error)?;
+        writeln!(f, "Compute units consumed: {}", self.logs.compute_units_consumed)?;
+
+        for log_message in self.logs.log_messages.iter() {
+            writeln!(f, "  {}", log_message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<TransactionFailure> for BanksClientError {
+    fn from(failure: TransactionFailure) -> Self {
+        failure.error
+    }
+}
+
+impl From<TransactionFailure> for TransportError {
+    fn from(failure: TransactionFailure) -> Self {
+        failure.error.into()
+    }
+}
+
 pub struct ProgramTestBench {
     pub context: RefCell<ProgramTestContext>,
     pub payer: Keypair,
     pub rent: Rent,
+    pub signers: SignerRegistry,
 }
 
 impl ProgramTestBench {
@@ -59,6 +125,7 @@ impl ProgramTestBench {
             payer,
             context: RefCell::new(context),
             rent,
+            signers: SignerRegistry::default(),
         }
     }
 
@@ -90,6 +157,145 @@ impl ProgramTestBench {
             .await
     }
 
+    /// Processes the transaction and returns its program logs and compute units
+    /// The logs and compute units are also returned when the transaction fails
+    #[allow(dead_code)]
+    pub async fn process_transaction_with_logs(
+        &self,
+        instructions: &[Instruction],
+        signers: Option<&[&Keypair]>,
+    ) -> Result<TransactionLogs, TransactionFailure> {
+        let mut context = self.context.borrow_mut();
+
+        let mut transaction =
+            Transaction::new_with_payer(&instructions, Some(&context.payer.pubkey()));
+
+        let mut all_signers = vec![&context.payer];
+
+        if let Some(signers) = signers {
+            all_signers.extend_from_slice(signers);
+        }
+
+        transaction.sign(&all_signers, context.last_blockhash);
+
+        let result = context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .map_err(|error| TransactionFailure {
+                error,
+                logs: TransactionLogs::default(),
+            })?;
+
+        let logs = result
+            .metadata
+            .map(|metadata| TransactionLogs {
+                log_messages: metadata.log_messages,
+                compute_units_consumed: metadata.compute_units_consumed,
+            })
+            .unwrap_or_default();
+
+        match result.result {
+            Ok(()) => Ok(logs),
+            Err(error) => Err(TransactionFailure {
+                error: BanksClientError::TransactionError(error),
+                logs,
+            }),
+        }
+    }
+
+    /// Returns the size of the serialized transaction with the given instructions paid by the bench payer
+    /// It must not exceed PACKET_DATA_SIZE (1232 bytes) to be accepted by a cluster
+    #[allow(dead_code)]
+    pub fn get_transaction_size(&self, instructions: &[Instruction]) -> usize {
+        let transaction = Transaction::new_with_payer(instructions, Some(&self.payer.pubkey()));
+
+        // The signatures are prefixed with their compact-u16 count
+        1 + transaction.signatures.len() * 64 + transaction.message_data().len()
+    }
+
+    /// Processes the transaction with the given compute unit limit
+    /// It also makes the transaction distinct from the same instructions processed with a different limit
+    #[allow(dead_code)]
+    pub async fn process_transaction_with_compute_budget(
+        &self,
+        instructions: &[Instruction],
+        signers: Option<&[&Keypair]>,
+        compute_unit_limit: u32,
+    ) -> Result<TransactionLogs, TransactionFailure> {
+        let mut budget_instructions =
+            vec![ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit)];
+        budget_instructions.extend_from_slice(instructions);
+
+        self.process_transaction_with_logs(&budget_instructions, signers)
+            .await
+    }
+
+    /// Processes the transaction and retries it with a new blockhash when the blockhash expired
+    /// or the same transaction was already processed with the current blockhash
+    #[allow(dead_code)]
+    pub async fn process_transaction_with_retry(
+        &self,
+        instructions: &[Instruction],
+        signers: Option<&[&Keypair]>,
+        max_retries: u8,
+    ) -> Result<TransactionLogs, TransactionFailure> {
+        let mut retries = 0;
+
+        loop {
+            match self.process_transaction_with_logs(instructions, signers).await {
+                Err(TransactionFailure {
+                    error:
+                        BanksClientError::TransactionError(
+                            TransactionError::BlockhashNotFound
+                            | TransactionError::AlreadyProcessed,
+                        ),
+                    ..
+                }) if retries < max_retries => {
+                    retries += 1;
+
+                    let mut context = self.context.borrow_mut();
+                    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+                    context.last_blockhash = blockhash;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Processes the transaction and returns the return data set by its instructions
+    #[allow(dead_code)]
+    pub async fn process_transaction_with_return_data(
+        &self,
+        instructions: &[Instruction],
+        signers: Option<&[&Keypair]>,
+    ) -> Result<Option<Vec<u8>>, BanksClientError> {
+        let mut context = self.context.borrow_mut();
+
+        let mut transaction =
+            Transaction::new_with_payer(&instructions, Some(&context.payer.pubkey()));
+
+        let mut all_signers = vec![&context.payer];
+
+        if let Some(signers) = signers {
+            all_signers.extend_from_slice(signers);
+        }
+
+        transaction.sign(&all_signers, context.last_blockhash);
+
+        let result = context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await?;
+
+        result.result.map_err(BanksClientError::TransactionError)?;
+
+        Ok(result
+            .metadata
+            .and_then(|metadata| metadata.return_data)
+            .map(|return_data| return_data.data))
+    }
+
     pub async fn get_clock(&self) -> solana_program::clock::Clock {
         self.context
             .borrow_mut()
@@ -108,6 +314,23 @@ impl ProgramTestBench {
             .unwrap();
     }
 
+    #[allow(dead_code)]
+    pub async fn advance_clock_by_slots(&self, slots: u64) {
+        let clock = self.get_clock().await;
+        self.context
+            .borrow_mut()
+            .warp_to_slot(clock.slot + slots)
+            .unwrap();
+    }
+
+    #[allow(dead_code)]
+    pub async fn advance_clock_by_seconds(&self, seconds: i64) {
+        let mut clock = self.get_clock().await;
+        clock.unix_timestamp += seconds;
+
+        self.context.borrow_mut().set_sysvar(&clock);
+    }
+
     pub async fn with_mint(&self) -> Result<MintCookie, TransportError> {
         let mint_keypair = Keypair::new();
         let mint_authority = Keypair::new();
@@ -288,6 +511,87 @@ impl ProgramTestBench {
         }
     }
 
+    /// Returns the named test signer with the given index funded with at least the given lamports
+    /// The signer is created on first use and topped up from the payer only when its balance is lower
+    #[allow(dead_code)]
+    pub async fn with_signer(&self, name: &str, index: u32, lamports: u64) -> Rc<Keypair> {
+        let signer = self.signers.get(name, index);
+
+        let balance = self
+            .get_account(&signer.pubkey())
+            .await
+            .map_or(0, |account| account.lamports);
+
+        if balance < lamports {
+            let transfer_ix = system_instruction::transfer(
+                &self.payer.pubkey(),
+                &signer.pubkey(),
+                lamports - balance,
+            );
+
+            self.process_transaction(&[transfer_ix], None)
+                .await
+                .unwrap();
+        }
+
+        signer
+    }
+
+    /// Creates a rent exempt account with the given owner and data without executing the owner program
+    /// It's used to simulate accounts of programs which are not loaded into the test validator
+    #[allow(dead_code)]
+    pub async fn with_account_data(&self, owner: &Pubkey, data: Vec<u8>) -> Pubkey {
+        let address = Keypair::new().pubkey();
+
+        self.set_account_data(&address, owner, data).await;
+
+        address
+    }
+
+    /// Sets (overrides) the given account with a rent exempt account with the given owner and data
+    #[allow(dead_code)]
+    pub async fn set_account_data(&self, address: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+        let mut account = AccountSharedData::new(
+            self.rent.minimum_balance(data.len()),
+            data.len(),
+            owner,
+        );
+        account.set_data_from_slice(&data);
+
+        self.context.borrow_mut().set_account(address, &account);
+    }
+
+    /// Creates spl-token-2022 mint with TransferHook and MetadataPointer extensions
+    /// The mint is created directly because spl-token-2022 program is not loaded into the test validator
+    #[allow(dead_code)]
+    pub async fn with_token_2022_mint(&self) -> Pubkey {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(self.payer.pubkey()),
+            supply: 0,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data).unwrap();
+
+        // Mints are padded to the token account size followed by AccountType::Mint
+        data.resize(spl_token::state::Account::LEN, 0);
+        data.push(TOKEN_2022_ACCOUNT_TYPE_MINT);
+
+        // TransferHook (authority, program_id) and MetadataPointer (authority, metadata_address) extensions
+        for extension_type in [TRANSFER_HOOK_EXTENSION_TYPE, METADATA_POINTER_EXTENSION_TYPE] {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&64u16.to_le_bytes());
+            data.extend_from_slice(self.payer.pubkey().as_ref());
+            data.extend_from_slice(Keypair::new().pubkey().as_ref());
+        }
+
+        self.with_account_data(&spl_token_2022_program::id(), data)
+            .await
+    }
+
     #[allow(dead_code)]
     pub async fn get_account(&self, address: &Pubkey) -> Option<Account> {
         self.context
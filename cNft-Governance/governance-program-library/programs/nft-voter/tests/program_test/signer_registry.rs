@@ -0,0 +1,35 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use solana_sdk::signature::Keypair;
+
+/// Registry of the named test signers of the bench
+/// The signers are created on first use and identified by name and index (ex. "tree-creator", 0)
+/// They are shared as Rc<Keypair> instead of cloning the keypairs and they never alias the bench payer
+#[derive(Default)]
+pub struct SignerRegistry {
+    signers: RefCell<HashMap<(String, u32), Rc<Keypair>>>,
+}
+
+impl SignerRegistry {
+    /// Returns the signer with the given name and index and creates it if it doesn't exist yet
+    pub fn get(&self, name: &str, index: u32) -> Rc<Keypair> {
+        self.signers
+            .borrow_mut()
+            .entry((name.to_string(), index))
+            .or_insert_with(|| Rc::new(Keypair::new()))
+            .clone()
+    }
+
+    /// Returns a new signer with the given name and the next unused index
+    pub fn next(&self, name: &str) -> Rc<Keypair> {
+        let index = self.signers
+            .borrow()
+            .keys()
+            .filter(|(signer_name, _)| signer_name == name)
+            .count() as u32;
+
+        self.get(name, index)
+    }
+}
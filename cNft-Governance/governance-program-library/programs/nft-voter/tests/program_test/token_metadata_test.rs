@@ -1,27 +1,46 @@
 use std::{ fmt::Display, str::FromStr, sync::Arc, convert::TryFrom };
 // use anchor_lang::prelude::Pubkey;
 use mpl_bubblegum;
-use mpl_bubblegum::state::leaf_schema::LeafSchema;
-use mpl_bubblegum::state::metaplex_adapter::{
+use mpl_bubblegum::types::{
     Collection as CNFT_Collection,
     Creator,
     MetadataArgs,
     TokenProgramVersion,
     TokenStandard,
+    Uses,
 };
-use mpl_bubblegum::utils::get_asset_id;
-use mpl_bubblegum::{ hash_creators, hash_metadata };
-use mpl_token_metadata::state::{ Collection, CollectionDetails };
-use solana_program::instruction::Instruction;
+use mpl_bubblegum::instructions::{
+    MintToCollectionV1Builder,
+    MintV1Builder,
+    SetAndVerifyCollectionBuilder,
+    VerifyCollectionBuilder,
+};
+use cnft_verification::get_asset_id;
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3Builder,
+    CreateMetadataAccountV3Builder,
+    VerifySizedCollectionItemBuilder,
+};
+use mpl_token_metadata::types::{ Collection, CollectionDetails, DataV2 };
+use solana_program::instruction::AccountMeta;
 use solana_program::pubkey::Pubkey;
 use solana_program::system_program;
 use solana_program_test::ProgramTest;
 use solana_sdk::signature::Keypair;
 use solana_sdk::{ signer::Signer, transport::TransportError };
 
+#[cfg(feature = "metaplex-core")]
+use gpl_nft_voter::tools::metaplex_core::{
+    mpl_core_program,
+    CoreAsset,
+    CoreCollection,
+    CoreUpdateAuthority,
+    CORE_ASSET_V1_KEY,
+    CORE_COLLECTION_V1_KEY,
+};
+
 use crate::program_test::merkle_tree_test::{ LeafArgs, MerkleTreeCookie };
 use crate::program_test::program_test_bench::{ MintCookie, ProgramTestBench, WalletCookie };
-use crate::program_test::tools::clone_keypair;
 
 pub struct NftCookie {
     pub address: Pubkey,
@@ -106,24 +125,30 @@ impl TokenMetadataTest {
             });
         }
 
-        let create_coll_metadata_ix = mpl_token_metadata::instruction::create_metadata_accounts_v3(
-            self.program_id,
-            coll_metadata_key,
-            coll_mint_cookie.address,
-            coll_mint_cookie.mint_authority.pubkey(),
-            payer.clone(),
-            update_authority.clone(),
-            coll_name,
-            coll_symbol,
-            coll_uri,
-            None,
-            10,
-            false,
-            false,
-            None,
-            None,
-            coll_details
-        );
+        let mut create_coll_metadata_builder = CreateMetadataAccountV3Builder::new();
+
+        create_coll_metadata_builder
+            .metadata(coll_metadata_key)
+            .mint(coll_mint_cookie.address)
+            .mint_authority(coll_mint_cookie.mint_authority.pubkey())
+            .payer(payer)
+            .update_authority(update_authority, false)
+            .data(DataV2 {
+                name: coll_name,
+                symbol: coll_symbol,
+                uri: coll_uri,
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: None,
+                uses: None,
+            })
+            .is_mutable(false);
+
+        if let Some(coll_details) = coll_details {
+            create_coll_metadata_builder.collection_details(coll_details);
+        }
+
+        let create_coll_metadata_ix = create_coll_metadata_builder.instruction();
 
         self.bench.process_transaction(
             &[create_coll_metadata_ix],
@@ -141,16 +166,15 @@ impl TokenMetadataTest {
             &self.program_id
         );
 
-        let create_master_edition_ix = mpl_token_metadata::instruction::create_master_edition_v3(
-            self.program_id,
-            master_edition_key,
-            coll_mint_cookie.address,
-            update_authority,
-            coll_mint_cookie.mint_authority.pubkey(),
-            coll_metadata_key,
-            payer,
-            Some(0)
-        );
+        let create_master_edition_ix = CreateMasterEditionV3Builder::new()
+            .edition(master_edition_key)
+            .mint(coll_mint_cookie.address)
+            .update_authority(update_authority)
+            .mint_authority(coll_mint_cookie.mint_authority.pubkey())
+            .metadata(coll_metadata_key)
+            .payer(payer)
+            .max_supply(0)
+            .instruction();
 
         self.bench.process_transaction(
             &[create_master_edition_ix],
@@ -198,24 +222,23 @@ impl TokenMetadataTest {
             key: nft_collection_cookie.mint,
         };
 
-        let create_metadata_ix = mpl_token_metadata::instruction::create_metadata_accounts_v3(
-            self.program_id,
-            metadata_key,
-            mint_cookie.address,
-            mint_cookie.mint_authority.pubkey(),
-            self.bench.payer.pubkey(),
-            self.bench.payer.pubkey(),
-            name,
-            symbol,
-            uri,
-            None,
-            10,
-            false,
-            false,
-            Some(collection),
-            None,
-            None
-        );
+        let create_metadata_ix = CreateMetadataAccountV3Builder::new()
+            .metadata(metadata_key)
+            .mint(mint_cookie.address)
+            .mint_authority(mint_cookie.mint_authority.pubkey())
+            .payer(self.bench.payer.pubkey())
+            .update_authority(self.bench.payer.pubkey(), false)
+            .data(DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: Some(collection),
+                uses: None,
+            })
+            .is_mutable(false)
+            .instruction();
 
         self.bench.process_transaction(
             &[create_metadata_ix],
@@ -223,16 +246,14 @@ impl TokenMetadataTest {
         ).await?;
 
         if verify_collection {
-            let verify_collection = mpl_token_metadata::instruction::verify_sized_collection_item(
-                self.program_id,
-                metadata_key,
-                self.bench.payer.pubkey(),
-                self.bench.payer.pubkey(),
-                nft_collection_cookie.mint,
-                nft_collection_cookie.metadata,
-                nft_collection_cookie.master_edition,
-                None
-            );
+            let verify_collection = VerifySizedCollectionItemBuilder::new()
+                .metadata(metadata_key)
+                .collection_authority(self.bench.payer.pubkey())
+                .payer(self.bench.payer.pubkey())
+                .collection_mint(nft_collection_cookie.mint)
+                .collection(nft_collection_cookie.metadata)
+                .collection_master_edition_account(nft_collection_cookie.master_edition)
+                .instruction();
 
             self.bench.process_transaction(&[verify_collection], None).await?;
         }
@@ -291,55 +312,124 @@ impl TokenMetadataTest {
         let symbol = format!("tst{}", tree_cookie.num_minted);
         let uri = "https://www.bubblegum-nfts.com/".to_owned();
         let metadata = self.default_cnft_metadata(name, symbol, uri, &nft_collection_cookie.mint);
-        let mut args = LeafArgs::new(owner, &tree_cookie.address, metadata);
+        let mut args = LeafArgs::new(&owner.pubkey(), &tree_cookie.address, metadata);
 
         args.index = u32::try_from(tree_cookie.num_minted).unwrap();
         args.nonce = tree_cookie.num_minted;
 
-        let accounts = mpl_bubblegum::accounts::MintV1 {
-            tree_authority: tree_cookie.tree_authority,
-            tree_delegate: tree_cookie.tree_delegate.pubkey(),
-            payer: args.owner.pubkey(),
-            log_wrapper: spl_noop::id(),
-            compression_program: spl_account_compression::id(),
-            leaf_owner: args.owner.pubkey(),
-            leaf_delegate: args.delegate.pubkey(),
-            merkle_tree: tree_cookie.address,
-            system_program: system_program::id(),
-        };
+        let mint_cnft_ix = MintV1Builder::new()
+            .tree_config(tree_cookie.tree_authority)
+            .leaf_owner(args.owner)
+            .leaf_delegate(args.delegate)
+            .merkle_tree(tree_cookie.address)
+            .payer(args.owner)
+            .tree_creator_or_delegate(tree_cookie.tree_delegate.pubkey())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .system_program(system_program::id())
+            .metadata(args.metadata.clone())
+            .instruction();
+
+        let signers = &[&*tree_cookie.tree_delegate, owner];
+        self.bench.process_transaction(&[mint_cnft_ix], Some(signers)).await?;
 
-        let data = anchor_lang::InstructionData::data(
-            &(mpl_bubblegum::instruction::MintV1 {
-                message: args.metadata.clone(),
-            })
-        );
+        args.asset_id = get_asset_id(&tree_cookie.address, args.nonce);
+
+        let leaf_node = args.leaf_node();
 
-        let mint_cnft_ix = Instruction {
-            program_id: mpl_bubblegum::id(),
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
-            data,
+        tree_cookie.num_minted += 1;
+        tree_cookie.proof_tree.add_leaf(leaf_node, usize::try_from(args.index).unwrap());
+        Ok(args)
+    }
+
+    /// Verifies the collection of an already minted compressed NFT using Bubblegum verify_collection
+    /// or set_and_verify_collection when set_collection is true
+    /// Bubblegum rehashes the leaf metadata and the args and the proof tree are updated accordingly
+    #[allow(dead_code)]
+    pub async fn with_compressed_nft_collection_verification(
+        &self,
+        nft_collection_cookie: &NftCollectionCookie,
+        tree_cookie: &mut MerkleTreeCookie,
+        args: &mut LeafArgs,
+        set_collection: bool
+    ) -> Result<(), TransportError> {
+        let root = tree_cookie.proof_tree.get_root();
+        let data_hash = args.data_hash();
+        let creator_hash = args.creator_hash();
+
+        let collection_authority = &self.bench.payer;
+
+        let proof = tree_cookie.proof_tree.get_proof_of_leaf(usize::try_from(args.index).unwrap());
+        let proof_metas = proof[..proof.len() - (tree_cookie.canopy_depth as usize)]
+            .iter()
+            .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+            .collect::<Vec<_>>();
+
+        let verify_collection_ix = if set_collection {
+            SetAndVerifyCollectionBuilder::new()
+                .tree_config(tree_cookie.tree_authority)
+                .leaf_owner(args.owner)
+                .leaf_delegate(args.delegate)
+                .merkle_tree(tree_cookie.address)
+                .payer(self.bench.payer.pubkey())
+                .tree_creator_or_delegate(tree_cookie.tree_delegate.pubkey(), false)
+                .collection_authority(collection_authority.pubkey())
+                .collection_mint(nft_collection_cookie.mint)
+                .collection_metadata(nft_collection_cookie.metadata)
+                .collection_edition(nft_collection_cookie.master_edition)
+                .bubblegum_signer(self.get_bubblegum_signer_address())
+                .log_wrapper(spl_noop::id())
+                .compression_program(spl_account_compression::id())
+                .token_metadata_program(self.program_id)
+                .system_program(system_program::id())
+                .root(root)
+                .data_hash(data_hash)
+                .creator_hash(creator_hash)
+                .nonce(args.nonce)
+                .index(args.index)
+                .metadata(args.metadata.clone())
+                .collection(nft_collection_cookie.mint)
+                .add_remaining_accounts(&proof_metas)
+                .instruction()
+        } else {
+            VerifyCollectionBuilder::new()
+                .tree_config(tree_cookie.tree_authority)
+                .leaf_owner(args.owner)
+                .leaf_delegate(args.delegate)
+                .merkle_tree(tree_cookie.address)
+                .payer(self.bench.payer.pubkey())
+                .tree_creator_or_delegate(tree_cookie.tree_delegate.pubkey())
+                .collection_authority(collection_authority.pubkey())
+                .collection_mint(nft_collection_cookie.mint)
+                .collection_metadata(nft_collection_cookie.metadata)
+                .collection_edition(nft_collection_cookie.master_edition)
+                .bubblegum_signer(self.get_bubblegum_signer_address())
+                .log_wrapper(spl_noop::id())
+                .compression_program(spl_account_compression::id())
+                .token_metadata_program(self.program_id)
+                .system_program(system_program::id())
+                .root(root)
+                .data_hash(data_hash)
+                .creator_hash(creator_hash)
+                .nonce(args.nonce)
+                .index(args.index)
+                .metadata(args.metadata.clone())
+                .add_remaining_accounts(&proof_metas)
+                .instruction()
         };
 
-        let owner = clone_keypair(&args.owner);
-        let signers = &[&tree_cookie.tree_delegate, &owner];
-        self.bench.process_transaction(&[mint_cnft_ix], Some(signers)).await?;
+        self.bench.process_transaction(&[verify_collection_ix], None).await?;
 
-        let data_hash = hash_metadata(&args.metadata).unwrap();
-        let creator_hash = hash_creators(&args.metadata.creators.as_slice()).unwrap();
-        let asset_id = get_asset_id(&tree_cookie.address, args.nonce);
+        args.metadata.collection = Some(CNFT_Collection {
+            verified: true,
+            key: nft_collection_cookie.mint,
+        });
 
-        let leaf_node = LeafSchema::new_v0(
-            asset_id,
-            args.owner.pubkey(),
-            args.delegate.pubkey(),
-            args.nonce,
-            data_hash,
-            creator_hash
-        ).to_node();
+        let leaf_node = args.leaf_node();
 
-        tree_cookie.num_minted += 1;
         tree_cookie.proof_tree.add_leaf(leaf_node, usize::try_from(args.index).unwrap());
-        Ok(args)
+
+        Ok(())
     }
 
     #[allow(dead_code)]
@@ -348,80 +438,129 @@ impl TokenMetadataTest {
         nft_collection_cookie: &NftCollectionCookie,
         tree_cookie: &mut MerkleTreeCookie,
         voter_cookie: &WalletCookie
+    ) -> Result<LeafArgs, TransportError> {
+        self.with_compressed_nft_to_collection_and_uses(
+            nft_collection_cookie,
+            tree_cookie,
+            voter_cookie,
+            None
+        ).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn with_compressed_nft_to_collection_and_uses(
+        &self,
+        nft_collection_cookie: &NftCollectionCookie,
+        tree_cookie: &mut MerkleTreeCookie,
+        voter_cookie: &WalletCookie,
+        uses: Option<Uses>
     ) -> Result<LeafArgs, TransportError> {
         let owner = &voter_cookie.signer;
 
         let name = format!("test{}", tree_cookie.num_minted);
         let symbol = format!("tst{}", tree_cookie.num_minted);
         let uri = "https://www.bubblegum-nfts.com/".to_owned();
-        let metadata = self.default_cnft_metadata(name, symbol, uri, &nft_collection_cookie.mint);
-        let mut args = LeafArgs::new(owner, &tree_cookie.address, metadata);
+        let mut metadata = self.default_cnft_metadata(
+            name,
+            symbol,
+            uri,
+            &nft_collection_cookie.mint
+        );
+        metadata.uses = uses;
+        let mut args = LeafArgs::new(&owner.pubkey(), &tree_cookie.address, metadata);
 
         args.index = u32::try_from(tree_cookie.num_minted).unwrap();
         args.nonce = tree_cookie.num_minted;
 
         // let collection_mint_authority = nft_collection_cookie.mint_authority.as_ref().unwrap();
         let collection_mint_authority = &self.bench.payer;
-        let accounts = mpl_bubblegum::accounts::MintToCollectionV1 {
-            tree_authority: tree_cookie.tree_authority,
-            tree_delegate: tree_cookie.tree_delegate.pubkey(),
-            payer: args.owner.pubkey(),
-            log_wrapper: spl_noop::id(),
-            compression_program: spl_account_compression::id(),
-            leaf_owner: args.owner.pubkey(),
-            leaf_delegate: args.delegate.pubkey(),
-            merkle_tree: tree_cookie.address,
-            system_program: system_program::id(),
-            collection_mint: nft_collection_cookie.mint,
-            collection_authority: collection_mint_authority.pubkey(),
-            collection_authority_record_pda: mpl_bubblegum::id(),
-            collection_metadata: nft_collection_cookie.metadata,
-            edition_account: nft_collection_cookie.master_edition,
-            bubblegum_signer: self.get_bubblegum_signer_address(),
-            token_metadata_program: self.program_id,
-        };
-
-        let data = anchor_lang::InstructionData::data(
-            &(mpl_bubblegum::instruction::MintToCollectionV1 {
-                metadata_args: args.metadata.clone(),
-            })
-        );
-
-        let mint_cnft_ix = Instruction {
-            program_id: mpl_bubblegum::id(),
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
-            data,
-        };
-
-        let owner = clone_keypair(&args.owner);
-        let signers = &[&tree_cookie.tree_delegate, &owner, &collection_mint_authority];
+        let mint_cnft_ix = MintToCollectionV1Builder::new()
+            .tree_config(tree_cookie.tree_authority)
+            .leaf_owner(args.owner)
+            .leaf_delegate(args.delegate)
+            .merkle_tree(tree_cookie.address)
+            .payer(args.owner)
+            .tree_creator_or_delegate(tree_cookie.tree_delegate.pubkey())
+            .collection_authority(collection_mint_authority.pubkey())
+            .collection_mint(nft_collection_cookie.mint)
+            .collection_metadata(nft_collection_cookie.metadata)
+            .collection_edition(nft_collection_cookie.master_edition)
+            .bubblegum_signer(self.get_bubblegum_signer_address())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .token_metadata_program(self.program_id)
+            .system_program(system_program::id())
+            .metadata(args.metadata.clone())
+            .instruction();
+
+        let signers = &[&*tree_cookie.tree_delegate, owner, collection_mint_authority];
         self.bench.process_transaction(&[mint_cnft_ix], Some(signers)).await?;
 
         if let Some(collection) = args.metadata.collection.as_mut() {
             collection.verified = true;
         }
 
-        let data_hash = hash_metadata(&args.metadata).unwrap();
-        let creator_hash = hash_creators(&args.metadata.creators.as_slice()).unwrap();
-        let asset_id = get_asset_id(&tree_cookie.address, args.nonce);
-        args.asset_id = asset_id;
+        args.asset_id = get_asset_id(&tree_cookie.address, args.nonce);
 
-        let leaf_node = LeafSchema::new_v0(
-            asset_id,
-            args.owner.pubkey(),
-            args.delegate.pubkey(),
-            args.nonce,
-            data_hash,
-            creator_hash
-        ).to_node();
+        let leaf_node = args.leaf_node();
 
         tree_cookie.num_minted += 1;
         tree_cookie.proof_tree.add_leaf(leaf_node, usize::try_from(args.index).unwrap());
         Ok(args)
     }
 
+    /// Creates Metaplex Core CollectionV1 account
+    /// The returned cookie has the Core collection address as the collection mint
+    #[cfg(feature = "metaplex-core")]
+    #[allow(dead_code)]
+    pub async fn with_core_collection(&self) -> NftCollectionCookie {
+        let core_collection = CoreCollection {
+            key: CORE_COLLECTION_V1_KEY,
+            update_authority: self.bench.payer.pubkey(),
+            name: "Core Collection".to_string(),
+            uri: "https://core.metaplex.com/".to_string(),
+            num_minted: 0,
+            current_size: 0,
+        };
+
+        let address = self.bench.with_account_data(
+            &mpl_core_program::id(),
+            anchor_lang::AnchorSerialize::try_to_vec(&core_collection).unwrap()
+        ).await;
+
+        NftCollectionCookie {
+            mint: address,
+            metadata: Pubkey::default(),
+            master_edition: Pubkey::default(),
+            mint_authority: None,
+        }
+    }
+
+    /// Creates Metaplex Core AssetV1 account of the given collection owned by the voter
+    #[cfg(feature = "metaplex-core")]
+    #[allow(dead_code)]
+    pub async fn with_core_asset(
+        &self,
+        nft_collection_cookie: &NftCollectionCookie,
+        voter_cookie: &WalletCookie
+    ) -> Pubkey {
+        let core_asset = CoreAsset {
+            key: CORE_ASSET_V1_KEY,
+            owner: voter_cookie.address,
+            update_authority: CoreUpdateAuthority::Collection(nft_collection_cookie.mint),
+            name: "Core Asset".to_string(),
+            uri: "https://core.metaplex.com/".to_string(),
+            seq: None,
+        };
+
+        self.bench.with_account_data(
+            &mpl_core_program::id(),
+            anchor_lang::AnchorSerialize::try_to_vec(&core_asset).unwrap()
+        ).await
+    }
+
     #[allow(dead_code)]
     pub fn get_bubblegum_signer_address(&self) -> Pubkey {
-        Pubkey::find_program_address(&[b"collection_cpi".as_ref()], &mpl_bubblegum::id()).0
+        Pubkey::find_program_address(&[b"collection_cpi".as_ref()], &mpl_bubblegum::ID).0
     }
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::ERROR_CODE_OFFSET;
 use gpl_nft_voter::error::NftVoterError;
-use solana_program::instruction::InstructionError;
+use solana_program::instruction::{ Instruction, InstructionError };
+use solana_sdk::ed25519_instruction::new_ed25519_instruction;
 use solana_program_test::BanksClientError;
 use solana_sdk::{ signature::Keypair, transaction::TransactionError, transport::TransportError };
 use spl_governance::error::GovernanceError;
@@ -11,6 +12,13 @@ pub fn clone_keypair(source: &Keypair) -> Keypair {
     Keypair::from_bytes(&source.to_bytes()).unwrap()
 }
 
+/// Creates ed25519 program instruction verifying the signature of the given signer over the message
+#[allow(dead_code)]
+pub fn new_ed25519_signature_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    let keypair = ed25519_dalek::Keypair::from_bytes(&signer.to_bytes()).unwrap();
+    new_ed25519_instruction(&keypair, message)
+}
+
 /// NOP (No Operation) Override function
 #[allow(non_snake_case)]
 pub fn NopOverride<T>(_: &mut T) {}
@@ -0,0 +1,138 @@
+use ::cnft_verification::{
+    get_proof_encoding_size,
+    select_proof_encoding,
+    ProofEncoding,
+    LEAF_SCHEMA_V1,
+};
+use gpl_nft_voter::state::*;
+use program_test::nft_voter_test::*;
+use crate::program_test::merkle_tree_test::MerkleTreeArgs;
+use crate::program_test::tools::NopOverride;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+mod program_test;
+
+/// Measures the compute units and transaction size of create_cnft_action_ticket with the proof supplied
+/// as accounts and as instruction data across tree depths
+/// The measurements are printed (cargo test --test proof_encoding -- --nocapture) and checked against
+/// cnft_verification::select_proof_encoding used by the clients to pick the encoding
+#[tokio::test]
+async fn test_create_cnft_action_ticket_proof_encodings() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    println!("max_depth encoding tx_bytes compute_units");
+
+    for (max_depth, max_buffer_size) in [(3, 8), (5, 8), (14, 64)] {
+        let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(
+            Some(MerkleTreeArgs {
+                max_depth,
+                max_buffer_size,
+                public: Some(false),
+            })
+        ).await?;
+
+        let mut measurements = vec![];
+
+        for encoding in [ProofEncoding::Accounts, ProofEncoding::Data] {
+            let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+                &nft_collection_cookie,
+                &mut tree_cookie,
+                &voter_cookie
+            ).await?;
+
+            let (mut leaf_verification_cookie, mut proofs, _) =
+                nft_voter_test.merkle_tree.get_leaf_verification_info(
+                    &mut tree_cookie,
+                    &leaf_cookie,
+                    max_depth as usize,
+                    max_buffer_size as usize
+                ).await?;
+
+            // The trees have no canopy and the proof accounts are the full proof
+            if encoding == ProofEncoding::Data {
+                leaf_verification_cookie.proof_nodes = Some(
+                    proofs
+                        .iter()
+                        .map(|proof| proof.pubkey.to_bytes())
+                        .collect()
+                );
+                leaf_verification_cookie.proof_len = 0;
+                proofs = vec![];
+            }
+
+            let (instructions, _) = nft_voter_test.create_cnft_action_ticket_ixs(
+                &registrar_cookie,
+                &voter_weight_record_cookie,
+                &voter_cookie,
+                &[&leaf_cookie],
+                &[&leaf_verification_cookie],
+                &[&proofs],
+                &action,
+                vec![],
+                vec![],
+                NopOverride
+            );
+
+            // Act
+            let transaction_size = nft_voter_test.bench.get_transaction_size(&instructions);
+            let logs = nft_voter_test.bench
+                .process_transaction_with_logs(&instructions, Some(&[&voter_cookie.signer]))
+                .await
+                .unwrap();
+
+            println!(
+                "{:>9} {:>8?} {:>8} {:>13}",
+                max_depth,
+                encoding,
+                transaction_size,
+                logs.compute_units_consumed
+            );
+
+            measurements.push((encoding, transaction_size));
+        }
+
+        // Assert
+        let (_, accounts_size) = measurements[0];
+        let (_, data_size) = measurements[1];
+
+        // The transactions differ only by the encoded proof
+        assert_eq!(
+            (accounts_size as i64) - (data_size as i64),
+            (get_proof_encoding_size(ProofEncoding::Accounts, max_depth, 0) as i64) -
+                (get_proof_encoding_size(ProofEncoding::Data, max_depth, 0) as i64)
+        );
+
+        let selected_size = match select_proof_encoding(max_depth, 0, LEAF_SCHEMA_V1) {
+            ProofEncoding::Accounts => accounts_size,
+            ProofEncoding::Data => data_size,
+        };
+
+        assert_eq!(selected_size, accounts_size.min(data_size));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use gpl_nft_voter::state::*;
+use program_test::governance_test::{ ProposalCookie, RealmCookie, TokenOwnerRecordCookie };
+use program_test::merkle_tree_test::{ LeafArgs, MerkleTreeCookie };
+use program_test::nft_voter_test::*;
+use program_test::program_test_bench::WalletCookie;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use spl_governance::state::{ enums::ProposalState, vote_record::Vote };
+mod program_test;
+
+/// Casts the cNFT vote of the voter together with spl-gov CastVote
+/// and returns the TokenOwnerRecord of the voter the spl-gov VoteRecord is created for
+async fn cast_cnft_vote(
+    nft_voter_test: &mut NftVoterTest,
+    realm_cookie: &RealmCookie,
+    registrar_cookie: &RegistrarCookie,
+    max_voter_weight_record_cookie: &MaxVoterWeightRecordCookie,
+    proposal_cookie: &ProposalCookie,
+    tree_cookie: &mut MerkleTreeCookie,
+    voter_cookie: &WalletCookie,
+    leaf_cookies: &[&LeafArgs],
+    vote_kind: NftVoteKind
+) -> Result<TokenOwnerRecordCookie, TransportError> {
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        realm_cookie,
+        voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        registrar_cookie,
+        voter_cookie
+    ).await?;
+
+    let mut leaf_verification_cookies = vec![];
+    let mut proofs = vec![];
+
+    for leaf_cookie in leaf_cookies {
+        let (leaf_verification_cookie, leaf_proofs, _) =
+            nft_voter_test.merkle_tree.get_leaf_verification_info(
+                tree_cookie,
+                leaf_cookie,
+                5,
+                8
+            ).await?;
+
+        leaf_verification_cookies.push(leaf_verification_cookie);
+        proofs.push(leaf_proofs);
+    }
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket(
+        registrar_cookie,
+        &voter_weight_record_cookie,
+        voter_cookie,
+        leaf_cookies,
+        &leaf_verification_cookies.iter().collect::<Vec<_>>(),
+        &proofs.iter().collect::<Vec<_>>(),
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    nft_voter_test.cast_nft_vote(
+        registrar_cookie,
+        &voter_weight_record_cookie,
+        max_voter_weight_record_cookie,
+        proposal_cookie,
+        voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            vote_kind,
+            ..Default::default()
+        })
+    ).await?;
+
+    Ok(voter_token_owner_record_cookie)
+}
+
+#[tokio::test]
+async fn test_proposal_lifecycle_with_cnft_votes_defeated_on_finalize() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 10,
+        })
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let voter_cookie1 = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie2 = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie3 = nft_voter_test.bench.with_wallet().await;
+
+    // All the leaves are minted before any proof is taken because every mint changes the proofs of the tree
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie1
+    ).await?;
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie1
+    ).await?;
+    let leaf_cookie3 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie2
+    ).await?;
+    let leaf_cookie4 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie3
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let voter_token_owner_record_cookie1 = cast_cnft_vote(
+        &mut nft_voter_test,
+        &realm_cookie,
+        &registrar_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &mut tree_cookie,
+        &voter_cookie1,
+        &[&leaf_cookie1, &leaf_cookie2],
+        NftVoteKind::Approve
+    ).await?;
+    let voter_token_owner_record_cookie2 = cast_cnft_vote(
+        &mut nft_voter_test,
+        &realm_cookie,
+        &registrar_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &mut tree_cookie,
+        &voter_cookie2,
+        &[&leaf_cookie3],
+        NftVoteKind::Approve
+    ).await?;
+    let voter_token_owner_record_cookie3 = cast_cnft_vote(
+        &mut nft_voter_test,
+        &realm_cookie,
+        &registrar_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &mut tree_cookie,
+        &voter_cookie3,
+        &[&leaf_cookie4],
+        NftVoteKind::Deny
+    ).await?;
+
+    // Voting time of the governance is 600 seconds
+    nft_voter_test.bench.advance_clock_by_seconds(601).await;
+
+    // Act
+    nft_voter_test.governance.finalize_vote(
+        &realm_cookie,
+        &proposal_cookie,
+        Some(max_voter_weight_record_cookie.address)
+    ).await?;
+
+    // Assert
+
+    // 3 approving votes don't reach 60% of the max voter weight 10
+    let proposal = nft_voter_test.governance.get_proposal(&proposal_cookie.address).await;
+
+    assert_eq!(proposal.state, ProposalState::Defeated);
+    assert_eq!(proposal.options[0].vote_weight, 3);
+    assert_eq!(proposal.deny_vote_weight, Some(1));
+    assert_eq!(proposal.max_vote_weight, Some(10));
+
+    let vote_record1 = nft_voter_test.governance.get_vote_record(
+        &proposal_cookie,
+        &voter_token_owner_record_cookie1
+    ).await;
+    assert_eq!(vote_record1.voter_weight, 2);
+    assert!(matches!(vote_record1.vote, Vote::Approve(_)));
+
+    let vote_record2 = nft_voter_test.governance.get_vote_record(
+        &proposal_cookie,
+        &voter_token_owner_record_cookie2
+    ).await;
+    assert_eq!(vote_record2.voter_weight, 1);
+    assert!(matches!(vote_record2.vote, Vote::Approve(_)));
+
+    let vote_record3 = nft_voter_test.governance.get_vote_record(
+        &proposal_cookie,
+        &voter_token_owner_record_cookie3
+    ).await;
+    assert_eq!(vote_record3.voter_weight, 1);
+    assert_eq!(vote_record3.vote, Vote::Deny);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proposal_lifecycle_with_cnft_votes_succeeded_on_tipping() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 5,
+        })
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let voter_cookie1 = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie2 = nft_voter_test.bench.with_wallet().await;
+    let voter_cookie3 = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    let leaf_cookie1 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie1
+    ).await?;
+    let leaf_cookie2 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie2
+    ).await?;
+    let leaf_cookie3 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie3
+    ).await?;
+    let leaf_cookie4 = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &voter_cookie3
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    cast_cnft_vote(
+        &mut nft_voter_test,
+        &realm_cookie,
+        &registrar_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &mut tree_cookie,
+        &voter_cookie1,
+        &[&leaf_cookie1],
+        NftVoteKind::Deny
+    ).await?;
+    cast_cnft_vote(
+        &mut nft_voter_test,
+        &realm_cookie,
+        &registrar_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &mut tree_cookie,
+        &voter_cookie2,
+        &[&leaf_cookie2],
+        NftVoteKind::Approve
+    ).await?;
+
+    let proposal = nft_voter_test.governance.get_proposal(&proposal_cookie.address).await;
+    assert_eq!(proposal.state, ProposalState::Voting);
+
+    // Act
+    cast_cnft_vote(
+        &mut nft_voter_test,
+        &realm_cookie,
+        &registrar_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &mut tree_cookie,
+        &voter_cookie3,
+        &[&leaf_cookie3, &leaf_cookie4],
+        NftVoteKind::Approve
+    ).await?;
+
+    // Assert
+
+    // 3 approving votes reach 60% of the max voter weight 5 and outweigh the remaining weight 2 with Strict tipping
+    let proposal = nft_voter_test.governance.get_proposal(&proposal_cookie.address).await;
+
+    assert_eq!(proposal.state, ProposalState::Succeeded);
+    assert_eq!(proposal.options[0].vote_weight, 3);
+    assert_eq!(proposal.deny_vote_weight, Some(1));
+    assert_eq!(proposal.max_vote_weight, Some(5));
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_prune_expired_collections() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie1,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 2,
+            size: 10,
+        })
+    ).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie2,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 5,
+        })
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 100;
+
+    nft_voter_test.with_collection_expiry(
+        &registrar_cookie,
+        &nft_collection_cookie1,
+        expires_at
+    ).await?;
+
+    nft_voter_test.bench.advance_clock_by_seconds(100).await;
+
+    // Act
+    nft_voter_test.prune_expired_collections(
+        &registrar_cookie,
+        &max_voter_weight_record_cookie
+    ).await?;
+
+    // Assert
+    let registrar = nft_voter_test.get_registrar_account(&registrar_cookie.address).await;
+
+    assert_eq!(registrar.collection_configs.len(), 1);
+    assert_eq!(registrar.collection_configs[0].collection, nft_collection_cookie2.mint);
+
+    let max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(max_voter_weight_record.max_voter_weight, 15);
+    assert_eq!(max_voter_weight_record.max_voter_weight_expiry, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_expired_collections_with_no_expired_collections_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 100;
+
+    nft_voter_test.with_collection_expiry(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        expires_at
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .prune_expired_collections(&registrar_cookie, &max_voter_weight_record_cookie).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::NoExpiredCollections);
+
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use gpl_nft_voter::error::NftVoterError;
+use gpl_nft_voter::state::{ RegistrarConfig, VoterWeightAction };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_reclaim_nft_vote_records() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 1,
+        })
+    ).await?;
+
+    let treasury_cookie = nft_voter_test.bench.with_wallet().await;
+    let crank_cookie = nft_voter_test.bench.with_wallet().await;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        rent_reclaim_treasury: treasury_cookie.address,
+        rent_reclaim_treasury_share_bps: 7_500,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    // The vote is decisive and the Proposal leaves Voting state
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    let nft_vote_record_lamports = nft_voter_test.bench
+        .get_account(&nft_vote_record_cookies[0].address).await
+        .unwrap().lamports;
+
+    // Act
+    nft_voter_test.reclaim_nft_vote_records(
+        &registrar_cookie,
+        &proposal_cookie,
+        &nft_vote_record_cookies,
+        &crank_cookie.address
+    ).await?;
+
+    // Assert
+    let nft_vote_record = nft_voter_test.bench.get_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(None, nft_vote_record);
+
+    let treasury_share = (nft_vote_record_lamports * 7_500) / 10_000;
+
+    let treasury = nft_voter_test.bench.get_account(&treasury_cookie.address).await.unwrap();
+    assert_eq!(treasury.lamports, treasury_cookie.account.lamports + treasury_share);
+
+    let crank = nft_voter_test.bench.get_account(&crank_cookie.address).await.unwrap();
+    assert_eq!(
+        crank.lamports,
+        crank_cookie.account.lamports + nft_vote_record_lamports - treasury_share
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reclaim_nft_vote_records_with_proposal_in_voting_state_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 10,
+        })
+    ).await?;
+
+    let treasury_cookie = nft_voter_test.bench.with_wallet().await;
+    let crank_cookie = nft_voter_test.bench.with_wallet().await;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        rent_reclaim_treasury: treasury_cookie.address,
+        rent_reclaim_treasury_share_bps: 7_500,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .reclaim_nft_vote_records(
+            &registrar_cookie,
+            &proposal_cookie,
+            &nft_vote_record_cookies,
+            &crank_cookie.address
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::ProposalVotingNotFinished);
+
+    Ok(())
+}
@@ -1,5 +1,10 @@
 use gpl_nft_voter::error::NftVoterError;
-use gpl_nft_voter::state::VoterWeightAction;
+use gpl_nft_voter::state::{
+    get_collection_vote_weight_record_address,
+    NftVoteKind,
+    RegistrarConfig,
+    VoterWeightAction,
+};
 use program_test::nft_voter_test::*;
 use solana_program_test::*;
 use solana_sdk::transport::TransportError;
@@ -387,6 +392,111 @@ async fn test_relinquish_nft_vote_for_proposal_in_voting_state_with_nft() -> Res
     Ok(())
 }
 
+#[tokio::test]
+async fn test_relinquish_nft_vote_with_capped_collection() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    // 15% of max_voter_weight 33 caps the collection at 4
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        max_collection_weight_per_proposal: 1500,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie1, &nft_cookie2],
+        &action
+    ).await?;
+
+    let nft_vote_record_cookies: Vec<NftVoteRecordCookie> = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(CastNftVoteArgs {
+            capped_collection: Some(nft_collection_cookie.mint),
+            ..Default::default()
+        })
+    ).await?;
+
+    nft_voter_test.governance.relinquish_vote(
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie
+    ).await?;
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    nft_voter_test.relinquish_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_vote_record_cookies
+    ).await?;
+
+    // Assert
+
+    // The relinquished NFTs no longer consume the cap of the collection on the Proposal
+    let collection_vote_weight_record = nft_voter_test.get_collection_vote_weight_record(
+        &get_collection_vote_weight_record_address(
+            &registrar_cookie.address,
+            &proposal_cookie.address,
+            &nft_collection_cookie.mint
+        )
+    ).await;
+
+    assert_eq!(collection_vote_weight_record.voter_weight, 0);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_relinquish_nft_vote_for_proposal_in_voting_state_with_cnft() -> Result<
     (),
@@ -813,6 +923,7 @@ async fn test_relinquish_nft_vote_unexpired_vote_weight_record() -> Result<(), T
 
     let args = CastNftVoteArgs {
         cast_spl_gov_vote: false,
+        ..Default::default()
     };
 
     let nft_vote_record_cookies: Vec<NftVoteRecordCookie> = nft_voter_test.cast_nft_vote(
@@ -1060,3 +1171,178 @@ async fn test_relinquish_nft_vote_using_delegate() -> Result<(), TransportError>
     assert_eq!(None, cnft_vote_record);
     Ok(())
 }
+
+// relinquish Deny vote after the proposal has been defeated
+#[tokio::test]
+async fn test_relinquish_nft_vote_with_deny_vote_kind() -> Result<(), TransportError> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 1,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        vote_kind: NftVoteKind::Deny,
+        ..Default::default()
+    };
+
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(nft_vote_record.vote_kind, NftVoteKind::Deny);
+
+    nft_voter_test.bench.advance_clock().await;
+
+    nft_voter_test.relinquish_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_vote_record_cookies
+    ).await?;
+
+    // Check NftVoteRecord was disposed
+    let nft_vote_record = nft_voter_test.bench.get_account(
+        &nft_vote_record_cookies[0].address
+    ).await;
+
+    assert_eq!(None, nft_vote_record);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_relinquish_nft_vote_with_vote_kind_mismatch_error() -> Result<(), TransportError> {
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 1,
+        })
+    ).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    let args = CastNftVoteArgs {
+        vote_kind: NftVoteKind::Deny,
+        ..Default::default()
+    };
+
+    // Record Approve in NftVoteRecord while spl-gov Deny vote is cast
+    let (nft_vote_record_cookies, _) = nft_voter_test.cast_nft_vote_using_ix(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args),
+        |i| {
+            *i.data.last_mut().unwrap() = NftVoteKind::Approve as u8;
+        }
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .relinquish_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_vote_record_cookies
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::NftVoteKindMismatch);
+    Ok(())
+}
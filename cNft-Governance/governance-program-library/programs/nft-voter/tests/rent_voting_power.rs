@@ -0,0 +1,908 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{ instruction::Instruction, transport::TransportError };
+use spl_account_compression::AccountCompressionError;
+use crate::program_test::tools::{ assert_compression_err, assert_nft_voter_err };
+mod program_test;
+
+/// Returns instruction override supplying the WeightLease in place of the ColdWalletLink of the asset owner
+/// The renter uses the asset with the WeightLease and doesn't supply the WeightLease of the owner
+fn with_weight_lease(cold_wallet_link: Pubkey, weight_lease: Pubkey) -> impl Fn(&mut Instruction) {
+    move |i| {
+        let position = i.accounts
+            .iter()
+            .position(|a| a.pubkey == cold_wallet_link)
+            .unwrap();
+
+        i.accounts[position].pubkey = weight_lease;
+        i.accounts.remove(position + 1);
+    }
+}
+
+#[tokio::test]
+async fn test_rent_voting_power() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &renter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let rented_at_slot = nft_voter_test.bench.get_clock().await.slot;
+    let expires_at_slot = rented_at_slot + 1000;
+
+    let weight_lease = nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    // The renter can use the asset once the tickets the owner could have created before the lease expire
+    nft_voter_test.bench.advance_clock_by_slots(NFT_ACTION_TICKET_VALIDITY_SLOTS + 1).await;
+
+    // Act
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_ix(
+        &registrar_cookie,
+        &renter_weight_record_cookie,
+        &renter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        with_weight_lease(
+            get_cold_wallet_link_address(&registrar_cookie.address, &owner_cookie.address),
+            weight_lease
+        ),
+        None
+    ).await?;
+
+    // Assert
+    let weight_lease_account = nft_voter_test.get_weight_lease(&weight_lease).await;
+
+    assert_eq!(weight_lease_account, WeightLease {
+        registrar: registrar_cookie.address,
+        asset_id: leaf_cookie.asset_id,
+        owner: owner_cookie.address,
+        renter: renter_cookie.address,
+        starts_at_slot: rented_at_slot + NFT_ACTION_TICKET_VALIDITY_SLOTS + 1,
+        expires_at_slot,
+    });
+
+    let cnft_action_ticket = nft_voter_test.get_nft_action_ticket(
+        &cnft_action_ticket_cookies[0].address
+    ).await;
+
+    assert_eq!(cnft_action_ticket.governing_token_owner, renter_cookie.address);
+    assert_eq!(cnft_action_ticket.weight, 3);
+    assert!(cnft_action_ticket.expiry.unwrap() < expires_at_slot);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rent_voting_power_with_weight_leases_not_allowed_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    // Act
+    let err = nft_voter_test
+        .rent_voting_power(
+            &registrar_cookie,
+            &owner_cookie,
+            &leaf_cookie,
+            &leaf_verification_cookie,
+            &proofs,
+            &renter_cookie.address,
+            expires_at_slot
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightLeasesNotAllowed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rent_voting_power_with_asset_of_other_owner_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    // Act
+    let err = nft_voter_test
+        .rent_voting_power(
+            &registrar_cookie,
+            &renter_cookie,
+            &leaf_cookie,
+            &leaf_verification_cookie,
+            &proofs,
+            &Pubkey::new_unique(),
+            expires_at_slot
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterDoesNotOwnNft);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rent_voting_power_with_invalid_proof_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (mut leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    // The renter claims the asset it doesn't hold
+    leaf_verification_cookie.leaf_owner = renter_cookie.address;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    // Act
+    let err = nft_voter_test
+        .rent_voting_power(
+            &registrar_cookie,
+            &renter_cookie,
+            &leaf_cookie,
+            &leaf_verification_cookie,
+            &proofs,
+            &Pubkey::new_unique(),
+            expires_at_slot
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_compression_err(err, AccountCompressionError::ConcurrentMerkleTreeError);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_by_owner_with_active_weight_lease_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+    let owner_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &owner_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket(
+            &registrar_cookie,
+            &owner_weight_record_cookie,
+            &owner_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightLeaseActive);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_before_weight_lease_start_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &renter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    let weight_lease = nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    // The tickets the owner created before the lease are still valid
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket_using_ix(
+            &registrar_cookie,
+            &renter_weight_record_cookie,
+            &renter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action,
+            with_weight_lease(
+                get_cold_wallet_link_address(&registrar_cookie.address, &owner_cookie.address),
+                weight_lease
+            ),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightLeaseNotStarted);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_cnft_action_ticket_with_expired_weight_lease_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &renter_cookie
+    ).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot =
+        nft_voter_test.bench.get_clock().await.slot + NFT_ACTION_TICKET_VALIDITY_SLOTS + 2;
+
+    let weight_lease = nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock_by_slots(NFT_ACTION_TICKET_VALIDITY_SLOTS + 2).await;
+
+    // Act
+    let err = nft_voter_test
+        .with_create_cnft_action_ticket_using_ix(
+            &registrar_cookie,
+            &renter_weight_record_cookie,
+            &renter_cookie,
+            &[&leaf_cookie],
+            &[&leaf_verification_cookie],
+            &[&proofs],
+            &action,
+            with_weight_lease(
+                get_cold_wallet_link_address(&registrar_cookie.address, &owner_cookie.address),
+                weight_lease
+            ),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightLeaseExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_ticket_of_expired_weight_lease_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &renter_cookie
+    ).await?;
+    let renter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &renter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot =
+        nft_voter_test.bench.get_clock().await.slot + NFT_ACTION_TICKET_VALIDITY_SLOTS + 4;
+
+    let weight_lease = nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock_by_slots(NFT_ACTION_TICKET_VALIDITY_SLOTS + 1).await;
+
+    let cnft_action_ticket_cookies = nft_voter_test.with_create_cnft_action_ticket_using_ix(
+        &registrar_cookie,
+        &renter_weight_record_cookie,
+        &renter_cookie,
+        &[&leaf_cookie],
+        &[&leaf_verification_cookie],
+        &[&proofs],
+        &action,
+        with_weight_lease(
+            get_cold_wallet_link_address(&registrar_cookie.address, &owner_cookie.address),
+            weight_lease
+        ),
+        None
+    ).await?;
+
+    // The ticket outlives the lease and the weight reverts to the owner at vote time
+    nft_voter_test.bench.advance_clock_by_slots(4).await;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &renter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &renter_cookie,
+            &renter_token_owner_record_cookie,
+            &cnft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::NftTicketExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rent_voting_power_with_active_lease_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .rent_voting_power(
+            &registrar_cookie,
+            &owner_cookie,
+            &leaf_cookie,
+            &leaf_verification_cookie,
+            &proofs,
+            &Pubkey::new_unique(),
+            expires_at_slot + 1000
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightLeaseNotExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reclaim_voting_power_before_expiry_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    let weight_lease = nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .reclaim_voting_power(&registrar_cookie, &weight_lease, &owner_cookie.address, &owner_cookie).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightLeaseNotExpired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reclaim_voting_power_by_renter_before_expiry() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        allow_weight_leases: true,
+        ..Default::default()
+    }).await?;
+
+    let owner_cookie = nft_voter_test.bench.with_wallet().await;
+    let renter_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+    let leaf_cookie = nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+        &nft_collection_cookie,
+        &mut tree_cookie,
+        &owner_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let (leaf_verification_cookie, proofs, _) =
+        nft_voter_test.merkle_tree.get_leaf_verification_info(
+            &mut tree_cookie,
+            &leaf_cookie,
+            5,
+            8
+        ).await?;
+
+    let expires_at_slot = nft_voter_test.bench.get_clock().await.slot + 1000;
+
+    let weight_lease = nft_voter_test.rent_voting_power(
+        &registrar_cookie,
+        &owner_cookie,
+        &leaf_cookie,
+        &leaf_verification_cookie,
+        &proofs,
+        &renter_cookie.address,
+        expires_at_slot
+    ).await?;
+
+    let returned_at_slot = nft_voter_test.bench.get_clock().await.slot;
+
+    // Act
+    nft_voter_test.reclaim_voting_power(
+        &registrar_cookie,
+        &weight_lease,
+        &owner_cookie.address,
+        &renter_cookie
+    ).await?;
+
+    // Assert
+    // The lease ends once the tickets the renter could have created expire
+    let weight_lease_account = nft_voter_test.get_weight_lease(&weight_lease).await;
+
+    assert_eq!(
+        weight_lease_account.expires_at_slot,
+        returned_at_slot + NFT_ACTION_TICKET_VALIDITY_SLOTS + 1
+    );
+
+    let weight_lease_lamports = nft_voter_test.bench
+        .get_account(&weight_lease).await
+        .unwrap().lamports;
+    let owner_lamports = nft_voter_test.bench
+        .get_account(&owner_cookie.address).await
+        .unwrap().lamports;
+
+    nft_voter_test.bench.advance_clock_by_slots(NFT_ACTION_TICKET_VALIDITY_SLOTS + 1).await;
+
+    nft_voter_test.reclaim_voting_power(
+        &registrar_cookie,
+        &weight_lease,
+        &owner_cookie.address,
+        &owner_cookie
+    ).await?;
+
+    assert_eq!(None, nft_voter_test.bench.get_account(&weight_lease).await);
+
+    let owner = nft_voter_test.bench.get_account(&owner_cookie.address).await.unwrap();
+    assert_eq!(owner.lamports, owner_lamports + weight_lease_lamports);
+
+    Ok(())
+}
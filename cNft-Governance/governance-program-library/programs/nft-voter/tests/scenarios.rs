@@ -0,0 +1,242 @@
+//! Data driven voting scenarios
+//!
+//! Every YAML file in fixtures/scenarios describes the collections configured on a Registrar, the voters with the
+//! compressed NFTs they hold and the expected voter weight or NftVoterError of each voter.
+//! The scenarios are executed against program_test with the same helpers as the other tests
+//! and let regression cases (ex. configurations observed on mainnet) be added without writing Rust.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use gpl_nft_voter::state::*;
+use program_test::merkle_tree_test::LeafArgs;
+use program_test::nft_voter_test::*;
+use program_test::tools::NopOverride;
+use serde_yaml::Value;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+fn get_scenario_paths() -> Vec<PathBuf> {
+    let scenarios_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/scenarios");
+
+    let mut paths = fs::read_dir(scenarios_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |extension| extension == "yaml"))
+        .collect::<Vec<_>>();
+
+    paths.sort();
+    paths
+}
+
+fn to_voter_weight_action(value: &Value) -> VoterWeightAction {
+    match value.as_str().unwrap_or("CreateProposal") {
+        "CreateProposal" => VoterWeightAction::CreateProposal,
+        "CreateGovernance" => VoterWeightAction::CreateGovernance,
+        "CommentProposal" => VoterWeightAction::CommentProposal,
+        "SignOffProposal" => VoterWeightAction::SignOffProposal,
+        action => panic!("Invalid action {}", action),
+    }
+}
+
+/// Runs the given scenario and returns the failures of its expectations
+async fn run_scenario(scenario: &Value) -> Result<Vec<String>, TransportError> {
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    let mut nft_collection_cookies = HashMap::new();
+
+    for collection in scenario["collections"].as_sequence().unwrap() {
+        let size = collection["size"].as_u64().unwrap();
+        let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(
+            Some(size)
+        ).await?;
+
+        // Collections which aren't configured can be listed to mint assets the Registrar doesn't know
+        if collection["configured"].as_bool().unwrap_or(true) {
+            nft_voter_test.with_collection(
+                &registrar_cookie,
+                &nft_collection_cookie,
+                &max_voter_weight_record_cookie,
+                Some(ConfigureCollectionArgs {
+                    weight: collection["weight"].as_u64().unwrap(),
+                    size: size as u32,
+                })
+            ).await?;
+        }
+
+        nft_collection_cookies.insert(
+            collection["name"].as_str().unwrap().to_string(),
+            nft_collection_cookie
+        );
+    }
+
+    let voters = scenario["voters"].as_sequence().unwrap();
+
+    let mut voter_cookies = vec![];
+    let mut voter_leaf_cookies: Vec<Vec<LeafArgs>> = vec![];
+
+    // All the assets are minted before any proof is taken because every mint changes the proofs of the tree
+    let mut tree_cookie = nft_voter_test.merkle_tree.with_merkle_tree(None).await?;
+
+    for voter in voters {
+        let voter_cookie = nft_voter_test.bench.with_wallet().await;
+        let mut leaf_cookies = vec![];
+
+        for asset in voter["assets"].as_sequence().unwrap() {
+            let nft_collection_cookie = &nft_collection_cookies[asset["collection"].as_str().unwrap()];
+
+            for _ in 0..asset["count"].as_u64().unwrap_or(1) {
+                let leaf_cookie = if asset["verified"].as_bool().unwrap_or(true) {
+                    nft_voter_test.token_metadata.with_compressed_nft_to_collection(
+                        nft_collection_cookie,
+                        &mut tree_cookie,
+                        &voter_cookie
+                    ).await?
+                } else {
+                    nft_voter_test.token_metadata.with_compressed_nft(
+                        nft_collection_cookie,
+                        &mut tree_cookie,
+                        &voter_cookie
+                    ).await?
+                };
+
+                leaf_cookies.push(leaf_cookie);
+            }
+        }
+
+        voter_cookies.push(voter_cookie);
+        voter_leaf_cookies.push(leaf_cookies);
+    }
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let mut failures = vec![];
+
+    for ((voter, voter_cookie), leaf_cookies) in voters
+        .iter()
+        .zip(voter_cookies.iter())
+        .zip(voter_leaf_cookies.iter()) {
+        let voter_name = voter["name"].as_str().unwrap();
+        let action = to_voter_weight_action(&voter["action"]);
+
+        let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+            &registrar_cookie,
+            voter_cookie
+        ).await?;
+
+        let mut leaf_verification_cookies = vec![];
+        let mut proofs = vec![];
+
+        for leaf_cookie in leaf_cookies {
+            let (leaf_verification_cookie, leaf_proofs, _) =
+                nft_voter_test.merkle_tree.get_leaf_verification_info(
+                    &mut tree_cookie,
+                    leaf_cookie,
+                    5,
+                    8
+                ).await?;
+
+            leaf_verification_cookies.push(leaf_verification_cookie);
+            proofs.push(leaf_proofs);
+        }
+
+        let (instructions, nft_action_ticket_cookies) = nft_voter_test.create_cnft_action_ticket_ixs(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            voter_cookie,
+            &leaf_cookies.iter().collect::<Vec<_>>(),
+            &leaf_verification_cookies.iter().collect::<Vec<_>>(),
+            &proofs.iter().collect::<Vec<_>>(),
+            &action,
+            vec![],
+            vec![],
+            NopOverride
+        );
+
+        let result = nft_voter_test.bench.process_transaction_with_logs(
+            &instructions,
+            Some(&[&voter_cookie.signer])
+        ).await;
+
+        match (result, voter["expected_error"].as_str()) {
+            (Ok(_), Some(expected_error)) => {
+                failures.push(format!("{}: expected {} but succeeded", voter_name, expected_error));
+            }
+            (Err(failure), Some(expected_error)) => {
+                // Anchor logs the name of the error as "Error Code: <name>."
+                let error_code = format!("Error Code: {}.", expected_error);
+
+                if !failure.logs.log_messages.iter().any(|log_message| log_message.contains(&error_code)) {
+                    failures.push(format!("{}: expected {} but got {:?}", voter_name, expected_error, failure));
+                }
+            }
+            (Err(failure), None) => {
+                failures.push(format!("{}: unexpected failure {:?}", voter_name, failure));
+            }
+            (Ok(_), None) => {
+                nft_voter_test.update_voter_weight_record(
+                    &registrar_cookie,
+                    &mut voter_weight_record_cookie,
+                    action,
+                    &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+                ).await?;
+
+                let voter_weight_record = nft_voter_test.get_voter_weight_record(
+                    &voter_weight_record_cookie.address
+                ).await;
+
+                let expected_weight = voter["expected_weight"].as_u64().unwrap();
+
+                if voter_weight_record.voter_weight != expected_weight {
+                    failures.push(
+                        format!(
+                            "{}: expected weight {} but got {}",
+                            voter_name,
+                            expected_weight,
+                            voter_weight_record.voter_weight
+                        )
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+#[tokio::test]
+async fn test_scenarios() -> Result<(), TransportError> {
+    let mut failures = vec![];
+
+    for path in get_scenario_paths() {
+        // Arrange
+        let scenario = serde_yaml::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        // Act
+        let scenario_failures = run_scenario(&scenario).await?;
+
+        // Assert
+        for failure in scenario_failures {
+            failures.push(
+                format!(
+                    "{} ({}): {}",
+                    path.display(),
+                    scenario["description"].as_str().unwrap_or_default(),
+                    failure
+                )
+            );
+        }
+    }
+
+    assert!(failures.is_empty(), "Scenario failures:\n{}", failures.join("\n"));
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use gpl_nft_voter::{ state::*, error::NftVoterError };
+use program_test::{ nft_voter_test::*, tools::assert_nft_voter_err };
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+
+mod program_test;
+
+#[tokio::test]
+async fn test_sync_parent_weight() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let parent_realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let parent_registrar_cookie = nft_voter_test.with_registrar(&parent_realm_cookie).await?;
+    let parent_max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &parent_registrar_cookie
+    ).await?;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 4,
+            size: 10,
+        })
+    ).await?;
+
+    // 0.5x weight in the parent
+    nft_voter_test.with_sub_registrar(&parent_registrar_cookie, &registrar_cookie, 5_000).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        parent_registrar: parent_registrar_cookie.address,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let mut voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let parent_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &parent_registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let clock = nft_voter_test.bench.get_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.update_voter_weight_record(
+        &registrar_cookie,
+        &mut voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>()
+    ).await?;
+
+    let parent_governance = Pubkey::new_unique();
+
+    // Act
+    nft_voter_test.sync_parent_weight(
+        &registrar_cookie,
+        &parent_registrar_cookie,
+        &voter_weight_record_cookie,
+        &parent_voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &parent_max_voter_weight_record_cookie,
+        action,
+        Some(parent_governance)
+    ).await?;
+
+    // Assert
+    let parent_voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &parent_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(parent_voter_weight_record.voter_weight, 2);
+    assert_eq!(parent_voter_weight_record.voter_weight_expiry, Some(clock.slot));
+    assert_eq!(parent_voter_weight_record.weight_action, Some(VoterWeightAction::CreateProposal));
+    assert_eq!(parent_voter_weight_record.weight_action_target, Some(parent_governance));
+
+    let parent_max_voter_weight_record = nft_voter_test.get_max_voter_weight_record(
+        &parent_max_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(parent_max_voter_weight_record.max_voter_weight, 20);
+
+    let parent_registrar_extension = nft_voter_test.get_registrar_extension_account(
+        &parent_registrar_cookie.extension_address
+    ).await;
+
+    assert_eq!(parent_registrar_extension.sub_registrars_max_voter_weight, 20);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_parent_weight_without_parent_registrar_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let parent_realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let parent_registrar_cookie = nft_voter_test.with_registrar(&parent_realm_cookie).await?;
+    let parent_max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &parent_registrar_cookie
+    ).await?;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    // The parent approves the sub-DAO but the sub-DAO Registrar doesn't point to the parent
+    nft_voter_test.with_sub_registrar(&parent_registrar_cookie, &registrar_cookie, 5_000).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let parent_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &parent_registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .sync_parent_weight(
+            &registrar_cookie,
+            &parent_registrar_cookie,
+            &voter_weight_record_cookie,
+            &parent_voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &parent_max_voter_weight_record_cookie,
+            VoterWeightAction::CreateProposal,
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidParentRegistrar);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_parent_weight_with_cast_vote_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+
+    let parent_realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let parent_registrar_cookie = nft_voter_test.with_registrar(&parent_realm_cookie).await?;
+    let parent_max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &parent_registrar_cookie
+    ).await?;
+
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    nft_voter_test.with_sub_registrar(&parent_registrar_cookie, &registrar_cookie, 5_000).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        parent_registrar: parent_registrar_cookie.address,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let parent_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &parent_registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .sync_parent_weight(
+            &registrar_cookie,
+            &parent_registrar_cookie,
+            &voter_weight_record_cookie,
+            &parent_voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &parent_max_voter_weight_record_cookie,
+            VoterWeightAction::CastVote,
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::CastVoteIsNotAllowed);
+
+    Ok(())
+}
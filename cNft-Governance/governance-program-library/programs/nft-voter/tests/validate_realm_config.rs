@@ -0,0 +1,93 @@
+use gpl_nft_voter::error::NftVoterError;
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_validate_realm_config_with_both_addins() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm_using_addins(
+        Some(gpl_nft_voter::id()),
+        Some(gpl_nft_voter::id())
+    ).await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    nft_voter_test.validate_realm_config(&registrar_cookie, true, true).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_realm_config_with_voter_weight_addin_only() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm_using_addins(
+        Some(gpl_nft_voter::id()),
+        None
+    ).await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    nft_voter_test.validate_realm_config(&registrar_cookie, true, false).await?;
+
+    // Act
+    let err = nft_voter_test
+        .validate_realm_config(&registrar_cookie, false, true).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::PluginNotMaxVoterWeightAddin);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_realm_config_with_max_voter_weight_addin_only() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm_using_addins(
+        None,
+        Some(gpl_nft_voter::id())
+    ).await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    nft_voter_test.validate_realm_config(&registrar_cookie, false, true).await?;
+
+    // Act
+    let err = nft_voter_test
+        .validate_realm_config(&registrar_cookie, true, false).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::PluginNotVoterWeightAddin);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_realm_config_with_other_addin_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let other_addin = nft_voter_test.governance.program_id;
+    let realm_cookie = nft_voter_test.governance.with_realm_using_addins(
+        Some(other_addin),
+        None
+    ).await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .validate_realm_config(&registrar_cookie, true, false).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::PluginNotVoterWeightAddin);
+
+    Ok(())
+}
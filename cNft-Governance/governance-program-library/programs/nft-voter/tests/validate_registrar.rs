@@ -0,0 +1,94 @@
+use gpl_nft_voter::state::*;
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+mod program_test;
+
+#[tokio::test]
+async fn test_validate_registrar() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    // Act
+    let warnings = nft_voter_test.validate_registrar(&registrar_cookie).await?;
+
+    // Assert
+    assert!(warnings.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_registrar_with_zero_weight_and_expired_collections() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+
+    let nft_collection_cookie1 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie1,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 0,
+            size: 10,
+        })
+    ).await?;
+
+    let nft_collection_cookie2 = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie2,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 10,
+        })
+    ).await?;
+
+    let expires_at = nft_voter_test.bench.get_clock().await.unix_timestamp + 100;
+
+    nft_voter_test.with_collection_expiry(
+        &registrar_cookie,
+        &nft_collection_cookie2,
+        expires_at
+    ).await?;
+
+    nft_voter_test.bench.advance_clock_by_seconds(100).await;
+
+    // Act
+    let warnings = nft_voter_test.validate_registrar(&registrar_cookie).await?;
+
+    // Assert
+    assert_eq!(
+        warnings.bits,
+        RegistrarWarnings::ZERO_WEIGHT_COLLECTION | RegistrarWarnings::EXPIRED_COLLECTION
+    );
+
+    Ok(())
+}
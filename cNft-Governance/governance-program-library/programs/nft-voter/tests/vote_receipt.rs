@@ -0,0 +1,358 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use spl_governance_tools::error::GovernanceToolsError;
+use crate::program_test::tools::{ assert_gov_tools_err, assert_ix_err, assert_nft_voter_err };
+mod program_test;
+
+#[tokio::test]
+async fn test_mint_vote_receipt() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 1,
+        })
+    ).await?;
+
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        vote_receipt_tree: ballot_tree_cookie.merkle_tree,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    // Act
+    let (vote_receipt, receipt) = nft_voter_test.mint_vote_receipt(
+        &registrar_cookie,
+        &mut ballot_tree_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie
+    ).await?;
+
+    // Assert
+    let vote_receipt_account = nft_voter_test.get_vote_receipt(&vote_receipt).await;
+
+    assert_eq!(vote_receipt_account, VoteReceipt {
+        registrar: registrar_cookie.address,
+        proposal: proposal_cookie.address,
+        governing_token_owner: voter_cookie.address,
+        vote_kind: NftVoteKind::Approve,
+        asset_id: receipt.asset_id,
+    });
+
+    assert_eq!(receipt.owner, voter_cookie.address);
+    assert_eq!(
+        receipt.metadata.uri,
+        format!("https://www.ballot-nfts.com/?proposal={}&vote=approve-0", proposal_cookie.address)
+    );
+
+    let ballot_tree = nft_voter_test.get_ballot_tree(&ballot_tree_cookie.address).await;
+    assert_eq!(ballot_tree.ballots_minted, 1);
+
+    // The receipt leaf encoding the vote is minted to the tree
+    let root = nft_voter_test.merkle_tree.decode_root(&ballot_tree_cookie.merkle_tree, 5, 8).await?;
+    assert_eq!(root, ballot_tree_cookie.proof_tree.get_root());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_vote_receipt_with_vote_receipts_not_enabled_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .mint_vote_receipt(
+            &registrar_cookie,
+            &mut ballot_tree_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoteReceiptsNotEnabled);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_vote_receipt_with_ballot_tree_not_configured_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let vote_receipt_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        vote_receipt_tree: vote_receipt_tree_cookie.merkle_tree,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .mint_vote_receipt(
+            &registrar_cookie,
+            &mut ballot_tree_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidBallotTree);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_vote_receipt_with_invalid_vote_record_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        vote_receipt_tree: ballot_tree_cookie.merkle_tree,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .mint_vote_receipt_using_ix(
+            &registrar_cookie,
+            &mut ballot_tree_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie,
+            |i| {
+                // VoteRecord
+                i.accounts[4].pubkey = Pubkey::new_unique();
+            }
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidVoteRecordAccount);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_vote_receipt_without_vote_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        vote_receipt_tree: ballot_tree_cookie.merkle_tree,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    // Act
+    let err = nft_voter_test
+        .mint_vote_receipt(
+            &registrar_cookie,
+            &mut ballot_tree_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    // The voter didn't vote and the VoteRecord doesn't exist
+    assert_gov_tools_err(err.into(), GovernanceToolsError::AccountDoesNotExist);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint_vote_receipt_with_receipt_already_minted_error() -> Result<(), TransportError> {
+    // Arrange
+    let action = VoterWeightAction::CastVote;
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 1,
+            size: 1,
+        })
+    ).await?;
+
+    let mut ballot_tree_cookie = nft_voter_test.with_ballot_tree(&registrar_cookie).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        vote_receipt_tree: ballot_tree_cookie.merkle_tree,
+        ..Default::default()
+    }).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voter_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voter_cookie,
+        None
+    ).await?;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voter_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        None
+    ).await?;
+
+    nft_voter_test.mint_vote_receipt(
+        &registrar_cookie,
+        &mut ballot_tree_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        &voter_token_owner_record_cookie
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    // Act
+    let err = nft_voter_test
+        .mint_vote_receipt(
+            &registrar_cookie,
+            &mut ballot_tree_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            &voter_token_owner_record_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    // VoteReceipt of the voter already exists
+    assert_ix_err(err, InstructionError::Custom(0));
+
+    Ok(())
+}
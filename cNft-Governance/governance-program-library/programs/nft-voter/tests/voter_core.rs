@@ -0,0 +1,431 @@
+//! Compatibility tests of the cnft-voter-core crate used by web clients
+//!
+//! The crate mirrors the account layouts, PDAs and weight math of the program without depending on it
+//! and the tests ensure the mirrors don't drift apart
+
+use anchor_lang::AccountSerialize;
+use gpl_nft_voter::state::max_voter_weight_record::get_max_voter_weight_record_address;
+use gpl_nft_voter::state::*;
+use solana_program::pubkey::Pubkey;
+
+fn create_test_registrar() -> Registrar {
+    Registrar {
+        governance_program_id: Pubkey::new_unique(),
+        realm: Pubkey::new_unique(),
+        governing_token_mint: Pubkey::new_unique(),
+        collection_configs: vec![
+            CollectionConfig::new(Pubkey::new_unique(), 4, 10),
+            CollectionConfig::new(Pubkey::new_unique(), 3, 5)
+        ],
+        reserved: [0; 128],
+    }
+}
+
+fn create_test_registrar_extension(registrar: &Registrar) -> RegistrarExtension {
+    let mut boosted_collection_extension = CollectionExtension {
+        weight_strategy: CollectionWeightStrategy::Capped { max_assets: 2 },
+        ..CollectionExtension::new(registrar.collection_configs[0].collection)
+    };
+    boosted_collection_extension.creator_boosts[0] = CreatorBoost {
+        creator: Pubkey::new_unique(),
+        boost_bps: 5_000,
+    };
+
+    RegistrarExtension {
+        config: RegistrarConfig {
+            collection_weight_mode: CollectionWeightMode::Normalized,
+            normalized_collection_weight: 20,
+            max_collection_weight_per_proposal: 2_000,
+            max_assets_per_tx: 5,
+            record_voter_checkpoints: true,
+            min_weight_if_registered_before_slot: 3,
+            ..Default::default()
+        },
+        sub_registrars_max_voter_weight: 7,
+        legacy_nft_vote_records_cutoff: 5,
+        registered_voters: 2,
+        collection_extensions: vec![
+            boosted_collection_extension,
+            CollectionExtension {
+                weight_strategy: CollectionWeightStrategy::Quadratic,
+                expires_at: 1_000,
+                uses_limited: true,
+                ..CollectionExtension::new(registrar.collection_configs[1].collection)
+            }
+        ],
+        ..RegistrarExtension::new(Pubkey::new_unique())
+    }
+}
+
+fn serialize_account<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn test_decode_registrar() {
+    // Arrange
+    let registrar = create_test_registrar();
+    let registrar_extension = create_test_registrar_extension(&registrar);
+
+    // Space allocated for the collections which are not configured yet
+    let data = [serialize_account(&registrar), vec![0; 100]].concat();
+
+    // Act
+    let decoded = cnft_voter_core::decode_account::<cnft_voter_core::Registrar>(&data).unwrap();
+    let decoded_extension = cnft_voter_core
+        ::decode_account::<cnft_voter_core::RegistrarExtension>(
+            &serialize_account(&registrar_extension)
+        )
+        .unwrap();
+
+    // Assert
+    assert_eq!(decoded.realm, registrar.realm);
+    assert_eq!(decoded.governing_token_mint, registrar.governing_token_mint);
+    assert_eq!(decoded.collection_configs.len(), 2);
+    assert_eq!(decoded.collection_configs[1].weight, 3);
+    assert_eq!(
+        decoded_extension
+            .get_collection_extension(&registrar.collection_configs[0].collection)
+            .creator_boosts[0].boost_bps,
+        5_000
+    );
+    assert_eq!(
+        decoded_extension
+            .get_collection_extension(&registrar.collection_configs[1].collection)
+            .weight_strategy,
+        cnft_voter_core::CollectionWeightStrategy::Quadratic
+    );
+    assert!(
+        decoded_extension
+            .get_collection_extension(&registrar.collection_configs[1].collection)
+            .uses_limited
+    );
+    assert_eq!(decoded_extension.registrar, registrar_extension.registrar);
+    assert_eq!(decoded_extension.config.normalized_collection_weight, 20);
+    assert!(decoded_extension.config.record_voter_checkpoints);
+    assert_eq!(decoded_extension.sub_registrars_max_voter_weight, 7);
+    assert_eq!(decoded_extension.legacy_nft_vote_records_cutoff, 5);
+    assert_eq!(decoded_extension.registered_voters, 2);
+
+    assert_eq!(
+        decoded.get_max_voter_weight(&decoded_extension).unwrap(),
+        registrar.get_max_voter_weight(&registrar_extension).unwrap()
+    );
+}
+
+#[test]
+fn test_decode_voter_weight_record() {
+    // Arrange
+    let voter_weight_record = VoterWeightRecord {
+        realm: Pubkey::new_unique(),
+        governing_token_mint: Pubkey::new_unique(),
+        governing_token_owner: Pubkey::new_unique(),
+        voter_weight: 20,
+        voter_weight_expiry: Some(30),
+        weight_action: Some(VoterWeightAction::CreateProposal),
+        weight_action_target: Some(Pubkey::new_unique()),
+        weight_state: VoterWeightState::Finalized,
+        record_nonce: 3,
+        reserved: [0; 3],
+    };
+
+    let data = serialize_account(&voter_weight_record);
+
+    // Act
+    let decoded = cnft_voter_core
+        ::decode_account::<cnft_voter_core::VoterWeightRecord>(&data)
+        .unwrap();
+
+    // Assert
+    assert_eq!(decoded.governing_token_owner, voter_weight_record.governing_token_owner);
+    assert_eq!(decoded.voter_weight, 20);
+    assert_eq!(decoded.voter_weight_expiry, Some(30));
+    assert_eq!(decoded.weight_action, Some(cnft_voter_core::VoterWeightAction::CreateProposal));
+    assert_eq!(decoded.weight_action_target, voter_weight_record.weight_action_target);
+    assert_eq!(decoded.get_weight_state(31), cnft_voter_core::VoterWeightState::Consumed);
+    assert_eq!(decoded.record_nonce, 3);
+}
+
+#[test]
+fn test_decode_registrar_metrics() {
+    // Arrange
+    let registrar_metrics = RegistrarMetrics {
+        registrar: Pubkey::new_unique(),
+        shard: 3,
+        total_votes_cast: 12,
+        total_weight_granted: 36,
+        last_activity_slot: 500,
+        reserved: [0; 16],
+    };
+
+    let data = serialize_account(&registrar_metrics);
+
+    // Act
+    let decoded = cnft_voter_core
+        ::decode_account::<cnft_voter_core::RegistrarMetrics>(&data)
+        .unwrap();
+
+    // Assert
+    assert_eq!(decoded.registrar, registrar_metrics.registrar);
+    assert_eq!(decoded.shard, 3);
+    assert_eq!(decoded.total_votes_cast, 12);
+    assert_eq!(decoded.total_weight_granted, 36);
+    assert_eq!(decoded.last_activity_slot, 500);
+}
+
+#[test]
+fn test_decode_nft_action_ticket() {
+    // Arrange
+    let nft_action_ticket = NftActionTicket::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        3,
+        Pubkey::new_unique(),
+        Some(110),
+        0,
+        100
+    );
+
+    let data = borsh::BorshSerialize::try_to_vec(&nft_action_ticket).unwrap();
+
+    // Act
+    let decoded = cnft_voter_core
+        ::decode_account::<cnft_voter_core::NftActionTicket>(&data)
+        .unwrap();
+
+    // Assert
+    assert_eq!(decoded.nft_mint, nft_action_ticket.nft_mint);
+    assert_eq!(decoded.weight, 3);
+    assert_eq!(decoded.expiry, Some(110));
+    assert_eq!(decoded.created_at_slot, 100);
+}
+
+#[test]
+fn test_simulate_voter_weight() {
+    // Arrange
+    let registrar = create_test_registrar();
+    let registrar_extension = create_test_registrar_extension(&registrar);
+    let boosted_collection_config = registrar.collection_configs[0];
+    let quadratic_collection_config = registrar.collection_configs[1];
+    let creator = registrar_extension.collection_extensions[0].creator_boosts[0].creator;
+
+    let assets = [
+        (boosted_collection_config, vec![creator]),
+        (boosted_collection_config, vec![]),
+        (boosted_collection_config, vec![]),
+        (quadratic_collection_config, vec![]),
+        (quadratic_collection_config, vec![]),
+        (quadratic_collection_config, vec![]),
+        (quadratic_collection_config, vec![]),
+    ];
+
+    let max_collection_weight = registrar_extension.config
+        .get_max_collection_weight_per_proposal(
+            registrar.get_max_voter_weight(&registrar_extension).unwrap()
+        )
+        .unwrap();
+
+    let mut collection_weight_tally = CollectionWeightTally::default();
+    let mut collection_weights = vec![0u64; 2];
+    let mut expected_voter_weight = 0;
+
+    for (collection_config, verified_creators) in assets.iter() {
+        let nft_weight = registrar
+            .get_nft_weight(&registrar_extension, collection_config, verified_creators, 0)
+            .unwrap();
+        let nft_weight = collection_weight_tally.add_asset(
+            &registrar_extension,
+            &collection_config.collection,
+            nft_weight
+        );
+
+        let collection_idx = registrar.collection_configs
+            .iter()
+            .position(|cc| cc.collection == collection_config.collection)
+            .unwrap();
+
+        let nft_weight = nft_weight.min(
+            max_collection_weight.saturating_sub(collection_weights[collection_idx])
+        );
+        collection_weights[collection_idx] += nft_weight;
+        expected_voter_weight += nft_weight;
+    }
+
+    let decoded = cnft_voter_core
+        ::decode_account::<cnft_voter_core::Registrar>(&serialize_account(&registrar))
+        .unwrap();
+    let decoded_extension = cnft_voter_core
+        ::decode_account::<cnft_voter_core::RegistrarExtension>(
+            &serialize_account(&registrar_extension)
+        )
+        .unwrap();
+
+    let simulated_assets = assets
+        .iter()
+        .map(|(collection_config, verified_creators)| cnft_voter_core::SimulatedAsset {
+            collection: collection_config.collection,
+            verified_creators: verified_creators.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    // Act
+    let voter_weight = cnft_voter_core
+        ::simulate_voter_weight(&decoded, &decoded_extension, &simulated_assets, 0)
+        .unwrap();
+
+    // Assert
+    assert!(expected_voter_weight > 0);
+    assert_eq!(voter_weight, expected_voter_weight);
+}
+
+#[test]
+fn test_pda_addresses() {
+    // Arrange
+    let program_id = &cnft_voter_core::NFT_VOTER_PROGRAM_ID;
+    let realm = Pubkey::new_unique();
+    let governing_token_mint = Pubkey::new_unique();
+    let registrar = Pubkey::new_unique();
+    let governing_token_owner = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let proposal = Pubkey::new_unique();
+    let merkle_tree = Pubkey::new_unique();
+    let campaign = Pubkey::new_unique();
+
+    // Act + Assert
+    assert_eq!(*program_id, gpl_nft_voter::id());
+
+    assert_eq!(
+        cnft_voter_core::get_registrar_address(program_id, &realm, &governing_token_mint),
+        get_registrar_address(&realm, &governing_token_mint)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_registrar_extension_address(program_id, &registrar),
+        get_registrar_extension_address(&registrar)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_voter_weight_record_address(
+            program_id,
+            &realm,
+            &governing_token_mint,
+            &governing_token_owner
+        ),
+        get_voter_weight_record_address(&realm, &governing_token_mint, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_max_voter_weight_record_address(program_id, &realm, &governing_token_mint),
+        get_max_voter_weight_record_address(&realm, &governing_token_mint)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_cnft_vote_record_address(program_id, &registrar, &proposal, &nft_mint),
+        get_nft_vote_record_address(&registrar, &proposal, &nft_mint)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_voter_freeze_record_address(program_id, &registrar, &governing_token_owner),
+        get_voter_freeze_record_address(&registrar, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_voter_group_address(program_id, &registrar, &governing_token_owner),
+        get_voter_group_address(&registrar, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_voter_group_membership_address(program_id, &registrar, &governing_token_owner),
+        get_voter_group_membership_address(&registrar, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_cold_wallet_link_address(program_id, &registrar, &governing_token_owner),
+        get_cold_wallet_link_address(&registrar, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_voter_checkpoint_address(
+            program_id,
+            &registrar,
+            &proposal,
+            &governing_token_owner
+        ),
+        get_voter_checkpoint_address(&registrar, &proposal, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_ballot_tree_address(program_id, &registrar, &merkle_tree),
+        get_ballot_tree_address(&registrar, &merkle_tree)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_campaign_address(program_id, &registrar, &proposal),
+        get_campaign_address(&registrar, &proposal)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_campaign_claim_address(program_id, &campaign, &governing_token_owner),
+        get_campaign_claim_address(&campaign, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_collection_alias_address(program_id, &registrar, &nft_mint),
+        get_collection_alias_address(&registrar, &nft_mint)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_weight_lease_address(
+            program_id,
+            &registrar,
+            &nft_mint,
+            &governing_token_owner
+        ),
+        get_weight_lease_address(&registrar, &nft_mint, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_voter_registration_address(
+            program_id,
+            &registrar,
+            &governing_token_owner
+        ),
+        get_voter_registration_address(&registrar, &governing_token_owner)
+    );
+
+    assert_eq!(
+        cnft_voter_core::get_wallet_weight_override_address(
+            program_id,
+            &registrar,
+            &governing_token_owner
+        ),
+        get_wallet_weight_override_address(&registrar, &governing_token_owner)
+    );
+
+    for (action, core_action) in [
+        (VoterWeightAction::CastVote, cnft_voter_core::VoterWeightAction::CastVote),
+        (VoterWeightAction::CommentProposal, cnft_voter_core::VoterWeightAction::CommentProposal),
+        (VoterWeightAction::CreateGovernance, cnft_voter_core::VoterWeightAction::CreateGovernance),
+        (VoterWeightAction::CreateProposal, cnft_voter_core::VoterWeightAction::CreateProposal),
+        (VoterWeightAction::SignOffProposal, cnft_voter_core::VoterWeightAction::SignOffProposal),
+    ] {
+        assert_eq!(
+            cnft_voter_core::get_nft_action_ticket_address(
+                program_id,
+                core_action,
+                &registrar,
+                &governing_token_owner,
+                &nft_mint
+            ),
+            get_nft_action_ticket_address(
+                &format!("nft-{}-ticket", action),
+                &registrar,
+                &governing_token_owner,
+                &nft_mint
+            ).0
+        );
+    }
+}
@@ -0,0 +1,729 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::{ assert_anchor_err, assert_ix_err, assert_nft_voter_err };
+mod program_test;
+
+#[tokio::test]
+async fn test_join_voter_group() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    // Act
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    // Assert
+    let voter_group = nft_voter_test.get_voter_group(&voter_group_cookie.address).await;
+
+    assert_eq!(voter_group, VoterGroup {
+        registrar: registrar_cookie.address,
+        voting_wallet: voting_wallet_cookie.address,
+        members: vec![member_cookie.address],
+    });
+
+    let voter_group_membership = nft_voter_test.get_voter_group_membership(
+        &get_voter_group_membership_address(&registrar_cookie.address, &member_cookie.address)
+    ).await;
+
+    assert_eq!(voter_group_membership, VoterGroupMembership {
+        registrar: registrar_cookie.address,
+        voter_group: voter_group_cookie.address,
+        member: member_cookie.address,
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_join_voter_group_without_member_signature_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .join_voter_group_using_ix(
+            &mut voter_group_cookie,
+            &voting_wallet_cookie,
+            &member_cookie,
+            |i| {
+                i.accounts[2].is_signer = false;
+            }, // member
+            Some(&[&voting_wallet_cookie.signer])
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_anchor_err(err, anchor_lang::error::ErrorCode::AccountNotSigner);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_voter_group() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &member_cookie
+    ).await?;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voting_wallet_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &member_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let mut nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voting_wallet_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    nft_action_ticket_cookies.extend(
+        nft_voter_test.with_create_nft_action_ticket(
+            &registrar_cookie,
+            &member_voter_weight_record_cookie,
+            &member_cookie,
+            &[&nft_cookie2],
+            &action
+        ).await?
+    );
+
+    // Act
+    nft_voter_test.update_voter_weight_record_with_voter_group(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        action,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        &voter_group_cookie
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 6);
+
+    let member_voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &member_voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(member_voter_weight_record.voter_weight, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_ticket_of_member_who_left_voter_group_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &member_cookie
+    ).await?;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &member_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &member_voter_weight_record_cookie,
+        &member_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.leave_voter_group(
+        &mut voter_group_cookie,
+        &member_cookie.address,
+        &member_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .update_voter_weight_record_with_voter_group(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            action,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            &voter_group_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidNftTicket);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_voter_weight_record_with_frozen_voter_group_member_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &member_cookie
+    ).await?;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &member_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CreateProposal;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &member_voter_weight_record_cookie,
+        &member_cookie,
+        &[&nft_cookie],
+        &action
+    ).await?;
+
+    nft_voter_test.freeze_voter(&registrar_cookie, &member_cookie.address).await?;
+
+    // Act
+    let err = nft_voter_test
+        .update_voter_weight_record_with_voter_group(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            action,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            &voter_group_cookie
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterFrozen);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_join_voter_group_with_member_of_another_voter_group_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voting_wallet_cookie1 = nft_voter_test.bench.with_wallet().await;
+    let voting_wallet_cookie2 = nft_voter_test.bench.with_wallet().await;
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut voter_group_cookie1 = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie1
+    ).await?;
+    let mut voter_group_cookie2 = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie2
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie1,
+        &voting_wallet_cookie1,
+        &member_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .join_voter_group(&mut voter_group_cookie2, &voting_wallet_cookie2, &member_cookie).await
+        .err()
+        .unwrap();
+
+    // Assert
+    // The VoterGroupMembership of the member already exists
+    assert_ix_err(err, InstructionError::Custom(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_join_voter_group_with_voting_wallet_of_another_voter_group_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voting_wallet_cookie1 = nft_voter_test.bench.with_wallet().await;
+    let voting_wallet_cookie2 = nft_voter_test.bench.with_wallet().await;
+
+    let mut voter_group_cookie1 = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie1
+    ).await?;
+    nft_voter_test.with_voter_group(&registrar_cookie, &voting_wallet_cookie2).await?;
+
+    // Act
+    let err = nft_voter_test
+        .join_voter_group(
+            &mut voter_group_cookie1,
+            &voting_wallet_cookie1,
+            &voting_wallet_cookie2
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WalletAlreadyInVoterGroup);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_voter_group_with_member_of_another_voter_group_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .with_voter_group(&registrar_cookie, &member_cookie).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WalletAlreadyInVoterGroup);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_join_voter_group_after_leaving_another_voter_group() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voting_wallet_cookie1 = nft_voter_test.bench.with_wallet().await;
+    let voting_wallet_cookie2 = nft_voter_test.bench.with_wallet().await;
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+
+    let mut voter_group_cookie1 = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie1
+    ).await?;
+    let mut voter_group_cookie2 = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie2
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie1,
+        &voting_wallet_cookie1,
+        &member_cookie
+    ).await?;
+
+    nft_voter_test.leave_voter_group(
+        &mut voter_group_cookie1,
+        &member_cookie.address,
+        &voting_wallet_cookie1
+    ).await?;
+
+    // Act
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie2,
+        &voting_wallet_cookie2,
+        &member_cookie
+    ).await?;
+
+    // Assert
+    let voter_group_membership = nft_voter_test.get_voter_group_membership(
+        &get_voter_group_membership_address(&registrar_cookie.address, &member_cookie.address)
+    ).await;
+
+    assert_eq!(voter_group_membership.voter_group, voter_group_cookie2.address);
+
+    let voter_group = nft_voter_test.get_voter_group(&voter_group_cookie1.address).await;
+
+    assert!(voter_group.members.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_voter_group() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        Some(ConfigureCollectionArgs {
+            weight: 3,
+            size: 11,
+        })
+    ).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voting_wallet_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &member_cookie
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    let nft_cookie1 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &voting_wallet_cookie,
+        None
+    ).await?;
+
+    let nft_cookie2 = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &member_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+    let action = VoterWeightAction::CastVote;
+
+    let mut nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &voting_wallet_cookie,
+        &[&nft_cookie1],
+        &action
+    ).await?;
+
+    nft_action_ticket_cookies.extend(
+        nft_voter_test.with_create_nft_action_ticket(
+            &registrar_cookie,
+            &member_voter_weight_record_cookie,
+            &member_cookie,
+            &[&nft_cookie2],
+            &action
+        ).await?
+    );
+
+    let args = CastNftVoteArgs {
+        voter_group: Some(voter_group_cookie.clone()),
+        ..Default::default()
+    };
+
+    // Act
+    let nft_vote_record_cookies = nft_voter_test.cast_nft_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &max_voter_weight_record_cookie,
+        &proposal_cookie,
+        &voting_wallet_cookie,
+        &voter_token_owner_record_cookie,
+        &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+        Some(args)
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 6);
+
+    let nft_vote_record = nft_voter_test.get_nft_vote_record_account(
+        &nft_vote_record_cookies[1].address
+    ).await;
+
+    assert_eq!(nft_vote_record.governing_token_owner, voting_wallet_cookie.address);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_nft_vote_with_ticket_of_member_without_voter_group_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let max_voter_weight_record_cookie = nft_voter_test.with_max_voter_weight_record(
+        &registrar_cookie
+    ).await?;
+    let nft_collection_cookie = nft_voter_test.token_metadata.with_nft_collection(Some(10)).await?;
+
+    nft_voter_test.with_collection(
+        &registrar_cookie,
+        &nft_collection_cookie,
+        &max_voter_weight_record_cookie,
+        None
+    ).await?;
+
+    let voting_wallet_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_token_owner_record_cookie = nft_voter_test.governance.with_token_owner_record(
+        &realm_cookie,
+        &voting_wallet_cookie
+    ).await?;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    let member_cookie = nft_voter_test.bench.with_wallet().await;
+    let member_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &member_cookie
+    ).await?;
+
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let mut voter_group_cookie = nft_voter_test.with_voter_group(
+        &registrar_cookie,
+        &voting_wallet_cookie
+    ).await?;
+
+    nft_voter_test.join_voter_group(
+        &mut voter_group_cookie,
+        &voting_wallet_cookie,
+        &member_cookie
+    ).await?;
+
+    let nft_cookie = nft_voter_test.token_metadata.with_nft_v2(
+        &nft_collection_cookie,
+        &member_cookie,
+        None
+    ).await?;
+
+    nft_voter_test.bench.advance_clock().await;
+
+    let nft_action_ticket_cookies = nft_voter_test.with_create_nft_action_ticket(
+        &registrar_cookie,
+        &member_voter_weight_record_cookie,
+        &member_cookie,
+        &[&nft_cookie],
+        &VoterWeightAction::CastVote
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .cast_nft_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &max_voter_weight_record_cookie,
+            &proposal_cookie,
+            &voting_wallet_cookie,
+            &voter_token_owner_record_cookie,
+            &nft_action_ticket_cookies.iter().collect::<Vec<_>>(),
+            None
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidNftTicket);
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use gpl_nft_voter::tools::merkle_tree::get_weight_snapshot_leaf;
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program::keccak::hashv;
+use solana_program_test::*;
+use solana_sdk::{ pubkey::Pubkey, signature::Keypair, signer::Signer, transport::TransportError };
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+/// Returns the holdings root of the voter and another wallet and the proof of the voter
+fn get_holdings(voter: &Pubkey, voter_weight: u64) -> ([u8; 32], Vec<[u8; 32]>) {
+    let voter_leaf = get_weight_snapshot_leaf(voter, voter_weight);
+    let other_leaf = get_weight_snapshot_leaf(&Pubkey::new_unique(), 5);
+
+    let holdings_root = if voter_leaf <= other_leaf {
+        hashv(&[&voter_leaf, &other_leaf]).to_bytes()
+    } else {
+        hashv(&[&other_leaf, &voter_leaf]).to_bytes()
+    };
+
+    (holdings_root, vec![other_leaf])
+}
+
+#[tokio::test]
+async fn test_create_weight_snapshot() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let snapshot_slot = nft_voter_test.bench.get_clock().await.slot;
+
+    // Act
+    let weight_snapshot_cookie = nft_voter_test.with_weight_snapshot(
+        &registrar_cookie,
+        &proposal_cookie,
+        &registrar_cookie.realm_authority,
+        snapshot_slot,
+        [1; 32],
+        15
+    ).await?;
+
+    // Assert
+    let weight_snapshot = nft_voter_test.get_weight_snapshot(&weight_snapshot_cookie.address).await;
+
+    assert_eq!(weight_snapshot_cookie.account, weight_snapshot);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_weight_snapshot_with_holding_attestation_authority() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let oracle = Keypair::new();
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        holding_attestation_authority: oracle.pubkey(),
+        ..Default::default()
+    }).await?;
+
+    let snapshot_slot = nft_voter_test.bench.get_clock().await.slot;
+
+    // Act
+    let weight_snapshot_cookie = nft_voter_test.with_weight_snapshot(
+        &registrar_cookie,
+        &proposal_cookie,
+        &oracle,
+        snapshot_slot,
+        [1; 32],
+        15
+    ).await?;
+
+    // Assert
+    let weight_snapshot = nft_voter_test.get_weight_snapshot(&weight_snapshot_cookie.address).await;
+
+    assert_eq!(weight_snapshot.authority, oracle.pubkey());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_weight_snapshot_with_invalid_authority_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let snapshot_slot = nft_voter_test.bench.get_clock().await.slot;
+
+    // Act
+    let err = nft_voter_test
+        .with_weight_snapshot(
+            &registrar_cookie,
+            &proposal_cookie,
+            &Keypair::new(),
+            snapshot_slot,
+            [1; 32],
+            15
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidRealmAuthority);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_weight_snapshot_with_future_slot_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let snapshot_slot = nft_voter_test.bench.get_clock().await.slot + 100;
+
+    // Act
+    let err = nft_voter_test
+        .with_weight_snapshot(
+            &registrar_cookie,
+            &proposal_cookie,
+            &registrar_cookie.realm_authority,
+            snapshot_slot,
+            [1; 32],
+            15
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidWeightSnapshotSlot);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_snapshot_vote() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let (holdings_root, proof) = get_holdings(&voter_cookie.address, 10);
+
+    let snapshot_slot = nft_voter_test.bench.get_clock().await.slot;
+
+    let weight_snapshot_cookie = nft_voter_test.with_weight_snapshot(
+        &registrar_cookie,
+        &proposal_cookie,
+        &registrar_cookie.realm_authority,
+        snapshot_slot,
+        holdings_root,
+        15
+    ).await?;
+
+    // Act
+    nft_voter_test.cast_snapshot_vote(
+        &registrar_cookie,
+        &voter_weight_record_cookie,
+        &weight_snapshot_cookie,
+        &proposal_cookie,
+        &voter_cookie,
+        10,
+        proof
+    ).await?;
+
+    // Assert
+    let voter_weight_record = nft_voter_test.get_voter_weight_record(
+        &voter_weight_record_cookie.address
+    ).await;
+
+    assert_eq!(voter_weight_record.voter_weight, 10);
+    assert_eq!(voter_weight_record.weight_action, Some(VoterWeightAction::CastVote));
+    assert_eq!(voter_weight_record.weight_action_target, Some(proposal_cookie.address));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cast_snapshot_vote_with_invalid_voter_weight_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+    let proposal_cookie = nft_voter_test.governance.with_proposal(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+    let (holdings_root, proof) = get_holdings(&voter_cookie.address, 10);
+
+    let snapshot_slot = nft_voter_test.bench.get_clock().await.slot;
+
+    let weight_snapshot_cookie = nft_voter_test.with_weight_snapshot(
+        &registrar_cookie,
+        &proposal_cookie,
+        &registrar_cookie.realm_authority,
+        snapshot_slot,
+        holdings_root,
+        15
+    ).await?;
+
+    // Act
+    let err = nft_voter_test
+        .cast_snapshot_vote(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &weight_snapshot_cookie,
+            &proposal_cookie,
+            &voter_cookie,
+            11,
+            proof
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidWeightSnapshotProof);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use gpl_nft_voter::{ error::NftVoterError, state::* };
+use program_test::nft_voter_test::*;
+use solana_program_test::*;
+use solana_sdk::transport::TransportError;
+use crate::program_test::tools::assert_nft_voter_err;
+mod program_test;
+
+#[tokio::test]
+async fn test_write_external_voter_weight_with_export_not_allowed_error() -> Result<(), TransportError> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    // Act
+    // RegistrarExtension.config.weight_export_program is not set and it defaults to the system program
+    let err = nft_voter_test
+        .write_external_voter_weight(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_cookie.address,
+            &solana_sdk::system_program::id()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::WeightExportNotAllowed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_external_voter_weight_with_record_of_other_voter_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    let other_voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let other_voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &other_voter_cookie
+    ).await?;
+
+    // The program itself acts as the consumer plugin owning spl-governance-addin-api records
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        weight_export_program: gpl_nft_voter::id(),
+        ..Default::default()
+    }).await?;
+
+    // Act
+    let err = nft_voter_test
+        .write_external_voter_weight(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &other_voter_weight_record_cookie.address,
+            &gpl_nft_voter::id()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::InvalidExternalVoterWeightRecord);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_external_voter_weight_with_expired_voter_weight_record_error() -> Result<
+    (),
+    TransportError
+> {
+    // Arrange
+    let mut nft_voter_test = NftVoterTest::start_new().await;
+    let realm_cookie = nft_voter_test.governance.with_realm().await?;
+    let mut registrar_cookie = nft_voter_test.with_registrar(&realm_cookie).await?;
+
+    let voter_cookie = nft_voter_test.bench.with_wallet().await;
+    let voter_weight_record_cookie = nft_voter_test.with_voter_weight_record(
+        &registrar_cookie,
+        &voter_cookie
+    ).await?;
+
+    nft_voter_test.with_registrar_config(&mut registrar_cookie, RegistrarConfig {
+        weight_export_program: gpl_nft_voter::id(),
+        ..Default::default()
+    }).await?;
+
+    // Act
+    let err = nft_voter_test
+        .write_external_voter_weight(
+            &registrar_cookie,
+            &voter_weight_record_cookie,
+            &voter_weight_record_cookie.address,
+            &gpl_nft_voter::id()
+        ).await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_nft_voter_err(err, NftVoterError::VoterWeightRecordExpired);
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use mpl_bubblegum::state::TreeConfig as BubblegumTreeConfig;
+use mpl_bubblegum::accounts::TreeConfig as BubblegumTreeConfig;
 use spl_account_compression::{ self, Noop, program::SplAccountCompression };
 use spl_account_compression::state::{
     CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1,
@@ -35,7 +35,9 @@ pub struct CreateParallelTree<'info> {
     pub parallel_tree: UncheckedAccount<'info>,
 
     /// The authority for the main tree.
-    pub main_tree_authority: Account<'info, BubblegumTreeConfig>,
+    /// CHECK: Owned by Bubblegum and deserialized in the instruction
+    #[account(owner = mpl_bubblegum::ID @ ParallelTreeError::TreeAuthorityIncorrect)]
+    pub main_tree_authority: UncheckedAccount<'info>,
 
     /// CHECK: This account is checked in the instruction
     pub main_tree: UncheckedAccount<'info>,
@@ -60,7 +62,9 @@ pub fn create_parallel_tree<'a, 'b, 'c, 'info>(
     let parallel_tree = &mut ctx.accounts.parallel_tree.to_account_info();
     let parallel_tree_authority = &mut ctx.accounts.parallel_tree_authority;
     let main_tree = &ctx.accounts.main_tree.to_account_info();
-    let main_tree_authority = &ctx.accounts.main_tree_authority;
+    let main_tree_authority = BubblegumTreeConfig::from_bytes(
+        &ctx.accounts.main_tree_authority.try_borrow_data()?
+    ).map_err(|_| error!(ParallelTreeError::TreeAuthorityIncorrect))?;
     let tree_creator = &mut ctx.accounts.tree_creator.to_account_info();
     let public = public.unwrap_or(false);
 
@@ -94,7 +98,7 @@ pub fn create_parallel_tree<'a, 'b, 'c, 'info>(
     )?;
 
     let seed = parallel_tree.key();
-    let seeds = &[seed.as_ref(), &[*ctx.bumps.get("parallel_tree_authority").unwrap()]];
+    let seeds = &[seed.as_ref(), &[ctx.bumps.parallel_tree_authority]];
     let authority_pda_signer = &[&seeds[..]];
     parallel_tree_authority.set_inner(TreeConfig {
         tree_creator: ctx.accounts.tree_creator.key(),
@@ -86,7 +86,7 @@ pub fn modify_governance_metadata<'info>(
     );
 
     let seed = parallel_tree.key();
-    let seeds = &[seed.as_ref(), &[*ctx.bumps.get("parallel_tree_authority").unwrap()]];
+    let seeds = &[seed.as_ref(), &[ctx.bumps.parallel_tree_authority]];
     let authority_pda_signer = &[&seeds[..]];
 
     let cpi_ctx = CpiContext::new_with_signer(
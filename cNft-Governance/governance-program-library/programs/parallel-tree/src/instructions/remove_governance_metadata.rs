@@ -75,7 +75,7 @@ pub fn remove_governance_metadata<'info>(
     let new_leaf = Node::default();
 
     let seed = parallel_tree.key();
-    let seeds = &[seed.as_ref(), &[*ctx.bumps.get("parallel_tree_authority").unwrap()]];
+    let seeds = &[seed.as_ref(), &[ctx.bumps.parallel_tree_authority]];
     let authority_pda_signer = &[&seeds[..]];
 
     let cpi_ctx = CpiContext::new_with_signer(
@@ -6,10 +6,10 @@ use anchor_lang::error::Error;
 use anchor_lang::prelude::Pubkey;
 use borsh::BorshDeserialize;
 use bytemuck::try_from_bytes;
-use mpl_bubblegum::state::metaplex_adapter::MetadataArgs;
-use mpl_bubblegum::state::TreeConfig;
+use mpl_bubblegum::types::MetadataArgs;
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::instructions::CreateTreeConfigBuilder;
 use mpl_bubblegum::utils::get_asset_id;
-use solana_program::instruction::Instruction;
 use solana_program::{ msg, system_instruction, system_program };
 use solana_program_test::ProgramTest;
 use solana_sdk::{ signature::Keypair, signer::Signer, transport::TransportError };
@@ -170,29 +170,24 @@ impl MerkleTreeTest {
             &self.bench.payer
         ).await?;
 
-        let accounts = mpl_bubblegum::accounts::CreateTree {
-            tree_authority: tree_authority,
-            payer: self.bench.payer.pubkey(),
-            tree_creator: tree_creator.pubkey(),
-            log_wrapper: spl_noop::id(),
-            system_program: system_program::id(),
-            compression_program: spl_account_compression::id(),
-            merkle_tree: merkle_tree.pubkey(),
-        };
-
-        let data = anchor_lang::InstructionData::data(
-            &(mpl_bubblegum::instruction::CreateTree {
-                max_depth: args.max_depth,
-                max_buffer_size: args.max_buffer_size,
-                public: args.public,
-            })
-        );
+        let mut create_merkle_tree_builder = CreateTreeConfigBuilder::new();
+
+        create_merkle_tree_builder
+            .tree_config(tree_authority)
+            .merkle_tree(merkle_tree.pubkey())
+            .payer(self.bench.payer.pubkey())
+            .tree_creator(tree_creator.pubkey())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .system_program(system_program::id())
+            .max_depth(args.max_depth)
+            .max_buffer_size(args.max_buffer_size);
+
+        if let Some(public) = args.public {
+            create_merkle_tree_builder.public(public);
+        }
 
-        let create_merkle_tree_ix = Instruction {
-            program_id: self.program_id,
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
-            data,
-        };
+        let create_merkle_tree_ix = create_merkle_tree_builder.instruction();
 
         let signers = &[&wallet_cookie.signer];
 
@@ -222,8 +217,7 @@ impl MerkleTreeTest {
         &self,
         tree_cookie: &mut MerkleTreeCookie
     ) -> Result<TreeConfig, TransportError> {
-        let tree_authority = &tree_cookie.tree_authority;
-        let tree_config = self.bench.get_anchor_account::<TreeConfig>(*tree_authority).await;
+        let tree_config = self.get_tree_authority_account(&tree_cookie.tree_authority).await;
         Ok(tree_config)
     }
 
@@ -286,8 +280,9 @@ impl MerkleTreeTest {
     }
 
     #[allow(dead_code)]
-    pub async fn get_tree_authority_account(&mut self, tree_authority: &Pubkey) -> TreeConfig {
-        self.bench.get_anchor_account::<TreeConfig>(*tree_authority).await
+    pub async fn get_tree_authority_account(&self, tree_authority: &Pubkey) -> TreeConfig {
+        let data = self.bench.get_account_data(*tree_authority).await;
+        TreeConfig::from_bytes(&data).unwrap()
     }
 
     #[allow(dead_code)]
@@ -2,18 +2,23 @@ use std::{ fmt::Display, str::FromStr, sync::Arc };
 
 // use anchor_lang::prelude::Pubkey;
 use mpl_bubblegum;
-use mpl_bubblegum::state::leaf_schema::LeafSchema;
-use mpl_bubblegum::state::metaplex_adapter::{
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::types::{
     Collection as CNFT_Collection,
     Creator,
     MetadataArgs,
     TokenProgramVersion,
     TokenStandard,
 };
+use mpl_bubblegum::hash::{ hash_creators, hash_metadata };
+use mpl_bubblegum::instructions::{ MintToCollectionV1Builder, MintV1Builder };
 use mpl_bubblegum::utils::get_asset_id;
-use mpl_bubblegum::{ hash_creators, hash_metadata };
-use mpl_token_metadata::state::{ Collection, CollectionDetails };
-use solana_program::instruction::Instruction;
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3Builder,
+    CreateMetadataAccountV3Builder,
+    VerifyCollectionBuilder,
+};
+use mpl_token_metadata::types::{ Collection, CollectionDetails, DataV2 };
 use solana_program::pubkey::Pubkey;
 use solana_program::system_program;
 use solana_program_test::ProgramTest;
@@ -100,26 +105,26 @@ impl TokenMetadataTest {
         let coll_symbol = "NFT_C".to_string();
         let coll_uri = "URI".to_string();
 
-        let create_coll_metadata_ix = mpl_token_metadata::instruction::create_metadata_accounts_v3(
-            self.program_id,
-            coll_metadata_key,
-            coll_mint_cookie.address,
-            coll_mint_cookie.mint_authority.pubkey(),
-            payer.clone(),
-            update_authority.clone(),
-            coll_name,
-            coll_symbol,
-            coll_uri,
-            None,
-            10,
-            false,
-            false,
-            None,
-            None,
-            Some(CollectionDetails::V1 {
+        let create_coll_metadata_ix = CreateMetadataAccountV3Builder::new()
+            .metadata(coll_metadata_key)
+            .mint(coll_mint_cookie.address)
+            .mint_authority(coll_mint_cookie.mint_authority.pubkey())
+            .payer(payer)
+            .update_authority(update_authority, false)
+            .data(DataV2 {
+                name: coll_name,
+                symbol: coll_symbol,
+                uri: coll_uri,
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: None,
+                uses: None,
+            })
+            .is_mutable(false)
+            .collection_details(CollectionDetails::V1 {
                 size: collection_size,
             })
-        );
+            .instruction();
 
         self.bench.process_transaction(
             &[create_coll_metadata_ix],
@@ -137,16 +142,15 @@ impl TokenMetadataTest {
             &self.program_id
         );
 
-        let create_master_edition_ix = mpl_token_metadata::instruction::create_master_edition_v3(
-            self.program_id,
-            master_edition_key,
-            coll_mint_cookie.address,
-            update_authority,
-            coll_mint_cookie.mint_authority.pubkey(),
-            coll_metadata_key,
-            payer,
-            Some(0)
-        );
+        let create_master_edition_ix = CreateMasterEditionV3Builder::new()
+            .edition(master_edition_key)
+            .mint(coll_mint_cookie.address)
+            .update_authority(update_authority)
+            .mint_authority(coll_mint_cookie.mint_authority.pubkey())
+            .metadata(coll_metadata_key)
+            .payer(payer)
+            .max_supply(0)
+            .instruction();
 
         self.bench.process_transaction(
             &[create_master_edition_ix],
@@ -194,24 +198,23 @@ impl TokenMetadataTest {
             key: nft_collection_cookie.mint,
         };
 
-        let create_metadata_ix = mpl_token_metadata::instruction::create_metadata_accounts_v3(
-            self.program_id,
-            metadata_key,
-            mint_cookie.address,
-            mint_cookie.mint_authority.pubkey(),
-            self.bench.payer.pubkey(),
-            self.bench.payer.pubkey(),
-            name,
-            symbol,
-            uri,
-            None,
-            10,
-            false,
-            false,
-            Some(collection),
-            None,
-            None
-        );
+        let create_metadata_ix = CreateMetadataAccountV3Builder::new()
+            .metadata(metadata_key)
+            .mint(mint_cookie.address)
+            .mint_authority(mint_cookie.mint_authority.pubkey())
+            .payer(self.bench.payer.pubkey())
+            .update_authority(self.bench.payer.pubkey(), false)
+            .data(DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points: 10,
+                creators: None,
+                collection: Some(collection),
+                uses: None,
+            })
+            .is_mutable(false)
+            .instruction();
 
         self.bench.process_transaction(
             &[create_metadata_ix],
@@ -219,16 +222,14 @@ impl TokenMetadataTest {
         ).await?;
 
         if verify_collection {
-            let verify_collection = mpl_token_metadata::instruction::verify_collection(
-                self.program_id,
-                metadata_key,
-                self.bench.payer.pubkey(),
-                self.bench.payer.pubkey(),
-                nft_collection_cookie.mint,
-                nft_collection_cookie.metadata,
-                nft_collection_cookie.master_edition,
-                None
-            );
+            let verify_collection = VerifyCollectionBuilder::new()
+                .metadata(metadata_key)
+                .collection_authority(self.bench.payer.pubkey())
+                .payer(self.bench.payer.pubkey())
+                .collection_mint(nft_collection_cookie.mint)
+                .collection(nft_collection_cookie.metadata)
+                .collection_master_edition_account(nft_collection_cookie.master_edition)
+                .instruction();
 
             self.bench.process_transaction(&[verify_collection], None).await?;
         }
@@ -292,46 +293,35 @@ impl TokenMetadataTest {
         args.index = u32::try_from(tree_cookie.num_minted).unwrap();
         args.nonce = tree_cookie.num_minted;
 
-        let accounts = mpl_bubblegum::accounts::MintV1 {
-            tree_authority: tree_cookie.tree_authority,
-            tree_delegate: tree_cookie.tree_delegate.pubkey(),
-            payer: self.bench.payer.pubkey(),
-            log_wrapper: spl_noop::id(),
-            compression_program: spl_account_compression::id(),
-            leaf_owner: args.owner.pubkey(),
-            leaf_delegate: args.delegate.pubkey(),
-            merkle_tree: tree_cookie.address,
-            system_program: system_program::id(),
-        };
-
-        let data = anchor_lang::InstructionData::data(
-            &(mpl_bubblegum::instruction::MintV1 {
-                message: args.metadata.clone(),
-            })
-        );
-
-        let mint_cnft_ix = Instruction {
-            program_id: mpl_bubblegum::id(),
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
-            data,
-        };
+        let mint_cnft_ix = MintV1Builder::new()
+            .tree_config(tree_cookie.tree_authority)
+            .leaf_owner(args.owner.pubkey())
+            .leaf_delegate(args.delegate.pubkey())
+            .merkle_tree(tree_cookie.address)
+            .payer(self.bench.payer.pubkey())
+            .tree_creator_or_delegate(tree_cookie.tree_delegate.pubkey())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .system_program(system_program::id())
+            .metadata(args.metadata.clone())
+            .instruction();
 
         let owner = clone_keypair(&args.owner);
         let signers = &[&tree_cookie.tree_delegate, &owner];
         self.bench.process_transaction(&[mint_cnft_ix], Some(signers)).await?;
 
         let data_hash = hash_metadata(&args.metadata).unwrap();
-        let creator_hash = hash_creators(&args.metadata.creators.as_slice()).unwrap();
+        let creator_hash = hash_creators(&args.metadata.creators);
         let asset_id = get_asset_id(&tree_cookie.address, args.nonce);
 
-        let leaf_node = LeafSchema::new_v0(
-            asset_id,
-            args.owner.pubkey(),
-            args.delegate.pubkey(),
-            args.nonce,
+        let leaf_node = (LeafSchema::V1 {
+            id: asset_id,
+            owner: args.owner.pubkey(),
+            delegate: args.delegate.pubkey(),
+            nonce: args.nonce,
             data_hash,
-            creator_hash
-        ).to_node();
+            creator_hash,
+        }).hash();
 
         tree_cookie.num_minted += 1;
         tree_cookie.proof_tree.add_leaf(leaf_node, usize::try_from(args.index).unwrap());
@@ -358,36 +348,24 @@ impl TokenMetadataTest {
 
         // let collection_mint_authority = nft_collection_cookie.mint_authority.as_ref().unwrap();
         let collection_mint_authority = &self.bench.payer;
-        let accounts = mpl_bubblegum::accounts::MintToCollectionV1 {
-            tree_authority: tree_cookie.tree_authority,
-            tree_delegate: tree_cookie.tree_delegate.pubkey(),
-            payer: self.bench.payer.pubkey(),
-            log_wrapper: spl_noop::id(),
-            compression_program: spl_account_compression::id(),
-            leaf_owner: args.owner.pubkey(),
-            leaf_delegate: args.delegate.pubkey(),
-            merkle_tree: tree_cookie.address,
-            system_program: system_program::id(),
-            collection_mint: nft_collection_cookie.mint,
-            collection_authority: collection_mint_authority.pubkey(),
-            collection_authority_record_pda: mpl_bubblegum::id(),
-            collection_metadata: nft_collection_cookie.metadata,
-            edition_account: nft_collection_cookie.master_edition,
-            bubblegum_signer: self.get_bubblegum_signer_address(),
-            token_metadata_program: self.program_id,
-        };
-
-        let data = anchor_lang::InstructionData::data(
-            &(mpl_bubblegum::instruction::MintToCollectionV1 {
-                metadata_args: args.metadata.clone(),
-            })
-        );
-
-        let mint_cnft_ix = Instruction {
-            program_id: mpl_bubblegum::id(),
-            accounts: anchor_lang::ToAccountMetas::to_account_metas(&accounts, None),
-            data,
-        };
+        let mint_cnft_ix = MintToCollectionV1Builder::new()
+            .tree_config(tree_cookie.tree_authority)
+            .leaf_owner(args.owner.pubkey())
+            .leaf_delegate(args.delegate.pubkey())
+            .merkle_tree(tree_cookie.address)
+            .payer(self.bench.payer.pubkey())
+            .tree_creator_or_delegate(tree_cookie.tree_delegate.pubkey())
+            .collection_authority(collection_mint_authority.pubkey())
+            .collection_mint(nft_collection_cookie.mint)
+            .collection_metadata(nft_collection_cookie.metadata)
+            .collection_edition(nft_collection_cookie.master_edition)
+            .bubblegum_signer(self.get_bubblegum_signer_address())
+            .log_wrapper(spl_noop::id())
+            .compression_program(spl_account_compression::id())
+            .token_metadata_program(self.program_id)
+            .system_program(system_program::id())
+            .metadata(args.metadata.clone())
+            .instruction();
 
         let owner = clone_keypair(&args.owner);
         let signers = &[&tree_cookie.tree_delegate, &owner, &collection_mint_authority];
@@ -398,17 +376,17 @@ impl TokenMetadataTest {
         }
 
         let data_hash = hash_metadata(&args.metadata).unwrap();
-        let creator_hash = hash_creators(&args.metadata.creators.as_slice()).unwrap();
+        let creator_hash = hash_creators(&args.metadata.creators);
         let asset_id = get_asset_id(&tree_cookie.address, args.nonce);
 
-        let leaf_node = LeafSchema::new_v0(
-            asset_id,
-            args.owner.pubkey(),
-            args.delegate.pubkey(),
-            args.nonce,
+        let leaf_node = (LeafSchema::V1 {
+            id: asset_id,
+            owner: args.owner.pubkey(),
+            delegate: args.delegate.pubkey(),
+            nonce: args.nonce,
             data_hash,
-            creator_hash
-        ).to_node();
+            creator_hash,
+        }).hash();
 
         tree_cookie.num_minted += 1;
         tree_cookie.proof_tree.add_leaf(leaf_node, usize::try_from(args.index).unwrap());
@@ -417,6 +395,6 @@ impl TokenMetadataTest {
 
     #[allow(dead_code)]
     pub fn get_bubblegum_signer_address(&self) -> Pubkey {
-        Pubkey::find_program_address(&[b"collection_cpi".as_ref()], &mpl_bubblegum::id()).0
+        Pubkey::find_program_address(&[b"collection_cpi".as_ref()], &mpl_bubblegum::ID).0
     }
 }